@@ -0,0 +1,628 @@
+use std::ops::{Index, IndexMut};
+
+use crate::ast::{Block, ExprId, FnDef, Item, Node, NodeKind, Pat, PatKind, Span, Stmt, StmtId, StmtKind};
+
+// 표현식과 문장이 겹칠 수 있는 가장 깊은 단계입니다. 저장소의 도우미와 해제는 재귀하지 않지만,
+// 이름 해석부터 IR까지의 단계와 예쁜 출력, 덤프는 트리를 재귀로 내려갑니다.
+// 파서는 이보다 깊은 소스를 거부하고, 컴파일러는 이 깊이를 견디는 스택에서 돕니다.
+pub const MAX_DEPTH: usize = 5_000;
+
+// 크레이트의 표현식과 문장을 한곳에 모아 두는 저장소입니다.
+// 노드는 자식을 `Box`로 품지 않고 번호(`ExprId`, `StmtId`)로 가리키므로 파싱은 벡터 끝에 덧붙이기만 하고,
+// 아무리 깊은 식도 해제할 때 재귀하지 않습니다. 노드를 고칠 때는 번호 자리에 덮어쓰며,
+// 어디서도 가리키지 않게 된 노드는 저장소가 해제될 때까지 남아 있습니다.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AstArena {
+    exprs: Vec<Node>,           // ExprId 순서의 표현식
+    stmts: Vec<Stmt>,           // StmtId 순서의 문장
+}
+
+// 표현식이나 문장을 가리키는 번호입니다. 둘을 함께 훑는 순회 도우미가 씁니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AstId {
+    Expr(ExprId),
+    Stmt(StmtId),
+}
+
+// 꺼낸 문장 자리에 남는 번호입니다. 어떤 표현식도 가리키지 않습니다.
+const VACANT: ExprId = ExprId(u32::MAX);
+
+// 노드 안에서 자식을 가리키는 번호 자리입니다. 복사할 때 새 번호로 바꿔 끼웁니다.
+enum ChildMut<'a> {
+    Expr(&'a mut ExprId),
+    Stmt(&'a mut StmtId),
+}
+
+// 노드 하나의 자식 번호 자리를 차례로 넘겨주는 함수입니다 (`expr_children_mut`, `stmt_children_mut`).
+type ChildrenMut<K> = fn(&mut K, &mut dyn FnMut(ChildMut<'_>));
+
+impl AstArena {
+    pub fn alloc_expr(&mut self, node: Node) -> ExprId {
+        let id = ExprId(u32::try_from(self.exprs.len()).expect("too many expressions"));
+        self.exprs.push(node);
+        id
+    }
+
+    pub fn alloc_stmt(&mut self, stmt: Stmt) -> StmtId {
+        let id = StmtId(u32::try_from(self.stmts.len()).expect("too many statements"));
+        self.stmts.push(stmt);
+        id
+    }
+
+    // `id` 자리의 노드를 꺼내고 그 자리는 `break`로 채웁니다. 고친 노드는 `arena[id] = node`로 되돌려 놓습니다.
+    // 꺼낸 노드를 `&mut`로 다루는 동안에도 저장소의 다른 노드(자식)는 고칠 수 있습니다.
+    pub fn take_expr(&mut self, id: ExprId) -> Node {
+        let Node { id: node_id, span, .. } = self[id];
+        std::mem::replace(&mut self[id], Node { id: node_id, span, kind: NodeKind::Break })
+    }
+
+    // `take_expr`와 같이 문장을 꺼냅니다. 빈 자리는 아무것도 가리키지 않는 문장으로 채웁니다.
+    pub fn take_stmt(&mut self, id: StmtId) -> Stmt {
+        let Stmt { id: node_id, span, .. } = self[id];
+        std::mem::replace(&mut self[id], Stmt { id: node_id, span, kind: StmtKind::Expr(VACANT) })
+    }
+
+    // `id` 바로 아래의 표현식과 문장을 소스 순서대로 돌려줍니다.
+    // 문장 안의 아이템은 그 안 함수 본문의 문장까지 내려갑니다.
+    pub fn children(&self, id: AstId) -> Vec<AstId> {
+        let mut children = Vec::new();
+        match id {
+            AstId::Expr(id) => expr_children(&self[id].kind, &mut children),
+            AstId::Stmt(id) => stmt_children(&self[id].kind, &mut children),
+        }
+        children
+    }
+
+    // `root`와 그 아래 모든 표현식과 문장을 전위 순서로 훑습니다.
+    // 재귀 대신 명시적인 스택을 쓰므로 아무리 깊은 식에서도 스택이 넘치지 않습니다.
+    pub fn descendants(&self, root: AstId) -> Descendants<'_> {
+        Descendants { arena: self, stack: vec![root] }
+    }
+
+    // 블록 안 문장과 그 아래 모든 노드를 전위 순서로 훑습니다.
+    pub fn block_descendants(&self, block: &Block) -> Descendants<'_> {
+        Descendants { arena: self, stack: block.stmts.iter().rev().map(|&id| AstId::Stmt(id)).collect() }
+    }
+
+    // `items` 안의 표현식과 문장 가운데 가장 깊이 있는 노드와 그 깊이입니다. 함수 본문의 문장이 1단계입니다.
+    // 깊이가 같으면 소스에서 앞선 노드를 돌려줍니다.
+    pub fn deepest(&self, items: &[Item]) -> Option<(AstId, usize)> {
+        let mut roots = Vec::new();
+        items.iter().for_each(|item| item_children(item, &mut roots));
+        let mut stack: Vec<(AstId, usize)> = roots.into_iter().rev().map(|id| (id, 1)).collect();
+        let mut deepest: Option<(AstId, usize)> = None;
+        while let Some((id, depth)) = stack.pop() {
+            if deepest.is_none_or(|(_, max)| depth > max) {
+                deepest = Some((id, depth));
+            }
+            stack.extend(self.children(id).into_iter().rev().map(|child| (child, depth + 1)));
+        }
+        deepest
+    }
+
+    pub fn span(&self, id: AstId) -> Span {
+        match id {
+            AstId::Expr(id) => self[id].span,
+            AstId::Stmt(id) => self[id].span,
+        }
+    }
+
+    // `root` 아래 트리 전체를 새 번호로 복사합니다. 노드 번호(`NodeId`)와 위치는 원본 그대로입니다.
+    // 복사본을 고쳐도 원본은 바뀌지 않습니다.
+    pub fn copy_expr(&mut self, root: ExprId) -> ExprId {
+        let copy = self.alloc_expr(self[root].clone());
+        self.copy_children(AstId::Expr(copy));
+        copy
+    }
+
+    pub fn copy_block(&mut self, block: &Block) -> Block {
+        let stmts = block
+            .stmts
+            .iter()
+            .map(|&stmt| {
+                let copy = self.alloc_stmt(self[stmt].clone());
+                self.copy_children(AstId::Stmt(copy));
+                copy
+            })
+            .collect();
+        Block { stmts }
+    }
+
+    // 함수 정의를 본문까지 복사합니다. 제네릭 함수를 타입 인자마다 따로 고칠 때 씁니다.
+    pub fn copy_fn(&mut self, function: &FnDef) -> FnDef {
+        let body = function.body.as_ref().map(|body| self.copy_block(body));
+        FnDef { body, ..function.clone() }
+    }
+
+    // 방금 복사한 노드는 아직 원본의 자식을 가리킵니다. 자식을 하나씩 복사해 바꿔 끼우고 그 아래로 내려갑니다.
+    fn copy_children(&mut self, root: AstId) {
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            let mut copy_child = |arena: &mut AstArena, child: ChildMut<'_>| match child {
+                ChildMut::Expr(child) => {
+                    *child = arena.alloc_expr(arena[*child].clone());
+                    stack.push(AstId::Expr(*child));
+                },
+                ChildMut::Stmt(child) => {
+                    *child = arena.alloc_stmt(arena[*child].clone());
+                    stack.push(AstId::Stmt(*child));
+                },
+            };
+            match id {
+                AstId::Expr(id) => {
+                    let mut kind = std::mem::replace(&mut self[id].kind, NodeKind::Break);
+                    expr_children_mut(&mut kind, &mut |child| copy_child(self, child));
+                    self[id].kind = kind;
+                },
+                AstId::Stmt(id) => {
+                    let mut kind = std::mem::replace(&mut self[id].kind, StmtKind::Expr(VACANT));
+                    stmt_children_mut(&mut kind, &mut |child| copy_child(self, child));
+                    self[id].kind = kind;
+                },
+            }
+        }
+    }
+
+    // 두 표현식이 같은 모양의 트리인지 비교합니다. 번호와 위치는 비교하지 않으므로
+    // 다른 저장소에 있거나 따로 파싱한 트리도 같은 코드라면 같습니다.
+    pub fn expr_eq(&self, a: ExprId, other: &AstArena, b: ExprId) -> bool {
+        self.tree_eq(AstId::Expr(a), other, AstId::Expr(b))
+    }
+
+    pub fn block_eq(&self, a: &Block, other: &AstArena, b: &Block) -> bool {
+        a.stmts.len() == b.stmts.len()
+            && a.stmts.iter().zip(&b.stmts).all(|(&a, &b)| self.tree_eq(AstId::Stmt(a), other, AstId::Stmt(b)))
+    }
+
+    fn tree_eq(&self, a: AstId, other: &AstArena, b: AstId) -> bool {
+        let mut stack = vec![(a, b)];
+        while let Some((a, b)) = stack.pop() {
+            let (same, a_children, b_children) = match (a, b) {
+                (AstId::Expr(a), AstId::Expr(b)) => {
+                    let (mut a_kind, mut b_kind) = (self[a].kind.clone(), other[b].kind.clone());
+                    let (a_children, b_children) = (erase_ids(&mut a_kind, expr_children_mut), erase_ids(&mut b_kind, expr_children_mut));
+                    (a_kind == b_kind, a_children, b_children)
+                },
+                (AstId::Stmt(a), AstId::Stmt(b)) => {
+                    let (mut a_kind, mut b_kind) = (self[a].kind.clone(), other[b].kind.clone());
+                    let (a_children, b_children) = (erase_ids(&mut a_kind, stmt_children_mut), erase_ids(&mut b_kind, stmt_children_mut));
+                    (a_kind == b_kind, a_children, b_children)
+                },
+                _ => (false, Vec::new(), Vec::new()),
+            };
+            if !same {
+                return false;
+            }
+            stack.extend(a_children.into_iter().zip(b_children));
+        }
+        true
+    }
+}
+
+impl Index<ExprId> for AstArena {
+    type Output = Node;
+
+    fn index(&self, id: ExprId) -> &Node {
+        &self.exprs[id.0 as usize]
+    }
+}
+
+impl IndexMut<ExprId> for AstArena {
+    fn index_mut(&mut self, id: ExprId) -> &mut Node {
+        &mut self.exprs[id.0 as usize]
+    }
+}
+
+impl Index<StmtId> for AstArena {
+    type Output = Stmt;
+
+    fn index(&self, id: StmtId) -> &Stmt {
+        &self.stmts[id.0 as usize]
+    }
+}
+
+impl IndexMut<StmtId> for AstArena {
+    fn index_mut(&mut self, id: StmtId) -> &mut Stmt {
+        &mut self.stmts[id.0 as usize]
+    }
+}
+
+// `AstArena::descendants`가 돌려주는 반복자입니다.
+pub struct Descendants<'a> {
+    arena: &'a AstArena,
+    stack: Vec<AstId>,
+}
+
+impl Iterator for Descendants<'_> {
+    type Item = AstId;
+
+    fn next(&mut self) -> Option<AstId> {
+        let id = self.stack.pop()?;
+        let children = self.arena.children(id);
+        self.stack.extend(children.into_iter().rev());
+        Some(id)
+    }
+}
+
+// 자식 번호를 모두 0으로 바꾸고 원래 번호를 돌려줍니다. 남은 부분끼리 비교하면 노드 하나의 모양을 비교하는 셈입니다.
+fn erase_ids<K>(kind: &mut K, children_mut: ChildrenMut<K>) -> Vec<AstId> {
+    let mut children = Vec::new();
+    children_mut(kind, &mut |child| match child {
+        ChildMut::Expr(id) => children.push(AstId::Expr(std::mem::replace(id, ExprId(0)))),
+        ChildMut::Stmt(id) => children.push(AstId::Stmt(std::mem::replace(id, StmtId(0)))),
+    });
+    children
+}
+
+fn block_children(block: &Block, children: &mut Vec<AstId>) {
+    children.extend(block.stmts.iter().map(|&stmt| AstId::Stmt(stmt)));
+}
+
+// 표현식 바로 아래의 표현식과 문장을 소스 순서대로 모읍니다. `expr_children_mut`과 같은 순서입니다.
+fn expr_children(kind: &NodeKind, children: &mut Vec<AstId>) {
+    let mut expr = |id: &ExprId| children.push(AstId::Expr(*id));
+    match kind {
+        NodeKind::Int(..) | NodeKind::Float(..) | NodeKind::Char(_) | NodeKind::Ident(_) | NodeKind::Bool(_) | NodeKind::Str(_) => {},
+        NodeKind::Path(_) | NodeKind::QualifiedPath(..) | NodeKind::Break | NodeKind::Continue => {},
+        NodeKind::BinaryOp(binary_op) => {
+            expr(&binary_op.left);
+            expr(&binary_op.right);
+        },
+        NodeKind::Unary(unary_op) => expr(&unary_op.operand),
+        NodeKind::Ref { expr: inner, .. } => expr(inner),
+        NodeKind::Call(call) => {
+            expr(&call.callee);
+            call.args.iter().for_each(expr);
+        },
+        NodeKind::MethodCall(call) => {
+            expr(&call.receiver);
+            call.args.iter().for_each(expr);
+        },
+        NodeKind::Field(base, _) => expr(base),
+        NodeKind::StructLit(lit) => lit.fields.iter().for_each(|(_, value)| expr(value)),
+        NodeKind::Assign(assign) => {
+            expr(&assign.target);
+            expr(&assign.value);
+        },
+        NodeKind::Block(block) | NodeKind::Loop(block) => block_children(block, children),
+        NodeKind::If(if_expr) => {
+            expr(&if_expr.cond);
+            block_children(&if_expr.then, children);
+            children.extend(if_expr.els.map(AstId::Expr));
+        },
+        NodeKind::Match(match_expr) => {
+            expr(&match_expr.scrutinee);
+            for arm in &match_expr.arms {
+                pat_children(&arm.pat, &mut expr);
+                arm.guard.iter().for_each(&mut expr);
+                expr(&arm.body);
+            }
+        },
+        NodeKind::While(while_expr) => {
+            expr(&while_expr.cond);
+            block_children(&while_expr.body, children);
+        },
+        NodeKind::ForLoop(for_loop) => {
+            expr(&for_loop.iter);
+            block_children(&for_loop.body, children);
+        },
+        NodeKind::Return(value) => value.iter().for_each(expr),
+        NodeKind::Closure(closure) => expr(&closure.body),
+        NodeKind::Array(elems) | NodeKind::Tuple(elems) => elems.iter().for_each(expr),
+        NodeKind::Repeat(base, other) | NodeKind::Index(base, other) => {
+            expr(base);
+            expr(other);
+        },
+        NodeKind::Range(range) => range.start.iter().chain(&range.end).for_each(expr),
+        NodeKind::FormatArgs(format) => format.args.iter().for_each(|arg| expr(&arg.expr)),
+    }
+}
+
+// 패턴 안의 리터럴과 범위 끝 표현식입니다.
+fn pat_children(pat: &Pat, expr: &mut impl FnMut(&ExprId)) {
+    pat.walk(&mut |pat| match &pat.kind {
+        PatKind::Lit(lit) => expr(lit),
+        PatKind::Range(start, end) => {
+            expr(start);
+            expr(end);
+        },
+        _ => {},
+    });
+}
+
+fn stmt_children(kind: &StmtKind, children: &mut Vec<AstId>) {
+    match kind {
+        StmtKind::Let(local) => children.extend(local.init.map(AstId::Expr)),
+        StmtKind::Item(item) => item_children(item, children),
+        StmtKind::Expr(expr) | StmtKind::Semi(expr) => children.push(AstId::Expr(*expr)),
+    }
+}
+
+// 아이템 안 함수 본문의 문장을 모읍니다.
+fn item_children(item: &Item, children: &mut Vec<AstId>) {
+    let functions: Vec<&FnDef> = match item {
+        Item::Fn(def) => vec![def],
+        Item::Impl(imp) => imp.items.iter().collect(),
+        Item::Trait(def) => def.items.iter().collect(),
+        Item::Mod(def) => {
+            def.items.iter().flatten().for_each(|item| item_children(item, children));
+            return;
+        },
+        Item::Const(def) | Item::Static(def) => {
+            children.push(AstId::Expr(def.value));
+            return;
+        },
+        Item::Struct(_) | Item::Use(_) => return,
+    };
+    for function in functions {
+        if let Some(body) = &function.body {
+            block_children(body, children);
+        }
+    }
+}
+
+fn block_children_mut(block: &mut Block, f: &mut dyn FnMut(ChildMut<'_>)) {
+    block.stmts.iter_mut().for_each(|stmt| f(ChildMut::Stmt(stmt)));
+}
+
+// 표현식 안에서 자식을 가리키는 번호 자리를 소스 순서대로 넘겨줍니다.
+fn expr_children_mut(kind: &mut NodeKind, f: &mut dyn FnMut(ChildMut<'_>)) {
+    let mut expr = |id: &mut ExprId| f(ChildMut::Expr(id));
+    match kind {
+        NodeKind::Int(..) | NodeKind::Float(..) | NodeKind::Char(_) | NodeKind::Ident(_) | NodeKind::Bool(_) | NodeKind::Str(_) => {},
+        NodeKind::Path(_) | NodeKind::QualifiedPath(..) | NodeKind::Break | NodeKind::Continue => {},
+        NodeKind::BinaryOp(binary_op) => {
+            expr(&mut binary_op.left);
+            expr(&mut binary_op.right);
+        },
+        NodeKind::Unary(unary_op) => expr(&mut unary_op.operand),
+        NodeKind::Ref { expr: inner, .. } => expr(inner),
+        NodeKind::Call(call) => {
+            expr(&mut call.callee);
+            call.args.iter_mut().for_each(expr);
+        },
+        NodeKind::MethodCall(call) => {
+            expr(&mut call.receiver);
+            call.args.iter_mut().for_each(expr);
+        },
+        NodeKind::Field(base, _) => expr(base),
+        NodeKind::StructLit(lit) => lit.fields.iter_mut().for_each(|(_, value)| expr(value)),
+        NodeKind::Assign(assign) => {
+            expr(&mut assign.target);
+            expr(&mut assign.value);
+        },
+        NodeKind::Block(block) | NodeKind::Loop(block) => block_children_mut(block, f),
+        NodeKind::If(if_expr) => {
+            expr(&mut if_expr.cond);
+            block_children_mut(&mut if_expr.then, f);
+            if let Some(els) = &mut if_expr.els {
+                f(ChildMut::Expr(els));
+            }
+        },
+        NodeKind::Match(match_expr) => {
+            expr(&mut match_expr.scrutinee);
+            for arm in &mut match_expr.arms {
+                pat_children_mut(&mut arm.pat, &mut expr);
+                arm.guard.iter_mut().for_each(&mut expr);
+                expr(&mut arm.body);
+            }
+        },
+        NodeKind::While(while_expr) => {
+            expr(&mut while_expr.cond);
+            block_children_mut(&mut while_expr.body, f);
+        },
+        NodeKind::ForLoop(for_loop) => {
+            expr(&mut for_loop.iter);
+            block_children_mut(&mut for_loop.body, f);
+        },
+        NodeKind::Return(value) => value.iter_mut().for_each(expr),
+        NodeKind::Closure(closure) => expr(&mut closure.body),
+        NodeKind::Array(elems) | NodeKind::Tuple(elems) => elems.iter_mut().for_each(expr),
+        NodeKind::Repeat(base, other) | NodeKind::Index(base, other) => {
+            expr(base);
+            expr(other);
+        },
+        NodeKind::Range(range) => range.start.iter_mut().chain(&mut range.end).for_each(expr),
+        NodeKind::FormatArgs(format) => format.args.iter_mut().for_each(|arg| expr(&mut arg.expr)),
+    }
+}
+
+fn pat_children_mut(pat: &mut Pat, expr: &mut impl FnMut(&mut ExprId)) {
+    match &mut pat.kind {
+        PatKind::Ident { .. } | PatKind::Wild => {},
+        PatKind::Lit(lit) => expr(lit),
+        PatKind::Range(start, end) => {
+            expr(start);
+            expr(end);
+        },
+        PatKind::Tuple(pats) | PatKind::Or(pats) => pats.iter_mut().for_each(|pat| pat_children_mut(pat, expr)),
+        PatKind::Struct { fields, .. } => fields.iter_mut().for_each(|(_, pat)| pat_children_mut(pat, expr)),
+    }
+}
+
+fn stmt_children_mut(kind: &mut StmtKind, f: &mut dyn FnMut(ChildMut<'_>)) {
+    match kind {
+        StmtKind::Let(local) => local.init.iter_mut().for_each(|init| f(ChildMut::Expr(init))),
+        StmtKind::Item(item) => item_children_mut(item, f),
+        StmtKind::Expr(expr) | StmtKind::Semi(expr) => f(ChildMut::Expr(expr)),
+    }
+}
+
+// 아이템 안 함수 본문의 문장을 넘겨줍니다.
+fn item_children_mut(item: &mut Item, f: &mut dyn FnMut(ChildMut<'_>)) {
+    let mut function = |function: &mut FnDef| {
+        if let Some(body) = &mut function.body {
+            block_children_mut(body, f);
+        }
+    };
+    match item {
+        Item::Fn(def) => function(def),
+        Item::Impl(imp) => imp.items.iter_mut().for_each(function),
+        Item::Trait(def) => def.items.iter_mut().for_each(function),
+        Item::Mod(def) => def.items.iter_mut().flatten().for_each(|item| item_children_mut(item, f)),
+        Item::Const(def) | Item::Static(def) => f(ChildMut::Expr(&mut def.value)),
+        Item::Struct(_) | Item::Use(_) => {},
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use self::serialize::Resolved;
+
+// 직렬화는 번호를 그대로 쓰지 않고, 번호 자리에 가리키는 노드를 펼쳐 써서 중첩된 트리를 내보냅니다.
+#[cfg(feature = "serde")]
+mod serialize {
+    use std::cell::Cell;
+    use std::ptr;
+
+    use serde::ser::{Error, Serialize, Serializer};
+
+    use super::AstArena;
+    use crate::ast::{ExprId, StmtId};
+
+    // serde는 직렬화하는 값에 문맥을 넘겨줄 길이 없으므로, 직렬화하는 동안 빌린 저장소를 스레드 지역 변수로 가리킵니다.
+    // 저장소가 없으면 null입니다.
+    thread_local! {
+        static SERIALIZING: Cell<*const AstArena> = const { Cell::new(ptr::null()) };
+    }
+
+    // 직렬화하면 `value` 안의 번호를 `arena`의 노드로 풀어 쓰는 값입니다 (`AstArena::resolved`).
+    pub struct Resolved<'a, T: ?Sized> {
+        arena: &'a AstArena,
+        value: &'a T,
+    }
+
+    impl AstArena {
+        // 이 저장소를 가리키는 번호가 든 값(노드, 블록, 아이템)을 직렬화할 때 씁니다.
+        pub fn resolved<'a, T: ?Sized>(&'a self, value: &'a T) -> Resolved<'a, T> {
+            Resolved { arena: self, value }
+        }
+    }
+
+    // 직렬화가 끝나면 오류나 패닉으로 끝났더라도 바깥 저장소를 다시 가리킵니다.
+    struct Restore(*const AstArena);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SERIALIZING.with(|arena| arena.set(self.0));
+        }
+    }
+
+    impl<T: ?Sized + Serialize> Serialize for Resolved<'_, T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let _restore = Restore(SERIALIZING.with(|arena| arena.replace(self.arena)));
+            self.value.serialize(serializer)
+        }
+    }
+
+    // 직렬화 중인 저장소를 `f`에 빌려줍니다.
+    fn with_arena<R>(f: impl FnOnce(Option<&AstArena>) -> R) -> R {
+        let arena = SERIALIZING.with(Cell::get);
+        // SAFETY: null이 아니면 아직 돌아가지 않은 `Resolved::serialize`가 빌린 저장소를 가리킵니다.
+        // 그 호출이 끝나기 전에 `Restore`가 포인터를 되돌리고, 여기서 만든 참조는 `f` 밖으로 나가지 않습니다.
+        f(unsafe { arena.as_ref() })
+    }
+
+    impl Serialize for ExprId {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            with_arena(|arena| match arena {
+                Some(arena) => arena[*self].serialize(serializer),
+                None => Err(S::Error::custom("expression id serialized without its arena; use `AstArena::resolved`")),
+            })
+        }
+    }
+
+    impl Serialize for StmtId {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            with_arena(|arena| match arena {
+                Some(arena) => arena[*self].serialize(serializer),
+                None => Err(S::Error::custom("statement id serialized without its arena; use `AstArena::resolved`")),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::BinaryOp;
+    use rusttc_lexer::{Token, TokenKind};
+
+    fn number(arena: &mut AstArena, n: u128) -> ExprId {
+        arena.alloc_expr(Node::from(NodeKind::Int(n, None)))
+    }
+
+    fn add(arena: &mut AstArena, left: ExprId, right: ExprId) -> ExprId {
+        let operator = Token::new(TokenKind::Plus, "+".to_string());
+        arena.alloc_expr(Node::from(NodeKind::BinaryOp(BinaryOp { left, operator, right })))
+    }
+
+    // `1 + 1 + ... + 1`처럼 왼쪽으로 `depth`단계 깊어지는 식을 만듭니다.
+    fn left_chain(arena: &mut AstArena, depth: usize) -> ExprId {
+        let mut left = number(arena, 1);
+        for _ in 0..depth {
+            let right = number(arena, 1);
+            left = add(arena, left, right);
+        }
+        left
+    }
+
+    #[test]
+    fn test_descendants_in_preorder() {
+        let mut arena = AstArena::default();
+        let (one, two, three) = (number(&mut arena, 1), number(&mut arena, 2), number(&mut arena, 3));
+        let inner = add(&mut arena, one, two);
+        let root = add(&mut arena, inner, three);
+        let stmt = arena.alloc_stmt(Stmt::from(StmtKind::Semi(root)));
+
+        let order: Vec<AstId> = arena.descendants(AstId::Stmt(stmt)).collect();
+        let exprs = [root, inner, one, two, three].map(AstId::Expr);
+        assert_eq!(order[0], AstId::Stmt(stmt));
+        assert_eq!(order[1..], exprs);
+        assert_eq!(arena.children(AstId::Expr(root)), vec![AstId::Expr(inner), AstId::Expr(three)]);
+    }
+
+    #[test]
+    fn test_copy_is_independent_of_original() {
+        let mut arena = AstArena::default();
+        let (one, two) = (number(&mut arena, 1), number(&mut arena, 2));
+        let root = add(&mut arena, one, two);
+
+        let copy = arena.copy_expr(root);
+        assert_ne!(copy, root);
+        assert!(arena.expr_eq(root, &arena, copy));
+
+        let NodeKind::BinaryOp(binary_op) = &arena[copy].kind else { panic!() };
+        let right = binary_op.right;
+        arena[right].kind = NodeKind::Int(5, None);
+        assert!(!arena.expr_eq(root, &arena, copy));
+        assert_eq!(arena[two].kind, NodeKind::Int(2, None));
+    }
+
+    #[test]
+    fn test_take_and_put_back() {
+        let mut arena = AstArena::default();
+        let (one, two) = (number(&mut arena, 1), number(&mut arena, 2));
+        let root = add(&mut arena, one, two);
+
+        let mut node = arena.take_expr(root);
+        assert_eq!(arena[root].kind, NodeKind::Break);
+        // 꺼낸 동안에도 자식은 고칠 수 있습니다.
+        if let NodeKind::BinaryOp(binary_op) = &mut node.kind {
+            arena[binary_op.left].kind = NodeKind::Int(7, None);
+        }
+        arena[root] = node;
+        assert_eq!(arena.descendants(AstId::Expr(root)).count(), 3);
+        assert_eq!(arena[one].kind, NodeKind::Int(7, None));
+    }
+
+    #[test]
+    fn test_deep_tree_does_not_overflow() {
+        let mut arena = AstArena::default();
+        let root = left_chain(&mut arena, 200_000);
+        assert_eq!(arena.descendants(AstId::Expr(root)).count(), 400_001);
+
+        let copy = arena.copy_expr(root);
+        assert!(arena.expr_eq(root, &arena, copy));
+        drop(arena);
+    }
+}
@@ -0,0 +1,1072 @@
+use std::fmt;
+
+use rusttc_lexer::{Token, TokenKind};
+
+pub use crate::arena::AstArena;
+pub use rusttc_lexer::Span;
+
+// 파서가 노드마다 매기는 번호입니다. 한 크레이트 안에서 겹치지 않고, 같은 소스를 다시 파싱해도 같은 번호가 나옵니다.
+// 이후 단계는 이 번호를 열쇠로 노드마다 부가 정보를 따로 저장합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NodeId(pub u32);
+
+impl NodeId {
+    // 크레이트 첫 노드의 번호입니다.
+    pub const FIRST: NodeId = NodeId(0);
+
+    // 파싱 뒤에 컴파일러가 만든 노드의 번호입니다.
+    pub const DUMMY: NodeId = NodeId(u32::MAX);
+
+    pub fn next(self) -> NodeId {
+        NodeId(self.0 + 1)
+    }
+}
+
+// 위치와 번호는 구조 비교에 들어가지 않습니다. 같은 코드는 어디서 파싱했든 같은 트리이고,
+// 메서드 해석 단계는 타입 표기를 그대로 타입으로 비교합니다.
+macro_rules! impl_eq_by_kind {
+    ($($name:ident),*) => {
+        $(impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.kind == other.kind
+            }
+        })*
+    };
+}
+
+impl_eq_by_kind!(Node, Stmt, Pat, Ty);
+
+// `AstArena` 안의 표현식을 가리키는 번호입니다. 부모 노드는 자식을 `Box`로 품지 않고 이 번호로 가리킵니다.
+// 직렬화하면 번호 대신 가리키는 노드를 씁니다 (`AstArena::resolved`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExprId(pub u32);
+
+// `AstArena` 안의 문장을 가리키는 번호입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StmtId(pub u32);
+
+// 크레이트(소스 파일 하나)를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crate {
+    pub attrs: Vec<Attribute>,  // 크레이트 속성 (`#![...]`)
+    pub items: Vec<Item>,       // 최상위 아이템 목록
+    pub arena: AstArena,        // 크레이트 안 모든 함수 본문의 표현식과 문장
+}
+
+// 저장소는 따로 쓰지 않고, 아이템 안의 번호 자리마다 노드를 펼쳐 씁니다 (`{ "attrs": [...], "items": [...] }`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for Crate {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(serde::Serialize)]
+        #[serde(rename = "Crate")]
+        struct Fields<'a> {
+            attrs: &'a [Attribute],
+            items: &'a [Item],
+        }
+        self.arena.resolved(&Fields { attrs: &self.attrs, items: &self.items }).serialize(serializer)
+    }
+}
+
+// 아이템을 나타내는 열거형입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Item {
+    Fn(FnDef),                  // 함수 정의
+    Struct(StructDef),          // 구조체 정의
+    Impl(Impl),                 // impl 블록
+    Trait(TraitDef),            // 트레이트 정의
+    Mod(ModDef),                // 모듈 선언
+    Use(UseDecl),               // use 선언
+    Const(ConstDef),            // 상수 정의
+    Static(ConstDef),           // 정적 변수 정의
+}
+
+impl Item {
+    pub fn id(&self) -> NodeId {
+        match self {
+            Item::Fn(def) => def.id,
+            Item::Struct(def) => def.id,
+            Item::Impl(def) => def.id,
+            Item::Trait(def) => def.id,
+            Item::Mod(def) => def.id,
+            Item::Use(def) => def.id,
+            Item::Const(def) | Item::Static(def) => def.id,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Item::Fn(def) => def.span,
+            Item::Struct(def) => def.span,
+            Item::Impl(def) => def.span,
+            Item::Trait(def) => def.span,
+            Item::Mod(def) => def.span,
+            Item::Use(def) => def.span,
+            Item::Const(def) | Item::Static(def) => def.span,
+        }
+    }
+
+    pub fn attrs(&self) -> &[Attribute] {
+        match self {
+            Item::Fn(def) => &def.attrs,
+            Item::Struct(def) => &def.attrs,
+            Item::Impl(def) => &def.attrs,
+            Item::Trait(def) => &def.attrs,
+            Item::Mod(def) => &def.attrs,
+            Item::Use(def) => &def.attrs,
+            Item::Const(def) | Item::Static(def) => &def.attrs,
+        }
+    }
+}
+
+// 속성 `#[inline]`, `#![allow(unused)]`를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Attribute {
+    pub style: AttrStyle,       // 바깥 속성인지 안쪽 속성인지
+    pub meta: MetaItem,         // 속성 내용
+}
+
+impl Attribute {
+    pub fn has_name(&self, name: &str) -> bool {
+        self.meta.path.as_ident() == Some(name)
+    }
+}
+
+// 속성이 붙는 방향을 나타내는 열거형입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AttrStyle {
+    Outer,                      // #[...] (다음 아이템에 붙습니다)
+    Inner,                      // #![...] (둘러싼 크레이트나 모듈에 붙습니다)
+}
+
+// 메타 아이템 `name`, `name(...)`, `name = lit`을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MetaItem {
+    pub path: Path,             // 이름 (`cfg`, `rustfmt::skip`)
+    pub kind: MetaItemKind,     // 이름 뒤의 형태
+}
+
+// 메타 아이템의 형태를 나타내는 열거형입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum MetaItemKind {
+    Word,                       // #[test]
+    List(Vec<NestedMeta>),      // #[derive(Debug, Clone)]
+    NameValue(Lit),             // feature = "x"
+}
+
+// 메타 아이템 목록의 원소를 나타내는 열거형입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum NestedMeta {
+    Meta(MetaItem),             // not(test)
+    Lit(Lit),                   // "text", 1
+}
+
+// 속성에 쓰이는 리터럴을 나타내는 열거형입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Lit {
+    Str(String),                // "text" (따옴표 제외)
+    Int(i64),                   // 1
+    Bool(bool),                 // true
+}
+
+// 아이템의 가시성을 나타내는 열거형입니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Visibility {
+    Public,                     // pub, pub(crate) (크레이트가 하나뿐이므로 같습니다)
+    #[default]
+    Private,                    // 선언된 모듈과 그 하위 모듈에서만 보입니다
+}
+
+// 모듈 선언 `mod foo { ... }`, `mod foo;`를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ModDef {
+    pub attrs: Vec<Attribute>,      // 속성
+    pub id: NodeId,                 // 노드 번호
+    pub span: Span,                 // 아이템 전체의 위치
+    pub vis: Visibility,            // 가시성
+    pub name: String,               // 모듈 이름
+    pub items: Option<Vec<Item>>,   // 모듈 안의 아이템 (`mod foo;`는 로더가 파일을 읽기 전까지 None)
+}
+
+// `use a::b::{c, d as e};` 선언을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UseDecl {
+    pub attrs: Vec<Attribute>,  // 속성
+    pub id: NodeId,             // 노드 번호
+    pub span: Span,             // 아이템 전체의 위치
+    pub vis: Visibility,        // 가시성 (`pub use`는 다시 내보냅니다)
+    pub tree: UseTree,          // 가져올 경로
+}
+
+// use 경로의 트리를 나타내는 구조체입니다. `a::{b, c::*}`는 접두사 `a` 아래 두 트리입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UseTree {
+    pub prefix: Path,           // 공통 접두사
+    pub kind: UseTreeKind,      // 접두사 뒤의 형태
+}
+
+// use 트리의 형태를 나타내는 열거형입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum UseTreeKind {
+    Simple(Option<String>),     // `a::b`, `a::b as c`
+    Nested(Vec<UseTree>),       // `a::{b, c}`
+    Glob,                       // `a::*`
+}
+
+// 함수 정의를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FnDef {
+    pub attrs: Vec<Attribute>,          // 속성
+    pub id: NodeId,                     // 노드 번호
+    pub span: Span,                     // 아이템 전체의 위치
+    pub vis: Visibility,                // 가시성
+    pub name: String,                   // 함수 이름
+    pub generics: Generics,             // 제네릭 매개변수
+    pub self_param: Option<SelfKind>,   // self 리시버 (메서드인 경우)
+    pub params: Vec<Param>,             // 매개변수 목록 (self 제외)
+    pub ret: Option<Ty>,                // 반환 타입
+    pub body: Option<Block>,            // 함수 본문 (트레이트 메서드 선언은 None)
+}
+
+// 제네릭 매개변수 목록 `<T: Bound, U>`을 나타내는 구조체입니다.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Generics {
+    pub params: Vec<GenericParam>,      // 타입 매개변수
+}
+
+// 타입 매개변수와 그 트레이트 바운드를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GenericParam {
+    pub name: String,           // 매개변수 이름
+    pub bounds: Vec<Path>,      // 트레이트 바운드
+}
+
+// self 리시버의 형태를 나타내는 열거형입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum SelfKind {
+    Value { mutable: bool },    // self, mut self
+    Ref,                        // &self
+    RefMut,                     // &mut self
+}
+
+// 함수 매개변수를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Param {
+    pub pat: Pat,               // 바인딩 패턴
+    pub ty: Ty,                 // 타입
+}
+
+// 구조체 정의를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StructDef {
+    pub attrs: Vec<Attribute>,      // 속성
+    pub id: NodeId,                 // 노드 번호
+    pub span: Span,                 // 아이템 전체의 위치
+    pub vis: Visibility,            // 가시성
+    pub name: String,               // 구조체 이름
+    pub generics: Generics,         // 제네릭 매개변수
+    pub fields: Vec<FieldDef>,      // 필드 목록
+}
+
+// 상수 `const N: usize = 4 * 16;`나 정적 변수 `static LIMIT: u32 = 10;`의 정의를 나타내는 구조체입니다.
+// 초기값은 컴파일 중에 계산됩니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConstDef {
+    pub attrs: Vec<Attribute>,      // 속성
+    pub id: NodeId,                 // 노드 번호
+    pub span: Span,                 // 아이템 전체의 위치
+    pub vis: Visibility,            // 가시성
+    pub name: String,               // 상수 이름
+    pub ty: Ty,                     // 타입 (생략할 수 없습니다)
+    pub value: ExprId,              // 초기값 식
+}
+
+// 구조체 필드 정의를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FieldDef {
+    pub vis: Visibility,        // 가시성
+    pub name: String,           // 필드 이름
+    pub ty: Ty,                 // 필드 타입
+}
+
+// impl 블록을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Impl {
+    pub attrs: Vec<Attribute>,  // 속성
+    pub id: NodeId,             // 노드 번호
+    pub span: Span,             // 아이템 전체의 위치
+    pub generics: Generics,     // 제네릭 매개변수 (`impl<T>`)
+    pub of_trait: Option<Path>, // 구현하는 트레이트 (`impl Trait for Type`)
+    pub self_ty: Ty,            // 구현 대상 타입
+    pub items: Vec<FnDef>,      // 연관 함수와 메서드
+}
+
+// 트레이트 정의를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TraitDef {
+    pub attrs: Vec<Attribute>,  // 속성
+    pub id: NodeId,             // 노드 번호
+    pub span: Span,             // 아이템 전체의 위치
+    pub vis: Visibility,        // 가시성
+    pub name: String,           // 트레이트 이름
+    pub items: Vec<FnDef>,      // 메서드 선언 (본문이 있으면 기본 구현)
+}
+
+// 타입 표기를 나타내는 구조체입니다.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Ty {
+    pub id: NodeId,             // 노드 번호
+    pub span: Span,             // 소스 위치
+    pub kind: TyKind,           // 타입의 형태
+}
+
+impl Ty {
+    pub fn from_ident(ident: &str) -> Self {
+        TyKind::Path(Path::from_ident(ident)).into()
+    }
+}
+
+// 컴파일러가 만든 타입에는 위치와 번호가 없습니다.
+impl From<TyKind> for Ty {
+    fn from(kind: TyKind) -> Self {
+        Ty { id: NodeId::DUMMY, span: Span::DUMMY, kind }
+    }
+}
+
+// 타입의 형태를 나타내는 열거형입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TyKind {
+    Path(Path),                             // i32, Point, Self
+    Ref { mutable: bool, ty: Box<Ty> },     // &T, &mut T
+    Unit,                                   // ()
+    Array(Box<Ty>, ArrayLen),               // [T; 3], [T; N]
+    Slice(Box<Ty>),                         // [T]
+    Tuple(Vec<Ty>),                         // (T, U)
+}
+
+// 배열 타입의 길이입니다. 상수 식은 타입 검사가 계산해 `Known`으로 바꿔 씁니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ArrayLen {
+    Known(usize),               // 리터럴 길이나 계산을 마친 길이
+    Expr(ExprId),               // 아직 계산하지 않은 상수 식 (`N`, `N * 2`)
+}
+
+// `a::b::c` 형태의 경로를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Path {
+    pub segments: Vec<PathSegment>,     // 경로 세그먼트
+}
+
+// 경로 세그먼트를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PathSegment {
+    pub ident: String,          // 세그먼트 이름
+    pub args: Vec<Ty>,          // 제네릭 인자 (`Pair<i32>`, `max::<i32>`, `Fn(i32)`)
+    pub output: Option<Box<Ty>>,    // 클로저 트레이트의 반환 타입 (`Fn(i32) -> i32`)
+}
+
+// 클로저가 구현하는 내장 트레이트입니다. 인자를 `Fn(i32) -> i32`처럼 괄호로 씁니다.
+pub const FN_TRAITS: &[&str] = &["Fn", "FnMut", "FnOnce"];
+
+impl PathSegment {
+    pub fn from_ident(ident: &str) -> Self {
+        PathSegment { ident: ident.to_string(), args: Vec::new(), output: None }
+    }
+
+    pub fn is_fn_trait(&self) -> bool {
+        FN_TRAITS.contains(&self.ident.as_str())
+    }
+}
+
+impl Path {
+    pub fn from_ident(ident: &str) -> Self {
+        Path { segments: vec![PathSegment::from_ident(ident)] }
+    }
+
+    // 세그먼트가 하나뿐인 경로라면 그 이름을 돌려줍니다. 제네릭 인자는 무시합니다.
+    pub fn as_ident(&self) -> Option<&str> {
+        match self.segments.as_slice() {
+            [segment] => Some(&segment.ident),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            if i > 0 {
+                write!(f, "::")?;
+            }
+            write!(f, "{}", segment.ident)?;
+            let args: Vec<String> = segment.args.iter().map(|arg| arg.to_string()).collect();
+            if segment.is_fn_trait() {
+                write!(f, "({})", args.join(", "))?;
+                if let Some(output) = &segment.output {
+                    write!(f, " -> {}", output)?;
+                }
+            } else if !args.is_empty() {
+                write!(f, "<{}>", args.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TyKind::Path(path) => write!(f, "{}", path),
+            TyKind::Ref { mutable: true, ty } => write!(f, "&mut {}", ty),
+            TyKind::Ref { mutable: false, ty } => write!(f, "&{}", ty),
+            TyKind::Unit => write!(f, "()"),
+            TyKind::Array(elem, ArrayLen::Known(len)) => write!(f, "[{}; {}]", elem, len),
+            // 저장소 없이는 식을 출력할 수 없으므로 길이를 비워 둡니다. 소스 모양은 `pretty`가 씁니다.
+            TyKind::Array(elem, ArrayLen::Expr(_)) => write!(f, "[{}; _]", elem),
+            TyKind::Slice(elem) => write!(f, "[{}]", elem),
+            TyKind::Tuple(tys) if tys.len() == 1 => write!(f, "({},)", tys[0]),
+            TyKind::Tuple(tys) => {
+                let tys: Vec<String> = tys.iter().map(Ty::to_string).collect();
+                write!(f, "({})", tys.join(", "))
+            },
+        }
+    }
+}
+
+// 한정 경로 `<Type as Trait>::item`의 앞부분을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct QSelf {
+    pub ty: Ty,                 // 대상 타입
+    pub trait_path: Path,       // 트레이트 경로
+}
+
+// 패턴을 나타내는 구조체입니다.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Pat {
+    pub id: NodeId,             // 노드 번호
+    pub span: Span,             // 소스 위치
+    pub kind: PatKind,          // 패턴의 형태
+}
+
+impl From<PatKind> for Pat {
+    fn from(kind: PatKind) -> Self {
+        Pat { id: NodeId::DUMMY, span: Span::DUMMY, kind }
+    }
+}
+
+impl Pat {
+    // 이 패턴과 안쪽 패턴을 모두 앞에서부터 차례로 `f`에 넘깁니다.
+    pub fn walk(&self, f: &mut impl FnMut(&Pat)) {
+        f(self);
+        match &self.kind {
+            PatKind::Ident { .. } | PatKind::Wild | PatKind::Lit(_) | PatKind::Range(..) => {},
+            PatKind::Tuple(pats) | PatKind::Or(pats) => pats.iter().for_each(|pat| pat.walk(f)),
+            PatKind::Struct { fields, .. } => fields.iter().for_each(|(_, pat)| pat.walk(f)),
+        }
+    }
+
+    // 패턴이 바인딩하는 변수 패턴(`x`, `mut x`)입니다. or 패턴은 첫 번째 갈래의 것만 셉니다.
+    pub fn bindings(&self) -> Vec<&Pat> {
+        let mut bindings = Vec::new();
+        self.collect_bindings(&mut bindings);
+        bindings
+    }
+
+    // or 패턴을 펼쳐 `|`가 없는 패턴들로 만듭니다: `(1 | 2, x)` → `(1, x)`, `(2, x)`
+    pub fn alternatives(&self) -> Vec<Pat> {
+        let kinds: Vec<PatKind> = match &self.kind {
+            PatKind::Or(pats) => return pats.iter().flat_map(Pat::alternatives).collect(),
+            PatKind::Tuple(pats) => product(pats.iter().map(Pat::alternatives)).into_iter().map(PatKind::Tuple).collect(),
+            PatKind::Struct { path, fields, rest } => product(fields.iter().map(|(_, pat)| pat.alternatives()))
+                .into_iter()
+                .map(|pats| {
+                    let fields = fields.iter().map(|(name, _)| name.clone()).zip(pats).collect();
+                    PatKind::Struct { path: path.clone(), fields, rest: *rest }
+                })
+                .collect(),
+            _ => return vec![self.clone()],
+        };
+        kinds.into_iter().map(|kind| Pat { id: self.id, span: self.span, kind }).collect()
+    }
+
+    fn collect_bindings<'a>(&'a self, bindings: &mut Vec<&'a Pat>) {
+        match &self.kind {
+            PatKind::Ident { .. } => bindings.push(self),
+            PatKind::Wild | PatKind::Lit(_) | PatKind::Range(..) => {},
+            PatKind::Tuple(pats) => pats.iter().for_each(|pat| pat.collect_bindings(bindings)),
+            PatKind::Struct { fields, .. } => fields.iter().for_each(|(_, pat)| pat.collect_bindings(bindings)),
+            PatKind::Or(pats) => pats.iter().take(1).for_each(|pat| pat.collect_bindings(bindings)),
+        }
+    }
+}
+
+// 자리마다 후보 가운데 하나씩 고른 모든 조합입니다.
+fn product(lists: impl Iterator<Item = Vec<Pat>>) -> Vec<Vec<Pat>> {
+    lists.fold(vec![Vec::new()], |combos, list| {
+        combos.iter().flat_map(|combo| list.iter().map(move |pat| [combo.clone(), vec![pat.clone()]].concat())).collect()
+    })
+}
+
+// 패턴의 형태를 나타내는 열거형입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PatKind {
+    Ident { name: String, mutable: bool },  // x, mut x
+    Wild,                                   // _
+    Lit(ExprId),                            // 1, -1, true, 'a' (리터럴 표현식)
+    Range(ExprId, ExprId),                  // 1..=9, 'a'..='z'
+    Tuple(Vec<Pat>),                        // (a, _)
+    Struct { path: Path, fields: Vec<(String, Pat)>, rest: bool },   // Point { x: 0, y, .. }
+    Or(Vec<Pat>),                           // 1 | 2
+}
+
+// 블록 `{ ... }`을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Block {
+    pub stmts: Vec<StmtId>,     // 문장 목록 (마지막 StmtKind::Expr는 블록의 값)
+}
+
+// 문장을 나타내는 구조체입니다.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Stmt {
+    pub id: NodeId,             // 노드 번호
+    pub span: Span,             // 소스 위치 (끝의 `;` 포함)
+    pub kind: StmtKind,         // 문장의 형태
+}
+
+impl From<StmtKind> for Stmt {
+    fn from(kind: StmtKind) -> Self {
+        Stmt { id: NodeId::DUMMY, span: Span::DUMMY, kind }
+    }
+}
+
+// 문장의 형태를 나타내는 열거형입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum StmtKind {
+    Let(Local),                 // let 바인딩
+    Item(Item),                 // 블록 안의 아이템
+    Expr(ExprId),               // 세미콜론 없는 표현식
+    Semi(ExprId),               // 세미콜론으로 끝나는 표현식
+}
+
+// `let` 바인딩을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Local {
+    pub pat: Pat,               // 바인딩 패턴
+    pub ty: Option<Ty>,         // 타입 표기
+    pub init: Option<ExprId>,   // 초기값
+}
+
+// AST의 표현식 노드를 나타내는 구조체입니다.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Node {
+    pub id: NodeId,             // 노드 번호
+    pub span: Span,             // 소스 위치
+    pub kind: NodeKind,         // 표현식의 형태
+}
+
+impl From<NodeKind> for Node {
+    fn from(kind: NodeKind) -> Self {
+        Node { id: NodeId::DUMMY, span: Span::DUMMY, kind }
+    }
+}
+
+// 표현식의 형태를 나타내는 열거형입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum NodeKind {
+    Int(u128, Option<IntTy>),       // 정수 리터럴과 접미사 (`1`, `255u8`)
+    Float(f64, Option<FloatTy>),    // 실수 리터럴과 접미사 (`1.5`, `2f32`)
+    Char(char),              // 'a'
+    Ident(String),           // 식별자
+    BinaryOp(BinaryOp),      // 이항 연산자
+    Bool(bool),              // true, false
+    Str(String),             // "text" (따옴표 제외)
+    Unary(UnaryOp),          // 단항 연산자 (-x, !x, *x)
+    Ref { mutable: bool, expr: ExprId },        // &x, &mut x
+    Path(Path),              // Point::new
+    QualifiedPath(Box<QSelf>, Path),    // <Circle as Shape>::area
+    Call(Call),              // 함수 호출
+    MethodCall(MethodCall),  // 메서드 호출
+    Field(ExprId, String),   // 필드 접근, 튜플 필드 `t.0`
+    StructLit(StructLit),    // Point { x: 1 }
+    Assign(Assign),          // 대입 (=, += 등)
+    Block(Block),            // 블록 표현식
+    If(If),                  // if 표현식
+    Match(Match),            // match 표현식
+    While(While),            // while 루프
+    ForLoop(ForLoop),        // for 루프
+    Loop(Block),             // loop 루프
+    Break,                   // break
+    Continue,                // continue
+    Return(Option<ExprId>),  // return
+    Closure(Box<Closure>),   // |x| x + 1, move |x| ...
+    Array(Vec<ExprId>),      // [1, 2, 3]
+    Repeat(ExprId, ExprId),  // [0; 16]
+    Tuple(Vec<ExprId>),      // (1, true), ()
+    Index(ExprId, ExprId),   // a[i], a[1..3]
+    Range(Range),            // a..b, a..=b, ..b, a..
+    FormatArgs(Box<FormatArgs>),    // format_args!("{} {}", a, b)
+}
+
+// 정수 타입입니다. 접미사 없는 리터럴의 타입은 타입 검사가 추론해 채웁니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum IntTy {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl IntTy {
+    pub const ALL: [IntTy; 12] = [
+        IntTy::I8, IntTy::I16, IntTy::I32, IntTy::I64, IntTy::I128, IntTy::Isize,
+        IntTy::U8, IntTy::U16, IntTy::U32, IntTy::U64, IntTy::U128, IntTy::Usize,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntTy::I8 => "i8",
+            IntTy::I16 => "i16",
+            IntTy::I32 => "i32",
+            IntTy::I64 => "i64",
+            IntTy::I128 => "i128",
+            IntTy::Isize => "isize",
+            IntTy::U8 => "u8",
+            IntTy::U16 => "u16",
+            IntTy::U32 => "u32",
+            IntTy::U64 => "u64",
+            IntTy::U128 => "u128",
+            IntTy::Usize => "usize",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<IntTy> {
+        IntTy::ALL.into_iter().find(|ty| ty.name() == name)
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(self, IntTy::I8 | IntTy::I16 | IntTy::I32 | IntTy::I64 | IntTy::I128 | IntTy::Isize)
+    }
+
+    // 비트 수입니다. `isize`, `usize`는 64비트 대상으로 봅니다.
+    pub fn bits(self) -> u32 {
+        match self {
+            IntTy::I8 | IntTy::U8 => 8,
+            IntTy::I16 | IntTy::U16 => 16,
+            IntTy::I32 | IntTy::U32 => 32,
+            IntTy::I64 | IntTy::U64 | IntTy::Isize | IntTy::Usize => 64,
+            IntTy::I128 | IntTy::U128 => 128,
+        }
+    }
+
+    // 가장 큰 값입니다.
+    pub fn max(self) -> u128 {
+        match self.is_signed() {
+            true => (1 << (self.bits() - 1)) - 1,
+            false => u128::MAX >> (128 - self.bits()),
+        }
+    }
+
+    // 가장 작은 값의 절댓값입니다. 부호 없는 타입은 0입니다.
+    pub fn min_abs(self) -> u128 {
+        match self.is_signed() {
+            true => 1 << (self.bits() - 1),
+            false => 0,
+        }
+    }
+}
+
+// 실수 타입입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FloatTy {
+    F32,
+    F64,
+}
+
+impl FloatTy {
+    pub fn name(self) -> &'static str {
+        match self {
+            FloatTy::F32 => "f32",
+            FloatTy::F64 => "f64",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<FloatTy> {
+        [FloatTy::F32, FloatTy::F64].into_iter().find(|ty| ty.name() == name)
+    }
+}
+
+// 매크로 전개 뒤에 남는 `format_args!`, `format_args_nl!`을 나타내는 구조체입니다.
+// 포맷 문자열은 파싱할 때 검사되어 조각으로 나뉘고, 자리표시자는 `args`의 위치를 가리킵니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FormatArgs {
+    pub template: String,           // 포맷 문자열 (따옴표 제외, 이스케이프는 소스 그대로)
+    pub pieces: Vec<FormatPiece>,   // 문자열 조각과 자리표시자 (`format_args_nl!`은 끝에 `\n` 조각)
+    pub args: Vec<FormatArg>,       // 인자 (위치 인자, 이름 있는 인자, 캡처한 변수 순)
+}
+
+// 포맷 문자열의 조각을 나타내는 열거형입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FormatPiece {
+    Literal(String),                // 그대로 출력할 문자열 (`{{`, `}}`는 풀려 있습니다)
+    Placeholder(FormatPlaceholder), // {0:>8.2}
+}
+
+// 자리표시자 `{arg:spec}`을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FormatPlaceholder {
+    pub arg: usize,             // 출력할 인자의 위치
+    pub spec: FormatSpec,       // 포맷 사양
+}
+
+// 포맷 사양 `[[fill]align][sign][#][0][width][.precision][type]`을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FormatSpec {
+    pub fill: char,                         // 채움 문자 (기본값은 공백)
+    pub align: Option<FormatAlign>,         // 정렬
+    pub sign: Option<FormatSign>,           // `+`, `-`
+    pub alternate: bool,                    // `#`
+    pub zero_pad: bool,                     // `0`
+    pub width: Option<FormatCount>,         // 너비
+    pub precision: Option<FormatCount>,     // 정밀도
+    pub trait_: FormatTrait,                // 출력에 쓸 포맷 트레이트
+}
+
+impl Default for FormatSpec {
+    fn default() -> Self {
+        FormatSpec {
+            fill: ' ',
+            align: None,
+            sign: None,
+            alternate: false,
+            zero_pad: false,
+            width: None,
+            precision: None,
+            trait_: FormatTrait::Display,
+        }
+    }
+}
+
+// 정렬 방향을 나타내는 열거형입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FormatAlign {
+    Left,                       // <
+    Center,                     // ^
+    Right,                      // >
+}
+
+// 부호 플래그를 나타내는 열거형입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FormatSign {
+    Plus,                       // +
+    Minus,                      // -
+}
+
+// 너비와 정밀도의 값을 나타내는 열거형입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FormatCount {
+    Literal(usize),             // {:8}
+    Arg(usize),                 // {:1$}, {:width$}, {:.*} (인자의 위치)
+}
+
+// 자리표시자가 쓰는 포맷 트레이트입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FormatTrait {
+    Display,                    // {}
+    Debug,                      // {:?}, {:x?}, {:X?}
+    LowerHex,                   // {:x}
+    UpperHex,                   // {:X}
+    Octal,                      // {:o}
+    Binary,                     // {:b}
+    LowerExp,                   // {:e}
+    UpperExp,                   // {:E}
+}
+
+impl FormatTrait {
+    pub fn from_spec(spec: &str) -> Option<FormatTrait> {
+        match spec {
+            "" => Some(FormatTrait::Display),
+            "?" | "x?" | "X?" => Some(FormatTrait::Debug),
+            "x" => Some(FormatTrait::LowerHex),
+            "X" => Some(FormatTrait::UpperHex),
+            "o" => Some(FormatTrait::Octal),
+            "b" => Some(FormatTrait::Binary),
+            "e" => Some(FormatTrait::LowerExp),
+            "E" => Some(FormatTrait::UpperExp),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FormatTrait::Display => "Display",
+            FormatTrait::Debug => "Debug",
+            FormatTrait::LowerHex => "LowerHex",
+            FormatTrait::UpperHex => "UpperHex",
+            FormatTrait::Octal => "Octal",
+            FormatTrait::Binary => "Binary",
+            FormatTrait::LowerExp => "LowerExp",
+            FormatTrait::UpperExp => "UpperExp",
+        }
+    }
+}
+
+// `format_args!`의 인자를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FormatArg {
+    pub kind: FormatArgKind,    // 인자를 쓴 방식
+    pub expr: ExprId,           // 인자 표현식
+}
+
+// 포맷 인자를 쓴 방식입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FormatArgKind {
+    Normal,                     // "{}", x
+    Named(String),              // "{x}", x = 1
+    Captured(String),           // "{x}" (같은 이름의 변수를 캡처합니다)
+}
+
+// 범위 표현식을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Range {
+    pub start: Option<ExprId>,  // 시작 (포함)
+    pub end: Option<ExprId>,    // 끝
+    pub inclusive: bool,        // `..=` 여부
+}
+
+// 이항 연산자 노드를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BinaryOp {
+    pub left: ExprId,        // 왼쪽 피연산자
+    pub operator: Token,     // 연산자
+    pub right: ExprId,       // 오른쪽 피연산자
+}
+
+// 이항 연산자의 우선순위입니다. 숫자가 클수록 먼저 결합합니다.
+// 비교 연산자(3)는 결합하지 않으므로 `a == b == c`는 괄호 없이 쓸 수 없습니다.
+pub fn binary_precedence(kind: &TokenKind) -> Option<u8> {
+    match kind {
+        TokenKind::OrOr => Some(1),
+        TokenKind::AndAnd => Some(2),
+        TokenKind::EqEq | TokenKind::Ne | TokenKind::Lt | TokenKind::Gt | TokenKind::Le | TokenKind::Ge => Some(3),
+        TokenKind::Or => Some(4),
+        TokenKind::Caret => Some(5),
+        TokenKind::And => Some(6),
+        TokenKind::Plus | TokenKind::Minus => Some(7),
+        TokenKind::Star | TokenKind::Slash | TokenKind::Percent => Some(8),
+        _ => None,
+    }
+}
+
+pub fn is_comparison(kind: &TokenKind) -> bool {
+    binary_precedence(kind) == Some(3)
+}
+
+// 단항 연산자 노드를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UnaryOp {
+    pub operator: Token,     // 연산자
+    pub operand: ExprId,     // 피연산자
+}
+
+// 함수 호출을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Call {
+    pub callee: ExprId,      // 호출 대상
+    pub args: Vec<ExprId>,   // 인자 목록
+}
+
+// 메서드 호출 `receiver.method(args)`를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MethodCall {
+    pub receiver: ExprId,    // 리시버
+    pub method: String,      // 메서드 이름
+    pub args: Vec<ExprId>,   // 인자 목록 (리시버 제외)
+}
+
+// 구조체 리터럴을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StructLit {
+    pub path: Path,                     // 구조체 경로
+    pub fields: Vec<(String, ExprId)>,  // 필드 이름과 값
+}
+
+// 대입 표현식을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Assign {
+    pub target: ExprId,         // 대입 대상
+    pub operator: Option<Token>,    // 복합 대입 연산자 (+=, -= 등)
+    pub value: ExprId,          // 대입할 값
+}
+
+// if 표현식을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct If {
+    pub cond: ExprId,           // 조건
+    pub then: Block,            // 참일 때 블록
+    pub els: Option<ExprId>,    // else 블록 또는 else if
+}
+
+// match 표현식을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Match {
+    pub scrutinee: ExprId,      // 검사할 값
+    pub arms: Vec<Arm>,         // 갈래 (위에서부터 차례로 맞춰 봅니다)
+}
+
+// match 갈래 `pat if guard => body`를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Arm {
+    pub pat: Pat,               // 패턴
+    pub guard: Option<ExprId>,  // `if` 조건
+    pub body: ExprId,           // 본문
+}
+
+// 클로저 표현식을 나타내는 구조체입니다.
+// `name`, `kind`, `captures`는 파싱 직후에는 비어 있습니다. `name`은 메서드 해석 단계에서,
+// `kind`와 `captures`는 타입 검사가 캡처를 분석한 뒤 쓰기 단계에서 채워집니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Closure {
+    pub capture_by: CaptureBy,      // `move` 여부
+    pub params: Vec<ClosureParam>,  // 매개변수
+    pub ret: Option<Ty>,            // 반환 타입 표기
+    pub body: ExprId,               // 본문
+    pub name: String,               // 클로저 타입 이름 (`main::{closure#0}`)
+    pub kind: ClosureKind,          // 구현하는 클로저 트레이트
+    pub captures: Vec<Capture>,     // 캡처한 변수
+}
+
+// 클로저 매개변수를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ClosureParam {
+    pub pat: Pat,               // 바인딩 패턴
+    pub ty: Option<Ty>,         // 타입 표기
+}
+
+// 클로저가 변수를 캡처하는 기본 방식입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CaptureBy {
+    Ref,                        // 사용 방식에 따라 참조, 가변 참조, 이동 중에서 고릅니다
+    Value,                      // `move`: 모두 이동합니다
+}
+
+// 클로저가 구현하는 가장 강한 트레이트입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ClosureKind {
+    Fn,                         // 캡처를 읽기만 합니다
+    FnMut,                      // 캡처를 변경합니다
+    FnOnce,                     // 캡처를 밖으로 이동합니다
+}
+
+impl ClosureKind {
+    pub fn trait_name(self) -> &'static str {
+        match self {
+            ClosureKind::Fn => "Fn",
+            ClosureKind::FnMut => "FnMut",
+            ClosureKind::FnOnce => "FnOnce",
+        }
+    }
+}
+
+// 캡처한 변수 하나를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Capture {
+    pub name: String,           // 변수 이름
+    pub mode: CaptureMode,      // 캡처 방식
+}
+
+// 변수를 캡처하는 방식입니다. 약한 것부터 강한 순서입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CaptureMode {
+    ByRef,                      // &x
+    ByMutRef,                   // &mut x
+    ByValue,                    // x (이동 또는 복사)
+}
+
+// while 루프를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct While {
+    pub cond: ExprId,           // 조건
+    pub body: Block,            // 본문
+}
+
+// for 루프를 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ForLoop {
+    pub pat: Pat,               // 원소를 묶을 패턴
+    pub iter: ExprId,           // 반복할 값 (범위, 배열 참조, 반복자)
+    pub body: Block,            // 본문
+}
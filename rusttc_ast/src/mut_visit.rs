@@ -0,0 +1,457 @@
+use crate::ast::{
+    Arm, ArrayLen, AstArena, Attribute, Block, ConstDef, Crate, ExprId, FieldDef, FnDef, GenericParam, Generics, Impl, Item, Local, ModDef, Node, NodeKind,
+    Param, Pat, PatKind, Path, Stmt, StmtKind, StructDef, TraitDef, Ty, TyKind, UseDecl, UseTree, UseTreeKind,
+};
+
+// AST를 제자리에서 고쳐 쓰는 방문자입니다. 노드를 통째로 바꾸려면 `*node = ...`로 덮어씁니다.
+// `Visitor`와 같은 순서로 방문하고, 기본 구현은 이 모듈의 `walk_*` 함수입니다.
+// 표현식과 문장은 `arena`에서 잠시 꺼내 방문한 뒤 되돌려 놓습니다. 방문하는 동안 노드를 새로 만들어 넣을 수도 있습니다.
+pub trait MutVisitor: Sized {
+    fn arena(&mut self) -> &mut AstArena;
+
+    fn visit_crate(&mut self, krate: &mut Crate) {
+        walk_crate(self, krate)
+    }
+
+    fn visit_attribute(&mut self, _attr: &mut Attribute) {}
+
+    fn visit_item(&mut self, item: &mut Item) {
+        walk_item(self, item)
+    }
+
+    // 최상위 함수와 impl, 트레이트 안의 함수 모두 여기로 옵니다.
+    fn visit_fn(&mut self, function: &mut FnDef) {
+        walk_fn(self, function)
+    }
+
+    fn visit_struct(&mut self, def: &mut StructDef) {
+        walk_struct(self, def)
+    }
+
+    fn visit_field_def(&mut self, field: &mut FieldDef) {
+        walk_field_def(self, field)
+    }
+
+    fn visit_impl(&mut self, imp: &mut Impl) {
+        walk_impl(self, imp)
+    }
+
+    fn visit_trait(&mut self, def: &mut TraitDef) {
+        walk_trait(self, def)
+    }
+
+    fn visit_mod(&mut self, def: &mut ModDef) {
+        walk_mod(self, def)
+    }
+
+    fn visit_use(&mut self, decl: &mut UseDecl) {
+        walk_use(self, decl)
+    }
+
+    // `const`와 `static` 모두 여기로 옵니다.
+    fn visit_const(&mut self, def: &mut ConstDef) {
+        walk_const(self, def)
+    }
+
+    fn visit_use_tree(&mut self, tree: &mut UseTree) {
+        walk_use_tree(self, tree)
+    }
+
+    fn visit_generics(&mut self, generics: &mut Generics) {
+        walk_generics(self, generics)
+    }
+
+    fn visit_generic_param(&mut self, param: &mut GenericParam) {
+        walk_generic_param(self, param)
+    }
+
+    fn visit_param(&mut self, param: &mut Param) {
+        walk_param(self, param)
+    }
+
+    fn visit_block(&mut self, block: &mut Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_stmt(&mut self, stmt: &mut Stmt) {
+        walk_stmt(self, stmt)
+    }
+
+    fn visit_local(&mut self, local: &mut Local) {
+        walk_local(self, local)
+    }
+
+    fn visit_expr(&mut self, node: &mut Node) {
+        walk_expr(self, node)
+    }
+
+    fn visit_arm(&mut self, arm: &mut Arm) {
+        walk_arm(self, arm)
+    }
+
+    fn visit_pat(&mut self, pat: &mut Pat) {
+        walk_pat(self, pat)
+    }
+
+    fn visit_ty(&mut self, ty: &mut Ty) {
+        walk_ty(self, ty)
+    }
+
+    fn visit_path(&mut self, path: &mut Path) {
+        walk_path(self, path)
+    }
+}
+
+pub fn walk_crate<V: MutVisitor>(visitor: &mut V, krate: &mut Crate) {
+    krate.attrs.iter_mut().for_each(|attr| visitor.visit_attribute(attr));
+    krate.items.iter_mut().for_each(|item| visitor.visit_item(item));
+}
+
+pub fn walk_item<V: MutVisitor>(visitor: &mut V, item: &mut Item) {
+    match item {
+        Item::Fn(function) => visitor.visit_fn(function),
+        Item::Struct(def) => visitor.visit_struct(def),
+        Item::Impl(imp) => visitor.visit_impl(imp),
+        Item::Trait(def) => visitor.visit_trait(def),
+        Item::Mod(def) => visitor.visit_mod(def),
+        Item::Use(decl) => visitor.visit_use(decl),
+        Item::Const(def) | Item::Static(def) => visitor.visit_const(def),
+    }
+}
+
+pub fn walk_fn<V: MutVisitor>(visitor: &mut V, function: &mut FnDef) {
+    function.attrs.iter_mut().for_each(|attr| visitor.visit_attribute(attr));
+    visitor.visit_generics(&mut function.generics);
+    function.params.iter_mut().for_each(|param| visitor.visit_param(param));
+    if let Some(ret) = &mut function.ret {
+        visitor.visit_ty(ret);
+    }
+    if let Some(body) = &mut function.body {
+        visitor.visit_block(body);
+    }
+}
+
+pub fn walk_struct<V: MutVisitor>(visitor: &mut V, def: &mut StructDef) {
+    def.attrs.iter_mut().for_each(|attr| visitor.visit_attribute(attr));
+    visitor.visit_generics(&mut def.generics);
+    def.fields.iter_mut().for_each(|field| visitor.visit_field_def(field));
+}
+
+pub fn walk_field_def<V: MutVisitor>(visitor: &mut V, field: &mut FieldDef) {
+    visitor.visit_ty(&mut field.ty);
+}
+
+pub fn walk_impl<V: MutVisitor>(visitor: &mut V, imp: &mut Impl) {
+    imp.attrs.iter_mut().for_each(|attr| visitor.visit_attribute(attr));
+    visitor.visit_generics(&mut imp.generics);
+    if let Some(of_trait) = &mut imp.of_trait {
+        visitor.visit_path(of_trait);
+    }
+    visitor.visit_ty(&mut imp.self_ty);
+    imp.items.iter_mut().for_each(|function| visitor.visit_fn(function));
+}
+
+pub fn walk_trait<V: MutVisitor>(visitor: &mut V, def: &mut TraitDef) {
+    def.attrs.iter_mut().for_each(|attr| visitor.visit_attribute(attr));
+    def.items.iter_mut().for_each(|function| visitor.visit_fn(function));
+}
+
+// 아직 파일을 읽지 않은 `mod foo;`는 안으로 내려가지 않습니다.
+pub fn walk_mod<V: MutVisitor>(visitor: &mut V, def: &mut ModDef) {
+    def.attrs.iter_mut().for_each(|attr| visitor.visit_attribute(attr));
+    if let Some(items) = &mut def.items {
+        items.iter_mut().for_each(|item| visitor.visit_item(item));
+    }
+}
+
+pub fn walk_const<V: MutVisitor>(visitor: &mut V, def: &mut ConstDef) {
+    def.attrs.iter_mut().for_each(|attr| visitor.visit_attribute(attr));
+    visitor.visit_ty(&mut def.ty);
+    visit_expr_id(visitor, def.value);
+}
+
+pub fn walk_use<V: MutVisitor>(visitor: &mut V, decl: &mut UseDecl) {
+    decl.attrs.iter_mut().for_each(|attr| visitor.visit_attribute(attr));
+    visitor.visit_use_tree(&mut decl.tree);
+}
+
+pub fn walk_use_tree<V: MutVisitor>(visitor: &mut V, tree: &mut UseTree) {
+    visitor.visit_path(&mut tree.prefix);
+    if let UseTreeKind::Nested(trees) = &mut tree.kind {
+        trees.iter_mut().for_each(|tree| visitor.visit_use_tree(tree));
+    }
+}
+
+pub fn walk_generics<V: MutVisitor>(visitor: &mut V, generics: &mut Generics) {
+    generics.params.iter_mut().for_each(|param| visitor.visit_generic_param(param));
+}
+
+pub fn walk_generic_param<V: MutVisitor>(visitor: &mut V, param: &mut GenericParam) {
+    param.bounds.iter_mut().for_each(|bound| visitor.visit_path(bound));
+}
+
+pub fn walk_param<V: MutVisitor>(visitor: &mut V, param: &mut Param) {
+    visitor.visit_pat(&mut param.pat);
+    visitor.visit_ty(&mut param.ty);
+}
+
+pub fn walk_block<V: MutVisitor>(visitor: &mut V, block: &mut Block) {
+    for &id in &block.stmts {
+        let mut stmt = visitor.arena().take_stmt(id);
+        visitor.visit_stmt(&mut stmt);
+        visitor.arena()[id] = stmt;
+    }
+}
+
+// 저장소의 `id` 자리 표현식을 방문합니다.
+pub fn visit_expr_id<V: MutVisitor>(visitor: &mut V, id: ExprId) {
+    let mut node = visitor.arena().take_expr(id);
+    visitor.visit_expr(&mut node);
+    visitor.arena()[id] = node;
+}
+
+pub fn walk_stmt<V: MutVisitor>(visitor: &mut V, stmt: &mut Stmt) {
+    match &mut stmt.kind {
+        StmtKind::Let(local) => visitor.visit_local(local),
+        StmtKind::Item(item) => visitor.visit_item(item),
+        StmtKind::Expr(expr) | StmtKind::Semi(expr) => visit_expr_id(visitor, *expr),
+    }
+}
+
+pub fn walk_local<V: MutVisitor>(visitor: &mut V, local: &mut Local) {
+    visitor.visit_pat(&mut local.pat);
+    if let Some(ty) = &mut local.ty {
+        visitor.visit_ty(ty);
+    }
+    if let Some(init) = local.init {
+        visit_expr_id(visitor, init);
+    }
+}
+
+pub fn walk_expr<V: MutVisitor>(visitor: &mut V, node: &mut Node) {
+    match &mut node.kind {
+        NodeKind::Int(..) | NodeKind::Float(..) | NodeKind::Char(_) | NodeKind::Ident(_) | NodeKind::Bool(_) | NodeKind::Str(_) => {},
+        NodeKind::Break | NodeKind::Continue => {},
+        NodeKind::Path(path) => visitor.visit_path(path),
+        NodeKind::QualifiedPath(qself, path) => {
+            visitor.visit_ty(&mut qself.ty);
+            visitor.visit_path(&mut qself.trait_path);
+            visitor.visit_path(path);
+        },
+        NodeKind::BinaryOp(binary_op) => {
+            visit_expr_id(visitor, binary_op.left);
+            visit_expr_id(visitor, binary_op.right);
+        },
+        NodeKind::Unary(unary_op) => visit_expr_id(visitor, unary_op.operand),
+        NodeKind::Ref { expr, .. } => visit_expr_id(visitor, *expr),
+        NodeKind::Call(call) => {
+            visit_expr_id(visitor, call.callee);
+            call.args.iter().for_each(|&arg| visit_expr_id(visitor, arg));
+        },
+        NodeKind::MethodCall(call) => {
+            visit_expr_id(visitor, call.receiver);
+            call.args.iter().for_each(|&arg| visit_expr_id(visitor, arg));
+        },
+        NodeKind::Field(base, _) => visit_expr_id(visitor, *base),
+        NodeKind::StructLit(lit) => {
+            visitor.visit_path(&mut lit.path);
+            lit.fields.iter().for_each(|&(_, value)| visit_expr_id(visitor, value));
+        },
+        NodeKind::Assign(assign) => {
+            visit_expr_id(visitor, assign.target);
+            visit_expr_id(visitor, assign.value);
+        },
+        NodeKind::Block(block) | NodeKind::Loop(block) => visitor.visit_block(block),
+        NodeKind::If(if_expr) => {
+            visit_expr_id(visitor, if_expr.cond);
+            visitor.visit_block(&mut if_expr.then);
+            if let Some(els) = &mut if_expr.els {
+                visit_expr_id(visitor, *els);
+            }
+        },
+        NodeKind::Match(match_expr) => {
+            visit_expr_id(visitor, match_expr.scrutinee);
+            match_expr.arms.iter_mut().for_each(|arm| visitor.visit_arm(arm));
+        },
+        NodeKind::While(while_expr) => {
+            visit_expr_id(visitor, while_expr.cond);
+            visitor.visit_block(&mut while_expr.body);
+        },
+        NodeKind::ForLoop(for_loop) => {
+            visitor.visit_pat(&mut for_loop.pat);
+            visit_expr_id(visitor, for_loop.iter);
+            visitor.visit_block(&mut for_loop.body);
+        },
+        NodeKind::Return(value) => {
+            if let Some(value) = value {
+                visit_expr_id(visitor, *value);
+            }
+        },
+        NodeKind::Closure(closure) => {
+            for param in &mut closure.params {
+                visitor.visit_pat(&mut param.pat);
+                if let Some(ty) = &mut param.ty {
+                    visitor.visit_ty(ty);
+                }
+            }
+            if let Some(ret) = &mut closure.ret {
+                visitor.visit_ty(ret);
+            }
+            visit_expr_id(visitor, closure.body);
+        },
+        NodeKind::Array(elems) | NodeKind::Tuple(elems) => elems.iter().for_each(|&elem| visit_expr_id(visitor, elem)),
+        NodeKind::Repeat(base, other) | NodeKind::Index(base, other) => {
+            visit_expr_id(visitor, *base);
+            visit_expr_id(visitor, *other);
+        },
+        NodeKind::Range(range) => range.start.iter().chain(&range.end).for_each(|&bound| visit_expr_id(visitor, bound)),
+        NodeKind::FormatArgs(format) => format.args.iter().for_each(|arg| visit_expr_id(visitor, arg.expr)),
+    }
+}
+
+pub fn walk_arm<V: MutVisitor>(visitor: &mut V, arm: &mut Arm) {
+    visitor.visit_pat(&mut arm.pat);
+    if let Some(guard) = arm.guard {
+        visit_expr_id(visitor, guard);
+    }
+    visit_expr_id(visitor, arm.body);
+}
+
+pub fn walk_pat<V: MutVisitor>(visitor: &mut V, pat: &mut Pat) {
+    match &mut pat.kind {
+        PatKind::Ident { .. } | PatKind::Wild => {},
+        PatKind::Lit(lit) => visit_expr_id(visitor, *lit),
+        PatKind::Range(start, end) => {
+            visit_expr_id(visitor, *start);
+            visit_expr_id(visitor, *end);
+        },
+        PatKind::Tuple(pats) | PatKind::Or(pats) => pats.iter_mut().for_each(|pat| visitor.visit_pat(pat)),
+        PatKind::Struct { path, fields, .. } => {
+            visitor.visit_path(path);
+            fields.iter_mut().for_each(|(_, pat)| visitor.visit_pat(pat));
+        },
+    }
+}
+
+pub fn walk_ty<V: MutVisitor>(visitor: &mut V, ty: &mut Ty) {
+    match &mut ty.kind {
+        TyKind::Path(path) => visitor.visit_path(path),
+        TyKind::Array(ty, len) => {
+            visitor.visit_ty(ty);
+            if let ArrayLen::Expr(len) = len {
+                visit_expr_id(visitor, *len);
+            }
+        },
+        TyKind::Ref { ty, .. } | TyKind::Slice(ty) => visitor.visit_ty(ty),
+        TyKind::Unit => {},
+        TyKind::Tuple(tys) => tys.iter_mut().for_each(|ty| visitor.visit_ty(ty)),
+    }
+}
+
+// 세그먼트의 제네릭 인자와 클로저 트레이트의 반환 타입을 방문합니다.
+pub fn walk_path<V: MutVisitor>(visitor: &mut V, path: &mut Path) {
+    for segment in &mut path.segments {
+        segment.args.iter_mut().for_each(|arg| visitor.visit_ty(arg));
+        if let Some(output) = &mut segment.output {
+            visitor.visit_ty(output);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::BinaryOp;
+    use rusttc_lexer::{Token, TokenKind};
+
+    // 숫자끼리 더하는 식을 그 결과로 바꿉니다. 자식을 먼저 접으므로 `1 + 2 + 3`도 한 번에 접힙니다.
+    struct FoldAdd(AstArena);
+
+    impl MutVisitor for FoldAdd {
+        fn arena(&mut self) -> &mut AstArena {
+            &mut self.0
+        }
+
+        fn visit_expr(&mut self, node: &mut Node) {
+            walk_expr(self, node);
+            if let NodeKind::BinaryOp(binary_op) = &node.kind {
+                if let (NodeKind::Int(left, None), TokenKind::Plus, NodeKind::Int(right, None)) =
+                    (&self.0[binary_op.left].kind, &binary_op.operator.kind, &self.0[binary_op.right].kind)
+                {
+                    node.kind = NodeKind::Int(left + right, None);
+                }
+            }
+        }
+    }
+
+    // 식별자와 패턴의 이름을 바꿉니다.
+    struct Rename<'a>(&'a mut AstArena, &'static str, &'static str);
+
+    impl MutVisitor for Rename<'_> {
+        fn arena(&mut self) -> &mut AstArena {
+            self.0
+        }
+
+        fn visit_expr(&mut self, node: &mut Node) {
+            match &mut node.kind {
+                NodeKind::Ident(name) if name == self.1 => *name = self.2.to_string(),
+                _ => walk_expr(self, node),
+            }
+        }
+
+        fn visit_pat(&mut self, pat: &mut Pat) {
+            if let PatKind::Ident { name, .. } = &mut pat.kind {
+                if name == self.1 {
+                    *name = self.2.to_string();
+                }
+            }
+        }
+    }
+
+    fn add(arena: &mut AstArena, left: ExprId, right: ExprId) -> ExprId {
+        let operator = Token::new(TokenKind::Plus, "+".to_string());
+        arena.alloc_expr(Node::from(NodeKind::BinaryOp(BinaryOp { left, operator, right })))
+    }
+
+    fn number(arena: &mut AstArena, value: u128) -> ExprId {
+        arena.alloc_expr(Node::from(NodeKind::Int(value, None)))
+    }
+
+    fn ident(arena: &mut AstArena, name: &str) -> ExprId {
+        arena.alloc_expr(Node::from(NodeKind::Ident(name.to_string())))
+    }
+
+    #[test]
+    fn test_mut_visit_replaces_nodes() {
+        let mut arena = AstArena::default();
+        let (one, two, three) = (number(&mut arena, 1), number(&mut arena, 2), number(&mut arena, 3));
+        let inner = add(&mut arena, one, two);
+        let sum = add(&mut arena, inner, three);
+        let array = arena.alloc_expr(Node::from(NodeKind::Array(vec![sum])));
+
+        let mut fold = FoldAdd(arena);
+        visit_expr_id(&mut fold, array);
+        assert_eq!(fold.0[sum].kind, NodeKind::Int(6, None));
+        assert_eq!(fold.0[array].kind, NodeKind::Array(vec![sum]));
+    }
+
+    #[test]
+    fn test_mut_visit_renames_bindings() {
+        let mut arena = AstArena::default();
+        let init = Some(number(&mut arena, 1));
+        let local = arena.alloc_stmt(Stmt::from(StmtKind::Let(Local {
+            pat: Pat::from(PatKind::Ident { name: "x".to_string(), mutable: false }),
+            ty: None,
+            init,
+        })));
+        let (left, right) = (ident(&mut arena, "x"), ident(&mut arena, "x"));
+        let sum = add(&mut arena, left, right);
+        let mut block = Block { stmts: vec![local, arena.alloc_stmt(Stmt::from(StmtKind::Expr(sum)))] };
+        Rename(&mut arena, "x", "y").visit_block(&mut block);
+
+        let StmtKind::Let(local) = &arena[block.stmts[0]].kind else { panic!("expected let") };
+        assert_eq!(local.pat.kind, PatKind::Ident { name: "y".to_string(), mutable: false });
+        assert_eq!(arena[left].kind, NodeKind::Ident("y".to_string()));
+        assert_eq!(arena[right].kind, NodeKind::Ident("y".to_string()));
+    }
+}
@@ -0,0 +1,973 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::{
+    binary_precedence, is_comparison, ArrayLen, AstArena, AttrStyle, Attribute, Block, CaptureBy, ConstDef, Crate, ExprId, FloatTy,
+    FieldDef, FnDef, FormatArgKind, FormatArgs, GenericParam, Generics, Impl, IntTy, Item, Lit, MetaItem, MetaItemKind, ModDef, NestedMeta,
+    Node, NodeKind, Pat, PatKind, Path, Range, SelfKind, Stmt, StmtId, StmtKind, StructDef, TraitDef, Ty, TyKind, UseTree, UseTreeKind,
+    Visibility,
+};
+use crate::mut_visit::{self, MutVisitor};
+
+// 표현식이 결합하는 세기입니다. 피연산자의 세기가 그 자리에서 요구하는 것보다 약하면 괄호로 감쌉니다.
+const PREC_JUMP: u8 = 0;        // 클로저, return (뒤의 표현식을 모두 가져갑니다)
+const PREC_ASSIGN: u8 = 1;      // =, +=
+const PREC_RANGE: u8 = 2;       // a..b
+const PREC_BINARY: u8 = 10;     // 이항 연산자는 10 + 연산자 우선순위
+const PREC_PREFIX: u8 = 30;     // -x, !x, *x, &x
+const PREC_POSTFIX: u8 = 40;    // f(x), x.f, x.f(), x[i]
+const PREC_PRIMARY: u8 = 50;
+
+const INDENT: &str = "    ";
+
+// 크레이트를 Rustt 소스로 출력합니다. 출력을 다시 파싱하면 같은 트리가 나옵니다.
+pub fn crate_to_string(krate: &Crate) -> String {
+    let mut printer = Printer::new(&krate.arena);
+    printer.print_attrs(&krate.attrs, AttrStyle::Inner);
+    if !krate.attrs.is_empty() && !krate.items.is_empty() {
+        printer.out.push('\n');
+    }
+    printer.print_items(&krate.items);
+    if !printer.out.is_empty() {
+        printer.out.push('\n');
+    }
+    printer.out
+}
+
+// `--pretty=expanded`의 출력입니다. 매크로 전개가 붙인 구문 문맥(`x#1`)은 소스에 쓸 수 없으므로
+// 크레이트의 어떤 이름과도 겹치지 않는 보통 이름(`x_1`, 이미 있으면 `x_1_`)으로 바꿔 씁니다.
+// 같은 문맥의 이름은 모두 같은 이름이 되므로 출력을 다시 컴파일해도 같은 변수를 가리킵니다.
+pub fn expanded_crate_to_string(krate: &Crate) -> String {
+    let mut krate = krate.clone();
+    let mut taken = HashSet::new();
+    let mut collector = Names { arena: std::mem::take(&mut krate.arena), f: |name: &mut String| {
+        taken.insert(name.clone());
+    } };
+    collector.visit_crate(&mut krate);
+    krate.arena = collector.arena;
+
+    let mut renamed: HashMap<String, String> = HashMap::new();
+    let mut renamer = Names { arena: std::mem::take(&mut krate.arena), f: |name: &mut String| {
+        let Some((base, ctx)) = name.split_once('#') else { return };
+        let new_name = renamed.entry(name.clone()).or_insert_with(|| {
+            let mut candidate = format!("{}_{}", base, ctx);
+            while taken.contains(&candidate) {
+                candidate.push('_');
+            }
+            taken.insert(candidate.clone());
+            candidate
+        });
+        *name = new_name.clone();
+    } };
+    renamer.visit_crate(&mut krate);
+    krate.arena = renamer.arena;
+    crate_to_string(&krate)
+}
+
+// 크레이트 안의 이름(아이템, 변수, 경로 세그먼트, 필드, 메서드)마다 `f`를 부르는 방문자입니다.
+struct Names<F: FnMut(&mut String)> {
+    arena: AstArena,
+    f: F,
+}
+
+impl<F: FnMut(&mut String)> MutVisitor for Names<F> {
+    fn arena(&mut self) -> &mut AstArena {
+        &mut self.arena
+    }
+
+    fn visit_fn(&mut self, function: &mut FnDef) {
+        (self.f)(&mut function.name);
+        mut_visit::walk_fn(self, function);
+    }
+
+    fn visit_struct(&mut self, def: &mut StructDef) {
+        (self.f)(&mut def.name);
+        mut_visit::walk_struct(self, def);
+    }
+
+    fn visit_field_def(&mut self, field: &mut FieldDef) {
+        (self.f)(&mut field.name);
+        mut_visit::walk_field_def(self, field);
+    }
+
+    fn visit_trait(&mut self, def: &mut TraitDef) {
+        (self.f)(&mut def.name);
+        mut_visit::walk_trait(self, def);
+    }
+
+    fn visit_mod(&mut self, def: &mut ModDef) {
+        (self.f)(&mut def.name);
+        mut_visit::walk_mod(self, def);
+    }
+
+    fn visit_const(&mut self, def: &mut ConstDef) {
+        (self.f)(&mut def.name);
+        mut_visit::walk_const(self, def);
+    }
+
+    fn visit_generic_param(&mut self, param: &mut GenericParam) {
+        (self.f)(&mut param.name);
+        mut_visit::walk_generic_param(self, param);
+    }
+
+    fn visit_expr(&mut self, node: &mut Node) {
+        match &mut node.kind {
+            NodeKind::Ident(name) | NodeKind::Field(_, name) => (self.f)(name),
+            NodeKind::MethodCall(call) => (self.f)(&mut call.method),
+            NodeKind::StructLit(lit) => lit.fields.iter_mut().for_each(|(name, _)| (self.f)(name)),
+            _ => {},
+        }
+        mut_visit::walk_expr(self, node);
+    }
+
+    fn visit_pat(&mut self, pat: &mut Pat) {
+        match &mut pat.kind {
+            PatKind::Ident { name, .. } => (self.f)(name),
+            PatKind::Struct { fields, .. } => fields.iter_mut().for_each(|(name, _)| (self.f)(name)),
+            _ => {},
+        }
+        mut_visit::walk_pat(self, pat);
+    }
+
+    fn visit_path(&mut self, path: &mut Path) {
+        path.segments.iter_mut().for_each(|segment| (self.f)(&mut segment.ident));
+        mut_visit::walk_path(self, path);
+    }
+}
+
+pub fn item_to_string(arena: &AstArena, item: &Item) -> String {
+    let mut printer = Printer::new(arena);
+    printer.print_item(item);
+    printer.out
+}
+
+pub fn stmt_to_string(arena: &AstArena, stmt: StmtId) -> String {
+    let mut printer = Printer::new(arena);
+    printer.print_stmt(&arena[stmt]);
+    printer.out
+}
+
+pub fn expr_to_string(arena: &AstArena, expr: ExprId) -> String {
+    let mut printer = Printer::new(arena);
+    printer.print_expr(&arena[expr]);
+    printer.out
+}
+
+struct Printer<'a> {
+    arena: &'a AstArena,
+    out: String,
+    indent: usize,
+    no_struct_literal: bool,    // if, while, for의 조건처럼 구조체 리터럴을 괄호로 감싸야 하는 위치인지
+}
+
+impl<'a> Printer<'a> {
+    fn new(arena: &'a AstArena) -> Self {
+        Printer { arena, out: String::new(), indent: 0, no_struct_literal: false }
+    }
+
+    fn expr(&self, id: ExprId) -> &'a Node {
+        &self.arena[id]
+    }
+
+    fn word(&mut self, text: &str) {
+        self.out.push_str(text);
+    }
+
+    fn newline(&mut self) {
+        self.out.push('\n');
+        for _ in 0..self.indent {
+            self.out.push_str(INDENT);
+        }
+    }
+
+    // `items`를 `, `로 이어 출력합니다.
+    fn comma_sep<T>(&mut self, items: &[T], mut print: impl FnMut(&mut Self, &T)) {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.word(", ");
+            }
+            print(self, item);
+        }
+    }
+
+    fn print_attrs(&mut self, attrs: &[Attribute], style: AttrStyle) {
+        for attr in attrs.iter().filter(|attr| attr.style == style) {
+            self.word(if style == AttrStyle::Inner { "#![" } else { "#[" });
+            self.print_meta(&attr.meta);
+            self.word("]");
+            self.newline();
+        }
+    }
+
+    fn print_meta(&mut self, meta: &MetaItem) {
+        self.print_path(&meta.path, true);
+        match &meta.kind {
+            MetaItemKind::Word => {},
+            MetaItemKind::List(list) => {
+                self.word("(");
+                self.comma_sep(list, |printer, nested| match nested {
+                    NestedMeta::Meta(meta) => printer.print_meta(meta),
+                    NestedMeta::Lit(lit) => printer.print_lit(lit),
+                });
+                self.word(")");
+            },
+            MetaItemKind::NameValue(lit) => {
+                self.word(" = ");
+                self.print_lit(lit);
+            },
+        }
+    }
+
+    fn print_lit(&mut self, lit: &Lit) {
+        match lit {
+            Lit::Str(text) => self.out.push_str(&format!("\"{}\"", text)),
+            Lit::Int(value) => self.out.push_str(&value.to_string()),
+            Lit::Bool(value) => self.out.push_str(&value.to_string()),
+        }
+    }
+
+    fn print_vis(&mut self, vis: Visibility) {
+        if vis == Visibility::Public {
+            self.word("pub ");
+        }
+    }
+
+    // 아이템 사이에는 빈 줄을 둡니다. 이어지는 `use`끼리는 붙여 씁니다.
+    fn print_items(&mut self, items: &[Item]) {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                if !matches!((&items[i - 1], item), (Item::Use(_), Item::Use(_))) {
+                    self.out.push('\n');
+                }
+                self.newline();
+            }
+            self.print_item(item);
+        }
+    }
+
+    fn print_item(&mut self, item: &Item) {
+        self.print_attrs(item.attrs(), AttrStyle::Outer);
+        match item {
+            Item::Fn(function) => self.print_fn(function),
+            Item::Struct(def) => self.print_struct(def),
+            Item::Impl(imp) => self.print_impl(imp),
+            Item::Trait(def) => self.print_trait(def),
+            Item::Mod(def) => self.print_mod(def),
+            Item::Use(decl) => {
+                self.print_vis(decl.vis);
+                self.word("use ");
+                self.print_use_tree(&decl.tree);
+                self.word(";");
+            },
+            Item::Const(def) => self.print_const("const", def),
+            Item::Static(def) => self.print_const("static", def),
+        }
+    }
+
+    fn print_fn(&mut self, function: &FnDef) {
+        self.print_vis(function.vis);
+        self.word("fn ");
+        self.word(&function.name);
+        self.print_generics(&function.generics);
+        self.word("(");
+        let receiver = function.self_param.map(|kind| match kind {
+            SelfKind::Value { mutable: false } => "self",
+            SelfKind::Value { mutable: true } => "mut self",
+            SelfKind::Ref => "&self",
+            SelfKind::RefMut => "&mut self",
+        });
+        if let Some(receiver) = receiver {
+            self.word(receiver);
+            if !function.params.is_empty() {
+                self.word(", ");
+            }
+        }
+        self.comma_sep(&function.params, |printer, param| {
+            printer.print_pat(&param.pat);
+            printer.word(": ");
+            printer.print_ty(&param.ty);
+        });
+        self.word(")");
+        if let Some(ret) = &function.ret {
+            self.word(" -> ");
+            self.print_ty(ret);
+        }
+        match &function.body {
+            Some(body) => {
+                self.word(" ");
+                self.print_block(body);
+            },
+            None => self.word(";"),
+        }
+    }
+
+    fn print_generics(&mut self, generics: &Generics) {
+        if generics.params.is_empty() {
+            return;
+        }
+        self.word("<");
+        self.comma_sep(&generics.params, |printer, param| {
+            printer.word(&param.name);
+            for (i, bound) in param.bounds.iter().enumerate() {
+                printer.word(if i == 0 { ": " } else { " + " });
+                printer.print_path(bound, false);
+            }
+        });
+        self.word(">");
+    }
+
+    // 필드가 없는 구조체는 `struct Name;`으로 씁니다.
+    fn print_const(&mut self, keyword: &str, def: &ConstDef) {
+        self.print_vis(def.vis);
+        self.word(&format!("{} {}: ", keyword, def.name));
+        self.print_ty(&def.ty);
+        self.word(" = ");
+        self.print_expr(self.expr(def.value));
+        self.word(";");
+    }
+
+    fn print_struct(&mut self, def: &StructDef) {
+        self.print_vis(def.vis);
+        self.word("struct ");
+        self.word(&def.name);
+        self.print_generics(&def.generics);
+        if def.fields.is_empty() {
+            self.word(";");
+            return;
+        }
+        self.word(" {");
+        self.indent += 1;
+        for field in &def.fields {
+            self.newline();
+            self.print_vis(field.vis);
+            self.out.push_str(&format!("{}: ", field.name));
+            self.print_ty(&field.ty);
+            self.word(",");
+        }
+        self.indent -= 1;
+        self.newline();
+        self.word("}");
+    }
+
+    fn print_impl(&mut self, imp: &Impl) {
+        self.word("impl");
+        self.print_generics(&imp.generics);
+        self.word(" ");
+        if let Some(of_trait) = &imp.of_trait {
+            self.print_path(of_trait, false);
+            self.word(" for ");
+        }
+        self.print_ty(&imp.self_ty);
+        self.word(" ");
+        self.print_fn_list(&imp.items);
+    }
+
+    fn print_trait(&mut self, def: &TraitDef) {
+        self.print_vis(def.vis);
+        self.word("trait ");
+        self.word(&def.name);
+        self.word(" ");
+        self.print_fn_list(&def.items);
+    }
+
+    fn print_fn_list(&mut self, functions: &[FnDef]) {
+        if functions.is_empty() {
+            self.word("{}");
+            return;
+        }
+        self.word("{");
+        self.indent += 1;
+        for (i, function) in functions.iter().enumerate() {
+            if i > 0 {
+                self.out.push('\n');
+            }
+            self.newline();
+            self.print_attrs(&function.attrs, AttrStyle::Outer);
+            self.print_fn(function);
+        }
+        self.indent -= 1;
+        self.newline();
+        self.word("}");
+    }
+
+    // 안쪽 속성은 모듈 본문 맨 앞에 씁니다.
+    fn print_mod(&mut self, def: &ModDef) {
+        self.print_vis(def.vis);
+        self.word("mod ");
+        self.word(&def.name);
+        let Some(items) = &def.items else {
+            self.word(";");
+            return;
+        };
+        let inner = def.attrs.iter().any(|attr| attr.style == AttrStyle::Inner);
+        if items.is_empty() && !inner {
+            self.word(" {}");
+            return;
+        }
+        self.word(" {");
+        self.indent += 1;
+        self.newline();
+        self.print_attrs(&def.attrs, AttrStyle::Inner);
+        self.print_items(items);
+        self.indent -= 1;
+        if items.is_empty() {
+            // 마지막 속성 뒤의 줄바꿈은 이미 들어가 있습니다.
+            self.out.truncate(self.out.trim_end().len());
+        }
+        self.newline();
+        self.word("}");
+    }
+
+    fn print_use_tree(&mut self, tree: &UseTree) {
+        self.print_path(&tree.prefix, true);
+        let sep = if tree.prefix.segments.is_empty() { "" } else { "::" };
+        match &tree.kind {
+            UseTreeKind::Simple(None) => {},
+            UseTreeKind::Simple(Some(rename)) => self.out.push_str(&format!(" as {}", rename)),
+            UseTreeKind::Glob => self.out.push_str(&format!("{}*", sep)),
+            UseTreeKind::Nested(trees) => {
+                self.out.push_str(&format!("{}{{", sep));
+                self.comma_sep(trees, Printer::print_use_tree);
+                self.word("}");
+            },
+        }
+    }
+
+    // 표현식 위치의 경로는 제네릭 인자를 터보피시 `max::<i32>`로 씁니다.
+    fn print_path(&mut self, path: &Path, expr_style: bool) {
+        for (i, segment) in path.segments.iter().enumerate() {
+            if i > 0 {
+                self.word("::");
+            }
+            self.word(&segment.ident);
+            if segment.is_fn_trait() && !expr_style {
+                self.word("(");
+                self.comma_sep(&segment.args, |printer, arg| printer.print_ty(arg));
+                self.word(")");
+                if let Some(output) = &segment.output {
+                    self.word(" -> ");
+                    self.print_ty(output);
+                }
+            } else if !segment.args.is_empty() {
+                self.word(if expr_style { "::<" } else { "<" });
+                self.comma_sep(&segment.args, |printer, arg| printer.print_ty(arg));
+                self.word(">");
+            }
+        }
+    }
+
+    // 타입은 `Ty`의 `Display`와 같은 모양이지만, 배열 길이 식(`[T; N]`)은 저장소에서 찾아 씁니다.
+    fn print_ty(&mut self, ty: &Ty) {
+        match &ty.kind {
+            TyKind::Path(path) => self.print_path(path, false),
+            TyKind::Ref { mutable, ty } => {
+                self.word(if *mutable { "&mut " } else { "&" });
+                self.print_ty(ty);
+            },
+            TyKind::Unit => self.word("()"),
+            TyKind::Array(elem, len) => {
+                self.word("[");
+                self.print_ty(elem);
+                self.word("; ");
+                match len {
+                    ArrayLen::Known(len) => self.word(&len.to_string()),
+                    ArrayLen::Expr(len) => self.print_expr(self.expr(*len)),
+                }
+                self.word("]");
+            },
+            TyKind::Slice(elem) => {
+                self.word("[");
+                self.print_ty(elem);
+                self.word("]");
+            },
+            TyKind::Tuple(tys) => {
+                self.word("(");
+                self.comma_sep(tys, |printer, ty| printer.print_ty(ty));
+                if tys.len() == 1 {
+                    self.word(",");
+                }
+                self.word(")");
+            },
+        }
+    }
+
+    fn print_pat(&mut self, pat: &Pat) {
+        match &pat.kind {
+            PatKind::Ident { name, mutable } => {
+                if *mutable {
+                    self.word("mut ");
+                }
+                self.word(name);
+            },
+            PatKind::Wild => self.word("_"),
+            PatKind::Lit(lit) => self.print_expr(self.expr(*lit)),
+            PatKind::Range(start, end) => {
+                self.print_expr(self.expr(*start));
+                self.word("..=");
+                self.print_expr(self.expr(*end));
+            },
+            PatKind::Tuple(pats) => {
+                self.word("(");
+                self.comma_sep(pats, |printer, pat| printer.print_pat(pat));
+                if pats.len() == 1 {
+                    self.word(",");
+                }
+                self.word(")");
+            },
+            PatKind::Struct { path, fields, rest } => {
+                self.print_path(path, true);
+                if fields.is_empty() && !rest {
+                    self.word(" {}");
+                    return;
+                }
+                self.word(" { ");
+                self.comma_sep(fields, |printer, (name, pat)| {
+                    printer.word(name);
+                    // 같은 이름의 변수로 바인딩하면 줄여 씁니다.
+                    if !matches!(&pat.kind, PatKind::Ident { name: ident, mutable: false } if ident == name) {
+                        printer.word(": ");
+                        printer.print_pat(pat);
+                    }
+                });
+                if *rest {
+                    self.word(if fields.is_empty() { ".." } else { ", .." });
+                }
+                self.word(" }");
+            },
+            PatKind::Or(pats) => {
+                for (i, pat) in pats.iter().enumerate() {
+                    if i > 0 {
+                        self.word(" | ");
+                    }
+                    self.print_pat(pat);
+                }
+            },
+        }
+    }
+
+    fn print_block(&mut self, block: &Block) {
+        if block.stmts.is_empty() {
+            self.word("{}");
+            return;
+        }
+        let old = std::mem::replace(&mut self.no_struct_literal, false);
+        self.word("{");
+        self.indent += 1;
+        for &stmt in &block.stmts {
+            self.newline();
+            self.print_stmt(&self.arena[stmt]);
+        }
+        self.indent -= 1;
+        self.newline();
+        self.word("}");
+        self.no_struct_literal = old;
+    }
+
+    fn print_stmt(&mut self, stmt: &Stmt) {
+        match &stmt.kind {
+            StmtKind::Let(local) => {
+                self.word("let ");
+                self.print_pat(&local.pat);
+                if let Some(ty) = &local.ty {
+                    self.word(": ");
+                    self.print_ty(ty);
+                }
+                if let Some(init) = local.init {
+                    self.word(" = ");
+                    self.print_expr(self.expr(init));
+                }
+                self.word(";");
+            },
+            StmtKind::Item(item) => self.print_item(item),
+            StmtKind::Expr(expr) => self.print_stmt_expr(self.expr(*expr)),
+            StmtKind::Semi(expr) => {
+                self.print_stmt_expr(self.expr(*expr));
+                self.word(";");
+            },
+        }
+    }
+
+    // 문장 맨 앞의 블록 형태 표현식은 그 자리에서 문장이 끝나므로,
+    // `{ a } + 1`처럼 블록으로 시작하는 더 큰 표현식은 괄호로 감쌉니다.
+    fn print_stmt_expr(&mut self, node: &Node) {
+        if !is_block_like(node) && starts_with_block_like(self.arena, node) {
+            self.print_parenthesized(node);
+        } else {
+            self.print_expr(node);
+        }
+    }
+
+    fn print_expr(&mut self, node: &Node) {
+        self.print_expr_prec(node, PREC_JUMP);
+    }
+
+    // 결합 세기가 `min_prec`보다 약한 표현식은 괄호로 감쌉니다.
+    fn print_expr_prec(&mut self, node: &Node, min_prec: u8) {
+        if precedence(node) < min_prec {
+            self.print_parenthesized(node);
+        } else {
+            self.print_expr_inner(node);
+        }
+    }
+
+    fn print_parenthesized(&mut self, node: &Node) {
+        let old = std::mem::replace(&mut self.no_struct_literal, false);
+        self.word("(");
+        self.print_expr_inner(node);
+        self.word(")");
+        self.no_struct_literal = old;
+    }
+
+    // 괄호, 대괄호 안의 표현식에는 구조체 리터럴 제한이 없습니다.
+    fn print_nested(&mut self, node: &Node) {
+        let old = std::mem::replace(&mut self.no_struct_literal, false);
+        self.print_expr(node);
+        self.no_struct_literal = old;
+    }
+
+    fn print_cond(&mut self, node: &Node) {
+        let old = std::mem::replace(&mut self.no_struct_literal, true);
+        self.print_expr(node);
+        self.no_struct_literal = old;
+    }
+
+    fn print_args(&mut self, args: &[ExprId]) {
+        self.word("(");
+        self.comma_sep(args, |printer, &arg| printer.print_nested(printer.expr(arg)));
+        self.word(")");
+    }
+
+    fn print_expr_inner(&mut self, node: &Node) {
+        match &node.kind {
+            NodeKind::Int(value, suffix) => self.word(&format!("{}{}", value, suffix.map_or("", IntTy::name))),
+            NodeKind::Float(value, suffix) => self.word(&format!("{:?}{}", value, suffix.map_or("", FloatTy::name))),
+            NodeKind::Char(value) => self.word(&format!("{:?}", value)),
+            NodeKind::Ident(name) => self.word(name),
+            NodeKind::Bool(value) => self.word(&value.to_string()),
+            NodeKind::Str(text) => self.out.push_str(&format!("\"{}\"", text)),
+            NodeKind::Break => self.word("break"),
+            NodeKind::Continue => self.word("continue"),
+            NodeKind::Path(path) => self.print_path(path, true),
+            NodeKind::QualifiedPath(qself, path) => {
+                self.word("<");
+                self.print_ty(&qself.ty);
+                self.word(" as ");
+                self.print_path(&qself.trait_path, false);
+                self.word(">::");
+                self.print_path(path, true);
+            },
+            NodeKind::BinaryOp(binary_op) => {
+                let prec = PREC_BINARY + binary_precedence(&binary_op.operator.kind).unwrap_or(0);
+                // 비교 연산자는 결합하지 않으므로 왼쪽에도 괄호가 필요합니다.
+                let left_prec = if is_comparison(&binary_op.operator.kind) { prec + 1 } else { prec };
+                self.print_expr_prec(self.expr(binary_op.left), left_prec);
+                self.out.push_str(&format!(" {} ", binary_op.operator.text));
+                self.print_expr_prec(self.expr(binary_op.right), prec + 1);
+            },
+            NodeKind::Unary(unary_op) => {
+                self.word(&unary_op.operator.text);
+                self.print_expr_prec(self.expr(unary_op.operand), PREC_PREFIX);
+            },
+            NodeKind::Ref { mutable, expr } => {
+                self.word(if *mutable { "&mut " } else { "&" });
+                self.print_expr_prec(self.expr(*expr), PREC_PREFIX);
+            },
+            NodeKind::Call(call) => {
+                // `(s.f)(x)`의 괄호를 빼면 메서드 호출이 됩니다.
+                if let NodeKind::Field(..) = self.expr(call.callee).kind {
+                    self.print_parenthesized(self.expr(call.callee));
+                } else {
+                    self.print_expr_prec(self.expr(call.callee), PREC_POSTFIX);
+                }
+                self.print_args(&call.args);
+            },
+            NodeKind::MethodCall(call) => {
+                self.print_expr_prec(self.expr(call.receiver), PREC_POSTFIX);
+                self.word(".");
+                self.word(&call.method);
+                self.print_args(&call.args);
+            },
+            NodeKind::Field(base, field) => {
+                self.print_expr_prec(self.expr(*base), PREC_POSTFIX);
+                self.word(".");
+                self.word(field);
+            },
+            NodeKind::Index(base, index) => {
+                self.print_expr_prec(self.expr(*base), PREC_POSTFIX);
+                self.word("[");
+                self.print_nested(self.expr(*index));
+                self.word("]");
+            },
+            NodeKind::StructLit(lit) => {
+                if self.no_struct_literal {
+                    return self.print_parenthesized(node);
+                }
+                self.print_path(&lit.path, true);
+                if lit.fields.is_empty() {
+                    self.word(" {}");
+                    return;
+                }
+                self.word(" { ");
+                self.comma_sep(&lit.fields, |printer, (name, value)| {
+                    printer.word(name);
+                    // 변수 이름이 필드 이름과 같으면 줄여 씁니다.
+                    let value = printer.expr(*value);
+                    if !matches!(&value.kind, NodeKind::Ident(ident) if ident == name) {
+                        printer.word(": ");
+                        printer.print_nested(value);
+                    }
+                });
+                self.word(" }");
+            },
+            NodeKind::Assign(assign) => {
+                self.print_expr_prec(self.expr(assign.target), PREC_RANGE);
+                let operator = assign.operator.as_ref().map_or("=", |operator| operator.text.as_str());
+                self.out.push_str(&format!(" {} ", operator));
+                self.print_expr(self.expr(assign.value));
+            },
+            NodeKind::Block(block) => self.print_block(block),
+            NodeKind::If(if_expr) => {
+                self.word("if ");
+                self.print_cond(self.expr(if_expr.cond));
+                self.word(" ");
+                self.print_block(&if_expr.then);
+                if let Some(els) = if_expr.els {
+                    self.word(" else ");
+                    self.print_expr(self.expr(els));
+                }
+            },
+            NodeKind::Match(match_expr) => {
+                self.word("match ");
+                self.print_cond(self.expr(match_expr.scrutinee));
+                if match_expr.arms.is_empty() {
+                    self.word(" {}");
+                    return;
+                }
+                let old = std::mem::replace(&mut self.no_struct_literal, false);
+                self.word(" {");
+                self.indent += 1;
+                for arm in &match_expr.arms {
+                    self.newline();
+                    self.print_pat(&arm.pat);
+                    if let Some(guard) = arm.guard {
+                        self.word(" if ");
+                        self.print_expr(self.expr(guard));
+                    }
+                    self.word(" => ");
+                    let body = self.expr(arm.body);
+                    self.print_expr(body);
+                    // 블록 본문 뒤의 쉼표는 생략합니다.
+                    if !matches!(body.kind, NodeKind::Block(_)) {
+                        self.word(",");
+                    }
+                }
+                self.indent -= 1;
+                self.newline();
+                self.word("}");
+                self.no_struct_literal = old;
+            },
+            NodeKind::While(while_expr) => {
+                self.word("while ");
+                self.print_cond(self.expr(while_expr.cond));
+                self.word(" ");
+                self.print_block(&while_expr.body);
+            },
+            NodeKind::ForLoop(for_loop) => {
+                self.word("for ");
+                self.print_pat(&for_loop.pat);
+                self.word(" in ");
+                self.print_cond(self.expr(for_loop.iter));
+                self.word(" ");
+                self.print_block(&for_loop.body);
+            },
+            NodeKind::Loop(body) => {
+                self.word("loop ");
+                self.print_block(body);
+            },
+            NodeKind::Return(value) => {
+                self.word("return");
+                if let Some(value) = value {
+                    self.word(" ");
+                    self.print_expr(self.expr(*value));
+                }
+            },
+            NodeKind::Closure(closure) => {
+                if closure.capture_by == CaptureBy::Value {
+                    self.word("move ");
+                }
+                self.word("|");
+                self.comma_sep(&closure.params, |printer, param| {
+                    printer.print_pat(&param.pat);
+                    if let Some(ty) = &param.ty {
+                        printer.word(": ");
+                        printer.print_ty(ty);
+                    }
+                });
+                self.word("| ");
+                if let Some(ret) = &closure.ret {
+                    self.word("-> ");
+                    self.print_ty(ret);
+                    self.word(" ");
+                }
+                self.print_expr(self.expr(closure.body));
+            },
+            NodeKind::Array(elems) => {
+                self.word("[");
+                self.comma_sep(elems, |printer, &elem| printer.print_nested(printer.expr(elem)));
+                self.word("]");
+            },
+            NodeKind::Repeat(value, count) => {
+                self.word("[");
+                self.print_nested(self.expr(*value));
+                self.word("; ");
+                self.print_nested(self.expr(*count));
+                self.word("]");
+            },
+            NodeKind::Tuple(elems) => {
+                self.word("(");
+                self.comma_sep(elems, |printer, &elem| printer.print_nested(printer.expr(elem)));
+                if elems.len() == 1 {
+                    self.word(",");
+                }
+                self.word(")");
+            },
+            NodeKind::Range(range) => {
+                if let Some(start) = range.start {
+                    self.print_expr_prec(self.expr(start), PREC_BINARY + 1);
+                }
+                self.word(if range.inclusive { "..=" } else { ".." });
+                if let Some(end) = range.end {
+                    self.print_expr_prec(self.expr(end), PREC_BINARY + 1);
+                }
+            },
+            NodeKind::FormatArgs(format) => self.print_format_args(format),
+        }
+    }
+
+    // `format_args_nl!`은 끝의 `\n`이 포맷 문자열에 들어 있으므로 `format_args!`로 씁니다.
+    // 문자열 안에서 잡아 온 변수는 포맷 문자열에 이미 적혀 있습니다.
+    fn print_format_args(&mut self, format: &FormatArgs) {
+        self.out.push_str(&format!("format_args!(\"{}\"", format.template));
+        let old = std::mem::replace(&mut self.no_struct_literal, false);
+        for arg in &format.args {
+            match &arg.kind {
+                FormatArgKind::Normal => self.word(", "),
+                FormatArgKind::Named(name) => self.out.push_str(&format!(", {} = ", name)),
+                FormatArgKind::Captured(_) => continue,
+            }
+            self.print_expr(self.expr(arg.expr));
+        }
+        self.no_struct_literal = old;
+        self.word(")");
+    }
+}
+
+fn precedence(node: &Node) -> u8 {
+    match &node.kind {
+        NodeKind::Closure(_) | NodeKind::Return(_) => PREC_JUMP,
+        NodeKind::Assign(_) => PREC_ASSIGN,
+        NodeKind::Range(_) => PREC_RANGE,
+        NodeKind::BinaryOp(binary_op) => PREC_BINARY + binary_precedence(&binary_op.operator.kind).unwrap_or(0),
+        NodeKind::Unary(_) | NodeKind::Ref { .. } => PREC_PREFIX,
+        NodeKind::Call(_) | NodeKind::MethodCall(_) | NodeKind::Field(..) | NodeKind::Index(..) => PREC_POSTFIX,
+        _ => PREC_PRIMARY,
+    }
+}
+
+fn is_block_like(node: &Node) -> bool {
+    matches!(
+        node.kind,
+        NodeKind::Block(_) | NodeKind::If(_) | NodeKind::Match(_) | NodeKind::While(_) | NodeKind::ForLoop(_) | NodeKind::Loop(_)
+    )
+}
+
+// 표현식을 출력했을 때 맨 앞에 오는 하위 표현식이 블록 형태인지 확인합니다.
+// 맨 앞 자식을 따라 내려가기만 하므로 재귀 없이 반복합니다.
+fn starts_with_block_like<'a>(arena: &'a AstArena, mut node: &'a Node) -> bool {
+    loop {
+        let first = match &node.kind {
+            _ if is_block_like(node) => return true,
+            NodeKind::BinaryOp(binary_op) => binary_op.left,
+            NodeKind::Call(call) => call.callee,
+            NodeKind::MethodCall(call) => call.receiver,
+            NodeKind::Field(base, _) | NodeKind::Index(base, _) => *base,
+            NodeKind::Assign(assign) => assign.target,
+            NodeKind::Range(Range { start: Some(start), .. }) => *start,
+            _ => return false,
+        };
+        node = &arena[first];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Assign, BinaryOp, Call, StructLit, UnaryOp};
+    use rusttc_lexer::{Token, TokenKind};
+
+    fn ident(arena: &mut AstArena, name: &str) -> ExprId {
+        arena.alloc_expr(Node::from(NodeKind::Ident(name.to_string())))
+    }
+
+    fn binary(arena: &mut AstArena, left: ExprId, kind: TokenKind, text: &str, right: ExprId) -> ExprId {
+        let operator = Token::new(kind, text.to_string());
+        arena.alloc_expr(Node::from(NodeKind::BinaryOp(BinaryOp { left, operator, right })))
+    }
+
+    fn sum(arena: &mut AstArena) -> ExprId {
+        let (a, b) = (ident(arena, "a"), ident(arena, "b"));
+        binary(arena, a, TokenKind::Plus, "+", b)
+    }
+
+    #[test]
+    fn test_print_minimal_parentheses() {
+        let arena = &mut AstArena::default();
+        let (left, c) = (sum(arena), ident(arena, "c"));
+        let product = binary(arena, left, TokenKind::Star, "*", c);
+        assert_eq!(expr_to_string(arena, product), "(a + b) * c");
+        // 왼쪽 결합이므로 오른쪽의 같은 우선순위만 괄호가 필요합니다.
+        let (left, c) = (sum(arena), ident(arena, "c"));
+        let left = binary(arena, left, TokenKind::Minus, "-", c);
+        assert_eq!(expr_to_string(arena, left), "a + b - c");
+        let (c, right) = (ident(arena, "c"), sum(arena));
+        let right = binary(arena, c, TokenKind::Minus, "-", right);
+        assert_eq!(expr_to_string(arena, right), "c - (a + b)");
+
+        let (a, b) = (ident(arena, "a"), ident(arena, "b"));
+        let less = binary(arena, a, TokenKind::Lt, "<", b);
+        let yes = arena.alloc_expr(Node::from(NodeKind::Bool(true)));
+        let compare = binary(arena, less, TokenKind::EqEq, "==", yes);
+        assert_eq!(expr_to_string(arena, compare), "(a < b) == true");
+
+        let base = sum(arena);
+        let operand = arena.alloc_expr(Node::from(NodeKind::Field(base, "x".to_string())));
+        let operator = Token::new(TokenKind::Minus, "-".to_string());
+        let neg = arena.alloc_expr(Node::from(NodeKind::Unary(UnaryOp { operator, operand })));
+        assert_eq!(expr_to_string(arena, neg), "-(a + b).x");
+
+        let (v, start) = (ident(arena, "v"), sum(arena));
+        let range = arena.alloc_expr(Node::from(NodeKind::Range(Range { start: Some(start), end: None, inclusive: false })));
+        let index = arena.alloc_expr(Node::from(NodeKind::Index(v, range)));
+        assert_eq!(expr_to_string(arena, index), "v[a + b..]");
+    }
+
+    #[test]
+    fn test_print_callee_and_assignment() {
+        let arena = &mut AstArena::default();
+        let s = ident(arena, "s");
+        let callee = arena.alloc_expr(Node::from(NodeKind::Field(s, "f".to_string())));
+        let args = vec![ident(arena, "x")];
+        let call = arena.alloc_expr(Node::from(NodeKind::Call(Call { callee, args })));
+        assert_eq!(expr_to_string(arena, call), "(s.f)(x)");
+
+        let assign = |arena: &mut AstArena, target, value| {
+            arena.alloc_expr(Node::from(NodeKind::Assign(Assign { target, operator: None, value })))
+        };
+        let (a, b, c) = (ident(arena, "a"), ident(arena, "b"), ident(arena, "c"));
+        let inner = assign(arena, b, c);
+        let chained = assign(arena, a, inner);
+        assert_eq!(expr_to_string(arena, chained), "a = b = c");
+        let fields = vec![("x".to_string(), ident(arena, "x"))];
+        let lit = arena.alloc_expr(Node::from(NodeKind::StructLit(StructLit { path: Path::from_ident("P"), fields })));
+        assert_eq!(expr_to_string(arena, lit), "P { x }");
+    }
+}
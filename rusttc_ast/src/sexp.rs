@@ -0,0 +1,395 @@
+use std::fmt;
+
+use serde::ser::{self, Serialize};
+
+// serde로 직렬화할 수 있는 값을 한 줄짜리 S-식으로 씁니다.
+// 구조체는 `(Node :id 0 :span (Span :lo 0 :hi 1) :kind (Int 1 nil))`, 값 없는 열거형 변형은 `Wild`,
+// 목록은 `(a b c)`, `None`은 `nil`, 문자열은 따옴표와 `\`로 이스케이프합니다.
+pub fn to_string<T: Serialize>(value: &T) -> Result<String, Error> {
+    let mut serializer = Serializer { out: String::new() };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.out)
+}
+
+// 직렬화하는 값이 보고한 오류입니다. AST를 직렬화할 때는 생기지 않습니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Error { message: message.to_string() }
+    }
+}
+
+pub struct Serializer {
+    out: String,
+}
+
+impl Serializer {
+    // 여는 괄호와 머리 기호를 씁니다. 목록처럼 머리가 없으면 `head`는 빈 문자열입니다.
+    fn open(&mut self, head: &str) -> Compound<'_> {
+        self.out.push('(');
+        self.out.push_str(head);
+        Compound { ser: self, first: head.is_empty() }
+    }
+
+    fn write_str(&mut self, text: &str) {
+        self.out.push('"');
+        for c in text.chars() {
+            match c {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                c => self.out.push(c),
+            }
+        }
+        self.out.push('"');
+    }
+}
+
+// 괄호 안의 원소를 하나씩 씁니다. 첫 원소 앞에만 공백을 두지 않습니다.
+pub struct Compound<'a> {
+    ser: &'a mut Serializer,
+    first: bool,
+}
+
+impl Compound<'_> {
+    fn separate(&mut self) {
+        if !self.first {
+            self.ser.out.push(' ');
+        }
+        self.first = false;
+    }
+
+    fn element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.separate();
+        value.serialize(&mut *self.ser)
+    }
+
+    fn field<T: ?Sized + Serialize>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        self.separate();
+        self.ser.out.push(':');
+        self.ser.out.push_str(key);
+        self.ser.out.push(' ');
+        value.serialize(&mut *self.ser)
+    }
+
+    fn close(self) -> Result<(), Error> {
+        self.ser.out.push(')');
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, value: bool) -> Result<(), Error> {
+        self.out.push_str(if value { "true" } else { "false" });
+        Ok(())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<(), Error> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<(), Error> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<(), Error> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<(), Error> {
+        self.out.push_str(&value.to_string());
+        Ok(())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<(), Error> {
+        self.serialize_u64(value.into())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<(), Error> {
+        self.serialize_u64(value.into())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<(), Error> {
+        self.serialize_u64(value.into())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<(), Error> {
+        self.out.push_str(&value.to_string());
+        Ok(())
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<(), Error> {
+        self.out.push_str(&value.to_string());
+        Ok(())
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<(), Error> {
+        self.serialize_f64(value.into())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<(), Error> {
+        self.out.push_str(&value.to_string());
+        Ok(())
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), Error> {
+        self.write_str(value.encode_utf8(&mut [0; 4]));
+        Ok(())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), Error> {
+        self.write_str(value);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), Error> {
+        let mut seq = self.open("");
+        value.iter().try_for_each(|byte| seq.element(byte))?;
+        seq.close()
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.out.push_str("nil");
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.out.push_str("()");
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), Error> {
+        self.out.push_str(name);
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<(), Error> {
+        self.out.push_str(variant);
+        Ok(())
+    }
+
+    // `NodeId(3)`처럼 감싸기만 하는 구조체는 안의 값만 씁니다.
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _name: &'static str, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        let mut compound = self.open(variant);
+        compound.element(value)?;
+        compound.close()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>, Error> {
+        Ok(self.open(""))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a>, Error> {
+        Ok(self.open(""))
+    }
+
+    fn serialize_tuple_struct(self, name: &'static str, _len: usize) -> Result<Compound<'a>, Error> {
+        Ok(self.open(name))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        Ok(self.open(variant))
+    }
+
+    // 맵은 `((key value) ...)`로 씁니다.
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>, Error> {
+        Ok(self.open(""))
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Compound<'a>, Error> {
+        Ok(self.open(name))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        Ok(self.open(variant))
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        self.separate();
+        self.ser.out.push('(');
+        key.serialize(&mut *self.ser)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.ser.out.push(' ');
+        value.serialize(&mut *self.ser)?;
+        self.ser.out.push(')');
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{AstArena, BinaryOp, Crate, Node, NodeId, NodeKind, Pat, PatKind, Span};
+    use rusttc_lexer::{Token, TokenKind};
+
+    #[test]
+    fn test_sexp_nodes() {
+        let mut arena = AstArena::default();
+        let left = arena.alloc_expr(Node { id: NodeId(0), span: Span::new(0, 1), kind: NodeKind::Int(1, None) });
+        let right = arena.alloc_expr(Node { id: NodeId(1), span: Span::new(4, 5), kind: NodeKind::Str("a\"b".to_string()) });
+        let operator = Token::new(TokenKind::Plus, "+".to_string()).with_span(Span::new(2, 3));
+        let node = Node { id: NodeId(2), span: Span::new(0, 5), kind: NodeKind::BinaryOp(BinaryOp { left, operator, right }) };
+        assert_eq!(
+            to_string(&arena.resolved(&node)).unwrap(),
+            "(Node :id 2 :span (Span :lo 0 :hi 5) :kind (BinaryOp (BinaryOp \
+             :left (Node :id 0 :span (Span :lo 0 :hi 1) :kind (Int 1 nil)) \
+             :operator (Token :kind Plus :text \"+\" :span (Span :lo 2 :hi 3)) \
+             :right (Node :id 1 :span (Span :lo 4 :hi 5) :kind (Str \"a\\\"b\")))))"
+        );
+        // 저장소 없이는 번호를 풀 수 없습니다.
+        assert!(to_string(&node).unwrap_err().message.contains("AstArena::resolved"));
+    }
+
+    #[test]
+    fn test_sexp_crate_has_no_arena() {
+        let krate = Crate { attrs: Vec::new(), items: Vec::new(), arena: AstArena::default() };
+        assert_eq!(to_string(&krate).unwrap(), "(Crate :attrs () :items ())");
+    }
+
+    #[test]
+    fn test_sexp_variants_and_lists() {
+        let pat = Pat::from(PatKind::Ident { name: "x".to_string(), mutable: true });
+        assert_eq!(to_string(&pat.kind).unwrap(), "(Ident :name \"x\" :mutable true)");
+        assert_eq!(to_string(&PatKind::Wild).unwrap(), "Wild");
+        let elems = NodeKind::Tuple(Vec::new());
+        assert_eq!(to_string(&elems).unwrap(), "(Tuple ())");
+        assert_eq!(to_string(&(None::<i32>, vec![1, 2])).unwrap(), "(nil (1 2))");
+    }
+}
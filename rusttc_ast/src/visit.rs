@@ -0,0 +1,437 @@
+use crate::ast::{
+    Arm, ArrayLen, AstArena, Attribute, Block, ConstDef, Crate, ExprId, FieldDef, FnDef, GenericParam, Generics, Impl, Item, Local, ModDef, Node, NodeKind,
+    Param, Pat, PatKind, Path, Stmt, StmtKind, StructDef, TraitDef, Ty, TyKind, UseDecl, UseTree, UseTreeKind,
+};
+
+// AST를 읽기만 하며 훑는 방문자입니다.
+// 메서드마다 기본 구현은 같은 이름의 `walk_*` 함수로 자식 노드를 차례로 방문합니다.
+// 필요한 메서드만 덮어쓰고, 그 안에서 `walk_*`를 부르면 하위 노드까지 계속 내려갑니다.
+// 표현식과 문장의 자식은 `arena`에서 찾습니다.
+pub trait Visitor<'a>: Sized {
+    fn arena(&self) -> &'a AstArena;
+
+    fn visit_crate(&mut self, krate: &'a Crate) {
+        walk_crate(self, krate)
+    }
+
+    fn visit_attribute(&mut self, _attr: &'a Attribute) {}
+
+    fn visit_item(&mut self, item: &'a Item) {
+        walk_item(self, item)
+    }
+
+    // 최상위 함수와 impl, 트레이트 안의 함수 모두 여기로 옵니다.
+    fn visit_fn(&mut self, function: &'a FnDef) {
+        walk_fn(self, function)
+    }
+
+    fn visit_struct(&mut self, def: &'a StructDef) {
+        walk_struct(self, def)
+    }
+
+    fn visit_field_def(&mut self, field: &'a FieldDef) {
+        walk_field_def(self, field)
+    }
+
+    fn visit_impl(&mut self, imp: &'a Impl) {
+        walk_impl(self, imp)
+    }
+
+    fn visit_trait(&mut self, def: &'a TraitDef) {
+        walk_trait(self, def)
+    }
+
+    fn visit_mod(&mut self, def: &'a ModDef) {
+        walk_mod(self, def)
+    }
+
+    fn visit_use(&mut self, decl: &'a UseDecl) {
+        walk_use(self, decl)
+    }
+
+    // `const`와 `static` 모두 여기로 옵니다.
+    fn visit_const(&mut self, def: &'a ConstDef) {
+        walk_const(self, def)
+    }
+
+    fn visit_use_tree(&mut self, tree: &'a UseTree) {
+        walk_use_tree(self, tree)
+    }
+
+    fn visit_generics(&mut self, generics: &'a Generics) {
+        walk_generics(self, generics)
+    }
+
+    fn visit_generic_param(&mut self, param: &'a GenericParam) {
+        walk_generic_param(self, param)
+    }
+
+    fn visit_param(&mut self, param: &'a Param) {
+        walk_param(self, param)
+    }
+
+    fn visit_block(&mut self, block: &'a Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        walk_stmt(self, stmt)
+    }
+
+    fn visit_local(&mut self, local: &'a Local) {
+        walk_local(self, local)
+    }
+
+    fn visit_expr(&mut self, node: &'a Node) {
+        walk_expr(self, node)
+    }
+
+    fn visit_arm(&mut self, arm: &'a Arm) {
+        walk_arm(self, arm)
+    }
+
+    fn visit_pat(&mut self, pat: &'a Pat) {
+        walk_pat(self, pat)
+    }
+
+    fn visit_ty(&mut self, ty: &'a Ty) {
+        walk_ty(self, ty)
+    }
+
+    fn visit_path(&mut self, path: &'a Path) {
+        walk_path(self, path)
+    }
+}
+
+pub fn walk_crate<'a, V: Visitor<'a>>(visitor: &mut V, krate: &'a Crate) {
+    krate.attrs.iter().for_each(|attr| visitor.visit_attribute(attr));
+    krate.items.iter().for_each(|item| visitor.visit_item(item));
+}
+
+pub fn walk_item<'a, V: Visitor<'a>>(visitor: &mut V, item: &'a Item) {
+    match item {
+        Item::Fn(function) => visitor.visit_fn(function),
+        Item::Struct(def) => visitor.visit_struct(def),
+        Item::Impl(imp) => visitor.visit_impl(imp),
+        Item::Trait(def) => visitor.visit_trait(def),
+        Item::Mod(def) => visitor.visit_mod(def),
+        Item::Use(decl) => visitor.visit_use(decl),
+        Item::Const(def) | Item::Static(def) => visitor.visit_const(def),
+    }
+}
+
+pub fn walk_fn<'a, V: Visitor<'a>>(visitor: &mut V, function: &'a FnDef) {
+    function.attrs.iter().for_each(|attr| visitor.visit_attribute(attr));
+    visitor.visit_generics(&function.generics);
+    function.params.iter().for_each(|param| visitor.visit_param(param));
+    if let Some(ret) = &function.ret {
+        visitor.visit_ty(ret);
+    }
+    if let Some(body) = &function.body {
+        visitor.visit_block(body);
+    }
+}
+
+pub fn walk_struct<'a, V: Visitor<'a>>(visitor: &mut V, def: &'a StructDef) {
+    def.attrs.iter().for_each(|attr| visitor.visit_attribute(attr));
+    visitor.visit_generics(&def.generics);
+    def.fields.iter().for_each(|field| visitor.visit_field_def(field));
+}
+
+pub fn walk_field_def<'a, V: Visitor<'a>>(visitor: &mut V, field: &'a FieldDef) {
+    visitor.visit_ty(&field.ty);
+}
+
+pub fn walk_impl<'a, V: Visitor<'a>>(visitor: &mut V, imp: &'a Impl) {
+    imp.attrs.iter().for_each(|attr| visitor.visit_attribute(attr));
+    visitor.visit_generics(&imp.generics);
+    if let Some(of_trait) = &imp.of_trait {
+        visitor.visit_path(of_trait);
+    }
+    visitor.visit_ty(&imp.self_ty);
+    imp.items.iter().for_each(|function| visitor.visit_fn(function));
+}
+
+pub fn walk_trait<'a, V: Visitor<'a>>(visitor: &mut V, def: &'a TraitDef) {
+    def.attrs.iter().for_each(|attr| visitor.visit_attribute(attr));
+    def.items.iter().for_each(|function| visitor.visit_fn(function));
+}
+
+// 아직 파일을 읽지 않은 `mod foo;`는 안으로 내려가지 않습니다.
+pub fn walk_mod<'a, V: Visitor<'a>>(visitor: &mut V, def: &'a ModDef) {
+    def.attrs.iter().for_each(|attr| visitor.visit_attribute(attr));
+    if let Some(items) = &def.items {
+        items.iter().for_each(|item| visitor.visit_item(item));
+    }
+}
+
+pub fn walk_const<'a, V: Visitor<'a>>(visitor: &mut V, def: &'a ConstDef) {
+    def.attrs.iter().for_each(|attr| visitor.visit_attribute(attr));
+    visitor.visit_ty(&def.ty);
+    visitor.visit_expr(&visitor.arena()[def.value]);
+}
+
+pub fn walk_use<'a, V: Visitor<'a>>(visitor: &mut V, decl: &'a UseDecl) {
+    decl.attrs.iter().for_each(|attr| visitor.visit_attribute(attr));
+    visitor.visit_use_tree(&decl.tree);
+}
+
+pub fn walk_use_tree<'a, V: Visitor<'a>>(visitor: &mut V, tree: &'a UseTree) {
+    visitor.visit_path(&tree.prefix);
+    if let UseTreeKind::Nested(trees) = &tree.kind {
+        trees.iter().for_each(|tree| visitor.visit_use_tree(tree));
+    }
+}
+
+pub fn walk_generics<'a, V: Visitor<'a>>(visitor: &mut V, generics: &'a Generics) {
+    generics.params.iter().for_each(|param| visitor.visit_generic_param(param));
+}
+
+pub fn walk_generic_param<'a, V: Visitor<'a>>(visitor: &mut V, param: &'a GenericParam) {
+    param.bounds.iter().for_each(|bound| visitor.visit_path(bound));
+}
+
+pub fn walk_param<'a, V: Visitor<'a>>(visitor: &mut V, param: &'a Param) {
+    visitor.visit_pat(&param.pat);
+    visitor.visit_ty(&param.ty);
+}
+
+pub fn walk_block<'a, V: Visitor<'a>>(visitor: &mut V, block: &'a Block) {
+    let arena = visitor.arena();
+    block.stmts.iter().for_each(|&stmt| visitor.visit_stmt(&arena[stmt]));
+}
+
+pub fn walk_stmt<'a, V: Visitor<'a>>(visitor: &mut V, stmt: &'a Stmt) {
+    match &stmt.kind {
+        StmtKind::Let(local) => visitor.visit_local(local),
+        StmtKind::Item(item) => visitor.visit_item(item),
+        StmtKind::Expr(expr) | StmtKind::Semi(expr) => visitor.visit_expr(&visitor.arena()[*expr]),
+    }
+}
+
+pub fn walk_local<'a, V: Visitor<'a>>(visitor: &mut V, local: &'a Local) {
+    visitor.visit_pat(&local.pat);
+    if let Some(ty) = &local.ty {
+        visitor.visit_ty(ty);
+    }
+    if let Some(init) = local.init {
+        visitor.visit_expr(&visitor.arena()[init]);
+    }
+}
+
+pub fn walk_expr<'a, V: Visitor<'a>>(visitor: &mut V, node: &'a Node) {
+    let arena = visitor.arena();
+    let visit = |visitor: &mut V, id: &ExprId| visitor.visit_expr(&arena[*id]);
+    match &node.kind {
+        NodeKind::Int(..) | NodeKind::Float(..) | NodeKind::Char(_) | NodeKind::Ident(_) | NodeKind::Bool(_) | NodeKind::Str(_) => {},
+        NodeKind::Break | NodeKind::Continue => {},
+        NodeKind::Path(path) => visitor.visit_path(path),
+        NodeKind::QualifiedPath(qself, path) => {
+            visitor.visit_ty(&qself.ty);
+            visitor.visit_path(&qself.trait_path);
+            visitor.visit_path(path);
+        },
+        NodeKind::BinaryOp(binary_op) => {
+            visit(visitor, &binary_op.left);
+            visit(visitor, &binary_op.right);
+        },
+        NodeKind::Unary(unary_op) => visit(visitor, &unary_op.operand),
+        NodeKind::Ref { expr, .. } => visit(visitor, expr),
+        NodeKind::Call(call) => {
+            visit(visitor, &call.callee);
+            call.args.iter().for_each(|arg| visit(visitor, arg));
+        },
+        NodeKind::MethodCall(call) => {
+            visit(visitor, &call.receiver);
+            call.args.iter().for_each(|arg| visit(visitor, arg));
+        },
+        NodeKind::Field(base, _) => visit(visitor, base),
+        NodeKind::StructLit(lit) => {
+            visitor.visit_path(&lit.path);
+            lit.fields.iter().for_each(|(_, value)| visit(visitor, value));
+        },
+        NodeKind::Assign(assign) => {
+            visit(visitor, &assign.target);
+            visit(visitor, &assign.value);
+        },
+        NodeKind::Block(block) | NodeKind::Loop(block) => visitor.visit_block(block),
+        NodeKind::If(if_expr) => {
+            visit(visitor, &if_expr.cond);
+            visitor.visit_block(&if_expr.then);
+            if let Some(els) = &if_expr.els {
+                visit(visitor, els);
+            }
+        },
+        NodeKind::Match(match_expr) => {
+            visit(visitor, &match_expr.scrutinee);
+            match_expr.arms.iter().for_each(|arm| visitor.visit_arm(arm));
+        },
+        NodeKind::While(while_expr) => {
+            visit(visitor, &while_expr.cond);
+            visitor.visit_block(&while_expr.body);
+        },
+        NodeKind::ForLoop(for_loop) => {
+            visitor.visit_pat(&for_loop.pat);
+            visit(visitor, &for_loop.iter);
+            visitor.visit_block(&for_loop.body);
+        },
+        NodeKind::Return(value) => {
+            if let Some(value) = value {
+                visit(visitor, value);
+            }
+        },
+        NodeKind::Closure(closure) => {
+            for param in &closure.params {
+                visitor.visit_pat(&param.pat);
+                if let Some(ty) = &param.ty {
+                    visitor.visit_ty(ty);
+                }
+            }
+            if let Some(ret) = &closure.ret {
+                visitor.visit_ty(ret);
+            }
+            visit(visitor, &closure.body);
+        },
+        NodeKind::Array(elems) | NodeKind::Tuple(elems) => elems.iter().for_each(|elem| visit(visitor, elem)),
+        NodeKind::Repeat(base, other) | NodeKind::Index(base, other) => {
+            visit(visitor, base);
+            visit(visitor, other);
+        },
+        NodeKind::Range(range) => range.start.iter().chain(&range.end).for_each(|bound| visit(visitor, bound)),
+        NodeKind::FormatArgs(format) => format.args.iter().for_each(|arg| visit(visitor, &arg.expr)),
+    }
+}
+
+pub fn walk_arm<'a, V: Visitor<'a>>(visitor: &mut V, arm: &'a Arm) {
+    let arena = visitor.arena();
+    visitor.visit_pat(&arm.pat);
+    if let Some(guard) = arm.guard {
+        visitor.visit_expr(&arena[guard]);
+    }
+    visitor.visit_expr(&arena[arm.body]);
+}
+
+pub fn walk_pat<'a, V: Visitor<'a>>(visitor: &mut V, pat: &'a Pat) {
+    let arena = visitor.arena();
+    match &pat.kind {
+        PatKind::Ident { .. } | PatKind::Wild => {},
+        PatKind::Lit(lit) => visitor.visit_expr(&arena[*lit]),
+        PatKind::Range(start, end) => {
+            visitor.visit_expr(&arena[*start]);
+            visitor.visit_expr(&arena[*end]);
+        },
+        PatKind::Tuple(pats) | PatKind::Or(pats) => pats.iter().for_each(|pat| visitor.visit_pat(pat)),
+        PatKind::Struct { path, fields, .. } => {
+            visitor.visit_path(path);
+            fields.iter().for_each(|(_, pat)| visitor.visit_pat(pat));
+        },
+    }
+}
+
+pub fn walk_ty<'a, V: Visitor<'a>>(visitor: &mut V, ty: &'a Ty) {
+    match &ty.kind {
+        TyKind::Path(path) => visitor.visit_path(path),
+        TyKind::Array(ty, len) => {
+            visitor.visit_ty(ty);
+            if let ArrayLen::Expr(len) = len {
+                visitor.visit_expr(&visitor.arena()[*len]);
+            }
+        },
+        TyKind::Ref { ty, .. } | TyKind::Slice(ty) => visitor.visit_ty(ty),
+        TyKind::Unit => {},
+        TyKind::Tuple(tys) => tys.iter().for_each(|ty| visitor.visit_ty(ty)),
+    }
+}
+
+// 세그먼트의 제네릭 인자와 클로저 트레이트의 반환 타입을 방문합니다.
+pub fn walk_path<'a, V: Visitor<'a>>(visitor: &mut V, path: &'a Path) {
+    for segment in &path.segments {
+        segment.args.iter().for_each(|arg| visitor.visit_ty(arg));
+        if let Some(output) = &segment.output {
+            visitor.visit_ty(output);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Call, Closure, ClosureParam, CaptureBy, ClosureKind};
+
+    // 방문한 식별자와 타입 이름을 모읍니다.
+    struct Names<'a> {
+        arena: &'a AstArena,
+        names: Vec<String>,
+    }
+
+    impl<'a> Visitor<'a> for Names<'a> {
+        fn arena(&self) -> &'a AstArena {
+            self.arena
+        }
+
+        fn visit_expr(&mut self, node: &'a Node) {
+            if let NodeKind::Ident(name) = &node.kind {
+                self.names.push(name.clone());
+            }
+            walk_expr(self, node)
+        }
+
+        fn visit_pat(&mut self, pat: &'a Pat) {
+            if let PatKind::Ident { name, .. } = &pat.kind {
+                self.names.push(format!("pat {}", name));
+            }
+        }
+
+        fn visit_ty(&mut self, ty: &'a Ty) {
+            self.names.push(ty.to_string());
+            walk_ty(self, ty)
+        }
+    }
+
+    fn ident(arena: &mut AstArena, name: &str) -> ExprId {
+        arena.alloc_expr(Node::from(NodeKind::Ident(name.to_string())))
+    }
+
+    #[test]
+    fn test_visit_nested_expressions() {
+        // f(|x: &i32| y)
+        let mut arena = AstArena::default();
+        let closure = Closure {
+            capture_by: CaptureBy::Ref,
+            params: vec![ClosureParam {
+                pat: Pat::from(PatKind::Ident { name: "x".to_string(), mutable: false }),
+                ty: Some(Ty::from(TyKind::Ref { mutable: false, ty: Box::new(Ty::from_ident("i32")) })),
+            }],
+            ret: None,
+            body: ident(&mut arena, "y"),
+            name: String::new(),
+            kind: ClosureKind::Fn,
+            captures: Vec::new(),
+        };
+        let callee = ident(&mut arena, "f");
+        let closure = arena.alloc_expr(Node::from(NodeKind::Closure(Box::new(closure))));
+        let call = Node::from(NodeKind::Call(Call { callee, args: vec![closure] }));
+
+        let mut names = Names { arena: &arena, names: Vec::new() };
+        names.visit_expr(&call);
+        assert_eq!(names.names, vec!["f", "pat x", "&i32", "i32", "y"]);
+    }
+
+    #[test]
+    fn test_visit_block_statements() {
+        let mut arena = AstArena::default();
+        let init = Some(ident(&mut arena, "b"));
+        let local = arena.alloc_stmt(Stmt::from(StmtKind::Let(Local {
+            pat: Pat::from(PatKind::Ident { name: "a".to_string(), mutable: false }),
+            ty: None,
+            init,
+        })));
+        let elems = vec![ident(&mut arena, "a"), ident(&mut arena, "c")];
+        let tuple = arena.alloc_expr(Node::from(NodeKind::Tuple(elems)));
+        let block = Block { stmts: vec![local, arena.alloc_stmt(Stmt::from(StmtKind::Expr(tuple)))] };
+
+        let mut names = Names { arena: &arena, names: Vec::new() };
+        names.visit_block(&block);
+        assert_eq!(names.names, vec!["pat a", "b", "a", "c"]);
+    }
+}
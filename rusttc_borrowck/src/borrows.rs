@@ -0,0 +1,413 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use rusttc_lexer::Span;
+use rusttc_typeck::ty::Ty;
+
+use crate::mir::{BasicBlock, Body, Local, Operand, Place, PlaceElem, Rvalue, StatementKind, TerminatorKind};
+use crate::BorrowckError;
+
+// 본문 안의 한 지점입니다. 문장 번호가 문장 수와 같으면 종결자입니다.
+type Location = (BasicBlock, usize);
+
+// 참조를 만든 곳 하나입니다 (`&x`, `&mut v[i]`).
+struct Loan {
+    place: Place,               // 빌린 위치
+    mutable: bool,
+    span: Span,                 // 참조를 만든 식의 위치
+    temp: Local,                // 참조를 처음 담은 지역 변수
+}
+
+// 위치를 쓰는 방식입니다.
+#[derive(Clone, Copy, PartialEq)]
+enum Access {
+    Read,                       // `Copy` 값으로 읽습니다
+    Move,
+    Borrow(bool),               // `&`, `&mut`
+    Write,                      // 대입으로 덮어씁니다
+    Dead,                       // 바인딩이 사라집니다
+}
+
+// 빌림이 살아 있는 동안 그 위치와 충돌하는 접근을 보고합니다 (non-lexical lifetimes).
+// 빌림은 그 참조를 담은 지역 변수가 뒤에서 다시 쓰일 수 있는 지점에서만 살아 있습니다.
+// 참조를 담을 수 있는 지역 변수마다 어떤 빌림을 품고 있는지 앞으로 흘려 보내고, 지역 변수의 생존 구간은 뒤에서부터 구합니다.
+pub(crate) fn check(body: &Body, errors: &mut Vec<BorrowckError>) {
+    let mut loans = Vec::new();
+    let mut loan_at = HashMap::new();
+    for (i, data) in body.blocks.iter().enumerate() {
+        for (j, statement) in data.statements.iter().enumerate() {
+            if let StatementKind::Assign(dest, Rvalue::Ref(mutable, place)) = &statement.kind {
+                loan_at.insert((BasicBlock(i as u32), j), loans.len());
+                loans.push(Loan { place: place.clone(), mutable: *mutable, span: statement.span, temp: dest.local });
+            }
+        }
+    }
+    if loans.is_empty() {
+        return;
+    }
+
+    let cx = BorrowCtxt { body, loans, loan_at, live_after: liveness(body), states: Vec::new() };
+    let cx = BorrowCtxt { states: cx.loan_flow(), ..cx };
+
+    let mut reported = HashSet::new();
+    for (i, states) in cx.states.iter().enumerate() {
+        let block = BasicBlock(i as u32);
+        for (j, before) in states.iter().enumerate() {
+            let location = (block, j);
+            let mut after = before.clone();
+            cx.apply(location, &mut after);
+            let here = cx.loan_at.get(&location);
+            let live: BTreeSet<usize> = cx.live_after[i][j]
+                .iter()
+                .flat_map(|local| after[local.0 as usize].iter().copied())
+                .filter(|loan| Some(loan) != here)
+                .collect();
+            if live.is_empty() {
+                continue;
+            }
+            for (place, access, span) in cx.accesses(location) {
+                for &loan in &live {
+                    // 빌림 하나에는 오류를 한 번만 보고합니다. 같은 빌림이 뒤따르는 접근마다 다시 걸리기 때문입니다.
+                    if conflicts(access, &place, &cx.loans[loan]) && reported.insert(loan) {
+                        if let Some(error) = cx.report(location, access, &place, span, loan) {
+                            errors.push(error);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct BorrowCtxt<'a> {
+    body: &'a Body,
+    loans: Vec<Loan>,
+    loan_at: HashMap<Location, usize>,      // 참조를 만드는 문장 → 빌림 번호
+    live_after: Vec<Vec<BTreeSet<Local>>>,  // 블록마다, 각 지점 바로 뒤에 살아 있는 지역 변수
+    states: Vec<Vec<Vec<BTreeSet<usize>>>>, // 블록마다, 각 지점 바로 앞에서 지역 변수가 품은 빌림 (도달할 수 없는 블록은 비어 있습니다)
+}
+
+impl BorrowCtxt<'_> {
+    // 참조를 담을 수 있는 지역 변수인지 확인합니다. 반환 값 자리는 타입을 모르므로 항상 추적합니다.
+    fn tracks(&self, local: Local) -> bool {
+        local == Local::RETURN || may_contain_refs(&self.body.local_decl(local).ty)
+    }
+
+    // 각 지역 변수가 품었을 수 있는 빌림을 모든 경로에 걸쳐 구하고, 지점마다 그 직전 상태를 돌려줍니다.
+    fn loan_flow(&self) -> Vec<Vec<Vec<BTreeSet<usize>>>> {
+        let body = self.body;
+        let mut entry: Vec<Option<Vec<BTreeSet<usize>>>> = vec![None; body.blocks.len()];
+        entry[BasicBlock::START.0 as usize] = Some(vec![BTreeSet::new(); body.locals.len()]);
+        let mut worklist = VecDeque::from([BasicBlock::START]);
+        while let Some(block) = worklist.pop_front() {
+            let mut state = entry[block.0 as usize].clone().unwrap();
+            for i in 0..=body.block(block).statements.len() {
+                self.apply((block, i), &mut state);
+            }
+            for successor in body.block(block).terminator.successors() {
+                let slot = &mut entry[successor.0 as usize];
+                let changed = match slot {
+                    Some(existing) => existing.iter_mut().zip(&state).fold(false, |changed, (existing, incoming)| {
+                        let before = existing.len();
+                        existing.extend(incoming.iter().copied());
+                        changed | (existing.len() != before)
+                    }),
+                    None => {
+                        *slot = Some(state.clone());
+                        true
+                    },
+                };
+                if changed && !worklist.contains(&successor) {
+                    worklist.push_back(successor);
+                }
+            }
+        }
+
+        entry
+            .into_iter()
+            .enumerate()
+            .map(|(i, state)| {
+                let Some(mut state) = state else { return Vec::new() };
+                let block = BasicBlock(i as u32);
+                (0..=body.block(block).statements.len())
+                    .map(|j| {
+                        let before = state.clone();
+                        self.apply((block, j), &mut state);
+                        before
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // 한 지점을 실행한 뒤의 상태를 구합니다. 참조를 담은 값을 옮기거나 새 참조를 만들면 대상이 그 빌림을 품습니다.
+    fn apply(&self, (block, i): Location, state: &mut [BTreeSet<usize>]) {
+        let data = self.body.block(block);
+        let (dest, sources, created) = match data.statements.get(i).map(|statement| &statement.kind) {
+            Some(StatementKind::Assign(dest, rvalue)) => {
+                let sources: Vec<Local> = match rvalue {
+                    Rvalue::Ref(_, place) => vec![place.local],
+                    _ => rvalue.operands().into_iter().filter_map(Operand::place).map(|place| place.local).collect(),
+                };
+                (dest, sources, self.loan_at.get(&(block, i)).copied())
+            },
+            Some(StatementKind::StorageLive(local) | StatementKind::StorageDead(local)) => {
+                state[local.0 as usize].clear();
+                return;
+            },
+            None => match &data.terminator.kind {
+                TerminatorKind::Call { func, args, dest, .. } => {
+                    (dest, [func].into_iter().chain(args).filter_map(Operand::place).map(|place| place.local).collect(), None)
+                },
+                _ => return,
+            },
+        };
+        if !self.tracks(dest.local) {
+            return;
+        }
+        let incoming: BTreeSet<usize> = sources.iter().flat_map(|local| state[local.0 as usize].iter().copied()).chain(created).collect();
+        let slot = &mut state[dest.local.0 as usize];
+        if dest.projection.is_empty() {
+            *slot = incoming;
+        } else {
+            slot.extend(incoming);
+        }
+    }
+
+    // 지점에서 일어나는 접근입니다. 값을 계산하는 접근이 대입보다 먼저 옵니다.
+    fn accesses(&self, (block, i): Location) -> Vec<(Place, Access, Span)> {
+        let data = self.body.block(block);
+        let operand = |operand: &Operand, span: Span| match operand {
+            Operand::Copy(place) => Some((place.clone(), Access::Read, span)),
+            Operand::Move(place) => Some((place.clone(), Access::Move, span)),
+            Operand::Constant => None,
+        };
+        match data.statements.get(i) {
+            Some(statement) => match &statement.kind {
+                StatementKind::Assign(dest, rvalue) => {
+                    let mut accesses: Vec<_> = match rvalue {
+                        Rvalue::Ref(mutable, place) => vec![(place.clone(), Access::Borrow(*mutable), statement.span)],
+                        _ => rvalue.operands().into_iter().filter_map(|op| operand(op, statement.span)).collect(),
+                    };
+                    accesses.push((dest.clone(), Access::Write, statement.span));
+                    accesses
+                },
+                StatementKind::StorageDead(local) => vec![(Place::from_local(*local), Access::Dead, statement.span)],
+                StatementKind::StorageLive(_) => Vec::new(),
+            },
+            None => match &data.terminator.kind {
+                TerminatorKind::SwitchBool { cond, .. } => operand(cond, data.terminator.span).into_iter().collect(),
+                TerminatorKind::Call { func, args, dest, .. } => {
+                    let span = data.terminator.span;
+                    let mut accesses: Vec<_> = [func].into_iter().chain(args).filter_map(|op| operand(op, span)).collect();
+                    accesses.push((dest.clone(), Access::Write, span));
+                    accesses
+                },
+                TerminatorKind::Goto(_) | TerminatorKind::Return | TerminatorKind::Unreachable => Vec::new(),
+            },
+        }
+    }
+
+    // 빌림을 품은 지역 변수를 `location` 뒤에서 처음 쓰는 곳을 찾습니다.
+    fn later_use(&self, (block, i): Location, loan: usize) -> Option<Location> {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([(block, i + 1)]);
+        while let Some((block, start)) = queue.pop_front() {
+            let states = &self.states[block.0 as usize];
+            for (j, state) in states.iter().enumerate().skip(start) {
+                let holds = |local: &Local| state[local.0 as usize].contains(&loan);
+                if uses(self.body, (block, j)).iter().any(holds) {
+                    return Some((block, j));
+                }
+            }
+            for successor in self.body.block(block).terminator.successors() {
+                if visited.insert(successor) {
+                    queue.push_back((successor, 0));
+                }
+            }
+        }
+        None
+    }
+
+    fn span_of(&self, (block, i): Location) -> Span {
+        let data = self.body.block(block);
+        data.statements.get(i).map_or(data.terminator.span, |statement| statement.span)
+    }
+
+    // 반환 값 자리에 옮겨 담는 지점인지 확인합니다.
+    fn is_return(&self, (block, i): Location) -> bool {
+        let data = self.body.block(block);
+        match data.statements.get(i) {
+            Some(statement) => matches!(&statement.kind, StatementKind::Assign(dest, _) if dest.local == Local::RETURN),
+            None => matches!(data.terminator.kind, TerminatorKind::Return),
+        }
+    }
+
+    fn report(&self, location: Location, access: Access, place: &Place, span: Span, index: usize) -> Option<BorrowckError> {
+        let body = self.body;
+        let loan = &self.loans[index];
+        let later = self.later_use(location, index);
+        let name = body.describe_place(place)?;
+        let kind = |mutable: bool| if mutable { "mutable" } else { "immutable" };
+
+        let (error, later_label) = match access {
+            Access::Borrow(true) if loan.mutable => (
+                BorrowckError::new(format!("cannot borrow `{}` as mutable more than once at a time", name), span)
+                    .with_label(loan.span, "first mutable borrow occurs here")
+                    .with_label(span, "second mutable borrow occurs here"),
+                "first borrow later used here".to_string(),
+            ),
+            Access::Borrow(mutable) => (
+                BorrowckError::new(
+                    format!("cannot borrow `{}` as {} because it is also borrowed as {}", name, kind(mutable), kind(loan.mutable)),
+                    span,
+                )
+                .with_label(loan.span, format!("{} borrow occurs here", kind(loan.mutable)))
+                .with_label(span, format!("{} borrow occurs here", kind(mutable))),
+                format!("{} borrow later used here", kind(loan.mutable)),
+            ),
+            Access::Read => (
+                BorrowckError::new(format!("cannot use `{}` because it was mutably borrowed", name), span)
+                    .with_label(loan.span, format!("`{}` is borrowed here", name))
+                    .with_label(span, format!("use of borrowed `{}`", name)),
+                "borrow later used here".to_string(),
+            ),
+            Access::Move => (
+                BorrowckError::new(format!("cannot move out of `{}` because it is borrowed", name), span)
+                    .with_label(loan.span, format!("borrow of `{}` occurs here", name))
+                    .with_label(span, format!("move out of `{}` occurs here", name)),
+                "borrow later used here".to_string(),
+            ),
+            Access::Write => (
+                BorrowckError::new(format!("cannot assign to `{}` because it is borrowed", name), span)
+                    .with_label(loan.span, format!("`{}` is borrowed here", name))
+                    .with_label(span, format!("`{}` is assigned to here but it was already borrowed", name)),
+                "borrow later used here".to_string(),
+            ),
+            // 반환 값이 품은 빌림이라면 지역 변수의 참조를 함수 밖으로 내보내려는 것입니다.
+            Access::Dead if later.is_some_and(|later| self.is_return(later)) => {
+                let what = if place.local.0 as usize <= body.arg_count { "function parameter" } else { "local variable" };
+                let direct = body.blocks.iter().flat_map(|data| &data.statements).any(|statement| {
+                    matches!(&statement.kind, StatementKind::Assign(dest, Rvalue::Use(Operand::Move(value)))
+                        if dest.local == Local::RETURN && *value == Place::from_local(loan.temp))
+                });
+                let error = match direct {
+                    true => BorrowckError::new(format!("cannot return reference to {} `{}`", what, name), loan.span)
+                        .with_label(loan.span, "returns a reference to data owned by the current function"),
+                    false => BorrowckError::new(format!("cannot return value referencing {} `{}`", what, name), loan.span)
+                        .with_label(loan.span, format!("`{}` is borrowed here", name)),
+                };
+                return Some(error);
+            },
+            Access::Dead => (
+                BorrowckError::new(format!("`{}` does not live long enough", name), loan.span)
+                    .with_label(loan.span, "borrowed value does not live long enough")
+                    .with_label(span, format!("`{}` dropped here while still borrowed", name)),
+                "borrow later used here".to_string(),
+            ),
+        };
+        Some(match later {
+            Some(later) => error.with_label(self.span_of(later), later_label),
+            None => error,
+        })
+    }
+}
+
+// 접근이 살아 있는 빌림을 깨뜨리는지 확인합니다. 공유 빌림끼리, 읽기와 공유 빌림은 함께 있을 수 있습니다.
+fn conflicts(access: Access, place: &Place, loan: &Loan) -> bool {
+    match access {
+        Access::Read | Access::Borrow(false) => loan.mutable && place.overlaps(&loan.place),
+        Access::Borrow(true) | Access::Move => place.overlaps(&loan.place),
+        // 참조를 덮어쓰거나 없애도 그 참조가 가리키던 값(`*r`)은 그대로 남습니다.
+        Access::Write | Access::Dead => {
+            place.overlaps(&loan.place) && !(place.is_prefix_of(&loan.place) && loan.place.projection[place.projection.len()..].contains(&PlaceElem::Deref))
+        },
+    }
+}
+
+// 참조를 담을 수 있는 타입인지 확인합니다. 모르는 타입은 담지 않는다고 봅니다.
+fn may_contain_refs(ty: &Ty) -> bool {
+    match ty {
+        Ty::Ref(..) | Ty::Param(_) | Ty::Closure(_) => true,
+        Ty::Adt(_, tys) | Ty::Tuple(tys) => tys.iter().any(may_contain_refs),
+        Ty::Array(ty, _) | Ty::Slice(ty) => may_contain_refs(ty),
+        _ => false,
+    }
+}
+
+// 지점에서 값을 읽는 지역 변수입니다. 참조를 거친 대입(`*r = v`)은 참조를 읽습니다.
+fn uses(body: &Body, (block, i): Location) -> Vec<Local> {
+    let data = body.block(block);
+    let places: Vec<&Place> = match data.statements.get(i).map(|statement| &statement.kind) {
+        Some(StatementKind::Assign(dest, rvalue)) => {
+            let mut places: Vec<&Place> = match rvalue {
+                Rvalue::Ref(_, place) => vec![place],
+                _ => rvalue.operands().into_iter().filter_map(Operand::place).collect(),
+            };
+            if dest.projection.contains(&PlaceElem::Deref) {
+                places.push(dest);
+            }
+            places
+        },
+        Some(StatementKind::StorageLive(_) | StatementKind::StorageDead(_)) => Vec::new(),
+        None => match &data.terminator.kind {
+            TerminatorKind::SwitchBool { cond, .. } => cond.place().into_iter().collect(),
+            TerminatorKind::Call { func, args, .. } => [func].into_iter().chain(args).filter_map(Operand::place).collect(),
+            TerminatorKind::Return => return vec![Local::RETURN],
+            TerminatorKind::Goto(_) | TerminatorKind::Unreachable => Vec::new(),
+        },
+    };
+    places.into_iter().map(|place| place.local).collect()
+}
+
+// 지점에서 값을 통째로 새로 정하는 지역 변수입니다.
+fn defs(body: &Body, (block, i): Location) -> Option<Local> {
+    let data = body.block(block);
+    match data.statements.get(i).map(|statement| &statement.kind) {
+        Some(StatementKind::Assign(dest, _)) => dest.projection.is_empty().then_some(dest.local),
+        Some(StatementKind::StorageLive(local) | StatementKind::StorageDead(local)) => Some(*local),
+        None => match &data.terminator.kind {
+            TerminatorKind::Call { dest, .. } => dest.projection.is_empty().then_some(dest.local),
+            _ => None,
+        },
+    }
+}
+
+// 지점마다 바로 뒤에 살아 있는(뒤에서 다시 읽힐 수 있는) 지역 변수를 구합니다.
+fn liveness(body: &Body) -> Vec<Vec<BTreeSet<Local>>> {
+    let transfer = |location: Location, live: &mut BTreeSet<Local>| {
+        if let Some(local) = defs(body, location) {
+            live.remove(&local);
+        }
+        live.extend(uses(body, location));
+    };
+
+    let mut live_in = vec![BTreeSet::new(); body.blocks.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (i, data) in body.blocks.iter().enumerate().rev() {
+            let mut live: BTreeSet<Local> = data.terminator.successors().iter().flat_map(|s| live_in[s.0 as usize].iter().copied()).collect();
+            for j in (0..=data.statements.len()).rev() {
+                transfer((BasicBlock(i as u32), j), &mut live);
+            }
+            if live != live_in[i] {
+                live_in[i] = live;
+                changed = true;
+            }
+        }
+    }
+
+    body.blocks
+        .iter()
+        .enumerate()
+        .map(|(i, data)| {
+            let mut live: BTreeSet<Local> = data.terminator.successors().iter().flat_map(|s| live_in[s.0 as usize].iter().copied()).collect();
+            let mut after = vec![BTreeSet::new(); data.statements.len() + 1];
+            for j in (0..=data.statements.len()).rev() {
+                after[j] = live.clone();
+                transfer((BasicBlock(i as u32), j), &mut live);
+            }
+            after
+        })
+        .collect()
+}
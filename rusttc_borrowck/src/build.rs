@@ -0,0 +1,671 @@
+use std::collections::{HashMap, HashSet};
+
+use rusttc_ast::arena::AstId;
+use rusttc_ast::ast::{
+    AstArena, Block, CaptureMode, Closure, ExprId, FnDef, Generics, Impl, Local as LetStmt, Match, NodeId, NodeKind, Pat, PatKind,
+    StmtKind,
+};
+use rusttc_ir::resolve::{Res, Resolutions};
+use rusttc_lexer::{Span, TokenKind};
+use rusttc_typeck::ty::Ty;
+use rusttc_typeck::TypeckResults;
+
+use crate::mir::{
+    BasicBlock, BasicBlockData, Body, Local, LocalDecl, Operand, Place, PlaceElem, Rvalue, Statement, StatementKind, Terminator,
+    TerminatorKind,
+};
+
+// 본문을 만들 때 크레이트 전체에서 함께 쓰는 정보입니다.
+pub(crate) struct Cx<'a> {
+    pub arena: &'a AstArena,
+    pub resolutions: &'a Resolutions,
+    pub results: &'a TypeckResults,
+    pub copy_types: HashSet<String>,    // `Copy`를 구현한 구조체 이름
+}
+
+// 함수 본문과 그 안의 클로저 본문을 만듭니다. 함수 본문이 맨 앞에 옵니다.
+pub(crate) fn build_fn(cx: &Cx, function: &FnDef, imp: Option<&Impl>) -> Vec<Body> {
+    let Some(block) = &function.body else { return Vec::new() };
+    let generics = imp.map(|imp| &imp.generics).into_iter().chain([&function.generics]);
+    let copy_params = generics.flat_map(copy_params).collect();
+
+    // 반환 값 자리의 타입은 검사에 쓰지 않으므로 모르는 채로 둡니다.
+    let mut builder = Builder::new(cx, &copy_params, function.name.clone(), Ty::Error);
+    if function.self_param.is_some() {
+        let local = builder.bind_param(function.id, function.span);
+        builder.owned.push(local);
+    }
+    for param in &function.params {
+        builder.bind_pat_param(&param.pat);
+    }
+    builder.body.arg_count = builder.owned.len();
+    let value = builder.lower_block(block, function.span);
+    builder.finish(value, function.span);
+
+    let mut bodies = Vec::new();
+    let mut pending = std::mem::take(&mut builder.closures);
+    bodies.push(builder.body);
+    while let Some(closure) = pending.pop() {
+        let mut builder = Builder::new(cx, &copy_params, closure.name.clone(), cx.node_ty(cx.arena[closure.body].id));
+        for param in &closure.params {
+            builder.bind_pat_param(&param.pat);
+        }
+        builder.body.arg_count = builder.owned.len();
+        let span = cx.arena[closure.body].span;
+        let value = builder.lower_expr(closure.body);
+        builder.finish(value, span);
+        pending.append(&mut builder.closures);
+        bodies.push(builder.body);
+    }
+    bodies
+}
+
+// `T: Copy` 바운드가 있는 타입 매개변수입니다.
+fn copy_params(generics: &Generics) -> impl Iterator<Item = String> + '_ {
+    generics
+        .params
+        .iter()
+        .filter(|param| param.bounds.iter().any(|bound| bound.segments.last().is_some_and(|segment| segment.ident == "Copy")))
+        .map(|param| param.name.clone())
+}
+
+impl Cx<'_> {
+    fn node_ty(&self, id: NodeId) -> Ty {
+        self.results.node_types.get(&id).cloned().unwrap_or(Ty::Error)
+    }
+}
+
+struct Builder<'a, 'b> {
+    cx: &'b Cx<'a>,
+    copy_params: &'b HashSet<String>,
+    body: Body,
+    current: BasicBlock,
+    return_block: BasicBlock,
+    bindings: HashMap<NodeId, Local>,       // 바인딩 패턴의 노드 번호 → 지역 변수
+    scopes: Vec<Vec<Local>>,                // 블록마다 `let`으로 만든 지역 변수
+    loops: Vec<(BasicBlock, BasicBlock)>,   // (`continue`, `break`)가 갈 블록
+    closures: Vec<&'a Closure>,             // 따로 본문을 만들 클로저
+    owned: Vec<Local>,                      // 매개변수와 `let` 바인딩 (캡처한 바깥 변수는 빠집니다)
+}
+
+impl<'a, 'b> Builder<'a, 'b> {
+    fn new(cx: &'b Cx<'a>, copy_params: &'b HashSet<String>, name: String, ret: Ty) -> Self {
+        let mut builder = Builder {
+            cx,
+            copy_params,
+            body: Body { name, arg_count: 0, locals: Vec::new(), blocks: Vec::new() },
+            current: BasicBlock::START,
+            return_block: BasicBlock::START,
+            bindings: HashMap::new(),
+            scopes: Vec::new(),
+            loops: Vec::new(),
+            closures: Vec::new(),
+            owned: Vec::new(),
+        };
+        builder.new_local(None, ret, true, Span::DUMMY);
+        builder.current = builder.new_block();
+        builder.return_block = builder.new_block();
+        builder
+    }
+
+    // 본문의 값을 반환 값 자리에 넣고 반환 블록으로 갑니다.
+    // 반환 블록에서는 매개변수와 `return`으로 빠져나온 블록의 바인딩이 모두 사라집니다. 위치는 닫는 괄호입니다.
+    fn finish(&mut self, value: Operand, span: Span) {
+        self.push(StatementKind::Assign(Place::from_local(Local::RETURN), Rvalue::Use(value)), span);
+        self.goto(self.return_block, span);
+        self.current = self.return_block;
+        let end = span.with_lo(span.hi.saturating_sub(1));
+        for local in std::mem::take(&mut self.owned).into_iter().rev() {
+            self.push(StatementKind::StorageDead(local), end);
+        }
+        self.body.blocks[self.return_block.0 as usize].terminator = Terminator { kind: TerminatorKind::Return, span };
+    }
+
+    fn new_local(&mut self, name: Option<String>, ty: Ty, mutable: bool, span: Span) -> Local {
+        self.body.locals.push(LocalDecl { name, ty, mutable, span });
+        Local(self.body.locals.len() as u32 - 1)
+    }
+
+    fn temp(&mut self, ty: Ty, span: Span) -> Local {
+        self.new_local(None, ty, true, span)
+    }
+
+    fn new_block(&mut self) -> BasicBlock {
+        let terminator = Terminator { kind: TerminatorKind::Unreachable, span: Span::DUMMY };
+        self.body.blocks.push(BasicBlockData { statements: Vec::new(), terminator });
+        BasicBlock(self.body.blocks.len() as u32 - 1)
+    }
+
+    fn push(&mut self, kind: StatementKind, span: Span) {
+        self.body.blocks[self.current.0 as usize].statements.push(Statement { kind, span });
+    }
+
+    // 현재 블록을 끝냅니다. 이후 코드는 새 블록에 쌓이며, 어디서도 오지 않으면 도달할 수 없는 코드입니다.
+    fn terminate(&mut self, kind: TerminatorKind, span: Span) {
+        self.body.blocks[self.current.0 as usize].terminator = Terminator { kind, span };
+        self.current = self.new_block();
+    }
+
+    fn goto(&mut self, target: BasicBlock, span: Span) {
+        self.terminate(TerminatorKind::Goto(target), span);
+    }
+
+    fn node_ty(&self, expr: ExprId) -> Ty {
+        self.cx.node_ty(self.cx.arena[expr].id)
+    }
+
+    // 값으로 쓰여도 원래 자리를 비우지 않는 타입인지 확인합니다. 타입을 모르면 복사로 봅니다.
+    fn is_copy(&self, ty: &Ty) -> bool {
+        match ty {
+            // `&mut T`는 값으로 넘겨도 다시 빌려지므로 이동으로 보지 않습니다.
+            Ty::Ref(..) => true,
+            Ty::Adt(name, _) => self.cx.copy_types.contains(name),
+            Ty::Param(name) => self.copy_params.contains(name),
+            Ty::Array(ty, _) => self.is_copy(ty),
+            Ty::Tuple(tys) => tys.iter().all(|ty| self.is_copy(ty)),
+            Ty::Str | Ty::Slice(_) => false,
+            _ => true,
+        }
+    }
+
+    // 매개변수는 본문이 시작할 때 이미 초기화되어 있습니다.
+    fn bind_param(&mut self, id: NodeId, span: Span) -> Local {
+        let binding = self.cx.resolutions.bindings.get(&id);
+        let name = binding.map(|binding| binding.name.clone());
+        let mutable = binding.is_some_and(|binding| binding.mutable);
+        let local = self.new_local(name, self.cx.node_ty(id), mutable, span);
+        self.bindings.insert(id, local);
+        local
+    }
+
+    // 매개변수 패턴은 변수나 `_`뿐입니다.
+    fn bind_pat_param(&mut self, pat: &Pat) {
+        if let PatKind::Ident { .. } = pat.kind {
+            let local = self.bind_param(pat.id, pat.span);
+            self.owned.push(local);
+        }
+    }
+
+    // 바인딩의 지역 변수입니다. 클로저 본문에서 처음 만난 바깥 변수(캡처)는 초기화된 매개변수처럼 만듭니다.
+    fn local_for(&mut self, binding: NodeId) -> Local {
+        match self.bindings.get(&binding) {
+            Some(&local) => local,
+            None => {
+                let span = self.cx.resolutions.bindings.get(&binding).map_or(Span::DUMMY, |binding| binding.span);
+                self.bind_param(binding, span)
+            },
+        }
+    }
+
+    fn lower_block(&mut self, block: &Block, span: Span) -> Operand {
+        self.scopes.push(Vec::new());
+        let mut value = Operand::Constant;
+        for (i, &stmt) in block.stmts.iter().enumerate() {
+            let stmt = &self.cx.arena[stmt];
+            match &stmt.kind {
+                StmtKind::Let(local) => self.lower_let(local, stmt.span),
+                StmtKind::Item(_) => {},
+                StmtKind::Expr(expr) if i + 1 == block.stmts.len() => value = self.lower_expr(*expr),
+                StmtKind::Expr(expr) | StmtKind::Semi(expr) => {
+                    self.lower_expr(*expr);
+                },
+            }
+        }
+        // 블록이 끝나면 그 안의 바인딩이 뒤에서부터 사라집니다. 위치는 닫는 괄호입니다.
+        let end = span.with_lo(span.hi.saturating_sub(1));
+        for local in self.scopes.pop().unwrap().into_iter().rev() {
+            self.push(StatementKind::StorageDead(local), end);
+        }
+        value
+    }
+
+    fn lower_let(&mut self, local: &LetStmt, span: Span) {
+        match &local.pat.kind {
+            PatKind::Ident { name, mutable } => {
+                let value = local.init.map(|init| self.lower_expr(init));
+                let ty = self.cx.node_ty(local.pat.id);
+                let binding = self.new_local(Some(name.clone()), ty, *mutable, local.pat.span);
+                self.bindings.insert(local.pat.id, binding);
+                self.scopes.last_mut().unwrap().push(binding);
+                self.owned.push(binding);
+                self.push(StatementKind::StorageLive(binding), span);
+                if let Some(value) = value {
+                    self.push(StatementKind::Assign(Place::from_local(binding), Rvalue::Use(value)), span);
+                }
+            },
+            // `let _ = x;`는 `x`를 읽지 않으므로 옮기지도 않습니다. `let` 패턴은 변수나 `_`뿐입니다.
+            _ => match local.init {
+                Some(init) if self.is_place_expr(init) => {
+                    self.as_place(init);
+                },
+                Some(init) => {
+                    self.lower_expr(init);
+                },
+                None => {},
+            },
+        }
+    }
+
+    // 메모리 위치를 가리키는 식인지 확인합니다: 지역 변수, 필드, 인덱스, 역참조.
+    fn is_place_expr(&self, expr: ExprId) -> bool {
+        let node = &self.cx.arena[expr];
+        match &node.kind {
+            NodeKind::Ident(_) => matches!(self.cx.resolutions.uses.get(&node.id), Some(Res::Local(_))),
+            NodeKind::Field(..) | NodeKind::Index(..) => true,
+            NodeKind::Unary(unary) => unary.operator.kind == TokenKind::Star,
+            _ => false,
+        }
+    }
+
+    // 식이 가리키는 위치입니다. 위치가 아닌 식은 임시 값에 담아 그 자리를 돌려줍니다.
+    fn as_place(&mut self, expr: ExprId) -> Place {
+        let arena = self.cx.arena;
+        let node = &arena[expr];
+        match &node.kind {
+            NodeKind::Ident(_) if self.is_place_expr(expr) => {
+                let Some(&Res::Local(binding)) = self.cx.resolutions.uses.get(&node.id) else { unreachable!() };
+                Place::from_local(self.local_for(binding))
+            },
+            NodeKind::Field(base, field) => {
+                let base = self.as_place(*base);
+                self.auto_deref(base).project(PlaceElem::Field(field.clone(), self.node_ty(expr)))
+            },
+            NodeKind::Index(base, index) => {
+                let base = self.as_place(*base);
+                self.lower_expr(*index);
+                self.auto_deref(base).project(PlaceElem::Index(self.node_ty(expr)))
+            },
+            NodeKind::Unary(unary) if unary.operator.kind == TokenKind::Star => self.as_place(unary.operand).project(PlaceElem::Deref),
+            _ => match self.lower_expr(expr) {
+                Operand::Copy(place) | Operand::Move(place) => place,
+                Operand::Constant => {
+                    let temp = self.temp(self.node_ty(expr), node.span);
+                    self.push(StatementKind::Assign(Place::from_local(temp), Rvalue::Use(Operand::Constant)), node.span);
+                    Place::from_local(temp)
+                },
+            },
+        }
+    }
+
+    // 필드 접근과 인덱싱은 참조를 자동으로 따라갑니다.
+    fn auto_deref(&self, mut place: Place) -> Place {
+        while let Ty::Ref(..) = place.ty(&self.body) {
+            place = place.project(PlaceElem::Deref);
+        }
+        place
+    }
+
+    // 값을 임시 값에 담고 그 임시 값을 돌려줍니다.
+    fn assign_temp(&mut self, rvalue: Rvalue, ty: Ty, span: Span) -> Operand {
+        let temp = self.temp(ty, span);
+        self.push(StatementKind::Assign(Place::from_local(temp), rvalue), span);
+        Operand::Move(Place::from_local(temp))
+    }
+
+    // 위치를 빌린 참조를 임시 값에 담습니다.
+    fn borrow(&mut self, mutable: bool, place: Place, span: Span) -> Operand {
+        let ty = Ty::ref_to(mutable, place.ty(&self.body));
+        self.assign_temp(Rvalue::Ref(mutable, place), ty, span)
+    }
+
+    // 위치의 값을 읽습니다. `Copy`가 아닌 타입이면 값을 옮깁니다.
+    fn read_place(&mut self, place: Place, span: Span) -> Operand {
+        let ty = place.ty(&self.body);
+        let operand = if self.is_copy(&ty) { Operand::Copy(place) } else { Operand::Move(place) };
+        self.assign_temp(Rvalue::Use(operand), ty, span)
+    }
+
+    // 식을 계산해 그 값을 돌려줍니다.
+    fn lower_expr(&mut self, expr: ExprId) -> Operand {
+        let arena = self.cx.arena;
+        let node = &arena[expr];
+        let span = node.span;
+        match &node.kind {
+            NodeKind::Int(..) | NodeKind::Float(..) | NodeKind::Char(_) | NodeKind::Bool(_) | NodeKind::Str(_) => Operand::Constant,
+            NodeKind::Path(_) | NodeKind::QualifiedPath(..) => Operand::Constant,
+            NodeKind::Ident(_) if !self.is_place_expr(expr) => Operand::Constant,
+            NodeKind::Ident(_) | NodeKind::Field(..) | NodeKind::Index(..) => {
+                let place = self.as_place(expr);
+                self.read_place(place, span)
+            },
+            NodeKind::Unary(unary) if unary.operator.kind == TokenKind::Star => {
+                let place = self.as_place(expr);
+                self.read_place(place, span)
+            },
+            NodeKind::Unary(unary) => {
+                let operand = self.lower_expr(unary.operand);
+                self.assign_temp(Rvalue::UnaryOp(operand), self.node_ty(expr), span)
+            },
+            NodeKind::Ref { mutable, expr: inner } => {
+                let place = self.as_place(*inner);
+                self.borrow(*mutable, place, span)
+            },
+            NodeKind::BinaryOp(op) if matches!(op.operator.kind, TokenKind::AndAnd | TokenKind::OrOr) => {
+                let dest = Place::from_local(self.temp(Ty::Bool, span));
+                let left = self.lower_expr(op.left);
+                let (right_block, short_block, join) = (self.new_block(), self.new_block(), self.new_block());
+                let (then, els) = match op.operator.kind {
+                    TokenKind::AndAnd => (right_block, short_block),
+                    _ => (short_block, right_block),
+                };
+                self.terminate(TerminatorKind::SwitchBool { cond: left, then, els }, span);
+                self.current = short_block;
+                self.push(StatementKind::Assign(dest.clone(), Rvalue::Use(Operand::Constant)), span);
+                self.goto(join, span);
+                self.current = right_block;
+                let right = self.lower_expr(op.right);
+                self.push(StatementKind::Assign(dest.clone(), Rvalue::Use(right)), span);
+                self.goto(join, span);
+                self.current = join;
+                Operand::Move(dest)
+            },
+            NodeKind::BinaryOp(op) => {
+                // 비교 연산자는 피연산자를 참조로 받으므로 옮기지 않습니다.
+                let compares = matches!(
+                    op.operator.kind,
+                    TokenKind::EqEq | TokenKind::Ne | TokenKind::Lt | TokenKind::Le | TokenKind::Gt | TokenKind::Ge
+                );
+                let operand = |this: &mut Self, expr: ExprId| match compares && this.is_place_expr(expr) {
+                    true => {
+                        let place = this.as_place(expr);
+                        this.borrow(false, place, arena[expr].span)
+                    },
+                    false => this.lower_expr(expr),
+                };
+                let left = operand(self, op.left);
+                let right = operand(self, op.right);
+                self.assign_temp(Rvalue::BinaryOp(left, right), self.node_ty(expr), span)
+            },
+            NodeKind::Assign(assign) => {
+                let value = self.lower_expr(assign.value);
+                let place = self.as_place(assign.target);
+                let rvalue = match assign.operator {
+                    None => Rvalue::Use(value),
+                    Some(_) => Rvalue::BinaryOp(Operand::Copy(place.clone()), value),
+                };
+                self.push(StatementKind::Assign(place, rvalue), span);
+                Operand::Constant
+            },
+            NodeKind::Call(call) => {
+                let callee = &arena[call.callee];
+                let func = match &callee.kind {
+                    NodeKind::Path(_) | NodeKind::QualifiedPath(..) => Operand::Constant,
+                    _ => self.lower_expr(call.callee),
+                };
+                // 메서드 호출에서 자동으로 붙인 `&mut` 리시버는 나머지 인자를 모두 계산한 뒤에 빌립니다 (`v.push(v.len())`).
+                let two_phase = call.args.first().and_then(|&receiver| match arena[receiver].kind {
+                    NodeKind::Ref { mutable: true, expr: inner } if arena[receiver].id == NodeId::DUMMY => Some((self.as_place(inner), arena[receiver].span)),
+                    _ => None,
+                });
+                let skip = two_phase.is_some() as usize;
+                let mut args: Vec<Operand> = call.args.iter().skip(skip).map(|&arg| self.lower_expr(arg)).collect();
+                if let Some((place, receiver_span)) = two_phase {
+                    let receiver = self.borrow(true, place, receiver_span);
+                    args.insert(0, receiver);
+                }
+                self.call(func, args, self.node_ty(expr), span)
+            },
+            NodeKind::MethodCall(call) => {
+                let receiver = self.lower_expr(call.receiver);
+                let args = [receiver].into_iter().chain(call.args.iter().map(|&arg| self.lower_expr(arg))).collect();
+                self.call(Operand::Constant, args, self.node_ty(expr), span)
+            },
+            NodeKind::FormatArgs(format) => {
+                // 포맷 인자는 참조로 전달됩니다.
+                let args = format
+                    .args
+                    .iter()
+                    .map(|arg| {
+                        let place = self.as_place(arg.expr);
+                        self.borrow(false, place, arena[arg.expr].span)
+                    })
+                    .collect();
+                self.assign_temp(Rvalue::Aggregate(args), self.node_ty(expr), span)
+            },
+            NodeKind::StructLit(lit) => {
+                let fields = lit.fields.iter().map(|&(_, value)| self.lower_expr(value)).collect();
+                self.assign_temp(Rvalue::Aggregate(fields), self.node_ty(expr), span)
+            },
+            NodeKind::Tuple(elems) if elems.is_empty() => Operand::Constant,
+            NodeKind::Array(elems) | NodeKind::Tuple(elems) => {
+                let elems = elems.iter().map(|&elem| self.lower_expr(elem)).collect();
+                self.assign_temp(Rvalue::Aggregate(elems), self.node_ty(expr), span)
+            },
+            NodeKind::Repeat(value, _) => {
+                let value = self.lower_expr(*value);
+                self.assign_temp(Rvalue::Aggregate(vec![value]), self.node_ty(expr), span)
+            },
+            NodeKind::Range(range) => {
+                let bounds = range.start.iter().chain(&range.end).map(|&bound| self.lower_expr(bound)).collect();
+                self.assign_temp(Rvalue::Aggregate(bounds), self.node_ty(expr), span)
+            },
+            NodeKind::Block(block) => self.lower_block(block, span),
+            NodeKind::If(if_expr) => {
+                let dest = Place::from_local(self.temp(self.node_ty(expr), span));
+                let cond = self.lower_expr(if_expr.cond);
+                let (then, els, join) = (self.new_block(), self.new_block(), self.new_block());
+                self.terminate(TerminatorKind::SwitchBool { cond, then, els }, arena[if_expr.cond].span);
+
+                self.current = then;
+                let then_span = match if_expr.els {
+                    Some(els) => span.with_hi(arena[els].span.lo),
+                    None => span,
+                };
+                let value = self.lower_block(&if_expr.then, then_span);
+                self.push(StatementKind::Assign(dest.clone(), Rvalue::Use(value)), span);
+                self.goto(join, span);
+
+                self.current = els;
+                let value = if_expr.els.map_or(Operand::Constant, |els| self.lower_expr(els));
+                self.push(StatementKind::Assign(dest.clone(), Rvalue::Use(value)), span);
+                self.goto(join, span);
+
+                self.current = join;
+                Operand::Move(dest)
+            },
+            NodeKind::Match(match_expr) => self.lower_match(match_expr, self.node_ty(expr), span),
+            NodeKind::While(while_loop) => {
+                let (header, body, exit) = (self.new_block(), self.new_block(), self.new_block());
+                self.goto(header, span);
+                self.current = header;
+                let cond = self.lower_expr(while_loop.cond);
+                self.terminate(TerminatorKind::SwitchBool { cond, then: body, els: exit }, arena[while_loop.cond].span);
+                self.current = body;
+                self.lower_loop_body(&while_loop.body, span, header, exit);
+                Operand::Constant
+            },
+            NodeKind::Loop(block) => {
+                let (body, exit) = (self.new_block(), self.new_block());
+                self.goto(body, span);
+                self.current = body;
+                self.lower_loop_body(block, span, body, exit);
+                Operand::Constant
+            },
+            NodeKind::ForLoop(for_loop) => {
+                // `let mut iter = into_iter(v); loop { match next(&mut iter) { Some(x) => body, None => break } }`
+                let iter_span = arena[for_loop.iter].span;
+                let value = self.lower_expr(for_loop.iter);
+                let iter_ty = self.node_ty(for_loop.iter);
+                let iter = Place::from_local(self.temp(iter_ty, iter_span));
+                self.push(StatementKind::Assign(iter.clone(), Rvalue::Use(value)), iter_span);
+
+                let (header, check, body, exit) = (self.new_block(), self.new_block(), self.new_block(), self.new_block());
+                self.goto(header, span);
+                self.current = header;
+                // `next`가 돌려준 원소는 반복자 자체가 아니라 반복자가 빌린 값을 빌리므로, 반복자는 빌리지 않고 그대로 넘깁니다.
+                let next = Place::from_local(self.temp(self.cx.node_ty(for_loop.pat.id), for_loop.pat.span));
+                let args = vec![Operand::Copy(iter)];
+                let kind = TerminatorKind::Call { func: Operand::Constant, args, dest: next.clone(), target: Some(check) };
+                self.terminate(kind, iter_span);
+                self.current = check;
+                self.terminate(TerminatorKind::SwitchBool { cond: Operand::Constant, then: body, els: exit }, iter_span);
+
+                self.current = body;
+                self.scopes.push(Vec::new());
+                if let PatKind::Ident { name, mutable } = &for_loop.pat.kind {
+                    let binding = self.new_local(Some(name.clone()), next.ty(&self.body), *mutable, for_loop.pat.span);
+                    self.bindings.insert(for_loop.pat.id, binding);
+                    self.scopes.last_mut().unwrap().push(binding);
+                    self.owned.push(binding);
+                    self.push(StatementKind::StorageLive(binding), for_loop.pat.span);
+                    self.push(StatementKind::Assign(Place::from_local(binding), Rvalue::Use(Operand::Move(next))), for_loop.pat.span);
+                }
+                self.lower_loop_body(&for_loop.body, span, header, exit);
+                Operand::Constant
+            },
+            NodeKind::Break | NodeKind::Continue => {
+                if let Some(&(continue_block, break_block)) = self.loops.last() {
+                    let target = if matches!(node.kind, NodeKind::Break) { break_block } else { continue_block };
+                    self.goto(target, span);
+                }
+                Operand::Constant
+            },
+            NodeKind::Return(value) => {
+                let value = value.map_or(Operand::Constant, |value| self.lower_expr(value));
+                self.push(StatementKind::Assign(Place::from_local(Local::RETURN), Rvalue::Use(value)), span);
+                self.goto(self.return_block, span);
+                Operand::Constant
+            },
+            NodeKind::Closure(closure) => {
+                let captures = closure.captures.iter().map(|capture| self.lower_capture(closure, &capture.name, capture.mode, span)).collect();
+                self.closures.push(closure);
+                self.assign_temp(Rvalue::Aggregate(captures), self.node_ty(expr), span)
+            },
+        }
+    }
+
+    // `match`는 갈래마다 패턴을 검사하는 블록을 두고, 맞으면 바인딩에 값을 넣은 뒤 가드와 본문으로, 아니면 다음 갈래의 검사로 갑니다.
+    // 패턴과 비교할 때는 값을 읽기만 하고, 바인딩이 그 자리의 값을 복사하거나 옮깁니다.
+    // or 패턴은 대안마다 검사와 바인딩을 따로 만들고, 이름이 같은 바인딩은 첫 대안의 지역 변수를 함께 씁니다.
+    fn lower_match(&mut self, match_expr: &Match, ty: Ty, span: Span) -> Operand {
+        let arena = self.cx.arena;
+        let dest = Place::from_local(self.temp(ty, span));
+        let scrutinee = self.as_place(match_expr.scrutinee);
+        let scrutinee_span = arena[match_expr.scrutinee].span;
+        let join = self.new_block();
+        let mut next = self.new_block();
+        self.goto(next, span);
+        for arm in &match_expr.arms {
+            let mut locals = HashMap::new();
+            for binding in arm.pat.bindings() {
+                let PatKind::Ident { name, mutable } = &binding.kind else { continue };
+                let local = self.new_local(Some(name.clone()), self.cx.node_ty(binding.id), *mutable, binding.span);
+                self.bindings.insert(binding.id, local);
+                self.owned.push(local);
+                locals.insert(name.clone(), local);
+            }
+
+            let body = self.new_block();
+            for pat in arm.pat.alternatives() {
+                self.current = next;
+                next = self.new_block();
+                let mut refutable = false;
+                pat.walk(&mut |pat| refutable |= matches!(pat.kind, PatKind::Lit(_) | PatKind::Range(..)));
+                if refutable {
+                    let matched = self.new_block();
+                    let cond = Operand::Copy(scrutinee.clone());
+                    self.terminate(TerminatorKind::SwitchBool { cond, then: matched, els: next }, scrutinee_span);
+                    self.current = matched;
+                }
+
+                // 가드가 있으면 가드가 통과한 뒤에야 값을 옮기므로, 가드를 검사하는 동안은 복사한 것처럼 읽습니다.
+                let mut moves = Vec::new();
+                self.bind_match_pat(&pat, scrutinee.clone(), &locals, arm.guard.is_some(), &mut moves);
+                if let Some(guard) = arm.guard {
+                    let cond = self.lower_expr(guard);
+                    let passed = self.new_block();
+                    self.terminate(TerminatorKind::SwitchBool { cond, then: passed, els: next }, arena[guard].span);
+                    self.current = passed;
+                    for (place, span) in moves {
+                        let ty = place.ty(&self.body);
+                        self.assign_temp(Rvalue::Use(Operand::Move(place)), ty, span);
+                    }
+                }
+                self.goto(body, span);
+            }
+
+            self.current = body;
+            let value = self.lower_expr(arm.body);
+            self.push(StatementKind::Assign(dest.clone(), Rvalue::Use(value)), span);
+            let end = arena[arm.body].span;
+            let end = end.with_lo(end.hi.saturating_sub(1));
+            let mut locals: Vec<Local> = locals.into_values().collect();
+            locals.sort_by_key(|local| std::cmp::Reverse(local.0));
+            for local in locals {
+                self.push(StatementKind::StorageDead(local), end);
+            }
+            self.goto(join, span);
+        }
+        // 어느 갈래에도 맞지 않는 값은 타입 검사가 이미 막았으므로 마지막 검사 블록은 도달할 수 없습니다.
+        self.current = join;
+        Operand::Move(dest)
+    }
+
+    // 패턴의 바인딩에 `place` 안의 자리를 넣습니다. `deferred`면 옮겨야 할 자리를 복사로 읽고 `moves`에 남깁니다.
+    fn bind_match_pat(&mut self, pat: &Pat, place: Place, locals: &HashMap<String, Local>, deferred: bool, moves: &mut Vec<(Place, Span)>) {
+        match &pat.kind {
+            PatKind::Ident { name, .. } => {
+                let local = locals[name];
+                let copy = self.is_copy(&place.ty(&self.body));
+                if deferred && !copy {
+                    moves.push((place.clone(), pat.span));
+                }
+                let operand = if copy || deferred { Operand::Copy(place) } else { Operand::Move(place) };
+                self.push(StatementKind::StorageLive(local), pat.span);
+                self.push(StatementKind::Assign(Place::from_local(local), Rvalue::Use(operand)), pat.span);
+            },
+            PatKind::Tuple(pats) => {
+                for (i, field) in pats.iter().enumerate() {
+                    let place = place.project(PlaceElem::Field(i.to_string(), self.cx.node_ty(field.id)));
+                    self.bind_match_pat(field, place, locals, deferred, moves);
+                }
+            },
+            PatKind::Struct { fields, .. } => {
+                for (name, field) in fields {
+                    let place = place.project(PlaceElem::Field(name.clone(), self.cx.node_ty(field.id)));
+                    self.bind_match_pat(field, place, locals, deferred, moves);
+                }
+            },
+            PatKind::Wild | PatKind::Lit(_) | PatKind::Range(..) => {},
+            PatKind::Or(_) => unreachable!("or-patterns are expanded before lowering"),
+        }
+    }
+
+    // 반복 본문을 만들고 루프 머리로 돌아갑니다. 루프를 빠져나오면 `exit` 블록에서 이어집니다.
+    fn lower_loop_body(&mut self, block: &Block, span: Span, continue_block: BasicBlock, exit: BasicBlock) {
+        self.loops.push((continue_block, exit));
+        self.lower_block(block, span);
+        self.loops.pop();
+        self.goto(continue_block, span.with_lo(span.hi.saturating_sub(1)));
+        self.current = exit;
+    }
+
+    // 클로저가 캡처한 바깥 변수를 방식대로 넘깁니다: 참조, 가변 참조, 또는 값.
+    fn lower_capture(&mut self, closure: &Closure, name: &str, mode: CaptureMode, span: Span) -> Operand {
+        let arena = self.cx.arena;
+        let binding = arena.descendants(AstId::Expr(closure.body)).find_map(|id| match id {
+            AstId::Expr(expr) => match (&arena[expr].kind, self.cx.resolutions.uses.get(&arena[expr].id)) {
+                (NodeKind::Ident(ident), Some(&Res::Local(binding))) if ident == name && self.bindings.contains_key(&binding) => Some(binding),
+                _ => None,
+            },
+            _ => None,
+        });
+        let Some(binding) = binding else { return Operand::Constant };
+        let place = Place::from_local(self.local_for(binding));
+        match mode {
+            CaptureMode::ByValue => self.read_place(place, span),
+            CaptureMode::ByRef | CaptureMode::ByMutRef => self.borrow(mode == CaptureMode::ByMutRef, place, span),
+        }
+    }
+
+    fn call(&mut self, func: Operand, args: Vec<Operand>, ty: Ty, span: Span) -> Operand {
+        let diverges = ty == Ty::Never;
+        let dest = Place::from_local(self.temp(ty, span));
+        let target = (!diverges).then(|| self.new_block());
+        self.terminate(TerminatorKind::Call { func, args, dest: dest.clone(), target }, span);
+        if let Some(target) = target {
+            self.current = target;
+        }
+        Operand::Move(dest)
+    }
+}
@@ -0,0 +1,478 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use rusttc_ast::ast::{AstArena, Crate, Item, MetaItemKind, NestedMeta, StructDef, TyKind};
+use rusttc_ast::visit::{self, Visitor};
+use rusttc_ir::resolve::Resolutions;
+use rusttc_lexer::Span;
+use rusttc_typeck::TypeckResults;
+
+mod borrows;
+mod build;
+pub mod mir;
+mod moves;
+mod mutability;
+
+use build::Cx;
+
+// 소유권 검사 중에 발생한 오류입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BorrowckError {
+    pub message: String,
+    pub span: Span,                     // 잘못 쓴 곳
+    pub labels: Vec<(Span, String)>,    // 관련된 곳과 그 설명 (값이 이동한 곳, 빌린 곳)
+    pub note: Option<String>,
+    pub suggestion: Option<Suggestion>,
+}
+
+// 그대로 적용할 수 있는 고침입니다. 소스의 `span` 부분을 `replacement`로 바꾸면 됩니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub message: String,                // "consider making this binding mutable"
+    pub span: Span,
+    pub replacement: String,            // "mut x"
+}
+
+impl BorrowckError {
+    fn new(message: impl Into<String>, span: Span) -> Self {
+        BorrowckError { message: message.into(), span, labels: Vec::new(), note: None, suggestion: None }
+    }
+
+    fn with_label(mut self, span: Span, label: impl Into<String>) -> Self {
+        self.labels.push((span, label.into()));
+        self
+    }
+
+    fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestion = Some(suggestion);
+        self
+    }
+}
+
+// 잘못 쓴 곳 다음에 관련된 곳을 `::: 위치: 설명`으로 하나씩 출력합니다.
+// 소스 맵에 파일이 없어 위치를 모르면 설명만 출력합니다.
+impl fmt::Display for BorrowckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)?;
+        if let Some(location) = self.span.location() {
+            write!(f, "\n  --> {}", location)?;
+        }
+        for (span, label) in &self.labels {
+            match span.location() {
+                Some(location) => write!(f, "\n  ::: {}: {}", location, label)?,
+                None => write!(f, "\n  ::: {}", label)?,
+            }
+        }
+        if let Some(note) = &self.note {
+            write!(f, "\n  = note: {}", note)?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "\n  = help: {}: `{}`", suggestion.message, suggestion.replacement)?;
+        }
+        Ok(())
+    }
+}
+
+// 크레이트의 모든 함수와 클로저 본문을 제어 흐름 그래프(`mir::Body`)로 바꿔 가변성, 소유권, 빌림 규칙을 검사합니다.
+// 타입 검사를 마친 크레이트(`rusttc_typeck::write_back` 이후)를 받습니다.
+pub fn check_crate(krate: &Crate, resolutions: &Resolutions, results: &TypeckResults) -> Result<(), Vec<BorrowckError>> {
+    let mut collector = ItemCollector { krate, items: Vec::new() };
+    collector.visit_crate(krate);
+    let cx = Cx { arena: &krate.arena, resolutions, results, copy_types: copy_types(&collector.items) };
+
+    let mut errors = Vec::new();
+    for item in &collector.items {
+        let bodies = match item {
+            Item::Fn(function) => build::build_fn(&cx, function, None),
+            Item::Impl(imp) => imp.items.iter().flat_map(|function| build::build_fn(&cx, function, Some(imp))).collect(),
+            _ => Vec::new(),
+        };
+        for body in &bodies {
+            mutability::check(body, &mut errors);
+            moves::check(body, &mut errors);
+            borrows::check(body, &mut errors);
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+// 블록 안의 아이템까지 포함해 크레이트의 모든 아이템을 모읍니다.
+struct ItemCollector<'a> {
+    krate: &'a Crate,
+    items: Vec<&'a Item>,
+}
+
+impl<'a> Visitor<'a> for ItemCollector<'a> {
+    fn arena(&self) -> &'a AstArena {
+        &self.krate.arena
+    }
+
+    fn visit_item(&mut self, item: &'a Item) {
+        self.items.push(item);
+        visit::walk_item(self, item);
+    }
+}
+
+// `Copy`를 구현한 구조체의 이름입니다. `#[derive(Copy)]`와 `impl Copy for T`를 모두 봅니다.
+fn copy_types(items: &[&Item]) -> HashSet<String> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(def) if derives_copy(def) => Some(def.name.clone()),
+            Item::Impl(imp) if imp.of_trait.as_ref().and_then(|path| path.segments.last()).is_some_and(|segment| segment.ident == "Copy") => {
+                match &imp.self_ty.kind {
+                    TyKind::Path(path) => Some(path.segments.iter().map(|segment| segment.ident.as_str()).collect::<Vec<_>>().join("::")),
+                    _ => None,
+                }
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn derives_copy(def: &StructDef) -> bool {
+    def.attrs.iter().filter(|attr| attr.has_name("derive")).any(|attr| match &attr.meta.kind {
+        MetaItemKind::List(list) => list.iter().any(|nested| matches!(nested, NestedMeta::Meta(meta) if meta.path.as_ident() == Some("Copy"))),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusttc_expand::expand;
+    use rusttc_ir::method::resolve_methods;
+    use rusttc_ir::modules::resolve_modules;
+    use rusttc_ir::resolve::resolve_names;
+    use rusttc_lexer::{source_map, tokenize};
+    use rusttc_parse::parser::parse_crate;
+
+    // 소유권 검사까지 마친 오류를 돌려줍니다. 앞 단계의 오류는 테스트 실패입니다.
+    fn check(source: &str) -> Vec<BorrowckError> {
+        let mut krate = resolve_modules(&parse_crate(&expand(&tokenize(source)).unwrap()).unwrap()).unwrap();
+        let resolutions = resolve_names(&krate).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let results = rusttc_typeck::check_crate(&krate, &resolutions).unwrap();
+        rusttc_typeck::write_back(&mut krate, &results);
+        check_crate(&krate, &resolutions, &results).err().unwrap_or_default()
+    }
+
+    fn messages(source: &str) -> Vec<String> {
+        check(source).into_iter().map(|error| error.message).collect()
+    }
+
+    // 소스에서 `context`가 처음 나오는 곳 안의 `text` 위치입니다.
+    fn span_of(source: &str, context: &str, text: &str) -> Span {
+        let lo = (source.find(context).unwrap() + context.find(text).unwrap()) as u32;
+        Span::new(lo, lo + text.len() as u32)
+    }
+
+    const S: &str = "struct S { v: i32 } fn take(s: S) -> i32 { s.v } fn fresh() -> S { S { v: 0 } }";
+
+    #[test]
+    fn test_use_after_move_points_at_move_site() {
+        let source = format!("{} fn main() {{ let s = fresh(); let t = s; let u = s; }}", S);
+        let errors = check(&source);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "error: use of moved value: `s`\n  ::: value moved here\n  ::: value used here after move\n  \
+             = note: move occurs because `s` has type `S`, which does not implement the `Copy` trait",
+        );
+        assert_eq!(errors[0].span, span_of(&source, "u = s", "s"));
+        assert_eq!(
+            errors[0].labels,
+            [
+                (span_of(&source, "t = s", "s"), "value moved here".to_string()),
+                (errors[0].span, "value used here after move".to_string()),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_rendered_move_errors_show_move_site_and_later_use() {
+        let source = format!("{}\nfn main() {{\n    let s = fresh();\n    let t = s;\n    take(s);\n}}", S);
+        source_map::clear();
+        source_map::add_file("main.rs", &source);
+        assert_eq!(
+            check(&source)[0].to_string(),
+            "error: use of moved value: `s`\n  \
+             --> main.rs:5:10\n  \
+             ::: main.rs:4:13: value moved here\n  \
+             ::: main.rs:5:10: value used here after move\n  \
+             = note: move occurs because `s` has type `S`, which does not implement the `Copy` trait",
+        );
+
+        let source = format!("{}\nfn main() {{\n    let w = fresh();\n    loop {{\n        take(w);\n    }}\n}}", S);
+        source_map::clear();
+        source_map::add_file("main.rs", &source);
+        assert_eq!(
+            check(&source)[0].to_string(),
+            "error: use of moved value: `w`\n  \
+             --> main.rs:5:14\n  \
+             ::: main.rs:5:14: value moved here, in previous iteration of loop\n  \
+             ::: main.rs:5:14: value used here after move\n  \
+             = note: move occurs because `w` has type `S`, which does not implement the `Copy` trait",
+        );
+        source_map::clear();
+    }
+
+    #[test]
+    fn test_arguments_fields_and_loops_move() {
+        let source = format!("{} fn main() {{ let a = fresh(); take(a); let r = &a; }}", S);
+        assert_eq!(messages(&source), ["borrow of moved value: `a`"]);
+
+        let source = format!(
+            "{} struct P {{ name: S, age: u32 }} fn main() {{ let p = P {{ name: fresh(), age: 1 }}; let n = p.name; let a = p.age; let q = p; }}",
+            S,
+        );
+        assert_eq!(messages(&source), ["use of partially moved value: `p`"]);
+
+        let source = format!("{} fn main() {{ let w = fresh(); loop {{ take(w); }} }}", S);
+        let errors = check(&source);
+        assert_eq!(errors[0].message, "use of moved value: `w`");
+        assert_eq!(errors[0].labels[0].1, "value moved here, in previous iteration of loop");
+
+        let source = format!("{} fn main() {{ let m = fresh(); let f = move || take(m); let n = m; }}", S);
+        assert_eq!(messages(&source), ["use of moved value: `m`"]);
+    }
+
+    #[test]
+    fn test_copy_types_and_reinitialization_do_not_move() {
+        let source = format!(
+            "{}
+            #[derive(Clone, Copy)]
+            struct C {{ x: i32 }}
+            struct D {{ x: i32 }}
+            impl Copy for D {{}}
+            impl PartialEq for S {{}}
+            fn pair<T: Copy>(t: T) -> (T, T) {{ (t, t) }}
+            fn main() {{
+                let c = C {{ x: 1 }}; let d = c; let e = c.x + d.x;
+                let k = D {{ x: 1 }}; let l = k; let m = k;
+                let n = 1; let o = n; let p = [n, o]; let q = p; let r = p;
+                let mut s = fresh(); let t = s; s = fresh(); take(s);
+                let u = fresh(); let v = &u; let w = &u; let z = u == u;
+                let _ = u; take(u);
+            }}",
+            S,
+        );
+        assert_eq!(messages(&source), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_conditional_moves_and_uninitialized_bindings() {
+        let source = format!("{} fn f(c: bool) {{ let s = fresh(); if c {{ take(s); }} let k = s.v; }}", S);
+        assert_eq!(messages(&source), ["use of moved value: `s`"]);
+
+        let source = format!("{} fn f(c: bool) {{ let s = fresh(); if c {{ take(s); }} else {{ take(s); }} }}", S);
+        assert_eq!(messages(&source), Vec::<String>::new());
+
+        let source = "fn f(c: bool) -> i32 { let x: i32; let y; if c { y = 1; } else { y = 2; } x + y }";
+        assert_eq!(messages(source), ["used binding `x` isn't initialized"]);
+    }
+
+    #[test]
+    fn test_match_bindings_move_matched_places() {
+        // 바인딩이 옮긴 필드만 이동하고, 가드가 실패한 갈래는 값을 옮기지 않습니다.
+        let source = format!(
+            "{} struct P {{ name: S, age: i32 }} fn f(p: P) -> i32 {{
+                let n = match p {{ P {{ name, age }} if age > 1 => take(name), P {{ name: s, age: 0 }} => s.v, P {{ age, .. }} => age }};
+                let a = p.age;
+                let q = p;
+                a
+            }}",
+            S,
+        );
+        let errors = check(&source);
+        assert_eq!(errors.iter().map(|error| error.message.as_str()).collect::<Vec<_>>(), ["use of partially moved value: `p`"]);
+        assert_eq!(errors[0].labels[0], (span_of(&source, "P { name, age }", "name"), "value moved here".to_string()));
+
+        // 패턴과 비교만 하는 `match`는 값을 옮기지 않고, 빌린 동안에는 바꿀 수 없는 값을 읽습니다.
+        let source = format!("{} fn f(mut s: S) {{ let r = &mut s; match s {{ S {{ v: 0 }} => {{}} _ => {{}} }} r.v = 1; }}", S);
+        assert_eq!(messages(&source), ["cannot use `s` because it was mutably borrowed"]);
+        let source = format!("{} fn f(s: S) -> S {{ match s {{ S {{ v: 0 }} => {{}} _ => {{}} }} s }}", S);
+        assert_eq!(messages(&source), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_cannot_move_out_of_reference() {
+        let source = format!("{} struct P {{ name: S }} fn f(p: &P) -> S {{ p.name }} fn g(v: &[S]) -> S {{ v[0] }}", S);
+        assert_eq!(
+            messages(&source),
+            ["cannot move out of `p.name` which is behind a shared reference", "cannot move out of index of `[S]`"],
+        );
+    }
+
+    const V: &str = "struct V { n: i32 } impl V { fn push(&mut self, x: i32) { self.n = x; } fn len(&self) -> i32 { self.n } }";
+
+    #[test]
+    fn test_non_lexical_and_two_phase_borrows_are_accepted() {
+        let source = format!(
+            "{}
+            fn bump(r: &mut V) {{ r.push(r.len()); let k = &mut *r; k.push(2); r.push(3); }}
+            fn main() {{
+                let mut v = V {{ n: 0 }};
+                v.push(v.len());
+                let r = &v; let n = r.len(); v.push(n);
+                let m = &mut v; m.push(1); v.push(2);
+                let a = &mut v.n; let b = &v; let c = b.len();
+                let mut s = 0; {{ let t = &mut s; *t = 2; }} s = 3;
+                let mut count = 0; let mut inc = || count += 1; inc(); inc(); println!(\"{{}}\", count);
+                for e in [1, 2].iter() {{ let x = &mut v; x.push(*e); }}
+            }}",
+            V,
+        );
+        assert_eq!(messages(&source), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_conflicting_borrow_points_at_borrow_and_later_use() {
+        let source = format!("{} fn main() {{ let mut v = V {{ n: 0 }}; let r = &v; v.push(2); let n = r.len(); }}", V);
+        let errors = check(&source);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "cannot borrow `v` as mutable because it is also borrowed as immutable");
+        assert_eq!(errors[0].span, span_of(&source, "v.push(2)", "v"));
+        assert_eq!(
+            errors[0].labels,
+            [
+                (span_of(&source, "r = &v", "&v"), "immutable borrow occurs here".to_string()),
+                (errors[0].span, "mutable borrow occurs here".to_string()),
+                (span_of(&source, "r.len()", "r"), "immutable borrow later used here".to_string()),
+            ],
+        );
+
+        let source = format!("{} fn main() {{ let mut v = V {{ n: 0 }}; let a = &mut v; let b = &mut v; a.push(1); }}", V);
+        assert_eq!(messages(&source), ["cannot borrow `v` as mutable more than once at a time"]);
+    }
+
+    #[test]
+    fn test_rendered_errors_show_borrow_sites() {
+        let source = format!("{}\nfn main() {{\n    let mut v = V {{ n: 0 }};\n    let a = &mut v;\n    let b = &mut v;\n    a.push(1);\n}}", V);
+        source_map::clear();
+        source_map::add_file("main.rs", &source);
+        assert_eq!(
+            check(&source)[0].to_string(),
+            "error: cannot borrow `v` as mutable more than once at a time\n  \
+             --> main.rs:5:13\n  \
+             ::: main.rs:4:13: first mutable borrow occurs here\n  \
+             ::: main.rs:5:13: second mutable borrow occurs here\n  \
+             ::: main.rs:6:5: first borrow later used here",
+        );
+        source_map::clear();
+    }
+
+    #[test]
+    fn test_assign_move_and_use_while_borrowed() {
+        let source = format!(
+            "{} fn keep(s: &S) -> i32 {{ s.v }}
+            fn main() {{
+                let mut x = 1; let rx = &x; x = 2; let u = *rx;
+                let s = fresh(); let rs = &s; let t = s; keep(rs);
+                let mut k = 1; let rk = &mut k; let kk = k; *rk = 3;
+            }}",
+            S,
+        );
+        assert_eq!(
+            messages(&source),
+            [
+                "cannot assign to `x` because it is borrowed",
+                "cannot move out of `s` because it is borrowed",
+                "cannot use `k` because it was mutably borrowed",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_references_outliving_their_value() {
+        let source = "fn main() { let r; { let x = 1; r = &x; } let y = *r; }";
+        let errors = check(source);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "`x` does not live long enough");
+        assert_eq!(errors[0].labels[1], (span_of(source, "; } let", "}"), "`x` dropped here while still borrowed".to_string()));
+        assert_eq!(errors[0].labels[2], (span_of(source, "y = *r", "*r"), "borrow later used here".to_string()));
+
+        let source = "fn local() -> &i32 { let x = 1; &x } fn param(x: i32) -> &i32 { return &x; }";
+        assert_eq!(
+            messages(source),
+            ["cannot return reference to local variable `x`", "cannot return reference to function parameter `x`"],
+        );
+    }
+
+    #[test]
+    fn test_assign_twice_suggests_mut_binding() {
+        let source = "fn main() { let x = 1; x = 2; }";
+        let errors = check(source);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "error: cannot assign twice to immutable variable `x`\n  ::: first assignment to `x`\n  \
+             ::: cannot assign twice to immutable variable\n  = help: consider making this binding mutable: `mut x`",
+        );
+        assert_eq!(errors[0].labels[0], (span_of(source, "let x = 1;", "let x = 1;"), "first assignment to `x`".to_string()));
+
+        // 제안을 그대로 적용하면 오류가 사라집니다.
+        let suggestion = errors[0].suggestion.clone().unwrap();
+        let mut fixed = source.to_string();
+        fixed.replace_range(suggestion.span.lo as usize..suggestion.span.hi as usize, &suggestion.replacement);
+        assert_eq!(fixed, "fn main() { let mut x = 1; x = 2; }");
+        assert_eq!(messages(&fixed), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_mutable_borrows_and_writes_need_mutable_places() {
+        let source = format!(
+            "{}
+            fn arg(n: i32) {{ n = 2; }}
+            fn shared(r: &V) {{ r.n = 1; let k = &mut r.n; }}
+            fn main() {{
+                let v = V {{ n: 0 }}; v.push(1);
+                let w = V {{ n: 0 }}; w.n = 3;
+                let c = 0; let mut inc = || c += 1; inc();
+            }}",
+            V,
+        );
+        assert_eq!(
+            messages(&source),
+            [
+                "cannot assign to immutable argument `n`",
+                "cannot assign to `r.n`, which is behind a `&` reference",
+                "cannot borrow `r.n` as mutable, as it is behind a `&` reference",
+                "cannot borrow `v` as mutable, as it is not declared as mutable",
+                "cannot assign to `w.n`, as `w` is not declared as mutable",
+                "cannot borrow `c` as mutable, as it is not declared as mutable",
+            ],
+        );
+        let errors = check(&source);
+        assert!(errors[2].suggestion.is_none());
+        assert_eq!(errors[3].suggestion.as_ref().unwrap().replacement, "mut v");
+    }
+
+    #[test]
+    fn test_deferred_initialization_and_mutable_references() {
+        let source = format!(
+            "{}
+            fn write(m: &mut V) {{ m.n = 3; let r = &mut *m; r.n = 4; m.push(5); }}
+            fn main(c: bool) {{
+                let y; y = 1;
+                let z; if c {{ z = 1; }} else {{ z = 2; }}
+                let mut v = V {{ n: 0 }}; let r = &mut v; r.n = 2; write(r);
+                for i in [1, 2].iter() {{ let q = *i; }}
+            }}",
+            V,
+        );
+        assert_eq!(messages(&source), Vec::<String>::new());
+
+        let source = "fn main() { let l; loop { l = 1; } }";
+        assert_eq!(messages(source), ["cannot assign twice to immutable variable `l`"]);
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt;
+
+use rusttc_lexer::Span;
+use rusttc_typeck::ty::Ty;
+
+// 함수 본문 하나를 기본 블록의 제어 흐름 그래프로 나타낸 것입니다.
+// 모든 중간 값은 지역 변수(임시 값 포함)에 담기고, 문장마다 원본 위치가 붙습니다.
+#[derive(Debug)]
+pub struct Body {
+    pub name: String,                   // 함수 이름 (클로저는 `main::{closure#0}`)
+    pub arg_count: usize,               // 매개변수 수로, `_1`부터 차례로 매개변수입니다
+    pub locals: Vec<LocalDecl>,         // `_0`은 반환 값입니다
+    pub blocks: Vec<BasicBlockData>,    // `bb0`이 시작 블록입니다
+}
+
+// 지역 변수 번호입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Local(pub u32);
+
+impl Local {
+    pub const RETURN: Local = Local(0);
+}
+
+// 기본 블록 번호입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BasicBlock(pub u32);
+
+impl BasicBlock {
+    pub const START: BasicBlock = BasicBlock(0);
+}
+
+// 지역 변수 하나의 정보입니다.
+#[derive(Debug, Clone)]
+pub struct LocalDecl {
+    pub name: Option<String>,   // 바인딩 이름 (임시 값은 없습니다)
+    pub ty: Ty,
+    pub mutable: bool,          // `let mut`
+    pub span: Span,             // 바인딩 패턴이나 임시 값을 만든 식의 위치
+}
+
+// 문장을 차례로 실행한 뒤 종결자로 다음 블록을 고르는 기본 블록입니다.
+#[derive(Debug, Clone)]
+pub struct BasicBlockData {
+    pub statements: Vec<Statement>,
+    pub terminator: Terminator,
+}
+
+// 메모리 위치입니다. 지역 변수에서 시작해 필드, 역참조, 인덱스를 차례로 따라갑니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Place {
+    pub local: Local,
+    pub projection: Vec<PlaceElem>,
+}
+
+// 위치를 한 단계 따라가는 방법입니다. 필드와 인덱스에는 따라간 결과의 타입이 붙습니다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlaceElem {
+    Deref,                      // *p
+    Field(String, Ty),          // p.x, p.0
+    Index(Ty),                  // p[i]
+}
+
+impl Place {
+    pub fn from_local(local: Local) -> Self {
+        Place { local, projection: Vec::new() }
+    }
+
+    pub fn project(&self, elem: PlaceElem) -> Place {
+        let mut projection = self.projection.clone();
+        projection.push(elem);
+        Place { local: self.local, projection }
+    }
+
+    // `self`가 `other`의 앞부분인지 확인합니다. `p`는 `p.x`의 앞부분입니다.
+    pub fn is_prefix_of(&self, other: &Place) -> bool {
+        self.local == other.local
+            && self.projection.len() <= other.projection.len()
+            && self.projection.iter().zip(&other.projection).all(|(a, b)| elem_eq(a, b))
+    }
+
+    // 두 위치가 같은 메모리를 가리킬 수 있는지 확인합니다. 한쪽이 다른 쪽의 앞부분이면 겹칩니다.
+    pub fn overlaps(&self, other: &Place) -> bool {
+        self.is_prefix_of(other) || other.is_prefix_of(self)
+    }
+
+    // 역참조나 인덱스를 거치는 위치인지 확인합니다.
+    pub fn is_indirect(&self) -> bool {
+        self.projection.iter().any(|elem| !matches!(elem, PlaceElem::Field(..)))
+    }
+
+    // 위치의 타입입니다.
+    pub fn ty(&self, body: &Body) -> Ty {
+        self.projection.iter().fold(body.locals[self.local.0 as usize].ty.clone(), |ty, elem| match (elem, ty) {
+            (PlaceElem::Deref, Ty::Ref(_, inner)) => *inner,
+            (PlaceElem::Field(_, ty) | PlaceElem::Index(ty), _) => ty.clone(),
+            _ => Ty::Error,
+        })
+    }
+}
+
+// 인덱스는 값과 상관없이 같은 원소로 봅니다.
+fn elem_eq(a: &PlaceElem, b: &PlaceElem) -> bool {
+    match (a, b) {
+        (PlaceElem::Field(a, _), PlaceElem::Field(b, _)) => a == b,
+        (PlaceElem::Deref, PlaceElem::Deref) | (PlaceElem::Index(_), PlaceElem::Index(_)) => true,
+        _ => false,
+    }
+}
+
+// 연산에 쓰는 값입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Copy(Place),                // `Copy` 타입 값을 읽습니다
+    Move(Place),                // 값을 꺼내 옮깁니다. 이후 원래 위치는 쓸 수 없습니다
+    Constant,                   // 리터럴, 함수 이름처럼 지역 변수에 기대지 않는 값
+}
+
+impl Operand {
+    pub fn place(&self) -> Option<&Place> {
+        match self {
+            Operand::Copy(place) | Operand::Move(place) => Some(place),
+            Operand::Constant => None,
+        }
+    }
+}
+
+// 대입의 오른쪽 값입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rvalue {
+    Use(Operand),
+    Ref(bool, Place),           // &p, &mut p
+    BinaryOp(Operand, Operand),
+    UnaryOp(Operand),
+    Aggregate(Vec<Operand>),    // 구조체, 튜플, 배열, 범위, 클로저
+}
+
+impl Rvalue {
+    pub fn operands(&self) -> Vec<&Operand> {
+        match self {
+            Rvalue::Use(operand) | Rvalue::UnaryOp(operand) => vec![operand],
+            Rvalue::BinaryOp(left, right) => vec![left, right],
+            Rvalue::Aggregate(operands) => operands.iter().collect(),
+            Rvalue::Ref(..) => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum StatementKind {
+    Assign(Place, Rvalue),
+    StorageLive(Local),         // `let`: 바인딩이 생깁니다. 초기값이 없으면 아직 쓸 수 없습니다
+    StorageDead(Local),         // 블록이 끝나 바인딩이 사라집니다
+}
+
+#[derive(Debug, Clone)]
+pub struct Terminator {
+    pub kind: TerminatorKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum TerminatorKind {
+    Goto(BasicBlock),
+    SwitchBool { cond: Operand, then: BasicBlock, els: BasicBlock },
+    // 함수 호출입니다. 반환하지 않는 함수(`-> !`)라면 `target`이 없습니다.
+    Call { func: Operand, args: Vec<Operand>, dest: Place, target: Option<BasicBlock> },
+    Return,
+    Unreachable,
+}
+
+impl Terminator {
+    pub fn successors(&self) -> Vec<BasicBlock> {
+        match &self.kind {
+            TerminatorKind::Goto(target) => vec![*target],
+            TerminatorKind::SwitchBool { then, els, .. } => vec![*then, *els],
+            TerminatorKind::Call { target, .. } => target.iter().copied().collect(),
+            TerminatorKind::Return | TerminatorKind::Unreachable => Vec::new(),
+        }
+    }
+}
+
+impl Body {
+    pub fn local_decl(&self, local: Local) -> &LocalDecl {
+        &self.locals[local.0 as usize]
+    }
+
+    pub fn block(&self, block: BasicBlock) -> &BasicBlockData {
+        &self.blocks[block.0 as usize]
+    }
+
+    // 블록마다 앞선 블록의 목록입니다.
+    pub fn predecessors(&self) -> Vec<Vec<BasicBlock>> {
+        let mut predecessors = vec![Vec::new(); self.blocks.len()];
+        for (i, data) in self.blocks.iter().enumerate() {
+            for successor in data.terminator.successors() {
+                predecessors[successor.0 as usize].push(BasicBlock(i as u32));
+            }
+        }
+        predecessors
+    }
+
+    // 오류 메시지에 쓸 위치 이름입니다 (`p.name`, `*r`). 임시 값이면 없습니다.
+    pub fn describe_place(&self, place: &Place) -> Option<String> {
+        let mut text = self.local_decl(place.local).name.clone()?;
+        for (i, elem) in place.projection.iter().enumerate() {
+            text = match elem {
+                // 필드 접근과 인덱싱이 자동으로 따라간 참조는 쓰지 않습니다 (`r.x`).
+                PlaceElem::Deref if matches!(place.projection.get(i + 1), Some(PlaceElem::Field(..) | PlaceElem::Index(_))) => text,
+                PlaceElem::Deref => format!("*{}", text),
+                PlaceElem::Field(field, _) => format!("{}.{}", text, field),
+                PlaceElem::Index(_) => format!("{}[_]", text),
+            };
+        }
+        Some(text)
+    }
+}
+
+impl fmt::Display for Local {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "_{}", self.0)
+    }
+}
+
+impl fmt::Display for BasicBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bb{}", self.0)
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use rusttc_lexer::Span;
+use rusttc_typeck::ty::Ty;
+
+use crate::mir::{BasicBlock, Body, Local, Operand, Place, PlaceElem, Rvalue, StatementKind, TerminatorKind};
+use crate::BorrowckError;
+
+// 값이 빠져나간 자리 하나입니다.
+struct MoveOut {
+    path: Place,                // 비워진 위치 (지역 변수와 필드만으로 이루어집니다)
+    span: Span,                 // 옮긴 식이나 초기값 없는 `let`의 위치
+    kind: MoveKind,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum MoveKind {
+    Move,                       // 값을 옮겼습니다
+    Uninit,                     // `let x;`로 만들고 아직 값을 넣지 않았습니다
+}
+
+// 위치를 쓰는 방식입니다. 오류 메시지의 낱말이 달라집니다.
+#[derive(Clone, Copy, PartialEq)]
+enum Access {
+    Use,
+    Borrow,
+}
+
+// 이 지점에서 비어 있을 수 있는 자리(이동 번호)를 지역 변수별로 모은 것입니다.
+// 겹치는지는 같은 지역 변수끼리만 따지므로, 긴 식이 남긴 임시 변수가 많아도 접근마다 그 변수의 이동만 봅니다.
+type MoveState = BTreeMap<Local, BTreeSet<usize>>;
+
+// 본문의 모든 경로에서 옮겨진 값을 추적해, 옮긴 뒤에 쓰거나 초기화하지 않고 쓰는 곳을 보고합니다.
+// 상태는 "이 지점에서 비어 있을 수 있는 자리"의 집합이고, 갈래가 만나면 합집합을 취합니다.
+pub(crate) fn check(body: &Body, errors: &mut Vec<BorrowckError>) {
+    let mut cx = MoveCtxt { body, move_outs: Vec::new(), index: HashMap::new(), report: false, reported: HashSet::new(), errors };
+
+    let mut entry: Vec<Option<MoveState>> = vec![None; body.blocks.len()];
+    entry[BasicBlock::START.0 as usize] = Some(MoveState::new());
+    let mut worklist = VecDeque::from([BasicBlock::START]);
+    while let Some(block) = worklist.pop_front() {
+        let mut state = entry[block.0 as usize].clone().unwrap();
+        cx.apply_block(block, &mut state);
+        for successor in body.block(block).terminator.successors() {
+            let slot = &mut entry[successor.0 as usize];
+            let changed = match slot {
+                Some(existing) => {
+                    let mut changed = false;
+                    for (&local, indices) in &state {
+                        let moves = existing.entry(local).or_default();
+                        let before = moves.len();
+                        moves.extend(indices.iter().copied());
+                        changed |= moves.len() != before;
+                    }
+                    changed
+                },
+                None => {
+                    *slot = Some(state.clone());
+                    true
+                },
+            };
+            if changed && !worklist.contains(&successor) {
+                worklist.push_back(successor);
+            }
+        }
+    }
+
+    // 상태가 더 바뀌지 않으면 블록마다 한 번 더 돌며 오류를 보고합니다. 도달할 수 없는 블록은 건너뜁니다.
+    cx.report = true;
+    for (i, state) in entry.iter().enumerate() {
+        if let Some(state) = state {
+            cx.apply_block(BasicBlock(i as u32), &mut state.clone());
+        }
+    }
+}
+
+// `local`에서 비어 있을 수 있는 자리를 이동 순서대로 돌려줍니다.
+fn moves_of<'s>(move_outs: &'s [MoveOut], state: &'s MoveState, local: Local) -> impl Iterator<Item = &'s MoveOut> + 's {
+    state.get(&local).into_iter().flatten().map(|&index| &move_outs[index])
+}
+
+struct MoveCtxt<'a> {
+    body: &'a Body,
+    move_outs: Vec<MoveOut>,
+    index: HashMap<(BasicBlock, usize, usize), usize>,  // (블록, 문장, 몇 번째 이동) → 이동 번호
+    report: bool,                                       // 오류를 보고하는 마지막 회차인지
+    reported: HashSet<(Span, Local)>,
+    errors: &'a mut Vec<BorrowckError>,
+}
+
+impl MoveCtxt<'_> {
+    // 이동을 추적하는 지역 변수인지 확인합니다. 오류는 이름 있는 바인딩에만 보고하므로 임시 값은 추적하지 않습니다.
+    // 깊은 식은 임시 값을 깊이만큼 만들므로, 임시 값까지 추적하면 상태가 깊이에 비례해 커집니다.
+    fn tracks(&self, local: Local) -> bool {
+        self.body.local_decl(local).name.is_some()
+    }
+
+    fn apply_block(&mut self, block: BasicBlock, state: &mut MoveState) {
+        let data = self.body.block(block);
+        for (i, statement) in data.statements.iter().enumerate() {
+            let mut slot = 0;
+            match &statement.kind {
+                StatementKind::Assign(place, rvalue) => {
+                    match rvalue {
+                        Rvalue::Ref(_, borrowed) => self.access(state, borrowed, Access::Borrow, statement.span),
+                        _ => {
+                            for operand in rvalue.operands() {
+                                self.operand(state, operand, (block, i, &mut slot), statement.span);
+                            }
+                        },
+                    }
+                    self.assign(state, place, statement.span);
+                },
+                StatementKind::StorageLive(local) if self.tracks(*local) => {
+                    self.kill_local(state, *local);
+                    let path = Place::from_local(*local);
+                    let span = self.body.local_decl(*local).span;
+                    let index = self.move_out((block, i, &mut slot), path, span, MoveKind::Uninit);
+                    state.entry(*local).or_default().insert(index);
+                },
+                StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => self.kill_local(state, *local),
+            }
+        }
+
+        let terminator = &data.terminator;
+        let location = data.statements.len();
+        let mut slot = 0;
+        match &terminator.kind {
+            TerminatorKind::SwitchBool { cond, .. } => self.operand(state, cond, (block, location, &mut slot), terminator.span),
+            TerminatorKind::Call { func, args, dest, .. } => {
+                for operand in [func].into_iter().chain(args) {
+                    self.operand(state, operand, (block, location, &mut slot), terminator.span);
+                }
+                self.assign(state, dest, terminator.span);
+            },
+            TerminatorKind::Goto(_) | TerminatorKind::Return | TerminatorKind::Unreachable => {},
+        }
+    }
+
+    // 이동 번호를 돌려줍니다. 같은 지점의 이동은 회차가 바뀌어도 같은 번호입니다.
+    fn move_out(&mut self, (block, statement, slot): (BasicBlock, usize, &mut usize), path: Place, span: Span, kind: MoveKind) -> usize {
+        let key = (block, statement, *slot);
+        *slot += 1;
+        if let Some(&index) = self.index.get(&key) {
+            return index;
+        }
+        self.move_outs.push(MoveOut { path, span, kind });
+        self.index.insert(key, self.move_outs.len() - 1);
+        self.move_outs.len() - 1
+    }
+
+    fn operand(&mut self, state: &mut MoveState, operand: &Operand, location: (BasicBlock, usize, &mut usize), span: Span) {
+        match operand {
+            Operand::Copy(place) => self.access(state, place, Access::Use, span),
+            Operand::Move(place) => {
+                self.access(state, place, Access::Use, span);
+                if place.is_indirect() {
+                    self.report_move_out_of_indirect(place, span);
+                } else if self.tracks(place.local) {
+                    let index = self.move_out(location, place.clone(), span, MoveKind::Move);
+                    state.entry(place.local).or_default().insert(index);
+                }
+            },
+            Operand::Constant => {},
+        }
+    }
+
+    // 대입은 대상 자리를 다시 채웁니다. 참조를 거친 대입(`*r = v`)은 참조를 읽을 뿐입니다.
+    fn assign(&mut self, state: &mut MoveState, place: &Place, span: Span) {
+        if let Some(position) = place.projection.iter().position(|elem| !matches!(elem, PlaceElem::Field(..))) {
+            let base = Place { local: place.local, projection: place.projection[..position].to_vec() };
+            self.access(state, &base, Access::Use, span);
+            return;
+        }
+        if self.report {
+            let whole = moves_of(&self.move_outs, state, place.local).find(|m| m.path.is_prefix_of(place) && m.path != *place);
+            if let Some((whole, name)) = whole.and_then(|whole| Some((whole, self.body.describe_place(&whole.path)?))) {
+                let (message, label) = match whole.kind {
+                    MoveKind::Move => (format!("assign to part of moved value: `{}`", name), "value moved here"),
+                    MoveKind::Uninit => (
+                        format!("partially assigned binding `{}` isn't fully initialized", name),
+                        "binding declared here but left uninitialized",
+                    ),
+                };
+                if self.reported.insert((span, place.local)) {
+                    self.errors.push(BorrowckError::new(message, span).with_label(whole.span, label));
+                }
+            }
+        }
+        if let Some(moves) = state.get_mut(&place.local) {
+            moves.retain(|&index| !place.is_prefix_of(&self.move_outs[index].path));
+        }
+    }
+
+    fn kill_local(&self, state: &mut MoveState, local: Local) {
+        state.remove(&local);
+    }
+
+    // 비어 있을 수 있는 자리와 겹치는 위치를 쓰면 보고합니다.
+    fn access(&mut self, state: &MoveState, place: &Place, access: Access, span: Span) {
+        if !self.report {
+            return;
+        }
+        let conflicts: Vec<&MoveOut> = moves_of(&self.move_outs, state, place.local).filter(|m| m.path.overlaps(place)).collect();
+        if conflicts.is_empty() || !self.reported.insert((span, place.local)) {
+            return;
+        }
+        let body = self.body;
+        let Some(name) = body.describe_place(place) else { return };
+
+        if conflicts.iter().all(|m| m.kind == MoveKind::Uninit) {
+            let declared = conflicts[0].span;
+            let error = BorrowckError::new(format!("used binding `{}` isn't initialized", name), span)
+                .with_label(declared, "binding declared here but left uninitialized")
+                .with_label(span, format!("`{}` {} here but it isn't initialized", name, if access == Access::Borrow { "borrowed" } else { "used" }));
+            self.errors.push(error);
+            return;
+        }
+
+        let moves: Vec<&MoveOut> = conflicts.into_iter().filter(|m| m.kind == MoveKind::Move).collect();
+        let partial = moves.iter().all(|m| place.is_prefix_of(&m.path) && m.path != *place);
+        let verb = if access == Access::Borrow { "borrow" } else { "use" };
+        let message = match partial {
+            true => format!("{} of partially moved value: `{}`", verb, name),
+            false => format!("{} of moved value: `{}`", verb, body.describe_place(&moves[0].path).unwrap_or(name)),
+        };
+        let mut error = BorrowckError::new(message, span);
+        for m in &moves {
+            let label = match m.span.lo >= span.lo {
+                true => "value moved here, in previous iteration of loop",
+                false => "value moved here",
+            };
+            error = error.with_label(m.span, label);
+        }
+        let used = match access {
+            Access::Borrow => "value borrowed here after move",
+            Access::Use if partial => "value used here after partial move",
+            Access::Use => "value used here after move",
+        };
+        error = error.with_label(span, used);
+
+        let moved = &moves[0].path;
+        let note = format!(
+            "{}move occurs because `{}` has type `{}`, which does not implement the `Copy` trait",
+            if partial { "partial " } else { "" },
+            body.describe_place(moved).unwrap_or_default(),
+            moved.ty(body),
+        );
+        self.errors.push(error.with_note(note));
+    }
+
+    // 참조나 인덱스 뒤의 값은 옮길 수 없습니다.
+    fn report_move_out_of_indirect(&mut self, place: &Place, span: Span) {
+        if !self.report || !self.reported.insert((span, place.local)) {
+            return;
+        }
+        let body = self.body;
+        let position = place.projection.iter().rposition(|elem| !matches!(elem, PlaceElem::Field(..))).unwrap();
+        let base = Place { local: place.local, projection: place.projection[..position].to_vec() };
+        let Some(name) = body.describe_place(place) else { return };
+        let message = match (&place.projection[position], base.ty(body)) {
+            (PlaceElem::Deref, Ty::Ref(mutable, _)) => format!(
+                "cannot move out of `{}` which is behind a {} reference",
+                name,
+                if mutable { "mutable" } else { "shared" },
+            ),
+            (PlaceElem::Index(_), ty) => format!("cannot move out of index of `{}`", ty),
+            _ => format!("cannot move out of `{}`", name),
+        };
+        let label = format!("move occurs because `{}` has type `{}`, which does not implement the `Copy` trait", name, place.ty(body));
+        self.errors.push(BorrowckError::new(message, span).with_label(span, label));
+    }
+}
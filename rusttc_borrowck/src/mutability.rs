@@ -0,0 +1,205 @@
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+use rusttc_lexer::Span;
+use rusttc_typeck::ty::Ty;
+
+use crate::mir::{BasicBlock, Body, Local, Place, PlaceElem, Rvalue, StatementKind, TerminatorKind};
+use crate::{BorrowckError, Suggestion};
+
+// 값을 바꿀 수 없는 위치에 대입하거나 `&mut`로 빌리는 곳을 보고합니다.
+// `mut` 없는 바인딩은 초기화가 안 된 동안 한 번만 대입할 수 있으므로(`let x; x = 1;`), 초기화되었을 수 있는 바인딩을 앞으로 흘려 보냅니다.
+pub(crate) fn check(body: &Body, errors: &mut Vec<BorrowckError>) {
+    // 클로저가 캡처한 바깥 변수는 `StorageLive` 없이 쓰이는 이름 있는 지역 변수입니다.
+    // 그 가변성은 바깥 본문에서 캡처를 빌릴 때 검사합니다.
+    let declared: HashSet<Local> = body
+        .blocks
+        .iter()
+        .flat_map(|data| &data.statements)
+        .filter_map(|statement| match statement.kind {
+            StatementKind::StorageLive(local) => Some(local),
+            _ => None,
+        })
+        .collect();
+    let upvars: HashSet<Local> = (body.arg_count + 1..body.locals.len())
+        .map(|i| Local(i as u32))
+        .filter(|local| body.local_decl(*local).name.is_some() && !declared.contains(local))
+        .collect();
+
+    // 매개변수는 처음부터 초기화되어 있습니다.
+    let mut start = BTreeMap::new();
+    for i in 1..=body.arg_count {
+        start.insert(Local(i as u32), body.local_decl(Local(i as u32)).span);
+    }
+    let mut entry: Vec<Option<BTreeMap<Local, Span>>> = vec![None; body.blocks.len()];
+    entry[BasicBlock::START.0 as usize] = Some(start);
+    let mut worklist = VecDeque::from([BasicBlock::START]);
+    while let Some(block) = worklist.pop_front() {
+        let mut state = entry[block.0 as usize].clone().unwrap();
+        apply_block(body, block, &mut state, None);
+        for successor in body.block(block).terminator.successors() {
+            let slot = &mut entry[successor.0 as usize];
+            let changed = match slot {
+                Some(existing) => {
+                    let before = existing.len();
+                    for (&local, &span) in &state {
+                        existing.entry(local).or_insert(span);
+                    }
+                    existing.len() != before
+                },
+                None => {
+                    *slot = Some(state.clone());
+                    true
+                },
+            };
+            if changed && !worklist.contains(&successor) {
+                worklist.push_back(successor);
+            }
+        }
+    }
+
+    let mut cx = MutCtxt { body, upvars, reported: HashSet::new(), errors };
+    for (i, state) in entry.iter().enumerate() {
+        if let Some(state) = state {
+            apply_block(body, BasicBlock(i as u32), &mut state.clone(), Some(&mut cx));
+        }
+    }
+}
+
+// 블록을 지나며 초기화되었을 수 있는 지역 변수(와 처음 대입한 곳)를 갱신합니다. `cx`가 있으면 잘못된 쓰기를 보고합니다.
+fn apply_block(body: &Body, block: BasicBlock, state: &mut BTreeMap<Local, Span>, mut cx: Option<&mut MutCtxt>) {
+    let data = body.block(block);
+    for statement in &data.statements {
+        match &statement.kind {
+            StatementKind::Assign(place, rvalue) => {
+                if let Some(cx) = cx.as_deref_mut() {
+                    if let Rvalue::Ref(true, borrowed) = rvalue {
+                        cx.borrow_mut(borrowed, statement.span);
+                    }
+                    cx.assign(state, place, statement.span);
+                }
+                if place.projection.is_empty() && tracks(body, place.local) {
+                    state.entry(place.local).or_insert(statement.span);
+                }
+            },
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                state.remove(local);
+            },
+        }
+    }
+    if let TerminatorKind::Call { dest, .. } = &data.terminator.kind {
+        if dest.projection.is_empty() && tracks(body, dest.local) {
+            state.entry(dest.local).or_insert(data.terminator.span);
+        }
+    }
+}
+
+// 초기화를 추적하는 지역 변수인지 확인합니다. 오류는 이름 있는 바인딩에만 보고하므로 임시 값은 추적하지 않습니다.
+fn tracks(body: &Body, local: Local) -> bool {
+    body.local_decl(local).name.is_some()
+}
+
+// 위치를 바꿀 수 없는 까닭입니다.
+enum Immutable {
+    Binding(Local),             // `mut` 없는 바인딩의 일부입니다
+    BehindRef(Place),           // `&T` 참조 뒤에 있습니다. 그 참조의 위치를 담습니다
+}
+
+// 위치를 바꿀 수 있는지 확인합니다. `&mut` 뒤의 값은 바인딩에 `mut`가 없어도 바꿀 수 있지만, 그 앞에 `&`가 있으면 안 됩니다.
+fn mutability(body: &Body, place: &Place) -> Result<(), Immutable> {
+    let Some(position) = place.projection.iter().rposition(|elem| *elem == PlaceElem::Deref) else {
+        return match body.local_decl(place.local).mutable {
+            true => Ok(()),
+            false => Err(Immutable::Binding(place.local)),
+        };
+    };
+    let base = Place { local: place.local, projection: place.projection[..position].to_vec() };
+    match base.ty(body) {
+        Ty::Ref(false, _) => Err(Immutable::BehindRef(base)),
+        _ if base.projection.contains(&PlaceElem::Deref) => mutability(body, &base).or_else(|reason| match reason {
+            Immutable::Binding(_) => Ok(()),
+            reason => Err(reason),
+        }),
+        _ => Ok(()),
+    }
+}
+
+struct MutCtxt<'a> {
+    body: &'a Body,
+    upvars: HashSet<Local>,
+    reported: HashSet<Span>,
+    errors: &'a mut Vec<BorrowckError>,
+}
+
+impl MutCtxt<'_> {
+    fn assign(&mut self, state: &BTreeMap<Local, Span>, place: &Place, span: Span) {
+        let body = self.body;
+        if self.upvars.contains(&place.local) && !place.projection.contains(&PlaceElem::Deref) {
+            return;
+        }
+        let Err(reason) = mutability(body, place) else { return };
+        let Some(name) = body.describe_place(place) else { return };
+        let error = match reason {
+            // `let x;`로 만든 바인딩의 첫 대입은 괜찮습니다.
+            Immutable::Binding(_) if place.projection.is_empty() && !state.contains_key(&place.local) => return,
+            Immutable::Binding(local) if place.projection.is_empty() && local.0 as usize <= body.arg_count => {
+                BorrowckError::new(format!("cannot assign to immutable argument `{}`", name), span)
+                    .with_label(span, "cannot assign to immutable argument")
+            },
+            Immutable::Binding(local) if place.projection.is_empty() => {
+                BorrowckError::new(format!("cannot assign twice to immutable variable `{}`", name), span)
+                    .with_label(state[&local], format!("first assignment to `{}`", name))
+                    .with_label(span, "cannot assign twice to immutable variable")
+            },
+            Immutable::Binding(local) => BorrowckError::new(
+                format!("cannot assign to `{}`, as `{}` is not declared as mutable", name, body.local_decl(local).name.as_deref().unwrap_or_default()),
+                span,
+            )
+            .with_label(span, "cannot assign"),
+            Immutable::BehindRef(reference) => {
+                let reference = body.describe_place(&reference).unwrap_or_default();
+                BorrowckError::new(format!("cannot assign to `{}`, which is behind a `&` reference", name), span)
+                    .with_label(span, format!("`{}` is a `&` reference, so the data it refers to cannot be written", reference))
+            },
+        };
+        self.report(error, reason_local(place), span);
+    }
+
+    fn borrow_mut(&mut self, place: &Place, span: Span) {
+        let body = self.body;
+        let Err(reason) = mutability(body, place) else { return };
+        let Some(name) = body.describe_place(place) else { return };
+        let error = match reason {
+            Immutable::Binding(_) => BorrowckError::new(format!("cannot borrow `{}` as mutable, as it is not declared as mutable", name), span)
+                .with_label(span, "cannot borrow as mutable"),
+            Immutable::BehindRef(reference) => {
+                let reference = body.describe_place(&reference).unwrap_or_default();
+                BorrowckError::new(format!("cannot borrow `{}` as mutable, as it is behind a `&` reference", name), span)
+                    .with_label(span, format!("`{}` is a `&` reference, so the data it refers to cannot be borrowed as mutable", reference))
+            },
+        };
+        self.report(error, reason_local(place), span);
+    }
+
+    // 바인딩에 `mut`를 붙이면 고쳐지는 오류에는 그대로 적용할 수 있는 제안을 붙입니다.
+    // `self`는 함수 위치를 바인딩 위치로 쓰므로 제안하지 않습니다.
+    fn report(&mut self, error: BorrowckError, binding: Option<Local>, span: Span) {
+        if !self.reported.insert(span) {
+            return;
+        }
+        let decl = binding.map(|local| self.body.local_decl(local));
+        let error = match decl.and_then(|decl| Some((decl, decl.name.as_deref()?))) {
+            Some((decl, name)) if name != "self" => error.with_suggestion(Suggestion {
+                message: "consider making this binding mutable".to_string(),
+                span: decl.span,
+                replacement: format!("mut {}", name),
+            }),
+            _ => error,
+        };
+        self.errors.push(error);
+    }
+}
+
+// 역참조를 거치지 않는 위치라면 그 바탕 바인딩입니다.
+fn reason_local(place: &Place) -> Option<Local> {
+    (!place.projection.contains(&PlaceElem::Deref)).then_some(place.local)
+}
@@ -0,0 +1,100 @@
+use rusttc_lexer::{Span, Token, TokenKind};
+
+use crate::tokentree::{to_source, Delimiter, TokenTree};
+use crate::ExpandError;
+
+// 모든 크레이트에 미리 정의된 매크로입니다. 사용자 매크로와 같은 `macro_rules!` 기계로 전개됩니다.
+// `format_args!`, `format_args_nl!`은 파서가 직접 읽는 컴파일러 내장 매크로이고,
+// `std::...` 함수는 IR 변환 단계에서 출력과 패닉으로 바뀝니다.
+pub const PRELUDE: &str = r#"
+macro_rules! print {
+    ($($arg:tt)*) => { std::io::_print(format_args!($($arg)*)) };
+}
+
+macro_rules! println {
+    () => { std::io::_print(format_args_nl!("")) };
+    ($($arg:tt)*) => { std::io::_print(format_args_nl!($($arg)*)) };
+}
+
+macro_rules! panic {
+    () => { std::panicking::panic("explicit panic") };
+    ($($arg:tt)+) => { std::panicking::panic_fmt(format_args!($($arg)+)) };
+}
+
+macro_rules! assert {
+    ($cond:expr $(,)?) => {
+        if !$cond { std::panicking::panic(concat!("assertion failed: ", stringify!($cond))) }
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond { panic!($($arg)+) }
+    };
+}
+
+macro_rules! assert_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let left_val = &$left;
+        let right_val = &$right;
+        if !(*left_val == *right_val) {
+            panic!("assertion `left == right` failed\n  left: {}\n right: {}", *left_val, *right_val)
+        }
+    }};
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        let left_val = &$left;
+        let right_val = &$right;
+        if !(*left_val == *right_val) {
+            panic!("assertion `left == right` failed: {}\n  left: {}\n right: {}", format_args!($($arg)+), *left_val, *right_val)
+        }
+    }};
+}
+
+macro_rules! vec {
+    () => { std::vec::new() };
+    ($elem:expr; $n:expr) => { std::vec::from_elem($elem, $n) };
+    ($($x:expr),+ $(,)?) => { std::vec::from_array([$($x),+]) };
+}
+"#;
+
+// 파서가 읽는 컴파일러 내장 매크로입니다. 인자 안의 매크로만 전개하고 호출은 그대로 둡니다.
+pub const PARSER_BUILTINS: &[&str] = &["format_args", "format_args_nl"];
+
+// `stringify!(tokens)`: 인자를 전개하지 않고 소스 문자열 리터럴로 만듭니다.
+pub fn stringify(args: &[TokenTree], span: Span) -> TokenTree {
+    string_literal(&to_source(args), span)
+}
+
+// `concat!(lit, ...)`: 이미 전개된 리터럴 인자들을 이어 붙여 문자열 리터럴 하나로 만듭니다.
+pub fn concat(args: &[TokenTree], span: Span) -> Result<TokenTree, ExpandError> {
+    Ok(string_literal(&concat_text(args)?, span))
+}
+
+fn concat_text(args: &[TokenTree]) -> Result<String, ExpandError> {
+    let mut text = String::new();
+    for arg in args.split(|tree| tree.is_token(&TokenKind::Comma)) {
+        match arg {
+            [] => continue,
+            [TokenTree::Token(token)] => text.push_str(&literal_text(token)?),
+            [TokenTree::Token(minus), TokenTree::Token(token)]
+                if minus.kind == TokenKind::Minus && token.kind == TokenKind::Literal =>
+            {
+                text.push('-');
+                text.push_str(&token.text);
+            },
+            [TokenTree::Delimited(_, Delimiter::Invisible, inner)] => text.push_str(&concat_text(inner)?),
+            _ => return Err(ExpandError::new(format!("expected a literal, found `{}`", to_source(arg)))),
+        }
+    }
+    Ok(text)
+}
+
+fn literal_text(token: &Token) -> Result<String, ExpandError> {
+    match token.kind {
+        TokenKind::StringLiteral | TokenKind::CharLiteral => Ok(token.text[1..token.text.len() - 1].to_string()),
+        TokenKind::Literal => Ok(token.text.clone()),
+        TokenKind::Ident if token.text == "true" || token.text == "false" => Ok(token.text.clone()),
+        _ => Err(ExpandError::new(format!("expected a literal, found `{}`", token.text))),
+    }
+}
+
+fn string_literal(text: &str, span: Span) -> TokenTree {
+    TokenTree::Token(Token::new(TokenKind::StringLiteral, format!("\"{}\"", text)).with_span(span))
+}
@@ -0,0 +1,269 @@
+mod builtin;
+mod macro_rules;
+mod tokentree;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use rusttc_lexer::{tokenize, Span, Token, TokenKind};
+
+use macro_rules::MacroRules;
+use tokentree::{flatten, parse_trees, DelimSpan, Delimiter, TokenTree};
+
+// 매크로가 매크로를 부를 수 있는 최대 깊이입니다.
+const RECURSION_LIMIT: usize = 128;
+
+// 뒤에 `!`가 와도 매크로 호출이 아닌 예약어입니다. `if !(a == b)`, `return !x`
+const EXPR_KEYWORDS: &[&str] = &["break", "else", "if", "in", "let", "match", "mut", "return", "while"];
+
+// 매크로 전개 중에 발생한 오류입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpandError {
+    pub message: String,
+    pub span: Span,             // 오류가 난 토큰이나 매크로 호출의 위치
+}
+
+impl ExpandError {
+    pub fn new(message: impl Into<String>) -> Self {
+        ExpandError { message: message.into(), span: Span::DUMMY }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    // 아직 위치가 없는 오류에만 `span`을 붙입니다. 안쪽 호출에서 정한 위치가 남습니다.
+    fn or_span(self, span: Span) -> Self {
+        if self.span.is_dummy() {
+            self.with_span(span)
+        } else {
+            self
+        }
+    }
+}
+
+impl fmt::Display for ExpandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)?;
+        if let Some(location) = self.span.location() {
+            write!(f, "\n  --> {}", location)?;
+        }
+        Ok(())
+    }
+}
+
+// 토큰 목록의 매크로를 모두 전개합니다. 아이템을 파싱하기 전에 토큰 트리 단계에서 실행됩니다.
+// `macro_rules!` 정의는 정의된 곳부터 그것을 둘러싼 블록이 끝날 때까지 쓸 수 있고,
+// `println!`, `assert_eq!`, `vec!` 같은 기본 매크로는 `builtin::PRELUDE`에 미리 정의되어 있습니다.
+// 전개식에서 온 토큰은 매크로를 호출한 자리(`name!(...)` 전체)의 위치를 가지고, 인자에서 온 토큰은 원래 위치를 유지합니다.
+pub fn expand(tokens: &[Token]) -> Result<Vec<Token>, ExpandError> {
+    let mut expander = Expander::new();
+    let trees = parse_trees(&without_trivia(tokens))?;
+    let trees = expander.expand_trees(trees, true, 0)?;
+
+    let mut expanded = Vec::new();
+    flatten(&trees, &mut expanded);
+    Ok(expanded)
+}
+
+fn without_trivia(tokens: &[Token]) -> Vec<Token> {
+    tokens
+        .iter()
+        .filter(|token| {
+            !matches!(
+                token.kind,
+                TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. } | TokenKind::EOF
+            )
+        })
+        .cloned()
+        .collect()
+}
+
+struct Expander {
+    macros: HashMap<String, Rc<MacroRules>>,    // 지금 위치에서 보이는 매크로
+    next_ctx: usize,                            // 다음 전개에 붙일 구문 문맥 번호
+}
+
+impl Expander {
+    fn new() -> Self {
+        let mut expander = Expander { macros: HashMap::new(), next_ctx: 0 };
+        let prelude = parse_trees(&without_trivia(&tokenize(builtin::PRELUDE))).expect("prelude has balanced delimiters");
+        expander.expand_trees(prelude, true, 0).expect("prelude macros are well-formed");
+        expander
+    }
+
+    // 트리 목록을 앞에서부터 훑으며 매크로 정의를 등록하고 호출을 전개합니다.
+    // `stmt_level`은 이 목록이 문장이나 아이템을 나열하는 자리(크레이트, 블록)인지입니다.
+    fn expand_trees(&mut self, mut trees: Vec<TokenTree>, stmt_level: bool, depth: usize) -> Result<Vec<TokenTree>, ExpandError> {
+        let mut output = Vec::new();
+        let mut i = 0;
+        while i < trees.len() {
+            // 괄호 안은 복사하지 않고 꺼내서 전개하므로 깊이 겹친 괄호도 트리마다 한 번씩만 훑습니다.
+            if let TokenTree::Delimited(span, delim, inner) = &mut trees[i] {
+                // 블록 안에서 정의한 매크로는 블록이 끝나면 보이지 않습니다.
+                let scope = self.macros.clone();
+                let inner = self.expand_trees(std::mem::take(inner), *delim == Delimiter::Brace, depth)?;
+                self.macros = scope;
+                output.push(TokenTree::Delimited(*span, *delim, inner));
+                i += 1;
+                continue;
+            }
+            match &trees[i..] {
+                [TokenTree::Token(keyword), bang, TokenTree::Token(name), TokenTree::Delimited(_, delim, body), ..]
+                    if keyword.text == "macro_rules" && bang.is_token(&TokenKind::Bang) && name.kind == TokenKind::Ident =>
+                {
+                    let rules = MacroRules::parse(&name.text, body).map_err(|error| error.or_span(name.span))?;
+                    self.macros.insert(name.text.clone(), Rc::new(rules));
+                    i += 4;
+                    // `macro_rules! m ( ... );`처럼 괄호로 쓴 정의는 세미콜론으로 끝납니다.
+                    if *delim != Delimiter::Brace && trees.get(i).is_some_and(|tree| tree.is_token(&TokenKind::Semi)) {
+                        i += 1;
+                    }
+                },
+                [TokenTree::Token(name), bang, TokenTree::Delimited(span, delim, args), ..]
+                    if name.kind == TokenKind::Ident
+                        && !EXPR_KEYWORDS.contains(&name.text.as_str())
+                        && bang.is_token(&TokenKind::Bang) =>
+                {
+                    let stmt_position = stmt_level && is_stmt_start(&output);
+                    let call_site = name.span.to(span.close);
+                    if builtin::PARSER_BUILTINS.contains(&name.text.as_str()) {
+                        let args = self.expand_trees(args.clone(), false, depth)?;
+                        output.extend([trees[i].clone(), trees[i + 1].clone(), TokenTree::Delimited(*span, *delim, args)]);
+                    } else {
+                        let expanded = self
+                            .expand_call(&name.text, args, call_site, stmt_position, depth)
+                            .map_err(|error| error.or_span(call_site))?;
+                        // 표현식 자리에서는 전개 결과가 주변 연산자와 섞이지 않도록 묶습니다.
+                        if stmt_position || expanded.len() <= 1 {
+                            output.extend(expanded);
+                        } else {
+                            output.push(TokenTree::Delimited(DelimSpan::from_single(call_site), Delimiter::Invisible, expanded));
+                        }
+                    }
+                    i += 3;
+                },
+                [tree, ..] => {
+                    output.push(tree.clone());
+                    i += 1;
+                },
+                [] => unreachable!(),
+            }
+        }
+        Ok(output)
+    }
+
+    fn expand_call(
+        &mut self,
+        name: &str,
+        args: &[TokenTree],
+        call_site: Span,
+        stmt_position: bool,
+        depth: usize,
+    ) -> Result<Vec<TokenTree>, ExpandError> {
+        if depth >= RECURSION_LIMIT {
+            return Err(ExpandError::new(format!("recursion limit reached while expanding `{}!`", name)));
+        }
+
+        match name {
+            "stringify" => Ok(vec![builtin::stringify(args, call_site)]),
+            "concat" => {
+                let args = self.expand_trees(args.to_vec(), false, depth + 1)?;
+                Ok(vec![builtin::concat(&args, call_site)?])
+            },
+            _ => {
+                let Some(mac) = self.macros.get(name).cloned() else {
+                    return Err(ExpandError::new(format!("cannot find macro `{}` in this scope", name)));
+                };
+                self.next_ctx += 1;
+                let expanded = mac.expand(args, self.next_ctx, call_site)?;
+                self.expand_trees(expanded, stmt_position, depth + 1)
+            },
+        }
+    }
+}
+
+// 지금까지 전개한 트리 다음이 새 문장이나 아이템이 시작되는 자리인지 확인합니다.
+fn is_stmt_start(output: &[TokenTree]) -> bool {
+    match output {
+        [] => true,
+        [.., last] if last.is_token(&TokenKind::Semi) => true,
+        [.., TokenTree::Delimited(_, Delimiter::Brace, _)] => true,
+        // `#[test] m! { ... }`처럼 속성 뒤
+        [.., pound, TokenTree::Delimited(_, Delimiter::Bracket, _)] => pound.is_token(&TokenKind::Pound),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusttc_lexer::{tokenize, Token};
+
+    use super::{expand, ExpandError};
+
+    fn expand_source(source: &str) -> Result<String, ExpandError> {
+        let tokens = expand(&tokenize(source))?;
+        Ok(tokens.iter().map(|token: &Token| token.text.as_str()).collect::<Vec<_>>().join(" "))
+    }
+
+    #[test]
+    fn test_expand_macro_rules_with_fragments_and_repetitions() {
+        let source = "
+            macro_rules! square { ($x:expr) => { $x * $x }; }
+            macro_rules! sum { ($($n:expr),* $(,)?) => { 0 $(+ $n)* }; }
+            fn main() { let a = square!(1 + 2); let b = sum!(1, a, 3,); }
+        ";
+        assert_eq!(
+            expand_source(source).unwrap(),
+            "fn main ( ) { let a = ( ( 1 + 2 ) * ( 1 + 2 ) ) ; let b = ( 0 + 1 + a + 3 ) ; }"
+        );
+    }
+
+    #[test]
+    fn test_expand_builtin_macros() {
+        let source = r#"fn main() { let v = vec![1, 2]; println!("{}", v); assert!(v == v); }"#;
+        assert_eq!(
+            expand_source(source).unwrap(),
+            r#"fn main ( ) { let v = ( std :: vec :: from_array ( [ 1 , 2 ] ) ) ; "#.to_string()
+                + r#"std :: io :: _print ( format_args_nl ! ( "{}" , v ) ) ; "#
+                + r#"if ! ( v == v ) { std :: panicking :: panic ( "assertion failed: v == v" ) } ; }"#
+        );
+    }
+
+    #[test]
+    fn test_expand_hygiene_renames_macro_locals() {
+        let source = "
+            macro_rules! double { ($e:expr) => {{ let tmp = $e; tmp + tmp }}; }
+            fn main() { let tmp = 1; let x = double!(tmp); }
+        ";
+        assert_eq!(
+            expand_source(source).unwrap(),
+            "fn main ( ) { let tmp = 1 ; let x = { let tmp#1 = tmp ; tmp#1 + tmp#1 } ; }"
+        );
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let error = |source: &str| expand_source(source).unwrap_err().message;
+        assert_eq!(error("fn main() { foo!(); }"), "cannot find macro `foo` in this scope");
+        assert_eq!(
+            error("macro_rules! one { (1) => { 1 }; } fn main() { one!(2); }"),
+            "no rules of macro `one` matched `one!(2)`"
+        );
+        assert_eq!(
+            error("macro_rules! m { ($x) => { $x }; }"),
+            "missing fragment specifier for `$x`"
+        );
+        assert_eq!(
+            error("macro_rules! r { () => { r!() }; } fn main() { r!(); }"),
+            "recursion limit reached while expanding `r!`"
+        );
+        // 블록 안에서 정의한 매크로는 블록 밖에서 보이지 않습니다.
+        assert_eq!(
+            error("fn f() { macro_rules! m { () => { 1 }; } } fn main() { m!(); }"),
+            "cannot find macro `m` in this scope"
+        );
+    }
+}
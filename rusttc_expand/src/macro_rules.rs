@@ -0,0 +1,495 @@
+use std::collections::{HashMap, HashSet};
+
+use rusttc_lexer::{Span, Token, TokenKind};
+
+use crate::tokentree::{to_source, trees_span, DelimSpan, Delimiter, TokenTree};
+use crate::ExpandError;
+
+// `macro_rules!`로 정의한 매크로입니다. 규칙은 위에서부터 차례로 맞춰 봅니다.
+#[derive(Debug)]
+pub struct MacroRules {
+    name: String,
+    rules: Vec<Rule>,
+}
+
+// `(매처) => { 전개식 }` 규칙 하나입니다.
+#[derive(Debug)]
+struct Rule {
+    matcher: Vec<MacroTree>,
+    transcriber: Vec<MacroTree>,
+    locals: HashSet<String>,    // 전개식이 `let`, `for`로 직접 묶는 지역 변수 (위생 처리 대상)
+}
+
+// 매처와 전개식을 이루는 트리입니다.
+#[derive(Debug, Clone)]
+enum MacroTree {
+    Token(Token),
+    Delimited(Delimiter, Vec<MacroTree>),
+    MetaVar(String, Option<FragmentKind>),  // 매처의 `$x:expr`, 전개식의 `$x`
+    Repeat(Repetition),                     // $( ... ) sep op
+}
+
+// 반복 `$( ... ),*`을 나타내는 구조체입니다.
+#[derive(Debug, Clone)]
+struct Repetition {
+    trees: Vec<MacroTree>,      // 반복할 내용
+    separator: Option<Token>,   // 구분 토큰
+    op: RepeatOp,               // 반복 횟수
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RepeatOp {
+    ZeroOrMore,                 // *
+    OneOrMore,                  // +
+    ZeroOrOne,                  // ?
+}
+
+// 메타 변수가 맞출 수 있는 구문 조각의 종류입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FragmentKind {
+    Block,
+    Expr,
+    Ident,
+    Literal,
+    Pat,
+    Tt,
+    Ty,
+}
+
+impl FragmentKind {
+    fn from_name(name: &str) -> Option<FragmentKind> {
+        match name {
+            "block" => Some(FragmentKind::Block),
+            "expr" => Some(FragmentKind::Expr),
+            "ident" => Some(FragmentKind::Ident),
+            "literal" => Some(FragmentKind::Literal),
+            "pat" => Some(FragmentKind::Pat),
+            "tt" => Some(FragmentKind::Tt),
+            "ty" => Some(FragmentKind::Ty),
+            _ => None,
+        }
+    }
+}
+
+// 메타 변수에 묶인 값입니다. 반복 안의 변수는 반복 횟수만큼의 값을 가집니다.
+#[derive(Debug, Clone)]
+enum Binding {
+    Fragment(FragmentKind, Vec<TokenTree>),
+    Seq(Vec<Binding>),
+}
+
+type Bindings = HashMap<String, Binding>;
+
+impl MacroRules {
+    // `macro_rules! name { ... }`의 본문을 규칙 목록으로 파싱합니다.
+    pub fn parse(name: &str, body: &[TokenTree]) -> Result<MacroRules, ExpandError> {
+        let mut rules = Vec::new();
+        let mut i = 0;
+        while i < body.len() {
+            let Some(TokenTree::Delimited(_, _, matcher)) = body.get(i) else {
+                return Err(ExpandError::new(format!("expected macro matcher in delimiters, found `{}`", to_source(&body[i..=i]))));
+            };
+            if !body.get(i + 1).is_some_and(|tree| tree.is_token(&TokenKind::FatArrow)) {
+                return Err(ExpandError::new(format!("expected `=>` after the matcher of macro `{}`", name)));
+            }
+            let Some(TokenTree::Delimited(_, _, transcriber)) = body.get(i + 2) else {
+                return Err(ExpandError::new(format!("expected macro body in delimiters after `=>` in macro `{}`", name)));
+            };
+            i += 3;
+            if i < body.len() {
+                if !body[i].is_token(&TokenKind::Semi) {
+                    return Err(ExpandError::new(format!("expected `;`, found `{}`", to_source(&body[i..=i]))));
+                }
+                i += 1;
+            }
+
+            let matcher = parse_macro_trees(matcher, true)?;
+            let mut names = Vec::new();
+            binders(&matcher, &mut names);
+            if let Some(name) = names.iter().enumerate().find_map(|(i, name)| names[..i].contains(name).then_some(name)) {
+                return Err(ExpandError::new(format!("duplicate matcher binding `${}`", name)));
+            }
+            let transcriber = parse_macro_trees(transcriber, false)?;
+            let mut locals = HashSet::new();
+            bound_locals(&transcriber, &mut locals);
+            rules.push(Rule { matcher, transcriber, locals });
+        }
+
+        if rules.is_empty() {
+            return Err(ExpandError::new(format!("macro `{}` has no rules", name)));
+        }
+        Ok(MacroRules { name: name.to_string(), rules })
+    }
+
+    // 호출 인자에 처음으로 맞는 규칙을 찾아 전개합니다.
+    // `ctx`는 이번 전개의 구문 문맥 번호로, 전개식이 묶는 지역 변수 이름에 붙어 호출 쪽 변수와 구별됩니다.
+    // 전개식에서 온 토큰은 모두 호출 위치 `call_site`를 가집니다.
+    pub fn expand(&self, input: &[TokenTree], ctx: usize, call_site: Span) -> Result<Vec<TokenTree>, ExpandError> {
+        for rule in &self.rules {
+            let matched = match_trees(&rule.matcher, input, 0).into_iter().find(|(end, _)| *end == input.len());
+            if let Some((_, bindings)) = matched {
+                let mut output = Vec::new();
+                rule.transcribe(&rule.transcriber, &bindings, &mut Vec::new(), ctx, call_site, &mut output)?;
+                return Ok(output);
+            }
+        }
+        Err(ExpandError::new(format!("no rules of macro `{}` matched `{}!({})`", self.name, self.name, to_source(input))))
+    }
+}
+
+// 매처(`matcher`가 참)나 전개식의 토큰 트리를 파싱합니다.
+fn parse_macro_trees(trees: &[TokenTree], matcher: bool) -> Result<Vec<MacroTree>, ExpandError> {
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < trees.len() {
+        match &trees[i] {
+            TokenTree::Token(token) if token.kind == TokenKind::Dollar => match trees.get(i + 1) {
+                Some(TokenTree::Token(name)) if name.kind == TokenKind::Ident => {
+                    i += 2;
+                    let kind = if matcher {
+                        if !trees.get(i).is_some_and(|tree| tree.is_token(&TokenKind::Colon)) {
+                            return Err(ExpandError::new(format!("missing fragment specifier for `${}`", name.text)));
+                        }
+                        let Some(TokenTree::Token(kind)) = trees.get(i + 1) else {
+                            return Err(ExpandError::new(format!("missing fragment specifier for `${}`", name.text)));
+                        };
+                        i += 2;
+                        Some(FragmentKind::from_name(&kind.text).ok_or_else(|| {
+                            ExpandError::new(format!(
+                                "invalid fragment specifier `{}`\n  = help: valid fragment specifiers are `block`, `expr`, `ident`, `literal`, `pat`, `tt` and `ty`",
+                                kind.text
+                            ))
+                        })?)
+                    } else {
+                        None
+                    };
+                    result.push(MacroTree::MetaVar(name.text.clone(), kind));
+                },
+                Some(TokenTree::Delimited(_, Delimiter::Paren, inner)) => {
+                    let trees_inner = parse_macro_trees(inner, matcher)?;
+                    let (separator, op, len) = parse_repeat_op(&trees[i + 2..])?;
+                    result.push(MacroTree::Repeat(Repetition { trees: trees_inner, separator, op }));
+                    i += 2 + len;
+                },
+                _ => return Err(ExpandError::new("expected identifier or `(` after `$`")),
+            },
+            TokenTree::Token(token) => {
+                result.push(MacroTree::Token(token.clone()));
+                i += 1;
+            },
+            TokenTree::Delimited(_, delim, inner) => {
+                result.push(MacroTree::Delimited(*delim, parse_macro_trees(inner, matcher)?));
+                i += 1;
+            },
+        }
+    }
+    Ok(result)
+}
+
+// 반복 뒤의 `sep op`를 파싱해 (구분 토큰, 반복 연산자, 소비한 트리 수)를 돌려줍니다.
+fn parse_repeat_op(trees: &[TokenTree]) -> Result<(Option<Token>, RepeatOp, usize), ExpandError> {
+    let op = |tree: Option<&TokenTree>| match tree {
+        Some(TokenTree::Token(token)) => match token.kind {
+            TokenKind::Star => Some(RepeatOp::ZeroOrMore),
+            TokenKind::Plus => Some(RepeatOp::OneOrMore),
+            TokenKind::Question => Some(RepeatOp::ZeroOrOne),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    if let Some(op) = op(trees.first()) {
+        return Ok((None, op, 1));
+    }
+    match (trees.first(), op(trees.get(1))) {
+        (Some(TokenTree::Token(separator)), Some(op)) if op != RepeatOp::ZeroOrOne => Ok((Some(separator.clone()), op, 2)),
+        (Some(TokenTree::Token(_)), Some(RepeatOp::ZeroOrOne)) => {
+            Err(ExpandError::new("the `?` macro repetition operator does not take a separator"))
+        },
+        _ => Err(ExpandError::new("expected one of: `*`, `+`, or `?`")),
+    }
+}
+
+// 매처가 묶는 메타 변수 이름을 모읍니다.
+fn binders(trees: &[MacroTree], names: &mut Vec<String>) {
+    for tree in trees {
+        match tree {
+            MacroTree::MetaVar(name, _) => names.push(name.clone()),
+            MacroTree::Delimited(_, inner) => binders(inner, names),
+            MacroTree::Repeat(rep) => binders(&rep.trees, names),
+            MacroTree::Token(_) => {},
+        }
+    }
+}
+
+// 전개식이 `let x`, `let mut x`, `for x in`으로 직접 묶는 지역 변수 이름을 모읍니다.
+// 메타 변수로 받은 이름(`let $x`)은 호출 쪽 이름이므로 제외됩니다.
+fn bound_locals(trees: &[MacroTree], locals: &mut HashSet<String>) {
+    let ident = |tree: Option<&MacroTree>| match tree {
+        Some(MacroTree::Token(token)) if token.kind == TokenKind::Ident && token.text != "_" => Some(token.text.clone()),
+        _ => None,
+    };
+
+    for (i, tree) in trees.iter().enumerate() {
+        match tree {
+            MacroTree::Token(token) if token.text == "let" || token.text == "for" => {
+                let mut next = i + 1;
+                if ident(trees.get(next)).as_deref() == Some("mut") {
+                    next += 1;
+                }
+                locals.extend(ident(trees.get(next)));
+            },
+            MacroTree::Delimited(_, inner) => bound_locals(inner, locals),
+            MacroTree::Repeat(rep) => bound_locals(&rep.trees, locals),
+            _ => {},
+        }
+    }
+}
+
+fn same_token(a: &Token, b: &Token) -> bool {
+    a.kind == b.kind && a.text == b.text
+}
+
+// `input[pos..]`의 앞부분을 `matchers`와 맞춰 봅니다.
+// 맞출 수 있는 모든 방법을 (끝 위치, 묶인 변수)로 돌려주며, 반복을 많이 한 쪽이 앞에 옵니다.
+fn match_trees(matchers: &[MacroTree], input: &[TokenTree], pos: usize) -> Vec<(usize, Bindings)> {
+    let Some((first, rest)) = matchers.split_first() else {
+        return vec![(pos, Bindings::new())];
+    };
+
+    match first {
+        MacroTree::Token(expected) => match input.get(pos) {
+            Some(TokenTree::Token(token)) if same_token(token, expected) => match_trees(rest, input, pos + 1),
+            _ => Vec::new(),
+        },
+        MacroTree::Delimited(delim, inner) => match input.get(pos) {
+            Some(TokenTree::Delimited(_, found, trees)) if found == delim => {
+                let inner: Vec<Bindings> = match_trees(inner, trees, 0)
+                    .into_iter()
+                    .filter(|(end, _)| *end == trees.len())
+                    .map(|(_, bindings)| bindings)
+                    .collect();
+                join(&inner, match_trees(rest, input, pos + 1))
+            },
+            _ => Vec::new(),
+        },
+        MacroTree::MetaVar(name, kind) => {
+            let kind = kind.expect("matcher variables always have a fragment specifier");
+            let Some(end) = fragment_end(kind, input, pos) else { return Vec::new() };
+            let binding = Bindings::from([(name.clone(), Binding::Fragment(kind, input[pos..end].to_vec()))]);
+            join(&[binding], match_trees(rest, input, end))
+        },
+        MacroTree::Repeat(rep) => match_repeat(rep, rest, input, pos),
+    }
+}
+
+fn match_repeat(rep: &Repetition, rest: &[MacroTree], input: &[TokenTree], pos: usize) -> Vec<(usize, Bindings)> {
+    let mut names = Vec::new();
+    binders(&rep.trees, &mut names);
+
+    let mut results = Vec::new();
+    // (위치, 지금까지 반복마다 묶인 변수)
+    let mut states: Vec<(usize, Vec<Bindings>)> = vec![(pos, Vec::new())];
+    while !states.is_empty() {
+        let mut next = Vec::new();
+        for (at, iterations) in states {
+            if !iterations.is_empty() || rep.op != RepeatOp::OneOrMore {
+                let seq: Bindings = names
+                    .iter()
+                    .map(|name| (name.clone(), Binding::Seq(iterations.iter().map(|b| b[name].clone()).collect())))
+                    .collect();
+                results.push(join(&[seq], match_trees(rest, input, at)));
+            }
+            if rep.op == RepeatOp::ZeroOrOne && !iterations.is_empty() {
+                continue;
+            }
+
+            let mut start = at;
+            if let (Some(separator), false) = (&rep.separator, iterations.is_empty()) {
+                match input.get(at) {
+                    Some(TokenTree::Token(token)) if same_token(token, separator) => start += 1,
+                    _ => continue,
+                }
+            }
+            for (end, bindings) in match_trees(&rep.trees, input, start) {
+                // 아무것도 소비하지 않는 반복은 끝나지 않으므로 건너뜁니다.
+                if end == at {
+                    continue;
+                }
+                let mut iterations = iterations.clone();
+                iterations.push(bindings);
+                next.push((end, iterations));
+            }
+        }
+        states = next;
+    }
+
+    results.into_iter().rev().flatten().collect()
+}
+
+// 앞부분의 변수 묶음과 뒷부분의 결과를 모든 조합으로 합칩니다.
+fn join(lefts: &[Bindings], rights: Vec<(usize, Bindings)>) -> Vec<(usize, Bindings)> {
+    let mut joined = Vec::new();
+    for (end, right) in rights {
+        for left in lefts {
+            let mut bindings = left.clone();
+            bindings.extend(right.clone());
+            joined.push((end, bindings));
+        }
+    }
+    joined
+}
+
+// `input[pos..]`에서 `kind` 조각이 끝나는 위치를 찾습니다.
+// 표현식과 타입은 뒤따를 수 있는 토큰(`,`, `;`, `=>` 등)이 나올 때까지를 한 조각으로 봅니다.
+fn fragment_end(kind: FragmentKind, input: &[TokenTree], pos: usize) -> Option<usize> {
+    let first = input.get(pos)?;
+    let single = |ok: bool| ok.then_some(pos + 1);
+    match kind {
+        FragmentKind::Tt => Some(pos + 1),
+        FragmentKind::Block => single(matches!(first, TokenTree::Delimited(_, Delimiter::Brace, _))),
+        FragmentKind::Ident => {
+            single(matches!(first, TokenTree::Token(token) if token.kind == TokenKind::Ident && token.text != "_"))
+        },
+        FragmentKind::Literal => {
+            let at = if first.is_token(&TokenKind::Minus) { pos + 1 } else { pos };
+            match input.get(at)? {
+                TokenTree::Token(token) if is_literal(token) => Some(at + 1),
+                _ => None,
+            }
+        },
+        FragmentKind::Expr => scan(input, pos, false, |tree| {
+            tree.is_token(&TokenKind::Comma) || tree.is_token(&TokenKind::Semi) || tree.is_token(&TokenKind::FatArrow)
+        }),
+        FragmentKind::Pat => scan(input, pos, false, |tree| {
+            [TokenKind::Comma, TokenKind::FatArrow, TokenKind::Eq, TokenKind::Or].iter().any(|kind| tree.is_token(kind))
+                || tree.is_ident("if")
+                || tree.is_ident("in")
+        }),
+        FragmentKind::Ty => scan(input, pos, true, |tree| {
+            [TokenKind::Comma, TokenKind::Semi, TokenKind::FatArrow, TokenKind::Eq, TokenKind::Or, TokenKind::Colon, TokenKind::Gt]
+                .iter()
+                .any(|kind| tree.is_token(kind))
+                || matches!(tree, TokenTree::Delimited(_, Delimiter::Brace, _))
+                || tree.is_ident("as")
+                || tree.is_ident("where")
+        }),
+    }
+}
+
+// `pos`부터 꺾쇠 괄호 밖에서 `stop`이 참인 트리가 나오기 전까지를 한 조각으로 봅니다.
+// 타입(`all_angles`)은 모든 `<`를, 표현식은 터보피시 `::<`만 꺾쇠 괄호로 셉니다.
+fn scan(input: &[TokenTree], pos: usize, all_angles: bool, stop: impl Fn(&TokenTree) -> bool) -> Option<usize> {
+    let mut depth = 0;
+    let mut end = pos;
+    while let Some(tree) = input.get(end) {
+        if depth == 0 && stop(tree) {
+            break;
+        }
+        if tree.is_token(&TokenKind::Lt) && (all_angles || end > 0 && input[end - 1].is_token(&TokenKind::PathSep)) {
+            depth += 1;
+        } else if tree.is_token(&TokenKind::Gt) && depth > 0 {
+            depth -= 1;
+        }
+        end += 1;
+    }
+    (end > pos).then_some(end)
+}
+
+fn is_literal(token: &Token) -> bool {
+    matches!(token.kind, TokenKind::Literal | TokenKind::StringLiteral | TokenKind::CharLiteral)
+        || token.kind == TokenKind::Ident && (token.text == "true" || token.text == "false")
+}
+
+impl Rule {
+    // 묶인 변수로 전개식을 채웁니다. `indices`는 지금 전개 중인 반복들의 몇 번째 회차인지입니다.
+    fn transcribe(
+        &self,
+        trees: &[MacroTree],
+        bindings: &Bindings,
+        indices: &mut Vec<usize>,
+        ctx: usize,
+        call_site: Span,
+        output: &mut Vec<TokenTree>,
+    ) -> Result<(), ExpandError> {
+        for tree in trees {
+            match tree {
+                MacroTree::Token(token) => {
+                    let mut token = token.clone().with_span(call_site);
+                    if token.kind == TokenKind::Ident && self.locals.contains(&token.text) {
+                        token.text = format!("{}#{}", token.text, ctx);
+                    }
+                    output.push(TokenTree::Token(token));
+                },
+                MacroTree::Delimited(delim, inner) => {
+                    let mut trees = Vec::new();
+                    self.transcribe(inner, bindings, indices, ctx, call_site, &mut trees)?;
+                    output.push(TokenTree::Delimited(DelimSpan::from_single(call_site), *delim, trees));
+                },
+                MacroTree::MetaVar(name, _) => match lookup(bindings, name, indices) {
+                    // 묶이지 않은 `$name`은 그대로 둡니다.
+                    None => {
+                        output.push(TokenTree::Token(Token::new(TokenKind::Dollar, "$".to_string()).with_span(call_site)));
+                        output.push(TokenTree::Token(Token::new(TokenKind::Ident, name.clone()).with_span(call_site)));
+                    },
+                    // 여러 토큰으로 된 표현식은 우선순위가 바뀌지 않도록 보이지 않는 구분자로 감쌉니다.
+                    Some(Binding::Fragment(FragmentKind::Expr, trees)) if trees.len() > 1 => {
+                        let span = DelimSpan::from_single(trees_span(trees));
+                        output.push(TokenTree::Delimited(span, Delimiter::Invisible, trees.clone()));
+                    },
+                    Some(Binding::Fragment(_, trees)) => output.extend(trees.iter().cloned()),
+                    Some(Binding::Seq(_)) => {
+                        return Err(ExpandError::new(format!("variable `{}` is still repeating at this depth", name)));
+                    },
+                },
+                MacroTree::Repeat(rep) => {
+                    let count = repeat_count(rep, bindings, indices)?;
+                    for i in 0..count {
+                        if let (Some(separator), true) = (&rep.separator, i > 0) {
+                            output.push(TokenTree::Token(separator.clone().with_span(call_site)));
+                        }
+                        indices.push(i);
+                        self.transcribe(&rep.trees, bindings, indices, ctx, call_site, output)?;
+                        indices.pop();
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+// 반복 회차 `indices`에서 변수 `name`의 값을 찾습니다. 바깥 깊이에서 묶인 변수는 회차마다 같은 값입니다.
+fn lookup<'a>(bindings: &'a Bindings, name: &str, indices: &[usize]) -> Option<&'a Binding> {
+    let mut binding = bindings.get(name)?;
+    for &index in indices {
+        match binding {
+            Binding::Seq(values) => binding = values.get(index)?,
+            Binding::Fragment(..) => break,
+        }
+    }
+    Some(binding)
+}
+
+// 전개식의 반복을 몇 번 펼칠지 안에 쓰인 반복 변수의 값 개수로 정합니다.
+fn repeat_count(rep: &Repetition, bindings: &Bindings, indices: &[usize]) -> Result<usize, ExpandError> {
+    let mut names = Vec::new();
+    binders(&rep.trees, &mut names);
+
+    let mut counts = names.iter().filter_map(|name| match lookup(bindings, name, indices) {
+        Some(Binding::Seq(values)) => Some((name, values.len())),
+        _ => None,
+    });
+    let Some((first_name, count)) = counts.next() else {
+        return Err(ExpandError::new(
+            "attempted to repeat an expression containing no syntax variables matched as repeating at this depth",
+        ));
+    };
+    if let Some((name, other)) = counts.find(|(_, other)| *other != count) {
+        return Err(ExpandError::new(format!(
+            "meta-variable `{}` repeats {} times, but `{}` repeats {} times",
+            first_name, count, name, other
+        )));
+    }
+    Ok(count)
+}
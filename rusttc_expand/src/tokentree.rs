@@ -0,0 +1,220 @@
+use rusttc_ast::arena::MAX_DEPTH;
+use rusttc_lexer::{Span, Token, TokenKind};
+
+use crate::ExpandError;
+
+// 토큰 트리를 감싸는 구분자입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Delimiter {
+    Paren,                      // ( ... )
+    Bracket,                    // [ ... ]
+    Brace,                      // { ... }
+    Invisible,                  // `$x:expr`로 치환된 표현식 (토큰으로 펼칠 때 괄호가 됩니다)
+}
+
+impl Delimiter {
+    fn from_open(kind: &TokenKind) -> Option<Delimiter> {
+        match kind {
+            TokenKind::OpenParen => Some(Delimiter::Paren),
+            TokenKind::OpenBracket => Some(Delimiter::Bracket),
+            TokenKind::OpenBrace => Some(Delimiter::Brace),
+            _ => None,
+        }
+    }
+
+    fn tokens(self) -> (Token, Token) {
+        let (open, close) = match self {
+            Delimiter::Paren | Delimiter::Invisible => ((TokenKind::OpenParen, "("), (TokenKind::CloseParen, ")")),
+            Delimiter::Bracket => ((TokenKind::OpenBracket, "["), (TokenKind::CloseBracket, "]")),
+            Delimiter::Brace => ((TokenKind::OpenBrace, "{"), (TokenKind::CloseBrace, "}")),
+        };
+        (Token::new(open.0, open.1.to_string()), Token::new(close.0, close.1.to_string()))
+    }
+}
+
+// 여는 구분자와 닫는 구분자의 위치입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DelimSpan {
+    pub open: Span,
+    pub close: Span,
+}
+
+impl DelimSpan {
+    // 전개로 만든 구분자는 양쪽 모두 `span`에 둡니다.
+    pub fn from_single(span: Span) -> Self {
+        DelimSpan { open: span, close: span }
+    }
+}
+
+// 토큰 하나, 또는 구분자로 묶인 토큰 트리의 나열입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenTree {
+    Token(Token),
+    Delimited(DelimSpan, Delimiter, Vec<TokenTree>),
+}
+
+impl TokenTree {
+    // 종류가 `kind`인 토큰인지 확인합니다.
+    pub fn is_token(&self, kind: &TokenKind) -> bool {
+        matches!(self, TokenTree::Token(token) if token.kind == *kind)
+    }
+
+    // 텍스트가 `text`인 식별자인지 확인합니다.
+    pub fn is_ident(&self, text: &str) -> bool {
+        matches!(self, TokenTree::Token(token) if token.kind == TokenKind::Ident && token.text == text)
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            TokenTree::Token(token) => token.span,
+            TokenTree::Delimited(span, ..) => span.open.to(span.close),
+        }
+    }
+}
+
+// 트리 목록 전체가 차지하는 위치입니다.
+pub fn trees_span(trees: &[TokenTree]) -> Span {
+    match trees {
+        [] => Span::DUMMY,
+        [first, .., last] => first.span().to(last.span()),
+        [only] => only.span(),
+    }
+}
+
+// 공백이 제거된 토큰 목록을 토큰 트리로 묶습니다.
+pub fn parse_trees(tokens: &[Token]) -> Result<Vec<TokenTree>, ExpandError> {
+    // (여는 구분자와 그 위치, 그 앞까지 모은 트리)
+    let mut stack: Vec<(Delimiter, Span, Vec<TokenTree>)> = Vec::new();
+    let mut trees = Vec::new();
+
+    for token in tokens {
+        if let Some(delim) = Delimiter::from_open(&token.kind) {
+            // 전개와 파싱은 괄호마다 재귀로 내려가므로 너무 깊이 겹친 괄호는 여기서 멈춥니다.
+            if stack.len() >= MAX_DEPTH {
+                return Err(ExpandError::new(format!("nesting exceeds the supported depth of {} levels", MAX_DEPTH)).with_span(token.span));
+            }
+            stack.push((delim, token.span, std::mem::take(&mut trees)));
+            continue;
+        }
+        if matches!(token.kind, TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseBrace) {
+            match stack.pop() {
+                Some((delim, open, outer)) if delim.tokens().1.kind == token.kind => {
+                    let inner = std::mem::replace(&mut trees, outer);
+                    trees.push(TokenTree::Delimited(DelimSpan { open, close: token.span }, delim, inner));
+                },
+                Some((delim, _, _)) => {
+                    return Err(ExpandError::new(format!(
+                        "mismatched closing delimiter: `{}` (unclosed `{}`)",
+                        token.text,
+                        delim.tokens().0.text
+                    ))
+                    .with_span(token.span));
+                },
+                None => {
+                    return Err(ExpandError::new(format!("unexpected closing delimiter: `{}`", token.text)).with_span(token.span));
+                },
+            }
+            continue;
+        }
+        trees.push(TokenTree::Token(token.clone()));
+    }
+
+    if let Some((_, open, _)) = stack.last() {
+        return Err(ExpandError::new("this file contains an unclosed delimiter").with_span(*open));
+    }
+    Ok(trees)
+}
+
+// 토큰 트리를 다시 토큰 목록으로 펼칩니다.
+pub fn flatten(trees: &[TokenTree], tokens: &mut Vec<Token>) {
+    for tree in trees {
+        match tree {
+            TokenTree::Token(token) => tokens.push(token.clone()),
+            TokenTree::Delimited(span, delim, inner) => {
+                let (open, close) = delim.tokens();
+                tokens.push(open.with_span(span.open));
+                flatten(inner, tokens);
+                tokens.push(close.with_span(span.close));
+            },
+        }
+    }
+}
+
+// 토큰 트리를 소스 코드처럼 보이는 문자열로 바꿉니다. `stringify!`와 오류 메시지에 씁니다.
+pub fn to_source(trees: &[TokenTree]) -> String {
+    let mut tokens = Vec::new();
+    flatten_visible(trees, &mut tokens);
+
+    let mut source = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 && needs_space(&tokens[..i], token) {
+            source.push(' ');
+        }
+        source.push_str(&token.text);
+    }
+    source
+}
+
+// 보이지 않는 구분자는 건너뛰고 펼칩니다.
+fn flatten_visible(trees: &[TokenTree], tokens: &mut Vec<Token>) {
+    for tree in trees {
+        match tree {
+            TokenTree::Delimited(_, Delimiter::Invisible, inner) => flatten_visible(inner, tokens),
+            TokenTree::Delimited(_, delim, inner) => {
+                let (open, close) = delim.tokens();
+                tokens.push(open);
+                flatten_visible(inner, tokens);
+                tokens.push(close);
+            },
+            TokenTree::Token(token) => tokens.push(token.clone()),
+        }
+    }
+}
+
+// `before` 다음에 `token`을 쓸 때 사이에 공백이 필요한지 판단합니다.
+fn needs_space(before: &[Token], token: &Token) -> bool {
+    let prev = &before[before.len() - 1];
+    let glued_after = matches!(
+        prev.kind,
+        TokenKind::OpenParen | TokenKind::OpenBracket | TokenKind::Dot | TokenKind::PathSep | TokenKind::Pound
+    );
+    let glued_before = matches!(
+        token.kind,
+        TokenKind::CloseParen
+            | TokenKind::CloseBracket
+            | TokenKind::Comma
+            | TokenKind::Semi
+            | TokenKind::Dot
+            | TokenKind::PathSep
+            | TokenKind::Question
+    );
+    if glued_after || glued_before {
+        return false;
+    }
+
+    let prev_is_operand = is_operand_end(prev);
+    match token.kind {
+        // 호출 `f(x)`, 인덱스 `a[i]`, 매크로 `m!`
+        TokenKind::OpenParen | TokenKind::OpenBracket | TokenKind::Bang => !prev_is_operand,
+        _ => {
+            // 단항 연산자 `!x`, `-x`, `*x`, `&x` 뒤에는 붙여 씁니다.
+            let unary = matches!(prev.kind, TokenKind::Bang | TokenKind::Minus | TokenKind::Star | TokenKind::And)
+                && !before[..before.len() - 1].last().is_some_and(is_operand_end);
+            !unary
+        },
+    }
+}
+
+// 피연산자가 끝나는 토큰인지 확인합니다. 그 뒤의 `-`, `*`, `&`는 이항 연산자입니다.
+fn is_operand_end(token: &Token) -> bool {
+    match token.kind {
+        TokenKind::Ident => !matches!(token.text.as_str(), "if" | "while" | "match" | "return" | "in" | "let"),
+        TokenKind::Literal
+        | TokenKind::StringLiteral
+        | TokenKind::CharLiteral
+        | TokenKind::CloseParen
+        | TokenKind::CloseBracket
+        | TokenKind::CloseBrace => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use rusttc_ast::ast::{AstArena, Attribute, Block, FnDef, Item, Lit, MetaItem, MetaItemKind, NestedMeta, Stmt, StmtKind};
+use rusttc_ast::mut_visit::{walk_block, MutVisitor};
+
+// 속성을 처리하는 중에 발생한 오류입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CfgError {
+    pub message: String,
+}
+
+impl CfgError {
+    fn new(message: impl Into<String>) -> Self {
+        CfgError { message: message.into() }
+    }
+}
+
+impl fmt::Display for CfgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)
+    }
+}
+
+// 컴파일러가 알고 있는 속성입니다. 그 밖의 이름은 오류입니다.
+// `derive`와 `inline`은 아직 아무 일도 하지 않지만 받아들입니다.
+const BUILTIN_ATTRIBUTES: &[&str] = &["allow", "cfg", "deny", "derive", "inline", "test", "warn"];
+
+// 켜져 있는 cfg 옵션입니다. `test`처럼 이름만 있거나 `feature = "x"`처럼 값이 있습니다.
+#[derive(Debug, Clone, Default)]
+pub struct CfgOptions {
+    options: HashSet<(String, Option<String>)>,
+}
+
+impl CfgOptions {
+    pub fn insert(&mut self, name: &str, value: Option<&str>) {
+        self.options.insert((name.to_string(), value.map(str::to_string)));
+    }
+
+    // 명령줄의 `--cfg test`, `--cfg feature="x"` 형태를 받아들입니다.
+    pub fn insert_spec(&mut self, spec: &str) -> Result<(), CfgError> {
+        let invalid = || CfgError::new(format!("invalid `--cfg` argument: `{}`", spec));
+        match spec.split_once('=') {
+            None => self.insert(spec, None),
+            Some((name, value)) => {
+                let value = value.strip_prefix('"').and_then(|value| value.strip_suffix('"')).ok_or_else(invalid)?;
+                self.insert(name, Some(value));
+            },
+        }
+        Ok(())
+    }
+
+    fn contains(&self, name: &str, value: Option<&str>) -> bool {
+        self.options.contains(&(name.to_string(), value.map(str::to_string)))
+    }
+}
+
+// `#[cfg(...)]` 조건이 거짓인 아이템을 지웁니다. `test` 옵션이 꺼져 있으면 `#[test]` 함수도 지웁니다.
+// 모듈, impl, 트레이트 안과 함수 본문 안의 아이템까지 내려가며, 알 수 없는 속성은 오류입니다.
+pub fn strip_unconfigured(items: &mut Vec<Item>, arena: &mut AstArena, cfg: &CfgOptions) -> Result<(), CfgError> {
+    let mut configured = Vec::new();
+    for mut item in std::mem::take(items) {
+        if !is_configured(item.attrs(), cfg)? {
+            continue;
+        }
+        match &mut item {
+            Item::Fn(function) => {
+                check_test_fn(function)?;
+                if is_test(&function.attrs) && !cfg.contains("test", None) {
+                    continue;
+                }
+                strip_fn(function, arena, cfg)?;
+            },
+            Item::Impl(imp) => strip_fns(&mut imp.items, arena, cfg)?,
+            Item::Trait(def) => strip_fns(&mut def.items, arena, cfg)?,
+            Item::Mod(def) => {
+                if let Some(items) = &mut def.items {
+                    strip_unconfigured(items, arena, cfg)?;
+                }
+            },
+            Item::Struct(_) | Item::Use(_) | Item::Const(_) | Item::Static(_) => {},
+        }
+        if !matches!(item, Item::Fn(_)) && is_test(item.attrs()) {
+            return Err(CfgError::new("the `#[test]` attribute may only be used on a non-associated function"));
+        }
+        configured.push(item);
+    }
+    *items = configured;
+    Ok(())
+}
+
+// 속성 목록의 cfg 조건이 모두 참인지 확인합니다.
+pub fn is_configured(attrs: &[Attribute], cfg: &CfgOptions) -> Result<bool, CfgError> {
+    for attr in attrs {
+        let Some(name) = attr.meta.path.as_ident().filter(|name| BUILTIN_ATTRIBUTES.contains(name)) else {
+            return Err(CfgError::new(format!("cannot find attribute `{}` in this scope", attr.meta.path)));
+        };
+        if name != "cfg" {
+            continue;
+        }
+        let predicate = match &attr.meta.kind {
+            MetaItemKind::List(list) => match list.as_slice() {
+                [] => return Err(CfgError::new("`cfg` predicate is not specified")),
+                [predicate] => predicate,
+                _ => return Err(CfgError::new("multiple `cfg` predicates are specified")),
+            },
+            _ => return Err(CfgError::new("malformed `cfg` attribute input")),
+        };
+        if !eval_nested(predicate, cfg)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+pub fn is_test(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.has_name("test"))
+}
+
+// `all(...)`, `any(...)`, `not(...)`, `name`, `name = "value"`
+fn eval_cfg(meta: &MetaItem, cfg: &CfgOptions) -> Result<bool, CfgError> {
+    let Some(name) = meta.path.as_ident() else {
+        return Err(CfgError::new("`cfg` predicate key must be an identifier"));
+    };
+    match &meta.kind {
+        MetaItemKind::Word => Ok(cfg.contains(name, None)),
+        MetaItemKind::NameValue(Lit::Str(value)) => Ok(cfg.contains(name, Some(value))),
+        MetaItemKind::NameValue(_) => Err(CfgError::new("literal in `cfg` predicate value must be a string")),
+        MetaItemKind::List(list) => {
+            let values = list.iter().map(|nested| eval_nested(nested, cfg)).collect::<Result<Vec<_>, _>>()?;
+            match name {
+                "all" => Ok(values.iter().all(|&value| value)),
+                "any" => Ok(values.iter().any(|&value| value)),
+                "not" if values.len() == 1 => Ok(!values[0]),
+                "not" => Err(CfgError::new("expected 1 cfg-pattern")),
+                _ => Err(CfgError::new(format!("invalid predicate `{}`", name))),
+            }
+        },
+    }
+}
+
+fn eval_nested(nested: &NestedMeta, cfg: &CfgOptions) -> Result<bool, CfgError> {
+    match nested {
+        NestedMeta::Meta(meta) => eval_cfg(meta, cfg),
+        NestedMeta::Lit(_) => Err(CfgError::new("unsupported literal")),
+    }
+}
+
+fn check_test_fn(function: &FnDef) -> Result<(), CfgError> {
+    if is_test(&function.attrs) && (!function.params.is_empty() || function.self_param.is_some()) {
+        return Err(CfgError::new("functions used as tests can not have any arguments"));
+    }
+    Ok(())
+}
+
+// impl과 트레이트의 함수에는 `#[test]`를 붙일 수 없습니다.
+fn strip_fns(functions: &mut Vec<FnDef>, arena: &mut AstArena, cfg: &CfgOptions) -> Result<(), CfgError> {
+    let mut configured = Vec::new();
+    for mut function in std::mem::take(functions) {
+        if is_test(&function.attrs) {
+            return Err(CfgError::new("the `#[test]` attribute may only be used on a non-associated function"));
+        }
+        if is_configured(&function.attrs, cfg)? {
+            strip_fn(&mut function, arena, cfg)?;
+            configured.push(function);
+        }
+    }
+    *functions = configured;
+    Ok(())
+}
+
+fn strip_fn(function: &mut FnDef, arena: &mut AstArena, cfg: &CfgOptions) -> Result<(), CfgError> {
+    let mut stripper = BlockStripper { cfg, arena, error: None };
+    if let Some(body) = &mut function.body {
+        stripper.visit_block(body);
+    }
+    stripper.error.map_or(Ok(()), Err)
+}
+
+// 함수 본문 안의 블록을 찾아 그 안의 아이템을 정리합니다. 오류는 처음 것만 남깁니다.
+struct BlockStripper<'a> {
+    cfg: &'a CfgOptions,
+    arena: &'a mut AstArena,
+    error: Option<CfgError>,
+}
+
+impl MutVisitor for BlockStripper<'_> {
+    fn arena(&mut self) -> &mut AstArena {
+        self.arena
+    }
+
+    fn visit_block(&mut self, block: &mut Block) {
+        let mut stmts = Vec::new();
+        for stmt in std::mem::take(&mut block.stmts) {
+            let Stmt { id, span, kind } = self.arena.take_stmt(stmt);
+            match kind {
+                StmtKind::Item(item) => {
+                    let mut items = vec![item];
+                    if let Err(error) = strip_unconfigured(&mut items, self.arena, self.cfg) {
+                        self.error.get_or_insert(error);
+                    }
+                    if let Some(item) = items.pop() {
+                        self.arena[stmt] = Stmt { id, span, kind: StmtKind::Item(item) };
+                        stmts.push(stmt);
+                    }
+                },
+                kind => {
+                    self.arena[stmt] = Stmt { id, span, kind };
+                    stmts.push(stmt);
+                },
+            }
+        }
+        block.stmts = stmts;
+        walk_block(self, block);
+    }
+
+    // 아이템 안은 `strip_unconfigured`가 이미 정리했습니다.
+    fn visit_item(&mut self, _item: &mut Item) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusttc_lexer::tokenize;
+    use rusttc_parse::parser::parse_crate;
+
+    fn strip(source: &str, options: &[&str]) -> Result<Vec<Item>, String> {
+        let mut cfg = CfgOptions::default();
+        for option in options {
+            cfg.insert_spec(option).unwrap();
+        }
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        strip_unconfigured(&mut krate.items, &mut krate.arena, &cfg).map_err(|error| error.message)?;
+        let items = krate.items;
+        Ok(items)
+    }
+
+    fn fn_names(items: &[Item]) -> Vec<String> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Fn(function) => Some(function.name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cfg_predicates_strip_items() {
+        let source = r#"
+            #[cfg(test)] fn only_test() {}
+            #[cfg(not(test))] fn not_test() {}
+            #[cfg(all(unix, feature = "fast"))] fn fast_unix() {}
+            #[cfg(any(windows, feature = "fast"))] fn fast_or_windows() {}
+            #[test] fn it_works() {}
+            fn main() { #[cfg(windows)] fn inner() {} }
+        "#;
+        let items = strip(source, &[]).unwrap();
+        assert_eq!(fn_names(&items), vec!["not_test", "main"]);
+        let Item::Fn(main) = &items[1] else { panic!() };
+        assert!(main.body.as_ref().unwrap().stmts.is_empty());
+
+        let items = strip(source, &["test", "unix", "feature=\"fast\""]).unwrap();
+        assert_eq!(fn_names(&items), vec!["only_test", "fast_unix", "fast_or_windows", "it_works", "main"]);
+    }
+
+    #[test]
+    fn test_cfg_in_modules_and_impls() {
+        let source = "
+            struct S;
+            impl S { #[cfg(debug)] fn debug(&self) {} fn keep(&self) {} }
+            #[cfg(test)] mod tests { #[test] fn t() {} }
+        ";
+        let items = strip(source, &[]).unwrap();
+        assert_eq!(items.len(), 2);
+        let Item::Impl(imp) = &items[1] else { panic!() };
+        assert_eq!(imp.items.len(), 1);
+        assert_eq!(imp.items[0].name, "keep");
+    }
+
+    #[test]
+    fn test_invalid_attributes() {
+        assert_eq!(strip("#[cfg] fn f() {}", &[]).unwrap_err(), "malformed `cfg` attribute input");
+        assert_eq!(strip("#[cfg()] fn f() {}", &[]).unwrap_err(), "`cfg` predicate is not specified");
+        assert_eq!(strip("#[cfg(a, b)] fn f() {}", &[]).unwrap_err(), "multiple `cfg` predicates are specified");
+        assert_eq!(strip("#[cfg(maybe(a))] fn f() {}", &[]).unwrap_err(), "invalid predicate `maybe`");
+        assert_eq!(strip("#[frobnicate] fn f() {}", &[]).unwrap_err(), "cannot find attribute `frobnicate` in this scope");
+        assert_eq!(
+            strip("#[test] fn t(x: i32) {}", &["test"]).unwrap_err(),
+            "functions used as tests can not have any arguments"
+        );
+        assert_eq!(
+            strip("#[test] struct S;", &[]).unwrap_err(),
+            "the `#[test]` attribute may only be used on a non-associated function"
+        );
+    }
+}
@@ -0,0 +1,701 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use rusttc_ast::ast::{FloatTy, IntTy};
+use rusttc_lexer::{Token, TokenKind};
+
+use crate::ir::{BinaryExpression, Const, IrNode, UnaryExpression};
+
+// 평가한 노드 수가 이보다 많으면 끝나지 않는 계산으로 보고 멈춥니다.
+const STEP_LIMIT: usize = 1_000_000;
+
+// 컴파일 중에 계산한 값입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(u128, IntTy),                       // 정수 (음수는 타입 비트 수만큼의 2의 보수)
+    Float(f64, FloatTy),
+    Bool(bool),
+    Char(char),
+    Str(String),
+    Ref(Box<Value>),                        // 참조 (가리키는 값을 그대로 품습니다)
+    Array(Vec<Value>),                      // 배열과 슬라이스
+    Tuple(Vec<Value>),                      // 튜플 (`()`는 빈 튜플)
+    Struct(String, Vec<(String, Value)>),   // 구조체 이름과 필드
+}
+
+impl Value {
+    pub fn unit() -> Value {
+        Value::Tuple(Vec::new())
+    }
+
+    // `usize` 값이면 그 값입니다. 배열 길이에 씁니다.
+    pub fn as_usize(&self) -> Option<u128> {
+        match *self {
+            Value::Int(value, IntTy::Usize) => Some(value),
+            _ => None,
+        }
+    }
+
+    // 값을 같은 값을 내는 IR 상수 식으로 바꿉니다. 음수는 `-`를 붙인 절댓값 리터럴입니다.
+    // 최솟값도 `-2147483648i32`처럼 리터럴을 부정한 모양이 되며, 이 모양은 다시 평가해도 넘치지 않습니다.
+    pub fn to_ir(&self) -> IrNode {
+        match self {
+            Value::Int(value, ty) if ty.is_signed() && signed(*value, *ty) < 0 => {
+                negate(IrNode::Constant(Const::Int(signed(*value, *ty).unsigned_abs(), Some(*ty))))
+            },
+            Value::Int(value, ty) => IrNode::Constant(Const::Int(*value, Some(*ty))),
+            Value::Float(value, ty) if value.is_sign_negative() => negate(IrNode::Constant(Const::Float(-value, *ty))),
+            Value::Float(value, ty) => IrNode::Constant(Const::Float(*value, *ty)),
+            Value::Bool(b) => IrNode::Boolean(*b),
+            Value::Char(c) => IrNode::Constant(Const::Char(*c)),
+            Value::Str(text) => IrNode::Str(text.clone()),
+            Value::Ref(value) => IrNode::Ref { mutable: false, place: Box::new(value.to_ir()) },
+            Value::Array(elems) => IrNode::Array(elems.iter().map(Value::to_ir).collect()),
+            Value::Tuple(elems) => IrNode::Tuple(elems.iter().map(Value::to_ir).collect()),
+            Value::Struct(name, fields) => IrNode::StructInit {
+                name: name.clone(),
+                fields: fields.iter().map(|(field, value)| (field.clone(), value.to_ir())).collect(),
+            },
+        }
+    }
+}
+
+// `panic!` 메시지의 `{}` 자리에 들어가는 모양입니다.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(value, ty) if ty.is_signed() => write!(f, "{}", signed(*value, *ty)),
+            Value::Int(value, _) => write!(f, "{}", value),
+            Value::Float(value, _) => write!(f, "{}", value),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Char(c) => write!(f, "{}", c),
+            Value::Str(text) => write!(f, "{}", text),
+            Value::Ref(value) => write!(f, "{}", value),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+// 상수 평가가 실패한 까닭입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstEvalError {
+    Failed(String),             // 계산이 실패했습니다 (넘침, 0으로 나누기, 범위 밖 인덱스 등)
+    Cycle(String),              // 이 상수의 초기값이 자기 자신을 거쳐 다시 이 상수에 닿습니다
+    Erroneous(String),          // 초기값이 쓰는 다른 상수의 평가가 실패했습니다
+}
+
+impl fmt::Display for ConstEvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstEvalError::Failed(message) => write!(f, "{}", message),
+            ConstEvalError::Cycle(name) => write!(f, "cycle detected when const-evaluating + checking `{}`", name),
+            ConstEvalError::Erroneous(name) => write!(f, "referenced constant `{}` has errors", name),
+        }
+    }
+}
+
+// 평가할 `const`, `static` 아이템 하나입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstItem {
+    pub name: String,           // 전체 이름
+    pub init: IrNode,           // IR로 바꾼 초기값 (숫자 리터럴에는 추론한 타입이 붙어 있어야 합니다)
+    pub is_static: bool,
+}
+
+// 평가를 멈추고 바깥으로 나가는 까닭입니다.
+enum Flow {
+    Break,
+    Continue,
+    Error(ConstEvalError),
+}
+
+impl From<ConstEvalError> for Flow {
+    fn from(error: ConstEvalError) -> Self {
+        Flow::Error(error)
+    }
+}
+
+type EvalResult<T = Value> = Result<T, Flow>;
+
+fn fail(message: impl Into<String>) -> Flow {
+    Flow::Error(ConstEvalError::Failed(message.into()))
+}
+
+// 장소 표현식을 따라가는 한 걸음입니다.
+enum Projection {
+    Field(String),
+    Index(usize),
+    Deref,
+}
+
+// 타입 검사를 마친 IR을 직접 실행해 상수 값을 계산하는 인터프리터입니다.
+// 다른 상수를 쓰면 그 상수를 먼저 (한 번만) 계산하고, 자기 자신으로 돌아오는 초기값은 순환으로 보고합니다.
+// 정수 연산은 타입의 범위를 넘거나 0으로 나누면 실패하고, 인덱스는 범위를 검사합니다.
+pub struct ConstEvaluator<'a> {
+    items: HashMap<&'a str, &'a ConstItem>,
+    values: HashMap<String, Result<Value, ConstEvalError>>,    // 계산을 마친 상수
+    stack: Vec<String>,                                         // 계산 중인 상수 (바깥이 앞)
+    scopes: Vec<HashMap<String, Value>>,                        // 초기값 안의 지역 변수 (안쪽이 뒤)
+    steps: usize,
+}
+
+impl<'a> ConstEvaluator<'a> {
+    pub fn new(items: &'a [ConstItem]) -> Self {
+        ConstEvaluator {
+            items: items.iter().map(|item| (item.name.as_str(), item)).collect(),
+            values: HashMap::new(),
+            stack: Vec::new(),
+            scopes: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    // 이름이 `name`인 상수의 값을 계산합니다. 결과는 기억해 두었다가 다시 씁니다.
+    pub fn eval_const(&mut self, name: &str) -> Result<Value, ConstEvalError> {
+        if let Some(result) = self.values.get(name) {
+            return result.clone();
+        }
+        let Some(item) = self.items.get(name).copied() else {
+            return Err(ConstEvalError::Failed(format!("cannot find constant `{}`", name)));
+        };
+        if self.stack.iter().any(|outer| outer == name) {
+            return Err(ConstEvalError::Cycle(name.to_string()));
+        }
+
+        self.stack.push(name.to_string());
+        let scopes = std::mem::take(&mut self.scopes);
+        let result = self.eval_expr(&item.init);
+        self.scopes = scopes;
+        self.stack.pop();
+
+        // 순환에 걸린 상수들은 모두 순환의 시작점과 같은 오류를 돌려받습니다.
+        let result = match result {
+            Ok(value) => Ok(value),
+            Err(Flow::Error(ConstEvalError::Cycle(start))) if start != name && self.stack.contains(&start) => {
+                return Err(ConstEvalError::Cycle(start));
+            },
+            Err(Flow::Error(error)) => Err(error),
+            Err(Flow::Break | Flow::Continue) => Err(ConstEvalError::Failed("`break` outside of a loop".to_string())),
+        };
+        self.values.insert(name.to_string(), result.clone());
+        result
+    }
+
+    // 상수 아이템이 아닌 식 하나를 계산합니다 (배열 길이 `[0; N * 2]` 등).
+    pub fn eval(&mut self, node: &IrNode) -> Result<Value, ConstEvalError> {
+        let scopes = std::mem::take(&mut self.scopes);
+        let result = self.eval_expr(node);
+        self.scopes = scopes;
+        match result {
+            Ok(value) => Ok(value),
+            Err(Flow::Error(error)) => Err(error),
+            Err(Flow::Break | Flow::Continue) => Err(ConstEvalError::Failed("`break` outside of a loop".to_string())),
+        }
+    }
+
+    // 함수 본문의 상수 표현식을 그 값으로 접습니다. 상수 아이템을 쓰는 곳은 그 값이 되고,
+    // 피연산자가 모두 상수인 연산은 계산한 결과가 됩니다. 실행하면 실패할 연산(넘침 등)은 그대로 둡니다.
+    // `static`은 주소를 가진 변수이므로 펼치지 않습니다. `locals`는 본문 안에서 바인딩된 이름입니다.
+    pub fn fold(&mut self, node: &mut IrNode, locals: &HashSet<String>) {
+        match node {
+            IrNode::Constant(_) | IrNode::Boolean(_) | IrNode::Str(_) | IrNode::Break | IrNode::Continue => {},
+            IrNode::Variable(name) => {
+                let is_const = self.items.get(name.as_str()).is_some_and(|item| !item.is_static);
+                if is_const && !locals.contains(name) {
+                    if let Ok(value) = self.eval_const(name) {
+                        *node = value.to_ir();
+                    }
+                }
+            },
+            IrNode::BinaryExpression(binary) => {
+                self.fold(&mut binary.left, locals);
+                self.fold(&mut binary.right, locals);
+                if is_constant(&binary.left) && is_constant(&binary.right) {
+                    self.fold_value(node);
+                }
+            },
+            IrNode::UnaryExpression(unary) => {
+                self.fold(&mut unary.operand, locals);
+                let folds = unary.operator.kind != TokenKind::Star && is_constant(&unary.operand);
+                if folds && !is_constant(node) {
+                    self.fold_value(node);
+                }
+            },
+            IrNode::Ref { place, .. } => self.fold(place, locals),
+            IrNode::Call { args, .. } | IrNode::Array(args) | IrNode::Tuple(args) | IrNode::Panic { args, .. } => {
+                args.iter_mut().for_each(|arg| self.fold(arg, locals));
+            },
+            IrNode::Field { base, .. } | IrNode::Len(base) | IrNode::Loop(base) => self.fold(base, locals),
+            IrNode::StructInit { fields, .. } => fields.iter_mut().for_each(|(_, value)| self.fold(value, locals)),
+            IrNode::Let { value, .. } | IrNode::Return(value) => {
+                if let Some(value) = value {
+                    self.fold(value, locals);
+                }
+            },
+            // 대입 대상은 장소이므로 값만 접습니다.
+            IrNode::Assign { value, .. } => self.fold(value, locals),
+            IrNode::Block { stmts, value } => {
+                stmts.iter_mut().for_each(|stmt| self.fold(stmt, locals));
+                if let Some(value) = value {
+                    self.fold(value, locals);
+                }
+            },
+            IrNode::If { cond, then, els } => {
+                self.fold(cond, locals);
+                self.fold(then, locals);
+                if let Some(els) = els {
+                    self.fold(els, locals);
+                }
+            },
+            IrNode::Repeat { value: base, count: other } | IrNode::Index { base, index: other } => {
+                self.fold(base, locals);
+                self.fold(other, locals);
+            },
+            IrNode::Slice { base, start, end } => {
+                self.fold(base, locals);
+                self.fold(start, locals);
+                self.fold(end, locals);
+            },
+        }
+    }
+
+    fn fold_value(&mut self, node: &mut IrNode) {
+        self.steps = 0;
+        if let Ok(value) = self.eval(node) {
+            *node = value.to_ir();
+        }
+    }
+
+    fn step(&mut self, count: usize) -> EvalResult<()> {
+        self.steps += count;
+        if self.steps > STEP_LIMIT {
+            return Err(fail("constant evaluation is taking a long time"));
+        }
+        Ok(())
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Value> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    fn eval_expr(&mut self, node: &IrNode) -> EvalResult {
+        self.step(1)?;
+        match node {
+            IrNode::Constant(Const::Int(value, ty)) => Ok(Value::Int(*value, ty.unwrap_or(IntTy::I32))),
+            IrNode::Constant(Const::Float(value, ty)) => Ok(Value::Float(*value, *ty)),
+            IrNode::Constant(Const::Char(c)) => Ok(Value::Char(*c)),
+            IrNode::Boolean(b) => Ok(Value::Bool(*b)),
+            IrNode::Str(text) => Ok(Value::Str(text.clone())),
+            IrNode::Variable(name) => {
+                if let Some(value) = self.lookup(name) {
+                    return Ok(value.clone());
+                }
+                // 여기서 찾을 수 없는 이름은 타입 검사에 실패해 빠진 상수입니다.
+                if !self.items.contains_key(name.as_str()) {
+                    return Err(Flow::Error(ConstEvalError::Erroneous(name.clone())));
+                }
+                self.eval_const(name).map_err(|error| match error {
+                    ConstEvalError::Cycle(start) => Flow::Error(ConstEvalError::Cycle(start)),
+                    _ => Flow::Error(ConstEvalError::Erroneous(name.clone())),
+                })
+            },
+            IrNode::BinaryExpression(binary) => self.eval_binary(binary),
+            IrNode::UnaryExpression(unary) => self.eval_unary(unary),
+            IrNode::Ref { place, .. } => Ok(Value::Ref(Box::new(self.eval_expr(place)?))),
+            IrNode::Call { func, .. } => Err(fail(format!("cannot call non-const fn `{}` in constants", func))),
+            IrNode::Field { base, field } => {
+                let base = self.eval_expr(base)?;
+                project_field(deref(base), field)
+            },
+            IrNode::StructInit { name, fields } => {
+                let fields = fields
+                    .iter()
+                    .map(|(field, value)| Ok((field.clone(), self.eval_expr(value)?)))
+                    .collect::<EvalResult<_>>()?;
+                Ok(Value::Struct(name.clone(), fields))
+            },
+            IrNode::Let { name, value, .. } => {
+                if let Some(value) = value {
+                    let value = self.eval_expr(value)?;
+                    self.scopes.last_mut().expect("`let` outside of a block").insert(name.clone(), value);
+                }
+                Ok(Value::unit())
+            },
+            IrNode::Assign { target, value, .. } => {
+                let value = self.eval_expr(value)?;
+                self.assign(target, value)?;
+                Ok(Value::unit())
+            },
+            IrNode::Block { stmts, value } => {
+                self.scopes.push(HashMap::new());
+                let result = self.eval_block(stmts, value.as_deref());
+                self.scopes.pop();
+                result
+            },
+            IrNode::If { cond, then, els } => match self.eval_expr(cond)? {
+                Value::Bool(true) => self.eval_expr(then),
+                Value::Bool(false) => match els {
+                    Some(els) => self.eval_expr(els),
+                    None => Ok(Value::unit()),
+                },
+                other => Err(fail(format!("expected `bool`, found `{}`", other))),
+            },
+            IrNode::Loop(body) => loop {
+                match self.eval_expr(body) {
+                    Ok(_) | Err(Flow::Continue) => {},
+                    Err(Flow::Break) => return Ok(Value::unit()),
+                    Err(error) => return Err(error),
+                }
+            },
+            IrNode::Break => Err(Flow::Break),
+            IrNode::Continue => Err(Flow::Continue),
+            IrNode::Return(_) => Err(fail("return statement outside of function body")),
+            IrNode::Array(elems) => Ok(Value::Array(elems.iter().map(|elem| self.eval_expr(elem)).collect::<EvalResult<_>>()?)),
+            IrNode::Tuple(elems) => Ok(Value::Tuple(elems.iter().map(|elem| self.eval_expr(elem)).collect::<EvalResult<_>>()?)),
+            IrNode::Repeat { value, count } => {
+                let value = self.eval_expr(value)?;
+                let count = self.eval_usize(count)?;
+                self.step(count)?;
+                Ok(Value::Array(vec![value; count]))
+            },
+            IrNode::Index { base, index } => {
+                let base = deref(self.eval_expr(base)?);
+                let index = self.eval_usize(index)?;
+                let Value::Array(elems) = base else {
+                    return Err(fail(format!("cannot index into a value of type `{:?}`", base)));
+                };
+                match elems.get(index) {
+                    Some(elem) => Ok(elem.clone()),
+                    None => Err(out_of_bounds(elems.len(), index)),
+                }
+            },
+            IrNode::Slice { base, start, end } => {
+                let base = deref(self.eval_expr(base)?);
+                let (start, end) = (self.eval_usize(start)?, self.eval_usize(end)?);
+                let Value::Array(elems) = base else {
+                    return Err(fail(format!("cannot index into a value of type `{:?}`", base)));
+                };
+                if start > end || end > elems.len() {
+                    return Err(out_of_bounds(elems.len(), end.max(start)));
+                }
+                Ok(Value::Array(elems[start..end].to_vec()))
+            },
+            IrNode::Len(base) => match deref(self.eval_expr(base)?) {
+                Value::Array(elems) => Ok(Value::Int(elems.len() as u128, IntTy::Usize)),
+                other => Err(fail(format!("cannot take the length of `{}`", other))),
+            },
+            IrNode::Panic { message, args, .. } => {
+                let args = args.iter().map(|arg| self.eval_expr(arg)).collect::<EvalResult<Vec<_>>>()?;
+                Err(fail(format!("evaluation panicked: {}", format_panic(message, &args))))
+            },
+        }
+    }
+
+    fn eval_block(&mut self, stmts: &[IrNode], value: Option<&IrNode>) -> EvalResult {
+        for stmt in stmts {
+            self.eval_expr(stmt)?;
+        }
+        match value {
+            Some(value) => self.eval_expr(value),
+            None => Ok(Value::unit()),
+        }
+    }
+
+    fn eval_usize(&mut self, node: &IrNode) -> EvalResult<usize> {
+        match self.eval_expr(node)? {
+            Value::Int(value, _) => Ok(value as usize),
+            other => Err(fail(format!("expected `usize`, found `{}`", other))),
+        }
+    }
+
+    fn eval_binary(&mut self, binary: &BinaryExpression) -> EvalResult {
+        let operator = &binary.operator;
+        let left = self.eval_expr(&binary.left)?;
+        // `&&`, `||`는 왼쪽만으로 값이 정해지면 오른쪽을 계산하지 않습니다.
+        match (&operator.kind, &left) {
+            (TokenKind::AndAnd, Value::Bool(false)) => return Ok(Value::Bool(false)),
+            (TokenKind::OrOr, Value::Bool(true)) => return Ok(Value::Bool(true)),
+            (TokenKind::AndAnd | TokenKind::OrOr, _) => return self.eval_expr(&binary.right),
+            _ => {},
+        }
+        let right = self.eval_expr(&binary.right)?;
+
+        // 타입 없는 정수 상수는 함께 연산하는 피연산자의 타입을 따릅니다.
+        let (left, right) = match (left, right) {
+            (Value::Int(a, _), Value::Int(b, ty)) if is_untyped(&binary.left) => (Value::Int(a, ty), Value::Int(b, ty)),
+            (Value::Int(a, ty), Value::Int(b, _)) if is_untyped(&binary.right) => (Value::Int(a, ty), Value::Int(b, ty)),
+            pair => pair,
+        };
+
+        if let Some(ordering) = compare(&left, &right) {
+            let result = match operator.kind {
+                TokenKind::EqEq => Some(ordering.is_eq()),
+                TokenKind::Ne => Some(ordering.is_ne()),
+                TokenKind::Lt => Some(ordering.is_lt()),
+                TokenKind::Le => Some(ordering.is_le()),
+                TokenKind::Gt => Some(ordering.is_gt()),
+                TokenKind::Ge => Some(ordering.is_ge()),
+                _ => None,
+            };
+            if let Some(result) = result {
+                return Ok(Value::Bool(result));
+            }
+        }
+        match (&operator.kind, &left, &right) {
+            (TokenKind::EqEq, _, _) => Ok(Value::Bool(left == right)),
+            (TokenKind::Ne, _, _) => Ok(Value::Bool(left != right)),
+            (_, Value::Int(a, ty), Value::Int(b, _)) => Ok(Value::Int(int_binary(operator, *a, *b, *ty)?, *ty)),
+            (_, Value::Float(a, ty), Value::Float(b, _)) => {
+                let value = match operator.kind {
+                    TokenKind::Plus => a + b,
+                    TokenKind::Minus => a - b,
+                    TokenKind::Star => a * b,
+                    TokenKind::Slash => a / b,
+                    TokenKind::Percent => a % b,
+                    _ => return Err(unsupported_binary(operator, &left)),
+                };
+                Ok(Value::Float(round_float(value, *ty), *ty))
+            },
+            (_, Value::Bool(a), Value::Bool(b)) => match operator.kind {
+                TokenKind::And => Ok(Value::Bool(a & b)),
+                TokenKind::Or => Ok(Value::Bool(a | b)),
+                TokenKind::Caret => Ok(Value::Bool(a ^ b)),
+                _ => Err(unsupported_binary(operator, &left)),
+            },
+            _ => Err(unsupported_binary(operator, &left)),
+        }
+    }
+
+    fn eval_unary(&mut self, unary: &UnaryExpression) -> EvalResult {
+        let operand = self.eval_expr(&unary.operand)?;
+        match (&unary.operator.kind, operand) {
+            (TokenKind::Star, Value::Ref(value)) => Ok(*value),
+            (TokenKind::Minus, Value::Int(value, ty)) => {
+                // 리터럴에 붙은 `-`는 부호 있는 리터럴의 일부입니다. `-2147483648i32`는 넘치지 않는 `i32::MIN`입니다.
+                let literal = matches!(unary.operand, IrNode::Constant(Const::Int(..)));
+                let negated = signed(value, ty).checked_neg().filter(|_| ty.is_signed() && (literal || value != ty.min_abs()));
+                match negated {
+                    Some(negated) => Ok(Value::Int(truncate(negated as u128, ty), ty)),
+                    None => Err(fail(format!("attempt to negate `{}`, which would overflow", show_int(value, ty)))),
+                }
+            },
+            (TokenKind::Minus, Value::Float(value, ty)) => Ok(Value::Float(-value, ty)),
+            (TokenKind::Bang, Value::Bool(b)) => Ok(Value::Bool(!b)),
+            (TokenKind::Bang, Value::Int(value, ty)) => Ok(Value::Int(truncate(!value, ty), ty)),
+            (_, operand) => Err(fail(format!("cannot apply unary operator `{}` to `{}`", unary.operator.text, operand))),
+        }
+    }
+
+    // 장소에 값을 씁니다. 장소는 지역 변수에서 시작해 필드, 인덱스, 역참조를 따라갑니다.
+    fn assign(&mut self, target: &IrNode, value: Value) -> EvalResult<()> {
+        let mut projections = Vec::new();
+        let mut place = target;
+        let name = loop {
+            match place {
+                IrNode::Variable(name) => break name,
+                IrNode::Field { base, field } => {
+                    projections.push(Projection::Field(field.clone()));
+                    place = base;
+                },
+                IrNode::Index { base, index } => {
+                    let index = self.eval_usize(index)?;
+                    projections.push(Projection::Index(index));
+                    place = base;
+                },
+                IrNode::UnaryExpression(unary) if unary.operator.kind == TokenKind::Star => {
+                    projections.push(Projection::Deref);
+                    place = &unary.operand;
+                },
+                _ => return Err(fail("invalid left-hand side of assignment")),
+            }
+        };
+
+        let slot = self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(name));
+        let Some(mut slot) = slot else {
+            return Err(fail(format!("cannot assign to `{}` in a constant", name)));
+        };
+        for projection in projections.iter().rev() {
+            slot = match (projection, slot) {
+                (Projection::Field(field), Value::Struct(_, fields)) => match fields.iter_mut().find(|(name, _)| name == field) {
+                    Some((_, value)) => value,
+                    None => return Err(fail(format!("no field `{}`", field))),
+                },
+                (Projection::Field(field), Value::Tuple(elems)) => match field.parse::<usize>().ok().and_then(|i| elems.get_mut(i)) {
+                    Some(value) => value,
+                    None => return Err(fail(format!("no field `{}`", field))),
+                },
+                (Projection::Index(index), Value::Array(elems)) => {
+                    let len = elems.len();
+                    match elems.get_mut(*index) {
+                        Some(value) => value,
+                        None => return Err(out_of_bounds(len, *index)),
+                    }
+                },
+                (Projection::Deref, Value::Ref(value)) => value,
+                _ => return Err(fail("invalid left-hand side of assignment")),
+            };
+        }
+        *slot = value;
+        Ok(())
+    }
+}
+
+// 값을 바꾸지 않는 상수 식인지 확인합니다. 음수 리터럴(`-1`)도 상수입니다.
+fn is_constant(node: &IrNode) -> bool {
+    match node {
+        IrNode::Constant(_) | IrNode::Boolean(_) => true,
+        IrNode::UnaryExpression(unary) => unary.operator.kind == TokenKind::Minus && matches!(unary.operand, IrNode::Constant(_)),
+        _ => false,
+    }
+}
+
+fn is_untyped(node: &IrNode) -> bool {
+    matches!(node, IrNode::Constant(Const::Int(_, None)))
+}
+
+fn negate(operand: IrNode) -> IrNode {
+    IrNode::UnaryExpression(Box::new(UnaryExpression { operator: Token::new(TokenKind::Minus, "-".to_string()), operand }))
+}
+
+// 참조를 벗겨 가리키는 값을 꺼냅니다. 필드 접근과 인덱스는 참조를 자동으로 따라갑니다.
+fn deref(value: Value) -> Value {
+    match value {
+        Value::Ref(value) => deref(*value),
+        value => value,
+    }
+}
+
+fn project_field(base: Value, field: &str) -> EvalResult {
+    let found = match base {
+        Value::Struct(_, fields) => fields.into_iter().find(|(name, _)| name == field).map(|(_, value)| value),
+        Value::Tuple(elems) => field.parse::<usize>().ok().and_then(|i| elems.into_iter().nth(i)),
+        _ => None,
+    };
+    found.ok_or_else(|| fail(format!("no field `{}`", field)))
+}
+
+fn out_of_bounds(len: usize, index: usize) -> Flow {
+    fail(format!("index out of bounds: the length is {} but the index is {}", len, index))
+}
+
+fn unsupported_binary(operator: &Token, left: &Value) -> Flow {
+    fail(format!("cannot apply binary operator `{}` to `{}`", operator.text, left))
+}
+
+// `panic!` 메시지의 `{}`를 인자 값으로 채우고 `{{`, `}}`를 되돌립니다.
+fn format_panic(message: &str, args: &[Value]) -> String {
+    let mut args = args.iter();
+    let mut result = String::new();
+    let mut chars = message.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                result.push(c);
+            },
+            ('{', Some('}')) => {
+                chars.next();
+                if let Some(arg) = args.next() {
+                    result.push_str(&arg.to_string());
+                }
+            },
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+// 같은 종류의 값이면 순서를 비교합니다.
+fn compare(left: &Value, right: &Value) -> Option<std::cmp::Ordering> {
+    match (left, right) {
+        (Value::Int(a, ty), Value::Int(b, _)) if ty.is_signed() => Some(signed(*a, *ty).cmp(&signed(*b, *ty))),
+        (Value::Int(a, _), Value::Int(b, _)) => Some(a.cmp(b)),
+        (Value::Float(a, _), Value::Float(b, _)) => a.partial_cmp(b),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Char(a), Value::Char(b)) => Some(a.cmp(b)),
+        (Value::Ref(a), Value::Ref(b)) => compare(a, b),
+        _ => None,
+    }
+}
+
+// 비트 표현을 부호 있는 값으로 읽습니다.
+fn signed(value: u128, ty: IntTy) -> i128 {
+    let shift = 128 - ty.bits();
+    if ty.is_signed() {
+        ((value << shift) as i128) >> shift
+    } else {
+        value as i128
+    }
+}
+
+// 타입의 비트 수만큼만 남깁니다.
+fn truncate(value: u128, ty: IntTy) -> u128 {
+    value & (u128::MAX >> (128 - ty.bits()))
+}
+
+// 정수 이항 연산입니다. 결과가 타입의 범위를 벗어나거나 0으로 나누면 rustc와 같은 메시지로 실패합니다.
+fn int_binary(operator: &Token, a: u128, b: u128, ty: IntTy) -> EvalResult<u128> {
+    match operator.kind {
+        TokenKind::And => return Ok(a & b),
+        TokenKind::Or => return Ok(a | b),
+        TokenKind::Caret => return Ok(a ^ b),
+        TokenKind::Slash if b == 0 => {
+            return Err(fail(format!("attempt to divide `{}` by zero", show_int(a, ty))));
+        },
+        TokenKind::Percent if b == 0 => {
+            return Err(fail(format!("attempt to calculate the remainder of `{}` with a divisor of zero", show_int(a, ty))));
+        },
+        _ => {},
+    }
+
+    let result = if ty.is_signed() {
+        let (x, y) = (signed(a, ty), signed(b, ty));
+        let result = match operator.kind {
+            TokenKind::Plus => x.checked_add(y),
+            TokenKind::Minus => x.checked_sub(y),
+            TokenKind::Star => x.checked_mul(y),
+            TokenKind::Slash => x.checked_div(y),
+            TokenKind::Percent => x.checked_rem(y),
+            _ => return Err(unsupported_binary(operator, &Value::Int(a, ty))),
+        };
+        let min = -((ty.min_abs() - 1) as i128) - 1;
+        result.filter(|&result| result >= min && result <= ty.max() as i128).map(|result| truncate(result as u128, ty))
+    } else {
+        let result = match operator.kind {
+            TokenKind::Plus => a.checked_add(b),
+            TokenKind::Minus => a.checked_sub(b),
+            TokenKind::Star => a.checked_mul(b),
+            TokenKind::Slash => a.checked_div(b),
+            TokenKind::Percent => a.checked_rem(b),
+            _ => return Err(unsupported_binary(operator, &Value::Int(a, ty))),
+        };
+        result.filter(|&result| result <= ty.max())
+    };
+    result.ok_or_else(|| {
+        fail(format!("attempt to compute `{} {} {}`, which would overflow", show_int(a, ty), operator.text, show_int(b, ty)))
+    })
+}
+
+// 오류 메시지에 쓰는 정수 표기입니다: `u8::MAX`, `i32::MIN`, `-5_i32`
+fn show_int(value: u128, ty: IntTy) -> String {
+    if ty.is_signed() {
+        let value = signed(value, ty);
+        match value {
+            _ if value >= 0 && value as u128 == ty.max() => format!("{}::MAX", ty.name()),
+            _ if value < 0 && value.unsigned_abs() == ty.min_abs() => format!("{}::MIN", ty.name()),
+            _ => format!("{}_{}", value, ty.name()),
+        }
+    } else if value == ty.max() {
+        format!("{}::MAX", ty.name())
+    } else {
+        format!("{}_{}", value, ty.name())
+    }
+}
+
+// `f32` 연산의 결과는 `f32`로 반올림합니다.
+fn round_float(value: f64, ty: FloatTy) -> f64 {
+    match ty {
+        FloatTy::F32 => value as f32 as f64,
+        FloatTy::F64 => value,
+    }
+}
@@ -0,0 +1,424 @@
+mod closure;
+mod for_loop;
+mod format;
+mod index;
+mod matches;
+
+use std::collections::HashSet;
+
+use rusttc_ast::ast::{AstArena, Block, Crate, ExprId, FloatTy, FnDef, IntTy, Item, NodeKind, Pat, PatKind, Path, QSelf, StmtKind};
+use rusttc_ast::visit::{walk_item, Visitor};
+use rusttc_lexer::{Span, Token, TokenKind};
+
+use crate::cfg;
+use crate::consteval::{ConstEvaluator, ConstItem, Value};
+
+// IR 모듈(크레이트 하나)을 나타내는 구조체입니다.
+#[derive(Debug, Default, PartialEq)]
+pub struct IrModule {
+    pub structs: Vec<IrStruct>,         // 구조체 정의
+    pub functions: Vec<IrFunction>,     // 함수 정의 (메서드는 `Type::method` 이름으로)
+    pub tests: Vec<String>,             // `#[test]` 함수 이름
+    pub consts: Vec<IrConst>,           // 컴파일 중에 계산한 `const`, `static` 아이템
+}
+
+// 컴파일 중에 값을 계산한 `const`, `static` 아이템입니다.
+#[derive(Debug, PartialEq)]
+pub struct IrConst {
+    pub name: String,           // 아이템 이름
+    pub value: Value,           // 계산한 값
+    pub is_static: bool,        // `static`이면 참
+}
+
+// 구조체 정의를 나타내는 구조체입니다.
+#[derive(Debug, PartialEq)]
+pub struct IrStruct {
+    pub name: String,           // 구조체 이름
+    pub fields: Vec<String>,    // 필드 이름
+}
+
+// 함수 정의를 나타내는 구조체입니다.
+#[derive(Debug, PartialEq)]
+pub struct IrFunction {
+    pub name: String,           // 함수 이름
+    pub params: Vec<String>,    // 매개변수 이름 (리시버는 `self`)
+    pub body: IrNode,           // 함수 본문
+    pub span: Span,             // 원본 함수 정의의 위치
+}
+
+// IR의 노드를 나타내는 열거형입니다.
+// 호출, 선언, 대입, 중단에는 진단과 디버그 정보에 쓸 원본 위치가 붙습니다. 변환 중에 만든 노드는 `Span::DUMMY`입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IrNode {
+    Constant(Const),           // 숫자, 문자 상수
+    Variable(String),          // 변수
+    BinaryExpression(Box<BinaryExpression>), // 이항 표현식
+    Boolean(bool),             // 불리언 상수
+    UnaryExpression(Box<UnaryExpression>),   // 단항 표현식
+    Ref { mutable: bool, place: Box<IrNode> },  // 참조 생성
+    Call { func: String, args: Vec<IrNode>, span: Span },   // 함수 호출
+    Field { base: Box<IrNode>, field: String }, // 필드 접근
+    StructInit { name: String, fields: Vec<(String, IrNode)> },   // 구조체 생성
+    Let { name: String, value: Option<Box<IrNode>>, span: Span },   // 지역 변수 선언
+    Assign { target: Box<IrNode>, value: Box<IrNode>, span: Span }, // 대입
+    Block { stmts: Vec<IrNode>, value: Option<Box<IrNode>> },   // 블록과 그 값
+    If { cond: Box<IrNode>, then: Box<IrNode>, els: Option<Box<IrNode>> },  // 조건 분기
+    Loop(Box<IrNode>),         // 무한 루프
+    Break,                     // 루프 탈출
+    Continue,                  // 다음 반복
+    Return(Option<Box<IrNode>>),    // 반환
+    Array(Vec<IrNode>),        // 배열 생성
+    Repeat { value: Box<IrNode>, count: Box<IrNode> },  // 같은 값을 반복한 배열 생성
+    Tuple(Vec<IrNode>),        // 튜플 생성 (필드는 `0`, `1`, ...)
+    Index { base: Box<IrNode>, index: Box<IrNode> },    // 원소 접근 (범위 검사 없음)
+    Slice { base: Box<IrNode>, start: Box<IrNode>, end: Box<IrNode> },  // 부분 슬라이스 (범위 검사 없음)
+    Len(Box<IrNode>),          // 배열, 슬라이스의 길이
+    Panic { message: String, args: Vec<IrNode>, span: Span },   // 실행을 중단합니다 (메시지의 `{}`는 인자 값)
+    Str(String),               // 문자열 상수
+}
+
+// 상수 값입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Const {
+    // 정수와 그 타입입니다. 타입이 없는 상수는 변환 중에 만든 것으로, 함께 연산하는 피연산자와 같은 타입입니다.
+    Int(u128, Option<IntTy>),
+    Float(f64, FloatTy),
+    Char(char),
+}
+
+// 인자 없는 `panic!`이 전개되어 부르는 함수입니다. 메시지 문자열을 그대로 쓰는 `IrNode::Panic`이 됩니다.
+// 포맷 인자가 있는 `panic!`은 `std::panicking::panic_fmt` 호출로 남습니다.
+const PANIC: &str = "std::panicking::panic";
+
+// 이항 표현식을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryExpression {
+    pub left: IrNode,          // 왼쪽 피연산자
+    pub operator: Token,       // 연산자
+    pub right: IrNode,         // 오른쪽 피연산자
+}
+
+// 단항 표현식을 나타내는 구조체입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnaryExpression {
+    pub operator: Token,       // 연산자
+    pub operand: IrNode,       // 피연산자
+}
+
+// AST를 IR로 변환하는 함수입니다. 표현식은 `arena`에 들어 있습니다.
+pub fn convert_to_ir(arena: &AstArena, ast_nodes: &[ExprId]) -> Vec<IrNode> {
+    ast_nodes.iter().map(|&node| lower_expr(arena, node)).collect()
+}
+
+// 크레이트 전체를 IR 모듈로 변환합니다.
+// 메서드 호출은 타입 검사의 쓰기 단계(`rusttc_typeck::write_back`)에서 경로 호출로 바뀌어 있어야 하고,
+// 제네릭 아이템은 `mono::monomorphize`로 특수화되어 있어야 합니다.
+// `const`, `static`의 초기값은 타입 검사를 통과해 있어야 합니다. 계산한 값은 함수 본문의 상수 표현식과 함께 접습니다.
+pub fn lower_crate(krate: &Crate) -> IrModule {
+    let mut module = IrModule::default();
+    for item in &krate.items {
+        lower_item(&krate.arena, item, &mut module);
+    }
+
+    let mut collector = ConstCollector { arena: &krate.arena, items: Vec::new() };
+    collector.visit_crate(krate);
+    let items = collector.items;
+    let mut evaluator = ConstEvaluator::new(&items);
+    for item in &items {
+        let value = evaluator
+            .eval_const(&item.name)
+            .unwrap_or_else(|error| panic!("constant `{}` must be checked before lowering: {}", item.name, error));
+        module.consts.push(IrConst { name: item.name.clone(), value, is_static: item.is_static });
+    }
+    for function in &mut module.functions {
+        let locals = bound_names(function);
+        evaluator.fold(&mut function.body, &locals);
+    }
+    module
+}
+
+// 함수 안에 정의된 것까지 크레이트의 모든 `const`, `static` 아이템을 모읍니다.
+struct ConstCollector<'a> {
+    arena: &'a AstArena,
+    items: Vec<ConstItem>,
+}
+
+impl<'a> Visitor<'a> for ConstCollector<'a> {
+    fn arena(&self) -> &'a AstArena {
+        self.arena
+    }
+
+    fn visit_item(&mut self, item: &'a Item) {
+        if let Item::Const(def) | Item::Static(def) = item {
+            let init = lower_expr(self.arena, def.value);
+            self.items.push(ConstItem { name: def.name.clone(), init, is_static: matches!(item, Item::Static(_)) });
+        }
+        walk_item(self, item);
+    }
+}
+
+// 함수 안에서 바인딩되는 이름입니다. 같은 이름의 상수를 가리므로 펼치지 않습니다.
+fn bound_names(function: &IrFunction) -> HashSet<String> {
+    fn walk(node: &IrNode, names: &mut HashSet<String>) {
+        match node {
+            IrNode::Let { name, value, .. } => {
+                names.insert(name.clone());
+                if let Some(value) = value {
+                    walk(value, names);
+                }
+            },
+            IrNode::Block { stmts, value } => {
+                stmts.iter().for_each(|stmt| walk(stmt, names));
+                if let Some(value) = value {
+                    walk(value, names);
+                }
+            },
+            IrNode::If { then, els, .. } => {
+                walk(then, names);
+                if let Some(els) = els {
+                    walk(els, names);
+                }
+            },
+            IrNode::Loop(body) => walk(body, names),
+            _ => {},
+        }
+    }
+
+    let mut names: HashSet<String> = function.params.iter().cloned().collect();
+    walk(&function.body, &mut names);
+    names
+}
+
+fn lower_item(arena: &AstArena, item: &Item, module: &mut IrModule) {
+    match item {
+        Item::Fn(function) => {
+            if cfg::is_test(&function.attrs) {
+                module.tests.push(function.name.clone());
+            }
+            let function = lower_fn(arena, function.name.clone(), function, module);
+            module.functions.push(function);
+        },
+        Item::Struct(def) => module.structs.push(IrStruct {
+            name: def.name.clone(),
+            fields: def.fields.iter().map(|field| field.name.clone()).collect(),
+        }),
+        Item::Impl(imp) => {
+            // 트레이트 메서드는 `<Type as Trait>::method` 이름으로 내보냅니다.
+            let prefix = match &imp.of_trait {
+                Some(trait_path) => format!("<{} as {}>", imp.self_ty, trait_path),
+                None => imp.self_ty.to_string(),
+            };
+            for function in &imp.items {
+                let name = format!("{}::{}", prefix, function.name);
+                let function = lower_fn(arena, name, function, module);
+                module.functions.push(function);
+            }
+        },
+        // 트레이트 선언 자체는 코드를 만들지 않습니다. 기본 메서드는 각 impl로 복사됩니다.
+        Item::Trait(_) => {},
+        // 모듈과 `use`는 `modules::resolve_modules`에서 최상위 아이템으로 펼쳐져 있습니다.
+        Item::Mod(_) | Item::Use(_) => {},
+        // 상수는 `lower_crate`에서 모아 계산합니다.
+        Item::Const(_) | Item::Static(_) => {},
+    }
+}
+
+fn lower_fn(arena: &AstArena, name: String, function: &FnDef, module: &mut IrModule) -> IrFunction {
+    let mut params = Vec::new();
+    if function.self_param.is_some() {
+        params.push("self".to_string());
+    }
+    params.extend(function.params.iter().map(|param| pat_name(&param.pat)));
+
+    let body = function.body.as_ref().unwrap_or_else(|| panic!("function `{}` has no body", name));
+
+    // 블록 안에 정의된 아이템과 클로저는 모듈 최상위로 끌어올립니다.
+    hoist_items(arena, body, module);
+    closure::lower_closures(arena, body, module);
+
+    IrFunction { name, params, body: lower_block(arena, body), span: function.span }
+}
+
+fn hoist_items(arena: &AstArena, block: &Block, module: &mut IrModule) {
+    for &stmt in &block.stmts {
+        if let StmtKind::Item(item) = &arena[stmt].kind {
+            lower_item(arena, item, module);
+        }
+    }
+}
+
+// 매개변수의 이름입니다. 매개변수 패턴은 변수나 `_`뿐입니다.
+fn pat_name(pat: &Pat) -> String {
+    match &pat.kind {
+        PatKind::Ident { name, .. } => name.clone(),
+        _ => "_".to_string(),
+    }
+}
+
+fn qualified_name(qself: &QSelf, path: &Path) -> String {
+    // 클로저 호출 `<main::{closure#0} as Fn>::call`은 클로저 함수를 직접 부릅니다.
+    if qself.trait_path.segments.len() == 1 && qself.trait_path.segments[0].is_fn_trait() {
+        return qself.ty.to_string();
+    }
+    format!("<{} as {}>::{}", qself.ty, qself.trait_path, path)
+}
+
+fn lower_block(arena: &AstArena, block: &Block) -> IrNode {
+    let mut stmts = Vec::new();
+    let mut value = None;
+
+    for (i, &stmt) in block.stmts.iter().enumerate() {
+        let is_last = i + 1 == block.stmts.len();
+        let stmt = &arena[stmt];
+        match &stmt.kind {
+            StmtKind::Let(local) => {
+                let init = local.init.map(|init| Box::new(lower_expr(arena, init)));
+                // `let` 패턴은 변수나 `_`뿐입니다.
+                match &local.pat.kind {
+                    PatKind::Ident { name, .. } => stmts.push(IrNode::Let { name: name.clone(), value: init, span: stmt.span }),
+                    _ => stmts.extend(init.map(|init| *init)),
+                }
+            },
+            StmtKind::Item(_) => {},
+            StmtKind::Expr(node) if is_last => value = Some(Box::new(lower_expr(arena, *node))),
+            StmtKind::Expr(node) | StmtKind::Semi(node) => stmts.push(lower_expr(arena, *node)),
+        }
+    }
+
+    IrNode::Block { stmts, value }
+}
+
+// 변환 중에 만드는 임시 변수와 연산을 위한 도우미입니다.
+// 임시 변수 이름은 `next#0`처럼 구문 문맥 0번을 붙여 씁니다. 소스의 이름에는 `#`이 없고
+// 매크로 전개의 문맥 번호는 1부터 매기므로, 이 이름은 사용자나 매크로가 묶은 변수를 가리지도 붙잡지도 않습니다.
+fn var(name: &str) -> IrNode {
+    IrNode::Variable(name.to_string())
+}
+
+fn usize_const(value: u128) -> IrNode {
+    IrNode::Constant(Const::Int(value, Some(IntTy::Usize)))
+}
+
+fn let_var(name: &str, value: IrNode) -> IrNode {
+    IrNode::Let { name: name.to_string(), value: Some(Box::new(value)), span: Span::DUMMY }
+}
+
+fn call(func: &str, args: Vec<IrNode>) -> IrNode {
+    IrNode::Call { func: func.to_string(), args, span: Span::DUMMY }
+}
+
+fn binary(left: IrNode, kind: TokenKind, text: &str, right: IrNode) -> IrNode {
+    IrNode::BinaryExpression(Box::new(BinaryExpression { left, operator: Token::new(kind, text.to_string()), right }))
+}
+
+// 복합 대입 연산자를 대응하는 이항 연산자로 바꿉니다.
+fn compound_operator(token: &Token) -> Token {
+    let (kind, text) = match token.kind {
+        TokenKind::PlusEq => (TokenKind::Plus, "+"),
+        TokenKind::MinusEq => (TokenKind::Minus, "-"),
+        TokenKind::StarEq => (TokenKind::Star, "*"),
+        TokenKind::SlashEq => (TokenKind::Slash, "/"),
+        TokenKind::PercentEq => (TokenKind::Percent, "%"),
+        _ => panic!("`{}` is not a compound assignment operator", token.text),
+    };
+    Token::new(kind, text.to_string())
+}
+
+fn lower_expr(arena: &AstArena, id: ExprId) -> IrNode {
+    let node = &arena[id];
+    match &node.kind {
+        // 접미사 없는 리터럴은 타입 검사가 추론한 타입을 채워 둡니다. 검사 없이 변환하면 기본 타입입니다.
+        NodeKind::Int(value, ty) => IrNode::Constant(Const::Int(*value, Some(ty.unwrap_or(IntTy::I32)))),
+        NodeKind::Float(value, ty) => IrNode::Constant(Const::Float(*value, ty.unwrap_or(FloatTy::F64))),
+        NodeKind::Char(c) => IrNode::Constant(Const::Char(*c)),
+        NodeKind::Ident(id) => IrNode::Variable(id.clone()),
+        NodeKind::BinaryOp(binary_op) => IrNode::BinaryExpression(Box::new(BinaryExpression {
+            left: lower_expr(arena, binary_op.left),
+            operator: binary_op.operator.clone(),
+            right: lower_expr(arena, binary_op.right),
+        })),
+        NodeKind::Bool(b) => IrNode::Boolean(*b),
+        NodeKind::Str(text) => IrNode::Str(text.clone()),
+        NodeKind::Unary(unary_op) => IrNode::UnaryExpression(Box::new(UnaryExpression {
+            operator: unary_op.operator.clone(),
+            operand: lower_expr(arena, unary_op.operand),
+        })),
+        NodeKind::Ref { mutable, expr } => IrNode::Ref { mutable: *mutable, place: Box::new(lower_expr(arena, *expr)) },
+        NodeKind::Path(path) => IrNode::Variable(path.to_string()),
+        NodeKind::QualifiedPath(qself, path) => IrNode::Variable(qualified_name(qself, path)),
+        NodeKind::Call(call) => {
+            let func = match &arena[call.callee].kind {
+                NodeKind::Ident(name) => name.clone(),
+                NodeKind::Path(path) => path.to_string(),
+                NodeKind::QualifiedPath(qself, path) => qualified_name(qself, path),
+                other => panic!("unsupported callee `{:?}`", other),
+            };
+            let message = match call.args.as_slice() {
+                [arg] => match &arena[*arg].kind {
+                    NodeKind::Str(message) => Some(message),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match (func.as_str(), message) {
+                (PANIC, Some(message)) => IrNode::Panic {
+                    message: message.replace('{', "{{").replace('}', "}}"),
+                    args: Vec::new(),
+                    span: node.span,
+                },
+                _ => IrNode::Call { func, args: call.args.iter().map(|&arg| lower_expr(arena, arg)).collect(), span: node.span },
+            }
+        },
+        NodeKind::MethodCall(call) => {
+            panic!("method call `{}` must be resolved before lowering", call.method)
+        },
+        NodeKind::Field(base, field) => IrNode::Field { base: Box::new(lower_expr(arena, *base)), field: field.clone() },
+        NodeKind::StructLit(lit) => IrNode::StructInit {
+            name: lit.path.to_string(),
+            fields: lit.fields.iter().map(|&(ref name, value)| (name.clone(), lower_expr(arena, value))).collect(),
+        },
+        NodeKind::Assign(assign) => {
+            let value = match &assign.operator {
+                Some(operator) => IrNode::BinaryExpression(Box::new(BinaryExpression {
+                    left: lower_expr(arena, assign.target),
+                    operator: compound_operator(operator),
+                    right: lower_expr(arena, assign.value),
+                })),
+                None => lower_expr(arena, assign.value),
+            };
+            IrNode::Assign { target: Box::new(lower_expr(arena, assign.target)), value: Box::new(value), span: node.span }
+        },
+        NodeKind::Block(block) => lower_block(arena, block),
+        NodeKind::If(if_expr) => IrNode::If {
+            cond: Box::new(lower_expr(arena, if_expr.cond)),
+            then: Box::new(lower_block(arena, &if_expr.then)),
+            els: if_expr.els.map(|els| Box::new(lower_expr(arena, els))),
+        },
+        NodeKind::Match(match_expr) => matches::lower_match(arena, match_expr, node.span),
+        NodeKind::While(while_expr) => {
+            // `while c { body }`는 `loop { if c { body } else { break } }`로 변환합니다.
+            let body = IrNode::If {
+                cond: Box::new(lower_expr(arena, while_expr.cond)),
+                then: Box::new(lower_block(arena, &while_expr.body)),
+                els: Some(Box::new(IrNode::Break)),
+            };
+            IrNode::Loop(Box::new(body))
+        },
+        NodeKind::ForLoop(for_loop) => for_loop::lower_for(arena, for_loop),
+        NodeKind::Loop(body) => IrNode::Loop(Box::new(lower_block(arena, body))),
+        NodeKind::Break => IrNode::Break,
+        NodeKind::Continue => IrNode::Continue,
+        NodeKind::Return(value) => IrNode::Return(value.map(|value| Box::new(lower_expr(arena, value)))),
+        NodeKind::Closure(closure) => closure::env_init(closure),
+        NodeKind::Array(elems) => IrNode::Array(elems.iter().map(|&elem| lower_expr(arena, elem)).collect()),
+        NodeKind::Repeat(value, count) => IrNode::Repeat {
+            value: Box::new(lower_expr(arena, *value)),
+            count: Box::new(lower_expr(arena, *count)),
+        },
+        NodeKind::Tuple(elems) => IrNode::Tuple(elems.iter().map(|&elem| lower_expr(arena, elem)).collect()),
+        NodeKind::Index(base, index) => index::lower_index(arena, *base, *index, node.span),
+        NodeKind::Range(range) => index::lower_range(arena, range),
+        NodeKind::FormatArgs(format) => format::lower_format(arena, format),
+    }
+}
+
@@ -0,0 +1,208 @@
+use rusttc_ast::ast::{AstArena, Block, Capture, CaptureMode, Closure, ExprId, NodeKind, StmtKind};
+use rusttc_lexer::{Span, Token, TokenKind};
+
+use super::{lower_expr, pat_name, IrFunction, IrModule, IrNode, IrStruct, UnaryExpression};
+
+// 클로저 함수가 환경 구조체를 받는 첫 번째 매개변수 이름입니다.
+const ENV: &str = "env#0";
+
+// 블록 안의 클로저를 모두 찾아 환경 구조체와 함수로 모듈에 추가합니다.
+// `|x| x + y`는 필드 `y`를 가진 구조체 `main::{closure#0}`과
+// 매개변수 `(env#0, x)`를 받는 같은 이름의 함수가 됩니다.
+pub(super) fn lower_closures(arena: &AstArena, block: &Block, module: &mut IrModule) {
+    let mut closures = Vec::new();
+    collect_block(arena, block, &mut closures);
+    for (span, closure) in closures {
+        module.structs.push(IrStruct {
+            name: closure.name.clone(),
+            fields: closure.captures.iter().map(|capture| capture.name.clone()).collect(),
+        });
+
+        let mut params = vec![ENV.to_string()];
+        params.extend(closure.params.iter().map(|param| pat_name(&param.pat)));
+        let mut body = lower_expr(arena, closure.body);
+        let mut bound: Vec<String> = params[1..].to_vec();
+        replace_captures(&mut body, &closure.captures, &mut bound);
+        module.functions.push(IrFunction { name: closure.name.clone(), params, body, span });
+    }
+}
+
+// 클로저 표현식은 캡처 방식대로 환경 구조체를 만드는 식이 됩니다.
+pub(super) fn env_init(closure: &Closure) -> IrNode {
+    let fields = closure
+        .captures
+        .iter()
+        .map(|capture| {
+            let value = IrNode::Variable(capture.name.clone());
+            let value = match capture.mode {
+                CaptureMode::ByRef => IrNode::Ref { mutable: false, place: Box::new(value) },
+                CaptureMode::ByMutRef => IrNode::Ref { mutable: true, place: Box::new(value) },
+                CaptureMode::ByValue => value,
+            };
+            (capture.name.clone(), value)
+        })
+        .collect();
+    IrNode::StructInit { name: closure.name.clone(), fields }
+}
+
+// 클로저는 클로저 표현식의 위치와 함께 모읍니다.
+fn collect_block<'a>(arena: &'a AstArena, block: &Block, closures: &mut Vec<(Span, &'a Closure)>) {
+    for &stmt in &block.stmts {
+        match &arena[stmt].kind {
+            StmtKind::Let(local) => {
+                if let Some(init) = local.init {
+                    collect(arena, init, closures);
+                }
+            },
+            // 안쪽 아이템의 클로저는 그 아이템을 변환할 때 찾습니다.
+            StmtKind::Item(_) => {},
+            StmtKind::Expr(node) | StmtKind::Semi(node) => collect(arena, *node, closures),
+        }
+    }
+}
+
+fn collect<'a>(arena: &'a AstArena, id: ExprId, closures: &mut Vec<(Span, &'a Closure)>) {
+    let node = &arena[id];
+    match &node.kind {
+        NodeKind::Int(..) | NodeKind::Float(..) | NodeKind::Char(_) | NodeKind::Ident(_) | NodeKind::Bool(_) | NodeKind::Str(_) | NodeKind::Path(_) | NodeKind::QualifiedPath(..) => {},
+        NodeKind::Break | NodeKind::Continue => {},
+        NodeKind::BinaryOp(binary_op) => {
+            collect(arena, binary_op.left, closures);
+            collect(arena, binary_op.right, closures);
+        },
+        NodeKind::Unary(unary_op) => collect(arena, unary_op.operand, closures),
+        NodeKind::Ref { expr, .. } => collect(arena, *expr, closures),
+        NodeKind::Call(call) => {
+            collect(arena, call.callee, closures);
+            call.args.iter().for_each(|&arg| collect(arena, arg, closures));
+        },
+        NodeKind::MethodCall(call) => {
+            collect(arena, call.receiver, closures);
+            call.args.iter().for_each(|&arg| collect(arena, arg, closures));
+        },
+        NodeKind::Field(base, _) => collect(arena, *base, closures),
+        NodeKind::StructLit(lit) => lit.fields.iter().for_each(|&(_, value)| collect(arena, value, closures)),
+        NodeKind::Assign(assign) => {
+            collect(arena, assign.target, closures);
+            collect(arena, assign.value, closures);
+        },
+        NodeKind::Block(block) => collect_block(arena, block, closures),
+        NodeKind::If(if_expr) => {
+            collect(arena, if_expr.cond, closures);
+            collect_block(arena, &if_expr.then, closures);
+            if let Some(els) = if_expr.els {
+                collect(arena, els, closures);
+            }
+        },
+        NodeKind::Match(match_expr) => {
+            collect(arena, match_expr.scrutinee, closures);
+            for arm in &match_expr.arms {
+                arm.guard.iter().for_each(|&guard| collect(arena, guard, closures));
+                collect(arena, arm.body, closures);
+            }
+        },
+        NodeKind::While(while_expr) => {
+            collect(arena, while_expr.cond, closures);
+            collect_block(arena, &while_expr.body, closures);
+        },
+        NodeKind::ForLoop(for_loop) => {
+            collect(arena, for_loop.iter, closures);
+            collect_block(arena, &for_loop.body, closures);
+        },
+        NodeKind::Loop(body) => collect_block(arena, body, closures),
+        NodeKind::Return(value) => {
+            if let Some(value) = value {
+                collect(arena, *value, closures);
+            }
+        },
+        NodeKind::Closure(closure) => {
+            closures.push((node.span, closure));
+            collect(arena, closure.body, closures);
+        },
+        NodeKind::Array(elems) | NodeKind::Tuple(elems) => elems.iter().for_each(|&elem| collect(arena, elem, closures)),
+        NodeKind::FormatArgs(format) => format.args.iter().for_each(|arg| collect(arena, arg.expr, closures)),
+        NodeKind::Repeat(base, other) | NodeKind::Index(base, other) => {
+            collect(arena, *base, closures);
+            collect(arena, *other, closures);
+        },
+        NodeKind::Range(range) => range.start.iter().chain(&range.end).for_each(|&bound| collect(arena, bound, closures)),
+    }
+}
+
+// 클로저 본문에서 캡처한 변수를 환경 필드로 바꿉니다.
+// 값으로 캡처한 `v`는 `env#0.v`, 참조로 캡처한 `v`는 `*env#0.v`가 됩니다.
+fn replace_captures(node: &mut IrNode, captures: &[Capture], bound: &mut Vec<String>) {
+    match node {
+        IrNode::Variable(name) => {
+            if bound.contains(name) {
+                return;
+            }
+            let Some(capture) = captures.iter().find(|capture| &capture.name == name) else { return };
+            let field = IrNode::Field { base: Box::new(IrNode::Variable(ENV.to_string())), field: name.clone() };
+            *node = match capture.mode {
+                CaptureMode::ByValue => field,
+                CaptureMode::ByRef | CaptureMode::ByMutRef => IrNode::UnaryExpression(Box::new(UnaryExpression {
+                    operator: Token::new(TokenKind::Star, "*".to_string()),
+                    operand: field,
+                })),
+            };
+        },
+        IrNode::Constant(_) | IrNode::Boolean(_) | IrNode::Str(_) | IrNode::Break | IrNode::Continue => {},
+        IrNode::Array(elems) | IrNode::Tuple(elems) | IrNode::Panic { args: elems, .. } => {
+            elems.iter_mut().for_each(|elem| replace_captures(elem, captures, bound));
+        },
+        IrNode::Repeat { value: base, count: other } | IrNode::Index { base, index: other } => {
+            replace_captures(base, captures, bound);
+            replace_captures(other, captures, bound);
+        },
+        IrNode::Slice { base, start, end } => {
+            replace_captures(base, captures, bound);
+            replace_captures(start, captures, bound);
+            replace_captures(end, captures, bound);
+        },
+        IrNode::Len(base) => replace_captures(base, captures, bound),
+        IrNode::BinaryExpression(binary) => {
+            replace_captures(&mut binary.left, captures, bound);
+            replace_captures(&mut binary.right, captures, bound);
+        },
+        IrNode::UnaryExpression(unary) => replace_captures(&mut unary.operand, captures, bound),
+        IrNode::Ref { place, .. } => replace_captures(place, captures, bound),
+        IrNode::Call { args, .. } => args.iter_mut().for_each(|arg| replace_captures(arg, captures, bound)),
+        IrNode::Field { base, .. } => replace_captures(base, captures, bound),
+        IrNode::StructInit { fields, .. } => {
+            fields.iter_mut().for_each(|(_, value)| replace_captures(value, captures, bound));
+        },
+        // `let`으로 가린 이름은 블록이 끝날 때까지 캡처가 아닙니다.
+        IrNode::Let { name, value, .. } => {
+            if let Some(value) = value {
+                replace_captures(value, captures, bound);
+            }
+            bound.push(name.clone());
+        },
+        IrNode::Assign { target, value, .. } => {
+            replace_captures(target, captures, bound);
+            replace_captures(value, captures, bound);
+        },
+        IrNode::Block { stmts, value } => {
+            let len = bound.len();
+            stmts.iter_mut().for_each(|stmt| replace_captures(stmt, captures, bound));
+            if let Some(value) = value {
+                replace_captures(value, captures, bound);
+            }
+            bound.truncate(len);
+        },
+        IrNode::If { cond, then, els } => {
+            replace_captures(cond, captures, bound);
+            replace_captures(then, captures, bound);
+            if let Some(els) = els {
+                replace_captures(els, captures, bound);
+            }
+        },
+        IrNode::Loop(body) => replace_captures(body, captures, bound),
+        IrNode::Return(value) => {
+            if let Some(value) = value {
+                replace_captures(value, captures, bound);
+            }
+        },
+    }
+}
@@ -0,0 +1,107 @@
+use rusttc_ast::ast::{AstArena, ExprId, ForLoop, NodeKind, PatKind};
+use rusttc_lexer::{Span, TokenKind};
+
+use super::{binary, call, let_var, lower_block, lower_expr, var, Const, IrNode};
+
+// 루프 상태를 담는 임시 변수 이름입니다.
+const NEXT: &str = "next#0";
+const END: &str = "end#0";
+const DONE: &str = "done#0";
+const ITER: &str = "iter#0";
+
+// 반복자 프로토콜을 이루는 런타임 함수입니다.
+// `next`는 원소가 남았으면 `Some(x)`, 다 꺼냈으면 `None`을 돌려줍니다.
+const INTO_ITER: &str = "IntoIterator::into_iter";
+const ITER_NEXT: &str = "Iterator::next";
+const IS_NONE: &str = "Option::is_none";
+const UNWRAP: &str = "Option::unwrap";
+
+// `for` 루프를 `loop`로 바꿉니다.
+// 시작이 있는 범위 `a..b`, `a..=b`, `a..`는 카운터를 늘려 가는 루프가 되고,
+// 그 밖의 값은 `into_iter`로 반복자를 만든 뒤 `next`가 `None`을 줄 때까지 돕니다.
+pub(super) fn lower_for(arena: &AstArena, for_loop: &ForLoop) -> IrNode {
+    match &arena[for_loop.iter].kind {
+        NodeKind::Range(range) if range.start.is_some() => {
+            lower_counting(arena, for_loop, range.start.unwrap(), range.end, range.inclusive)
+        },
+        _ => lower_iterator(arena, for_loop, for_loop.iter),
+    }
+}
+
+// ```
+// { let next#0 = a; let end#0 = b;
+//   loop { if next#0 >= end#0 { break } let i = next#0; next#0 = next#0 + 1; body } }
+// ```
+// 카운터를 본문보다 먼저 늘리므로 `continue`도 다음 값으로 넘어갑니다.
+// `a..=b`는 마지막 값에서 카운터를 늘리지 않고 끝났다고 표시하므로 `0..=255u8`도 넘치지 않습니다.
+// ```
+// { let next#0 = a; let end#0 = b; let done#0 = next#0 > end#0;
+//   loop { if done#0 { break } let i = next#0;
+//          if next#0 == end#0 { done#0 = true } else { next#0 = next#0 + 1 } body } }
+// ```
+fn lower_counting(arena: &AstArena, for_loop: &ForLoop, start: ExprId, end: Option<ExprId>, inclusive: bool) -> IrNode {
+    let mut stmts = vec![let_var(NEXT, lower_expr(arena, start))];
+    let mut body = Vec::new();
+    let mut step = increment();
+    match end {
+        Some(end) if inclusive => {
+            stmts.push(let_var(END, lower_expr(arena, end)));
+            stmts.push(let_var(DONE, binary(var(NEXT), TokenKind::Gt, ">", var(END))));
+            body.push(IrNode::If { cond: Box::new(var(DONE)), then: Box::new(IrNode::Break), els: None });
+            step = IrNode::If {
+                cond: Box::new(binary(var(NEXT), TokenKind::EqEq, "==", var(END))),
+                then: Box::new(assign(DONE, IrNode::Boolean(true))),
+                els: Some(Box::new(step)),
+            };
+        },
+        Some(end) => {
+            stmts.push(let_var(END, lower_expr(arena, end)));
+            body.push(IrNode::If {
+                cond: Box::new(binary(var(NEXT), TokenKind::Ge, ">=", var(END))),
+                then: Box::new(IrNode::Break),
+                els: None,
+            });
+        },
+        None => {},
+    }
+    if let PatKind::Ident { name, .. } = &for_loop.pat.kind {
+        body.push(let_var(name, var(NEXT)));
+    }
+    body.push(step);
+    body.push(lower_block(arena, &for_loop.body));
+
+    stmts.push(IrNode::Loop(Box::new(IrNode::Block { stmts: body, value: None })));
+    IrNode::Block { stmts, value: None }
+}
+
+// `next#0 = next#0 + 1`
+fn increment() -> IrNode {
+    assign(NEXT, binary(var(NEXT), TokenKind::Plus, "+", IrNode::Constant(Const::Int(1, None))))
+}
+
+fn assign(name: &str, value: IrNode) -> IrNode {
+    IrNode::Assign { target: Box::new(var(name)), value: Box::new(value), span: Span::DUMMY }
+}
+
+// ```
+// { let iter#0 = IntoIterator::into_iter(iter);
+//   loop { let next#0 = Iterator::next(&mut iter#0); if Option::is_none(&next#0) { break }
+//          let x = Option::unwrap(next#0); body } }
+// ```
+fn lower_iterator(arena: &AstArena, for_loop: &ForLoop, iter: ExprId) -> IrNode {
+    let into_iter = call(INTO_ITER, vec![lower_expr(arena, iter)]);
+    let next = call(ITER_NEXT, vec![IrNode::Ref { mutable: true, place: Box::new(var(ITER)) }]);
+    let is_none = call(IS_NONE, vec![IrNode::Ref { mutable: false, place: Box::new(var(NEXT)) }]);
+
+    let mut body = vec![
+        let_var(NEXT, next),
+        IrNode::If { cond: Box::new(is_none), then: Box::new(IrNode::Break), els: None },
+    ];
+    if let PatKind::Ident { name, .. } = &for_loop.pat.kind {
+        body.push(let_var(name, call(UNWRAP, vec![var(NEXT)])));
+    }
+    body.push(lower_block(arena, &for_loop.body));
+
+    let stmts = vec![let_var(ITER, into_iter), IrNode::Loop(Box::new(IrNode::Block { stmts: body, value: None }))];
+    IrNode::Block { stmts, value: None }
+}
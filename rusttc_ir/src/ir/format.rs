@@ -0,0 +1,105 @@
+use rusttc_ast::ast::{AstArena, FormatAlign, FormatArgs, FormatCount, FormatPiece, FormatSign, FormatSpec, IntTy};
+use rusttc_lexer::{Token, TokenKind};
+
+use super::{call, let_var, lower_expr, usize_const, var, Const, IrNode, UnaryExpression};
+
+// 포맷 결과를 모으는 임시 변수 이름입니다. 인자는 `arg0#0`, `arg1#0`, ...에 담습니다.
+const FMT: &str = "fmt#0";
+const ARG: &str = "arg";
+
+// 포맷 런타임 함수입니다.
+const FORMATTER_NEW: &str = "std::fmt::Formatter::new";
+const WRITE_STR: &str = "std::fmt::Formatter::write_str";
+const SPEC: &str = "std::fmt::Spec";
+
+// `Spec.flags`의 비트입니다.
+const FLAG_PLUS: u128 = 1;
+const FLAG_MINUS: u128 = 2;
+const FLAG_ALTERNATE: u128 = 4;
+const FLAG_ZERO_PAD: u128 = 8;
+
+// 너비, 정밀도가 없을 때의 값입니다.
+const NO_COUNT: u128 = usize::MAX as u128;
+
+// `format_args!`를 포맷 런타임 호출로 바꿉니다.
+// ```
+// { let __arg0 = &name; let __arg1 = &value; let __fmt = std::fmt::Formatter::new();
+//   std::fmt::Display::fmt(__arg0, &mut __fmt, std::fmt::Spec { .. });
+//   std::fmt::Formatter::write_str(&mut __fmt, " is ");
+//   std::fmt::Display::fmt(__arg1, &mut __fmt, std::fmt::Spec { align: 3, width: 8, precision: 2, .. });
+//   __fmt }
+// ```
+// 인자는 자리표시자에 나온 순서와 상관없이 쓴 순서대로 한 번만 평가합니다.
+pub(super) fn lower_format(arena: &AstArena, format: &FormatArgs) -> IrNode {
+    let mut stmts: Vec<IrNode> = format
+        .args
+        .iter()
+        .enumerate()
+        .map(|(i, arg)| let_var(&arg_name(i), IrNode::Ref { mutable: false, place: Box::new(lower_expr(arena, arg.expr)) }))
+        .collect();
+    stmts.push(let_var(FMT, call(FORMATTER_NEW, Vec::new())));
+
+    for piece in &format.pieces {
+        match piece {
+            FormatPiece::Literal(text) => stmts.push(call(WRITE_STR, vec![fmt_ref(), IrNode::Str(text.clone())])),
+            FormatPiece::Placeholder(placeholder) => {
+                let func = format!("std::fmt::{}::fmt", placeholder.spec.trait_.name());
+                stmts.push(call(&func, vec![var(&arg_name(placeholder.arg)), fmt_ref(), lower_spec(&placeholder.spec)]));
+            },
+        }
+    }
+    IrNode::Block { stmts, value: Some(Box::new(var(FMT))) }
+}
+
+fn lower_spec(spec: &FormatSpec) -> IrNode {
+    let align = match spec.align {
+        None => 0,
+        Some(FormatAlign::Left) => 1,
+        Some(FormatAlign::Center) => 2,
+        Some(FormatAlign::Right) => 3,
+    };
+    let mut flags = 0;
+    match spec.sign {
+        Some(FormatSign::Plus) => flags |= FLAG_PLUS,
+        Some(FormatSign::Minus) => flags |= FLAG_MINUS,
+        None => {},
+    }
+    if spec.alternate {
+        flags |= FLAG_ALTERNATE;
+    }
+    if spec.zero_pad {
+        flags |= FLAG_ZERO_PAD;
+    }
+
+    let fields = vec![
+        ("fill", IrNode::Constant(Const::Char(spec.fill))),
+        ("align", IrNode::Constant(Const::Int(align, Some(IntTy::U8)))),
+        ("flags", IrNode::Constant(Const::Int(flags, Some(IntTy::U32)))),
+        ("width", lower_count(spec.width)),
+        ("precision", lower_count(spec.precision)),
+    ];
+    IrNode::StructInit {
+        name: SPEC.to_string(),
+        fields: fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect(),
+    }
+}
+
+// `{:1$}`처럼 인자로 준 값은 그 인자의 참조를 따라가 읽습니다.
+fn lower_count(count: Option<FormatCount>) -> IrNode {
+    match count {
+        None => usize_const(NO_COUNT),
+        Some(FormatCount::Literal(n)) => usize_const(n as u128),
+        Some(FormatCount::Arg(i)) => IrNode::UnaryExpression(Box::new(UnaryExpression {
+            operator: Token::new(TokenKind::Star, "*".to_string()),
+            operand: var(&arg_name(i)),
+        })),
+    }
+}
+
+fn arg_name(i: usize) -> String {
+    format!("{}{}#0", ARG, i)
+}
+
+fn fmt_ref() -> IrNode {
+    IrNode::Ref { mutable: true, place: Box::new(var(FMT)) }
+}
@@ -0,0 +1,101 @@
+use rusttc_ast::ast::{AstArena, ExprId, NodeKind, Range};
+use rusttc_lexer::{Span, TokenKind};
+
+use super::{binary, let_var, lower_expr, usize_const, var, IrNode};
+
+// 범위 검사에 쓰는 임시 변수 이름입니다.
+const BASE: &str = "base#0";
+const INDEX: &str = "index#0";
+const START: &str = "start#0";
+const END: &str = "end#0";
+
+// `a[i]`, `a[1..3]`를 범위 검사가 붙은 원소 접근, 부분 슬라이스로 바꿉니다.
+// 검사에 실패하면 rustc와 같은 메시지로 실행을 중단합니다. 중단 위치는 인덱스 표현식 전체입니다.
+pub(super) fn lower_index(arena: &AstArena, base: ExprId, index: ExprId, span: Span) -> IrNode {
+    let base = lower_expr(arena, base);
+
+    // 검사와 접근에서 바탕을 두 번 읽으므로, 장소가 아닌 값은 임시 변수에 담습니다.
+    if !is_place(&base) {
+        let temp = let_var(BASE, base);
+        let access = checked_access(arena, var(BASE), index, span);
+        return IrNode::Block { stmts: vec![temp], value: Some(Box::new(access)) };
+    }
+    checked_access(arena, base, index, span)
+}
+
+fn checked_access(arena: &AstArena, base: IrNode, index: ExprId, span: Span) -> IrNode {
+    let len = || IrNode::Len(Box::new(base.clone()));
+
+    let NodeKind::Range(range) = &arena[index].kind else {
+        // `{ let index#0 = i; if index#0 >= len { panic } index#0 }`
+        let check = IrNode::If {
+            cond: Box::new(binary(var(INDEX), TokenKind::Ge, ">=", len())),
+            then: Box::new(panic("index out of bounds: the len is {} but the index is {}", vec![len(), var(INDEX)], span)),
+            els: None,
+        };
+        let index = IrNode::Block {
+            stmts: vec![let_var(INDEX, lower_expr(arena, index)), check],
+            value: Some(Box::new(var(INDEX))),
+        };
+        return IrNode::Index { base: Box::new(base), index: Box::new(index) };
+    };
+
+    let start = range.start.map_or(usize_const(0), |start| lower_expr(arena, start));
+    let end = match range.end {
+        Some(end) if range.inclusive => binary(lower_expr(arena, end), TokenKind::Plus, "+", usize_const(1)),
+        Some(end) => lower_expr(arena, end),
+        None => len(),
+    };
+    let order_check = IrNode::If {
+        cond: Box::new(binary(var(START), TokenKind::Gt, ">", var(END))),
+        then: Box::new(panic("slice index starts at {} but ends at {}", vec![var(START), var(END)], span)),
+        els: None,
+    };
+    let len_check = IrNode::If {
+        cond: Box::new(binary(var(END), TokenKind::Gt, ">", len())),
+        then: Box::new(panic("range end index {} out of range for slice of length {}", vec![var(END), len()], span)),
+        els: None,
+    };
+    let slice = IrNode::Slice { base: Box::new(base.clone()), start: Box::new(var(START)), end: Box::new(var(END)) };
+    IrNode::Block {
+        stmts: vec![let_var(START, start), let_var(END, end), order_check, len_check],
+        value: Some(Box::new(slice)),
+    }
+}
+
+// 인덱스 밖에서 쓰인 범위는 표준 라이브러리와 같은 이름의 구조체 값이 됩니다.
+pub(super) fn lower_range(arena: &AstArena, range: &Range) -> IrNode {
+    let name = match (&range.start, &range.end, range.inclusive) {
+        (Some(_), Some(_), false) => "Range",
+        (Some(_), Some(_), true) => "RangeInclusive",
+        (Some(_), None, _) => "RangeFrom",
+        (None, Some(_), false) => "RangeTo",
+        (None, Some(_), true) => "RangeToInclusive",
+        (None, None, _) => "RangeFull",
+    };
+    let fields = [("start", range.start), ("end", range.end)]
+        .into_iter()
+        .filter_map(|(field, bound)| Some((field.to_string(), lower_expr(arena, bound?))))
+        .collect();
+    IrNode::StructInit { name: name.to_string(), fields }
+}
+
+// 다시 읽어도 같은 값을 주는 장소 표현식인지 확인합니다.
+fn is_place(node: &IrNode) -> bool {
+    match node {
+        IrNode::Variable(_) => true,
+        IrNode::Field { base, .. } => is_place(base),
+        IrNode::UnaryExpression(unary) => unary.operator.kind == TokenKind::Star && is_place(&unary.operand),
+        // 인덱스 값이 변수나 상수일 때만 다시 계산해도 같습니다.
+        IrNode::Index { base, index } => {
+            let IrNode::Block { stmts, .. } = &**index else { return false };
+            let Some(IrNode::Let { value: Some(value), .. }) = stmts.first() else { return false };
+            is_place(base) && matches!(**value, IrNode::Variable(_) | IrNode::Constant(_))
+        },
+        _ => false,
+    }
+}
+
+fn panic(message: &str, args: Vec<IrNode>, span: Span) -> IrNode {
+    IrNode::Panic { message: message.to_string(), args, span }
+}
@@ -0,0 +1,76 @@
+use rusttc_ast::ast::{Arm, AstArena, Match, Pat, PatKind};
+use rusttc_lexer::{Span, TokenKind};
+
+use super::{binary, let_var, lower_expr, var, IrNode};
+
+// 검사할 값을 담는 임시 변수 이름입니다.
+const SCRUTINEE: &str = "scrutinee#0";
+
+// 모든 갈래가 맞지 않을 때의 메시지입니다. 타입 검사가 빠짐없는 match만 통과시키므로 실행되지 않습니다.
+const UNREACHABLE: &str = "internal error: entered unreachable code";
+
+// `match`를 `if` 사슬로 바꿉니다. or 패턴은 갈래마다 바인딩하는 위치가 다르므로 펼쳐서 갈래를 나눕니다.
+// ```
+// { let scrutinee#0 = x;
+//   if cond(p1) && { bindings(p1); guard1 } { bindings(p1); body1 }
+//   else if cond(p2) { bindings(p2); body2 }
+//   else { panic!("internal error: entered unreachable code") } }
+// ```
+// 조건도 가드도 없는 갈래는 마지막 `else`가 되고, 그 뒤의 갈래는 버립니다.
+pub(super) fn lower_match(arena: &AstArena, match_expr: &Match, span: Span) -> IrNode {
+    let arms: Vec<(Pat, &Arm)> = match_expr.arms.iter().flat_map(|arm| arm.pat.alternatives().into_iter().map(move |pat| (pat, arm))).collect();
+
+    let mut branches = Vec::new();
+    let mut fallback = IrNode::Panic { message: UNREACHABLE.to_string(), args: Vec::new(), span };
+    for (pat, arm) in &arms {
+        let mut bindings = Vec::new();
+        let mut conds = Vec::new();
+        lower_pat(arena, pat, var(SCRUTINEE), &mut bindings, &mut conds);
+        let body = IrNode::Block { stmts: bindings.clone(), value: Some(Box::new(lower_expr(arena, arm.body))) };
+        if let Some(guard) = arm.guard {
+            conds.push(IrNode::Block { stmts: bindings, value: Some(Box::new(lower_expr(arena, guard))) });
+        }
+        let cond = conds.into_iter().reduce(|left, right| binary(left, TokenKind::AndAnd, "&&", right));
+        match cond {
+            Some(cond) => branches.push((cond, body)),
+            None => {
+                fallback = body;
+                break;
+            },
+        }
+    }
+
+    let chain = branches.into_iter().rev().fold(fallback, |els, (cond, then)| IrNode::If {
+        cond: Box::new(cond),
+        then: Box::new(then),
+        els: Some(Box::new(els)),
+    });
+    let stmts = vec![let_var(SCRUTINEE, lower_expr(arena, match_expr.scrutinee))];
+    IrNode::Block { stmts, value: Some(Box::new(chain)) }
+}
+
+// `place`에 있는 값이 패턴에 맞는지 확인하는 조건과 바인딩을 모읍니다. 패턴에 `|`는 없어야 합니다.
+fn lower_pat(arena: &AstArena, pat: &Pat, place: IrNode, bindings: &mut Vec<IrNode>, conds: &mut Vec<IrNode>) {
+    match &pat.kind {
+        PatKind::Ident { name, .. } => bindings.push(let_var(name, place)),
+        PatKind::Wild => {},
+        PatKind::Lit(lit) => conds.push(binary(place, TokenKind::EqEq, "==", lower_expr(arena, *lit))),
+        PatKind::Range(start, end) => {
+            conds.push(binary(place.clone(), TokenKind::Ge, ">=", lower_expr(arena, *start)));
+            conds.push(binary(place, TokenKind::Le, "<=", lower_expr(arena, *end)));
+        },
+        PatKind::Tuple(pats) => {
+            for (i, pat) in pats.iter().enumerate() {
+                let field = IrNode::Field { base: Box::new(place.clone()), field: i.to_string() };
+                lower_pat(arena, pat, field, bindings, conds);
+            }
+        },
+        PatKind::Struct { fields, .. } => {
+            for (name, pat) in fields {
+                let field = IrNode::Field { base: Box::new(place.clone()), field: name.clone() };
+                lower_pat(arena, pat, field, bindings, conds);
+            }
+        },
+        PatKind::Or(_) => unreachable!("or-patterns are expanded before lowering"),
+    }
+}
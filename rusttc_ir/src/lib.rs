@@ -1,76 +1,375 @@
-pub mod ir {
-    use rusttc_ast::ast::Node;
-    use rusttc_lexer::Token;
-
-    // IR의 노드를 나타내는 열거형입니다.
-    #[derive(Debug, PartialEq)]
-    pub enum IrNode {
-        Constant(i64),             // 상수
-        Variable(String),          // 변수
-        BinaryExpression(Box<BinaryExpression>), // 이항 표현식
-    }
-
-    // 이항 표현식을 나타내는 구조체입니다.
-    #[derive(Debug, PartialEq)]
-    pub struct BinaryExpression {
-        pub left: IrNode,          // 왼쪽 피연산자
-        pub operator: Token,       // 연산자
-        pub right: IrNode,         // 오른쪽 피연산자
-    }
-
-    // AST를 IR로 변환하는 함수입니다.
-    pub fn convert_to_ir(ast_nodes: Vec<Node>) -> Vec<IrNode> {
-        ast_nodes.into_iter().map(|node| {
-            match node {
-                Node::Number(n) => IrNode::Constant(n),
-                Node::Ident(id) => IrNode::Variable(id),
-                Node::BinaryOp(binary_op) => {
-                    let binary_op = *binary_op;
-                    IrNode::BinaryExpression(Box::new(BinaryExpression {
-                        left: convert_to_ir(vec![binary_op.left]).pop().unwrap(),
-                        operator: binary_op.operator,
-                        right: convert_to_ir(vec![binary_op.right]).pop().unwrap(),
-                    }))
-                },
-            }
-        }).collect()
-    }
-}
+pub mod cfg;
+pub mod consteval;
+pub mod ir;
+pub mod lint;
+pub mod method;
+pub mod modules;
+pub mod mono;
+pub mod resolve;
 
 #[cfg(test)]
 mod tests {
+    use super::consteval::{ConstEvaluator, Value};
     use super::ir::*;
-    use rusttc_ast::ast::{Node, BinaryOp};
-    use rusttc_lexer::{Token, TokenKind};
+    use super::method::resolve_methods;
+    use super::mono::monomorphize;
+    use rusttc_ast::ast::{AstArena, Node, NodeKind, BinaryOp, IntTy};
+    use rusttc_lexer::{tokenize, Span, Token, TokenKind};
+    use rusttc_parse::parser::parse_crate;
+
+    // 소스에서 `text`가 처음 나오는 위치입니다.
+    fn span_of(source: &str, text: &str) -> Span {
+        let lo = source.find(text).unwrap() as u32;
+        Span::new(lo, lo + text.len() as u32)
+    }
 
     #[test]
     fn test_convert_constant() {
-        let ast_nodes = vec![Node::Number(42)];
-        let ir_nodes = convert_to_ir(ast_nodes);
-        assert_eq!(ir_nodes, vec![IrNode::Constant(42)]);
+        let mut arena = AstArena::default();
+        let ast_nodes = vec![arena.alloc_expr(Node::from(NodeKind::Int(42, None)))];
+        let ir_nodes = convert_to_ir(&arena, &ast_nodes);
+        assert_eq!(ir_nodes, vec![IrNode::Constant(Const::Int(42, Some(IntTy::I32)))]);
     }
 
     #[test]
     fn test_convert_variable() {
-        let ast_nodes = vec![Node::Ident("x".to_string())];
-        let ir_nodes = convert_to_ir(ast_nodes);
+        let mut arena = AstArena::default();
+        let ast_nodes = vec![arena.alloc_expr(Node::from(NodeKind::Ident("x".to_string())))];
+        let ir_nodes = convert_to_ir(&arena, &ast_nodes);
         assert_eq!(ir_nodes, vec![IrNode::Variable("x".to_string())]);
     }
 
     #[test]
     fn test_convert_binary_expression() {
-        let ast_nodes = vec![Node::BinaryOp(Box::new(BinaryOp {
-            left: Node::Number(2),
+        let mut arena = AstArena::default();
+        let left = arena.alloc_expr(Node::from(NodeKind::Int(2, None)));
+        let right = arena.alloc_expr(Node::from(NodeKind::Int(3, None)));
+        let ast_nodes = vec![arena.alloc_expr(Node::from(NodeKind::BinaryOp(BinaryOp {
+            left,
             operator: Token::new(TokenKind::Plus, "+".to_string()),
-            right: Node::Number(3),
-        }))];
-        let ir_nodes = convert_to_ir(ast_nodes);
+            right,
+        })))];
+        let ir_nodes = convert_to_ir(&arena, &ast_nodes);
         assert_eq!(ir_nodes, vec![
             IrNode::BinaryExpression(Box::new(BinaryExpression {
-                left: IrNode::Constant(2),
+                left: IrNode::Constant(Const::Int(2, Some(IntTy::I32))),
                 operator: Token::new(TokenKind::Plus, "+".to_string()),
-                right: IrNode::Constant(3),
+                right: IrNode::Constant(Const::Int(3, Some(IntTy::I32))),
             }))
         ]);
     }
+
+    #[test]
+    fn test_lower_impl_methods() {
+        let source = "
+            struct Point { x: i32 }
+            impl Point {
+                fn new(x: i32) -> Self { Point { x } }
+                fn len(&self) -> i32 { self.x }
+            }
+            fn main() { let p = Point::new(1); Point::len(&p); }
+        ";
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let module = lower_crate(&krate);
+
+        let names: Vec<&str> = module.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["Point::new", "Point::len", "main"]);
+        assert_eq!(module.functions[1].params, vec!["self".to_string()]);
+        assert_eq!(module.structs, vec![IrStruct { name: "Point".to_string(), fields: vec!["x".to_string()] }]);
+
+        let IrNode::Block { stmts, .. } = &module.functions[2].body else { panic!() };
+        assert_eq!(stmts[1], IrNode::Call {
+            func: "Point::len".to_string(),
+            args: vec![IrNode::Ref { mutable: false, place: Box::new(IrNode::Variable("p".to_string())) }],
+            span: span_of(source, "Point::len(&p)"),
+        });
+    }
+
+    #[test]
+    fn test_lower_compound_assignment() {
+        let source = "fn main() { let mut x = 1; x += 2; }";
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let module = lower_crate(&krate);
+
+        let IrNode::Block { stmts, .. } = &module.functions[0].body else { panic!() };
+        assert_eq!(stmts[1], IrNode::Assign {
+            target: Box::new(IrNode::Variable("x".to_string())),
+            value: Box::new(IrNode::BinaryExpression(Box::new(BinaryExpression {
+                left: IrNode::Variable("x".to_string()),
+                operator: Token::new(TokenKind::Plus, "+".to_string()),
+                right: IrNode::Constant(Const::Int(2, Some(IntTy::I32))),
+            }))),
+            span: span_of(source, "x += 2"),
+        });
+    }
+
+    #[test]
+    fn test_lower_trait_impls() {
+        let source = "
+            struct Circle { r: i32 }
+            trait Shape { fn area(&self) -> i32; fn twice(&self) -> i32 { Self::area(self) * 2 } }
+            impl Shape for Circle { fn area(&self) -> i32 { self.r } }
+        ";
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let module = lower_crate(&krate);
+
+        let names: Vec<&str> = module.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["<Circle as Shape>::area", "<Circle as Shape>::twice"]);
+    }
+
+    #[test]
+    fn test_lower_monomorphized_generics() {
+        let source = "
+            struct Pair<T> { a: T, b: T }
+            impl<T> Pair<T> { fn first(&self) -> T { self.a } }
+            fn max<T: Ord>(a: T, b: T) -> T { if a > b { a } else { b } }
+            fn main() { let p = Pair::<i32> { a: 1, b: 2 }; max::<i32>(Pair::<i32>::first(&p), 3); }
+        ";
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let krate = monomorphize(&krate).unwrap();
+        let module = lower_crate(&krate);
+
+        let names: Vec<&str> = module.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["main", "Pair<i32>::first", "max::<i32>"]);
+        assert_eq!(module.structs, vec![IrStruct {
+            name: "Pair<i32>".to_string(),
+            fields: vec!["a".to_string(), "b".to_string()],
+        }]);
+
+        let IrNode::Block { stmts, .. } = &module.functions[0].body else { panic!() };
+        let IrNode::Call { func, args, .. } = &stmts[1] else { panic!() };
+        assert_eq!(func, "max::<i32>");
+        assert!(matches!(&args[0], IrNode::Call { func, .. } if func == "Pair<i32>::first"));
+    }
+
+    #[test]
+    fn test_lower_index_with_bounds_check() {
+        let source = "fn main() { let a = [1, 2, 3]; a[5]; a[1..]; }";
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let module = lower_crate(&krate);
+        let IrNode::Block { stmts, .. } = &module.functions[0].body else { panic!() };
+
+        let var = |name: &str| IrNode::Variable(name.to_string());
+        let len = || IrNode::Len(Box::new(var("a")));
+        let IrNode::Index { base, index } = &stmts[1] else { panic!("expected index") };
+        assert_eq!(**base, var("a"));
+        let IrNode::Block { stmts: checks, value } = index.as_ref() else { panic!("expected checked index") };
+        assert_eq!(checks[0], IrNode::Let {
+            name: "index#0".to_string(),
+            value: Some(Box::new(IrNode::Constant(Const::Int(5, Some(IntTy::I32))))),
+            span: Span::DUMMY,
+        });
+        let IrNode::If { then, .. } = &checks[1] else { panic!("expected bounds check") };
+        assert_eq!(**then, IrNode::Panic {
+            message: "index out of bounds: the len is {} but the index is {}".to_string(),
+            args: vec![len(), var("index#0")],
+            span: span_of(source, "a[5]"),
+        });
+        assert_eq!(value.as_deref(), Some(&var("index#0")));
+
+        // `a[1..]`의 끝은 배열의 길이입니다.
+        let IrNode::Block { stmts: checks, value } = &stmts[2] else { panic!("expected checked slice") };
+        assert_eq!(checks[1], IrNode::Let { name: "end#0".to_string(), value: Some(Box::new(len())), span: Span::DUMMY });
+        let messages: Vec<&str> = checks[2..].iter().map(|check| match check {
+            IrNode::If { then, .. } => match then.as_ref() {
+                IrNode::Panic { message, .. } => message.as_str(),
+                _ => panic!(),
+            },
+            _ => panic!(),
+        }).collect();
+        assert_eq!(messages, vec![
+            "slice index starts at {} but ends at {}",
+            "range end index {} out of range for slice of length {}",
+        ]);
+        assert_eq!(value.as_deref(), Some(&IrNode::Slice {
+            base: Box::new(var("a")),
+            start: Box::new(var("start#0")),
+            end: Box::new(var("end#0")),
+        }));
+    }
+
+    #[test]
+    fn test_lower_for_loops() {
+        let source = "fn main() { let a = [1, 2]; for i in 0..=3 { continue; } for x in &a {} }";
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let module = lower_crate(&krate);
+        let IrNode::Block { stmts, value } = &module.functions[0].body else { panic!() };
+        let var = |name: &str| IrNode::Variable(name.to_string());
+        let binary = |left, kind, text: &str, right| {
+            IrNode::BinaryExpression(Box::new(BinaryExpression { left, operator: Token::new(kind, text.to_string()), right }))
+        };
+
+        // 범위 루프는 카운터를 늘려 가는 루프가 됩니다.
+        let IrNode::Block { stmts: range_loop, .. } = &stmts[1] else { panic!("expected block") };
+        assert_eq!(range_loop[0], IrNode::Let {
+            name: "next#0".to_string(),
+            value: Some(Box::new(IrNode::Constant(Const::Int(0, Some(IntTy::I32))))),
+            span: Span::DUMMY,
+        });
+        // `a..=b`는 마지막 값에서 카운터를 늘리는 대신 끝났다고 표시합니다.
+        assert_eq!(range_loop[2], IrNode::Let {
+            name: "done#0".to_string(),
+            value: Some(Box::new(binary(var("next#0"), TokenKind::Gt, ">", var("end#0")))),
+            span: Span::DUMMY,
+        });
+        let IrNode::Loop(body) = &range_loop[3] else { panic!("expected loop") };
+        let IrNode::Block { stmts: body, .. } = body.as_ref() else { panic!() };
+        assert_eq!(body[0], IrNode::If { cond: Box::new(var("done#0")), then: Box::new(IrNode::Break), els: None });
+        assert_eq!(body[1], IrNode::Let { name: "i".to_string(), value: Some(Box::new(var("next#0"))), span: Span::DUMMY });
+        let IrNode::If { cond, els: Some(_), .. } = &body[2] else { panic!("expected step") };
+        assert_eq!(**cond, binary(var("next#0"), TokenKind::EqEq, "==", var("end#0")));
+
+        // 배열 반복은 반복자 프로토콜을 거칩니다.
+        let Some(IrNode::Block { stmts: iter_loop, .. }) = value.as_deref() else { panic!("expected block") };
+        let a = IrNode::Ref { mutable: false, place: Box::new(var("a")) };
+        assert_eq!(iter_loop[0], IrNode::Let {
+            name: "iter#0".to_string(),
+            value: Some(Box::new(IrNode::Call {
+                func: "IntoIterator::into_iter".to_string(),
+                args: vec![a],
+                span: Span::DUMMY,
+            })),
+            span: Span::DUMMY,
+        });
+        let IrNode::Loop(body) = &iter_loop[1] else { panic!("expected loop") };
+        let IrNode::Block { stmts: body, .. } = body.as_ref() else { panic!() };
+        let funcs: Vec<&str> = body.iter().filter_map(|stmt| match stmt {
+            IrNode::Let { value: Some(value), .. } | IrNode::If { cond: value, .. } => match value.as_ref() {
+                IrNode::Call { func, .. } => Some(func.as_str()),
+                _ => None,
+            },
+            _ => None,
+        }).collect();
+        assert_eq!(funcs, vec!["Iterator::next", "Option::is_none", "Option::unwrap"]);
+    }
+
+    #[test]
+    fn test_lowered_for_loops_run_to_the_end_bound() {
+        // 끝값이 타입의 최댓값이어도 넘치지 않고, 임시 변수가 사용자의 `__next`를 가리지 않습니다.
+        let source = "fn count() -> u32 { let mut n = 0u32; let __next = 10u32; \
+                      for i in 0u8..=255u8 { if i == 3u8 { continue; } n = n + 1u32; } \
+                      for j in 0..3 { n = n + __next; } for k in 5..=4 { n = n + 1000u32; } n }";
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let module = lower_crate(&krate);
+        let result = ConstEvaluator::new(&[]).eval(&module.functions[0].body);
+        assert_eq!(result, Ok(Value::Int(255 + 30, IntTy::U32)));
+    }
+
+    #[test]
+    fn test_lower_print_and_panic() {
+        let source = r#"fn main() {
+            std::io::_print(format_args!("{}", 1));
+            std::panicking::panic("assertion failed: {x}");
+            std::panicking::panic_fmt(format_args!("bad {}", 2));
+        }"#;
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let module = lower_crate(&krate);
+        let IrNode::Block { stmts, .. } = &module.functions[0].body else { panic!() };
+
+        let IrNode::Call { func, args, .. } = &stmts[0] else { panic!("expected call") };
+        assert_eq!(func, "std::io::_print");
+        let IrNode::Block { stmts: format, value: Some(value) } = &args[0] else { panic!("expected block") };
+        assert_eq!(format[0], IrNode::Let {
+            name: "arg0#0".to_string(),
+            value: Some(Box::new(IrNode::Ref { mutable: false, place: Box::new(IrNode::Constant(Const::Int(1, Some(IntTy::I32)))) })),
+            span: Span::DUMMY,
+        });
+        let IrNode::Call { func, args, .. } = &format[2] else { panic!("expected call") };
+        assert_eq!(func, "std::fmt::Display::fmt");
+        assert_eq!(args[0], IrNode::Variable("arg0#0".to_string()));
+        assert_eq!(**value, IrNode::Variable("fmt#0".to_string()));
+        // 포맷 문자열이 아닌 메시지의 중괄호는 이스케이프됩니다.
+        assert_eq!(stmts[1], IrNode::Panic {
+            message: "assertion failed: {{x}}".to_string(),
+            args: Vec::new(),
+            span: span_of(source, r#"std::panicking::panic("assertion failed: {x}")"#),
+        });
+        let IrNode::Call { func, .. } = &stmts[2] else { panic!("expected call") };
+        assert_eq!(func, "std::panicking::panic_fmt");
+    }
+
+    #[test]
+    fn test_lower_format_spec() {
+        let source = r#"fn main() { let w = 6; std::io::_print(format_args!("a{:+>w$.2x}", 255)); }"#;
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let module = lower_crate(&krate);
+        let IrNode::Block { stmts, .. } = &module.functions[0].body else { panic!() };
+        let IrNode::Call { args, .. } = &stmts[1] else { panic!("expected call") };
+        let IrNode::Block { stmts: format, .. } = &args[0] else { panic!("expected block") };
+
+        // `w`는 캡처한 인자 `__arg1`이 되고, 값은 쓴 순서대로 먼저 묶입니다.
+        assert_eq!(format[1], IrNode::Let {
+            name: "arg1#0".to_string(),
+            value: Some(Box::new(IrNode::Ref { mutable: false, place: Box::new(IrNode::Variable("w".to_string())) })),
+            span: Span::DUMMY,
+        });
+        let fmt = IrNode::Ref { mutable: true, place: Box::new(IrNode::Variable("fmt#0".to_string())) };
+        assert_eq!(format[3], IrNode::Call {
+            func: "std::fmt::Formatter::write_str".to_string(),
+            args: vec![fmt.clone(), IrNode::Str("a".to_string())],
+            span: Span::DUMMY,
+        });
+        let IrNode::Call { func, args, .. } = &format[4] else { panic!("expected call") };
+        assert_eq!(func, "std::fmt::LowerHex::fmt");
+        let IrNode::StructInit { name, fields } = &args[2] else { panic!("expected spec") };
+        assert_eq!(name, "std::fmt::Spec");
+        let field = |name: &str| fields.iter().find(|(field, _)| field == name).unwrap().1.clone();
+        assert_eq!(field("fill"), IrNode::Constant(Const::Char('+')));
+        assert_eq!(field("align"), IrNode::Constant(Const::Int(3, Some(IntTy::U8))));
+        assert_eq!(field("precision"), IrNode::Constant(Const::Int(2, Some(IntTy::Usize))));
+        assert!(matches!(field("width"), IrNode::UnaryExpression(unary) if unary.operand == IrNode::Variable("arg1#0".to_string())));
+    }
+
+    #[test]
+    fn test_lower_consts_and_fold() {
+        let source = "const N: u32 = 4u32 * 16u32; const M: i8 = -3i8 * 2i8; static S: u32 = N; \
+                      fn main() { let a = N + 1u32; let b = M; let c = S; let d = 255u8 + 1u8; }";
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let module = lower_crate(&krate);
+        assert_eq!(module.consts, vec![
+            IrConst { name: "N".to_string(), value: Value::Int(64, IntTy::U32), is_static: false },
+            IrConst { name: "M".to_string(), value: Value::Int(0xfa, IntTy::I8), is_static: false },
+            IrConst { name: "S".to_string(), value: Value::Int(64, IntTy::U32), is_static: true },
+        ]);
+
+        // 상수와 상수 연산은 값으로 접히고, `static`과 넘치는 연산은 그대로 남습니다.
+        let IrNode::Block { stmts, .. } = &module.functions[0].body else { panic!() };
+        let values: Vec<&IrNode> = stmts.iter().map(|stmt| match stmt {
+            IrNode::Let { value: Some(value), .. } => &**value,
+            _ => panic!("expected let"),
+        }).collect();
+        assert_eq!(*values[0], IrNode::Constant(Const::Int(65, Some(IntTy::U32))));
+        assert_eq!(*values[1], Value::Int(0xfa, IntTy::I8).to_ir());
+        assert!(matches!(values[1], IrNode::UnaryExpression(unary) if unary.operand == IrNode::Constant(Const::Int(6, Some(IntTy::I8)))));
+        assert_eq!(*values[2], IrNode::Variable("S".to_string()));
+        assert!(matches!(values[3], IrNode::BinaryExpression(_)));
+    }
+
+    #[test]
+    fn test_const_eval_min_literals_round_trip() {
+        let source = "const A: i32 = -2147483648; const B: i8 = -128i8 + 1i8; fn main() { let a = A; }";
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let module = lower_crate(&krate);
+        assert_eq!(module.consts[0].value, Value::Int(0x8000_0000, IntTy::I32));
+        assert_eq!(module.consts[1].value, Value::Int(0x81, IntTy::I8));
+
+        // 접은 최솟값은 리터럴을 부정한 모양이고, 다시 평가해도 같은 값입니다.
+        let mut evaluator = ConstEvaluator::new(&[]);
+        let min = Value::Int(0x8000_0000, IntTy::I32);
+        assert_eq!(evaluator.eval(&min.to_ir()), Ok(min));
+
+        // 타입 없는 정수는 `i32`입니다.
+        assert_eq!(evaluator.eval(&IrNode::Constant(Const::Int(5, None))), Ok(Value::Int(5, IntTy::I32)));
+    }
 }
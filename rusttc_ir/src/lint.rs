@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use rusttc_ast::ast::{AstArena, Attribute, Crate, FnDef, Item, MetaItemKind, NestedMeta, Pat, PatKind};
+use rusttc_ast::visit::{walk_fn, walk_item, walk_pat, Visitor};
+
+// 린트의 수준입니다. `allow`는 보고하지 않고, `warn`은 경고, `deny`는 오류로 보고합니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl Level {
+    fn from_attr(name: &str) -> Option<Level> {
+        match name {
+            "allow" => Some(Level::Allow),
+            "warn" => Some(Level::Warn),
+            "deny" => Some(Level::Deny),
+            _ => None,
+        }
+    }
+}
+
+// 린트의 이름과 기본 수준입니다.
+pub struct Lint {
+    pub name: &'static str,
+    pub default_level: Level,
+}
+
+pub const NON_SNAKE_CASE: Lint = Lint { name: "non_snake_case", default_level: Level::Warn };
+pub const NON_CAMEL_CASE_TYPES: Lint = Lint { name: "non_camel_case_types", default_level: Level::Warn };
+pub const NON_UPPER_CASE_GLOBALS: Lint = Lint { name: "non_upper_case_globals", default_level: Level::Warn };
+pub const UNKNOWN_LINTS: Lint = Lint { name: "unknown_lints", default_level: Level::Warn };
+
+const LINTS: &[Lint] = &[NON_SNAKE_CASE, NON_CAMEL_CASE_TYPES, NON_UPPER_CASE_GLOBALS, UNKNOWN_LINTS];
+
+// 경고 수준인 린트 전체를 한꺼번에 가리키는 이름입니다 (`#![deny(warnings)]`).
+const WARNINGS: &str = "warnings";
+
+// rustc에는 있지만 이 컴파일러가 검사하지 않는 린트입니다.
+// 수준을 정해도 보고할 것이 없을 뿐, rustc에서 옮겨 온 소스를 모르는 린트로 보고하지 않습니다.
+const RUSTC_LINTS: &[&str] = &[
+    "dead_code", "unused_variables", "unused_mut", "unused_imports", "unused_assignments", "unused_must_use",
+    "unused_unsafe", "unused_attributes", "unused_macros", "unused_macro_rules", "unused_allocation", "unused_doc_comments",
+    "unused_extern_crates", "unused_features", "unused_labels", "unused_parens", "unused_braces", "unused_results",
+    "unreachable_code", "unreachable_patterns", "path_statements", "redundant_semicolons", "map_unit_fn",
+    "overflowing_literals", "arithmetic_overflow", "unconditional_recursion", "while_true", "deprecated", "missing_docs",
+    "non_fmt_panics", "irrefutable_let_patterns",
+];
+
+// 여러 린트를 한꺼번에 가리키는 이름과 그 구성원입니다 (`#[allow(unused)]`).
+const LINT_GROUPS: &[(&str, &[&str])] = &[
+    ("unused", &[
+        "unused_imports", "unused_variables", "unused_assignments", "dead_code", "unused_mut", "unreachable_code",
+        "unreachable_patterns", "unused_must_use", "unused_unsafe", "path_statements", "unused_attributes", "unused_macros",
+        "unused_macro_rules", "unused_allocation", "unused_doc_comments", "unused_extern_crates", "unused_features",
+        "unused_labels", "unused_parens", "unused_braces", "redundant_semicolons", "map_unit_fn",
+    ]),
+    ("nonstandard_style", &["non_camel_case_types", "non_snake_case", "non_upper_case_globals"]),
+];
+
+// 도구 린트(`clippy::all`)는 그 도구가 검사하므로 이름을 따지지 않습니다.
+const LINT_TOOLS: &[&str] = &["clippy", "rustdoc"];
+
+fn is_known_lint(name: &str) -> bool {
+    let tool = name.split_once("::").map(|(tool, _)| tool);
+    name == WARNINGS
+        || LINTS.iter().any(|lint| lint.name == name)
+        || RUSTC_LINTS.contains(&name)
+        || tool.is_some_and(|tool| LINT_TOOLS.contains(&tool))
+}
+
+// 린트가 보고한 진단입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintDiagnostic {
+    pub level: Level,           // `Warn` 또는 `Deny`
+    pub lint: &'static str,     // 린트 이름
+    pub message: String,
+    pub help: Option<String>,   // 고치는 방법
+}
+
+impl fmt::Display for LintDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = if self.level == Level::Deny { "error" } else { "warning" };
+        write!(f, "{}: {}", level, self.message)?;
+        if let Some(help) = &self.help {
+            write!(f, "\n  = help: {}", help)?;
+        }
+        Ok(())
+    }
+}
+
+// 이름 규칙 린트를 실행합니다.
+// `#![allow(...)]`, `#[warn(...)]`, `#[deny(...)]`로 정한 수준은 그 크레이트, 모듈, 아이템 안에서 적용되고,
+// 안쪽에서 다시 정한 수준이 우선합니다. 모듈 트리를 펼치기 전에 실행되어야 합니다.
+pub fn check_crate(krate: &Crate) -> Vec<LintDiagnostic> {
+    let mut checker = LintChecker { arena: &krate.arena, levels: Vec::new(), diagnostics: Vec::new() };
+    checker.with_attrs(&krate.attrs, |checker| krate.items.iter().for_each(|item| checker.visit_item(item)));
+    checker.diagnostics
+}
+
+struct LintChecker<'a> {
+    arena: &'a AstArena,
+    levels: Vec<HashMap<String, Level>>,    // 속성으로 정한 수준 (안쪽이 뒤)
+    diagnostics: Vec<LintDiagnostic>,
+}
+
+impl LintChecker<'_> {
+    fn level(&self, name: &str) -> Option<Level> {
+        self.levels.iter().rev().find_map(|levels| levels.get(name).copied())
+    }
+
+    fn emit(&mut self, lint: &Lint, message: String, help: Option<String>) {
+        let mut level = self.level(lint.name).unwrap_or(lint.default_level);
+        if level == Level::Warn {
+            level = self.level(WARNINGS).unwrap_or(Level::Warn);
+        }
+        if level != Level::Allow {
+            self.diagnostics.push(LintDiagnostic { level, lint: lint.name, message, help });
+        }
+    }
+
+    // 속성으로 정한 수준을 적용한 채로 `check`를 실행합니다.
+    fn with_attrs(&mut self, attrs: &[Attribute], check: impl FnOnce(&mut Self)) {
+        let mut levels = HashMap::new();
+        let mut unknown = Vec::new();
+        for attr in attrs {
+            let level = attr.meta.path.as_ident().and_then(Level::from_attr);
+            let (Some(level), MetaItemKind::List(list)) = (level, &attr.meta.kind) else { continue };
+            for nested in list {
+                let NestedMeta::Meta(meta) = nested else { continue };
+                let name = meta.path.to_string();
+                if let Some((_, members)) = LINT_GROUPS.iter().find(|(group, _)| *group == name) {
+                    levels.extend(members.iter().map(|member| (member.to_string(), level)));
+                } else if is_known_lint(&name) {
+                    levels.insert(name, level);
+                } else {
+                    unknown.push(name);
+                }
+            }
+        }
+
+        self.levels.push(levels);
+        for name in unknown {
+            self.emit(&UNKNOWN_LINTS, format!("unknown lint: `{}`", name), None);
+        }
+        check(self);
+        self.levels.pop();
+    }
+
+    fn check_snake_case(&mut self, kind: &str, name: &str) {
+        if name.chars().any(char::is_uppercase) {
+            let message = format!("{} `{}` should have a snake case name", kind, name);
+            let help = format!("convert the identifier to snake case: `{}`", to_snake_case(name));
+            self.emit(&NON_SNAKE_CASE, message, Some(help));
+        }
+    }
+
+    fn check_camel_case(&mut self, kind: &str, name: &str) {
+        let trimmed = name.trim_matches('_');
+        if trimmed.starts_with(char::is_lowercase) || trimmed.contains('_') {
+            let message = format!("{} `{}` should have an upper camel case name", kind, name);
+            let help = format!("convert the identifier to upper camel case: `{}`", to_camel_case(name));
+            self.emit(&NON_CAMEL_CASE_TYPES, message, Some(help));
+        }
+    }
+
+    fn check_upper_case(&mut self, kind: &str, name: &str) {
+        if name.chars().any(char::is_lowercase) {
+            let message = format!("{} `{}` should have an upper case name", kind, name);
+            let help = format!("convert the identifier to upper case: `{}`", to_snake_case(name).to_uppercase());
+            self.emit(&NON_UPPER_CASE_GLOBALS, message, Some(help));
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for LintChecker<'a> {
+    fn arena(&self) -> &'a AstArena {
+        self.arena
+    }
+
+    // 함수의 속성은 impl, 트레이트 안의 함수와 마찬가지로 `visit_fn`에서 적용합니다.
+    fn visit_item(&mut self, item: &'a Item) {
+        if let Item::Fn(_) = item {
+            return walk_item(self, item);
+        }
+        self.with_attrs(item.attrs(), |checker| {
+            match item {
+                Item::Struct(def) => checker.check_camel_case("struct", &def.name),
+                Item::Trait(def) => checker.check_camel_case("trait", &def.name),
+                Item::Mod(def) => checker.check_snake_case("module", &def.name),
+                Item::Const(def) => checker.check_upper_case("constant", &def.name),
+                Item::Static(def) => checker.check_upper_case("static variable", &def.name),
+                Item::Fn(_) | Item::Impl(_) | Item::Use(_) => {},
+            }
+            walk_item(checker, item);
+        });
+    }
+
+    fn visit_fn(&mut self, function: &'a FnDef) {
+        self.with_attrs(&function.attrs, |checker| {
+            let kind = if function.self_param.is_some() { "method" } else { "function" };
+            checker.check_snake_case(kind, &function.name);
+            walk_fn(checker, function);
+        });
+    }
+
+    // 함수 매개변수, `let`, `for`, 클로저 매개변수, match 갈래의 바인딩을 검사합니다.
+    fn visit_pat(&mut self, pat: &'a Pat) {
+        if let PatKind::Ident { name, .. } = &pat.kind {
+            self.check_snake_case("variable", name);
+        }
+        walk_pat(self, pat);
+    }
+}
+
+// `fooBar` -> `foo_bar`, `HTTPServer` -> `httpserver`가 아닌 `http_server`
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_ascii_digit());
+            let before_lower = i > 0 && chars[i - 1].is_uppercase() && chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            if (after_lower || before_lower) && !result.ends_with('_') {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+// `foo_bar` -> `FooBar`
+fn to_camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().unwrap();
+            first.to_uppercase().chain(chars).collect::<String>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusttc_lexer::tokenize;
+    use rusttc_parse::parser::parse_crate;
+
+    fn lint(source: &str) -> Vec<String> {
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        check_crate(&krate).iter().map(LintDiagnostic::to_string).collect()
+    }
+
+    #[test]
+    fn test_naming_lints() {
+        assert_eq!(
+            lint("struct point_2d; fn doThing(someArg: i32) { let HTTPServer = 1; }"),
+            vec![
+                "warning: struct `point_2d` should have an upper camel case name\n  = help: convert the identifier to upper camel case: `Point2d`",
+                "warning: function `doThing` should have a snake case name\n  = help: convert the identifier to snake case: `do_thing`",
+                "warning: variable `someArg` should have a snake case name\n  = help: convert the identifier to snake case: `some_arg`",
+                "warning: variable `HTTPServer` should have a snake case name\n  = help: convert the identifier to snake case: `http_server`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_levels_nest() {
+        let source = "
+            #![deny(non_snake_case)]
+            fn badName() {}
+            #[allow(non_snake_case)]
+            mod m { fn alsoBad() {} #[warn(non_snake_case)] fn warned() { let xY = 1; } }
+        ";
+        let diagnostics = check_crate(&parse_crate(&tokenize(source)).unwrap());
+        let levels: Vec<(Level, &str)> = diagnostics.iter().map(|d| (d.level, d.message.as_str())).collect();
+        assert_eq!(levels, vec![
+            (Level::Deny, "function `badName` should have a snake case name"),
+            (Level::Warn, "variable `xY` should have a snake case name"),
+        ]);
+    }
+
+    #[test]
+    fn test_warnings_group_and_unknown_lints() {
+        let diagnostics = check_crate(&parse_crate(&tokenize("#![deny(warnings)] #[allow(dead_cod)] fn f() {}")).unwrap());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].level, Level::Deny);
+        assert_eq!(diagnostics[0].message, "unknown lint: `dead_cod`");
+        assert!(lint("#[allow(warnings)] fn fooBar() {}").is_empty());
+    }
+
+    #[test]
+    fn test_rustc_lints_and_groups() {
+        let source = "#![allow(unused)] #[allow(dead_code, unused_variables)] #[warn(clippy::all)] fn f() {} \
+                      #[allow(nonstandard_style)] fn fooBar() { let xY = 1; } fn barBaz() {}";
+        assert_eq!(lint(source), vec![
+            "warning: function `barBaz` should have a snake case name\n  = help: convert the identifier to snake case: `bar_baz`",
+        ]);
+
+        // 그룹 안에서 다시 정한 린트가 우선합니다.
+        let source = "#![allow(nonstandard_style)] #[deny(non_snake_case)] fn fooBar() {} struct my_struct;";
+        let diagnostics = check_crate(&parse_crate(&tokenize(source)).unwrap());
+        let levels: Vec<(Level, &str)> = diagnostics.iter().map(|d| (d.level, d.message.as_str())).collect();
+        assert_eq!(levels, vec![(Level::Deny, "function `fooBar` should have a snake case name")]);
+        assert_eq!(lint("#[allow(unused_variable)] fn f() {}"), vec!["warning: unknown lint: `unused_variable`"]);
+    }
+}
@@ -0,0 +1,769 @@
+mod traits;
+
+use std::collections::HashMap;
+use std::fmt;
+
+use rusttc_ast::ast::{AstArena, Crate, FnDef, Generics, Impl, Item, Node, NodeKind, Pat, PatKind, Path, QSelf, StmtKind, Ty, TyKind, FN_TRAITS};
+use rusttc_ast::mut_visit::{self, MutVisitor};
+use rusttc_lexer::Span;
+
+// 메서드 해석 중에 발생한 오류입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodError {
+    pub message: String,
+    pub span: Span,             // 오류를 낸 아이템이나 표현식의 위치
+}
+
+impl fmt::Display for MethodError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)?;
+        if let Some(location) = self.span.location() {
+            write!(f, "\n  --> {}", location)?;
+        }
+        Ok(())
+    }
+}
+
+// 컴파일러가 기본으로 제공하는 트레이트입니다. 메서드 없이 바운드로만 쓰입니다.
+const BUILTIN_TRAITS: &[&str] = &["Copy", "Clone", "PartialEq", "Eq", "PartialOrd", "Ord"];
+
+const PRIMITIVE_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
+    "f64", "bool", "char",
+];
+
+// 기본 타입이 구현하는 내장 트레이트입니다. 부동소수점은 전순서가 없습니다.
+fn builtin_impls(ty: &str) -> &'static [&'static str] {
+    match ty {
+        "f32" | "f64" => &["Copy", "Clone", "PartialEq", "PartialOrd"],
+        _ => BUILTIN_TRAITS,
+    }
+}
+
+// impl 블록의 정보입니다. 트레이트 구현이라면 메서드 시그니처는 트레이트 선언에서 가져옵니다.
+struct ImplInfo {
+    generics: Generics,         // impl의 타입 매개변수
+    of_trait: Option<String>,   // 구현하는 트레이트
+    self_ty: Ty,                // 구현 대상 타입 (`Pair<T>`)
+    items: Vec<FnDef>,          // 고유 impl의 함수들
+}
+
+impl ImplInfo {
+    // 구현 대상 타입이 `ty`와 맞는지 확인하고, 맞다면 impl 타입 매개변수의 값을 돌려줍니다.
+    fn matches(&self, ty: &Ty) -> Option<HashMap<String, Ty>> {
+        let names: Vec<&str> = self.generics.params.iter().map(|param| param.name.as_str()).collect();
+        let mut subst = HashMap::new();
+        unify(&self.self_ty, ty, &names, &mut subst).then_some(subst)
+    }
+}
+
+// impl 블록을 모아 트레이트 구현을 확인하고, impl 블록 안의 `Self`를 구현 대상 타입으로 치환합니다.
+// 연관 함수 경로 `Type::name`은 고유 impl의 경로로 두거나 `<Type as Trait>::name`으로 바꾸고,
+// 클로저에는 IR에서 쓸 이름(`main::{closure#0}`)을 붙입니다.
+// 메서드 호출 `p.len()`은 타입을 알아야 하므로 타입 검사(`rusttc_typeck`)가 해석하고 경로 호출로 바꿉니다.
+pub fn resolve_methods(krate: &mut Crate) -> Result<(), Vec<MethodError>> {
+    let mut resolver = MethodResolver::new();
+    resolver.arena = std::mem::take(&mut krate.arena);
+    resolver.collect(&krate.items);
+    resolver.visit_crate(krate);
+    krate.arena = std::mem::take(&mut resolver.arena);
+
+    if resolver.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(resolver.errors)
+    }
+}
+
+#[derive(Default)]
+struct MethodResolver {
+    arena: AstArena,                                    // 크레이트의 표현식과 문장
+    structs: HashMap<String, Vec<String>>,              // 구조체 이름 -> 필드 이름
+    functions: Vec<String>,                             // 함수 이름
+    impls: HashMap<String, Vec<ImplInfo>>,              // 타입 이름 -> impl 블록들
+    traits: HashMap<String, Vec<FnDef>>,                // 트레이트 이름 -> 메서드 선언
+    self_ty: Option<Ty>,                                // 현재 impl 블록의 대상 타입
+    generics: HashMap<String, Vec<Path>>,               // 타입 매개변수 -> 트레이트 바운드
+    fn_path: String,                                    // 현재 함수의 이름 (`Point::new`), 클로저 이름에 씁니다
+    closure_count: usize,                               // 현재 함수 안의 클로저 수
+    errors: Vec<MethodError>,
+}
+
+impl MethodResolver {
+    fn new() -> Self {
+        let mut resolver = MethodResolver::default();
+        for name in BUILTIN_TRAITS.iter().chain(FN_TRAITS) {
+            resolver.traits.insert(name.to_string(), Vec::new());
+        }
+        for ty in PRIMITIVE_TYPES {
+            let impls = builtin_impls(ty).iter().map(|name| ImplInfo {
+                generics: Generics::default(),
+                of_trait: Some(name.to_string()),
+                self_ty: Ty::from_ident(ty),
+                items: Vec::new(),
+            });
+            resolver.impls.insert(ty.to_string(), impls.collect());
+        }
+        resolver
+    }
+
+    fn error(&mut self, message: String, span: Span) {
+        self.errors.push(MethodError { message, span });
+    }
+
+    // 구조체, 함수, 트레이트, impl 블록을 모읍니다.
+    fn collect(&mut self, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::Fn(function) => {
+                    self.functions.push(function.name.clone());
+                    self.collect_fn(function);
+                },
+                Item::Struct(def) => {
+                    self.structs.insert(def.name.clone(), def.fields.iter().map(|field| field.name.clone()).collect());
+                },
+                Item::Impl(imp) => imp.items.iter().for_each(|function| self.collect_fn(function)),
+                Item::Trait(def) => {
+                    self.traits.insert(def.name.clone(), def.items.clone());
+                    def.items.iter().for_each(|function| self.collect_fn(function));
+                },
+                // 모듈과 `use`는 `modules::resolve_modules`에서 이미 풀려 있습니다.
+                Item::Const(_) | Item::Static(_) | Item::Mod(_) | Item::Use(_) => {},
+            }
+        }
+
+        // impl 블록은 모든 구조체와 트레이트를 모은 다음에 등록합니다.
+        for item in items {
+            if let Item::Impl(imp) = item {
+                if let Some(message) = self.check_impl_ty(imp) {
+                    self.error(message, imp.self_ty.span);
+                    continue;
+                }
+                match &imp.of_trait {
+                    Some(trait_path) => self.register_trait_impl(trait_path, imp),
+                    None => self.register_inherent_impl(imp),
+                }
+            }
+        }
+    }
+
+    // impl 블록의 대상 타입을 해석하고, 구현할 수 없는 타입이면 오류 메시지를 돌려줍니다.
+    // 트레이트는 기본 타입, 튜플, 참조에도 구현할 수 있지만 고유 impl은 구조체에만 쓸 수 있습니다.
+    fn check_impl_ty(&self, imp: &Impl) -> Option<String> {
+        let params: Vec<&str> = imp.generics.params.iter().map(|param| param.name.as_str()).collect();
+        if let Err(name) = self.resolve_ty(&imp.self_ty, &params) {
+            return Some(format!("cannot find type `{}` in this scope", name));
+        }
+        match ty_name(&imp.self_ty) {
+            _ if imp.of_trait.is_some() => None,
+            Some(name) if self.structs.contains_key(&name) => None,
+            Some(name) if params.contains(&name.as_str()) => Some("no nominal type found for inherent implementation".to_string()),
+            _ => Some("cannot define inherent `impl` for primitive types".to_string()),
+        }
+    }
+
+    // 타입 표기 안의 경로가 모두 구조체, 기본 타입, 타입 매개변수(`params`) 중 하나인지 확인합니다.
+    // 찾지 못한 이름이 있으면 그 이름을 돌려줍니다.
+    fn resolve_ty(&self, ty: &Ty, params: &[&str]) -> Result<(), String> {
+        match &ty.kind {
+            TyKind::Path(path) => {
+                let name = trait_name(path);
+                let known = self.structs.contains_key(&name)
+                    || PRIMITIVE_TYPES.contains(&name.as_str())
+                    || name == "str"
+                    || params.contains(&name.as_str());
+                if !known {
+                    return Err(name);
+                }
+                path.segments.iter().flat_map(|segment| &segment.args).try_for_each(|arg| self.resolve_ty(arg, params))
+            },
+            TyKind::Ref { ty, .. } | TyKind::Array(ty, _) | TyKind::Slice(ty) => self.resolve_ty(ty, params),
+            TyKind::Tuple(tys) => tys.iter().try_for_each(|ty| self.resolve_ty(ty, params)),
+            TyKind::Unit => Ok(()),
+        }
+    }
+
+    fn collect_fn(&mut self, function: &FnDef) {
+        let Some(body) = &function.body else { return };
+        let items: Vec<Item> = body
+            .stmts
+            .iter()
+            .filter_map(|&stmt| match &self.arena[stmt].kind {
+                StmtKind::Item(item) => Some(item.clone()),
+                _ => None,
+            })
+            .collect();
+        self.collect(&items);
+    }
+
+    fn register_inherent_impl(&mut self, imp: &Impl) {
+        let name = ty_name(&imp.self_ty).unwrap();
+        let impls = self.impls.entry(name).or_default();
+        let existing: Vec<String> = impls
+            .iter()
+            .filter(|imp| imp.of_trait.is_none())
+            .flat_map(|imp| imp.items.iter().map(|function| function.name.clone()))
+            .collect();
+
+        let mut signatures: Vec<FnDef> = Vec::new();
+        let mut duplicates = Vec::new();
+        for function in &imp.items {
+            if existing.contains(&function.name) || signatures.iter().any(|other| other.name == function.name) {
+                duplicates.push((function.name.clone(), function.span));
+                continue;
+            }
+            signatures.push(FnDef { body: None, ..function.clone() });
+        }
+        impls.push(ImplInfo { generics: imp.generics.clone(), of_trait: None, self_ty: imp.self_ty.clone(), items: signatures });
+
+        for (name, span) in duplicates {
+            self.error(format!("duplicate definitions with name `{}`", name), span);
+        }
+    }
+
+    // 제네릭 매개변수의 트레이트 바운드를 모읍니다. `span`은 매개변수를 선언한 아이템의 위치입니다.
+    fn collect_bounds(&mut self, generics: &Generics, span: Span) -> HashMap<String, Vec<Path>> {
+        let mut result = HashMap::new();
+        for param in &generics.params {
+            for bound in &param.bounds {
+                let name = trait_name(bound);
+                if !self.traits.contains_key(&name) {
+                    self.error(format!("cannot find trait `{}` in this scope", name), span);
+                }
+            }
+            result.insert(param.name.clone(), param.bounds.clone());
+        }
+        result
+    }
+
+    fn resolve_impl(&mut self, imp: &mut Impl) {
+        if self.check_impl_ty(imp).is_some() {
+            return;
+        }
+        if let Some(trait_path) = imp.of_trait.clone() {
+            self.check_trait_impl(&trait_path, imp);
+        }
+        self.generics = self.collect_bounds(&imp.generics, imp.span);
+        self.self_ty = Some(imp.self_ty.clone());
+        let prefix = match &imp.of_trait {
+            Some(trait_path) => format!("<{} as {}>", imp.self_ty, trait_path),
+            None => imp.self_ty.to_string(),
+        };
+        for function in &mut imp.items {
+            let path = format!("{}::{}", prefix, function.name);
+            self.resolve_fn(function, path);
+        }
+    }
+
+    // `path`는 IR에서 쓰일 함수 이름으로, 본문 안 클로저의 이름에 붙습니다.
+    fn resolve_fn(&mut self, function: &mut FnDef, path: String) {
+        let old_path = std::mem::replace(&mut self.fn_path, path);
+        let old_count = std::mem::replace(&mut self.closure_count, 0);
+        if self.self_ty.is_none() && function.self_param.is_some() {
+            self.error(
+                format!("`self` parameter is only allowed in associated functions (found in `{}`)", function.name),
+                function.span,
+            );
+        }
+
+        // impl 블록의 타입 매개변수에 함수 자신의 타입 매개변수를 더합니다.
+        let mut generics = self.generics.clone();
+        generics.extend(self.collect_bounds(&function.generics, function.span));
+        let old_generics = std::mem::replace(&mut self.generics, generics);
+        mut_visit::walk_fn(self, function);
+        self.generics = old_generics;
+        self.closure_count = old_count;
+        self.fn_path = old_path;
+    }
+
+    // 경로 첫 세그먼트의 `Self`를 impl 대상 타입의 세그먼트로 바꿉니다 (`Self::new`, `Self { x }`).
+    fn subst_self_segment(&self, path: &mut Path) {
+        if let (Some(first), Some(Ty { kind: TyKind::Path(self_path), .. })) = (path.segments.first_mut(), &self.self_ty) {
+            if first.ident == "Self" {
+                *first = self_path.segments[0].clone();
+            }
+        }
+    }
+
+    // 구조체 리터럴이나 패턴의 구조체가 있는지, 적은 필드가 모두 그 구조체의 필드인지 확인합니다.
+    fn check_struct_fields(&mut self, path: &Path, fields: &[&String], unknown: &str, span: Span) {
+        let name = path.to_string();
+        match self.structs.get(&name) {
+            Some(defs) => {
+                let unknown_fields: Vec<String> = fields.iter().filter(|field| !defs.contains(field)).map(|field| field.to_string()).collect();
+                for field in unknown_fields {
+                    self.error(format!("struct `{}` {} `{}`", name, unknown, field), span);
+                }
+            },
+            None => {
+                let name = path.segments.first().map_or(name, |segment| segment.ident.clone());
+                if !self.structs.contains_key(&name) {
+                    self.error(format!("cannot find struct `{}` in this scope", name), span);
+                }
+            },
+        }
+    }
+
+    // `self_ty`의 연관 함수 `name`을 찾아 호출 대상 경로를 돌려줍니다.
+    // 고유 impl에 있으면 `path` 그대로 두고, 없으면 구현된 트레이트(타입 매개변수라면 바운드)에서 찾습니다.
+    fn find_assoc(&mut self, path: &Path, self_ty: &Ty, name: &str, span: Span) -> Option<NodeKind> {
+        if let Some(bounds) = ty_name(self_ty).and_then(|base| self.generics.get(&base)) {
+            let bounds: Vec<String> = bounds.iter().map(trait_name).collect();
+            return self.find_trait_item(self_ty, &bounds, name, span);
+        }
+
+        let impls = self.impls.get(&impl_key(self_ty)).map(Vec::as_slice).unwrap_or_default();
+        let inherent = impls
+            .iter()
+            .filter(|imp| imp.of_trait.is_none())
+            .any(|imp| imp.items.iter().any(|decl| decl.name == name) && imp.matches(self_ty).is_some());
+        if inherent {
+            return Some(NodeKind::Path(path.clone()));
+        }
+
+        let traits: Vec<String> = impls
+            .iter()
+            .filter(|imp| imp.matches(self_ty).is_some())
+            .filter_map(|imp| imp.of_trait.clone())
+            .collect();
+        self.find_trait_item(self_ty, &traits, name, span)
+    }
+
+    // `traits` 중에서 `name` 메서드를 선언한 트레이트를 찾아 `<self_ty as Trait>::name`으로 해석합니다.
+    fn find_trait_item(&mut self, self_ty: &Ty, traits: &[String], name: &str, span: Span) -> Option<NodeKind> {
+        let candidates: Vec<&String> = traits
+            .iter()
+            .filter(|trait_name| self.traits.get(*trait_name).is_some_and(|decls| decls.iter().any(|decl| decl.name == name)))
+            .collect();
+
+        if candidates.len() > 1 {
+            let message = format!(
+                "multiple applicable items in scope: `{}` is defined in traits {}",
+                name,
+                candidates.iter().map(|t| format!("`{}`", t)).collect::<Vec<_>>().join(", ")
+            );
+            self.error(message, span);
+        }
+        let trait_name = candidates.first()?;
+        let qself = QSelf { ty: self_ty.clone(), trait_path: Path::from_ident(trait_name) };
+        Some(NodeKind::QualifiedPath(Box::new(qself), Path::from_ident(name)))
+    }
+
+    // `Self::new`의 `Self`를 치환하고, `Type::name`을 고유 경로나 트레이트 한정 경로로 해석합니다.
+    fn resolve_path(&mut self, node: &mut Node) {
+        let NodeKind::Path(path) = &mut node.kind else { unreachable!() };
+        self.subst_self_segment(path);
+
+        match path.segments.as_slice() {
+            [function] if !self.functions.contains(&function.ident) => {
+                self.error(format!("cannot find function `{}` in this scope", function.ident), node.span);
+            },
+            [ty, name] => {
+                let (ty_segment, name) = (ty.clone(), name.clone());
+                // 경로가 아닌 타입(`(i32, i32)`)의 impl 안에서 남은 `Self`는 그 타입입니다.
+                let ty = match (&self.self_ty, ty_segment.ident.as_str()) {
+                    (Some(self_ty), "Self") => self_ty.clone(),
+                    _ => Ty::from(TyKind::Path(Path { segments: vec![ty_segment.clone()] })),
+                };
+                let path = path.clone();
+                match self.find_assoc(&path, &ty, &name.ident, node.span) {
+                    Some(mut callee) => {
+                        // 함수 이름 뒤의 터보피시는 해석된 경로로 옮깁니다.
+                        if let NodeKind::QualifiedPath(_, path) = &mut callee {
+                            path.segments.last_mut().unwrap().args = name.args;
+                        }
+                        node.kind = callee;
+                    },
+                    None => {
+                        let kind = if self.structs.contains_key(&ty_segment.ident) {
+                            "struct"
+                        } else if self.generics.contains_key(&ty_segment.ident) {
+                            "type parameter"
+                        } else {
+                            "type"
+                        };
+                        self.error(
+                            format!(
+                                "no function or associated item named `{}` found for {} `{}` in the current scope",
+                                name.ident, kind, ty_segment.ident
+                            ),
+                            node.span,
+                        );
+                    },
+                }
+            },
+            _ => {},
+        }
+    }
+
+    // 사용자가 직접 쓴 `<Type as Trait>::item` 경로의 트레이트와 항목이 있는지 확인합니다.
+    // 대상 타입이 트레이트를 구현하는지는 타입 검사에서 확인합니다.
+    fn resolve_qualified_path(&mut self, qself: &QSelf, path: &Path, span: Span) {
+        let trait_name = qself.trait_path.to_string();
+        let Some(decls) = self.traits.get(&trait_name) else {
+            self.error(format!("cannot find trait `{}` in this scope", trait_name), span);
+            return;
+        };
+        if !decls.iter().any(|decl| Some(decl.name.as_str()) == path.as_ident()) {
+            self.error(format!("cannot find method or associated item `{}` in trait `{}`", path, trait_name), span);
+        }
+    }
+}
+
+impl MutVisitor for MethodResolver {
+    fn arena(&mut self) -> &mut AstArena {
+        &mut self.arena
+    }
+
+    // 안쪽 아이템은 바깥 impl의 `Self`와 타입 매개변수를 볼 수 없습니다.
+    // 트레이트의 기본 메서드 본문은 impl 블록마다 복사된 뒤에 해석합니다.
+    fn visit_item(&mut self, item: &mut Item) {
+        let self_ty = self.self_ty.take();
+        let generics = std::mem::take(&mut self.generics);
+        match item {
+            Item::Fn(function) => {
+                let path = function.name.clone();
+                self.resolve_fn(function, path);
+            },
+            Item::Impl(imp) => self.resolve_impl(imp),
+            Item::Trait(_) => {},
+            _ => mut_visit::walk_item(self, item),
+        }
+        self.generics = generics;
+        self.self_ty = self_ty;
+    }
+
+    fn visit_pat(&mut self, pat: &mut Pat) {
+        if let PatKind::Struct { path, fields, .. } = &mut pat.kind {
+            self.subst_self_segment(path);
+            let names: Vec<&String> = fields.iter().map(|(field, _)| field).collect();
+            self.check_struct_fields(path, &names, "does not have a field named", pat.span);
+        }
+        mut_visit::walk_pat(self, pat);
+    }
+
+    fn visit_ty(&mut self, ty: &mut Ty) {
+        if let Some(self_ty) = &self.self_ty {
+            subst_self(ty, self_ty);
+        }
+        mut_visit::walk_ty(self, ty);
+    }
+
+    fn visit_expr(&mut self, node: &mut Node) {
+        match &mut node.kind {
+            // 클로저 이름은 바깥 클로저가 먼저 번호를 받도록 본문보다 먼저 붙입니다.
+            NodeKind::Closure(closure) => {
+                closure.name = format!("{}::{{closure#{}}}", self.fn_path, self.closure_count);
+                self.closure_count += 1;
+            },
+            NodeKind::Path(_) => self.resolve_path(node),
+            NodeKind::StructLit(lit) => {
+                self.subst_self_segment(&mut lit.path);
+                let fields: Vec<&String> = lit.fields.iter().map(|(field, _)| field).collect();
+                self.check_struct_fields(&lit.path, &fields, "has no field named", node.span);
+            },
+            _ => {},
+        }
+        mut_visit::walk_expr(self, node);
+        if let NodeKind::QualifiedPath(qself, path) = &node.kind {
+            self.resolve_qualified_path(qself, path, node.span);
+        }
+    }
+}
+
+// 경로 타입의 이름입니다. 제네릭 인자는 무시합니다(`Pair<i32>` -> `Pair`).
+fn ty_name(ty: &Ty) -> Option<String> {
+    match &ty.kind {
+        TyKind::Path(path) => path.as_ident().map(str::to_string),
+        _ => None,
+    }
+}
+
+// impl 블록을 모을 때 쓰는 대상 타입의 이름입니다. 경로 타입은 이름으로(`Pair<T>` -> `Pair`), 나머지는
+// 타입의 모양으로(`(i32, i32)` -> `(_, _)`) 묶고, 실제로 맞는지는 `ImplInfo::matches`로 확인합니다.
+fn impl_key(ty: &Ty) -> String {
+    match &ty.kind {
+        TyKind::Path(path) => trait_name(path),
+        TyKind::Ref { mutable: false, .. } => "&_".to_string(),
+        TyKind::Ref { mutable: true, .. } => "&mut _".to_string(),
+        TyKind::Array(..) => "[_; _]".to_string(),
+        TyKind::Slice(_) => "[_]".to_string(),
+        TyKind::Tuple(tys) => format!("({})", vec!["_"; tys.len()].join(", ")),
+        TyKind::Unit => "()".to_string(),
+    }
+}
+
+// 트레이트 바운드의 이름입니다. 클로저 트레이트의 인자는 무시합니다(`Fn(i32) -> i32` -> `Fn`).
+fn trait_name(bound: &Path) -> String {
+    bound.segments.iter().map(|segment| segment.ident.as_str()).collect::<Vec<_>>().join("::")
+}
+
+// 타입 안의 `Self`를 `self_ty`로 치환합니다.
+fn subst_self(ty: &mut Ty, self_ty: &Ty) {
+    let subst = HashMap::from([("Self".to_string(), self_ty.clone())]);
+    substitute(ty, &subst);
+}
+
+// 타입 안의 이름들을 `subst`에 따라 치환합니다. 제네릭 인자 안쪽까지 치환합니다.
+pub(crate) fn substitute(ty: &mut Ty, subst: &HashMap<String, Ty>) {
+    match &mut ty.kind {
+        TyKind::Path(path) => {
+            if let [segment] = path.segments.as_slice() {
+                if let Some(replacement) = subst.get(&segment.ident).filter(|_| segment.args.is_empty()) {
+                    *ty = replacement.clone();
+                    return;
+                }
+            }
+            for segment in &mut path.segments {
+                segment.args.iter_mut().for_each(|arg| substitute(arg, subst));
+            }
+        },
+        TyKind::Ref { ty, .. } | TyKind::Array(ty, _) | TyKind::Slice(ty) => substitute(ty, subst),
+        TyKind::Tuple(tys) => tys.iter_mut().for_each(|ty| substitute(ty, subst)),
+        TyKind::Unit => {},
+    }
+}
+
+// 매개변수 타입 `param`과 실제 타입 `arg`를 맞춰 보며 타입 매개변수(`names`)의 값을 `subst`에 기록합니다.
+// 두 타입이 어긋나면 false를 돌려줍니다.
+pub(crate) fn unify(param: &Ty, arg: &Ty, names: &[&str], subst: &mut HashMap<String, Ty>) -> bool {
+    match (&param.kind, &arg.kind) {
+        (TyKind::Path(path), _) if path.segments.len() == 1
+            && path.segments[0].args.is_empty()
+            && names.contains(&path.segments[0].ident.as_str()) =>
+        {
+            let name = &path.segments[0].ident;
+            match subst.get(name) {
+                Some(existing) => existing == arg,
+                None => {
+                    subst.insert(name.clone(), arg.clone());
+                    true
+                },
+            }
+        },
+        (TyKind::Path(param), TyKind::Path(arg)) => {
+            param.segments.len() == arg.segments.len()
+                && param.segments.iter().zip(&arg.segments).all(|(param, arg)| {
+                    param.ident == arg.ident
+                        && (param.args.is_empty()
+                            || arg.args.is_empty()
+                            || (param.args.len() == arg.args.len()
+                                && param.args.iter().zip(&arg.args).all(|(p, a)| unify(p, a, names, subst))))
+                })
+        },
+        (TyKind::Ref { ty: param, .. }, TyKind::Ref { ty: arg, .. }) => unify(param, arg, names, subst),
+        (TyKind::Array(param, n), TyKind::Array(arg, m)) => n == m && unify(param, arg, names, subst),
+        (TyKind::Slice(param), TyKind::Slice(arg)) => unify(param, arg, names, subst),
+        (TyKind::Tuple(params), TyKind::Tuple(args)) => {
+            params.len() == args.len() && params.iter().zip(args).all(|(param, arg)| unify(param, arg, names, subst))
+        },
+        (TyKind::Unit, TyKind::Unit) => true,
+        _ => false,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusttc_ast::pretty::stmt_to_string;
+    use rusttc_lexer::{source_map, tokenize};
+    use rusttc_parse::parser::parse_crate;
+
+    const POINT: &str = "
+        struct Point { x: i32 }
+        impl Point {
+            fn new(x: i32) -> Self { Self { x } }
+            fn len(&self) -> i32 { self.x }
+        }
+    ";
+
+    const SHAPES: &str = "
+        struct Circle { r: i32 }
+        struct Square { side: i32 }
+        trait Shape {
+            fn area(&self) -> i32;
+            fn describe(&self) -> i32 { Self::area(self) + 1 }
+        }
+        impl Shape for Circle {
+            fn area(&self) -> i32 { 3 * self.r * self.r }
+        }
+    ";
+
+    fn resolve(source: &str) -> Result<Crate, Vec<MethodError>> {
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate)?;
+        Ok(krate)
+    }
+
+    fn messages(source: &str) -> Vec<String> {
+        resolve(source).unwrap_err().into_iter().map(|error| error.message).collect()
+    }
+
+    // 블록의 `i`번째 문장을 소스로 출력합니다.
+    fn stmt(krate: &Crate, function: &FnDef, i: usize) -> String {
+        stmt_to_string(&krate.arena, function.body.as_ref().unwrap().stmts[i])
+    }
+
+    fn find_fn<'a>(krate: &'a Crate, name: &str) -> &'a FnDef {
+        krate
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Item::Fn(function) if function.name == name => Some(function),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_self_is_replaced_inside_impl() {
+        let krate = resolve(POINT).unwrap();
+        let Item::Impl(imp) = &krate.items[1] else { panic!() };
+        let new = &imp.items[0];
+
+        assert_eq!(new.ret, Some(Ty::from_ident("Point")));
+        let StmtKind::Expr(lit) = &krate.arena[new.body.as_ref().unwrap().stmts[0]].kind else { panic!() };
+        let NodeKind::StructLit(lit) = &krate.arena[*lit].kind else { panic!() };
+        assert_eq!(lit.path, Path::from_ident("Point"));
+    }
+
+    #[test]
+    fn test_associated_paths_are_resolved() {
+        let source = format!("{} fn main() {{ let p = Point::new(1); Point::len(&p); }} fn show<T: Shape>(s: &T) -> i32 {{ T::area(s) }}", SHAPES.replace("struct Square { side: i32 }", POINT));
+        let krate = resolve(&source).unwrap();
+        // 고유 메서드는 적은 경로 그대로, 트레이트 메서드는 트레이트 한정 경로가 됩니다.
+        assert_eq!(stmt(&krate, find_fn(&krate, "main"), 1), "Point::len(&p);");
+        assert_eq!(stmt(&krate, find_fn(&krate, "show"), 0), "<T as Shape>::area(s)");
+    }
+
+    #[test]
+    fn test_method_calls_are_left_for_type_checking() {
+        let source = format!("{} fn main() {{ let p = Point::new(1); p.len(); p.area(); }}", POINT);
+        let krate = resolve(&source).unwrap();
+        assert_eq!(stmt(&krate, find_fn(&krate, "main"), 2), "p.area();");
+    }
+
+    #[test]
+    fn test_unknown_associated_item_is_an_error() {
+        let source = format!("{} fn main() {{ Point::origin(); missing::<i32>(); }}", POINT);
+        assert_eq!(messages(&source), [
+            "no function or associated item named `origin` found for struct `Point` in the current scope",
+            "cannot find function `missing` in this scope",
+        ]);
+    }
+
+    #[test]
+    fn test_default_method_is_copied_into_impl() {
+        let krate = resolve(SHAPES).unwrap();
+        let Item::Impl(imp) = &krate.items[3] else { panic!() };
+        let names: Vec<&str> = imp.items.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["area", "describe"]);
+
+        // 기본 메서드 본문의 `Self::area`도 구현 타입의 메서드로 해석됩니다.
+        assert_eq!(stmt(&krate, &imp.items[1], 0), "<Circle as Shape>::area(self) + 1");
+
+        // 복사한 본문은 트레이트 선언의 본문과 따로 고쳐집니다.
+        let Item::Trait(def) = &krate.items[2] else { panic!() };
+        assert_eq!(stmt(&krate, &def.items[1], 0), "Self::area(self) + 1");
+    }
+
+    #[test]
+    fn test_incomplete_trait_impl() {
+        let source = format!("{} impl Shape for Square {{ fn perimeter(&self) -> i32 {{ 4 }} }}", SHAPES);
+        assert_eq!(messages(&source), [
+            "method `perimeter` is not a member of trait `Shape`",
+            "not all trait items implemented, missing: `area`",
+        ]);
+    }
+
+    #[test]
+    fn test_trait_signature_mismatch() {
+        let source = "
+            struct Circle { r: i32 }
+            trait Shape { fn area(&self) -> i32; fn scale(&mut self, by: i32); fn name() -> bool; }
+            impl Shape for Circle {
+                fn area(&self) -> bool { true }
+                fn scale(&self, by: i32) {}
+                fn name(&self) -> bool { true }
+            }
+        ";
+        assert_eq!(messages(source), [
+            "method `area` has an incompatible type for trait: expected `i32`, found `bool`",
+            "method `scale` has an incompatible receiver for trait: expected `&mut self`, found `&self`",
+            "method `name` has a `&self` declaration in the impl, but not in the trait",
+        ]);
+
+        let source = "
+            struct Circle { r: i32 }
+            trait Shape { fn scale(&mut self, by: i32); fn new(r: i32) -> Self; }
+            impl Shape for Circle {
+                fn scale(&mut self) {}
+                fn new(r: i32, s: i32) -> Self { Circle { r } }
+            }
+        ";
+        assert_eq!(messages(source), [
+            "method `scale` has 1 parameter but the declaration in trait `Shape::scale` has 2",
+            "method `new` has 2 parameters but the declaration in trait `Shape::new` has 1",
+        ]);
+    }
+
+    #[test]
+    fn test_trait_impls_for_primitives_and_tuples() {
+        let source = "
+            trait Double { fn double(&self) -> i32; fn twice(&self) -> i32 { Self::double(self) * 2 } }
+            impl Double for i32 { fn double(&self) -> i32 { *self * 2 } }
+            impl Double for (i32, bool) { fn double(&self) -> i32 { self.0 } }
+        ";
+        let krate = resolve(source).unwrap();
+        let Item::Impl(imp) = &krate.items[2] else { panic!() };
+        assert_eq!(stmt(&krate, &imp.items[1], 0), "<(i32, bool) as Double>::double(self) * 2");
+
+        let source = "
+            trait Double { fn double(&self) -> i32; }
+            impl Double for (i32, Missing) { fn double(&self) -> i32 { 0 } }
+            impl Double for i32 { fn double(&self) -> i32 { 0 } }
+            impl Double for i32 { fn double(&self) -> i32 { 1 } }
+            impl i32 { fn double(&self) -> i32 { 0 } }
+        ";
+        assert_eq!(messages(source), [
+            "cannot find type `Missing` in this scope",
+            "conflicting implementations of trait `Double` for type `i32`",
+            "cannot define inherent `impl` for primitive types",
+        ]);
+    }
+
+    #[test]
+    fn test_closures_are_named_in_order() {
+        let krate = resolve("fn main() { let f = |x: i32| x; let g = || { let h = || 1; h }; }").unwrap();
+        let main = find_fn(&krate, "main");
+        let names: Vec<&str> = main
+            .body
+            .as_ref()
+            .unwrap()
+            .stmts
+            .iter()
+            .filter_map(|&stmt| match &krate.arena[stmt].kind {
+                StmtKind::Let(local) => match &krate.arena[local.init?].kind {
+                    NodeKind::Closure(closure) => Some(closure.name.as_str()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, ["main::{closure#0}", "main::{closure#1}"]);
+    }
+
+    #[test]
+    fn test_errors_point_at_the_offending_item() {
+        let source = "struct P {}\nimpl P {\n    fn a() {}\n    fn a() {}\n}\nfn main() { P::b(); }\n";
+        source_map::clear();
+        source_map::add_file("main.rs", source);
+        let errors: Vec<String> = resolve(source).unwrap_err().iter().map(MethodError::to_string).collect();
+        assert_eq!(errors, [
+            "error: duplicate definitions with name `a`\n  --> main.rs:4:5",
+            "error: no function or associated item named `b` found for struct `P` in the current scope\n  --> main.rs:6:13",
+        ]);
+        source_map::clear();
+    }
+}
@@ -0,0 +1,151 @@
+use rusttc_ast::ast::{FnDef, Impl, Path, SelfKind, Ty, TyKind};
+
+use super::{impl_key, subst_self, ImplInfo, MethodResolver};
+
+impl MethodResolver {
+    // `impl<T> Trait for Type<T>`을 등록합니다.
+    pub(super) fn register_trait_impl(&mut self, trait_path: &Path, imp: &Impl) {
+        let (generics, self_ty) = (&imp.generics, &imp.self_ty);
+        let trait_name = trait_path.to_string();
+        if !self.traits.contains_key(&trait_name) {
+            self.error(format!("cannot find trait `{}` in this scope", trait_name), imp.span);
+            return;
+        }
+
+        let info = ImplInfo {
+            generics: generics.clone(),
+            of_trait: Some(trait_name.clone()),
+            self_ty: self_ty.clone(),
+            items: Vec::new(),
+        };
+        let impls = self.impls.entry(impl_key(self_ty)).or_default();
+        // 두 impl의 대상 타입이 겹치면(`Pair<T>`와 `Pair<i32>`) 충돌입니다.
+        let overlaps = impls.iter().any(|imp| {
+            imp.of_trait.as_deref() == Some(trait_name.as_str())
+                && (imp.matches(self_ty).is_some() || info.matches(&imp.self_ty).is_some())
+        });
+        if overlaps {
+            self.error(format!("conflicting implementations of trait `{}` for type `{}`", trait_name, self_ty), imp.span);
+            return;
+        }
+        impls.push(info);
+    }
+
+    // 트레이트 구현이 완전한지, 각 메서드의 시그니처가 트레이트 선언과 일치하는지 확인합니다.
+    // 구현되지 않은 기본 메서드는 impl 블록으로 복사해 구현 타입마다 정적으로 디스패치되게 합니다.
+    pub(super) fn check_trait_impl(&mut self, trait_path: &Path, imp: &mut Impl) {
+        let (self_ty, items) = (&imp.self_ty, &mut imp.items);
+        let trait_name = trait_path.to_string();
+        let Some(decls) = self.traits.get(&trait_name).cloned() else { return };
+
+        for function in items.iter() {
+            match decls.iter().find(|decl| decl.name == function.name) {
+                Some(decl) => self.compare_signatures(decl, function, &trait_name, self_ty),
+                None => self.error(
+                    format!("method `{}` is not a member of trait `{}`", function.name, trait_name),
+                    function.span,
+                ),
+            }
+        }
+
+        let mut missing = Vec::new();
+        for decl in &decls {
+            if items.iter().any(|function| function.name == decl.name) {
+                continue;
+            }
+            match decl.body {
+                Some(_) => items.push(self.arena.copy_fn(decl)),
+                None => missing.push(format!("`{}`", decl.name)),
+            }
+        }
+        if !missing.is_empty() {
+            self.error(format!("not all trait items implemented, missing: {}", missing.join(", ")), imp.span);
+        }
+    }
+
+    fn compare_signatures(&mut self, decl: &FnDef, function: &FnDef, trait_name: &str, self_ty: &Ty) {
+        let name = &function.name;
+        match (decl.self_param, function.self_param) {
+            (Some(expected), Some(found)) if !same_receiver(expected, found) => {
+                self.error(format!(
+                    "method `{}` has an incompatible receiver for trait: expected `{}`, found `{}`",
+                    name, receiver(expected), receiver(found)
+                ), function.span);
+                return;
+            },
+            (Some(expected), None) => {
+                self.error(format!(
+                    "method `{}` has a `{}` declaration in the trait, but not in the impl",
+                    name, receiver(expected)
+                ), function.span);
+                return;
+            },
+            (None, Some(found)) => {
+                self.error(format!(
+                    "method `{}` has a `{}` declaration in the impl, but not in the trait",
+                    name, receiver(found)
+                ), function.span);
+                return;
+            },
+            _ => {},
+        }
+
+        // rustc(E0050)처럼 `self`도 매개변수로 셉니다. 여기까지 왔으면 두 쪽 모두 `self`가 있거나 모두 없습니다.
+        let (expected, found) = (arity(decl), arity(function));
+        if expected != found {
+            self.error(format!(
+                "method `{}` has {} parameter{} but the declaration in trait `{}::{}` has {}",
+                name,
+                found,
+                if found == 1 { "" } else { "s" },
+                trait_name,
+                name,
+                expected
+            ), function.span);
+            return;
+        }
+
+        let expected_tys = decl.params.iter().map(|param| Some(&param.ty)).chain([decl.ret.as_ref()]);
+        let found_tys = function.params.iter().map(|param| Some(&param.ty)).chain([function.ret.as_ref()]);
+        for (expected, found) in expected_tys.zip(found_tys) {
+            let expected = normalize(expected, self_ty);
+            let found = normalize(found, self_ty);
+            if expected != found {
+                self.error(format!(
+                    "method `{}` has an incompatible type for trait: expected `{}`, found `{}`",
+                    name, expected, found
+                ), function.span);
+                return;
+            }
+        }
+    }
+}
+
+// `self`를 포함한 매개변수 수입니다.
+fn arity(function: &FnDef) -> usize {
+    function.params.len() + usize::from(function.self_param.is_some())
+}
+
+// 반환 타입 생략은 `()`로, `Self`는 구현 대상 타입으로 바꿔 비교할 수 있게 합니다.
+fn normalize(ty: Option<&Ty>, self_ty: &Ty) -> Ty {
+    let mut ty = ty.cloned().unwrap_or(Ty::from(TyKind::Unit));
+    subst_self(&mut ty, self_ty);
+    ty
+}
+
+fn same_receiver(expected: SelfKind, found: SelfKind) -> bool {
+    matches!(
+        (expected, found),
+        (SelfKind::Value { .. }, SelfKind::Value { .. })
+            | (SelfKind::Ref, SelfKind::Ref)
+            | (SelfKind::RefMut, SelfKind::RefMut)
+    )
+}
+
+fn receiver(kind: SelfKind) -> &'static str {
+    match kind {
+        SelfKind::Value { .. } => "self",
+        SelfKind::Ref => "&self",
+        SelfKind::RefMut => "&mut self",
+    }
+}
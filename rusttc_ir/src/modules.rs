@@ -0,0 +1,851 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use rusttc_ast::ast::{
+    AstArena, Block, Crate, ExprId, FnDef, Generics, Item, Node, NodeKind, Pat, PatKind, Path, PathSegment, StmtKind, Ty, TyKind, UseTree, UseTreeKind, Visibility,
+};
+use rusttc_lexer::Span;
+
+// 모듈 해석 중에 발생한 오류입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleError {
+    pub message: String,
+    pub span: Span,             // 오류를 낸 아이템, 문장, 표현식의 위치
+}
+
+impl fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)?;
+        if let Some(location) = self.span.location() {
+            write!(f, "\n  --> {}", location)?;
+        }
+        Ok(())
+    }
+}
+
+// 글롭 가져오기가 이보다 깊게 이어지면 순환으로 보고 찾기를 멈춥니다.
+const GLOB_DEPTH_LIMIT: usize = 32;
+
+type DefId = usize;
+type ModuleId = usize;
+
+const ROOT: ModuleId = 0;
+
+#[derive(Clone, Copy, PartialEq)]
+enum DefKind {
+    Fn,
+    Struct,
+    Trait,
+    Const,
+    Static,
+    Mod(ModuleId),
+}
+
+impl DefKind {
+    fn descr(self) -> &'static str {
+        match self {
+            DefKind::Fn => "function",
+            DefKind::Struct => "struct",
+            DefKind::Trait => "trait",
+            DefKind::Const => "constant",
+            DefKind::Static => "static",
+            DefKind::Mod(_) => "module",
+        }
+    }
+}
+
+// 모듈 트리의 아이템입니다.
+struct Def {
+    kind: DefKind,
+    name: String,               // 크레이트 루트부터의 전체 이름 (`shapes::Point`)
+}
+
+// 모듈 안에서 이름이 가리키는 아이템과 그 이름의 가시성입니다.
+#[derive(Clone, Copy)]
+struct Binding {
+    def: DefId,
+    vis: Visibility,
+}
+
+struct ModuleData {
+    def: DefId,                             // 모듈 자신을 가리키는 아이템
+    parent: Option<ModuleId>,               // 상위 모듈 (루트는 None)
+    path: String,                           // 루트부터의 경로 (루트는 빈 문자열)
+    names: HashMap<String, Binding>,        // 정의되었거나 가져온 이름
+    globs: Vec<(ModuleId, Visibility)>,     // `use a::*`로 가져온 모듈
+}
+
+impl ModuleData {
+    // 모듈 안 아이템의 전체 이름에 붙는 접두사입니다. 루트의 아이템은 이름이 그대로입니다.
+    fn prefix(&self) -> String {
+        if self.path.is_empty() {
+            String::new()
+        } else {
+            format!("{}::", self.path)
+        }
+    }
+}
+
+// 아직 풀지 못한 `use` 선언의 한 갈래입니다.
+struct Import {
+    module: ModuleId,           // 선언된 모듈
+    path: Vec<String>,          // 가져올 경로
+    name: Option<String>,       // 가져온 이름 (글롭이면 None)
+    vis: Visibility,
+    span: Span,                 // `use` 선언의 위치
+}
+
+// 경로를 앞에서부터 따라간 결과입니다.
+enum PathRes {
+    Def(DefId, usize),          // 앞의 세그먼트 몇 개가 가리키는 아이템
+    NotInScope,                 // 첫 세그먼트가 모듈 아이템이 아님 (지역 변수, 기본 타입, 타입 매개변수 등)
+    NotFound(String),           // 모듈 안에 없는 이름
+}
+
+// 모듈 트리를 최상위 아이템 목록 하나로 펼칩니다.
+// 모듈 안의 아이템은 `shapes::Point`처럼 전체 경로를 이름으로 갖게 되고,
+// 아이템을 가리키는 경로는 `use`와 `self`, `super`, `crate`를 따라가 그 전체 이름 하나로 바뀝니다.
+// `shapes::Point::new`처럼 아이템 뒤에 남은 세그먼트는 그대로 두어 메서드 해석에 넘깁니다.
+// 비공개 아이템을 선언된 모듈 밖에서 쓰면 오류입니다.
+// `mod foo;`는 드라이버의 로더가 파일을 읽어 채운 상태여야 하고, `method::resolve_methods`보다 먼저 실행되어야 합니다.
+pub fn resolve_modules(krate: &Crate) -> Result<Crate, Vec<ModuleError>> {
+    let mut resolver = ModuleResolver { arena: krate.arena.clone(), ..ModuleResolver::default() };
+    resolver.new_module(None, String::new());
+    resolver.build(&krate.items, ROOT);
+    resolver.resolve_imports();
+
+    let mut items = Vec::new();
+    resolver.flatten(&krate.items, ROOT, &mut items);
+
+    if resolver.errors.is_empty() {
+        Ok(Crate { attrs: krate.attrs.clone(), items, arena: resolver.arena })
+    } else {
+        Err(resolver.errors)
+    }
+}
+
+#[derive(Default)]
+struct ModuleResolver {
+    defs: Vec<Def>,
+    modules: Vec<ModuleData>,
+    imports: Vec<Import>,                           // 아직 풀지 못한 가져오기
+    scopes: Vec<HashMap<String, Option<String>>>,   // 지역 이름 (None은 변수와 타입 매개변수, Some은 블록 안 아이템의 전체 이름)
+    module: ModuleId,                               // 현재 모듈
+    arena: AstArena,                                // 펼친 크레이트의 표현식과 문장
+    span: Span,                                     // 지금 보고 있는 아이템, 문장, 표현식의 위치
+    errors: Vec<ModuleError>,
+}
+
+impl ModuleResolver {
+    fn error(&mut self, message: String) {
+        self.errors.push(ModuleError { message, span: self.span });
+    }
+
+    // 위치를 `span`으로 바꾼 채로 `f`를 실행합니다. 컴파일러가 만든 노드는 바깥 위치를 그대로 씁니다.
+    fn with_span<R>(&mut self, span: Span, f: impl FnOnce(&mut Self) -> R) -> R {
+        if span.is_dummy() {
+            return f(self);
+        }
+        let outer = std::mem::replace(&mut self.span, span);
+        let result = f(self);
+        self.span = outer;
+        result
+    }
+
+    fn new_module(&mut self, parent: Option<ModuleId>, path: String) -> ModuleId {
+        let id = self.modules.len();
+        let name = if path.is_empty() { "crate".to_string() } else { path.clone() };
+        self.defs.push(Def { kind: DefKind::Mod(id), name });
+        self.modules.push(ModuleData {
+            def: self.defs.len() - 1,
+            parent,
+            path,
+            names: HashMap::new(),
+            globs: Vec::new(),
+        });
+        id
+    }
+
+    fn define(&mut self, module: ModuleId, name: &str, binding: Binding) {
+        if self.modules[module].names.insert(name.to_string(), binding).is_some() {
+            self.error(format!("the name `{}` is defined multiple times", name));
+        }
+    }
+
+    fn define_item(&mut self, module: ModuleId, name: &str, kind: DefKind, vis: Visibility) {
+        let full_name = format!("{}{}", self.modules[module].prefix(), name);
+        self.defs.push(Def { kind, name: full_name });
+        self.define(module, name, Binding { def: self.defs.len() - 1, vis });
+    }
+
+    // 모듈 트리를 만들고 `use` 선언을 모읍니다.
+    fn build(&mut self, items: &[Item], module: ModuleId) {
+        for item in items {
+            self.span = item.span();
+            match item {
+                Item::Fn(function) => self.define_item(module, &function.name, DefKind::Fn, function.vis),
+                Item::Struct(def) => self.define_item(module, &def.name, DefKind::Struct, def.vis),
+                Item::Trait(def) => self.define_item(module, &def.name, DefKind::Trait, def.vis),
+                Item::Const(def) => self.define_item(module, &def.name, DefKind::Const, def.vis),
+                Item::Static(def) => self.define_item(module, &def.name, DefKind::Static, def.vis),
+                Item::Impl(_) => {},
+                Item::Mod(def) => {
+                    let Some(items) = &def.items else {
+                        self.error(format!("file not found for module `{}`", def.name));
+                        continue;
+                    };
+                    let path = format!("{}{}", self.modules[module].prefix(), def.name);
+                    let child = self.new_module(Some(module), path);
+                    let binding = Binding { def: self.modules[child].def, vis: def.vis };
+                    self.define(module, &def.name, binding);
+                    self.build(items, child);
+                },
+                Item::Use(decl) => {
+                    for (path, name) in use_leaves(&decl.tree) {
+                        self.imports.push(Import { module, path, name, vis: decl.vis, span: decl.span });
+                    }
+                },
+            }
+        }
+    }
+
+    // 가져오기가 다른 가져오기에 기대는 경우가 있으므로, 더 풀리는 것이 없을 때까지 반복합니다.
+    fn resolve_imports(&mut self) {
+        loop {
+            let pending = std::mem::take(&mut self.imports);
+            let count = pending.len();
+            for import in pending {
+                self.span = import.span;
+                match self.resolve_path(import.module, &import.path) {
+                    Ok(PathRes::Def(def, len)) if len == import.path.len() => self.add_import(&import, def),
+                    Ok(_) => self.imports.push(import),
+                    Err(message) => self.error(message),
+                }
+            }
+            if self.imports.len() == count {
+                break;
+            }
+        }
+
+        for import in std::mem::take(&mut self.imports) {
+            self.span = import.span;
+            self.error(format!("unresolved import `{}`", import.path.join("::")));
+        }
+    }
+
+    fn add_import(&mut self, import: &Import, def: DefId) {
+        let binding = Binding { def, vis: import.vis };
+        match (&import.name, self.defs[def].kind) {
+            (Some(name), _) => self.define(import.module, name, binding),
+            (None, DefKind::Mod(target)) => self.modules[import.module].globs.push((target, import.vis)),
+            (None, kind) => self.error(format!(
+                "expected module, found {} `{}`",
+                kind.descr(),
+                import.path.join("::")
+            )),
+        }
+    }
+
+    // 모듈 안에서 `name`을 찾습니다. 직접 정의하거나 가져온 이름이 글롭보다 우선합니다.
+    // 글롭으로는 가져오는 모듈에서 보이는 이름만 들어오고, 여러 글롭이 서로 다른 아이템을 들여오면 모호하다는 오류입니다.
+    fn lookup(&self, module: ModuleId, name: &str, depth: usize) -> Result<Option<Binding>, String> {
+        if let Some(binding) = self.modules[module].names.get(name) {
+            return Ok(Some(*binding));
+        }
+        if depth >= GLOB_DEPTH_LIMIT {
+            return Ok(None);
+        }
+        let mut found: Option<Binding> = None;
+        for &(target, vis) in &self.modules[module].globs {
+            let Some(binding) = self.lookup(target, name, depth + 1)? else { continue };
+            if !self.is_visible(binding.vis, target, module) {
+                continue;
+            }
+            match found {
+                Some(other) if other.def != binding.def => return Err(format!("`{}` is ambiguous", name)),
+                Some(_) => {},                                  // 같은 아이템을 여러 경로로 가져온 경우
+                None => found = Some(Binding { def: binding.def, vis }),
+            }
+        }
+        Ok(found)
+    }
+
+    // 비공개 이름은 선언된 모듈과 그 하위 모듈에서만 보입니다.
+    fn is_visible(&self, vis: Visibility, owner: ModuleId, from: ModuleId) -> bool {
+        let mut module = Some(from);
+        while let Some(id) = module {
+            if id == owner {
+                return true;
+            }
+            module = self.modules[id].parent;
+        }
+        vis == Visibility::Public
+    }
+
+    // 경로를 `from` 모듈에서 시작해 따라갑니다.
+    // `crate`는 루트, `self`는 현재 모듈, `super`는 상위 모듈에서 시작하고,
+    // 그 밖의 첫 세그먼트는 현재 모듈의 이름에서 찾습니다.
+    fn resolve_path(&self, from: ModuleId, segments: &[String]) -> Result<PathRes, String> {
+        let mut index = 0;
+        let mut module = None;
+        match segments[0].as_str() {
+            "crate" => {
+                module = Some(ROOT);
+                index = 1;
+            },
+            "self" if segments.len() > 1 => {
+                module = Some(from);
+                index = 1;
+            },
+            _ => {},
+        }
+        while segments.get(index).map(String::as_str) == Some("super") {
+            let current = module.unwrap_or(from);
+            let Some(parent) = self.modules[current].parent else {
+                return Err("there are too many leading `super` keywords".to_string());
+            };
+            module = Some(parent);
+            index += 1;
+        }
+
+        let mut def = match module {
+            Some(module) => self.modules[module].def,
+            None => match self.lookup(from, &segments[0], 0)? {
+                Some(binding) => {
+                    index = 1;
+                    binding.def
+                },
+                None => return Ok(PathRes::NotInScope),
+            },
+        };
+
+        while let Some(name) = segments.get(index) {
+            let DefKind::Mod(module) = self.defs[def].kind else { break };
+            let Some(binding) = self.lookup(module, name, 0)? else {
+                return Ok(PathRes::NotFound(format!("cannot find `{}` in `{}`", name, self.defs[def].name)));
+            };
+            if !self.is_visible(binding.vis, module, from) {
+                return Err(format!("{} `{}` is private", self.defs[binding.def].kind.descr(), name));
+            }
+            def = binding.def;
+            index += 1;
+        }
+        Ok(PathRes::Def(def, index))
+    }
+
+    // 아이템을 전체 이름으로 바꾸어 `out`에 펼쳐 넣습니다. 하위 모듈의 아이템은 그 자리에 들어갑니다.
+    fn flatten(&mut self, items: &[Item], module: ModuleId, out: &mut Vec<Item>) {
+        let old = std::mem::replace(&mut self.module, module);
+        let prefix = self.modules[module].prefix();
+        for item in items {
+            match item {
+                Item::Mod(def) => {
+                    let (Some(items), Some(binding)) = (&def.items, self.modules[module].names.get(&def.name)) else {
+                        continue;
+                    };
+                    if let DefKind::Mod(child) = self.defs[binding.def].kind {
+                        self.flatten(items, child, out);
+                    }
+                },
+                Item::Use(_) => {},
+                _ => {
+                    let mut item = item.clone();
+                    self.span = item.span();
+                    self.resolve_item(&mut item, &prefix);
+                    out.push(item);
+                },
+            }
+        }
+        self.module = old;
+    }
+
+    fn resolve_item(&mut self, item: &mut Item, prefix: &str) {
+        match item {
+            Item::Fn(function) => {
+                function.name = format!("{}{}", prefix, function.name);
+                self.resolve_fn(function);
+            },
+            Item::Struct(def) => {
+                def.name = format!("{}{}", prefix, def.name);
+                self.push_generics(&mut def.generics);
+                for field in &mut def.fields {
+                    self.resolve_ty(&mut field.ty);
+                }
+                self.scopes.pop();
+            },
+            Item::Trait(def) => {
+                def.name = format!("{}{}", prefix, def.name);
+                def.items.iter_mut().for_each(|function| self.resolve_fn(function));
+            },
+            Item::Impl(imp) => {
+                self.push_generics(&mut imp.generics);
+                self.resolve_ty(&mut imp.self_ty);
+                if let Some(trait_path) = &mut imp.of_trait {
+                    self.resolve_path_in_place(trait_path, "trait");
+                }
+                imp.items.iter_mut().for_each(|function| self.resolve_fn(function));
+                self.scopes.pop();
+            },
+            // 초기값은 바깥 함수의 지역 변수를 볼 수 없고, 블록 안의 아이템만 봅니다.
+            Item::Const(def) | Item::Static(def) => {
+                def.name = format!("{}{}", prefix, def.name);
+                self.resolve_ty(&mut def.ty);
+                let items = self
+                    .scopes
+                    .iter()
+                    .map(|scope| scope.iter().filter(|(_, item)| item.is_some()).map(|(name, item)| (name.clone(), item.clone())).collect())
+                    .collect();
+                let scopes = std::mem::replace(&mut self.scopes, items);
+                self.resolve_expr(def.value);
+                self.scopes = scopes;
+            },
+            Item::Mod(_) | Item::Use(_) => {},
+        }
+    }
+
+    // 타입 매개변수는 같은 이름의 모듈 아이템을 가립니다.
+    fn push_generics(&mut self, generics: &mut Generics) {
+        self.scopes.push(generics.params.iter().map(|param| (param.name.clone(), None)).collect());
+        for param in &mut generics.params {
+            param.bounds.iter_mut().for_each(|bound| self.resolve_path_in_place(bound, "trait"));
+        }
+    }
+
+    fn resolve_fn(&mut self, function: &mut FnDef) {
+        self.push_generics(&mut function.generics);
+        for param in &mut function.params {
+            self.resolve_ty(&mut param.ty);
+        }
+        if let Some(ret) = &mut function.ret {
+            self.resolve_ty(ret);
+        }
+
+        self.scopes.push(HashMap::new());
+        for param in &function.params {
+            self.bind(&param.pat);
+        }
+        if let Some(body) = &function.body {
+            self.resolve_block(body);
+        }
+        self.scopes.pop();
+        self.scopes.pop();
+    }
+
+    fn bind(&mut self, pat: &Pat) {
+        for binding in pat.bindings() {
+            if let PatKind::Ident { name, .. } = &binding.kind {
+                self.scopes.last_mut().unwrap().insert(name.clone(), None);
+            }
+        }
+    }
+
+    // 구조체 패턴의 경로와 리터럴 패턴의 값을 해석합니다. 바인딩은 `bind`가 따로 만듭니다.
+    fn resolve_pat(&mut self, pat: &mut Pat) {
+        self.with_span(pat.span, |this| match &mut pat.kind {
+            PatKind::Ident { .. } | PatKind::Wild => {},
+            PatKind::Lit(lit) => this.resolve_expr(*lit),
+            PatKind::Range(start, end) => {
+                this.resolve_expr(*start);
+                this.resolve_expr(*end);
+            },
+            PatKind::Tuple(pats) | PatKind::Or(pats) => pats.iter_mut().for_each(|pat| this.resolve_pat(pat)),
+            PatKind::Struct { path, fields, .. } => {
+                this.resolve_path_in_place(path, "struct");
+                fields.iter_mut().for_each(|(_, pat)| this.resolve_pat(pat));
+            },
+        })
+    }
+
+    // 블록 안의 아이템과 `use`는 블록 전체에서 보입니다.
+    fn resolve_block(&mut self, block: &Block) {
+        let prefix = self.modules[self.module].prefix();
+        let mut scope = HashMap::new();
+        for &stmt in &block.stmts {
+            let item = match &self.arena[stmt].kind {
+                StmtKind::Item(item) => item.clone(),
+                _ => continue,
+            };
+            self.span = item.span();
+            match item {
+                Item::Fn(FnDef { name, .. }) => {
+                    scope.insert(name.clone(), Some(format!("{}{}", prefix, name)));
+                },
+                Item::Struct(def) => {
+                    scope.insert(def.name.clone(), Some(format!("{}{}", prefix, def.name)));
+                },
+                Item::Trait(def) => {
+                    scope.insert(def.name.clone(), Some(format!("{}{}", prefix, def.name)));
+                },
+                Item::Const(def) | Item::Static(def) => {
+                    scope.insert(def.name.clone(), Some(format!("{}{}", prefix, def.name)));
+                },
+                Item::Use(decl) => {
+                    for (path, name) in use_leaves(&decl.tree) {
+                        let Some(name) = name else {
+                            self.error("glob imports are not supported inside blocks".to_string());
+                            continue;
+                        };
+                        match self.resolve_path(self.module, &path) {
+                            Ok(PathRes::Def(def, len)) if len == path.len() => {
+                                scope.insert(name, Some(self.defs[def].name.clone()));
+                            },
+                            Ok(_) => self.error(format!("unresolved import `{}`", path.join("::"))),
+                            Err(message) => self.error(message),
+                        }
+                    }
+                },
+                Item::Mod(def) => self.error(format!("module `{}` must be declared at module level", def.name)),
+                Item::Impl(_) => {},
+            }
+        }
+        self.scopes.push(scope);
+
+        for &id in &block.stmts {
+            let mut stmt = self.arena.take_stmt(id);
+            self.span = stmt.span;
+            match &mut stmt.kind {
+                StmtKind::Let(local) => {
+                    if let Some(ty) = &mut local.ty {
+                        self.resolve_ty(ty);
+                    }
+                    if let Some(init) = local.init {
+                        self.resolve_expr(init);
+                    }
+                    self.bind(&local.pat);
+                },
+                StmtKind::Item(item) => self.resolve_item(item, &prefix),
+                StmtKind::Expr(expr) | StmtKind::Semi(expr) => self.resolve_expr(*expr),
+            }
+            self.arena[id] = stmt;
+        }
+        self.scopes.pop();
+    }
+
+    fn resolve_expr(&mut self, id: ExprId) {
+        let mut node = self.arena.take_expr(id);
+        self.with_span(node.span, |this| this.resolve_node(&mut node));
+        self.arena[id] = node;
+    }
+
+    fn resolve_node(&mut self, node: &mut Node) {
+        match &mut node.kind {
+            NodeKind::Int(..) | NodeKind::Float(..) | NodeKind::Char(_) | NodeKind::Bool(_) | NodeKind::Str(_) | NodeKind::Break | NodeKind::Continue => {},
+            NodeKind::Ident(name) => {
+                let mut path = Path::from_ident(name);
+                self.resolve_path_in_place(&mut path, "value");
+                *name = path.segments.pop().unwrap().ident;
+            },
+            NodeKind::Path(path) => {
+                self.resolve_path_in_place(path, "value");
+                if let [segment] = path.segments.as_slice() {
+                    if segment.args.is_empty() {
+                        node.kind = NodeKind::Ident(segment.ident.clone());
+                    }
+                }
+            },
+            NodeKind::QualifiedPath(qself, path) => {
+                self.resolve_ty(&mut qself.ty);
+                self.resolve_path_in_place(&mut qself.trait_path, "trait");
+                for segment in &mut path.segments {
+                    segment.args.iter_mut().for_each(|arg| self.resolve_ty(arg));
+                }
+            },
+            NodeKind::BinaryOp(binary_op) => {
+                self.resolve_expr(binary_op.left);
+                self.resolve_expr(binary_op.right);
+            },
+            NodeKind::Unary(unary_op) => self.resolve_expr(unary_op.operand),
+            NodeKind::Ref { expr, .. } => self.resolve_expr(*expr),
+            NodeKind::Call(call) => {
+                self.resolve_expr(call.callee);
+                call.args.iter().for_each(|&arg| self.resolve_expr(arg));
+            },
+            NodeKind::MethodCall(call) => {
+                self.resolve_expr(call.receiver);
+                call.args.iter().for_each(|&arg| self.resolve_expr(arg));
+            },
+            NodeKind::Field(base, _) => self.resolve_expr(*base),
+            NodeKind::StructLit(lit) => {
+                self.resolve_path_in_place(&mut lit.path, "struct");
+                lit.fields.iter_mut().for_each(|(_, value)| self.resolve_expr(*value));
+            },
+            NodeKind::Assign(assign) => {
+                self.resolve_expr(assign.target);
+                self.resolve_expr(assign.value);
+            },
+            NodeKind::Block(block) | NodeKind::Loop(block) => self.resolve_block(block),
+            NodeKind::If(if_expr) => {
+                self.resolve_expr(if_expr.cond);
+                self.resolve_block(&if_expr.then);
+                if let Some(els) = &if_expr.els {
+                    self.resolve_expr(*els);
+                }
+            },
+            NodeKind::Match(match_expr) => {
+                self.resolve_expr(match_expr.scrutinee);
+                for arm in &mut match_expr.arms {
+                    self.resolve_pat(&mut arm.pat);
+                    self.scopes.push(HashMap::new());
+                    self.bind(&arm.pat);
+                    if let Some(guard) = arm.guard {
+                        self.resolve_expr(guard);
+                    }
+                    self.resolve_expr(arm.body);
+                    self.scopes.pop();
+                }
+            },
+            NodeKind::While(while_expr) => {
+                self.resolve_expr(while_expr.cond);
+                self.resolve_block(&while_expr.body);
+            },
+            NodeKind::ForLoop(for_loop) => {
+                self.resolve_expr(for_loop.iter);
+                self.scopes.push(HashMap::new());
+                self.bind(&for_loop.pat);
+                self.resolve_block(&for_loop.body);
+                self.scopes.pop();
+            },
+            NodeKind::Return(value) => {
+                if let Some(value) = value {
+                    self.resolve_expr(*value);
+                }
+            },
+            NodeKind::Closure(closure) => {
+                for param in &mut closure.params {
+                    if let Some(ty) = &mut param.ty {
+                        self.resolve_ty(ty);
+                    }
+                }
+                if let Some(ret) = &mut closure.ret {
+                    self.resolve_ty(ret);
+                }
+                self.scopes.push(HashMap::new());
+                for param in &closure.params {
+                    self.bind(&param.pat);
+                }
+                self.resolve_expr(closure.body);
+                self.scopes.pop();
+            },
+            NodeKind::Array(elems) | NodeKind::Tuple(elems) => elems.iter().for_each(|&elem| self.resolve_expr(elem)),
+            NodeKind::FormatArgs(format) => format.args.iter_mut().for_each(|arg| self.resolve_expr(arg.expr)),
+            NodeKind::Repeat(base, other) | NodeKind::Index(base, other) => {
+                self.resolve_expr(*base);
+                self.resolve_expr(*other);
+            },
+            NodeKind::Range(range) => {
+                if let Some(start) = &range.start {
+                    self.resolve_expr(*start);
+                }
+                if let Some(end) = &range.end {
+                    self.resolve_expr(*end);
+                }
+            },
+        }
+    }
+
+    fn resolve_ty(&mut self, ty: &mut Ty) {
+        self.with_span(ty.span, |this| this.resolve_ty_kind(&mut ty.kind));
+    }
+
+    fn resolve_ty_kind(&mut self, kind: &mut TyKind) {
+        match kind {
+            TyKind::Path(path) => self.resolve_path_in_place(path, "type"),
+            TyKind::Ref { ty, .. } | TyKind::Array(ty, _) | TyKind::Slice(ty) => self.resolve_ty(ty),
+            TyKind::Tuple(tys) => tys.iter_mut().for_each(|ty| self.resolve_ty(ty)),
+            TyKind::Unit => {},
+        }
+    }
+
+    // 경로 앞부분이 가리키는 아이템을 그 전체 이름의 세그먼트 하나로 바꿉니다.
+    // `shapes::Point::<i32>::new`는 `shapes::Point::<i32>`와 `new` 두 세그먼트가 됩니다.
+    // `expected`는 경로가 모듈 자체를 가리킬 때의 오류 메시지에 씁니다.
+    fn resolve_path_in_place(&mut self, path: &mut Path, expected: &str) {
+        for segment in &mut path.segments {
+            segment.args.iter_mut().for_each(|arg| self.resolve_ty(arg));
+            if let Some(output) = &mut segment.output {
+                self.resolve_ty(output);
+            }
+        }
+
+        let first = &path.segments[0].ident;
+        if let Some(local) = self.scopes.iter().rev().find_map(|scope| scope.get(first)) {
+            if let Some(full_name) = local.clone() {
+                path.segments[0].ident = full_name;
+            }
+            return;
+        }
+
+        let idents: Vec<String> = path.segments.iter().map(|segment| segment.ident.clone()).collect();
+        match self.resolve_path(self.module, &idents) {
+            Ok(PathRes::Def(def, len)) => {
+                let def = &self.defs[def];
+                if let (DefKind::Mod(_), true) = (def.kind, len == idents.len()) {
+                    let message = format!("expected {}, found module `{}`", expected, path);
+                    self.error(message);
+                    return;
+                }
+                let rest = path.segments.split_off(len);
+                let last = path.segments.pop().unwrap();
+                path.segments = vec![PathSegment { ident: def.name.clone(), ..last }];
+                path.segments.extend(rest);
+            },
+            Ok(PathRes::NotInScope) => {},
+            Ok(PathRes::NotFound(message)) | Err(message) => self.error(message),
+        }
+    }
+}
+
+// use 트리를 (가져올 경로, 가져온 이름) 갈래로 풉니다. 글롭의 이름은 None입니다.
+// `a::{self, b as c}`의 `self`는 접두사 `a` 자체를 가져옵니다.
+fn use_leaves(tree: &UseTree) -> Vec<(Vec<String>, Option<String>)> {
+    let mut leaves = Vec::new();
+    collect_leaves(tree, &[], &mut leaves);
+    leaves
+}
+
+fn collect_leaves(tree: &UseTree, base: &[String], leaves: &mut Vec<(Vec<String>, Option<String>)>) {
+    let mut path = base.to_vec();
+    path.extend(tree.prefix.segments.iter().map(|segment| segment.ident.clone()));
+    match &tree.kind {
+        UseTreeKind::Simple(rename) => {
+            if path.last().map(String::as_str) == Some("self") && path.len() > 1 {
+                path.pop();
+            }
+            let Some(last) = path.last() else { return };
+            let name = rename.clone().unwrap_or_else(|| last.clone());
+            leaves.push((path, Some(name)));
+        },
+        UseTreeKind::Nested(trees) => trees.iter().for_each(|tree| collect_leaves(tree, &path, leaves)),
+        UseTreeKind::Glob => leaves.push((path, None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusttc_ast::ast::StmtId;
+    use rusttc_lexer::tokenize;
+    use rusttc_parse::parser::parse_crate;
+
+    fn resolve(source: &str) -> Result<Crate, Vec<ModuleError>> {
+        resolve_modules(&parse_crate(&tokenize(source)).unwrap())
+    }
+
+    fn errors(source: &str) -> Vec<String> {
+        resolve(source).unwrap_err().into_iter().map(|error| error.message).collect()
+    }
+
+    fn main_stmts(krate: &Crate) -> &[StmtId] {
+        let main = krate.items.iter().find_map(|item| match item {
+            Item::Fn(function) if function.name == "main" => function.body.as_ref(),
+            _ => None,
+        });
+        &main.unwrap().stmts
+    }
+
+    // `f(..);` 문장에서 호출 대상 노드의 종류를 꺼냅니다.
+    fn callee(krate: &Crate, stmt: StmtId) -> &NodeKind {
+        let StmtKind::Semi(expr) = krate.arena[stmt].kind else { panic!() };
+        let NodeKind::Call(call) = &krate.arena[expr].kind else { panic!() };
+        &krate.arena[call.callee].kind
+    }
+
+    #[test]
+    fn test_items_are_flattened_with_full_names() {
+        let krate = resolve("
+            mod shapes {
+                pub struct Point { pub x: i32 }
+                impl Point { pub fn new(x: i32) -> Self { Point { x } } }
+                pub mod util { pub fn origin() -> super::Point { super::Point::new(0) } }
+            }
+            fn main() { let p: shapes::Point = shapes::util::origin(); crate::shapes::Point::new(1); }
+        ")
+        .unwrap();
+
+        let names: Vec<String> = krate
+            .items
+            .iter()
+            .map(|item| match item {
+                Item::Fn(function) => format!("fn {}", function.name),
+                Item::Struct(def) => format!("struct {}", def.name),
+                Item::Impl(imp) => format!("impl {}", imp.self_ty),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(names, vec!["struct shapes::Point", "impl shapes::Point", "fn shapes::util::origin", "fn main"]);
+
+        let stmts = main_stmts(&krate);
+        let StmtKind::Let(local) = &krate.arena[stmts[0]].kind else { panic!() };
+        assert_eq!(local.ty, Some(Ty::from(TyKind::Path(Path::from_ident("shapes::Point")))));
+        let NodeKind::Call(call) = &krate.arena[local.init.unwrap()].kind else { panic!() };
+        assert_eq!(krate.arena[call.callee].kind, NodeKind::Ident("shapes::util::origin".to_string()));
+        let NodeKind::Path(path) = callee(&krate, stmts[1]) else { panic!() };
+        assert_eq!(path.to_string(), "shapes::Point::new");
+        assert_eq!(path.segments.len(), 2);
+    }
+
+    #[test]
+    fn test_use_imports_nested_renamed_and_glob() {
+        let krate = resolve("
+            mod math {
+                pub fn add(a: i32, b: i32) -> i32 { a + b }
+                pub fn sub(a: i32, b: i32) -> i32 { a - b }
+                pub mod consts { pub fn zero() -> i32 { 0 } }
+            }
+            mod prelude { pub use crate::math::consts::*; }
+            use math::{add, sub as minus, consts::{self}};
+            use prelude::*;
+            fn main() { add(1, 2); minus(3, 1); consts::zero(); zero(); let add = 1; add; }
+        ")
+        .unwrap();
+
+        let ident = |name: &str| NodeKind::Ident(name.to_string());
+        let stmts = main_stmts(&krate);
+        assert_eq!(*callee(&krate, stmts[0]), ident("math::add"));
+        assert_eq!(*callee(&krate, stmts[1]), ident("math::sub"));
+        assert_eq!(*callee(&krate, stmts[2]), ident("math::consts::zero"));
+        assert_eq!(*callee(&krate, stmts[3]), ident("math::consts::zero"));
+        // 지역 변수는 가져온 이름을 가립니다.
+        let StmtKind::Semi(add) = krate.arena[stmts[5]].kind else { panic!() };
+        assert_eq!(krate.arena[add].kind, ident("add"));
+    }
+
+    #[test]
+    fn test_glob_imports_of_different_items_are_ambiguous() {
+        assert_eq!(
+            errors("mod a { pub fn f() {} } mod b { pub fn f() {} } use a::*; use b::*; fn main() { f(); }"),
+            vec!["`f` is ambiguous"]
+        );
+        // 직접 가져온 이름은 글롭보다 우선하고, 같은 아이템을 두 글롭으로 가져오는 것은 모호하지 않습니다.
+        assert!(resolve("mod a { pub fn f() {} } mod b { pub fn f() {} } use a::*; use b::*; use a::f; fn main() { f(); }").is_ok());
+        assert!(resolve("mod a { pub fn f() {} } mod b { pub use crate::a::*; } use a::*; use b::*; fn main() { f(); }").is_ok());
+        // 보이지 않는 이름은 글롭으로 들어오지 않으므로 겹치지 않습니다.
+        assert!(resolve("mod a { pub fn f() {} } mod b { fn f() {} } use a::*; use b::*; fn main() { f(); }").is_ok());
+    }
+
+    #[test]
+    fn test_private_items_are_rejected() {
+        assert_eq!(
+            errors("
+                mod a { fn secret() {} pub fn open() { secret() } mod inner { fn f() { super::secret() } } }
+                fn main() { a::open(); a::secret(); a::inner::f(); }
+            "),
+            vec!["function `secret` is private", "module `inner` is private"]
+        );
+        assert_eq!(errors("mod a { struct S; } use a::S; fn main() {}"), vec!["struct `S` is private"]);
+    }
+
+    #[test]
+    fn test_unresolved_paths() {
+        assert_eq!(
+            errors("mod a {} use a::missing; fn main() { a::nothing(); a; }"),
+            vec![
+                "unresolved import `a::missing`",
+                "cannot find `nothing` in `a`",
+                "expected value, found module `a`",
+            ]
+        );
+        assert_eq!(errors("fn main() { super::f(); }"), vec!["there are too many leading `super` keywords"]);
+        assert_eq!(errors("mod m; fn main() {}"), vec!["file not found for module `m`"]);
+    }
+}
@@ -0,0 +1,649 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use rusttc_ast::ast::{
+    AstArena, Block, Closure, ConstDef, Crate, ExprId, FnDef, Generics, Impl, Item, Node, NodeId, NodeKind, Path, PathSegment,
+    StmtKind, StructDef, Ty, TyKind,
+};
+use rusttc_lexer::Span;
+
+use crate::method::{substitute, unify};
+
+// 단형화 중에 발생한 오류입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonoError {
+    pub message: String,
+}
+
+impl fmt::Display for MonoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)
+    }
+}
+
+// 타입 인자가 이보다 깊게 중첩되면 끝나지 않는 인스턴스화로 봅니다.
+const RECURSION_LIMIT: usize = 32;
+
+// 만들어야 할 특수화 인스턴스입니다.
+enum Instance {
+    Fn { name: String, args: Vec<Ty> },        // `max::<i32>`
+    Struct { name: String, args: Vec<Ty> },    // `Pair<i32>`
+    Method { self_ty: Ty, of_trait: Option<String>, name: String, args: Vec<Ty> }, // `Pair<i32>::swap`
+}
+
+// 제네릭 함수, 구조체, impl을 실제로 쓰인 타입 인자마다 복사해 특수화합니다.
+// `max::<i32>`는 `max::<i32>`라는 이름의 함수로, `Pair<i32>`는 같은 이름의 구조체로,
+// 그 메서드는 `Pair<i32>::swap`으로 만들어집니다. 제네릭 정의 자체는 결과에서 빠집니다.
+// 타입 검사가 추론한 타입 인자를 경로에 적은 뒤(`rusttc_typeck::write_back`), IR 변환 전에 실행되어야 합니다.
+pub fn monomorphize(krate: &Crate) -> Result<Crate, MonoError> {
+    let mut collector = Collector { arena: krate.arena.clone(), ..Collector::default() };
+    collector.collect(&krate.items);
+
+    let mut items: Vec<Item> = krate.items.iter().filter_map(|item| collector.mono_item(item)).collect();
+    while let Some(instance) = collector.queue.pop_front() {
+        collector.instantiate(instance, &mut items);
+    }
+
+    match collector.error {
+        Some(error) => Err(error),
+        None => Ok(Crate { attrs: krate.attrs.clone(), items, arena: collector.arena }),
+    }
+}
+
+#[derive(Default)]
+struct Collector {
+    arena: AstArena,                        // 특수화한 본문까지 담는 표현식과 문장
+    fns: HashMap<String, FnDef>,            // 제네릭 함수
+    structs: HashMap<String, StructDef>,    // 제네릭 구조체
+    impls: Vec<Impl>,                       // 모든 impl 블록
+    seen: HashSet<String>,                  // 이미 요청된 인스턴스 이름
+    queue: VecDeque<Instance>,              // 아직 만들지 않은 인스턴스
+    impl_blocks: HashMap<String, usize>,    // 특수화된 impl 블록(`<Pair<i32> as Shape>`) -> 결과 아이템 위치
+    fn_path: Option<String>,                // 특수화 중인 함수 이름 (그 안의 클로저 이름에 붙습니다)
+    closures: HashMap<String, Ty>,          // 클로저 타입 이름 -> 특수화된 클로저 타입
+    error: Option<MonoError>,
+}
+
+impl Collector {
+    // 제네릭 정의와 impl 블록을 모읍니다. 함수 본문 안의 아이템도 포함합니다.
+    fn collect(&mut self, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::Fn(function) => {
+                    if !function.generics.params.is_empty() {
+                        self.fns.insert(function.name.clone(), function.clone());
+                    }
+                    self.collect_fn(function);
+                },
+                Item::Struct(def) => {
+                    if !def.generics.params.is_empty() {
+                        self.structs.insert(def.name.clone(), def.clone());
+                    }
+                },
+                Item::Impl(imp) => {
+                    imp.items.iter().for_each(|function| self.collect_fn(function));
+                    self.impls.push(imp.clone());
+                },
+                Item::Trait(_) | Item::Mod(_) | Item::Use(_) | Item::Const(_) | Item::Static(_) => {},
+            }
+        }
+    }
+
+    fn collect_fn(&mut self, function: &FnDef) {
+        let Some(body) = &function.body else { return };
+        let items: Vec<Item> = body
+            .stmts
+            .iter()
+            .filter_map(|&stmt| match &self.arena[stmt].kind {
+                StmtKind::Item(item) => Some(item.clone()),
+                _ => None,
+            })
+            .collect();
+        self.collect(&items);
+    }
+
+    // 제네릭이 아닌 아이템을 그대로 옮기면서, 그 안에서 쓰인 인스턴스를 요청합니다.
+    fn mono_item(&mut self, item: &Item) -> Option<Item> {
+        let empty = HashMap::new();
+        match item {
+            Item::Fn(function) if function.generics.params.is_empty() => {
+                Some(Item::Fn(self.mono_fn(function, function.name.clone(), None, &empty)))
+            },
+            Item::Struct(def) if def.generics.params.is_empty() => {
+                let mut def = def.clone();
+                for field in &mut def.fields {
+                    field.ty = self.mono_ty(&field.ty, &empty);
+                }
+                Some(Item::Struct(def))
+            },
+            Item::Impl(imp) if !is_generic_impl(imp) => {
+                let items = imp
+                    .items
+                    .iter()
+                    .filter(|function| function.generics.params.is_empty())
+                    .map(|function| self.mono_fn(function, function.name.clone(), None, &empty))
+                    .collect();
+                Some(Item::Impl(Impl { items, ..imp.clone() }))
+            },
+            Item::Trait(_) => Some(item.clone()),
+            // 상수의 초기값은 제네릭이 아니므로 타입만 특수화합니다.
+            Item::Const(def) | Item::Static(def) => {
+                let ty = self.mono_ty(&def.ty, &empty);
+                let def = ConstDef { ty, ..def.clone() };
+                Some(if matches!(item, Item::Const(_)) { Item::Const(def) } else { Item::Static(def) })
+            },
+            _ => None,
+        }
+    }
+
+    // 함수를 `subst`에 따라 특수화한 복사본을 만듭니다. 본문도 저장소 안에 새로 복사하므로 원본은 그대로입니다.
+    // `path`는 특수화된 함수의 IR 이름으로, 본문 안 클로저들은 그 아래로 이름이 바뀝니다.
+    fn mono_fn(&mut self, function: &FnDef, name: String, path: Option<String>, subst: &HashMap<String, Ty>) -> FnDef {
+        let old_path = std::mem::replace(&mut self.fn_path, path);
+        let mut function = self.arena.copy_fn(function);
+        function.name = name;
+        function.generics = Generics::default();
+        for param in &mut function.params {
+            param.ty = self.mono_ty(&param.ty, subst);
+        }
+        if let Some(ret) = &function.ret {
+            function.ret = Some(self.mono_ty(ret, subst));
+        }
+        if let Some(body) = &mut function.body {
+            self.mono_block(body, subst);
+        }
+        self.fn_path = old_path;
+        function
+    }
+
+    fn mono_block(&mut self, block: &mut Block, subst: &HashMap<String, Ty>) {
+        let stmts = std::mem::take(&mut block.stmts);
+        for id in stmts {
+            let mut stmt = self.arena.take_stmt(id);
+            match &mut stmt.kind {
+                StmtKind::Let(local) => {
+                    if let Some(ty) = &local.ty {
+                        local.ty = Some(self.mono_ty(ty, subst));
+                    }
+                    if let Some(init) = local.init {
+                        self.mono_expr(init, subst);
+                    }
+                },
+                // 안쪽 아이템은 바깥 함수의 타입 매개변수를 볼 수 없으므로 따로 처리합니다.
+                StmtKind::Item(item) => match self.mono_item(item) {
+                    Some(mono) => *item = mono,
+                    None => continue,
+                },
+                StmtKind::Expr(node) | StmtKind::Semi(node) => self.mono_expr(*node, subst),
+            }
+            self.arena[id] = stmt;
+            block.stmts.push(id);
+        }
+    }
+
+    fn mono_expr(&mut self, id: ExprId, subst: &HashMap<String, Ty>) {
+        let mut node = self.arena.take_expr(id);
+        self.mono_node(&mut node, subst);
+        self.arena[id] = node;
+    }
+
+    fn mono_node(&mut self, node: &mut Node, subst: &HashMap<String, Ty>) {
+        match &mut node.kind {
+            NodeKind::Int(..) | NodeKind::Float(..) | NodeKind::Char(_) | NodeKind::Bool(_) | NodeKind::Str(_) | NodeKind::Ident(_) | NodeKind::Break | NodeKind::Continue => {},
+            NodeKind::Path(path) => {
+                if let Some(replacement) = self.mono_path(path, subst) {
+                    node.kind = replacement;
+                }
+            },
+            NodeKind::QualifiedPath(qself, path) => {
+                self.subst_ty(&mut qself.ty, subst);
+                let segment = path.segments.last_mut().unwrap();
+                let args = self.subst_all(&segment.args, subst);
+                if has_generic_args(&qself.ty) || !args.is_empty() {
+                    let of_trait = Some(qself.trait_path.to_string());
+                    let (self_ty, name) = self.request_method(qself.ty.clone(), of_trait, &segment.ident, args);
+                    qself.ty = self_ty;
+                    *path = Path::from_ident(&name);
+                }
+            },
+            NodeKind::BinaryOp(binary_op) => {
+                self.mono_expr(binary_op.left, subst);
+                self.mono_expr(binary_op.right, subst);
+            },
+            NodeKind::Unary(unary_op) => self.mono_expr(unary_op.operand, subst),
+            NodeKind::Ref { expr, .. } => self.mono_expr(*expr, subst),
+            // 인자 안의 클로저 이름이 먼저 바뀌어야 `apply::<F>`의 타입 인자도 바뀝니다.
+            NodeKind::Call(call) => {
+                call.args.iter().for_each(|&arg| self.mono_expr(arg, subst));
+                self.mono_expr(call.callee, subst);
+            },
+            NodeKind::MethodCall(call) => {
+                self.mono_expr(call.receiver, subst);
+                call.args.iter().for_each(|&arg| self.mono_expr(arg, subst));
+            },
+            NodeKind::Field(base, _) => self.mono_expr(*base, subst),
+            NodeKind::StructLit(lit) => {
+                if let TyKind::Path(path) = self.mono_ty(&Ty::from(TyKind::Path(lit.path.clone())), subst).kind {
+                    lit.path = path;
+                }
+                lit.fields.iter().for_each(|&(_, value)| self.mono_expr(value, subst));
+            },
+            NodeKind::Assign(assign) => {
+                self.mono_expr(assign.target, subst);
+                self.mono_expr(assign.value, subst);
+            },
+            NodeKind::Block(block) => self.mono_block(block, subst),
+            NodeKind::If(if_expr) => {
+                self.mono_expr(if_expr.cond, subst);
+                self.mono_block(&mut if_expr.then, subst);
+                if let Some(els) = if_expr.els {
+                    self.mono_expr(els, subst);
+                }
+            },
+            // 패턴은 필드 이름으로만 값을 꺼내므로 구조체 패턴의 경로는 특수화하지 않습니다.
+            NodeKind::Match(match_expr) => {
+                self.mono_expr(match_expr.scrutinee, subst);
+                for arm in &match_expr.arms {
+                    if let Some(guard) = arm.guard {
+                        self.mono_expr(guard, subst);
+                    }
+                    self.mono_expr(arm.body, subst);
+                }
+            },
+            NodeKind::While(while_expr) => {
+                self.mono_expr(while_expr.cond, subst);
+                self.mono_block(&mut while_expr.body, subst);
+            },
+            NodeKind::ForLoop(for_loop) => {
+                self.mono_expr(for_loop.iter, subst);
+                self.mono_block(&mut for_loop.body, subst);
+            },
+            NodeKind::Loop(body) => self.mono_block(body, subst),
+            NodeKind::Return(value) => {
+                if let Some(value) = value {
+                    self.mono_expr(*value, subst);
+                }
+            },
+            NodeKind::Closure(closure) => self.mono_closure(closure, subst),
+            NodeKind::Array(elems) | NodeKind::Tuple(elems) => elems.iter().for_each(|&elem| self.mono_expr(elem, subst)),
+            NodeKind::FormatArgs(format) => format.args.iter().for_each(|arg| self.mono_expr(arg.expr, subst)),
+            NodeKind::Repeat(value, count) | NodeKind::Index(value, count) => {
+                self.mono_expr(*value, subst);
+                self.mono_expr(*count, subst);
+            },
+            NodeKind::Range(range) => {
+                range.start.iter().chain(&range.end).for_each(|&bound| self.mono_expr(bound, subst));
+            },
+        }
+    }
+
+    // 특수화된 함수 안의 클로저는 `max::<i32>::{closure#0}`처럼 새 이름을 받습니다.
+    fn mono_closure(&mut self, closure: &mut Closure, subst: &HashMap<String, Ty>) {
+        if let Some(path) = &self.fn_path {
+            let suffix = &closure.name[closure.name.rfind("::{closure").unwrap_or(0)..];
+            let name = format!("{}{}", path, suffix);
+            self.closures.insert(closure.name.clone(), Ty::from_ident(&name));
+            closure.name = name;
+        }
+        for param in &mut closure.params {
+            if let Some(ty) = &param.ty {
+                param.ty = Some(self.mono_ty(ty, subst));
+            }
+        }
+        if let Some(ret) = &closure.ret {
+            closure.ret = Some(self.mono_ty(ret, subst));
+        }
+        self.mono_expr(closure.body, subst);
+    }
+
+    // 타입 매개변수와 이름이 바뀐 클로저 타입을 치환합니다.
+    fn subst_ty(&self, ty: &mut Ty, subst: &HashMap<String, Ty>) {
+        substitute(ty, subst);
+        substitute(ty, &self.closures);
+    }
+
+    fn subst_all(&self, tys: &[Ty], subst: &HashMap<String, Ty>) -> Vec<Ty> {
+        tys.iter()
+            .map(|ty| {
+                let mut ty = ty.clone();
+                self.subst_ty(&mut ty, subst);
+                ty
+            })
+            .collect()
+    }
+
+    // `max::<T>`, `Pair::<T>::new`, `T::new` 같은 경로를 특수화된 이름으로 바꿉니다.
+    fn mono_path(&mut self, path: &mut Path, subst: &HashMap<String, Ty>) -> Option<NodeKind> {
+        match path.segments.as_mut_slice() {
+            [function] if !function.args.is_empty() => {
+                let args = self.subst_all(&function.args, subst);
+                let name = format!("{}::<{}>", function.ident, join(&args));
+                self.request(name.clone(), &args, Instance::Fn { name: function.ident.clone(), args: args.clone() });
+                Some(NodeKind::Ident(name))
+            },
+            [ty_segment, function] => {
+                let mut ty = Ty::from(TyKind::Path(Path { segments: vec![ty_segment.clone()] }));
+                self.subst_ty(&mut ty, subst);
+                let args = self.subst_all(&function.args, subst);
+
+                let (ty, name) = if has_generic_args(&ty) || !args.is_empty() {
+                    self.request_method(ty, None, &function.ident, args)
+                } else {
+                    (ty, function.ident.clone())
+                };
+                if let TyKind::Path(Path { segments }) = ty.kind {
+                    if let [segment] = segments.as_slice() {
+                        *ty_segment = segment.clone();
+                        *function = PathSegment::from_ident(&name);
+                    }
+                }
+                None
+            },
+            _ => None,
+        }
+    }
+
+    // 타입 매개변수를 치환하고, 제네릭 구조체 타입은 특수화된 구조체 이름으로 바꿉니다.
+    fn mono_ty(&mut self, ty: &Ty, subst: &HashMap<String, Ty>) -> Ty {
+        let mut ty = ty.clone();
+        self.subst_ty(&mut ty, subst);
+        self.mangle(ty)
+    }
+
+    // 바꾼 타입도 원래 타입의 노드 번호와 위치를 그대로 가집니다.
+    fn mangle(&mut self, ty: Ty) -> Ty {
+        let kind = match ty.kind {
+            TyKind::Ref { mutable, ty } => TyKind::Ref { mutable, ty: Box::new(self.mangle(*ty)) },
+            TyKind::Array(elem, len) => TyKind::Array(Box::new(self.mangle(*elem)), len),
+            TyKind::Slice(elem) => TyKind::Slice(Box::new(self.mangle(*elem))),
+            TyKind::Tuple(tys) => TyKind::Tuple(tys.into_iter().map(|ty| self.mangle(ty)).collect()),
+            TyKind::Path(path) if is_generic_path(&path) => {
+                let segment = &path.segments[0];
+                let name = path.to_string();
+                if self.structs.contains_key(&segment.ident) {
+                    let instance = Instance::Struct { name: segment.ident.clone(), args: segment.args.clone() };
+                    self.request(name.clone(), &segment.args, instance);
+                }
+                TyKind::Path(Path::from_ident(&name))
+            },
+            kind => kind,
+        };
+        Ty { kind, ..ty }
+    }
+
+    // `self_ty`의 메서드 인스턴스를 요청하고, 특수화된 대상 타입과 메서드 이름을 돌려줍니다.
+    fn request_method(&mut self, self_ty: Ty, of_trait: Option<String>, name: &str, args: Vec<Ty>) -> (Ty, String) {
+        let fn_name = if args.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}::<{}>", name, join(&args))
+        };
+        let key = format!("{}::{}", impl_prefix(&self_ty, of_trait.as_deref()), fn_name);
+        let mut all_args = args.clone();
+        if let TyKind::Path(path) = &self_ty.kind {
+            all_args.extend(path.segments[0].args.iter().cloned());
+        }
+
+        let instance = Instance::Method { self_ty: self_ty.clone(), of_trait, name: name.to_string(), args };
+        self.request(key, &all_args, instance);
+        (self.mangle(self_ty), fn_name)
+    }
+
+    fn request(&mut self, key: String, args: &[Ty], instance: Instance) {
+        if self.seen.contains(&key) || self.error.is_some() {
+            return;
+        }
+        if args.iter().map(ty_depth).max().unwrap_or(0) > RECURSION_LIMIT {
+            let message = format!("reached the recursion limit while instantiating `{}`", key);
+            self.error = Some(MonoError { message });
+            return;
+        }
+        self.seen.insert(key);
+        self.queue.push_back(instance);
+    }
+
+    // 요청된 인스턴스를 만들어 `items`에 추가합니다.
+    fn instantiate(&mut self, instance: Instance, items: &mut Vec<Item>) {
+        match instance {
+            Instance::Fn { name, args } => {
+                let Some(function) = self.fns.get(&name).cloned() else { return };
+                let subst = bind(&function.generics, &args);
+                let mono_name = format!("{}::<{}>", name, join(&args));
+                items.push(Item::Fn(self.mono_fn(&function, mono_name.clone(), Some(mono_name), &subst)));
+            },
+            Instance::Struct { name, args } => {
+                let Some(def) = self.structs.get(&name).cloned() else { return };
+                let subst = bind(&def.generics, &args);
+                let mut fields = def.fields.clone();
+                for field in &mut fields {
+                    field.ty = self.mono_ty(&field.ty, &subst);
+                }
+                let name = format!("{}<{}>", name, join(&args));
+                let def = StructDef {
+                    attrs: def.attrs.clone(),
+                    id: def.id,
+                    span: def.span,
+                    vis: def.vis,
+                    name,
+                    generics: Generics::default(),
+                    fields,
+                };
+                items.push(Item::Struct(def));
+            },
+            Instance::Method { self_ty, of_trait, name, args } => {
+                let found = self.impls.iter().find_map(|imp| {
+                    if imp.of_trait.as_ref().map(Path::to_string) != of_trait {
+                        return None;
+                    }
+                    let function = imp.items.iter().find(|function| function.name == name)?;
+                    let names: Vec<&str> = imp.generics.params.iter().map(|param| param.name.as_str()).collect();
+                    let mut subst = HashMap::new();
+                    unify(&imp.self_ty, &self_ty, &names, &mut subst).then(|| (imp.of_trait.clone(), function.clone(), subst))
+                });
+                let Some((trait_path, function, mut subst)) = found else {
+                    let message = format!("cannot find an implementation of `{}` for `{}`", name, self_ty);
+                    self.error = Some(MonoError { message });
+                    return;
+                };
+                subst.extend(bind(&function.generics, &args));
+
+                let fn_name = match args.is_empty() {
+                    true => name,
+                    false => format!("{}::<{}>", name, join(&args)),
+                };
+                // 같은 타입, 같은 트레이트의 메서드는 하나의 impl 블록으로 모읍니다.
+                let prefix = impl_prefix(&self_ty, of_trait.as_deref());
+                let path = format!("{}::{}", prefix, fn_name);
+                let function = self.mono_fn(&function, fn_name, Some(path), &subst);
+
+                match self.impl_blocks.get(&prefix) {
+                    Some(&index) => {
+                        let Item::Impl(imp) = &mut items[index] else { unreachable!() };
+                        imp.items.push(function);
+                    },
+                    None => {
+                        let self_ty = self.mangle(self_ty);
+                        self.impl_blocks.insert(prefix, items.len());
+                        // 여러 impl에서 모은 블록이므로 원본 위치가 없습니다.
+                        let imp = Impl {
+                            attrs: Vec::new(),
+                            id: NodeId::DUMMY,
+                            span: Span::DUMMY,
+                            generics: Generics::default(),
+                            of_trait: trait_path,
+                            self_ty,
+                            items: vec![function],
+                        };
+                        items.push(Item::Impl(imp));
+                    },
+                }
+            },
+        }
+    }
+}
+
+// 제네릭 매개변수를 순서대로 타입 인자에 대응시킵니다.
+fn bind(generics: &Generics, args: &[Ty]) -> HashMap<String, Ty> {
+    generics.params.iter().map(|param| param.name.clone()).zip(args.iter().cloned()).collect()
+}
+
+fn join(tys: &[Ty]) -> String {
+    tys.iter().map(Ty::to_string).collect::<Vec<_>>().join(", ")
+}
+
+fn impl_prefix(self_ty: &Ty, of_trait: Option<&str>) -> String {
+    match of_trait {
+        Some(trait_name) => format!("<{} as {}>", self_ty, trait_name),
+        None => self_ty.to_string(),
+    }
+}
+
+// 제네릭 인자를 가진 경로 타입(`Pair<i32>`)인지 확인합니다.
+fn has_generic_args(ty: &Ty) -> bool {
+    matches!(&ty.kind, TyKind::Path(path) if is_generic_path(path))
+}
+
+fn is_generic_path(path: &Path) -> bool {
+    path.segments.len() == 1 && !path.segments[0].args.is_empty()
+}
+
+fn is_generic_impl(imp: &Impl) -> bool {
+    !imp.generics.params.is_empty() || has_generic_args(&imp.self_ty)
+}
+
+fn ty_depth(ty: &Ty) -> usize {
+    match &ty.kind {
+        TyKind::Path(path) => 1 + path.segments.iter().flat_map(|segment| &segment.args).map(ty_depth).max().unwrap_or(0),
+        TyKind::Ref { ty, .. } | TyKind::Array(ty, _) | TyKind::Slice(ty) => 1 + ty_depth(ty),
+        TyKind::Tuple(tys) => 1 + tys.iter().map(ty_depth).max().unwrap_or(0),
+        TyKind::Unit => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::method::resolve_methods;
+    use rusttc_lexer::tokenize;
+    use rusttc_parse::parser::parse_crate;
+
+    // 타입 인자는 타입 검사의 쓰기 단계가 채운 모양대로 소스에 직접 적습니다.
+    fn mono(source: &str) -> Result<Crate, MonoError> {
+        let mut krate = parse_crate(&tokenize(source)).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        monomorphize(&krate)
+    }
+
+    // 결과 크레이트의 아이템 이름을 `fn max::<i32>`, `struct Pair<i32>`, `impl Pair<i32>::swap` 형태로 나열합니다.
+    fn item_names(krate: &Crate) -> Vec<String> {
+        let mut names = Vec::new();
+        for item in &krate.items {
+            match item {
+                Item::Fn(function) => names.push(format!("fn {}", function.name)),
+                Item::Struct(def) => names.push(format!("struct {}", def.name)),
+                Item::Impl(imp) => {
+                    let prefix = impl_prefix(&imp.self_ty, imp.of_trait.as_ref().map(Path::to_string).as_deref());
+                    names.extend(imp.items.iter().map(|function| format!("impl {}::{}", prefix, function.name)));
+                },
+                Item::Trait(def) => names.push(format!("trait {}", def.name)),
+                Item::Mod(_) | Item::Use(_) | Item::Const(_) | Item::Static(_) => {},
+            }
+        }
+        names
+    }
+
+    fn find_fn<'a>(krate: &'a Crate, name: &str) -> &'a FnDef {
+        krate
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Item::Fn(function) if function.name == name => Some(function),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    // 본문의 첫 문장인 호출 표현식에서 호출 대상 노드의 종류를 꺼냅니다.
+    fn first_callee<'a>(krate: &'a Crate, function: &FnDef) -> &'a NodeKind {
+        let StmtKind::Expr(expr) = krate.arena[function.body.as_ref().unwrap().stmts[0]].kind else { panic!() };
+        let NodeKind::Call(call) = &krate.arena[expr].kind else { panic!() };
+        &krate.arena[call.callee].kind
+    }
+
+    #[test]
+    fn test_generic_function_is_specialized_per_type() {
+        let krate = mono("
+            fn max<T: Ord>(a: T, b: T) -> T { if a > b { a } else { b } }
+            fn main() { let x: i64 = 3; max::<i32>(1, 2); max::<i64>(x, 4); max::<i32>(5, 6); }
+        ").unwrap();
+        assert_eq!(item_names(&krate), vec!["fn main", "fn max::<i32>", "fn max::<i64>"]);
+
+        let max = find_fn(&krate, "max::<i64>");
+        assert!(max.generics.params.is_empty());
+        assert_eq!(max.ret, Some(Ty::from(TyKind::Path(Path::from_ident("i64")))));
+    }
+
+    #[test]
+    fn test_generic_struct_and_impl_are_specialized() {
+        let krate = mono("
+            struct Pair<T> { a: T, b: T }
+            impl<T> Pair<T> {
+                fn new(a: T, b: T) -> Self { Pair::<T> { a, b } }
+                fn swap(self) -> Self { Pair::<T>::new(self.b, self.a) }
+                fn first(&self) -> T { self.a }
+            }
+            fn main() {
+                let p = Pair::<i32>::new(1, 2); Pair::<i32>::swap(p);
+                let q: Pair<bool> = Pair::<bool> { a: true, b: false }; Pair::<bool>::first(&q);
+            }
+        ").unwrap();
+        assert_eq!(item_names(&krate), vec![
+            "fn main",
+            "impl Pair<i32>::new",
+            "impl Pair<i32>::swap",
+            "struct Pair<i32>",
+            "struct Pair<bool>",
+            "impl Pair<bool>::first",
+        ]);
+
+        let main = find_fn(&krate, "main");
+        let StmtKind::Let(local) = &krate.arena[main.body.as_ref().unwrap().stmts[2]].kind else { panic!() };
+        assert_eq!(local.ty, Some(Ty::from(TyKind::Path(Path::from_ident("Pair<bool>")))));
+    }
+
+    #[test]
+    fn test_trait_bound_calls_become_concrete() {
+        let krate = mono("
+            struct Circle { r: i32 }
+            struct Wrapper<T> { inner: T }
+            trait Shape { fn area(&self) -> i32; }
+            impl Shape for Circle { fn area(&self) -> i32 { 3 * self.r * self.r } }
+            impl<T: Shape> Shape for Wrapper<T> { fn area(&self) -> i32 { <T as Shape>::area(&self.inner) } }
+            fn total<T: Shape>(s: &T) -> i32 { <T as Shape>::area(s) }
+            fn main() { let w = Wrapper::<Circle> { inner: Circle { r: 1 } }; total::<Wrapper<Circle>>(&w); }
+        ").unwrap();
+        assert_eq!(item_names(&krate), vec![
+            "struct Circle",
+            "trait Shape",
+            "impl <Circle as Shape>::area",
+            "fn main",
+            "struct Wrapper<Circle>",
+            "fn total::<Wrapper<Circle>>",
+            "impl <Wrapper<Circle> as Shape>::area",
+        ]);
+
+        let total = find_fn(&krate, "total::<Wrapper<Circle>>");
+        let NodeKind::QualifiedPath(qself, _) = first_callee(&krate, total) else { panic!() };
+        assert_eq!(qself.ty, Ty::from(TyKind::Path(Path::from_ident("Wrapper<Circle>"))));
+    }
+
+    #[test]
+    fn test_polymorphic_recursion_hits_limit() {
+        let error = mono("
+            struct Box1<T> { v: T }
+            fn nest<T>(x: T) -> i32 { nest::<Box1<T>>(Box1::<T> { v: x }) }
+            fn main() { nest::<i32>(1); }
+        ").unwrap_err();
+        assert!(error.message.starts_with("reached the recursion limit while instantiating `nest::<"));
+    }
+}
@@ -0,0 +1,518 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use rusttc_ast::ast::{Arm, AstArena, Block, Crate, FnDef, Impl, Item, Local, Node, NodeId, NodeKind, Pat, PatKind, SelfKind, TraitDef};
+use rusttc_ast::visit::{self, Visitor};
+use rusttc_lexer::Span;
+
+// 이름 해석 중에 발생한 오류입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolveError {
+    pub message: String,
+    pub span: Span,             // 찾지 못한 이름의 위치
+    pub help: Option<String>,   // 비슷한 이름 제안
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)?;
+        if let Some(location) = self.span.location() {
+            write!(f, "\n  --> {}", location)?;
+        }
+        if let Some(help) = &self.help {
+            write!(f, "\n  = help: {}", help)?;
+        }
+        Ok(())
+    }
+}
+
+// 이름이 가리키는 정의입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Res {
+    Local(NodeId),  // 지역 바인딩 (패턴의 노드 번호, `self`는 함수의 노드 번호)
+    Item(NodeId),   // 함수, 유닛 구조체, 상수나 정적 변수
+}
+
+// 지역 바인딩 하나의 정보입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub name: String,
+    pub mutable: bool,      // `mut x`, `mut self`
+    pub span: Span,         // 바인딩을 만든 패턴의 위치
+}
+
+// 이름 해석 결과입니다.
+#[derive(Debug, Default)]
+pub struct Resolutions {
+    pub uses: HashMap<NodeId, Res>,         // 식별자 표현식 → 정의
+    pub bindings: HashMap<NodeId, Binding>, // 바인딩 노드 번호 → 바인딩 정보
+}
+
+// 함수 본문의 식별자가 모두 정의를 가리키는지 확인하고, 각 사용을 정의에 연결합니다.
+// 블록, 함수, 클로저, `for` 문이 새 스코프를 만들고, 같은 이름의 `let`은 앞의 바인딩을 가립니다.
+// 모듈 트리를 펼친 뒤에 실행되어야 합니다. 이때 아이템 이름은 모두 전체 경로로 바뀌어 있습니다.
+pub fn resolve_names(krate: &Crate) -> Result<Resolutions, Vec<ResolveError>> {
+    let mut items = HashMap::new();
+    collect_items(&krate.arena, &krate.items, &mut items);
+
+    let mut resolver = NameResolver {
+        arena: &krate.arena,
+        items,
+        scopes: Vec::new(),
+        module: String::new(),
+        resolutions: Resolutions::default(),
+        errors: Vec::new(),
+    };
+    resolver.visit_crate(krate);
+
+    if resolver.errors.is_empty() {
+        Ok(resolver.resolutions)
+    } else {
+        Err(resolver.errors)
+    }
+}
+
+// 값으로 쓸 수 있는 아이템의 정의와 설명입니다.
+type ItemDefs = HashMap<String, (NodeId, &'static str)>;
+
+// 값으로 쓸 수 있는 아이템을 모읍니다. 블록 안의 아이템도 전체 이름을 가지므로 함께 모읍니다.
+fn collect_items(arena: &AstArena, items: &[Item], out: &mut ItemDefs) {
+    for item in items {
+        match item {
+            Item::Fn(function) => {
+                out.insert(function.name.clone(), (function.id, "a function"));
+                if let Some(body) = &function.body {
+                    collect_block_items(arena, body, out);
+                }
+            },
+            Item::Struct(def) => {
+                out.insert(def.name.clone(), (def.id, "a unit struct"));
+            },
+            Item::Const(def) => {
+                out.insert(def.name.clone(), (def.id, "a constant"));
+            },
+            Item::Static(def) => {
+                out.insert(def.name.clone(), (def.id, "a static"));
+            },
+            Item::Impl(imp) => imp.items.iter().filter_map(|function| function.body.as_ref()).for_each(|body| collect_block_items(arena, body, out)),
+            Item::Trait(def) => def.items.iter().filter_map(|function| function.body.as_ref()).for_each(|body| collect_block_items(arena, body, out)),
+            Item::Mod(def) => collect_items(arena, def.items.as_deref().unwrap_or_default(), out),
+            Item::Use(_) => {},
+        }
+    }
+}
+
+// 블록 안의 아이템 문장을 찾습니다. 표현식 안쪽 블록까지 내려갑니다.
+fn collect_block_items(arena: &AstArena, block: &Block, out: &mut ItemDefs) {
+    struct BlockItems<'a, 'b> {
+        arena: &'a AstArena,
+        out: &'b mut ItemDefs,
+    }
+
+    impl<'a> Visitor<'a> for BlockItems<'a, '_> {
+        fn arena(&self) -> &'a AstArena {
+            self.arena
+        }
+
+        fn visit_item(&mut self, item: &'a Item) {
+            collect_items(self.arena, std::slice::from_ref(item), self.out);
+        }
+    }
+
+    BlockItems { arena, out }.visit_block(block);
+}
+
+struct NameResolver<'a> {
+    arena: &'a AstArena,
+    items: ItemDefs,                        // 값으로 쓸 수 있는 아이템 (전체 이름 → 정의)
+    scopes: Vec<HashMap<String, NodeId>>,   // 지역 스코프 (안쪽이 뒤)
+    module: String,                         // 지금 아이템이 속한 모듈의 접두사 (`a::b::`)
+    resolutions: Resolutions,
+    errors: Vec<ResolveError>,
+}
+
+impl NameResolver<'_> {
+    fn with_scope(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        f(self);
+        self.scopes.pop();
+    }
+
+    // 지금 아이템의 모듈 접두사를 바꾼 채로 `f`를 실행합니다.
+    fn with_module(&mut self, full_name: &str, f: impl FnOnce(&mut Self)) {
+        let module = match full_name.rfind("::") {
+            Some(pos) => full_name[..pos + 2].to_string(),
+            None => String::new(),
+        };
+        let outer = std::mem::replace(&mut self.module, module);
+        f(self);
+        self.module = outer;
+    }
+
+    fn bind(&mut self, name: &str, id: NodeId, mutable: bool, span: Span) {
+        self.scopes.last_mut().expect("binding outside of a scope").insert(name.to_string(), id);
+        self.resolutions.bindings.insert(id, Binding { name: name.to_string(), mutable, span });
+    }
+
+    // 식별자 표현식을 안쪽 스코프부터 찾고, 없으면 아이템에서 찾습니다.
+    fn resolve_ident(&mut self, node: &Node, name: &str, expected: &str) {
+        let local = self.scopes.iter().rev().find_map(|scope| scope.get(name));
+        let res = match local {
+            Some(&id) => Res::Local(id),
+            None => match self.items.get(name) {
+                Some(&(id, _)) => Res::Item(id),
+                None => {
+                    let help = self.suggest(name);
+                    self.errors.push(ResolveError {
+                        message: format!("cannot find {} `{}` in this scope", expected, name),
+                        span: node.span,
+                        help,
+                    });
+                    return;
+                },
+            },
+        };
+        self.resolutions.uses.insert(node.id, res);
+    }
+
+    // 편집 거리가 가까운 지역 변수나 같은 모듈의 아이템을 제안합니다.
+    fn suggest(&self, name: &str) -> Option<String> {
+        let locals = self.scopes.iter().flat_map(|scope| scope.keys()).map(|local| (local.as_str(), "a local variable"));
+        let items = self.items.iter().filter_map(|(full_name, &(_, descr))| {
+            let short = full_name.strip_prefix(self.module.as_str()).filter(|short| !short.contains("::"))?;
+            Some((short, descr))
+        });
+        let candidates: Vec<_> = locals.chain(items).filter(|(candidate, _)| !candidate.contains('#')).collect();
+        let (best, descr) = find_best_match(&candidates, name)?;
+        Some(format!("{} with a similar name exists: `{}`", descr, best))
+    }
+}
+
+impl<'a> Visitor<'a> for NameResolver<'a> {
+    fn arena(&self) -> &'a AstArena {
+        self.arena
+    }
+
+    fn visit_item(&mut self, item: &'a Item) {
+        match item {
+            Item::Fn(function) => self.with_module(&function.name, |this| this.visit_fn(function)),
+            // 초기값은 바깥 함수의 지역 변수를 볼 수 없습니다.
+            Item::Const(def) | Item::Static(def) => self.with_module(&def.name, |this| {
+                let outer = std::mem::take(&mut this.scopes);
+                visit::walk_item(this, item);
+                this.scopes = outer;
+            }),
+            _ => visit::walk_item(self, item),
+        }
+    }
+
+    fn visit_impl(&mut self, imp: &'a Impl) {
+        let self_ty = imp.self_ty.to_string();
+        let self_ty = self_ty.split('<').next().unwrap_or_default();
+        self.with_module(self_ty, |this| visit::walk_impl(this, imp));
+    }
+
+    fn visit_trait(&mut self, def: &'a TraitDef) {
+        self.with_module(&def.name, |this| visit::walk_trait(this, def));
+    }
+
+    // 함수는 바깥 함수의 지역 변수를 볼 수 없으므로 스코프를 새로 시작합니다.
+    fn visit_fn(&mut self, function: &'a FnDef) {
+        let outer = std::mem::take(&mut self.scopes);
+        self.with_scope(|this| {
+            if let Some(self_param) = function.self_param {
+                let mutable = self_param == SelfKind::Value { mutable: true };
+                this.bind("self", function.id, mutable, function.span);
+            }
+            visit::walk_fn(this, function);
+        });
+        self.scopes = outer;
+    }
+
+    fn visit_block(&mut self, block: &'a Block) {
+        self.with_scope(|this| visit::walk_block(this, block));
+    }
+
+    // 초기값을 먼저 해석해야 `let x = x + 1;`의 오른쪽이 앞의 `x`를 가리킵니다.
+    fn visit_local(&mut self, local: &'a Local) {
+        if let Some(ty) = &local.ty {
+            self.visit_ty(ty);
+        }
+        if let Some(init) = local.init {
+            self.visit_expr(&self.arena[init]);
+        }
+        self.visit_pat(&local.pat);
+    }
+
+    // match 갈래마다 바인딩의 스코프가 따로 있습니다.
+    fn visit_arm(&mut self, arm: &'a Arm) {
+        self.with_scope(|this| visit::walk_arm(this, arm));
+    }
+
+    // 패턴의 바인딩을 지금 스코프에 넣습니다. or 패턴의 갈래는 모두 같은 이름을 바인딩해야 하고,
+    // 이름은 첫 번째 갈래의 바인딩을 가리킵니다.
+    fn visit_pat(&mut self, pat: &'a Pat) {
+        let mut seen = HashSet::new();
+        for binding in pat.bindings() {
+            let PatKind::Ident { name, mutable } = &binding.kind else { continue };
+            if !seen.insert(name.as_str()) {
+                let message = format!("identifier `{}` is bound more than once in the same pattern", name);
+                self.errors.push(ResolveError { message, span: binding.span, help: None });
+                continue;
+            }
+            self.bind(name, binding.id, *mutable, binding.span);
+        }
+
+        let mut errors = Vec::new();
+        pat.walk(&mut |pat| {
+            let PatKind::Or(alts) = &pat.kind else { return };
+            let names = |alt: &Pat| -> Vec<String> {
+                alt.bindings().iter().filter_map(|binding| match &binding.kind {
+                    PatKind::Ident { name, .. } => Some(name.clone()),
+                    _ => None,
+                }).collect()
+            };
+            let first = names(&alts[0]);
+            for alt in &alts[1..] {
+                let other = names(alt);
+                let missing = first.iter().filter(|name| !other.contains(name)).map(|name| (name, alt.span));
+                let extra = other.iter().filter(|name| !first.contains(name)).map(|name| (name, alts[0].span));
+                for (name, span) in missing.chain(extra) {
+                    let message = format!("variable `{}` is not bound in all patterns", name);
+                    errors.push(ResolveError { message, span, help: None });
+                }
+            }
+        });
+        self.errors.extend(errors);
+    }
+
+    fn visit_expr(&mut self, node: &'a Node) {
+        let arena = self.arena;
+        match &node.kind {
+            NodeKind::Ident(name) => self.resolve_ident(node, name, "value"),
+            // 한 세그먼트 경로는 `f::<T>`처럼 제네릭 인자가 붙은 이름입니다.
+            // 여러 세그먼트 경로는 타입에 딸린 함수나 외부 경로이므로 여기서는 보지 않습니다.
+            NodeKind::Path(path) if path.segments.len() == 1 => {
+                self.resolve_ident(node, &path.segments[0].ident, "value");
+                self.visit_path(path);
+            },
+            NodeKind::Call(call) => {
+                match &arena[call.callee].kind {
+                    NodeKind::Ident(name) => self.resolve_ident(&arena[call.callee], name, "function"),
+                    _ => self.visit_expr(&arena[call.callee]),
+                }
+                call.args.iter().for_each(|&arg| self.visit_expr(&arena[arg]));
+            },
+            NodeKind::ForLoop(for_loop) => {
+                self.visit_expr(&arena[for_loop.iter]);
+                self.with_scope(|this| {
+                    this.visit_pat(&for_loop.pat);
+                    this.visit_block(&for_loop.body);
+                });
+            },
+            NodeKind::Closure(_) => self.with_scope(|this| visit::walk_expr(this, node)),
+            _ => visit::walk_expr(self, node),
+        }
+    }
+}
+
+// `name`과 편집 거리가 가장 가까운 후보를 찾습니다.
+// 대소문자만 다른 후보가 먼저이고, 그다음은 이름 길이의 1/3 (최소 1) 이하로 떨어진 후보 중 가장 가까운 것입니다.
+fn find_best_match<'c>(candidates: &[(&'c str, &'static str)], name: &str) -> Option<(&'c str, &'static str)> {
+    if let Some(&found) = candidates.iter().find(|(candidate, _)| *candidate != name && candidate.eq_ignore_ascii_case(name)) {
+        return Some(found);
+    }
+    let max_dist = name.chars().count().max(3) / 3;
+    candidates
+        .iter()
+        .map(|&(candidate, descr)| (edit_distance(candidate, name), candidate, descr))
+        .filter(|&(dist, _, _)| dist <= max_dist)
+        .min_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(b.1)))
+        .map(|(_, candidate, descr)| (candidate, descr))
+}
+
+// 두 문자열 사이의 편집 거리입니다. rustc처럼 이웃한 두 글자를 맞바꾸는 것도 한 번의 편집으로 셉니다 (optimal string alignment).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev_prev: Vec<usize> = Vec::new();                     // i - 1 행
+    let mut prev: Vec<usize> = (0..=b.len()).collect();             // i 행
+    for i in 0..a.len() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for j in 0..b.len() {
+            let cost = usize::from(a[i] != b[j]);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+            if i > 0 && j > 0 && a[i] == b[j - 1] && a[i - 1] == b[j] {
+                cur[j + 1] = cur[j + 1].min(prev_prev[j - 1] + 1);
+            }
+        }
+        prev_prev = std::mem::replace(&mut prev, cur);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusttc_ast::arena::AstId;
+    use rusttc_ast::ast::StmtKind;
+    use crate::modules::resolve_modules;
+    use rusttc_lexer::{source_map, tokenize};
+    use rusttc_parse::parser::parse_crate;
+
+    fn resolve(source: &str) -> (Crate, Result<Resolutions, Vec<ResolveError>>) {
+        let krate = resolve_modules(&parse_crate(&tokenize(source)).unwrap()).unwrap();
+        let result = resolve_names(&krate);
+        (krate, result)
+    }
+
+    // 표현식 문장이면 그 표현식의 노드 번호를 돌려줍니다.
+    fn stmt_expr(arena: &AstArena, kind: &StmtKind) -> Option<NodeId> {
+        match kind {
+            StmtKind::Expr(expr) | StmtKind::Semi(expr) => Some(arena[*expr].id),
+            _ => None,
+        }
+    }
+
+    fn errors(source: &str) -> Vec<String> {
+        resolve(source).1.unwrap_err().into_iter().map(|error| error.to_string()).collect()
+    }
+
+    // `main` 본문의 식별자 문장마다, 그 식별자가 가리키는 바인딩의 위치를 돌려줍니다.
+    fn main_uses(source: &str) -> Vec<u32> {
+        let (krate, result) = resolve(source);
+        let resolutions = result.unwrap();
+        let main = krate.items.iter().find_map(|item| match item {
+            Item::Fn(function) if function.name == "main" => function.body.as_ref(),
+            _ => None,
+        });
+        main.unwrap()
+            .stmts
+            .iter()
+            .filter_map(|&stmt| stmt_expr(&krate.arena, &krate.arena[stmt].kind))
+            .filter_map(|id| match resolutions.uses.get(&id)? {
+                Res::Local(def) => Some(resolutions.bindings[def].span.lo),
+                Res::Item(_) => Some(u32::MAX),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_shadowing_links_to_latest_binding() {
+        let source = "fn main() { let x = 1; x; let x = x + 1; x; }";
+        let first = source.find("x =").unwrap() as u32;
+        let second = source.rfind("x =").unwrap() as u32;
+        assert_eq!(main_uses(source), vec![first, second]);
+
+        // `let x = x + 1;`의 오른쪽은 앞의 `x`를 가리킵니다.
+        let (krate, result) = resolve(source);
+        let resolutions = result.unwrap();
+        let main = krate.items.iter().find_map(|item| match item {
+            Item::Fn(function) => function.body.as_ref(),
+            _ => None,
+        });
+        let StmtKind::Let(local) = &krate.arena[main.unwrap().stmts[2]].kind else { panic!() };
+        let NodeKind::BinaryOp(binary_op) = &krate.arena[local.init.unwrap()].kind else { panic!() };
+        let Res::Local(def) = resolutions.uses[&krate.arena[binary_op.left].id] else { panic!() };
+        assert_eq!(resolutions.bindings[&def].span.lo, first);
+    }
+
+    #[test]
+    fn test_error_location_from_source_map() {
+        let source = "fn main() {\n    let count = 1;\n    count + missing;\n}";
+        source_map::clear();
+        source_map::add_file("main.rs", source);
+        assert_eq!(errors(source), ["error: cannot find value `missing` in this scope\n  --> main.rs:3:13"]);
+
+        // match 갈래의 바인딩 오류는 바인딩이 빠진 대안과 겹친 바인딩을 가리키고, 갈래 밖에서는 바인딩이 보이지 않습니다.
+        let source = "fn main() {\n    match (1, 2) {\n        (0, b) | (_, 0) => {}\n        (a, a) => a,\n    }\n    a;\n}";
+        source_map::clear();
+        source_map::add_file("main.rs", source);
+        assert_eq!(errors(source), [
+            "error: variable `b` is not bound in all patterns\n  --> main.rs:3:18",
+            "error: identifier `a` is bound more than once in the same pattern\n  --> main.rs:4:13",
+            "error: cannot find value `a` in this scope\n  --> main.rs:6:5",
+        ]);
+        source_map::clear();
+    }
+
+    #[test]
+    fn test_block_and_loop_scopes() {
+        let source = "fn f(n: i32) -> i32 { n } fn main() { let y = 1; { let y = 2; } y; for y in 0..3 { y; } f; }";
+        let outer = source.find("y = 1").unwrap() as u32;
+        assert_eq!(main_uses(source), vec![outer, u32::MAX]);
+
+        assert!(errors("fn main() { { let z = 1; } z; }")[0].contains("cannot find value `z` in this scope"));
+        assert!(errors("fn main() { for i in 0..3 {} i; }")[0].contains("cannot find value `i`"));
+        assert!(resolve("fn main() { let k = 2; let f = |a: i32| a + k; f(1); }").1.is_ok());
+        assert!(errors("fn main() { let f = |a: i32| a; a; }")[0].contains("`a`"));
+    }
+
+    #[test]
+    fn test_match_arm_scopes() {
+        // 갈래의 바인딩은 그 갈래의 가드와 본문에서만 보입니다. or 패턴의 뒤 대안은 첫 대안의 바인딩을 가리킵니다.
+        let source = "fn main() { let x = 1; match (x, 2) { (0, x) | (x, 0) if x > 1 => x, (y, _) => y }; x; }";
+        let (krate, result) = resolve(source);
+        let resolutions = result.unwrap();
+        let outer = source.find("x = 1").unwrap() as u32;
+        assert_eq!(main_uses(source), vec![outer]);
+        let first_binding = source.find("(0, x)").unwrap() as u32 + 4;
+        let Item::Fn(main) = &krate.items[0] else { panic!("expected fn") };
+        let uses: Vec<u32> = krate
+            .arena
+            .block_descendants(main.body.as_ref().unwrap())
+            .filter_map(|id| match id {
+                AstId::Expr(expr) if krate.arena[expr].kind == NodeKind::Ident("x".to_string()) => resolutions.uses.get(&krate.arena[expr].id),
+                _ => None,
+            })
+            .map(|res| match res {
+                Res::Local(def) => resolutions.bindings[def].span.lo,
+                Res::Item(_) => u32::MAX,
+            })
+            .collect();
+        assert_eq!(uses.iter().filter(|&&lo| lo == first_binding).count(), 2);
+
+        assert!(errors("fn main() { match 1 { y => {} } y; }")[0].contains("cannot find value `y` in this scope"));
+        assert_eq!(errors("fn main() { match (1, 2) { (a, a) => {} } }"), [
+            "error: identifier `a` is bound more than once in the same pattern"
+        ]);
+        assert_eq!(errors("fn main() { match (1, 2) { (0, b) | (_, 0) => {} _ => {} } }"), [
+            "error: variable `b` is not bound in all patterns"
+        ]);
+    }
+
+    #[test]
+    fn test_items_and_self() {
+        let source = "mod m { pub fn g() -> i32 { 1 } } struct Unit; struct P { x: i32 }\n\
+                      impl P { fn get(&self) -> i32 { self.x } }\n\
+                      fn main() { let u = Unit; let v = m::g(); }";
+        assert!(resolve(source).1.is_ok());
+
+        // 안쪽 함수는 바깥 함수의 지역 변수를 볼 수 없습니다.
+        let errors = errors("fn main() { let x = 1; fn inner() -> i32 { x } inner(); }");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("error: cannot find value `x` in this scope"));
+    }
+
+    #[test]
+    fn test_typo_suggestions() {
+        let errors = errors("fn compute() -> i32 { 1 } fn main() { let counter = 1; let value = 2; countr; computee(); valeu; zzz; }");
+        assert_eq!(errors, vec![
+            "error: cannot find value `countr` in this scope\n  = help: a local variable with a similar name exists: `counter`".to_string(),
+            "error: cannot find function `computee` in this scope\n  = help: a function with a similar name exists: `compute`".to_string(),
+            "error: cannot find value `valeu` in this scope\n  = help: a local variable with a similar name exists: `value`".to_string(),
+            "error: cannot find value `zzz` in this scope".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+        assert_eq!(edit_distance("valeu", "value"), 1);
+        assert_eq!(edit_distance("ab", "bca"), 3);
+        assert_eq!(find_best_match(&[("Foo", "a function")], "foo"), Some(("Foo", "a function")));
+        assert_eq!(find_best_match(&[("abc", "a function")], "xyz"), None);
+    }
+}
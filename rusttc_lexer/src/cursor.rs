@@ -0,0 +1,96 @@
+pub struct Cursor<'a> {
+    input: &'a str,
+    len: usize,         // 지금까지 소비한 글자 수
+    pos: u32,           // 파일 맨 앞부터의 바이트 위치
+    offset: u32,        // `input`이 파일에서 시작하는 위치
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Cursor { input, len: 0, pos: 0, offset: 0 }
+    }
+
+    // 파일의 `offset` 위치부터 시작하는 조각을 읽습니다. `pos`는 파일 맨 앞 기준으로 셉니다.
+    pub fn new_at(input: &'a str, offset: u32) -> Self {
+        Cursor { input, len: 0, pos: offset, offset }
+    }
+
+    // 남은 입력입니다. 매번 처음부터 세지 않으므로 글자 하나를 읽는 비용이 일정합니다.
+    fn rest(&self) -> &'a str {
+        &self.input[(self.pos - self.offset) as usize..]
+    }
+
+    pub(crate) fn bump(&mut self) -> char {
+        let char = self.rest().chars().next();
+        if let Some(c) = char {
+            self.len += 1;
+            self.pos += c.len_utf8() as u32;
+        }
+        char.unwrap_or('\0')
+    }
+
+    pub(crate) fn pos(&self) -> u32 {
+        self.pos
+    }
+
+    pub(crate) fn first(&self) -> char {
+        self.rest().chars().next().unwrap_or('\0')
+    }
+
+    pub(crate) fn second(&self) -> char {
+        self.rest().chars().nth(1).unwrap_or('\0')
+    }
+
+    pub(crate) fn is_eof(&self) -> bool {
+        (self.pos - self.offset) as usize >= self.input.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cursor;
+
+    #[test]
+    fn test_new_cursor() {
+        let input = "Hello, world!";
+        let cursor = Cursor::new(input);
+        assert_eq!(cursor.input, input);
+        assert_eq!(cursor.len, 0);
+    }
+
+    #[test]
+    fn test_bump() {
+        let mut cursor = Cursor::new("abc");
+        assert_eq!(cursor.bump(), 'a');
+        assert_eq!(cursor.bump(), 'b');
+        assert_eq!(cursor.bump(), 'c');
+        assert_eq!(cursor.bump(), '\0'); // EOF
+    }
+
+    #[test]
+    fn test_peek() {
+        let cursor = Cursor::new("abc");
+        assert_eq!(cursor.first(), 'a');
+        // `peek` should not advance the lenition
+        assert_eq!(cursor.len, 0);
+    }
+
+    #[test]
+    fn test_is_eof() {
+        let mut cursor = Cursor::new("a");
+        assert!(!cursor.is_eof());
+        cursor.bump();
+        assert!(cursor.is_eof());
+    }
+
+    #[test]
+    fn test_new_at() {
+        let mut cursor = Cursor::new_at("b", 4);
+        assert_eq!(cursor.pos(), 4);
+        assert_eq!(cursor.first(), 'b');
+        assert_eq!(cursor.bump(), 'b');
+        assert!(cursor.is_eof());
+        assert_eq!(cursor.bump(), '\0');
+        assert_eq!(cursor.pos(), 5);
+    }
+}
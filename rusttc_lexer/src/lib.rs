@@ -1,8 +1,13 @@
 mod cursor;
+pub mod source_map;
+mod span;
 
 use cursor::Cursor;
 
-#[derive(Clone, Debug, PartialEq)]
+pub use span::Span;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TokenKind {
     // Multi-char tokens:
     /// "// comment"
@@ -104,6 +109,40 @@ pub enum TokenKind {
     Caret,
     /// "%"
     Percent,
+
+    // Multi-char punctuation tokens:
+    /// "::"
+    PathSep,
+    /// "->"
+    RArrow,
+    /// "=>"
+    FatArrow,
+    /// "=="
+    EqEq,
+    /// "!="
+    Ne,
+    /// "<="
+    Le,
+    /// ">="
+    Ge,
+    /// "&&"
+    AndAnd,
+    /// "||"
+    OrOr,
+    /// ".."
+    DotDot,
+    /// "..="
+    DotDotEq,
+    /// "+="
+    PlusEq,
+    /// "-="
+    MinusEq,
+    /// "*="
+    StarEq,
+    /// "/="
+    SlashEq,
+    /// "%="
+    PercentEq,
     /// "'"
     CharLiteral,
     /// """
@@ -152,29 +191,63 @@ pub enum TokenKind {
 //     Hexadecimal = 16,
 // }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum DocStyle {
     Outer, 
     Inner,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Token {
     pub kind: TokenKind,
     pub text: String,
+    pub span: Span,
 }
 
 impl Token {
     pub fn new(kind: TokenKind, text: String) -> Self {
-        Token { kind, text }
+        Token { kind, text, span: Span::DUMMY }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+}
+
+// 토큰은 위치와 상관없이 종류와 텍스트로 비교합니다.
+impl PartialEq for Token {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.text == other.text
     }
 }
 
 pub fn tokenize(input: &str) -> Vec<Token> {
+    lex(Cursor::new(input))
+}
+
+// 파일의 `offset` 위치에서 시작하는 조각을 토큰화합니다. 스팬은 파일 맨 앞 기준입니다.
+// `offset`은 토큰 경계여야 합니다. 증분 재파싱이 손상된 구간만 다시 읽을 때 씁니다.
+pub fn tokenize_at(input: &str, offset: u32) -> Vec<Token> {
+    lex(Cursor::new_at(input, offset))
+}
+
+// 소스 맵에 등록한 파일 `file`을 토큰화합니다. 스팬마다 파일 번호가 붙습니다.
+pub fn tokenize_file(input: &str, file: u32) -> Vec<Token> {
+    let mut tokens = tokenize(input);
+    for token in &mut tokens {
+        token.span = token.span.with_file(file);
+    }
+    tokens
+}
+
+fn lex(mut cursor: Cursor) -> Vec<Token> {
     let mut tokens = Vec::new();
-    let mut cursor = Cursor::new(input);
 
     while !cursor.is_eof() {
+        let lo = cursor.pos();
         let cur = cursor.bump();
         let token = match cur {
             // Handling whitespace
@@ -185,7 +258,11 @@ pub fn tokenize(input: &str) -> Vec<Token> {
 
             ';' => Token::new(TokenKind::Semi, cur.to_string()),
             ',' => Token::new(TokenKind::Comma, cur.to_string()),
-            '.' => Token::new(TokenKind::Dot, cur.to_string()),
+            '.' => match (cursor.first(), cursor.second()) {
+                ('.', '=') => glue(&mut cursor, TokenKind::DotDotEq, "..="),
+                ('.', _) => glue(&mut cursor, TokenKind::DotDot, ".."),
+                _ => Token::new(TokenKind::Dot, cur.to_string()),
+            },
             '(' => Token::new(TokenKind::OpenParen, cur.to_string()),
             ')' => Token::new(TokenKind::CloseParen, cur.to_string()),
             '{' => Token::new(TokenKind::OpenBrace, cur.to_string()),
@@ -196,17 +273,49 @@ pub fn tokenize(input: &str) -> Vec<Token> {
             '#' => Token::new(TokenKind::Pound, cur.to_string()),
             '~' => Token::new(TokenKind::Tilde, cur.to_string()),
             '?' => Token::new(TokenKind::Question, cur.to_string()),
-            ':' => Token::new(TokenKind::Colon, cur.to_string()),
+            ':' => match cursor.first() {
+                ':' => glue(&mut cursor, TokenKind::PathSep, "::"),
+                _ => Token::new(TokenKind::Colon, cur.to_string()),
+            },
             '$' => Token::new(TokenKind::Dollar, cur.to_string()),
-            '=' => Token::new(TokenKind::Eq, cur.to_string()),
-            '!' => Token::new(TokenKind::Bang, cur.to_string()),
-            '<' => Token::new(TokenKind::Lt, cur.to_string()),
-            '>' => Token::new(TokenKind::Gt, cur.to_string()),
-            '-' => Token::new(TokenKind::Minus, cur.to_string()),
-            '&' => Token::new(TokenKind::And, cur.to_string()),
-            '|' => Token::new(TokenKind::Or, cur.to_string()),
-            '+' => Token::new(TokenKind::Plus, cur.to_string()),
-            '*' => Token::new(TokenKind::Star, cur.to_string()),
+            '=' => match cursor.first() {
+                '=' => glue(&mut cursor, TokenKind::EqEq, "=="),
+                '>' => glue(&mut cursor, TokenKind::FatArrow, "=>"),
+                _ => Token::new(TokenKind::Eq, cur.to_string()),
+            },
+            '!' => match cursor.first() {
+                '=' => glue(&mut cursor, TokenKind::Ne, "!="),
+                _ => Token::new(TokenKind::Bang, cur.to_string()),
+            },
+            '<' => match cursor.first() {
+                '=' => glue(&mut cursor, TokenKind::Le, "<="),
+                _ => Token::new(TokenKind::Lt, cur.to_string()),
+            },
+            '>' => match cursor.first() {
+                '=' => glue(&mut cursor, TokenKind::Ge, ">="),
+                _ => Token::new(TokenKind::Gt, cur.to_string()),
+            },
+            '-' => match cursor.first() {
+                '>' => glue(&mut cursor, TokenKind::RArrow, "->"),
+                '=' => glue(&mut cursor, TokenKind::MinusEq, "-="),
+                _ => Token::new(TokenKind::Minus, cur.to_string()),
+            },
+            '&' => match cursor.first() {
+                '&' => glue(&mut cursor, TokenKind::AndAnd, "&&"),
+                _ => Token::new(TokenKind::And, cur.to_string()),
+            },
+            '|' => match cursor.first() {
+                '|' => glue(&mut cursor, TokenKind::OrOr, "||"),
+                _ => Token::new(TokenKind::Or, cur.to_string()),
+            },
+            '+' => match cursor.first() {
+                '=' => glue(&mut cursor, TokenKind::PlusEq, "+="),
+                _ => Token::new(TokenKind::Plus, cur.to_string()),
+            },
+            '*' => match cursor.first() {
+                '=' => glue(&mut cursor, TokenKind::StarEq, "*="),
+                _ => Token::new(TokenKind::Star, cur.to_string()),
+            },
             
             '/' => {
                 match cursor.first() {
@@ -269,6 +378,7 @@ pub fn tokenize(input: &str) -> Vec<Token> {
             
                         Token::new(TokenKind::BlockComment { doc_style, terminated }, comment_text)
                     },
+                    '=' => glue(&mut cursor, TokenKind::SlashEq, "/="),
                     _ => {
                         Token::new(TokenKind::Slash, "/".to_string())
                     },
@@ -277,7 +387,11 @@ pub fn tokenize(input: &str) -> Vec<Token> {
             '\'' => {
                 let mut literal = cur.to_string();
                 while !cursor.is_eof() && cursor.first() != '\'' {
-                    literal.push(cursor.bump());
+                    let c = cursor.bump();
+                    literal.push(c);
+                    if c == '\\' && !cursor.is_eof() {
+                        literal.push(cursor.bump()); // `'\''`의 이스케이프된 따옴표
+                    }
                 }
                 if !cursor.is_eof() {
                     literal.push(cursor.bump()); // 마지막 단일 따옴표 추가
@@ -287,7 +401,11 @@ pub fn tokenize(input: &str) -> Vec<Token> {
             '"' => {
                 let mut literal = cur.to_string();
                 while !cursor.is_eof() && cursor.first() != '"' {
-                    literal.push(cursor.bump());
+                    let c = cursor.bump();
+                    literal.push(c);
+                    if c == '\\' && !cursor.is_eof() {
+                        literal.push(cursor.bump()); // `"\""`의 이스케이프된 따옴표
+                    }
                 }
                 if !cursor.is_eof() {
                     literal.push(cursor.bump()); // 마지막 이중 따옴표 추가
@@ -298,23 +416,47 @@ pub fn tokenize(input: &str) -> Vec<Token> {
                 }
             },
             '^' => Token::new(TokenKind::Caret, cur.to_string()),
-            '%' => Token::new(TokenKind::Percent, cur.to_string()),
+            '%' => match cursor.first() {
+                '=' => glue(&mut cursor, TokenKind::PercentEq, "%="),
+                _ => Token::new(TokenKind::Percent, cur.to_string()),
+            },
             ('0'..='9') => {
-                let num = cur.to_string() + &consume_while(&mut cursor, |a| a.is_digit(10));
-                Token::new(TokenKind::Literal , num.to_string())
+                let mut num = cur.to_string() + &consume_while(&mut cursor, |a| a.is_ascii_digit() || a == '_');
+                // 튜플 필드 `t.0.1`은 필드 접근 두 번이므로, 점 바로 뒤의 숫자에서는 소수부를 읽지 않습니다.
+                let after_dot = tokens.last().is_some_and(|token: &Token| token.kind == TokenKind::Dot);
+                if !after_dot && cursor.first() == '.' && cursor.second().is_ascii_digit() {
+                    num.push(cursor.bump());
+                    num += &consume_while(&mut cursor, |a| a.is_ascii_digit() || a == '_');
+                }
+                if matches!(cursor.first(), 'e' | 'E') && (cursor.second().is_ascii_digit() || matches!(cursor.second(), '+' | '-')) {
+                    num.push(cursor.bump());
+                    num.push(cursor.bump());
+                    num += &consume_while(&mut cursor, |a| a.is_ascii_digit() || a == '_');
+                }
+                // 접미사(`u8`, `f32`)와 `0x` 같은 진법 접두사 뒤의 숫자까지 한 토큰입니다.
+                num += &consume_while(&mut cursor, |a| a.is_alphanumeric() || a == '_');
+                Token::new(TokenKind::Literal, num)
             },
-            'a'..='z' | 'A'..='Z' => {
-                let str = cur.to_string() + &consume_while(&mut cursor, |a| a.is_alphabetic());
+            'a'..='z' | 'A'..='Z' | '_' => {
+                let str = cur.to_string() + &consume_while(&mut cursor, |a| a.is_alphanumeric() || a == '_');
                 Token::new(TokenKind::Ident, str.to_string())
             },
             '\0' => Token::new(TokenKind::EOF, cur.to_string()),
-            _ => Token::new(TokenKind::Error, "Err".to_string())
+            _ => Token::new(TokenKind::Error, cur.to_string())
         };
-        tokens.push(token);
+        tokens.push(token.with_span(Span::new(lo, cursor.pos())));
     }
     tokens
 }
 
+// 첫 글자는 이미 소비된 상태에서, 나머지 글자를 소비해 여러 글자 토큰을 만듭니다.
+fn glue(cursor: &mut Cursor, kind: TokenKind, text: &str) -> Token {
+    for _ in 1..text.chars().count() {
+        cursor.bump();
+    }
+    Token::new(kind, text.to_string())
+}
+
 fn consume_while<F>(cursor: &mut Cursor, mut condition: F) -> String
 where
     F: FnMut(char) -> bool {
@@ -414,5 +556,75 @@ mod tests {
         assert_eq!(tokens[15].kind, TokenKind::CloseBrace);
     }
 
+    #[test]
+    fn test_multi_char_punctuation() {
+        let input = "a::b -> => == != <= >= && || .. ..= += -=";
+        let kinds: Vec<TokenKind> = tokenize(input)
+            .into_iter()
+            .map(|token| token.kind)
+            .filter(|kind| *kind != TokenKind::Whitespace)
+            .collect();
+        assert_eq!(kinds, vec![
+            TokenKind::Ident, TokenKind::PathSep, TokenKind::Ident,
+            TokenKind::RArrow, TokenKind::FatArrow, TokenKind::EqEq, TokenKind::Ne,
+            TokenKind::Le, TokenKind::Ge, TokenKind::AndAnd, TokenKind::OrOr,
+            TokenKind::DotDot, TokenKind::DotDotEq, TokenKind::PlusEq, TokenKind::MinusEq,
+        ]);
+    }
+
+    #[test]
+    fn test_identifiers_with_underscores_and_digits() {
+        let tokens = tokenize("_tmp x1 snake_case");
+        assert_eq!(tokens[0].text, "_tmp");
+        assert_eq!(tokens[2].text, "x1");
+        assert_eq!(tokens[4].kind, TokenKind::Ident);
+        assert_eq!(tokens[4].text, "snake_case");
+    }
+
+    #[test]
+    fn test_token_spans() {
+        let source = "let s = \"가나\";\n  x";
+        let tokens = tokenize(source);
+        let spans: Vec<(&str, &str)> = tokens
+            .iter()
+            .map(|token| (token.text.as_str(), &source[token.span.lo as usize..token.span.hi as usize]))
+            .collect();
+        assert!(spans.iter().all(|(text, slice)| text == slice));
+        assert_eq!(tokens[6].span, Span::new(8, 16));
+        assert_eq!(tokens.last().unwrap().span.line_col(source), (2, 3));
+        // 위치가 달라도 같은 토큰입니다.
+        assert_eq!(tokens[0], Token::new(TokenKind::Ident, "let".to_string()));
+    }
+
+    #[test]
+    fn test_error_token_keeps_text() {
+        // 알 수 없는 문자도 원래 텍스트를 가지므로 토큰을 이으면 소스가 그대로 나옵니다.
+        let source = "a `b` \\";
+        let tokens = tokenize(source);
+        assert_eq!(tokens[2], Token::new(TokenKind::Error, "`".to_string()));
+        assert_eq!(tokens.iter().map(|token| token.text.as_str()).collect::<String>(), source);
+    }
+
+    #[test]
+    fn test_tokenize_at() {
+        let source = "let x = \"가\"; y";
+        let tokens = tokenize_at(&source[8..14], 8);
+        assert_eq!(tokens, tokenize(&source[8..14]));
+        assert_eq!(tokens[0].span, Span::new(8, 13));
+        assert_eq!(tokens[1].span, Span::new(13, 14));
+    }
+
+    #[test]
+    fn test_number_and_char_literals() {
+        let texts = |source: &str| -> Vec<String> {
+            tokenize(source).into_iter().filter(|token| token.kind != TokenKind::Whitespace).map(|token| token.text).collect()
+        };
+        assert_eq!(texts("1_000u8 2.5f32 1e-3 0xffu8 '\\'' 'a'"), ["1_000u8", "2.5f32", "1e-3", "0xffu8", "'\\''", "'a'"]);
+        // 범위와 튜플 필드의 점은 소수점이 아닙니다.
+        assert_eq!(texts("0..2 t.0.1"), ["0", "..", "2", "t", ".", "0", ".", "1"]);
+        // 문자열 안의 이스케이프된 따옴표는 리터럴을 끝내지 않습니다.
+        assert_eq!(texts(r#""a\"b" "\\" x"#), [r#""a\"b""#, r#""\\""#, "x"]);
+    }
+
     // ... 추가적인 테스트 케이스들 ...
 }
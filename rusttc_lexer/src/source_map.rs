@@ -0,0 +1,34 @@
+// 컴파일하는 파일들의 이름과 내용입니다. 스팬의 `file`이 여기의 번호입니다.
+//
+// 오류를 출력할 때마다 소스를 넘기지 않도록 스레드마다 하나를 둡니다. 로더가 파일을 읽을 때
+// 등록하고, 오류의 `Display`가 `Span::location`으로 위치를 찾습니다.
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct SourceFile {
+    name: String,
+    source: Rc<str>,
+}
+
+thread_local! {
+    static FILES: RefCell<Vec<SourceFile>> = const { RefCell::new(Vec::new()) };
+}
+
+// 파일을 등록하고 번호를 돌려줍니다. 처음 등록한 파일이 0번입니다.
+pub fn add_file(name: impl Into<String>, source: &str) -> u32 {
+    FILES.with(|files| {
+        let mut files = files.borrow_mut();
+        files.push(SourceFile { name: name.into(), source: Rc::from(source) });
+        (files.len() - 1) as u32
+    })
+}
+
+// 등록한 파일을 모두 지웁니다.
+pub fn clear() {
+    FILES.with(|files| files.borrow_mut().clear());
+}
+
+pub(crate) fn with_file<R>(file: u32, f: impl FnOnce(&str, &str) -> R) -> Option<R> {
+    let entry = FILES.with(|files| files.borrow().get(file as usize).map(|file| (file.name.clone(), file.source.clone())));
+    entry.map(|(name, source)| f(&name, &source))
+}
@@ -0,0 +1,87 @@
+use crate::source_map;
+
+// 소스 파일 안의 바이트 범위 `[lo, hi)`입니다.
+// 매크로 전개로 만든 토큰은 매크로를 호출한 자리의 범위를 가집니다.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Span {
+    pub lo: u32,                // 시작 위치 (포함)
+    pub hi: u32,                // 끝 위치 (제외)
+    // 범위가 속한 파일의 번호입니다. 모듈 파일마다 오프셋이 0부터 시작하므로 파일로 구분합니다.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub file: u32,
+}
+
+#[cfg(feature = "serde")]
+fn is_zero(file: &u32) -> bool {
+    *file == 0
+}
+
+impl Span {
+    // 소스에 없는, 컴파일러가 만든 노드와 토큰의 범위입니다.
+    pub const DUMMY: Span = Span { lo: 0, hi: 0, file: 0 };
+
+    pub fn new(lo: u32, hi: u32) -> Self {
+        Span { lo, hi, file: 0 }
+    }
+
+    pub fn with_file(self, file: u32) -> Span {
+        Span { file, ..self }
+    }
+
+    // 같은 파일에서 시작 위치만 바꾼 범위입니다.
+    pub fn with_lo(self, lo: u32) -> Span {
+        Span { lo, ..self }
+    }
+
+    // 같은 파일에서 끝 위치만 바꾼 범위입니다.
+    pub fn with_hi(self, hi: u32) -> Span {
+        Span { hi, ..self }
+    }
+
+    pub fn is_dummy(self) -> bool {
+        self == Span::DUMMY
+    }
+
+    // `self`의 시작부터 `end`의 끝까지 이어지는 범위입니다. 한쪽이 DUMMY면 다른 쪽을 씁니다.
+    pub fn to(self, end: Span) -> Span {
+        match (self.is_dummy(), end.is_dummy()) {
+            (true, _) => end,
+            (_, true) => self,
+            _ => Span { lo: self.lo.min(end.lo), hi: self.hi.max(end.hi), file: self.file },
+        }
+    }
+
+    // 끝 위치의 빈 범위입니다.
+    pub fn shrink_to_hi(self) -> Span {
+        Span { lo: self.hi, ..self }
+    }
+
+    // `source`에서 이 범위가 시작하는 줄과 열입니다. 둘 다 1부터 셉니다.
+    pub fn line_col(self, source: &str) -> (usize, usize) {
+        let before = &source[..(self.lo as usize).min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let col = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        (line, col)
+    }
+
+    // 소스 맵에 등록된 파일이면 `path:line:col`을 돌려줍니다.
+    // DUMMY이거나 파일을 등록하지 않았으면(파일 없이 파싱한 테스트 등) 위치를 알 수 없습니다.
+    pub fn location(self) -> Option<String> {
+        if self.is_dummy() {
+            return None;
+        }
+        source_map::with_file(self.file, |name, source| {
+            let (line, col) = self.line_col(source);
+            format!("{}:{}:{}", name, line, col)
+        })
+    }
+
+    // 소스 맵에 등록된 파일이면 이 범위의 소스 텍스트를 돌려줍니다.
+    pub fn snippet(self) -> Option<String> {
+        if self.is_dummy() {
+            return None;
+        }
+        source_map::with_file(self.file, |_, source| source.get(self.lo as usize..self.hi as usize).map(str::to_string)).flatten()
+    }
+}
@@ -1,87 +1,153 @@
-mod parser {
-    use rusttc_lexer::{Token, TokenKind};
-    use rusttc_ast::ast::{Node, BinaryOp};
-    pub fn parse(tokens: &[Token]) -> Vec<Node> {
-        let mut nodes = Vec::new();
-        let mut iter = tokens.iter().peekable();
-
-        while let Some(token) = iter.next() {
-            match &token.kind {
-                TokenKind::Number => {
-                    if let Ok(value) = token.text.parse() {
-                        nodes.push(Node::Number(value));
-                    }
-                },
-                TokenKind::Ident => nodes.push(Node::Ident(token.text.clone())),
-                TokenKind::Plus | TokenKind::Minus | TokenKind::Star | TokenKind::Slash => {
-                    if let (Some(left), Some(right)) = (nodes.pop(), iter.next()) {
-                        nodes.push(Node::BinaryOp(Box::new(BinaryOp {
-                            left,
-                            operator: token.clone(),
-                            right: match right.kind {
-                                TokenKind::Number => {
-                                    Node::Number(right.text.parse().unwrap_or(0))
-                                }
-                                TokenKind::Ident => Node::Ident(right.text.clone()),
-                                _ => Node::Number(0), // 간단한 예시를 위한 기본값 처리
-                            },
-                        })));
-                    }
-                },
-                _ => {}
-            }
-        }
-
-        nodes
-    }
-}
+pub mod parser;
+pub mod syntax_kind;
 
 #[cfg(test)]
 mod tests {
-    use rusttc_lexer::{Token, TokenKind};
-    use rusttc_ast::ast::{Node, BinaryOp};
+    use rusttc_lexer::{tokenize, Token, TokenKind};
+    use rusttc_ast::ast::{
+        ArrayLen, AstArena, AttrStyle, Attribute, Block, ExprId, FormatAlign, FormatArg, FormatArgKind, FormatArgs, FormatCount, FormatPiece,
+        FloatTy, FormatPlaceholder, FormatSpec, FormatTrait, IntTy, Lit, MetaItem, MetaItemKind, NestedMeta, Node, NodeId, NodeKind, BinaryOp, CaptureBy, Closure, ClosureParam, GenericParam, Item, Local, MethodCall, Pat, PatKind, Path,
+        PathSegment, Range, QSelf, SelfKind, Stmt, StmtId, StmtKind, Ty, TyKind, UseTree, UseTreeKind, Visibility,
+    };
+
+    use rusttc_ast::pretty::crate_to_string;
+
+    use crate::parser::{parse, parse_crate, parse_crate_within};
+
+    // 기대하는 트리는 따로 만든 저장소에 쌓고 `expr_eq`로 모양만 비교합니다.
+    fn expr(arena: &mut AstArena, kind: NodeKind) -> ExprId {
+        arena.alloc_expr(Node::from(kind))
+    }
+
+    fn number(arena: &mut AstArena, value: u128) -> ExprId {
+        expr(arena, NodeKind::Int(value, None))
+    }
 
-    use crate::parser::parse;
+    fn ident(arena: &mut AstArena, name: &str) -> ExprId {
+        expr(arena, NodeKind::Ident(name.to_string()))
+    }
+
+    fn binary(arena: &mut AstArena, left: ExprId, kind: TokenKind, text: &str, right: ExprId) -> ExprId {
+        let operator = Token::new(kind, text.to_string());
+        expr(arena, NodeKind::BinaryOp(BinaryOp { left, operator, right }))
+    }
     
+    #[test]
+    fn test_parse_literals() {
+        let kinds = |source: &str| -> Result<Vec<NodeKind>, String> {
+            let tokens: Vec<Token> = tokenize(source).into_iter().filter(|token| token.kind != TokenKind::Whitespace).collect();
+            let krate = parse_crate(&tokens).map_err(|error| error.message)?;
+            let Item::Fn(function) = &krate.items[0] else { panic!() };
+            let body = function.body.as_ref().unwrap();
+            Ok(body.stmts.iter().map(|&stmt| match &krate.arena[stmt].kind {
+                StmtKind::Semi(expr) => krate.arena[*expr].kind.clone(),
+                _ => panic!(),
+            }).collect())
+        };
+        assert_eq!(
+            kinds("fn f() { 1_000; 255u8; 0xff_i64; 1.5; 2f32; 1e3f64; 'a'; '\\n'; }").unwrap(),
+            [
+                NodeKind::Int(1000, None),
+                NodeKind::Int(255, Some(IntTy::U8)),
+                NodeKind::Int(255, Some(IntTy::I64)),
+                NodeKind::Float(1.5, None),
+                NodeKind::Float(2.0, Some(FloatTy::F32)),
+                NodeKind::Float(1000.0, Some(FloatTy::F64)),
+                NodeKind::Char('a'),
+                NodeKind::Char('\n'),
+            ],
+        );
+        assert_eq!(kinds("fn f() { 1.5u8; }").unwrap_err(), "invalid suffix `u8` for float literal");
+        assert_eq!(kinds("fn f() { 1x; }").unwrap_err(), "invalid suffix `x` for number literal");
+        assert_eq!(kinds("fn f() { 340282366920938463463374607431768211456; }").unwrap_err(), "integer literal is too large");
+        assert_eq!(kinds("fn f() { 'ab'; }").unwrap_err(), "character literal may only contain one codepoint");
+
+    }
+
+    #[test]
+    fn test_parse_char_and_string_escapes() {
+        let parse = |source: &str| -> Result<Vec<NodeKind>, String> {
+            let krate = parse_crate(&tokenize(&format!("fn f() {{ {} }}", source))).map_err(|error| error.message)?;
+            let Item::Fn(function) = &krate.items[0] else { panic!() };
+            let body = function.body.as_ref().unwrap();
+            Ok(body.stmts.iter().map(|&stmt| match &krate.arena[stmt].kind {
+                StmtKind::Semi(expr) => krate.arena[*expr].kind.clone(),
+                _ => panic!(),
+            }).collect())
+        };
+        assert_eq!(
+            parse(r"'\x41'; '\x7f'; '\u{41}'; '\u{1F600}'; '\u{10_FFFF}'; '\'';").unwrap(),
+            [
+                NodeKind::Char('A'),
+                NodeKind::Char('\x7f'),
+                NodeKind::Char('A'),
+                NodeKind::Char('\u{1F600}'),
+                NodeKind::Char('\u{10FFFF}'),
+                NodeKind::Char('\''),
+            ],
+        );
+        // 문자열은 이스케이프를 검사만 하고 소스에 쓴 그대로 둡니다.
+        assert_eq!(
+            parse(r#""a\x41\u{1F600}\"\n";"#).unwrap(),
+            [NodeKind::Str(r#"a\x41\u{1F600}\"\n"#.to_string())],
+        );
+
+        for (source, message) in [
+            (r"'\x80';", "out of range hex escape: `\\x80`\n  = note: must be a character in the range [\\x00-\\x7f]"),
+            (r"'\x4';", "numeric character escape is too short"),
+            (r"'\x4g';", "invalid character in numeric character escape: `g`"),
+            (r"'\u41';", "incorrect unicode escape sequence\n  = help: format of unicode escape sequences is `\\u{...}`"),
+            (r"'\u{}';", "empty unicode escape"),
+            (r"'\u{41';", "unterminated unicode escape"),
+            (r"'\u{4z}';", "invalid character in unicode escape: `z`"),
+            (r"'\u{1234567}';", "overlong unicode escape\n  = note: must have at most 6 hex digits"),
+            (r"'\u{D800}';", "invalid unicode character escape\n  = help: unicode escape must not be a surrogate"),
+            (r"'\u{110000}';", "invalid unicode character escape\n  = help: unicode escape must be at most 10FFFF"),
+            (r#""ok \x80";"#, "out of range hex escape: `\\x80`\n  = note: must be a character in the range [\\x00-\\x7f]"),
+            (r#""\u{D800}";"#, "invalid unicode character escape\n  = help: unicode escape must not be a surrogate"),
+            (r#""\q";"#, "unknown character escape: `q`"),
+        ] {
+            assert_eq!(parse(source).unwrap_err(), message, "{}", source);
+        }
+    }
+
     #[test]
     fn test_parse_number() {
         let tokens = vec![
-            Token::new(TokenKind::Number, "123".to_string()),
-            Token::new(TokenKind::Eof, "".to_string()),
+            Token::new(TokenKind::Literal, "123".to_string()),
+            Token::new(TokenKind::EOF, "".to_string()),
         ];
-        let result = parse(&tokens);
-        assert_eq!(result, vec![Node::Number(123)]);
+        let (arena, result) = parse(&tokens);
+        assert_eq!(result.len(), 1);
+        assert_eq!(arena[result[0]], Node::from(NodeKind::Int(123, None)));
     }
 
     #[test]
     fn test_parse_ident() {
         let tokens = vec![
             Token::new(TokenKind::Ident, "x".to_string()),
-            Token::new(TokenKind::Eof, "".to_string()),
+            Token::new(TokenKind::EOF, "".to_string()),
         ];
-        let result = parse(&tokens);
-        assert_eq!(result, vec![Node::Ident("x".to_string())]);
+        let (arena, result) = parse(&tokens);
+        assert_eq!(result.len(), 1);
+        assert_eq!(arena[result[0]], Node::from(NodeKind::Ident("x".to_string())));
     }
 
     #[test]
     fn test_parse_binary_op() {
         let tokens = vec![
-            Token::new(TokenKind::Number, "2".to_string()),
+            Token::new(TokenKind::Literal, "2".to_string()),
             Token::new(TokenKind::Plus, "+".to_string()),
-            Token::new(TokenKind::Number, "3".to_string()),
-            Token::new(TokenKind::Eof, "".to_string()),
+            Token::new(TokenKind::Literal, "3".to_string()),
+            Token::new(TokenKind::EOF, "".to_string()),
         ];
-        let result = parse(&tokens);
-        assert_eq!(
-            result,
-            vec![
-                Node::BinaryOp(Box::new(BinaryOp {
-                    left: Node::Number(2),
-                    operator: Token::new(TokenKind::Plus, "+".to_string()),
-                    right: Node::Number(3),
-                }))
-            ]
-        );
+        let (arena, result) = parse(&tokens);
+
+        let mut expected = AstArena::default();
+        let (left, right) = (number(&mut expected, 2), number(&mut expected, 3));
+        let sum = binary(&mut expected, left, TokenKind::Plus, "+", right);
+        assert_eq!(result.len(), 1);
+        assert!(arena.expr_eq(result[0], &expected, sum));
     }
 
     #[test]
@@ -89,11 +155,705 @@ mod tests {
         let tokens = vec![
             Token::new(TokenKind::Ident, "x".to_string()),
             Token::new(TokenKind::Star, "*".to_string()),
-            Token::new(TokenKind::Number, "5".to_string()),
+            Token::new(TokenKind::Literal, "5".to_string()),
             Token::new(TokenKind::Minus, "-".to_string()),
-            Token::new(TokenKind::Number, "3".to_string()),
-            Token::new(TokenKind::Eof, "".to_string()),
+            Token::new(TokenKind::Literal, "3".to_string()),
+            Token::new(TokenKind::EOF, "".to_string()),
         ];
-        // 예상되는 복잡한 표현식에 대한 결과를 구현합니다.
+        let (arena, result) = parse(&tokens);
+
+        let mut expected = AstArena::default();
+        let (x, five, three) = (ident(&mut expected, "x"), number(&mut expected, 5), number(&mut expected, 3));
+        let product = binary(&mut expected, x, TokenKind::Star, "*", five);
+        let difference = binary(&mut expected, product, TokenKind::Minus, "-", three);
+        assert_eq!(result.len(), 1);
+        assert!(arena.expr_eq(result[0], &expected, difference));
+    }
+
+    #[test]
+    fn test_parse_impl_block() {
+        let source = "impl Point {
+            fn new(x: i32) -> Self { Self { x: x } }
+            fn len(&self) -> i32 { self.x }
+            fn grow(&mut self, by: i32) { self.x += by; }
+            fn into_x(self) -> i32 { self.x }
+        }";
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let Item::Impl(imp) = &krate.items[0] else { panic!("expected impl block") };
+
+        assert_eq!(imp.self_ty, Ty::from(TyKind::Path(Path::from_ident("Point"))));
+        let receivers: Vec<Option<SelfKind>> = imp.items.iter().map(|f| f.self_param).collect();
+        assert_eq!(receivers, vec![
+            None,
+            Some(SelfKind::Ref),
+            Some(SelfKind::RefMut),
+            Some(SelfKind::Value { mutable: false }),
+        ]);
+        assert_eq!(imp.items[0].ret, Some(Ty::from(TyKind::Path(Path::from_ident("Self")))));
+        assert_eq!(imp.items[2].params.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_trait_and_trait_impl() {
+        let source = "
+            trait Shape { fn area(&self) -> f64; fn describe(&self) { self.area(); } }
+            impl Shape for Circle { fn area(&self) -> f64 { self.r } }
+        ";
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let Item::Trait(shape) = &krate.items[0] else { panic!("expected trait") };
+        assert_eq!(shape.name, "Shape");
+        assert!(shape.items[0].body.is_none());
+        assert!(shape.items[1].body.is_some());
+
+        let Item::Impl(imp) = &krate.items[1] else { panic!("expected impl") };
+        assert_eq!(imp.of_trait, Some(Path::from_ident("Shape")));
+        assert_eq!(imp.self_ty, Ty::from(TyKind::Path(Path::from_ident("Circle"))));
+    }
+
+    #[test]
+    fn test_parse_generic_bounds_and_qualified_path() {
+        let source = "fn show<T: Shape + Named, U>(t: &T) { <T as Shape>::area(t) }";
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let Item::Fn(show) = &krate.items[0] else { panic!("expected fn") };
+        assert_eq!(show.generics.params, vec![
+            GenericParam { name: "T".to_string(), bounds: vec![Path::from_ident("Shape"), Path::from_ident("Named")] },
+            GenericParam { name: "U".to_string(), bounds: vec![] },
+        ]);
+
+        let arena = &krate.arena;
+        let StmtKind::Expr(call) = arena[show.body.as_ref().unwrap().stmts[0]].kind else { panic!("expected stmt") };
+        let NodeKind::Call(call) = &arena[call].kind else { panic!("expected call") };
+        let qself = QSelf { ty: Ty::from(TyKind::Path(Path::from_ident("T"))), trait_path: Path::from_ident("Shape") };
+        assert_eq!(arena[call.callee], Node::from(NodeKind::QualifiedPath(Box::new(qself), Path::from_ident("area"))));
+    }
+
+    #[test]
+    fn test_parse_fn_without_body_outside_trait() {
+        let error = parse_crate(&tokenize("fn area(&self) -> f64;")).unwrap_err();
+        assert_eq!(error.message, "expected `{`, found `;`");
+    }
+
+    #[test]
+    fn test_parse_method_call_and_path() {
+        let krate = parse_crate(&tokenize("fn main() { Point::new(1).len() }")).unwrap();
+        let Item::Fn(main) = &krate.items[0] else { panic!("expected fn") };
+        let arena = &krate.arena;
+        let StmtKind::Expr(call) = arena[main.body.as_ref().unwrap().stmts[0]].kind else { panic!("expected stmt") };
+        let NodeKind::MethodCall(MethodCall { receiver, method, args }) = &arena[call].kind else { panic!("expected method call") };
+        let NodeKind::Call(new) = &arena[*receiver].kind else { panic!("expected call") };
+
+        assert_eq!(method, "len");
+        assert!(args.is_empty());
+        assert_eq!(arena[new.callee], Node::from(NodeKind::Path(Path {
+            segments: vec![
+                PathSegment::from_ident("Point"),
+                PathSegment::from_ident("new"),
+            ],
+        })));
+        assert_eq!(new.args.len(), 1);
+        assert_eq!(arena[new.args[0]], Node::from(NodeKind::Int(1, None)));
+    }
+
+    #[test]
+    fn test_parse_struct_literal_not_allowed_in_condition() {
+        let krate = parse_crate(&tokenize("fn f() { if x { y } else { z } }")).unwrap();
+        let Item::Fn(f) = &krate.items[0] else { panic!() };
+        let StmtKind::Expr(expr) = krate.arena[f.body.as_ref().unwrap().stmts[0]].kind else { panic!("expected stmt") };
+        assert!(matches!(krate.arena[expr].kind, NodeKind::If(_)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_crate(&tokenize("fn main() { let x = 1 }")).unwrap_err();
+        assert_eq!(error.message, "expected `;`, found `}`");
+        let error = parse_crate(&tokenize("fn main() { a < b < c }")).unwrap_err();
+        assert_eq!(error.message, "comparison operators cannot be chained");
+    }
+
+    #[test]
+    fn test_parse_generic_struct_impl_and_turbofish() {
+        let source = "
+            struct Pair<T> { a: T, b: T }
+            impl<T> Pair<T> { fn first(&self) -> T { self.a } }
+            fn main() { let p: Pair<i32> = Pair::<i32>::new(1, 2); max::<u8>(1, 2); }
+        ";
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let Item::Struct(pair) = &krate.items[0] else { panic!("expected struct") };
+        assert_eq!(pair.generics.params[0].name, "T");
+
+        let Item::Impl(imp) = &krate.items[1] else { panic!("expected impl") };
+        assert_eq!(imp.generics.params.len(), 1);
+        assert_eq!(imp.self_ty.to_string(), "Pair<T>");
+
+        let Item::Fn(main) = &krate.items[2] else { panic!("expected fn") };
+        let arena = &krate.arena;
+        let stmts = &main.body.as_ref().unwrap().stmts;
+        let StmtKind::Let(local) = &arena[stmts[0]].kind else { panic!("expected let") };
+        assert_eq!(local.ty.as_ref().unwrap().to_string(), "Pair<i32>");
+        let NodeKind::Call(new) = &arena[local.init.unwrap()].kind else { panic!("expected call") };
+        let i32_ty = Ty::from(TyKind::Path(Path::from_ident("i32")));
+        assert_eq!(arena[new.callee], Node::from(NodeKind::Path(Path {
+            segments: vec![
+                PathSegment { ident: "Pair".to_string(), args: vec![i32_ty], output: None },
+                PathSegment::from_ident("new"),
+            ],
+        })));
+
+        let StmtKind::Semi(max) = arena[stmts[1]].kind else { panic!("expected stmt") };
+        let NodeKind::Call(max) = &arena[max].kind else { panic!("expected call") };
+        let u8_ty = Ty::from(TyKind::Path(Path::from_ident("u8")));
+        assert_eq!(arena[max.callee], Node::from(NodeKind::Path(Path { segments: vec![PathSegment { ident: "max".to_string(), args: vec![u8_ty], output: None }] })));
+    }
+
+    #[test]
+    fn test_parse_generic_comparison_is_not_turbofish() {
+        // 표현식 위치의 `a < b`는 제네릭 인자가 아니라 비교입니다.
+        let krate = parse_crate(&tokenize("fn f() -> bool { a < b }")).unwrap();
+        let Item::Fn(f) = &krate.items[0] else { panic!() };
+        let StmtKind::Expr(expr) = krate.arena[f.body.as_ref().unwrap().stmts[0]].kind else { panic!("expected stmt") };
+        assert!(matches!(&krate.arena[expr].kind, NodeKind::BinaryOp(op) if op.operator.kind == TokenKind::Lt));
+    }
+
+    #[test]
+    fn test_parse_closures_and_fn_bounds() {
+        let source = "fn apply<F: Fn(i32) -> i32>(f: F) { let a = |x| x + 1; let b = move || {}; let c = |x: i32, _| -> i32 { x }; }";
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let Item::Fn(apply) = &krate.items[0] else { panic!("expected fn") };
+        assert_eq!(apply.generics.params[0].bounds[0].to_string(), "Fn(i32) -> i32");
+
+        let arena = &krate.arena;
+        let closures: Vec<&Closure> = apply.body.as_ref().unwrap().stmts.iter().map(|&stmt| match &arena[stmt].kind {
+            StmtKind::Let(Local { init: Some(init), .. }) => match &arena[*init].kind {
+                NodeKind::Closure(closure) => closure.as_ref(),
+                _ => panic!("expected closure"),
+            },
+            _ => panic!("expected let"),
+        }).collect();
+        assert_eq!(closures[0].capture_by, CaptureBy::Ref);
+        assert_eq!(closures[0].params, vec![ClosureParam { pat: Pat::from(PatKind::Ident { name: "x".to_string(), mutable: false }), ty: None }]);
+        assert!(matches!(arena[closures[0].body].kind, NodeKind::BinaryOp(_)));
+        assert_eq!(closures[1].capture_by, CaptureBy::Value);
+        assert!(closures[1].params.is_empty());
+        assert_eq!(closures[2].params[0].ty, Some(Ty::from(TyKind::Path(Path::from_ident("i32")))));
+        assert_eq!(closures[2].params[1].pat, Pat::from(PatKind::Wild));
+        assert_eq!(closures[2].ret, Some(Ty::from(TyKind::Path(Path::from_ident("i32")))));
+    }
+
+    #[test]
+    fn test_parse_arrays_tuples_and_indexing() {
+        let source = "fn f(a: [i32; 3], s: &[u8], t: (i32, bool)) { let b = [0; 16]; a[1] + t.0.1; (a[1..=2], [1, 2, 3], (1,), ()); }";
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let Item::Fn(f) = &krate.items[0] else { panic!("expected fn") };
+        let tys: Vec<String> = f.params.iter().map(|param| param.ty.to_string()).collect();
+        assert_eq!(tys, vec!["[i32; 3]", "&[u8]", "(i32, bool)"]);
+
+        let arena = &krate.arena;
+        let stmts = &f.body.as_ref().unwrap().stmts;
+        let mut expected = AstArena::default();
+        let e = &mut expected;
+        let StmtKind::Let(local) = &arena[stmts[0]].kind else { panic!("expected let") };
+        let (zero, sixteen) = (number(e, 0), number(e, 16));
+        let repeat = expr(e, NodeKind::Repeat(zero, sixteen));
+        assert!(arena.expr_eq(local.init.unwrap(), e, repeat));
+
+        let StmtKind::Semi(sum) = arena[stmts[1]].kind else { panic!("expected stmt") };
+        let (a, one) = (ident(e, "a"), number(e, 1));
+        let index = expr(e, NodeKind::Index(a, one));
+        let t = ident(e, "t");
+        let t0 = expr(e, NodeKind::Field(t, "0".to_string()));
+        let t01 = expr(e, NodeKind::Field(t0, "1".to_string()));
+        let expected_sum = binary(e, index, TokenKind::Plus, "+", t01);
+        assert!(arena.expr_eq(sum, e, expected_sum));
+
+        let StmtKind::Semi(tuple) = arena[stmts[2]].kind else { panic!("expected stmt") };
+        let (a, one, two) = (ident(e, "a"), number(e, 1), number(e, 2));
+        let range = expr(e, NodeKind::Range(Range { start: Some(one), end: Some(two), inclusive: true }));
+        let slice = expr(e, NodeKind::Index(a, range));
+        let elems = vec![number(e, 1), number(e, 2), number(e, 3)];
+        let array = expr(e, NodeKind::Array(elems));
+        let one = number(e, 1);
+        let single = expr(e, NodeKind::Tuple(vec![one]));
+        let unit = expr(e, NodeKind::Tuple(vec![]));
+        let expected_tuple = expr(e, NodeKind::Tuple(vec![slice, array, single, unit]));
+        assert!(arena.expr_eq(tuple, e, expected_tuple));
+    }
+
+    #[test]
+    fn test_parse_array_len_expressions() {
+        let source = "fn f(a: [i32; N * 2], b: [u8; 3usize]) -> [[bool; 4]; m::K] {}";
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let Item::Fn(f) = &krate.items[0] else { panic!("expected fn") };
+        let TyKind::Array(_, ArrayLen::Expr(len)) = f.params[0].ty.kind else { panic!("expected array length expression") };
+        let mut expected = AstArena::default();
+        let e = &mut expected;
+        let (n, two) = (ident(e, "N"), number(e, 2));
+        let product = binary(e, n, TokenKind::Star, "*", two);
+        assert!(krate.arena.expr_eq(len, e, product));
+        assert!(matches!(f.params[1].ty.kind, TyKind::Array(_, ArrayLen::Expr(_))));
+
+        // 출력하면 길이 식을 그대로 쓰고, 다시 파싱할 수 있습니다.
+        let printed = crate_to_string(&krate);
+        assert_eq!(printed, "fn f(a: [i32; N * 2], b: [u8; 3usize]) -> [[bool; 4]; m::K] {}\n");
+        assert_eq!(crate_to_string(&parse_crate(&tokenize(&printed)).unwrap()), printed);
+    }
+
+    #[test]
+    fn test_parse_open_ranges() {
+        let krate = parse_crate(&tokenize("fn f() { a[..2]; a[1..]; a[..]; }")).unwrap();
+        let Item::Fn(f) = &krate.items[0] else { panic!() };
+        let arena = &krate.arena;
+        let bounds: Vec<(bool, bool)> = f.body.as_ref().unwrap().stmts.iter().map(|&stmt| match arena[stmt].kind {
+            StmtKind::Semi(expr) => match &arena[expr].kind {
+                NodeKind::Index(_, index) => match &arena[*index].kind {
+                    NodeKind::Range(range) => (range.start.is_some(), range.end.is_some()),
+                    _ => panic!("expected range"),
+                },
+                _ => panic!("expected index"),
+            },
+            _ => panic!("expected stmt"),
+        }).collect();
+        assert_eq!(bounds, vec![(false, true), (true, false), (false, false)]);
+
+        let error = parse_crate(&tokenize("fn f() { a[1..=]; }")).unwrap_err();
+        assert_eq!(error.message, "inclusive range with no end");
+    }
+
+    #[test]
+    fn test_parse_for_loops() {
+        let krate = parse_crate(&tokenize("fn f() { for i in 0..n { g(i); } for x in a.iter() {} }")).unwrap();
+        let Item::Fn(f) = &krate.items[0] else { panic!("expected fn") };
+        let arena = &krate.arena;
+        let stmts = &f.body.as_ref().unwrap().stmts;
+        let for_loop = |stmt: StmtId| match arena[stmt].kind {
+            StmtKind::Expr(expr) => match &arena[expr].kind {
+                NodeKind::ForLoop(for_loop) => for_loop,
+                _ => panic!("expected for loop"),
+            },
+            _ => panic!("expected stmt"),
+        };
+        let range_loop = for_loop(stmts[0]);
+        assert_eq!(range_loop.pat, Pat::from(PatKind::Ident { name: "i".to_string(), mutable: false }));
+        let mut expected = AstArena::default();
+        let (zero, n) = (number(&mut expected, 0), ident(&mut expected, "n"));
+        let range = expr(&mut expected, NodeKind::Range(Range { start: Some(zero), end: Some(n), inclusive: false }));
+        assert!(arena.expr_eq(range_loop.iter, &expected, range));
+        assert_eq!(range_loop.body.stmts.len(), 1);
+
+        let iter_loop = for_loop(stmts[1]);
+        assert!(matches!(&arena[iter_loop.iter].kind, NodeKind::MethodCall(call) if call.method == "iter"));
+    }
+
+    #[test]
+    fn test_parse_modules_and_use_trees() {
+        let source = "
+            pub mod shapes { pub struct Point { pub x: i32, y: i32 } fn hidden() {} }
+            mod io;
+            pub(crate) use crate::shapes::{self, Point as P, *};
+        ";
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let Item::Mod(shapes) = &krate.items[0] else { panic!("expected mod") };
+        assert_eq!(shapes.vis, Visibility::Public);
+        let items = shapes.items.as_ref().unwrap();
+        let Item::Struct(point) = &items[0] else { panic!("expected struct") };
+        assert_eq!(point.vis, Visibility::Public);
+        assert_eq!((point.fields[0].vis, point.fields[1].vis), (Visibility::Public, Visibility::Private));
+        assert!(matches!(&items[1], Item::Fn(f) if f.vis == Visibility::Private));
+        assert!(matches!(&krate.items[1], Item::Mod(io) if io.items.is_none()));
+
+        let Item::Use(decl) = &krate.items[2] else { panic!("expected use") };
+        assert_eq!(decl.vis, Visibility::Public);
+        assert_eq!(decl.tree.prefix.to_string(), "crate::shapes");
+        let UseTreeKind::Nested(trees) = &decl.tree.kind else { panic!("expected nested use") };
+        let simple = |ident: &str, rename: Option<&str>| UseTree {
+            prefix: Path::from_ident(ident),
+            kind: UseTreeKind::Simple(rename.map(str::to_string)),
+        };
+        assert_eq!(trees[0], simple("self", None));
+        assert_eq!(trees[1], simple("Point", Some("P")));
+        assert_eq!(trees[2], UseTree { prefix: Path { segments: vec![] }, kind: UseTreeKind::Glob });
+
+        let error = parse_crate(&tokenize("trait T { pub fn f(); }")).unwrap_err();
+        assert_eq!(error.message, "unnecessary visibility qualifier: `pub` not permitted here");
+    }
+
+    #[test]
+    fn test_parse_attributes() {
+        let source = r#"
+            #![allow(unused)]
+            #[cfg(not(feature = "slow"))]
+            #[inline]
+            fn f() {}
+            mod m { #![deny(warnings)] #[test] fn t() {} }
+        "#;
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let word = |name: &str| MetaItem { path: Path::from_ident(name), kind: MetaItemKind::Word };
+        let list = |name: &str, nested: Vec<NestedMeta>| MetaItem {
+            path: Path::from_ident(name),
+            kind: MetaItemKind::List(nested),
+        };
+        let allow = list("allow", vec![NestedMeta::Meta(word("unused"))]);
+        assert_eq!(krate.attrs, vec![Attribute { style: AttrStyle::Inner, meta: allow }]);
+
+        let Item::Fn(f) = &krate.items[0] else { panic!("expected fn") };
+        let slow = MetaItemKind::NameValue(Lit::Str("slow".to_string()));
+        let feature = MetaItem { path: Path::from_ident("feature"), kind: slow };
+        let not = list("not", vec![NestedMeta::Meta(feature)]);
+        assert_eq!(f.attrs[0].meta, list("cfg", vec![NestedMeta::Meta(not)]));
+        assert_eq!(f.attrs[1], Attribute { style: AttrStyle::Outer, meta: word("inline") });
+
+        // 인라인 모듈 본문의 안쪽 속성은 모듈의 속성이 됩니다.
+        let Item::Mod(m) = &krate.items[1] else { panic!("expected mod") };
+        assert_eq!(m.attrs[0].style, AttrStyle::Inner);
+        assert!(matches!(&m.items.as_ref().unwrap()[0], Item::Fn(t) if t.attrs[0].has_name("test")));
+
+        let error = parse_crate(&tokenize("fn f() { #[inline] let x = 1; }")).unwrap_err();
+        assert_eq!(error.message, "expected item after attributes");
+        let error = parse_crate(&tokenize("fn f() {} #![allow(unused)]")).unwrap_err();
+        assert_eq!(error.message, "an inner attribute is not permitted in this context");
+    }
+
+    #[test]
+    fn test_parse_format_args_and_strings() {
+        let source = r#"fn f() { format_args_nl!("{} {}", a, "b"); }"#;
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let Item::Fn(f) = &krate.items[0] else { panic!("expected fn") };
+        let placeholder = |arg| FormatPiece::Placeholder(FormatPlaceholder { arg, spec: FormatSpec::default() });
+        let mut expected = AstArena::default();
+        let (a, b) = (ident(&mut expected, "a"), expr(&mut expected, NodeKind::Str("b".to_string())));
+        let format = FormatArgs {
+            template: "{} {}\\n".to_string(),
+            pieces: vec![placeholder(0), FormatPiece::Literal(" ".to_string()), placeholder(1), FormatPiece::Literal("\\n".to_string())],
+            args: vec![
+                FormatArg { kind: FormatArgKind::Normal, expr: a },
+                FormatArg { kind: FormatArgKind::Normal, expr: b },
+            ],
+        };
+        let format = expr(&mut expected, NodeKind::FormatArgs(Box::new(format)));
+        let body = Block { stmts: vec![expected.alloc_stmt(Stmt::from(StmtKind::Semi(format)))] };
+        assert!(krate.arena.block_eq(f.body.as_ref().unwrap(), &expected, &body));
+
+        // 사용자 매크로는 파싱 전에 전개되어 있어야 합니다.
+        let error = parse_crate(&tokenize("fn f() { foo!(1); }")).unwrap_err();
+        assert_eq!(error.message, "cannot find macro `foo` in this scope");
+        let error = parse_crate(&tokenize("fn f() { format_args!(x); }")).unwrap_err();
+        assert_eq!(error.message, "`format_args!` requires a format string literal as its first argument");
+    }
+
+    fn parse_format(source: &str) -> (AstArena, FormatArgs) {
+        let source = format!("fn f() {{ {}; }}", source);
+        let krate = parse_crate(&tokenize(&source)).unwrap();
+        let Item::Fn(f) = &krate.items[0] else { panic!("expected fn") };
+        let arena = &krate.arena;
+        match &f.body.as_ref().unwrap().stmts[..] {
+            [stmt] => match arena[*stmt].kind {
+                StmtKind::Semi(expr) => match &arena[expr].kind {
+                    NodeKind::FormatArgs(format) => (krate.arena.clone(), (**format).clone()),
+                    other => panic!("expected format_args, found {:?}", other),
+                },
+                ref other => panic!("expected format_args, found {:?}", other),
+            },
+            other => panic!("expected format_args, found {:?}", other),
+        }
+    }
+
+    fn format_error(source: &str) -> String {
+        let source = format!("fn f() {{ {}; }}", source);
+        parse_crate(&tokenize(&source)).unwrap_err().message
+    }
+
+    #[test]
+    fn test_parse_format_specs() {
+        let (_, format) = parse_format(r#"format_args!("{} is {:>8.2} {:?} {2:#010x} {0:*^w$.1$}", name, value, 3, w = 5)"#);
+        let specs: Vec<(usize, FormatSpec)> = format
+            .pieces
+            .iter()
+            .filter_map(|piece| match piece {
+                FormatPiece::Placeholder(placeholder) => Some((placeholder.arg, placeholder.spec.clone())),
+                FormatPiece::Literal(_) => None,
+            })
+            .collect();
+        assert_eq!(specs, vec![
+            (0, FormatSpec::default()),
+            (1, FormatSpec {
+                align: Some(FormatAlign::Right),
+                width: Some(FormatCount::Literal(8)),
+                precision: Some(FormatCount::Literal(2)),
+                ..FormatSpec::default()
+            }),
+            (2, FormatSpec { trait_: FormatTrait::Debug, ..FormatSpec::default() }),
+            (2, FormatSpec {
+                alternate: true,
+                zero_pad: true,
+                width: Some(FormatCount::Literal(10)),
+                trait_: FormatTrait::LowerHex,
+                ..FormatSpec::default()
+            }),
+            (0, FormatSpec {
+                fill: '*',
+                align: Some(FormatAlign::Center),
+                width: Some(FormatCount::Arg(3)),
+                precision: Some(FormatCount::Arg(1)),
+                ..FormatSpec::default()
+            }),
+        ]);
+        assert_eq!(format.pieces[1], FormatPiece::Literal(" is ".to_string()));
+    }
+
+    #[test]
+    fn test_parse_format_named_and_captured_args() {
+        let (arena, format) = parse_format(r#"format_args!("{{{x}}} {y} {x}", y = 1)"#);
+        let kinds: Vec<&FormatArgKind> = format.args.iter().map(|arg| &arg.kind).collect();
+        assert_eq!(kinds, vec![&FormatArgKind::Named("y".to_string()), &FormatArgKind::Captured("x".to_string())]);
+        assert_eq!(arena[format.args[1].expr], Node::from(NodeKind::Ident("x".to_string())));
+        let placeholder = |arg| FormatPiece::Placeholder(FormatPlaceholder { arg, spec: FormatSpec::default() });
+        assert_eq!(format.pieces, vec![
+            FormatPiece::Literal("{".to_string()),
+            placeholder(1),
+            FormatPiece::Literal("} ".to_string()),
+            placeholder(0),
+            FormatPiece::Literal(" ".to_string()),
+            placeholder(1),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_format_errors_point_into_literal() {
+        assert_eq!(
+            format_error(r#"format_args!("{} {}", 1)"#),
+            "2 positional arguments in format string, but there is 1 argument\n  |\n  | \"{} {}\"\n  |     ^^"
+        );
+        assert_eq!(
+            format_error(r#"format_args!("{:q}", 1)"#),
+            "unknown format trait `q`\n  |\n  | \"{:q}\"\n  |    ^ unknown format trait\n  = note: the only appropriate \
+             formatting traits are `?`, `x?`, `X?`, `x`, `X`, `o`, `b`, `e` and `E`"
+        );
+        assert_eq!(
+            format_error(r#"format_args!("a}", 1)"#),
+            "invalid format string: unmatched `}` found\n  |\n  | \"a}\"\n  |   ^ unmatched `}` in format string\n  \
+             = note: if you intended to print `}`, you can escape it using `}}`"
+        );
+        assert_eq!(
+            format_error(r#"format_args!("{:.}", 1)"#),
+            "invalid format string: expected a count or `*` after `.`\n  |\n  | \"{:.}\"\n  |    ^ precision is missing"
+        );
+        assert!(format_error(r#"format_args!("{1}", 1)"#)
+            .starts_with("invalid reference to positional argument 1 (there is 1 argument)"));
+        assert_eq!(
+            format_error(r#"format_args!("{}", 1, 2)"#),
+            "argument never used\n  |\n  | \"{}\"\n  |  ^^ formatting specifier missing"
+        );
+        assert!(format_error(r#"format_args!("{}", 1, x = 2)"#).starts_with("named argument `x` never used"));
+        assert_eq!(format_error(r#"format_args!("{x}", x = 1, x = 2)"#), "duplicate argument named `x`");
+        assert_eq!(format_error(r#"format_args!("{x}", x = 1, 2)"#), "positional arguments cannot follow named arguments");
+    }
+
+    #[test]
+    fn test_unused_format_argument_is_the_primary_location() {
+        let source = r#"fn f() { let y = 1; format_args!("{y}", x + 1); }"#;
+        let error = parse_crate(&tokenize(source)).unwrap_err();
+        assert!(error.message.starts_with("argument never used"), "{}", error.message);
+        assert!(error.message.ends_with("formatting specifier missing"), "{}", error.message);
+        assert_eq!(&source[error.span.lo as usize..error.span.hi as usize], "x + 1");
+    }
+
+    #[test]
+    fn test_parse_spans_and_node_ids() {
+        let source = "fn main() { let x = 1 + foo(2); x }";
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let text = |span: rusttc_lexer::Span| &source[span.lo as usize..span.hi as usize];
+
+        let Item::Fn(main) = &krate.items[0] else { panic!("expected fn") };
+        assert_eq!(text(main.span), source);
+        let arena = &krate.arena;
+        let body = main.body.as_ref().unwrap();
+        let (let_stmt, last) = (&arena[body.stmts[0]], &arena[body.stmts[1]]);
+        assert_eq!(text(let_stmt.span), "let x = 1 + foo(2);");
+        let StmtKind::Let(local) = &let_stmt.kind else { panic!("expected let") };
+        assert_eq!(text(local.pat.span), "x");
+        let init = &arena[local.init.unwrap()];
+        assert_eq!(text(init.span), "1 + foo(2)");
+        let NodeKind::BinaryOp(binary) = &init.kind else { panic!("expected binary") };
+        let (left, right) = (&arena[binary.left], &arena[binary.right]);
+        assert_eq!(text(right.span), "foo(2)");
+        assert_eq!(text(last.span), "x");
+
+        // 노드 번호는 크레이트 안에서 겹치지 않습니다.
+        let mut ids = vec![main.id, let_stmt.id, local.pat.id, init.id, left.id, right.id];
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 6);
+    }
+
+    // 출력한 소스를 다시 파싱해 출력하면 같은 소스가 나와야 합니다.
+    fn roundtrip(source: &str) -> String {
+        let printed = crate_to_string(&parse_crate(&tokenize(source)).unwrap());
+        let reparsed = parse_crate(&tokenize(&printed)).unwrap_or_else(|error| panic!("{}\n{}", error.message, printed));
+        assert_eq!(crate_to_string(&reparsed), printed);
+        printed
+    }
+
+    #[test]
+    fn test_pretty_print_items() {
+        let source = "#![allow(unused)] use a::{b, c::*}; use d as e; #[cfg(test)] pub struct P<T: Clone + Ord> { pub x: T, y: [i32; 2] } \
+                      struct U; trait Shape { fn area(&self) -> i32; } impl<T> Shape for P<T> { fn area(&self) -> i32 { 0 } } \
+                      mod m { #![deny(warnings)] fn f(mut a: &mut i32, _: (i32,)) {} }";
+        assert_eq!(roundtrip(source), "\
+#![allow(unused)]
+
+use a::{b, c::*};
+use d as e;
+
+#[cfg(test)]
+pub struct P<T: Clone + Ord> {
+    pub x: T,
+    y: [i32; 2],
+}
+
+struct U;
+
+trait Shape {
+    fn area(&self) -> i32;
+}
+
+impl<T> Shape for P<T> {
+    fn area(&self) -> i32 {
+        0
+    }
+}
+
+mod m {
+    #![deny(warnings)]
+    fn f(mut a: &mut i32, _: (i32,)) {}
+}
+");
+    }
+
+    #[test]
+    fn test_parse_const_and_static() {
+        let source = "pub const N: usize = 4 * 16; static GREETING: &str = \"hi\"; fn main() { const K: [i32; 2] = [0; 2]; }";
+        assert_eq!(roundtrip(source), "\
+pub const N: usize = 4 * 16;
+
+static GREETING: &str = \"hi\";
+
+fn main() {
+    const K: [i32; 2] = [0; 2];
+}
+");
+        let error = parse_crate(&tokenize("static mut X: i32 = 0;")).unwrap_err();
+        assert_eq!(error.message, "`static mut` items are not supported");
+        let error = parse_crate(&tokenize("const X = 0;")).unwrap_err();
+        assert_eq!(error.message, "missing type for `const` item");
+    }
+
+    #[test]
+    fn test_pretty_print_expressions() {
+        let source = r#"fn main() {
+            let x = (1 + 2) * -(3 - 4) % 5;
+            if (P { x: 1 }).x == 1 { x = 2; } else if !a { loop { break; } }
+            ({ 1 } + 2);
+            (s.f)(max::<i32>(1, 2), <T as Tr>::g());
+            let c = move |a: i32| -> i32 { a };
+            for i in 0..=n { v[i] += &&mut w[1..]; }
+            format_args_nl!("{} {x:>w$}", (1,), w = 3);
+            return (a, b);
+        }"#;
+        let printed = roundtrip(source);
+        assert_eq!(printed, r#"fn main() {
+    let x = (1 + 2) * -(3 - 4) % 5;
+    if (P { x: 1 }).x == 1 {
+        x = 2;
+    } else if !a {
+        loop {
+            break;
+        }
+    }
+    ({
+        1
+    } + 2);
+    (s.f)(max::<i32>(1, 2), <T as Tr>::g());
+    let c = move |a: i32| -> i32 {
+        a
+    };
+    for i in 0..=n {
+        v[i] += &&mut w[1..];
+    }
+    format_args!("{} {x:>w$}\n", (1,), w = 3);
+    return (a, b);
+}
+"#);
+    }
+
+    #[test]
+    fn test_parse_match_and_patterns() {
+        let source = "fn f() { let v = match (x, p) { (0, _) | (-1, Point { x: 1..=9, .. }) => 0, (n, Point { x, y: true }) if n > x => { n } _ => 2 }; }";
+        let krate = parse_crate(&tokenize(source)).unwrap();
+        let Item::Fn(f) = &krate.items[0] else { panic!("expected fn") };
+        let arena = &krate.arena;
+        let StmtKind::Let(local) = &arena[f.body.as_ref().unwrap().stmts[0]].kind else { panic!("expected let") };
+        let NodeKind::Match(match_expr) = &arena[local.init.unwrap()].kind else { panic!("expected match") };
+        assert_eq!(match_expr.arms.len(), 3);
+
+        let PatKind::Or(alternatives) = &match_expr.arms[0].pat.kind else { panic!("expected or-pattern") };
+        let PatKind::Tuple(fields) = &alternatives[1].kind else { panic!("expected tuple pattern") };
+        assert!(matches!(&fields[0].kind, PatKind::Lit(lit) if matches!(arena[*lit].kind, NodeKind::Unary(_))));
+        let PatKind::Struct { path, fields, rest } = &fields[1].kind else { panic!("expected struct pattern") };
+        assert_eq!((path.to_string(), fields.len(), *rest), ("Point".to_string(), 1, true));
+        assert!(matches!(fields[0].1.kind, PatKind::Range(..)));
+
+        // 필드 이름만 적으면 같은 이름의 바인딩입니다.
+        let second = &match_expr.arms[1];
+        assert!(second.guard.is_some());
+        let PatKind::Tuple(fields) = &second.pat.kind else { panic!("expected tuple pattern") };
+        let PatKind::Struct { fields, rest: false, .. } = &fields[1].kind else { panic!("expected struct pattern") };
+        assert_eq!(fields[0].1, Pat::from(PatKind::Ident { name: "x".to_string(), mutable: false }));
+        assert_eq!(match_expr.arms[2].pat, Pat::from(PatKind::Wild));
+
+        assert_eq!(roundtrip(source), "\
+fn f() {
+    let v = match (x, p) {
+        (0, _) | (-1, Point { x: 1..=9, .. }) => 0,
+        (n, Point { x, y: true }) if n > x => {
+            n
+        }
+        _ => 2,
+    };
+}
+");
+        // 블록이 아닌 갈래 뒤에는 `,`가 있어야 합니다.
+        let error = parse_crate(&tokenize("fn f() { match x { 0 => 1 _ => 2 } }")).unwrap_err();
+        assert_eq!(error.message, "expected `,`, found `_`");
+    }
+
+    #[test]
+    fn test_parse_long_expression() {
+        // 생성된 코드처럼 아주 긴 식도 재귀 없이 파싱하고 해제합니다.
+        // 렉서를 거치지 않도록 `1 + 1 + ... + 1`의 토큰을 직접 만듭니다.
+        let mut tokens = vec![Token::new(TokenKind::Literal, "1".to_string())];
+        for _ in 0..100_000 {
+            tokens.push(Token::new(TokenKind::Plus, "+".to_string()));
+            tokens.push(Token::new(TokenKind::Literal, "1".to_string()));
+        }
+        let (arena, nodes) = parse(&tokens);
+        assert_eq!(nodes.len(), 1);
+        let NodeKind::BinaryOp(binary_op) = &arena[nodes[0]].kind else { panic!() };
+        assert_eq!(arena[binary_op.right].kind, NodeKind::Int(1, None));
+        drop(arena);
+    }
+
+    #[test]
+    fn test_parse_nesting_limit() {
+        // 제한은 작은 값으로 확인합니다. 함수 본문의 문장이 1단계이고, 오류는 제한을 넘은 첫 노드를 가리킵니다.
+        let parse = |source: &str| {
+            parse_crate_within(&tokenize(source), AstArena::default(), NodeId::FIRST, 4).map(|_| ()).map_err(|error| (error.message, error.span.lo))
+        };
+        let too_deep = |lo| Err(("nesting exceeds the supported depth of 4 levels".to_string(), lo));
+        assert_eq!(parse("fn f() { let x = 1 + 1 + 1; }"), Ok(()));
+        assert_eq!(parse("fn f() { let x = 1 + 1 + 1 + 1; }"), too_deep(17));
+        assert_eq!(parse("fn f() { let x = - - 1; }"), Ok(()));
+        assert_eq!(parse("fn f() { let x = - - - 1; }"), too_deep(23));
+        assert_eq!(parse("fn f() { let x = - - - - - - 1; }"), too_deep(25));
+        assert_eq!(parse("fn f(x: & & & i32) {}"), Ok(()));
+        assert_eq!(parse("fn f(x: & & & & i32) {}"), too_deep(16));
     }
 }
@@ -0,0 +1,98 @@
+use rusttc_lexer::TokenKind;
+use rusttc_ast::ast::{AttrStyle, Attribute, Lit, MetaItem, MetaItemKind, NestedMeta, Path};
+
+use super::lit;
+use super::ty::PathStyle;
+use super::{ParseError, PResult, Parser};
+use crate::syntax_kind::SyntaxKind;
+
+impl Parser {
+    // 아이템 앞의 `#[...]` 속성들을 파싱합니다.
+    pub(crate) fn parse_outer_attrs(&mut self) -> PResult<Vec<Attribute>> {
+        let mut attrs = Vec::new();
+        while self.check(&TokenKind::Pound) {
+            if self.look_ahead(1).kind == TokenKind::Bang {
+                return Err(ParseError::new("an inner attribute is not permitted in this context"));
+            }
+            self.start_node(SyntaxKind::Attr);
+            self.bump();
+            attrs.push(self.parse_attr_body(AttrStyle::Outer)?);
+            self.finish_node();
+        }
+        Ok(attrs)
+    }
+
+    // 크레이트나 모듈 본문 맨 앞의 `#![...]` 속성들을 파싱합니다.
+    pub(crate) fn parse_inner_attrs(&mut self) -> PResult<Vec<Attribute>> {
+        let mut attrs = Vec::new();
+        while self.check(&TokenKind::Pound) && self.look_ahead(1).kind == TokenKind::Bang {
+            self.start_node(SyntaxKind::Attr);
+            self.bump();
+            self.bump();
+            attrs.push(self.parse_attr_body(AttrStyle::Inner)?);
+            self.finish_node();
+        }
+        Ok(attrs)
+    }
+
+    // `[meta]`. 구문 트리에서는 속성 내용을 토큰 트리로만 둡니다.
+    fn parse_attr_body(&mut self, style: AttrStyle) -> PResult<Attribute> {
+        if self.is_building_syntax() {
+            if !self.check(&TokenKind::OpenBracket) {
+                return Err(self.unexpected("`[`"));
+            }
+            self.parse_token_tree()?;
+            let meta = MetaItem { path: Path { segments: Vec::new() }, kind: MetaItemKind::Word };
+            return Ok(Attribute { style, meta });
+        }
+        self.expect(&TokenKind::OpenBracket, "`[`")?;
+        let meta = self.parse_meta_item()?;
+        self.expect(&TokenKind::CloseBracket, "`]`")?;
+        Ok(Attribute { style, meta })
+    }
+
+    // `name`, `name(nested, ...)`, `name = lit`
+    fn parse_meta_item(&mut self) -> PResult<MetaItem> {
+        let path = self.parse_path(PathStyle::Expr)?;
+        let kind = if self.eat(&TokenKind::OpenParen) {
+            let mut nested = Vec::new();
+            while !self.check(&TokenKind::CloseParen) {
+                nested.push(match self.parse_lit()? {
+                    Some(lit) => NestedMeta::Lit(lit),
+                    None => NestedMeta::Meta(self.parse_meta_item()?),
+                });
+                if !self.eat(&TokenKind::Comma) {
+                    break;
+                }
+            }
+            self.expect(&TokenKind::CloseParen, "`)`")?;
+            MetaItemKind::List(nested)
+        } else if self.eat(&TokenKind::Eq) {
+            match self.parse_lit()? {
+                Some(lit) => MetaItemKind::NameValue(lit),
+                None => return Err(self.unexpected("a literal")),
+            }
+        } else {
+            MetaItemKind::Word
+        };
+        Ok(MetaItem { path, kind })
+    }
+
+    // 현재 토큰이 리터럴이면 파싱합니다.
+    fn parse_lit(&mut self) -> PResult<Option<Lit>> {
+        let token = self.token().clone();
+        let lit = match token.kind {
+            TokenKind::StringLiteral => Lit::Str(lit::parse_str(&token.text).map_err(|error| error.with_span(token.span))?),
+            TokenKind::Literal => Lit::Int(
+                token
+                    .text
+                    .parse()
+                    .map_err(|_| ParseError::new(format!("integer literal `{}` is too large", token.text)))?,
+            ),
+            TokenKind::Ident if token.text == "true" || token.text == "false" => Lit::Bool(token.text == "true"),
+            _ => return Ok(None),
+        };
+        self.bump();
+        Ok(Some(lit))
+    }
+}
@@ -0,0 +1,123 @@
+use rusttc_lexer::{Token, TokenKind};
+use rusttc_ast::ast::NodeId;
+
+use crate::syntax_kind::SyntaxKind;
+
+use super::{ParseError, Parser};
+
+// 파서가 구문 트리를 쌓으라고 남기는 사건입니다. AST를 만드는 문법 그대로 노드의 시작과 끝을 남기고,
+// `rusttc_syntax`가 이 사건에 공백과 주석을 끼워 넣어 무손실 트리를 만듭니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Event {
+    Start(SyntaxKind),              // 노드를 엽니다
+    Checkpoint,                     // 나중에 `StartAt`이 이 자리부터 노드를 열 수 있게 표시합니다
+    StartAt(usize, SyntaxKind),     // 앞의 `Checkpoint` 사건(사건 목록 안 번호) 자리부터 노드를 엽니다
+    Finish,                         // 마지막으로 연 노드를 닫습니다
+    Token,                          // 공백과 주석이 아닌 다음 토큰 하나를 넣습니다
+}
+
+// 구문 트리를 만들며 얻은 사건과 오류입니다. 파서는 첫 오류에서 멈추므로, 오류가 있으면
+// 사건은 그 자리까지만 있고 열린 노드가 닫히지 않은 채 남습니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxEvents {
+    pub events: Vec<Event>,
+    pub error: Option<ParseError>,
+}
+
+// 소스 파일 하나의 내용(안쪽 속성과 아이템)을 사건으로 파싱합니다. 루트 노드는 받는 쪽이 엽니다.
+// 매크로는 전개하지 않고, 호출은 경로와 `!`, 토큰 트리로 둡니다.
+pub fn parse_source_file_events(tokens: &[Token]) -> SyntaxEvents {
+    let mut parser = Parser::for_syntax(tokens);
+    let error = parser.parse_crate_contents().err().map(|error| error.or_span(parser.prev_span));
+    SyntaxEvents { events: parser.events.unwrap_or_default(), error }
+}
+
+// `{`로 시작하는 토큰을 블록 표현식 하나로 파싱합니다. 블록 뒤에 토큰이 남으면 오류입니다.
+pub fn parse_block_events(tokens: &[Token]) -> SyntaxEvents {
+    let mut parser = Parser::for_syntax(tokens);
+    let mut error = parser.parse_block().err().map(|error| error.or_span(parser.prev_span));
+    if error.is_none() && !parser.is_eof() {
+        error = Some(parser.unexpected("end of block"));
+    }
+    SyntaxEvents { events: parser.events.unwrap_or_default(), error }
+}
+
+impl Parser {
+    fn for_syntax(tokens: &[Token]) -> Self {
+        let mut parser = Parser::new(tokens);
+        parser.events = Some(Vec::new());
+        parser.next_id = NodeId::FIRST;
+        parser
+    }
+
+    // 구문 트리를 쌓는 중인지 확인합니다. 이때는 매크로 호출을 토큰 트리로 남깁니다.
+    pub(crate) fn is_building_syntax(&self) -> bool {
+        self.events.is_some()
+    }
+
+    fn push_event(&mut self, event: Event) {
+        if let Some(events) = &mut self.events {
+            events.push(event);
+        }
+    }
+
+    pub(crate) fn start_node(&mut self, kind: SyntaxKind) {
+        self.push_event(Event::Start(kind));
+    }
+
+    pub(crate) fn finish_node(&mut self) {
+        self.push_event(Event::Finish);
+    }
+
+    pub(crate) fn record_token(&mut self) {
+        self.push_event(Event::Token);
+    }
+
+    // 아직 종류를 모르는 노드가 시작될 자리를 표시합니다. 종류가 정해지면 `start_node_at`으로 엽니다.
+    pub(crate) fn checkpoint(&mut self) -> usize {
+        let index = self.events.as_ref().map_or(0, Vec::len);
+        self.push_event(Event::Checkpoint);
+        index
+    }
+
+    pub(crate) fn start_node_at(&mut self, checkpoint: usize, kind: SyntaxKind) {
+        self.push_event(Event::StartAt(checkpoint, kind));
+    }
+
+    // `checkpoint` 뒤에 쌓인 자식들을 `kind` 노드로 감쌉니다.
+    pub(crate) fn wrap_node(&mut self, checkpoint: usize, kind: SyntaxKind) {
+        self.start_node_at(checkpoint, kind);
+        self.finish_node();
+    }
+
+    // 괄호로 묶인 토큰을 짝이 맞는 닫는 괄호까지 그대로 읽습니다. 안쪽 괄호는 중첩된 토큰 트리가 됩니다.
+    pub(crate) fn parse_token_tree(&mut self) -> Result<(), ParseError> {
+        let close = match self.token().kind {
+            TokenKind::OpenParen => TokenKind::CloseParen,
+            TokenKind::OpenBracket => TokenKind::CloseBracket,
+            TokenKind::OpenBrace => TokenKind::CloseBrace,
+            _ => return Err(self.unexpected("one of `(`, `[`, or `{`")),
+        };
+        self.start_node(SyntaxKind::TokenTree);
+        self.bump();
+        while !self.check(&close) {
+            match self.token().kind {
+                TokenKind::OpenParen | TokenKind::OpenBracket | TokenKind::OpenBrace => self.parse_token_tree()?,
+                TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseBrace | TokenKind::EOF => {
+                    let text = match close {
+                        TokenKind::CloseParen => "`)`",
+                        TokenKind::CloseBracket => "`]`",
+                        _ => "`}`",
+                    };
+                    return Err(self.unexpected(text));
+                },
+                _ => {
+                    self.bump();
+                },
+            }
+        }
+        self.bump();
+        self.finish_node();
+        Ok(())
+    }
+}
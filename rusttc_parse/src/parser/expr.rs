@@ -0,0 +1,671 @@
+use rusttc_lexer::TokenKind;
+use rusttc_ast::ast::{
+    binary_precedence, is_comparison, Arm, Assign, BinaryOp, Block, Call, CaptureBy, Closure, ClosureKind, ClosureParam, ExprId, ForLoop,
+    If, Local, Match, MethodCall, NodeKind, QSelf, Range, Stmt, StmtId, StmtKind, StructLit, UnaryOp, While,
+};
+
+use super::lit;
+use super::ty::PathStyle;
+use super::{ParseError, PResult, Parser};
+use crate::syntax_kind::SyntaxKind;
+
+impl Parser {
+    // 대입을 포함한 표현식을 파싱합니다.
+    pub(crate) fn parse_expr(&mut self) -> PResult<ExprId> {
+        self.nested(Self::parse_assign)
+    }
+
+    fn parse_assign(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        let checkpoint = self.checkpoint();
+        let target = self.parse_range()?;
+
+        let operator = match self.token().kind {
+            TokenKind::Eq => None,
+            TokenKind::PlusEq
+            | TokenKind::MinusEq
+            | TokenKind::StarEq
+            | TokenKind::SlashEq
+            | TokenKind::PercentEq => Some(self.token().clone()),
+            _ => return Ok(target),
+        };
+        self.start_node_at(checkpoint, SyntaxKind::AssignExpr);
+        self.bump();
+        let value = self.parse_expr()?;
+        self.finish_node();
+
+        Ok(self.mk_node(NodeKind::Assign(Assign { target, operator, value }), lo))
+    }
+
+    // 범위 표현식 `a..b`, `a..=b`, `..b`, `a..`, `..`를 파싱합니다. 이항 연산자보다 약하게 결합합니다.
+    fn parse_range(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        let checkpoint = self.checkpoint();
+        let start = if self.check(&TokenKind::DotDot) || self.check(&TokenKind::DotDotEq) {
+            None
+        } else {
+            let start = self.parse_binary(0)?;
+            if !self.check(&TokenKind::DotDot) && !self.check(&TokenKind::DotDotEq) {
+                return Ok(start);
+            }
+            Some(start)
+        };
+
+        self.start_node_at(checkpoint, SyntaxKind::RangeExpr);
+        let inclusive = self.bump().kind == TokenKind::DotDotEq;
+        let end = if self.can_begin_expr() {
+            Some(self.parse_binary(0)?)
+        } else if inclusive {
+            return Err(ParseError::new("inclusive range with no end"));
+        } else {
+            None
+        };
+        self.finish_node();
+        Ok(self.mk_node(NodeKind::Range(Range { start, end, inclusive }), lo))
+    }
+
+    // 현재 토큰이 범위의 끝이 될 표현식을 시작할 수 있는지 확인합니다.
+    fn can_begin_expr(&self) -> bool {
+        match self.token().kind {
+            TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseBrace => false,
+            TokenKind::Comma | TokenKind::Semi | TokenKind::Eq | TokenKind::EOF => false,
+            // `for i in 0.. {`의 `{`는 범위가 아니라 루프 본문입니다.
+            TokenKind::OpenBrace => !self.no_struct_literal,
+            _ => true,
+        }
+    }
+
+    // 구조체 리터럴을 허용하지 않는 위치(if, while 조건)의 표현식을 파싱합니다.
+    fn parse_cond_expr(&mut self) -> PResult<ExprId> {
+        let old = std::mem::replace(&mut self.no_struct_literal, true);
+        let result = self.parse_expr();
+        self.no_struct_literal = old;
+        result
+    }
+
+    fn parse_binary(&mut self, min_prec: u8) -> PResult<ExprId> {
+        let lo = self.lo();
+        let checkpoint = self.checkpoint();
+        let mut left = self.parse_unary()?;
+
+        while let Some(prec) = binary_precedence(&self.token().kind) {
+            if prec < min_prec {
+                break;
+            }
+            self.start_node_at(checkpoint, SyntaxKind::BinExpr);
+            let operator = self.bump();
+            let right = self.parse_binary(prec + 1)?;
+            if is_comparison(&operator.kind) && is_comparison(&self.token().kind) {
+                return Err(ParseError::new("comparison operators cannot be chained"));
+            }
+            self.finish_node();
+            left = self.mk_node(NodeKind::BinaryOp(BinaryOp { left, operator, right }), lo);
+        }
+
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        match self.token().kind {
+            TokenKind::Minus | TokenKind::Bang | TokenKind::Star => {
+                self.start_node(SyntaxKind::PrefixExpr);
+                let operator = self.bump();
+                let operand = self.nested(Self::parse_unary)?;
+                self.finish_node();
+                Ok(self.mk_node(NodeKind::Unary(UnaryOp { operator, operand }), lo))
+            },
+            TokenKind::And => {
+                self.start_node(SyntaxKind::RefExpr);
+                self.bump();
+                let mutable = self.eat_keyword("mut");
+                let expr = self.nested(Self::parse_unary)?;
+                self.finish_node();
+                Ok(self.mk_node(NodeKind::Ref { mutable, expr }, lo))
+            },
+            TokenKind::AndAnd => {
+                // `&&x`는 `& &x`로 취급합니다. 안쪽 참조는 두 번째 `&`부터 시작합니다.
+                // 구문 트리에서는 토큰이 하나이므로 노드도 하나입니다.
+                self.start_node(SyntaxKind::RefExpr);
+                self.bump();
+                let inner_lo = lo.with_lo(lo.lo + 1);
+                let mutable = self.eat_keyword("mut");
+                let expr = self.nested(Self::parse_unary)?;
+                self.finish_node();
+                let inner = self.mk_node(NodeKind::Ref { mutable, expr }, inner_lo);
+                Ok(self.mk_node(NodeKind::Ref { mutable: false, expr: inner }, lo))
+            },
+            _ => self.parse_postfix(),
+        }
+    }
+
+    fn parse_postfix(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        let checkpoint = self.checkpoint();
+        let mut node = self.parse_primary()?;
+
+        loop {
+            if self.check(&TokenKind::OpenParen) {
+                self.start_node_at(checkpoint, SyntaxKind::CallExpr);
+                let args = self.parse_call_args()?;
+                node = self.mk_node(NodeKind::Call(Call { callee: node, args }), lo);
+            } else if self.check(&TokenKind::OpenBracket) {
+                self.start_node_at(checkpoint, SyntaxKind::IndexExpr);
+                self.bump();
+                let index = self.parse_nested_expr()?;
+                self.expect(&TokenKind::CloseBracket, "`]`")?;
+                node = self.mk_node(NodeKind::Index(node, index), lo);
+            } else if self.check(&TokenKind::Dot) {
+                let method = self.look_ahead(1).kind == TokenKind::Ident && self.look_ahead(2).kind == TokenKind::OpenParen;
+                self.start_node_at(checkpoint, if method { SyntaxKind::MethodCallExpr } else { SyntaxKind::FieldExpr });
+                self.bump();
+                // 튜플 필드 `t.0`
+                if self.check(&TokenKind::Literal) {
+                    let index = self.bump().text;
+                    node = self.mk_node(NodeKind::Field(node, index), lo);
+                } else {
+                    let name = self.parse_ident()?;
+                    if self.check(&TokenKind::OpenParen) {
+                        let args = self.parse_call_args()?;
+                        node = self.mk_node(NodeKind::MethodCall(MethodCall { receiver: node, method: name, args }), lo);
+                    } else {
+                        node = self.mk_node(NodeKind::Field(node, name), lo);
+                    }
+                }
+            } else {
+                return Ok(node);
+            }
+            self.finish_node();
+        }
+    }
+
+    // 괄호를 포함한 인자 목록 `(a, b)`를 파싱합니다.
+    fn parse_call_args(&mut self) -> PResult<Vec<ExprId>> {
+        self.start_node(SyntaxKind::ArgList);
+        self.bump();
+        let mut args = Vec::new();
+        while !self.check(&TokenKind::CloseParen) {
+            args.push(self.parse_nested_expr()?);
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(&TokenKind::CloseParen, "`)`")?;
+        self.finish_node();
+        Ok(args)
+    }
+
+    // 괄호처럼 구분된 위치에서는 구조체 리터럴 제한을 풉니다.
+    pub(crate) fn parse_nested_expr(&mut self) -> PResult<ExprId> {
+        let old = std::mem::replace(&mut self.no_struct_literal, false);
+        let result = self.parse_expr();
+        self.no_struct_literal = old;
+        result
+    }
+
+    fn parse_primary(&mut self) -> PResult<ExprId> {
+        let token = self.token().clone();
+        let lo = token.span;
+        match token.kind {
+            TokenKind::Literal => self.parse_literal(lit::parse_number),
+            TokenKind::CharLiteral => self.parse_literal(|text| lit::parse_char(text).map(NodeKind::Char)),
+            TokenKind::OpenParen => {
+                let checkpoint = self.checkpoint();
+                self.bump();
+                let mut elems = Vec::new();
+                let mut trailing_comma = false;
+                while !self.check(&TokenKind::CloseParen) {
+                    elems.push(self.parse_nested_expr()?);
+                    trailing_comma = self.eat(&TokenKind::Comma);
+                    if !trailing_comma {
+                        break;
+                    }
+                }
+                self.expect(&TokenKind::CloseParen, "`)`")?;
+                let paren = elems.len() == 1 && !trailing_comma;
+                self.wrap_node(checkpoint, if paren { SyntaxKind::ParenExpr } else { SyntaxKind::TupleExpr });
+                // `(x)`는 괄호로 묶은 표현식이고, `(x,)`는 원소가 하나인 튜플입니다.
+                match elems.len() {
+                    1 if !trailing_comma => Ok(elems.pop().unwrap()),
+                    _ => Ok(self.mk_node(NodeKind::Tuple(elems), lo)),
+                }
+            },
+            TokenKind::StringLiteral => self.parse_literal(|text| lit::parse_str(text).map(NodeKind::Str)),
+            TokenKind::OpenBracket => self.parse_array(),
+            TokenKind::OpenBrace => {
+                let block = self.parse_block()?;
+                Ok(self.mk_node(NodeKind::Block(block), lo))
+            },
+            TokenKind::Or | TokenKind::OrOr => self.parse_closure(),
+            TokenKind::Lt => self.parse_qualified_path(),
+            TokenKind::Ident => match token.text.as_str() {
+                "true" | "false" => self.parse_literal(|text| Ok(NodeKind::Bool(text == "true"))),
+                "if" => self.parse_if(),
+                "match" => self.parse_match(),
+                "move" => self.parse_closure(),
+                "while" => self.parse_while(),
+                "for" => self.parse_for(),
+                "loop" => {
+                    self.start_node(SyntaxKind::LoopExpr);
+                    self.bump();
+                    let body = self.parse_block()?;
+                    self.finish_node();
+                    Ok(self.mk_node(NodeKind::Loop(body), lo))
+                },
+                "break" => {
+                    self.start_node(SyntaxKind::BreakExpr);
+                    self.bump();
+                    self.finish_node();
+                    Ok(self.mk_node(NodeKind::Break, lo))
+                },
+                "continue" => {
+                    self.start_node(SyntaxKind::ContinueExpr);
+                    self.bump();
+                    self.finish_node();
+                    Ok(self.mk_node(NodeKind::Continue, lo))
+                },
+                _ if self.look_ahead(1).kind == TokenKind::Bang => self.parse_macro_call(),
+                "return" => {
+                    self.start_node(SyntaxKind::ReturnExpr);
+                    self.bump();
+                    let value = if self.check(&TokenKind::Semi) || self.check(&TokenKind::CloseBrace) {
+                        None
+                    } else {
+                        Some(self.parse_expr()?)
+                    };
+                    self.finish_node();
+                    Ok(self.mk_node(NodeKind::Return(value), lo))
+                },
+                _ => self.parse_path_expr(),
+            },
+            _ => Err(self.unexpected("expression")),
+        }
+    }
+
+    // 리터럴 토큰 하나를 `parse`로 해석합니다. 구문 트리는 리터럴의 값을 검사하지 않으므로
+    // 토큰 하나만 고친 편집은 트리 모양도 오류도 바꾸지 않습니다.
+    fn parse_literal(&mut self, parse: impl FnOnce(&str) -> PResult<NodeKind>) -> PResult<ExprId> {
+        let lo = self.lo();
+        self.start_node(SyntaxKind::Literal);
+        let token = self.bump();
+        self.finish_node();
+        let kind = if self.is_building_syntax() {
+            NodeKind::Tuple(Vec::new())
+        } else {
+            parse(&token.text)?
+        };
+        Ok(self.mk_node(kind, lo))
+    }
+
+    // 패턴 자리의 리터럴(`1`, `'a'`, `true`)을 표현식으로 파싱합니다. 문자열 리터럴 패턴은 받지 않습니다.
+    pub(crate) fn parse_pat_literal(&mut self) -> PResult<ExprId> {
+        let token = self.token().clone();
+        match token.kind {
+            TokenKind::Literal => self.parse_literal(lit::parse_number),
+            TokenKind::CharLiteral => self.parse_literal(|text| lit::parse_char(text).map(NodeKind::Char)),
+            TokenKind::Ident if token.text == "true" || token.text == "false" => {
+                self.parse_literal(|text| Ok(NodeKind::Bool(text == "true")))
+            },
+            _ => Err(self.unexpected("literal pattern")),
+        }
+    }
+
+    // 경로 표현식, 식별자, 구조체 리터럴을 파싱합니다.
+    fn parse_path_expr(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        let checkpoint = self.checkpoint();
+        let path = self.parse_path(PathStyle::Expr)?;
+
+        if self.check_struct_literal() {
+            self.start_node_at(checkpoint, SyntaxKind::RecordExpr);
+            self.start_node(SyntaxKind::RecordExprFieldList);
+            self.bump();
+            let mut fields = Vec::new();
+            while !self.check(&TokenKind::CloseBrace) {
+                self.start_node(SyntaxKind::RecordExprField);
+                let name_lo = self.lo();
+                let name = self.parse_ident()?;
+                // 줄임 표기 `Point { x }`의 값은 필드 이름 자리의 변수입니다.
+                let value = if self.eat(&TokenKind::Colon) {
+                    self.parse_nested_expr()?
+                } else {
+                    self.mk_node(NodeKind::Ident(name.clone()), name_lo)
+                };
+                self.finish_node();
+                fields.push((name, value));
+                if !self.eat(&TokenKind::Comma) {
+                    break;
+                }
+            }
+            self.expect(&TokenKind::CloseBrace, "`}`")?;
+            self.finish_node();
+            self.finish_node();
+            return Ok(self.mk_node(NodeKind::StructLit(StructLit { path, fields }), lo));
+        }
+        self.wrap_node(checkpoint, SyntaxKind::PathExpr);
+
+        let kind = match path.segments.as_slice() {
+            [segment] if segment.args.is_empty() => NodeKind::Ident(segment.ident.clone()),
+            _ => NodeKind::Path(path),
+        };
+        Ok(self.mk_node(kind, lo))
+    }
+
+    // 매크로 전개 뒤에도 남는 컴파일러 내장 매크로 `format_args!("...", args)`를 파싱합니다.
+    // 다른 매크로 호출은 전개 단계에서 모두 사라졌어야 합니다. 구문 트리를 쌓을 때는 전개하지 않은
+    // 소스를 읽으므로 모든 호출을 토큰 트리로 두고, 표현식 자리에는 빈 튜플을 둡니다.
+    fn parse_macro_call(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        if self.is_building_syntax() {
+            self.start_node(SyntaxKind::MacroCall);
+            self.parse_macro_call_body()?;
+            self.finish_node();
+            return Ok(self.mk_node(NodeKind::Tuple(Vec::new()), lo));
+        }
+        let name = self.bump().text;
+        self.expect(&TokenKind::Bang, "`!`")?;
+        if name != "format_args" && name != "format_args_nl" {
+            return Err(ParseError::new(format!("cannot find macro `{}` in this scope", name)));
+        }
+
+        let format = self.parse_format_args(&name)?;
+        Ok(self.mk_node(NodeKind::FormatArgs(Box::new(format)), lo))
+    }
+
+    // `|x, y: i32| body`, `move || body`, `|x| -> i32 { body }` 클로저를 파싱합니다.
+    fn parse_closure(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        self.start_node(SyntaxKind::ClosureExpr);
+        let capture_by = if self.eat_keyword("move") {
+            CaptureBy::Value
+        } else {
+            CaptureBy::Ref
+        };
+
+        self.start_node(SyntaxKind::ParamList);
+        let mut params = Vec::new();
+        if !self.eat(&TokenKind::OrOr) {
+            self.expect(&TokenKind::Or, "`|`")?;
+            while !self.check(&TokenKind::Or) {
+                self.start_node(SyntaxKind::Param);
+                let pat = self.parse_pat()?;
+                let ty = if self.eat(&TokenKind::Colon) {
+                    Some(self.parse_ty()?)
+                } else {
+                    None
+                };
+                self.finish_node();
+                params.push(ClosureParam { pat, ty });
+                if !self.eat(&TokenKind::Comma) {
+                    break;
+                }
+            }
+            self.expect(&TokenKind::Or, "`|`")?;
+        }
+        self.finish_node();
+
+        // 반환 타입을 쓰면 본문은 블록이어야 합니다.
+        let (ret, body) = if let Some(ret) = self.parse_ret_ty()? {
+            let body_lo = self.lo();
+            let body = self.parse_block()?;
+            (Some(ret), self.mk_node(NodeKind::Block(body), body_lo))
+        } else {
+            (None, self.parse_expr()?)
+        };
+        self.finish_node();
+
+        let closure = NodeKind::Closure(Box::new(Closure {
+            capture_by,
+            params,
+            ret,
+            body,
+            name: String::new(),
+            kind: ClosureKind::Fn,
+            captures: Vec::new(),
+        }));
+        Ok(self.mk_node(closure, lo))
+    }
+
+    // 배열 표현식 `[a, b, c]`과 반복 배열 `[value; count]`를 파싱합니다.
+    fn parse_array(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        self.start_node(SyntaxKind::ArrayExpr);
+        self.expect(&TokenKind::OpenBracket, "`[`")?;
+        if self.eat(&TokenKind::CloseBracket) {
+            self.finish_node();
+            return Ok(self.mk_node(NodeKind::Array(Vec::new()), lo));
+        }
+
+        let first = self.parse_nested_expr()?;
+        if self.eat(&TokenKind::Semi) {
+            let count = self.parse_nested_expr()?;
+            self.expect(&TokenKind::CloseBracket, "`]`")?;
+            self.finish_node();
+            return Ok(self.mk_node(NodeKind::Repeat(first, count), lo));
+        }
+
+        let mut elems = vec![first];
+        while self.eat(&TokenKind::Comma) && !self.check(&TokenKind::CloseBracket) {
+            elems.push(self.parse_nested_expr()?);
+        }
+        self.expect(&TokenKind::CloseBracket, "`]`")?;
+        self.finish_node();
+        Ok(self.mk_node(NodeKind::Array(elems), lo))
+    }
+
+    // `<Type as Trait>::item` 한정 경로를 파싱합니다.
+    fn parse_qualified_path(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        self.start_node(SyntaxKind::QualifiedPathExpr);
+        self.expect(&TokenKind::Lt, "`<`")?;
+        let ty = self.parse_ty()?;
+        self.expect_keyword("as")?;
+        let trait_path = self.parse_path(PathStyle::Type)?;
+        self.expect(&TokenKind::Gt, "`>`")?;
+        self.expect(&TokenKind::PathSep, "`::`")?;
+        let path = self.parse_path(PathStyle::Expr)?;
+        self.finish_node();
+        Ok(self.mk_node(NodeKind::QualifiedPath(Box::new(QSelf { ty, trait_path }), path), lo))
+    }
+
+    // 경로 뒤의 `{`가 구조체 리터럴의 시작인지 확인합니다.
+    fn check_struct_literal(&self) -> bool {
+        if self.no_struct_literal || !self.check(&TokenKind::OpenBrace) {
+            return false;
+        }
+        let first = self.look_ahead(1);
+        let second = self.look_ahead(2);
+        first.kind == TokenKind::CloseBrace
+            || (first.kind == TokenKind::Ident
+                && matches!(second.kind, TokenKind::Colon | TokenKind::Comma | TokenKind::CloseBrace))
+    }
+
+    fn parse_if(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        self.start_node(SyntaxKind::IfExpr);
+        self.expect_keyword("if")?;
+        let cond = self.parse_cond_expr()?;
+        let then = self.parse_block()?;
+
+        let els = if self.eat_keyword("else") {
+            if self.check_keyword("if") {
+                Some(self.nested(Self::parse_if)?)
+            } else {
+                let els_lo = self.lo();
+                let block = self.parse_block()?;
+                Some(self.mk_node(NodeKind::Block(block), els_lo))
+            }
+        } else {
+            None
+        };
+        self.finish_node();
+
+        Ok(self.mk_node(NodeKind::If(If { cond, then, els }), lo))
+    }
+
+    // `match x { pat if guard => body, ... }`를 파싱합니다. 블록 형태의 본문 뒤에는 쉼표를 생략할 수 있습니다.
+    fn parse_match(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        self.start_node(SyntaxKind::MatchExpr);
+        self.expect_keyword("match")?;
+        let scrutinee = self.parse_cond_expr()?;
+        self.start_node(SyntaxKind::MatchArmList);
+        self.expect(&TokenKind::OpenBrace, "`{`")?;
+        let old = std::mem::replace(&mut self.no_struct_literal, false);
+
+        let mut arms = Vec::new();
+        while !self.check(&TokenKind::CloseBrace) {
+            self.start_node(SyntaxKind::MatchArm);
+            self.eat(&TokenKind::Or);
+            let pat = self.parse_match_pat()?;
+            let guard = if self.check_keyword("if") {
+                self.start_node(SyntaxKind::MatchGuard);
+                self.bump();
+                let guard = self.parse_expr()?;
+                self.finish_node();
+                Some(guard)
+            } else {
+                None
+            };
+            self.expect(&TokenKind::FatArrow, "`=>`")?;
+            let block_like = self.check_block_like();
+            let body = if block_like {
+                self.parse_primary()?
+            } else {
+                self.parse_expr()?
+            };
+            let comma = self.eat(&TokenKind::Comma);
+            self.finish_node();
+            arms.push(Arm { pat, guard, body });
+            if !comma && !block_like && !self.check(&TokenKind::CloseBrace) {
+                return Err(self.unexpected("`,`"));
+            }
+        }
+        self.bump();
+        self.no_struct_literal = old;
+        self.finish_node();
+        self.finish_node();
+        Ok(self.mk_node(NodeKind::Match(Match { scrutinee, arms }), lo))
+    }
+
+    // 세미콜론이나 쉼표 없이도 끝나는 블록 형태의 표현식이 시작하는지 확인합니다.
+    fn check_block_like(&self) -> bool {
+        self.check(&TokenKind::OpenBrace)
+            || self.check_keyword("if")
+            || self.check_keyword("match")
+            || self.check_keyword("while")
+            || self.check_keyword("for")
+            || self.check_keyword("loop")
+    }
+
+    fn parse_while(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        self.start_node(SyntaxKind::WhileExpr);
+        self.expect_keyword("while")?;
+        let cond = self.parse_cond_expr()?;
+        let body = self.parse_block()?;
+        self.finish_node();
+        Ok(self.mk_node(NodeKind::While(While { cond, body }), lo))
+    }
+
+    // `for pat in iter { body }`를 파싱합니다.
+    fn parse_for(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        self.start_node(SyntaxKind::ForExpr);
+        self.expect_keyword("for")?;
+        let pat = self.parse_pat()?;
+        self.expect_keyword("in")?;
+        let iter = self.parse_cond_expr()?;
+        let body = self.parse_block()?;
+        self.finish_node();
+        Ok(self.mk_node(NodeKind::ForLoop(ForLoop { pat, iter, body }), lo))
+    }
+
+    // `{ stmt* }` 블록을 파싱합니다.
+    pub(crate) fn parse_block(&mut self) -> PResult<Block> {
+        if !self.check(&TokenKind::OpenBrace) {
+            return Err(self.unexpected("`{`"));
+        }
+        self.start_node(SyntaxKind::BlockExpr);
+        self.bump();
+        let old = std::mem::replace(&mut self.no_struct_literal, false);
+
+        let mut stmts = Vec::new();
+        while !self.check(&TokenKind::CloseBrace) {
+            if self.is_eof() {
+                return Err(self.unexpected("`}`"));
+            }
+            if self.eat(&TokenKind::Semi) {
+                continue;
+            }
+            stmts.push(self.parse_stmt()?);
+        }
+        self.bump();
+        self.finish_node();
+
+        self.no_struct_literal = old;
+        Ok(Block { stmts })
+    }
+
+    fn parse_stmt(&mut self) -> PResult<StmtId> {
+        let lo = self.lo();
+        let kind = self.parse_stmt_kind()?;
+        let stmt = Stmt { id: self.next_node_id(), span: self.span_from(lo), kind };
+        Ok(self.arena.alloc_stmt(stmt))
+    }
+
+    fn parse_stmt_kind(&mut self) -> PResult<StmtKind> {
+        if self.check_keyword("let") {
+            self.start_node(SyntaxKind::LetStmt);
+            self.bump();
+            let pat = self.parse_pat()?;
+            let ty = if self.eat(&TokenKind::Colon) {
+                Some(self.parse_ty()?)
+            } else {
+                None
+            };
+            let init = if self.eat(&TokenKind::Eq) {
+                Some(self.parse_expr()?)
+            } else {
+                None
+            };
+            self.expect(&TokenKind::Semi, "`;`")?;
+            self.finish_node();
+            return Ok(StmtKind::Let(Local { pat, ty, init }));
+        }
+
+        if self.check_item() {
+            let lo = self.lo();
+            return Ok(match self.parse_item()? {
+                Some(item) => StmtKind::Item(item),
+                None => StmtKind::Semi(self.mk_node(NodeKind::Tuple(Vec::new()), lo)),
+            });
+        }
+
+        // 블록 형태의 표현식은 세미콜론 없이도 문장이 됩니다. 블록의 마지막 표현식은
+        // 구문 트리에서 문장으로 감싸지 않고 블록의 값으로 둡니다.
+        let checkpoint = self.checkpoint();
+        let block_like = self.check_block_like();
+        let expr = if block_like {
+            self.parse_primary()?
+        } else {
+            self.parse_expr()?
+        };
+
+        let is_stmt = self.check(&TokenKind::Semi) || block_like && !self.check(&TokenKind::CloseBrace);
+        if is_stmt {
+            self.start_node_at(checkpoint, SyntaxKind::ExprStmt);
+        }
+        let kind = if self.eat(&TokenKind::Semi) {
+            StmtKind::Semi(expr)
+        } else if block_like || self.check(&TokenKind::CloseBrace) {
+            StmtKind::Expr(expr)
+        } else {
+            return Err(self.unexpected("`;`"));
+        };
+        if is_stmt {
+            self.finish_node();
+        }
+        Ok(kind)
+    }
+}
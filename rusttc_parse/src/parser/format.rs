@@ -0,0 +1,439 @@
+use rusttc_lexer::{Span, TokenKind};
+use rusttc_ast::ast::{
+    AstArena, FormatAlign, FormatArg, FormatArgKind, FormatArgs, FormatCount, FormatPiece, FormatPlaceholder, FormatSign,
+    FormatSpec, FormatTrait, Node, NodeKind,
+};
+
+use super::lit;
+use super::{ParseError, PResult, Parser};
+
+// 자리표시자가 인자를 가리키는 방식입니다.
+#[derive(Debug, Clone, PartialEq)]
+enum ArgRef {
+    Next,                       // {} (다음 위치 인자)
+    Index(usize),               // {0}
+    Name(String),               // {name}
+}
+
+// 인자를 해석하기 전의 너비, 정밀도입니다.
+#[derive(Debug, Clone, PartialEq)]
+enum RawCount {
+    Literal(usize),             // 8
+    Arg(ArgRef),                // 1$, width$
+    Star,                       // .* (다음 위치 인자)
+}
+
+// 인자를 해석하기 전의 자리표시자입니다.
+#[derive(Debug)]
+struct RawPlaceholder {
+    arg: ArgRef,
+    spec: FormatSpec,               // 너비와 정밀도는 비어 있습니다
+    width: Option<RawCount>,
+    precision: Option<RawCount>,
+    span: (usize, usize),           // 포맷 문자열 안의 `{`부터 `}`까지 (문자 단위)
+}
+
+#[derive(Debug)]
+enum RawPiece {
+    Literal(String),
+    Placeholder(RawPlaceholder),
+}
+
+// 포맷 문자열 안의 위치를 가리키는 오류입니다.
+#[derive(Debug)]
+struct FormatError {
+    message: String,
+    spans: Vec<(usize, usize)>,     // 밑줄을 그을 구간 (문자 단위)
+    label: String,                  // 밑줄 옆에 붙일 설명
+    note: Option<String>,           // 밑에 덧붙일 설명
+    primary: Span,                  // 리터럴 밖의 주 위치 (없으면 파서가 채웁니다)
+}
+
+impl FormatError {
+    fn new(message: impl Into<String>, span: (usize, usize), label: impl Into<String>) -> Self {
+        FormatError { message: message.into(), spans: vec![span], label: label.into(), note: None, primary: Span::DUMMY }
+    }
+
+    fn with_note(mut self, note: &str) -> Self {
+        self.note = Some(note.to_string());
+        self
+    }
+
+    // 문자열 리터럴 `literal`(따옴표 포함)을 보여 주고 오류 위치에 밑줄을 긋습니다.
+    fn render(&self, literal: &str) -> ParseError {
+        // 여는 따옴표 한 칸을 건너뜁니다.
+        let width = literal.chars().count();
+        let underline: String = (0..width)
+            .map(|column| {
+                let inside = self.spans.iter().any(|&(start, end)| column > start && column <= end.max(start + 1));
+                if inside { '^' } else { ' ' }
+            })
+            .collect();
+        let marker = format!("{} {}", underline.trim_end(), self.label);
+        let mut message = format!("{}\n  |\n  | {}\n  | {}", self.message, literal, marker.trim_end());
+        if let Some(note) = &self.note {
+            message.push_str(&format!("\n  = note: {}", note));
+        }
+        ParseError::new(message).with_span(self.primary)
+    }
+}
+
+impl Parser {
+    // `format_args!(` 다음부터 포맷 문자열과 인자를 파싱하고 검사합니다.
+    // 자리표시자와 인자의 수가 맞는지, 이름과 위치가 모두 해석되는지, 쓰이지 않는 인자가 없는지 확인합니다.
+    pub(crate) fn parse_format_args(&mut self, name: &str) -> PResult<FormatArgs> {
+        self.expect(&TokenKind::OpenParen, "`(`")?;
+        if !self.check(&TokenKind::StringLiteral) {
+            return Err(ParseError::new(format!("`{}!` requires a format string literal as its first argument", name)));
+        }
+        let literal_token = self.bump();
+        let (literal, literal_span) = (literal_token.text, literal_token.span);
+        let mut template = lit::parse_str(&literal).map_err(|error| error.with_span(literal_span))?;
+
+        let mut args: Vec<FormatArg> = Vec::new();
+        while self.eat(&TokenKind::Comma) && !self.check(&TokenKind::CloseParen) {
+            if self.check_ident() && self.look_ahead(1).kind == TokenKind::Eq {
+                let name = self.parse_ident()?;
+                self.bump();
+                if args.iter().any(|arg| arg.kind == FormatArgKind::Named(name.clone())) {
+                    return Err(ParseError::new(format!("duplicate argument named `{}`", name)));
+                }
+                let expr = self.parse_nested_expr()?;
+                args.push(FormatArg { kind: FormatArgKind::Named(name), expr });
+            } else {
+                if args.iter().any(|arg| arg.kind != FormatArgKind::Normal) {
+                    return Err(ParseError::new("positional arguments cannot follow named arguments"));
+                }
+                let expr = self.parse_nested_expr()?;
+                args.push(FormatArg { kind: FormatArgKind::Normal, expr });
+            }
+        }
+        self.expect(&TokenKind::CloseParen, "`)`")?;
+
+        let raw = parse_pieces(&template).map_err(|error| error.render(&literal))?;
+        let mut pieces = resolve_pieces(raw, &mut args, &mut self.arena, template.chars().count())
+            .map_err(|error| error.render(&literal))?;
+        // 문자열 안에서 잡아 온 변수는 포맷 문자열 리터럴의 위치를 씁니다.
+        for arg in args.iter_mut().filter(|arg| matches!(arg.kind, FormatArgKind::Captured(_))) {
+            let id = self.next_node_id();
+            let expr = &mut self.arena[arg.expr];
+            expr.id = id;
+            expr.span = literal_span;
+        }
+        if name == "format_args_nl" {
+            template.push_str("\\n");
+            match pieces.last_mut() {
+                Some(FormatPiece::Literal(text)) => text.push_str("\\n"),
+                _ => pieces.push(FormatPiece::Literal("\\n".to_string())),
+            }
+        }
+        Ok(FormatArgs { template, pieces, args })
+    }
+}
+
+// 포맷 문자열을 문자열 조각과 자리표시자로 나눕니다.
+fn parse_pieces(template: &str) -> Result<Vec<RawPiece>, FormatError> {
+    let chars: Vec<char> = template.chars().collect();
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1)) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                literal.push(chars[i]);
+                i += 2;
+            },
+            ('}', _) => {
+                let error = FormatError::new(
+                    "invalid format string: unmatched `}` found",
+                    (i, i + 1),
+                    "unmatched `}` in format string",
+                );
+                return Err(error.with_note("if you intended to print `}`, you can escape it using `}}`"));
+            },
+            ('{', _) => {
+                if !literal.is_empty() {
+                    pieces.push(RawPiece::Literal(std::mem::take(&mut literal)));
+                }
+                let (placeholder, end) = parse_placeholder(&chars, i)?;
+                pieces.push(RawPiece::Placeholder(placeholder));
+                i = end;
+            },
+            (c, _) => {
+                literal.push(c);
+                i += 1;
+            },
+        }
+    }
+    if !literal.is_empty() {
+        pieces.push(RawPiece::Literal(literal));
+    }
+    Ok(pieces)
+}
+
+// `chars[start]`의 `{`부터 `}`까지 자리표시자 하나를 파싱하고, `}` 다음 위치를 함께 돌려줍니다.
+fn parse_placeholder(chars: &[char], start: usize) -> Result<(RawPlaceholder, usize), FormatError> {
+    let mut pos = start + 1;
+    let arg = parse_arg_ref(chars, &mut pos);
+    let mut placeholder = RawPlaceholder { arg, spec: FormatSpec::default(), width: None, precision: None, span: (start, start) };
+
+    if chars.get(pos) == Some(&':') {
+        pos += 1;
+        parse_spec(chars, &mut pos, &mut placeholder)?;
+    }
+
+    match chars.get(pos) {
+        Some('}') => {
+            placeholder.span = (start, pos + 1);
+            Ok((placeholder, pos + 1))
+        },
+        Some(c) => Err(FormatError::new(
+            format!("invalid format string: expected `}}`, found `{}`", c),
+            (pos, pos + 1),
+            "expected `}` in format string",
+        )),
+        None => Err(FormatError::new(
+            "invalid format string: expected `}` but string was terminated",
+            (start, start + 1),
+            "because of this opening brace",
+        )
+        .with_note("if you intended to print `{`, you can escape it using `{{`")),
+    }
+}
+
+// `{0}`, `{name}`, `{}`의 인자 부분을 읽습니다.
+fn parse_arg_ref(chars: &[char], pos: &mut usize) -> ArgRef {
+    if let Some(index) = parse_integer(chars, pos) {
+        return ArgRef::Index(index);
+    }
+    match parse_identifier(chars, pos) {
+        Some(name) => ArgRef::Name(name),
+        None => ArgRef::Next,
+    }
+}
+
+// `[[fill]align][sign][#][0][width][.precision][type]`을 읽습니다.
+fn parse_spec(chars: &[char], pos: &mut usize, placeholder: &mut RawPlaceholder) -> Result<(), FormatError> {
+    let align = |c: Option<&char>| match c {
+        Some('<') => Some(FormatAlign::Left),
+        Some('^') => Some(FormatAlign::Center),
+        Some('>') => Some(FormatAlign::Right),
+        _ => None,
+    };
+    let spec = &mut placeholder.spec;
+    if let (Some(&fill), Some(align)) = (chars.get(*pos), align(chars.get(*pos + 1))) {
+        spec.fill = fill;
+        spec.align = Some(align);
+        *pos += 2;
+    } else if let Some(align) = align(chars.get(*pos)) {
+        spec.align = Some(align);
+        *pos += 1;
+    }
+
+    spec.sign = match chars.get(*pos) {
+        Some('+') => Some(FormatSign::Plus),
+        Some('-') => Some(FormatSign::Minus),
+        _ => None,
+    };
+    if spec.sign.is_some() {
+        *pos += 1;
+    }
+    if chars.get(*pos) == Some(&'#') {
+        spec.alternate = true;
+        *pos += 1;
+    }
+    // `0$`는 0번 인자를 너비로 쓰는 것이므로 플래그가 아닙니다.
+    if chars.get(*pos) == Some(&'0') && chars.get(*pos + 1) != Some(&'$') {
+        spec.zero_pad = true;
+        *pos += 1;
+    }
+
+    placeholder.width = parse_count(chars, pos);
+    if chars.get(*pos) == Some(&'.') {
+        *pos += 1;
+        if chars.get(*pos) == Some(&'*') {
+            *pos += 1;
+            placeholder.precision = Some(RawCount::Star);
+        } else {
+            let Some(count) = parse_count(chars, pos) else {
+                return Err(FormatError::new(
+                    "invalid format string: expected a count or `*` after `.`",
+                    (*pos - 1, *pos),
+                    "precision is missing",
+                ));
+            };
+            placeholder.precision = Some(count);
+        }
+    }
+
+    let start = *pos;
+    while chars.get(*pos).is_some_and(|&c| c.is_alphanumeric() || c == '?' || c == '_') {
+        *pos += 1;
+    }
+    let name: String = chars[start..*pos].iter().collect();
+    placeholder.spec.trait_ = FormatTrait::from_spec(&name).ok_or_else(|| {
+        FormatError::new(format!("unknown format trait `{}`", name), (start, *pos), "unknown format trait")
+            .with_note("the only appropriate formatting traits are `?`, `x?`, `X?`, `x`, `X`, `o`, `b`, `e` and `E`")
+    })?;
+    Ok(())
+}
+
+// 너비나 정밀도 `8`, `1$`, `name$`를 읽습니다. `$`가 없는 이름은 포맷 트레이트이므로 되돌립니다.
+fn parse_count(chars: &[char], pos: &mut usize) -> Option<RawCount> {
+    let start = *pos;
+    if let Some(n) = parse_integer(chars, pos) {
+        if chars.get(*pos) == Some(&'$') {
+            *pos += 1;
+            return Some(RawCount::Arg(ArgRef::Index(n)));
+        }
+        return Some(RawCount::Literal(n));
+    }
+    if let Some(name) = parse_identifier(chars, pos) {
+        if chars.get(*pos) == Some(&'$') {
+            *pos += 1;
+            return Some(RawCount::Arg(ArgRef::Name(name)));
+        }
+    }
+    *pos = start;
+    None
+}
+
+fn parse_integer(chars: &[char], pos: &mut usize) -> Option<usize> {
+    let start = *pos;
+    while chars.get(*pos).is_some_and(char::is_ascii_digit) {
+        *pos += 1;
+    }
+    let digits: String = chars[start..*pos].iter().collect();
+    digits.parse().ok()
+}
+
+fn parse_identifier(chars: &[char], pos: &mut usize) -> Option<String> {
+    if !chars.get(*pos).is_some_and(|&c| c.is_alphabetic() || c == '_') {
+        return None;
+    }
+    let start = *pos;
+    while chars.get(*pos).is_some_and(|&c| c.is_alphanumeric() || c == '_') {
+        *pos += 1;
+    }
+    Some(chars[start..*pos].iter().collect())
+}
+
+// 자리표시자가 가리키는 인자를 `args`의 위치로 바꿉니다.
+// 이름 있는 인자가 없는 `{name}`은 같은 이름의 변수를 캡처하는 인자로 `args` 끝에 더합니다.
+fn resolve_pieces(
+    raw: Vec<RawPiece>,
+    args: &mut Vec<FormatArg>,
+    arena: &mut AstArena,
+    len: usize,
+) -> Result<Vec<FormatPiece>, FormatError> {
+    let positional = args.iter().filter(|arg| arg.kind == FormatArgKind::Normal).count();
+    let used = vec![false; args.len()];
+    let mut resolver = ArgResolver { args, arena, positional, used, next: 0, missing: Vec::new() };
+
+    let mut pieces = Vec::new();
+    for piece in raw {
+        match piece {
+            RawPiece::Literal(text) => pieces.push(FormatPiece::Literal(text)),
+            RawPiece::Placeholder(raw) => {
+                let mut spec = raw.spec;
+                spec.width = raw.width.map(|count| resolver.count(count, raw.span)).transpose()?;
+                // `.*`의 정밀도 인자는 값 인자보다 먼저 차례가 옵니다.
+                spec.precision = raw.precision.map(|count| resolver.count(count, raw.span)).transpose()?;
+                let arg = resolver.arg(raw.arg, raw.span)?;
+                pieces.push(FormatPiece::Placeholder(FormatPlaceholder { arg, spec }));
+            },
+        }
+    }
+
+    let (positional, next) = (resolver.positional, resolver.next);
+    if !resolver.missing.is_empty() {
+        let message = format!(
+            "{} positional argument{} in format string, but {}",
+            next,
+            if next == 1 { "" } else { "s" },
+            arguments(positional, "there is", "there are")
+        );
+        return Err(FormatError { message, spans: resolver.missing, label: String::new(), note: None, primary: Span::DUMMY });
+    }
+
+    let unused: Vec<&FormatArg> = resolver.args.iter().zip(&resolver.used).filter(|(_, used)| !**used).map(|(arg, _)| arg).collect();
+    let message = match unused.as_slice() {
+        [] => return Ok(pieces),
+        [FormatArg { kind: FormatArgKind::Named(name), .. }] => format!("named argument `{}` never used", name),
+        [_] => "argument never used".to_string(),
+        _ => format!("{} unused formatting arguments", unused.len()),
+    };
+    // rustc처럼 쓰이지 않은 (첫) 인자를 주 위치로 삼고, 리터럴에는 자리표시자가 빠졌다는 설명만 붙입니다.
+    let mut error = FormatError::new(message, (0, len), "formatting specifier missing");
+    error.primary = resolver.arena[unused[0].expr].span;
+    Err(error)
+}
+
+// "1 argument", "2 arguments", "no arguments were given"
+fn arguments(count: usize, singular: &str, plural: &str) -> String {
+    match count {
+        0 => "no arguments were given".to_string(),
+        1 => format!("{} 1 argument", singular),
+        n => format!("{} {} arguments", plural, n),
+    }
+}
+
+struct ArgResolver<'a> {
+    args: &'a mut Vec<FormatArg>,
+    arena: &'a mut AstArena,        // 잡아 온 변수의 표현식을 넣을 곳
+    positional: usize,              // 위치 인자 수
+    used: Vec<bool>,                // 인자마다 쓰였는지
+    next: usize,                    // 다음 `{}`가 가리킬 위치 인자
+    missing: Vec<(usize, usize)>,   // 위치 인자가 모자란 `{}`
+}
+
+impl ArgResolver<'_> {
+    fn arg(&mut self, arg: ArgRef, span: (usize, usize)) -> Result<usize, FormatError> {
+        let index = match arg {
+            ArgRef::Next => {
+                self.next += 1;
+                if self.next > self.positional {
+                    self.missing.push(span);
+                    return Ok(0);
+                }
+                self.next - 1
+            },
+            ArgRef::Index(index) => {
+                if index >= self.positional {
+                    let message = format!(
+                        "invalid reference to positional argument {} ({})",
+                        index,
+                        arguments(self.positional, "there is", "there are")
+                    );
+                    return Err(FormatError::new(message, span, ""));
+                }
+                index
+            },
+            ArgRef::Name(name) => {
+                let named = self.args.iter().position(|arg| match &arg.kind {
+                    FormatArgKind::Named(other) | FormatArgKind::Captured(other) => *other == name,
+                    FormatArgKind::Normal => false,
+                });
+                match named {
+                    Some(index) => index,
+                    None => {
+                        let expr = self.arena.alloc_expr(Node::from(NodeKind::Ident(name.clone())));
+                        self.args.push(FormatArg { kind: FormatArgKind::Captured(name), expr });
+                        self.used.push(false);
+                        self.args.len() - 1
+                    },
+                }
+            },
+        };
+        self.used[index] = true;
+        Ok(index)
+    }
+
+    fn count(&mut self, count: RawCount, span: (usize, usize)) -> Result<FormatCount, FormatError> {
+        match count {
+            RawCount::Literal(n) => Ok(FormatCount::Literal(n)),
+            RawCount::Arg(arg) => Ok(FormatCount::Arg(self.arg(arg, span)?)),
+            RawCount::Star => Ok(FormatCount::Arg(self.arg(ArgRef::Next, span)?)),
+        }
+    }
+}
@@ -0,0 +1,432 @@
+use rusttc_lexer::{Span, TokenKind};
+use rusttc_ast::ast::{
+    Attribute, ConstDef, FieldDef, FnDef, GenericParam, Generics, Impl, Item, ModDef, Param, Path, PathSegment, SelfKind, StructDef,
+    TraitDef, Ty, TyKind, UseDecl, UseTree, UseTreeKind, Visibility,
+};
+
+use super::ty::PathStyle;
+use super::{ParseError, PResult, Parser};
+use crate::syntax_kind::SyntaxKind;
+
+impl Parser {
+    // 아이템이 시작되는 위치인지 확인합니다.
+    pub(crate) fn check_item(&self) -> bool {
+        self.check_keyword("fn")
+            || self.check_keyword("struct")
+            || self.check_keyword("impl")
+            || self.check_keyword("trait")
+            || self.check_keyword("mod")
+            || self.check_keyword("use")
+            || self.check_keyword("const")
+            || self.check_keyword("static")
+            || self.check_keyword("pub")
+            || self.check(&TokenKind::Pound)
+            || self.is_building_syntax() && self.check_item_macro()
+    }
+
+    // 아이템 자리의 매크로 호출 `name! { ... }`, `macro_rules! name { ... }`입니다.
+    fn check_item_macro(&self) -> bool {
+        self.check_ident() && self.look_ahead(1).kind == TokenKind::Bang && self.look_ahead(2).kind == TokenKind::Ident
+            || self.check_keyword("macro_rules") && self.look_ahead(1).kind == TokenKind::Bang
+    }
+
+    // 아이템의 위치는 속성을 포함해 첫 토큰부터 잡습니다. 속성과 가시성까지 읽은 뒤에야
+    // 아이템의 종류를 알 수 있으므로, 구문 트리의 노드는 그 앞에 둔 체크포인트부터 엽니다.
+    // 구문 트리만 쌓을 때 만난 매크로 호출은 AST 아이템이 없으므로 `None`입니다.
+    pub(crate) fn parse_item(&mut self) -> PResult<Option<Item>> {
+        let lo = self.lo();
+        let checkpoint = self.checkpoint();
+        let attrs = self.parse_outer_attrs()?;
+        let vis = self.parse_vis()?;
+        let item = if self.check_keyword("fn") {
+            self.start_node_at(checkpoint, SyntaxKind::Fn);
+            Item::Fn(self.parse_fn(attrs, vis, false, lo)?)
+        } else if self.check_keyword("struct") {
+            self.start_node_at(checkpoint, SyntaxKind::Struct);
+            Item::Struct(self.parse_struct(attrs, vis, lo)?)
+        } else if self.check_keyword("impl") {
+            if vis == Visibility::Public {
+                return Err(ParseError::new("unnecessary visibility qualifier: `pub` not permitted here"));
+            }
+            self.start_node_at(checkpoint, SyntaxKind::Impl);
+            Item::Impl(self.parse_impl(attrs, lo)?)
+        } else if self.check_keyword("trait") {
+            self.start_node_at(checkpoint, SyntaxKind::Trait);
+            Item::Trait(self.parse_trait(attrs, vis, lo)?)
+        } else if self.check_keyword("mod") {
+            self.start_node_at(checkpoint, SyntaxKind::Module);
+            Item::Mod(self.parse_mod(attrs, vis, lo)?)
+        } else if self.check_keyword("use") {
+            self.start_node_at(checkpoint, SyntaxKind::Use);
+            Item::Use(self.parse_use(attrs, vis, lo)?)
+        } else if self.check_keyword("const") {
+            self.start_node_at(checkpoint, SyntaxKind::Const);
+            Item::Const(self.parse_const(attrs, vis, lo)?)
+        } else if self.check_keyword("static") {
+            self.start_node_at(checkpoint, SyntaxKind::Static);
+            Item::Static(self.parse_const(attrs, vis, lo)?)
+        } else if self.is_building_syntax() && self.check_ident() && self.look_ahead(1).kind == TokenKind::Bang {
+            // 괄호로 묶은 호출 뒤의 `;`도 호출에 넣습니다.
+            self.start_node_at(checkpoint, SyntaxKind::MacroCall);
+            self.parse_macro_call_body()?;
+            self.eat(&TokenKind::Semi);
+            self.finish_node();
+            return Ok(None);
+        } else if !attrs.is_empty() {
+            return Err(ParseError::new("expected item after attributes"));
+        } else {
+            return Err(self.unexpected("item"));
+        };
+        self.finish_node();
+        Ok(Some(item))
+    }
+
+    // 아이템 목록을 `}`나 입력 끝까지 파싱합니다.
+    pub(crate) fn parse_items(&mut self) -> PResult<Vec<Item>> {
+        let mut items = Vec::new();
+        while !self.check(&TokenKind::CloseBrace) && !self.is_eof() {
+            items.extend(self.parse_item()?);
+        }
+        Ok(items)
+    }
+
+    // `pub`, `pub(crate)` 또는 가시성 표기 없음
+    fn parse_vis(&mut self) -> PResult<Visibility> {
+        if !self.check_keyword("pub") {
+            return Ok(Visibility::Private);
+        }
+        self.start_node(SyntaxKind::Visibility);
+        self.bump();
+        if self.check(&TokenKind::OpenParen) && self.look_ahead(1).text == "crate" {
+            self.bump();
+            self.bump();
+            self.expect(&TokenKind::CloseParen, "`)`")?;
+        }
+        self.finish_node();
+        Ok(Visibility::Public)
+    }
+
+    // 정의하는 이름입니다. 구문 트리에서는 `Name` 노드가 됩니다.
+    pub(crate) fn parse_name(&mut self) -> PResult<String> {
+        if !self.check_ident() {
+            return Err(self.unexpected("identifier"));
+        }
+        self.start_node(SyntaxKind::Name);
+        let name = self.bump().text;
+        self.finish_node();
+        Ok(name)
+    }
+
+    // `fn name<T: Bound>(params) -> Ty { body }`
+    // 트레이트 안에서는 본문 대신 `;`로 끝나는 선언을 허용합니다.
+    fn parse_fn(&mut self, attrs: Vec<Attribute>, vis: Visibility, in_trait: bool, lo: Span) -> PResult<FnDef> {
+        self.expect_keyword("fn")?;
+        let name = self.parse_name()?;
+        let generics = self.parse_generics()?;
+        if !self.check(&TokenKind::OpenParen) {
+            return Err(self.unexpected("`(`"));
+        }
+        self.start_node(SyntaxKind::ParamList);
+        self.bump();
+
+        let self_param = self.parse_self_param()?;
+        let mut params = Vec::new();
+        if self_param.is_none() || self.eat(&TokenKind::Comma) {
+            while !self.check(&TokenKind::CloseParen) {
+                self.start_node(SyntaxKind::Param);
+                let pat = self.parse_pat()?;
+                self.expect(&TokenKind::Colon, "`:`")?;
+                let ty = self.parse_ty()?;
+                self.finish_node();
+                params.push(Param { pat, ty });
+                if !self.eat(&TokenKind::Comma) {
+                    break;
+                }
+            }
+        }
+        self.expect(&TokenKind::CloseParen, "`)`")?;
+        self.finish_node();
+
+        let ret = self.parse_ret_ty()?;
+        let body = if in_trait && self.eat(&TokenKind::Semi) {
+            None
+        } else {
+            Some(self.parse_block()?)
+        };
+
+        Ok(FnDef { attrs, id: self.next_node_id(), span: self.span_from(lo), vis, name, generics, self_param, params, ret, body })
+    }
+
+    // `-> Ty` 반환 타입이 있으면 파싱합니다.
+    pub(crate) fn parse_ret_ty(&mut self) -> PResult<Option<Ty>> {
+        if !self.check(&TokenKind::RArrow) {
+            return Ok(None);
+        }
+        self.start_node(SyntaxKind::RetType);
+        self.bump();
+        let ty = self.parse_ty()?;
+        self.finish_node();
+        Ok(Some(ty))
+    }
+
+    // `<T: A + B, U>` 제네릭 매개변수 목록을 파싱합니다.
+    pub(crate) fn parse_generics(&mut self) -> PResult<Generics> {
+        let mut generics = Generics::default();
+        if !self.check(&TokenKind::Lt) {
+            return Ok(generics);
+        }
+        self.start_node(SyntaxKind::GenericParamList);
+        self.bump();
+
+        while !self.check(&TokenKind::Gt) {
+            self.start_node(SyntaxKind::GenericParam);
+            let name = self.parse_name()?;
+            let mut bounds = Vec::new();
+            if self.eat(&TokenKind::Colon) {
+                loop {
+                    bounds.push(self.parse_path(PathStyle::Type)?);
+                    if !self.eat(&TokenKind::Plus) {
+                        break;
+                    }
+                }
+            }
+            self.finish_node();
+            generics.params.push(GenericParam { name, bounds });
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(&TokenKind::Gt, "`>`")?;
+        self.finish_node();
+
+        Ok(generics)
+    }
+
+    // `self`, `mut self`, `&self`, `&mut self` 리시버를 파싱합니다.
+    fn parse_self_param(&mut self) -> PResult<Option<SelfKind>> {
+        let is_self = |parser: &Self, n: usize| {
+            let token = parser.look_ahead(n);
+            token.kind == TokenKind::Ident && token.text == "self"
+        };
+        let is_mut = |parser: &Self, n: usize| {
+            let token = parser.look_ahead(n);
+            token.kind == TokenKind::Ident && token.text == "mut"
+        };
+
+        let (kind, len) = if is_self(self, 0) {
+            (SelfKind::Value { mutable: false }, 1)
+        } else if is_mut(self, 0) && is_self(self, 1) {
+            (SelfKind::Value { mutable: true }, 2)
+        } else if self.check(&TokenKind::And) && is_self(self, 1) {
+            (SelfKind::Ref, 2)
+        } else if self.check(&TokenKind::And) && is_mut(self, 1) && is_self(self, 2) {
+            (SelfKind::RefMut, 3)
+        } else {
+            return Ok(None);
+        };
+
+        self.start_node(SyntaxKind::SelfParam);
+        for _ in 0..len {
+            self.bump();
+        }
+        self.finish_node();
+        Ok(Some(kind))
+    }
+
+    // `struct Name { field: Ty, ... }` 또는 `struct Name;`
+    fn parse_struct(&mut self, attrs: Vec<Attribute>, vis: Visibility, lo: Span) -> PResult<StructDef> {
+        self.expect_keyword("struct")?;
+        let name = self.parse_name()?;
+        let generics = self.parse_generics()?;
+        let mut fields = Vec::new();
+
+        if self.eat(&TokenKind::Semi) {
+            return Ok(StructDef { attrs, id: self.next_node_id(), span: self.span_from(lo), vis, name, generics, fields });
+        }
+
+        if !self.check(&TokenKind::OpenBrace) {
+            return Err(self.unexpected("`{` or `;`"));
+        }
+        self.start_node(SyntaxKind::FieldList);
+        self.bump();
+        while !self.check(&TokenKind::CloseBrace) {
+            self.start_node(SyntaxKind::Field);
+            let vis = self.parse_vis()?;
+            let name = self.parse_name()?;
+            self.expect(&TokenKind::Colon, "`:`")?;
+            let ty = self.parse_ty()?;
+            self.finish_node();
+            fields.push(FieldDef { vis, name, ty });
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(&TokenKind::CloseBrace, "`}`")?;
+        self.finish_node();
+
+        Ok(StructDef { attrs, id: self.next_node_id(), span: self.span_from(lo), vis, name, generics, fields })
+    }
+
+    // `impl Type { fn ... }` 또는 `impl Trait for Type { fn ... }`
+    fn parse_impl(&mut self, attrs: Vec<Attribute>, lo: Span) -> PResult<Impl> {
+        self.expect_keyword("impl")?;
+        let generics = self.parse_generics()?;
+        let mut self_ty = self.parse_ty()?;
+        let mut of_trait = None;
+
+        if self.eat_keyword("for") {
+            match self_ty.kind {
+                TyKind::Path(path) => of_trait = Some(path),
+                _ => return Err(ParseError::new("expected a trait, found type")),
+            }
+            self_ty = self.parse_ty()?;
+        }
+
+        let items = self.parse_fn_list(false)?;
+        Ok(Impl { attrs, id: self.next_node_id(), span: self.span_from(lo), generics, of_trait, self_ty, items })
+    }
+
+    // `trait Name { fn ...; fn ... { default } }`
+    fn parse_trait(&mut self, attrs: Vec<Attribute>, vis: Visibility, lo: Span) -> PResult<TraitDef> {
+        self.expect_keyword("trait")?;
+        let name = self.parse_name()?;
+        let items = self.parse_fn_list(true)?;
+        Ok(TraitDef { attrs, id: self.next_node_id(), span: self.span_from(lo), vis, name, items })
+    }
+
+    // 트레이트 메서드는 트레이트의 가시성을 따르므로 `pub`을 붙일 수 없습니다.
+    fn parse_fn_list(&mut self, in_trait: bool) -> PResult<Vec<FnDef>> {
+        if !self.check(&TokenKind::OpenBrace) {
+            return Err(self.unexpected("`{`"));
+        }
+        self.start_node(SyntaxKind::ItemList);
+        self.bump();
+        let mut items = Vec::new();
+        while !self.check(&TokenKind::CloseBrace) && !self.is_eof() {
+            let lo = self.lo();
+            let checkpoint = self.checkpoint();
+            let attrs = self.parse_outer_attrs()?;
+            let vis = self.parse_vis()?;
+            if in_trait && vis == Visibility::Public {
+                return Err(ParseError::new("unnecessary visibility qualifier: `pub` not permitted here"));
+            }
+            self.start_node_at(checkpoint, SyntaxKind::Fn);
+            items.push(self.parse_fn(attrs, vis, in_trait, lo)?);
+            self.finish_node();
+        }
+        self.expect(&TokenKind::CloseBrace, "`}`")?;
+        self.finish_node();
+        Ok(items)
+    }
+
+    // `mod name;` 또는 `mod name { #![inner] items }`
+    // 본문 맨 앞의 안쪽 속성은 모듈 자신의 속성이 됩니다.
+    fn parse_mod(&mut self, mut attrs: Vec<Attribute>, vis: Visibility, lo: Span) -> PResult<ModDef> {
+        self.expect_keyword("mod")?;
+        let name = self.parse_name()?;
+        if self.eat(&TokenKind::Semi) {
+            return Ok(ModDef { attrs, id: self.next_node_id(), span: self.span_from(lo), vis, name, items: None });
+        }
+
+        if !self.check(&TokenKind::OpenBrace) {
+            return Err(self.unexpected("`{` or `;`"));
+        }
+        self.start_node(SyntaxKind::ItemList);
+        self.bump();
+        attrs.extend(self.parse_inner_attrs()?);
+        let items = self.parse_items()?;
+        self.expect(&TokenKind::CloseBrace, "`}`")?;
+        self.finish_node();
+        Ok(ModDef { attrs, id: self.next_node_id(), span: self.span_from(lo), vis, name, items: Some(items) })
+    }
+
+    // `const NAME: Ty = value;` 또는 `static NAME: Ty = value;`
+    // 타입은 생략할 수 없고, 값을 바꿀 수 있는 `static mut`은 지원하지 않습니다.
+    fn parse_const(&mut self, attrs: Vec<Attribute>, vis: Visibility, lo: Span) -> PResult<ConstDef> {
+        let keyword = self.bump().text;
+        if keyword == "static" && self.check_keyword("mut") {
+            return Err(ParseError::new("`static mut` items are not supported"));
+        }
+        let name = self.parse_name()?;
+        if !self.eat(&TokenKind::Colon) {
+            return Err(ParseError::new(format!("missing type for `{}` item", keyword)));
+        }
+        let ty = self.parse_ty()?;
+        self.expect(&TokenKind::Eq, "`=`")?;
+        let value = self.parse_expr()?;
+        self.expect(&TokenKind::Semi, "`;`")?;
+        Ok(ConstDef { attrs, id: self.next_node_id(), span: self.span_from(lo), vis, name, ty, value })
+    }
+
+    // `use a::b::{c, d as e, f::*};`
+    fn parse_use(&mut self, attrs: Vec<Attribute>, vis: Visibility, lo: Span) -> PResult<UseDecl> {
+        self.expect_keyword("use")?;
+        let tree = self.parse_use_tree()?;
+        self.expect(&TokenKind::Semi, "`;`")?;
+        Ok(UseDecl { attrs, id: self.next_node_id(), span: self.span_from(lo), vis, tree })
+    }
+
+    // 접두사 경로 `a::b` 뒤에 `as e`가 오거나, `::` 다음에 `*`나 `{...}`가 옵니다. 접두사는 없을 수도 있습니다.
+    fn parse_use_tree(&mut self) -> PResult<UseTree> {
+        self.start_node(SyntaxKind::UseTree);
+        let mut segments = Vec::new();
+        if !self.check(&TokenKind::Star) && !self.check(&TokenKind::OpenBrace) {
+            self.start_node(SyntaxKind::Path);
+            loop {
+                self.start_node(SyntaxKind::PathSegment);
+                segments.push(PathSegment::from_ident(&self.parse_segment_ident()?));
+                self.finish_node();
+                if !(self.check(&TokenKind::PathSep) && self.look_ahead(1).kind == TokenKind::Ident) {
+                    break;
+                }
+                self.bump();
+            }
+            self.finish_node();
+
+            if !self.eat(&TokenKind::PathSep) {
+                let rename = if self.check_keyword("as") {
+                    self.start_node(SyntaxKind::Rename);
+                    self.bump();
+                    let name = self.parse_name()?;
+                    self.finish_node();
+                    Some(name)
+                } else {
+                    None
+                };
+                self.finish_node();
+                return Ok(UseTree { prefix: Path { segments }, kind: UseTreeKind::Simple(rename) });
+            }
+        }
+
+        let kind = if self.eat(&TokenKind::Star) {
+            UseTreeKind::Glob
+        } else if self.check(&TokenKind::OpenBrace) {
+            self.start_node(SyntaxKind::UseTreeList);
+            self.bump();
+            let mut trees = Vec::new();
+            while !self.check(&TokenKind::CloseBrace) {
+                trees.push(self.parse_use_tree()?);
+                if !self.eat(&TokenKind::Comma) {
+                    break;
+                }
+            }
+            self.expect(&TokenKind::CloseBrace, "`}`")?;
+            self.finish_node();
+            UseTreeKind::Nested(trees)
+        } else {
+            return Err(self.unexpected("identifier"));
+        };
+        self.finish_node();
+        Ok(UseTree { prefix: Path { segments }, kind })
+    }
+
+    // 경로, `!`, (`macro_rules!`의 경우) 이름, 토큰 트리입니다. 구문 트리를 쌓을 때만 씁니다.
+    pub(crate) fn parse_macro_call_body(&mut self) -> PResult<()> {
+        self.parse_path(PathStyle::Expr)?;
+        self.expect(&TokenKind::Bang, "`!`")?;
+        if self.check_ident() {
+            self.parse_name()?;
+        }
+        self.parse_token_tree()
+    }
+}
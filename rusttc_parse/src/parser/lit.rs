@@ -0,0 +1,158 @@
+use std::str::Chars;
+
+use rusttc_ast::ast::{FloatTy, IntTy, NodeKind};
+
+use super::ParseError;
+
+// 숫자 리터럴 토큰을 정수나 실수 리터럴로 바꿉니다.
+// `1_000`, `0xff`, `255u8`, `1.5`, `1e3`, `2f32`를 받습니다. 접미사가 `f32`, `f64`이면 실수입니다.
+pub(crate) fn parse_number(text: &str) -> Result<NodeKind, ParseError> {
+    let (radix, body) = match text.get(..2) {
+        Some("0x") => (16, &text[2..]),
+        Some("0o") => (8, &text[2..]),
+        Some("0b") => (2, &text[2..]),
+        _ => (10, text),
+    };
+
+    // 숫자 부분이 끝나는 곳부터가 접미사입니다. 10진수라면 소수점과 지수도 숫자 부분입니다.
+    let mut end = 0;
+    let mut chars = body.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let exponent_sign = matches!(c, '+' | '-') && radix == 10 && matches!(body[..i].chars().last(), Some('e' | 'E'));
+        let exponent = matches!(c, 'e' | 'E') && radix == 10 && chars.peek().is_some_and(|&(_, c)| c.is_ascii_digit() || c == '+' || c == '-');
+        if c.is_digit(radix) || c == '_' || (c == '.' && radix == 10) || exponent || exponent_sign {
+            end = i + c.len_utf8();
+        } else {
+            break;
+        }
+    }
+    let (digits, suffix) = body.split_at(end);
+    let digits = digits.replace('_', "");
+    let is_float = radix == 10 && digits.contains(['.', 'e', 'E']);
+
+    if digits.is_empty() || digits.ends_with(['e', 'E', '+', '-']) {
+        return Err(ParseError::new(match is_float {
+            true => "expected at least one digit in exponent".to_string(),
+            false => format!("no valid digits found for number `{}`", text),
+        }));
+    }
+    if let Some(ty) = FloatTy::from_name(suffix) {
+        if radix != 10 {
+            return Err(ParseError::new(format!("invalid suffix `{}` for number literal", suffix)));
+        }
+        return Ok(NodeKind::Float(digits.parse().unwrap_or(f64::INFINITY), Some(ty)));
+    }
+    let suffix = match suffix {
+        "" => None,
+        _ => match IntTy::from_name(suffix) {
+            Some(ty) if !is_float => Some(ty),
+            Some(_) => return Err(ParseError::new(format!("invalid suffix `{}` for float literal", suffix))),
+            None => return Err(ParseError::new(format!("invalid suffix `{}` for number literal", suffix))),
+        },
+    };
+    if is_float {
+        return Ok(NodeKind::Float(digits.parse().unwrap_or(f64::INFINITY), None));
+    }
+    match u128::from_str_radix(&digits, radix) {
+        Ok(value) => Ok(NodeKind::Int(value, suffix)),
+        Err(_) => Err(ParseError::new("integer literal is too large")),
+    }
+}
+
+// 문자 리터럴 토큰(`'a'`, `'\n'`, `'\x41'`, `'\u{1F600}'`)을 문자로 바꿉니다.
+pub(crate) fn parse_char(text: &str) -> Result<char, ParseError> {
+    let Some(inner) = text.strip_prefix('\'').and_then(|text| text.strip_suffix('\'')).filter(|_| text.len() > 1) else {
+        return Err(ParseError::new("unterminated character literal"));
+    };
+    let mut chars = inner.chars();
+    let c = match chars.next() {
+        None => return Err(ParseError::new("empty character literal")),
+        Some('\\') => unescape(&mut chars)?.ok_or_else(|| ParseError::new("unterminated character literal"))?,
+        Some(c) => c,
+    };
+    match chars.next() {
+        None => Ok(c),
+        Some(_) => Err(ParseError::new("character literal may only contain one codepoint")),
+    }
+}
+
+// 문자열 리터럴 토큰의 이스케이프를 검사하고 따옴표를 뗀 내용을 돌려줍니다.
+// 내용은 소스에 쓴 그대로(이스케이프를 풀지 않은 채) 두므로 다시 출력해도 같은 리터럴이 됩니다.
+// 줄 끝의 `\`는 다음 줄 앞의 공백과 함께 건너뛰는 줄 이음입니다.
+pub(crate) fn parse_str(text: &str) -> Result<String, ParseError> {
+    let inner = &text[1..text.len() - 1];
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            continue;
+        }
+        if chars.as_str().starts_with('\n') {
+            chars.next();
+            continue;
+        }
+        unescape(&mut chars)?;
+    }
+    Ok(inner.to_string())
+}
+
+// `\` 다음의 이스케이프 하나를 읽어 그 문자를 돌려줍니다. 입력이 끝났으면 None입니다.
+// `\x`는 두 자리 16진수로 된 ASCII 문자(`\x00`..`\x7f`), `\u{...}`는 1~6자리 16진수로 된 유니코드 스칼라 값입니다.
+fn unescape(chars: &mut Chars) -> Result<Option<char>, ParseError> {
+    let c = match chars.next() {
+        None => return Ok(None),
+        Some('n') => '\n',
+        Some('t') => '\t',
+        Some('r') => '\r',
+        Some('0') => '\0',
+        Some(c @ ('\\' | '\'' | '"')) => c,
+        Some('x') => {
+            let digits: String = chars.clone().take(2).collect();
+            if digits.chars().count() < 2 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(match digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+                    Some(c) => ParseError::new(format!("invalid character in numeric character escape: `{}`", c)),
+                    _ => ParseError::new("numeric character escape is too short"),
+                });
+            }
+            chars.nth(1);
+            let value = u8::from_str_radix(&digits, 16).unwrap();
+            if value > 0x7f {
+                return Err(ParseError::new(format!(
+                    "out of range hex escape: `\\x{}`\n  = note: must be a character in the range [\\x00-\\x7f]",
+                    digits
+                )));
+            }
+            value as char
+        },
+        Some('u') => unescape_unicode(chars)?,
+        Some(c) => return Err(ParseError::new(format!("unknown character escape: `{}`", c))),
+    };
+    Ok(Some(c))
+}
+
+// `\u` 다음의 `{...}`를 읽습니다. 숫자 사이의 `_`는 건너뜁니다.
+fn unescape_unicode(chars: &mut Chars) -> Result<char, ParseError> {
+    if chars.next() != Some('{') {
+        return Err(ParseError::new("incorrect unicode escape sequence\n  = help: format of unicode escape sequences is `\\u{...}`"));
+    }
+    let mut digits = String::new();
+    loop {
+        match chars.next() {
+            Some('}') => break,
+            Some('_') if !digits.is_empty() => {},
+            Some(c) if c.is_ascii_hexdigit() => digits.push(c),
+            None => return Err(ParseError::new("unterminated unicode escape")),
+            Some(c) => return Err(ParseError::new(format!("invalid character in unicode escape: `{}`", c))),
+        }
+    }
+    if digits.is_empty() {
+        return Err(ParseError::new("empty unicode escape"));
+    }
+    if digits.len() > 6 {
+        return Err(ParseError::new("overlong unicode escape\n  = note: must have at most 6 hex digits"));
+    }
+    let value = u32::from_str_radix(&digits, 16).unwrap();
+    char::from_u32(value).ok_or_else(|| {
+        let help = if (0xD800..=0xDFFF).contains(&value) { "must not be a surrogate" } else { "must be at most 10FFFF" };
+        ParseError::new(format!("invalid unicode character escape\n  = help: unicode escape {}", help))
+    })
+}
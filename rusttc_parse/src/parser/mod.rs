@@ -0,0 +1,290 @@
+mod attr;
+mod event;
+mod expr;
+mod format;
+mod item;
+mod lit;
+mod ty;
+
+use std::fmt;
+
+use rusttc_lexer::{Span, Token, TokenKind};
+use rusttc_ast::arena::MAX_DEPTH;
+use rusttc_ast::ast::{AstArena, Attribute, Crate, ExprId, Item, Node, NodeId, NodeKind};
+
+pub use event::{parse_block_events, parse_source_file_events, Event, SyntaxEvents};
+
+// 예약어 목록입니다. 식별자 자리에 올 수 없습니다.
+pub const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "else", "false", "fn", "for", "if", "impl", "in", "let", "loop",
+    "match", "mod", "move", "mut", "pub", "return", "self", "Self", "static", "struct", "super", "trait", "true", "use", "while",
+];
+
+// 파싱 중에 발생한 오류입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Span,             // 오류가 난 토큰의 위치
+}
+
+impl ParseError {
+    // 위치는 파서가 오류를 돌려줄 때 마지막으로 읽은 토큰으로 채웁니다.
+    pub fn new(message: impl Into<String>) -> Self {
+        ParseError { message: message.into(), span: Span::DUMMY }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    // 아직 위치가 없는 오류에만 `span`을 붙입니다.
+    pub fn or_span(self, span: Span) -> Self {
+        if self.span.is_dummy() {
+            self.with_span(span)
+        } else {
+            self
+        }
+    }
+
+    // 소스가 `max_depth`보다 깊이 겹쳐 있습니다.
+    fn too_deep(max_depth: usize, span: Span) -> Self {
+        ParseError::new(format!("nesting exceeds the supported depth of {} levels", max_depth)).with_span(span)
+    }
+}
+
+// 형식 문자열 오류처럼 메시지가 여러 줄이면 위치는 첫 줄 바로 뒤에 둡니다.
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (head, rest) = match self.message.split_once('\n') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (self.message.as_str(), None),
+        };
+        write!(f, "error: {}", head)?;
+        if let Some(location) = self.span.location() {
+            write!(f, "\n  --> {}", location)?;
+        }
+        if let Some(rest) = rest {
+            write!(f, "\n{}", rest)?;
+        }
+        Ok(())
+    }
+}
+
+pub type PResult<T> = Result<T, ParseError>;
+
+// 토큰 목록을 표현식의 나열로 파싱합니다. 표현식은 함께 돌려주는 저장소에 들어 있습니다.
+pub fn parse(tokens: &[Token]) -> (AstArena, Vec<ExprId>) {
+    let mut parser = Parser::new(tokens);
+    let mut nodes = Vec::new();
+
+    while !parser.is_eof() {
+        if parser.eat(&TokenKind::Semi) {
+            continue;
+        }
+        match parser.parse_expr() {
+            Ok(node) => nodes.push(node),
+            Err(_) => break,
+        }
+    }
+
+    (parser.arena, nodes)
+}
+
+// 토큰 목록을 크레이트(아이템 목록)로 파싱합니다.
+pub fn parse_crate(tokens: &[Token]) -> PResult<Crate> {
+    parse_crate_from(tokens, AstArena::default(), NodeId::FIRST).map(|(krate, _)| krate)
+}
+
+// 노드 번호를 `first_id`부터 매기며 크레이트를 파싱하고, 다음에 쓸 번호를 함께 돌려줍니다.
+// 여러 파일로 된 크레이트는 파일마다 이어서 번호를 매겨 크레이트 전체에서 겹치지 않게 합니다.
+// 표현식과 문장은 `arena`에 이어 넣으므로, 앞 파일의 저장소를 넘기면 한 저장소에 모입니다.
+pub fn parse_crate_from(tokens: &[Token], arena: AstArena, first_id: NodeId) -> PResult<(Crate, NodeId)> {
+    parse_crate_within(tokens, arena, first_id, MAX_DEPTH)
+}
+
+// 식과 문장이 `max_depth`단계보다 깊이 겹친 크레이트는 오류입니다.
+// 왼쪽으로 이어지는 `1 + 1 + ...`처럼 재귀 없이 파싱한 식은 다 파싱한 뒤 트리의 깊이로 확인합니다.
+pub(crate) fn parse_crate_within(tokens: &[Token], arena: AstArena, first_id: NodeId, max_depth: usize) -> PResult<(Crate, NodeId)> {
+    let mut parser = Parser::new(tokens);
+    parser.arena = arena;
+    parser.next_id = first_id;
+    parser.max_depth = max_depth;
+    // 오류를 만든 자리에서 위치를 정하지 않았으면 마지막으로 읽은 토큰을 가리킵니다.
+    let (attrs, items) = parser.parse_crate_contents().map_err(|error| error.or_span(parser.prev_span))?;
+    if let Some((id, _)) = parser.arena.deepest(&items).filter(|&(_, depth)| depth > max_depth) {
+        return Err(ParseError::too_deep(max_depth, parser.arena.span(id)));
+    }
+    Ok((Crate { attrs, items, arena: parser.arena }, parser.next_id))
+}
+
+pub struct Parser {
+    tokens: Vec<Token>,     // 공백과 주석을 제외한 토큰
+    pos: usize,             // 현재 토큰 위치
+    eof: Token,             // 입력 끝을 나타내는 토큰
+    prev_span: Span,        // 마지막으로 소비한 토큰의 위치
+    next_id: NodeId,        // 다음 노드에 매길 번호
+    arena: AstArena,        // 파싱한 표현식과 문장
+    no_struct_literal: bool,    // 구조체 리터럴 금지 여부 (if, while 조건)
+    events: Option<Vec<Event>>, // 구문 트리를 쌓을 때만 남기는 사건
+    depth: usize,               // 지금 재귀로 들어가 있는 단계 (`nested`)
+    max_depth: usize,           // 허용하는 가장 깊은 단계 (`MAX_DEPTH`)
+}
+
+impl Parser {
+    pub fn new(tokens: &[Token]) -> Self {
+        let tokens: Vec<Token> = tokens
+            .iter()
+            .filter(|token| !is_trivia(&token.kind) && token.kind != TokenKind::EOF)
+            .cloned()
+            .collect();
+        let last_span = tokens.last().map_or(Span::DUMMY, |token| token.span);
+        Parser {
+            tokens,
+            pos: 0,
+            eof: Token::new(TokenKind::EOF, String::new()).with_span(last_span.shrink_to_hi()),
+            prev_span: Span::DUMMY,
+            next_id: NodeId::FIRST,
+            arena: AstArena::default(),
+            no_struct_literal: false,
+            events: None,
+            depth: 0,
+            max_depth: MAX_DEPTH,
+        }
+    }
+
+    // 재귀로 내려가는 자리(표현식, 단항 연산자의 피연산자, `else if`, 타입, 패턴)에서 `parse`를 부릅니다.
+    // 깊이가 `max_depth`에 이르면 스택이 넘치기 전에 오류로 멈춥니다.
+    pub(crate) fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> PResult<T>) -> PResult<T> {
+        if self.depth >= self.max_depth {
+            return Err(ParseError::too_deep(self.max_depth, self.token().span));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    pub(crate) fn token(&self) -> &Token {
+        self.look_ahead(0)
+    }
+
+    pub(crate) fn look_ahead(&self, n: usize) -> &Token {
+        self.tokens.get(self.pos + n).unwrap_or(&self.eof)
+    }
+
+    pub(crate) fn bump(&mut self) -> Token {
+        let token = self.token().clone();
+        if self.pos < self.tokens.len() {
+            self.pos += 1;
+            self.prev_span = token.span;
+            self.record_token();
+        }
+        token
+    }
+
+    // 지금 파싱을 시작하는 위치입니다. 노드가 끝나면 `span_from`으로 전체 범위를 얻습니다.
+    pub(crate) fn lo(&self) -> Span {
+        self.token().span
+    }
+
+    // `lo`부터 마지막으로 소비한 토큰까지의 범위입니다.
+    pub(crate) fn span_from(&self, lo: Span) -> Span {
+        lo.to(self.prev_span)
+    }
+
+    pub(crate) fn next_node_id(&mut self) -> NodeId {
+        let id = self.next_id;
+        self.next_id = id.next();
+        id
+    }
+
+    // `lo`에서 시작해 방금 끝난 표현식 노드를 만들어 저장소에 넣습니다.
+    pub(crate) fn mk_node(&mut self, kind: NodeKind, lo: Span) -> ExprId {
+        let node = Node { id: self.next_node_id(), span: self.span_from(lo), kind };
+        self.arena.alloc_expr(node)
+    }
+
+    pub(crate) fn is_eof(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    pub(crate) fn check(&self, kind: &TokenKind) -> bool {
+        self.token().kind == *kind
+    }
+
+    pub(crate) fn eat(&mut self, kind: &TokenKind) -> bool {
+        let present = self.check(kind);
+        if present {
+            self.bump();
+        }
+        present
+    }
+
+    pub(crate) fn expect(&mut self, kind: &TokenKind, what: &str) -> PResult<Token> {
+        if self.check(kind) {
+            Ok(self.bump())
+        } else {
+            Err(self.unexpected(what))
+        }
+    }
+
+    pub(crate) fn check_keyword(&self, keyword: &str) -> bool {
+        self.token().kind == TokenKind::Ident && self.token().text == keyword
+    }
+
+    pub(crate) fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let present = self.check_keyword(keyword);
+        if present {
+            self.bump();
+        }
+        present
+    }
+
+    pub(crate) fn expect_keyword(&mut self, keyword: &str) -> PResult<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", keyword)))
+        }
+    }
+
+    // 예약어나 `_`가 아닌 식별자인지 확인합니다.
+    pub(crate) fn check_ident(&self) -> bool {
+        let text = self.token().text.as_str();
+        self.token().kind == TokenKind::Ident && text != "_" && !KEYWORDS.contains(&text)
+    }
+
+    pub(crate) fn parse_ident(&mut self) -> PResult<String> {
+        if self.check_ident() {
+            Ok(self.bump().text)
+        } else {
+            Err(self.unexpected("identifier"))
+        }
+    }
+
+    pub(crate) fn unexpected(&self, expected: &str) -> ParseError {
+        let found = if self.is_eof() {
+            "end of input".to_string()
+        } else {
+            format!("`{}`", self.token().text)
+        };
+        ParseError::new(format!("expected {}, found {}", expected, found)).with_span(self.token().span)
+    }
+
+    fn parse_crate_contents(&mut self) -> PResult<(Vec<Attribute>, Vec<Item>)> {
+        let attrs = self.parse_inner_attrs()?;
+        let items = self.parse_items()?;
+        if !self.is_eof() {
+            return Err(self.unexpected("item"));
+        }
+        Ok((attrs, items))
+    }
+}
+
+fn is_trivia(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. }
+    )
+}
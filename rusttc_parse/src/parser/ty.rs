@@ -0,0 +1,301 @@
+use rusttc_lexer::TokenKind;
+use rusttc_ast::ast::{ArrayLen, ExprId, NodeKind, Pat, PatKind, Path, PathSegment, Ty, TyKind, UnaryOp, FN_TRAITS};
+
+use super::{ParseError, PResult, Parser};
+use crate::syntax_kind::SyntaxKind;
+
+// 경로가 쓰인 위치입니다. 제네릭 인자를 받는 방식이 다릅니다.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum PathStyle {
+    Type,                       // 타입 위치: `Pair<i32>`
+    Expr,                       // 표현식 위치: `Pair::<i32>::new`
+}
+
+impl Parser {
+    // 타입을 파싱합니다: `i32`, `Point`, `Self`, `&T`, `&mut T`, `()`, `[T; 3]`, `[T; N]`, `[T]`, `(T, U)`
+    pub(crate) fn parse_ty(&mut self) -> PResult<Ty> {
+        let lo = self.lo();
+        let kind = self.nested(Self::parse_ty_kind)?;
+        Ok(Ty { id: self.next_node_id(), span: self.span_from(lo), kind })
+    }
+
+    fn parse_ty_kind(&mut self) -> PResult<TyKind> {
+        if self.check(&TokenKind::And) {
+            self.start_node(SyntaxKind::RefType);
+            self.bump();
+            let mutable = self.eat_keyword("mut");
+            let ty = self.parse_ty()?;
+            self.finish_node();
+            return Ok(TyKind::Ref { mutable, ty: Box::new(ty) });
+        }
+        let checkpoint = self.checkpoint();
+        if self.eat(&TokenKind::OpenParen) {
+            let mut tys = Vec::new();
+            let mut trailing_comma = false;
+            while !self.check(&TokenKind::CloseParen) {
+                tys.push(self.parse_ty()?);
+                trailing_comma = self.eat(&TokenKind::Comma);
+                if !trailing_comma {
+                    break;
+                }
+            }
+            self.expect(&TokenKind::CloseParen, "`)`")?;
+            let paren = tys.len() == 1 && !trailing_comma;
+            self.wrap_node(checkpoint, if paren { SyntaxKind::ParenType } else { SyntaxKind::TupleType });
+            // `(T)`는 괄호로 묶은 타입이고, `(T,)`는 원소가 하나인 튜플입니다.
+            return Ok(match tys.len() {
+                0 => TyKind::Unit,
+                1 if !trailing_comma => tys.pop().unwrap().kind,
+                _ => TyKind::Tuple(tys),
+            });
+        }
+        if self.eat(&TokenKind::OpenBracket) {
+            let elem = Box::new(self.parse_ty()?);
+            if self.eat(&TokenKind::CloseBracket) {
+                self.wrap_node(checkpoint, SyntaxKind::SliceType);
+                return Ok(TyKind::Slice(elem));
+            }
+            self.expect(&TokenKind::Semi, "`;` or `]`")?;
+            let len = self.parse_array_len()?;
+            self.expect(&TokenKind::CloseBracket, "`]`")?;
+            self.wrap_node(checkpoint, SyntaxKind::ArrayType);
+            return Ok(TyKind::Array(elem, len));
+        }
+        self.start_node(SyntaxKind::PathType);
+        let path = self.parse_path(PathStyle::Type)?;
+        self.finish_node();
+        Ok(TyKind::Path(path))
+    }
+
+    // `a::b::c` 형태의 경로를 파싱합니다.
+    // 타입 경로는 `Pair<i32>`, 표현식 경로는 터보피시 `max::<i32>` 형태로 제네릭 인자를 받습니다.
+    pub(crate) fn parse_path(&mut self, style: PathStyle) -> PResult<Path> {
+        self.start_node(SyntaxKind::Path);
+        let mut segments = vec![self.parse_path_segment(style)?];
+        while self.check(&TokenKind::PathSep) && self.look_ahead(1).kind == TokenKind::Ident {
+            self.bump();
+            segments.push(self.parse_path_segment(style)?);
+        }
+        self.finish_node();
+        Ok(Path { segments })
+    }
+
+    fn parse_path_segment(&mut self, style: PathStyle) -> PResult<PathSegment> {
+        self.start_node(SyntaxKind::PathSegment);
+        let segment = self.parse_path_segment_inner(style)?;
+        self.finish_node();
+        Ok(segment)
+    }
+
+    fn parse_path_segment_inner(&mut self, style: PathStyle) -> PResult<PathSegment> {
+        let ident = self.parse_segment_ident()?;
+
+        // 클로저 트레이트는 `Fn(i32) -> i32`처럼 인자를 괄호로 받습니다.
+        if style == PathStyle::Type && FN_TRAITS.contains(&ident.as_str()) && self.check(&TokenKind::OpenParen) {
+            self.start_node(SyntaxKind::GenericArgList);
+            self.bump();
+            let mut args = Vec::new();
+            while !self.check(&TokenKind::CloseParen) {
+                args.push(self.parse_ty()?);
+                if !self.eat(&TokenKind::Comma) {
+                    break;
+                }
+            }
+            self.expect(&TokenKind::CloseParen, "`)`")?;
+            self.finish_node();
+            let output = self.parse_ret_ty()?.map(Box::new);
+            return Ok(PathSegment { ident, args, output });
+        }
+
+        let has_args = match style {
+            PathStyle::Type => self.check(&TokenKind::Lt),
+            PathStyle::Expr => self.check(&TokenKind::PathSep) && self.look_ahead(1).kind == TokenKind::Lt,
+        };
+        if !has_args {
+            return Ok(PathSegment::from_ident(&ident));
+        }
+        self.start_node(SyntaxKind::GenericArgList);
+        if style == PathStyle::Expr {
+            self.bump();
+        }
+        self.bump();
+
+        let mut args = Vec::new();
+        while !self.check(&TokenKind::Gt) {
+            args.push(self.parse_ty()?);
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(&TokenKind::Gt, "`>`")?;
+        self.finish_node();
+        Ok(PathSegment { ident, args, output: None })
+    }
+
+    // 경로 세그먼트에는 식별자 외에 `self`, `Self`, `super`, `crate`도 올 수 있습니다.
+    pub(crate) fn parse_segment_ident(&mut self) -> PResult<String> {
+        if ["self", "Self", "super", "crate"].iter().any(|keyword| self.check_keyword(keyword)) {
+            Ok(self.bump().text)
+        } else {
+            self.parse_ident()
+        }
+    }
+
+    // 배열 타입의 길이입니다. 접미사 없는 정수 리터럴은 바로 길이가 되고,
+    // 그 밖의 식(`N`, `N * 2`, `3usize`)은 타입 검사가 상수로 계산합니다.
+    fn parse_array_len(&mut self) -> PResult<ArrayLen> {
+        let token = self.token().clone();
+        let is_plain_int = token.kind == TokenKind::Literal && token.text.bytes().all(|b| b.is_ascii_digit());
+        if is_plain_int && self.look_ahead(1).kind == TokenKind::CloseBracket {
+            self.start_node(SyntaxKind::Literal);
+            self.bump();
+            self.finish_node();
+            // 구문 트리는 리터럴의 값을 검사하지 않습니다.
+            if self.is_building_syntax() {
+                return Ok(ArrayLen::Known(0));
+            }
+            return token
+                .text
+                .parse()
+                .map(ArrayLen::Known)
+                .map_err(|_| ParseError::new(format!("array length `{}` is too large", token.text)).with_span(token.span));
+        }
+        Ok(ArrayLen::Expr(self.parse_expr()?))
+    }
+
+    // 패턴을 파싱합니다: `x`, `mut x`, `_`
+    pub(crate) fn parse_pat(&mut self) -> PResult<Pat> {
+        let lo = self.lo();
+        let kind = if self.check(&TokenKind::Ident) && self.token().text == "_" {
+            self.start_node(SyntaxKind::WildcardPat);
+            self.bump();
+            self.finish_node();
+            PatKind::Wild
+        } else {
+            self.start_node(SyntaxKind::IdentPat);
+            let mutable = self.eat_keyword("mut");
+            let name = self.parse_name()?;
+            self.finish_node();
+            PatKind::Ident { name, mutable }
+        };
+        Ok(Pat { id: self.next_node_id(), span: self.span_from(lo), kind })
+    }
+
+    // match 갈래의 패턴을 파싱합니다. `let`, 매개변수, `for`의 패턴과 달리 리터럴, 범위, 튜플,
+    // 구조체 패턴과 or 패턴을 받습니다: `1 | 2`, `0..=9`, `(a, _)`, `Point { x: 0, y, .. }`
+    pub(crate) fn parse_match_pat(&mut self) -> PResult<Pat> {
+        let lo = self.lo();
+        let checkpoint = self.checkpoint();
+        let first = self.nested(Self::parse_match_pat_no_alt)?;
+        if !self.check(&TokenKind::Or) {
+            return Ok(first);
+        }
+        self.start_node_at(checkpoint, SyntaxKind::OrPat);
+        let mut pats = vec![first];
+        while self.eat(&TokenKind::Or) {
+            pats.push(self.nested(Self::parse_match_pat_no_alt)?);
+        }
+        self.finish_node();
+        Ok(Pat { id: self.next_node_id(), span: self.span_from(lo), kind: PatKind::Or(pats) })
+    }
+
+    // `|`로 묶지 않은 패턴 하나를 파싱합니다.
+    fn parse_match_pat_no_alt(&mut self) -> PResult<Pat> {
+        let lo = self.lo();
+        let checkpoint = self.checkpoint();
+        let token = self.token().clone();
+        let kind = match token.kind {
+            TokenKind::Literal | TokenKind::CharLiteral | TokenKind::Minus => self.parse_lit_or_range_pat(checkpoint)?,
+            TokenKind::Ident if token.text == "true" || token.text == "false" => self.parse_lit_or_range_pat(checkpoint)?,
+            TokenKind::OpenParen => {
+                self.bump();
+                let mut pats = Vec::new();
+                let mut trailing_comma = false;
+                while !self.check(&TokenKind::CloseParen) {
+                    pats.push(self.parse_match_pat()?);
+                    trailing_comma = self.eat(&TokenKind::Comma);
+                    if !trailing_comma {
+                        break;
+                    }
+                }
+                self.expect(&TokenKind::CloseParen, "`)`")?;
+                // `(p)`는 괄호로 묶은 패턴이고, `(p,)`는 원소가 하나인 튜플입니다.
+                if pats.len() == 1 && !trailing_comma {
+                    self.wrap_node(checkpoint, SyntaxKind::ParenPat);
+                    return Ok(pats.pop().unwrap());
+                }
+                self.wrap_node(checkpoint, SyntaxKind::TuplePat);
+                PatKind::Tuple(pats)
+            },
+            TokenKind::Ident if self.check_ident() && matches!(self.look_ahead(1).kind, TokenKind::OpenBrace | TokenKind::PathSep) => {
+                self.parse_struct_pat()?
+            },
+            _ => return self.parse_pat(),
+        };
+        Ok(Pat { id: self.next_node_id(), span: self.span_from(lo), kind })
+    }
+
+    // 리터럴 패턴 `-1`과 범위 패턴 `0..=9`를 파싱합니다.
+    fn parse_lit_or_range_pat(&mut self, checkpoint: usize) -> PResult<PatKind> {
+        let start = self.parse_lit_pat_expr()?;
+        if !self.check(&TokenKind::DotDotEq) {
+            return Ok(PatKind::Lit(start));
+        }
+        self.start_node_at(checkpoint, SyntaxKind::RangePat);
+        self.bump();
+        let end = self.parse_lit_pat_expr()?;
+        self.finish_node();
+        Ok(PatKind::Range(start, end))
+    }
+
+    // 리터럴 패턴의 값을 표현식으로 파싱합니다. 음수는 `-`를 붙인 단항 표현식입니다.
+    fn parse_lit_pat_expr(&mut self) -> PResult<ExprId> {
+        let lo = self.lo();
+        self.start_node(SyntaxKind::LiteralPat);
+        let expr = if self.check(&TokenKind::Minus) {
+            self.start_node(SyntaxKind::PrefixExpr);
+            let operator = self.bump();
+            let operand = self.parse_pat_literal()?;
+            self.finish_node();
+            self.mk_node(NodeKind::Unary(UnaryOp { operator, operand }), lo)
+        } else {
+            self.parse_pat_literal()?
+        };
+        self.finish_node();
+        Ok(expr)
+    }
+
+    // 구조체 패턴 `Point { x: 0, y, .. }`를 파싱합니다. 필드 이름만 쓰면 같은 이름의 변수로 바인딩합니다.
+    fn parse_struct_pat(&mut self) -> PResult<PatKind> {
+        self.start_node(SyntaxKind::RecordPat);
+        let path = self.parse_path(PathStyle::Expr)?;
+        self.start_node(SyntaxKind::RecordPatFieldList);
+        self.expect(&TokenKind::OpenBrace, "`{`")?;
+        let mut fields = Vec::new();
+        let mut rest = false;
+        while !self.check(&TokenKind::CloseBrace) {
+            if self.eat(&TokenKind::DotDot) {
+                rest = true;
+                break;
+            }
+            self.start_node(SyntaxKind::RecordPatField);
+            let name_lo = self.lo();
+            let name = self.parse_ident()?;
+            let pat = if self.eat(&TokenKind::Colon) {
+                self.parse_match_pat()?
+            } else {
+                let kind = PatKind::Ident { name: name.clone(), mutable: false };
+                Pat { id: self.next_node_id(), span: self.span_from(name_lo), kind }
+            };
+            self.finish_node();
+            fields.push((name, pat));
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(&TokenKind::CloseBrace, "`}`")?;
+        self.finish_node();
+        self.finish_node();
+        Ok(PatKind::Struct { path, fields, rest })
+    }
+}
@@ -0,0 +1,97 @@
+// 구문 트리 노드의 종류입니다. 파서가 사건으로 내보내고 `rusttc_syntax`가 트리로 쌓습니다.
+// 토큰의 종류는 렉서의 `TokenKind`를 그대로 씁니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SyntaxKind {
+    SourceFile,                 // 소스 파일 전체
+    Error,                      // 파싱하지 못한 토큰 묶음
+
+    // 아이템
+    Fn,                         // fn name(...) -> Ty { ... }
+    Struct,                     // struct Name { ... }
+    Impl,                       // impl Trait for Type { ... }
+    Trait,                      // trait Name { ... }
+    Module,                     // mod name { ... }, mod name;
+    Use,                        // use a::b;
+    Const,                      // const NAME: Ty = value;
+    Static,                     // static NAME: Ty = value;
+    MacroCall,                  // name!(...), macro_rules! name { ... }
+
+    // 아이템의 부분
+    Attr,                       // #[...], #![...]
+    TokenTree,                  // 괄호로 묶인 토큰 (매크로 인자, 속성 내용)
+    Visibility,                 // pub, pub(crate)
+    Name,                       // 정의하는 이름
+    ItemList,                   // impl, trait, mod 본문 `{ items }`
+    GenericParamList,           // <T: Bound, U>
+    GenericParam,               // T: Bound
+    ParamList,                  // 함수와 클로저의 매개변수 목록
+    SelfParam,                  // self, &self, &mut self, mut self
+    Param,                      // pat: Ty
+    RetType,                    // -> Ty
+    FieldList,                  // 구조체 필드 목록 `{ fields }`
+    Field,                      // name: Ty
+    UseTree,                    // a::b::{c, d as e}
+    UseTreeList,                // {c, d as e}
+    Rename,                     // as e
+
+    // 경로와 타입
+    Path,                       // a::b::c
+    PathSegment,                // b, Pair<i32>, Fn(i32) -> i32
+    GenericArgList,             // <i32>, ::<i32>, (i32)
+    PathType,                   // i32, Point
+    RefType,                    // &T, &mut T
+    TupleType,                  // (), (T, U)
+    ParenType,                  // (T)
+    ArrayType,                  // [T; 3]
+    SliceType,                  // [T]
+
+    // 패턴
+    IdentPat,                   // x, mut x
+    WildcardPat,                // _
+    LiteralPat,                 // 1, -1, 'a', true
+    RangePat,                   // 0..=9
+    TuplePat,                   // (a, _)
+    ParenPat,                   // (a)
+    RecordPat,                  // Point { x: 0, y, .. }
+    RecordPatFieldList,         // { x: 0, y, .. }
+    RecordPatField,             // x: 0, y
+    OrPat,                      // 1 | 2
+
+    // 문장
+    LetStmt,                    // let pat: Ty = init;
+    ExprStmt,                   // expr;
+
+    // 표현식
+    Literal,                    // 1, "text", 'c', true
+    PathExpr,                   // x, Point::new
+    ParenExpr,                  // (x)
+    TupleExpr,                  // (1, true), ()
+    ArrayExpr,                  // [1, 2, 3], [0; 16]
+    BinExpr,                    // a + b
+    PrefixExpr,                 // -x, !x, *x
+    RefExpr,                    // &x, &mut x
+    CallExpr,                   // f(a, b)
+    ArgList,                    // (a, b)
+    MethodCallExpr,             // x.f(a)
+    FieldExpr,                  // x.y, t.0
+    IndexExpr,                  // a[i]
+    RecordExpr,                 // Point { x: 1 }
+    RecordExprFieldList,        // { x: 1, y }
+    RecordExprField,            // x: 1
+    AssignExpr,                 // a = b, a += b
+    RangeExpr,                  // a..b, a..=b
+    BlockExpr,                  // { stmts }
+    IfExpr,                     // if cond { ... } else { ... }
+    MatchExpr,                  // match x { ... }
+    MatchArmList,               // { arms }
+    MatchArm,                   // pat if guard => body,
+    MatchGuard,                 // if guard
+    WhileExpr,                  // while cond { ... }
+    ForExpr,                    // for pat in iter { ... }
+    LoopExpr,                   // loop { ... }
+    BreakExpr,                  // break
+    ContinueExpr,               // continue
+    ReturnExpr,                 // return value
+    ClosureExpr,                // |x| x + 1, move || ...
+    QualifiedPathExpr,          // <Circle as Shape>::area
+}
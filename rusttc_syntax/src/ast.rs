@@ -0,0 +1,941 @@
+// 구문 트리 위에 얹는 타입 있는 AST입니다. 각 노드는 `SyntaxNode` 하나를 감싼 얇은 보기이며
+// 따로 데이터를 갖지 않습니다. 자식은 물어볼 때마다 트리에서 찾으므로, 빠진 부분이 있는
+// 트리에서는 `None`이 나옵니다.
+
+use rusttc_lexer::TokenKind;
+
+use crate::SyntaxKind;
+use crate::red::{SyntaxElement, SyntaxNode, SyntaxToken};
+
+// 특정 종류의 구문 노드를 타입 있는 노드로 봅니다.
+pub trait AstNode: Sized {
+    fn can_cast(kind: SyntaxKind) -> bool;
+
+    fn cast(node: SyntaxNode) -> Option<Self>;
+
+    fn syntax(&self) -> &SyntaxNode;
+}
+
+// 이름을 정의하는 노드입니다.
+pub trait HasName: AstNode {
+    fn name(&self) -> Option<Name> {
+        child(self.syntax())
+    }
+}
+
+// 앞에 속성이 붙을 수 있는 노드입니다.
+pub trait HasAttrs: AstNode {
+    fn attrs(&self) -> impl Iterator<Item = Attr> + '_ {
+        children(self.syntax())
+    }
+}
+
+// 가시성을 쓸 수 있는 노드입니다.
+pub trait HasVisibility: AstNode {
+    fn visibility(&self) -> Option<Visibility> {
+        child(self.syntax())
+    }
+}
+
+macro_rules! ast_node {
+    ($($name:ident),* $(,)?) => {
+        $(#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $name(SyntaxNode);
+
+        impl AstNode for $name {
+            fn can_cast(kind: SyntaxKind) -> bool {
+                kind == SyntaxKind::$name
+            }
+
+            fn cast(node: SyntaxNode) -> Option<Self> {
+                Self::can_cast(node.kind()).then(|| $name(node))
+            }
+
+            fn syntax(&self) -> &SyntaxNode {
+                &self.0
+            }
+        })*
+    };
+}
+
+// 여러 종류의 노드 가운데 하나를 나타내는 열거형입니다. 변형 이름은 감싼 노드 타입과 같습니다.
+macro_rules! ast_enum {
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant($variant),)*
+        }
+
+        impl AstNode for $name {
+            fn can_cast(kind: SyntaxKind) -> bool {
+                $($variant::can_cast(kind))||*
+            }
+
+            fn cast(node: SyntaxNode) -> Option<Self> {
+                $(if $variant::can_cast(node.kind()) {
+                    return Some($name::$variant($variant(node)));
+                })*
+                None
+            }
+
+            fn syntax(&self) -> &SyntaxNode {
+                match self {
+                    $($name::$variant(node) => node.syntax(),)*
+                }
+            }
+        }
+    };
+}
+
+ast_node!(
+    SourceFile, Attr, TokenTree, Visibility, Name,
+    Fn, Struct, Impl, Trait, Module, Use, Const, Static, MacroCall,
+    ItemList, GenericParamList, GenericParam, ParamList, SelfParam, Param, RetType, FieldList, Field,
+    UseTree, UseTreeList, Rename,
+    Path, PathSegment, GenericArgList, PathType, RefType, TupleType, ParenType, ArrayType, SliceType,
+    IdentPat, WildcardPat, LiteralPat, RangePat, TuplePat, ParenPat, RecordPat, RecordPatFieldList, RecordPatField,
+    OrPat, LetStmt, ExprStmt,
+    Literal, PathExpr, ParenExpr, TupleExpr, ArrayExpr, BinExpr, PrefixExpr, RefExpr, CallExpr, ArgList,
+    MethodCallExpr, FieldExpr, IndexExpr, RecordExpr, RecordExprFieldList, RecordExprField, AssignExpr,
+    RangeExpr, BlockExpr, IfExpr, MatchExpr, MatchArmList, MatchArm, MatchGuard, WhileExpr, ForExpr, LoopExpr,
+    BreakExpr, ContinueExpr, ReturnExpr, ClosureExpr, QualifiedPathExpr,
+);
+
+ast_enum!(Item { Fn, Struct, Impl, Trait, Module, Use, Const, Static, MacroCall });
+
+ast_enum!(Type { PathType, RefType, TupleType, ParenType, ArrayType, SliceType });
+
+ast_enum!(Pat { IdentPat, WildcardPat, LiteralPat, RangePat, TuplePat, ParenPat, RecordPat, OrPat });
+
+ast_enum!(Expr {
+    Literal, PathExpr, ParenExpr, TupleExpr, ArrayExpr, BinExpr, PrefixExpr, RefExpr, CallExpr, MethodCallExpr,
+    FieldExpr, IndexExpr, RecordExpr, AssignExpr, RangeExpr, BlockExpr, IfExpr, MatchExpr, WhileExpr, ForExpr,
+    LoopExpr, BreakExpr, ContinueExpr, ReturnExpr, ClosureExpr, QualifiedPathExpr, MacroCall,
+});
+
+// 블록 안의 문장입니다. 블록 안의 아이템도 문장입니다. 블록의 값이 되는 마지막 표현식은
+// 문장으로 감싸지 않으므로 `BlockExpr::tail_expr`로 얻습니다.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Stmt {
+    Let(LetStmt),
+    Expr(ExprStmt),
+    Item(Item),
+}
+
+impl AstNode for Stmt {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        LetStmt::can_cast(kind) || ExprStmt::can_cast(kind) || Item::can_cast(kind)
+    }
+
+    fn cast(node: SyntaxNode) -> Option<Self> {
+        match node.kind() {
+            SyntaxKind::LetStmt => Some(Stmt::Let(LetStmt(node))),
+            SyntaxKind::ExprStmt => Some(Stmt::Expr(ExprStmt(node))),
+            _ => Item::cast(node).map(Stmt::Item),
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        match self {
+            Stmt::Let(stmt) => stmt.syntax(),
+            Stmt::Expr(stmt) => stmt.syntax(),
+            Stmt::Item(item) => item.syntax(),
+        }
+    }
+}
+
+fn child<N: AstNode>(parent: &SyntaxNode) -> Option<N> {
+    parent.children().find_map(N::cast)
+}
+
+fn children<'a, N: AstNode + 'a>(parent: &'a SyntaxNode) -> impl Iterator<Item = N> + 'a {
+    parent.children().filter_map(N::cast)
+}
+
+fn nth_child<N: AstNode>(parent: &SyntaxNode, n: usize) -> Option<N> {
+    children(parent).nth(n)
+}
+
+// 공백과 주석을 뺀 직계 자식 토큰입니다.
+fn tokens(parent: &SyntaxNode) -> impl Iterator<Item = SyntaxToken> + '_ {
+    parent.children_with_tokens().filter_map(SyntaxElement::into_token).filter(|token| !token.is_trivia())
+}
+
+fn token(parent: &SyntaxNode, kind: &TokenKind) -> Option<SyntaxToken> {
+    tokens(parent).find(|token| token.kind() == kind)
+}
+
+fn keyword(parent: &SyntaxNode, keyword: &str) -> Option<SyntaxToken> {
+    tokens(parent).find(|token| token.is_keyword(keyword))
+}
+
+impl HasAttrs for SourceFile {}
+impl HasAttrs for Fn {}
+impl HasAttrs for Struct {}
+impl HasAttrs for Impl {}
+impl HasAttrs for Trait {}
+impl HasAttrs for Module {}
+impl HasAttrs for Use {}
+impl HasAttrs for Const {}
+impl HasAttrs for Static {}
+impl HasAttrs for ItemList {}
+
+impl HasName for Fn {}
+impl HasName for Struct {}
+impl HasName for Trait {}
+impl HasName for Module {}
+impl HasName for Const {}
+impl HasName for Static {}
+impl HasName for GenericParam {}
+impl HasName for Field {}
+impl HasName for IdentPat {}
+impl HasName for Rename {}
+
+impl HasVisibility for Fn {}
+impl HasVisibility for Struct {}
+impl HasVisibility for Trait {}
+impl HasVisibility for Module {}
+impl HasVisibility for Use {}
+impl HasVisibility for Const {}
+impl HasVisibility for Static {}
+impl HasVisibility for Field {}
+
+impl SourceFile {
+    pub fn items(&self) -> impl Iterator<Item = Item> + '_ {
+        children(&self.0)
+    }
+}
+
+impl Attr {
+    // `#![...]`인지 확인합니다.
+    pub fn is_inner(&self) -> bool {
+        token(&self.0, &TokenKind::Bang).is_some()
+    }
+
+    pub fn token_tree(&self) -> Option<TokenTree> {
+        child(&self.0)
+    }
+
+    // 속성 이름 `derive`, `cfg` 등입니다. 경로로 된 이름은 첫 세그먼트만 돌려줍니다.
+    pub fn name(&self) -> Option<String> {
+        let tree = self.token_tree()?;
+        let name = tokens(tree.syntax()).find(|token| *token.kind() == TokenKind::Ident)?;
+        Some(name.text().to_string())
+    }
+}
+
+impl TokenTree {
+    // 괄호 안의 토큰입니다. 안쪽 토큰 트리의 토큰도 차례로 들어 있고, 공백과 주석은 빠집니다.
+    pub fn tokens(&self) -> impl Iterator<Item = SyntaxToken> {
+        self.0.descendants_with_tokens().filter_map(SyntaxElement::into_token).filter(|token| !token.is_trivia())
+    }
+}
+
+impl Visibility {
+    // `pub(crate)`인지 확인합니다.
+    pub fn is_crate(&self) -> bool {
+        keyword(&self.0, "crate").is_some()
+    }
+}
+
+impl Name {
+    pub fn ident_token(&self) -> Option<SyntaxToken> {
+        token(&self.0, &TokenKind::Ident)
+    }
+
+    pub fn text(&self) -> String {
+        self.ident_token().map(|token| token.text().to_string()).unwrap_or_default()
+    }
+}
+
+impl Fn {
+    pub fn generic_param_list(&self) -> Option<GenericParamList> {
+        child(&self.0)
+    }
+
+    pub fn param_list(&self) -> Option<ParamList> {
+        child(&self.0)
+    }
+
+    pub fn ret_type(&self) -> Option<RetType> {
+        child(&self.0)
+    }
+
+    // 본문입니다. 트레이트 메서드 선언 `fn f(&self);`에는 없습니다.
+    pub fn body(&self) -> Option<BlockExpr> {
+        child(&self.0)
+    }
+}
+
+impl GenericParamList {
+    pub fn params(&self) -> impl Iterator<Item = GenericParam> + '_ {
+        children(&self.0)
+    }
+}
+
+impl GenericParam {
+    pub fn bounds(&self) -> impl Iterator<Item = Path> + '_ {
+        children(&self.0)
+    }
+}
+
+impl ParamList {
+    pub fn self_param(&self) -> Option<SelfParam> {
+        child(&self.0)
+    }
+
+    pub fn params(&self) -> impl Iterator<Item = Param> + '_ {
+        children(&self.0)
+    }
+}
+
+impl SelfParam {
+    // `&self`, `&mut self`인지 확인합니다.
+    pub fn is_ref(&self) -> bool {
+        token(&self.0, &TokenKind::And).is_some()
+    }
+
+    pub fn is_mut(&self) -> bool {
+        keyword(&self.0, "mut").is_some()
+    }
+}
+
+impl Param {
+    pub fn pat(&self) -> Option<Pat> {
+        child(&self.0)
+    }
+
+    // 클로저 매개변수에서는 타입을 생략할 수 있습니다.
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+}
+
+impl RetType {
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+}
+
+impl Struct {
+    pub fn generic_param_list(&self) -> Option<GenericParamList> {
+        child(&self.0)
+    }
+
+    // 필드 목록입니다. `struct Unit;`에는 없습니다.
+    pub fn field_list(&self) -> Option<FieldList> {
+        child(&self.0)
+    }
+}
+
+impl FieldList {
+    pub fn fields(&self) -> impl Iterator<Item = Field> + '_ {
+        children(&self.0)
+    }
+}
+
+impl Field {
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+}
+
+impl Impl {
+    pub fn generic_param_list(&self) -> Option<GenericParamList> {
+        child(&self.0)
+    }
+
+    // `impl Trait for Type`의 `Trait`입니다.
+    pub fn trait_(&self) -> Option<Type> {
+        keyword(&self.0, "for")?;
+        nth_child(&self.0, 0)
+    }
+
+    // 구현 대상 타입입니다.
+    pub fn self_ty(&self) -> Option<Type> {
+        let index = if keyword(&self.0, "for").is_some() { 1 } else { 0 };
+        nth_child(&self.0, index)
+    }
+
+    pub fn item_list(&self) -> Option<ItemList> {
+        child(&self.0)
+    }
+}
+
+impl Trait {
+    pub fn item_list(&self) -> Option<ItemList> {
+        child(&self.0)
+    }
+}
+
+impl Module {
+    // 모듈 본문입니다. `mod foo;`에는 없습니다.
+    pub fn item_list(&self) -> Option<ItemList> {
+        child(&self.0)
+    }
+}
+
+impl ItemList {
+    pub fn items(&self) -> impl Iterator<Item = Item> + '_ {
+        children(&self.0)
+    }
+}
+
+impl Use {
+    pub fn use_tree(&self) -> Option<UseTree> {
+        child(&self.0)
+    }
+}
+
+impl Const {
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+
+    pub fn body(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl Static {
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+
+    pub fn body(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl UseTree {
+    pub fn path(&self) -> Option<Path> {
+        child(&self.0)
+    }
+
+    pub fn use_tree_list(&self) -> Option<UseTreeList> {
+        child(&self.0)
+    }
+
+    pub fn is_glob(&self) -> bool {
+        token(&self.0, &TokenKind::Star).is_some()
+    }
+
+    pub fn rename(&self) -> Option<Rename> {
+        child(&self.0)
+    }
+}
+
+impl UseTreeList {
+    pub fn use_trees(&self) -> impl Iterator<Item = UseTree> + '_ {
+        children(&self.0)
+    }
+}
+
+impl MacroCall {
+    pub fn path(&self) -> Option<Path> {
+        child(&self.0)
+    }
+
+    // `macro_rules! name { ... }`가 정의하는 이름입니다.
+    pub fn name(&self) -> Option<Name> {
+        child(&self.0)
+    }
+
+    pub fn token_tree(&self) -> Option<TokenTree> {
+        child(&self.0)
+    }
+}
+
+impl Path {
+    pub fn segments(&self) -> impl Iterator<Item = PathSegment> + '_ {
+        children(&self.0)
+    }
+}
+
+impl PathSegment {
+    // 세그먼트 이름입니다. `self`, `Self`, `super`, `crate`일 수도 있습니다.
+    pub fn name_token(&self) -> Option<SyntaxToken> {
+        token(&self.0, &TokenKind::Ident)
+    }
+
+    pub fn generic_arg_list(&self) -> Option<GenericArgList> {
+        child(&self.0)
+    }
+
+    // 클로저 트레이트 `Fn(i32) -> i32`의 반환 타입입니다.
+    pub fn ret_type(&self) -> Option<RetType> {
+        child(&self.0)
+    }
+}
+
+impl GenericArgList {
+    pub fn types(&self) -> impl Iterator<Item = Type> + '_ {
+        children(&self.0)
+    }
+}
+
+impl PathType {
+    pub fn path(&self) -> Option<Path> {
+        child(&self.0)
+    }
+}
+
+impl RefType {
+    pub fn is_mut(&self) -> bool {
+        keyword(&self.0, "mut").is_some()
+    }
+
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+}
+
+impl TupleType {
+    // 원소 타입입니다. `()`에는 없습니다.
+    pub fn fields(&self) -> impl Iterator<Item = Type> + '_ {
+        children(&self.0)
+    }
+}
+
+impl ParenType {
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+}
+
+impl ArrayType {
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+
+    // 길이 식입니다: `3`, `N`, `N * 2`
+    pub fn len(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl SliceType {
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+}
+
+impl IdentPat {
+    pub fn is_mut(&self) -> bool {
+        keyword(&self.0, "mut").is_some()
+    }
+}
+
+impl LiteralPat {
+    // 리터럴이나 음수 `-1`의 단항 표현식입니다.
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl RangePat {
+    pub fn start(&self) -> Option<LiteralPat> {
+        nth_child(&self.0, 0)
+    }
+
+    pub fn end(&self) -> Option<LiteralPat> {
+        nth_child(&self.0, 1)
+    }
+}
+
+impl TuplePat {
+    pub fn fields(&self) -> impl Iterator<Item = Pat> + '_ {
+        children(&self.0)
+    }
+}
+
+impl ParenPat {
+    pub fn pat(&self) -> Option<Pat> {
+        child(&self.0)
+    }
+}
+
+impl RecordPat {
+    pub fn path(&self) -> Option<Path> {
+        child(&self.0)
+    }
+
+    pub fn field_list(&self) -> Option<RecordPatFieldList> {
+        child(&self.0)
+    }
+}
+
+impl RecordPatFieldList {
+    pub fn fields(&self) -> impl Iterator<Item = RecordPatField> + '_ {
+        children(&self.0)
+    }
+
+    // 나머지 필드를 무시하는 `..`가 있는지 확인합니다.
+    pub fn has_rest(&self) -> bool {
+        token(&self.0, &TokenKind::DotDot).is_some()
+    }
+}
+
+impl RecordPatField {
+    pub fn name_token(&self) -> Option<SyntaxToken> {
+        token(&self.0, &TokenKind::Ident)
+    }
+
+    // 필드의 패턴입니다. 줄임 표기 `Point { x }`에는 없습니다.
+    pub fn pat(&self) -> Option<Pat> {
+        child(&self.0)
+    }
+}
+
+impl OrPat {
+    pub fn pats(&self) -> impl Iterator<Item = Pat> + '_ {
+        children(&self.0)
+    }
+}
+
+impl LetStmt {
+    pub fn pat(&self) -> Option<Pat> {
+        child(&self.0)
+    }
+
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+
+    pub fn initializer(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl ExprStmt {
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    // 블록 형태의 표현식 문장은 `;` 없이 끝날 수 있습니다.
+    pub fn semicolon_token(&self) -> Option<SyntaxToken> {
+        token(&self.0, &TokenKind::Semi)
+    }
+}
+
+impl BlockExpr {
+    pub fn statements(&self) -> impl Iterator<Item = Stmt> + '_ {
+        children(&self.0)
+    }
+
+    // 블록의 값이 되는 마지막 표현식입니다.
+    pub fn tail_expr(&self) -> Option<Expr> {
+        self.0.children().filter(|node| !Stmt::can_cast(node.kind())).last().and_then(Expr::cast)
+    }
+}
+
+impl Literal {
+    pub fn token(&self) -> SyntaxToken {
+        tokens(&self.0).next().expect("a literal holds exactly one token")
+    }
+}
+
+impl PathExpr {
+    pub fn path(&self) -> Option<Path> {
+        child(&self.0)
+    }
+}
+
+impl ParenExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl TupleExpr {
+    pub fn fields(&self) -> impl Iterator<Item = Expr> + '_ {
+        children(&self.0)
+    }
+}
+
+impl ArrayExpr {
+    // 원소입니다. `[value; count]`는 값과 개수 두 표현식입니다.
+    pub fn exprs(&self) -> impl Iterator<Item = Expr> + '_ {
+        children(&self.0)
+    }
+
+    pub fn is_repeat(&self) -> bool {
+        token(&self.0, &TokenKind::Semi).is_some()
+    }
+}
+
+impl BinExpr {
+    pub fn lhs(&self) -> Option<Expr> {
+        operand_before(&self.0, self.op_token()?)
+    }
+
+    pub fn rhs(&self) -> Option<Expr> {
+        operand_after(&self.0, self.op_token()?)
+    }
+
+    pub fn op_token(&self) -> Option<SyntaxToken> {
+        tokens(&self.0).next()
+    }
+}
+
+impl PrefixExpr {
+    pub fn op_token(&self) -> Option<SyntaxToken> {
+        tokens(&self.0).next()
+    }
+
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl RefExpr {
+    pub fn is_mut(&self) -> bool {
+        keyword(&self.0, "mut").is_some()
+    }
+
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl CallExpr {
+    pub fn callee(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    pub fn arg_list(&self) -> Option<ArgList> {
+        child(&self.0)
+    }
+}
+
+impl ArgList {
+    pub fn args(&self) -> impl Iterator<Item = Expr> + '_ {
+        children(&self.0)
+    }
+}
+
+impl MethodCallExpr {
+    pub fn receiver(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    pub fn name_token(&self) -> Option<SyntaxToken> {
+        token(&self.0, &TokenKind::Ident)
+    }
+
+    pub fn arg_list(&self) -> Option<ArgList> {
+        child(&self.0)
+    }
+}
+
+impl FieldExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    // 필드 이름 `x`나 튜플 필드 번호 `0`입니다.
+    pub fn field_token(&self) -> Option<SyntaxToken> {
+        tokens(&self.0).find(|token| matches!(token.kind(), TokenKind::Ident | TokenKind::Literal))
+    }
+}
+
+impl IndexExpr {
+    pub fn base(&self) -> Option<Expr> {
+        nth_child(&self.0, 0)
+    }
+
+    pub fn index(&self) -> Option<Expr> {
+        nth_child(&self.0, 1)
+    }
+}
+
+impl RecordExpr {
+    pub fn path(&self) -> Option<Path> {
+        child(&self.0)
+    }
+
+    pub fn field_list(&self) -> Option<RecordExprFieldList> {
+        child(&self.0)
+    }
+}
+
+impl RecordExprFieldList {
+    pub fn fields(&self) -> impl Iterator<Item = RecordExprField> + '_ {
+        children(&self.0)
+    }
+}
+
+impl RecordExprField {
+    pub fn name_token(&self) -> Option<SyntaxToken> {
+        token(&self.0, &TokenKind::Ident)
+    }
+
+    // 값입니다. 줄임 표기 `Point { x }`에는 없습니다.
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl AssignExpr {
+    pub fn lhs(&self) -> Option<Expr> {
+        operand_before(&self.0, self.op_token()?)
+    }
+
+    pub fn rhs(&self) -> Option<Expr> {
+        operand_after(&self.0, self.op_token()?)
+    }
+
+    // `=` 또는 `+=` 같은 복합 대입 연산자입니다.
+    pub fn op_token(&self) -> Option<SyntaxToken> {
+        tokens(&self.0).next()
+    }
+}
+
+impl RangeExpr {
+    pub fn start(&self) -> Option<Expr> {
+        operand_before(&self.0, self.op_token()?)
+    }
+
+    pub fn end(&self) -> Option<Expr> {
+        operand_after(&self.0, self.op_token()?)
+    }
+
+    pub fn op_token(&self) -> Option<SyntaxToken> {
+        tokens(&self.0).next()
+    }
+
+    pub fn is_inclusive(&self) -> bool {
+        token(&self.0, &TokenKind::DotDotEq).is_some()
+    }
+}
+
+// 연산자 토큰 앞뒤의 피연산자입니다. 한쪽이 빠진 범위 `..b`, `a..`에서도 맞는 쪽을 찾습니다.
+fn operand_before(parent: &SyntaxNode, op: SyntaxToken) -> Option<Expr> {
+    children::<Expr>(parent).find(|expr| expr.syntax().text_range().hi <= op.text_range().lo)
+}
+
+fn operand_after(parent: &SyntaxNode, op: SyntaxToken) -> Option<Expr> {
+    children::<Expr>(parent).find(|expr| expr.syntax().text_range().lo >= op.text_range().hi)
+}
+
+impl IfExpr {
+    pub fn condition(&self) -> Option<Expr> {
+        nth_child(&self.0, 0)
+    }
+
+    pub fn then_branch(&self) -> Option<BlockExpr> {
+        nth_child::<Expr>(&self.0, 1).and_then(|expr| BlockExpr::cast(expr.syntax().clone()))
+    }
+
+    // `else` 뒤의 블록이나 `else if`의 if 표현식입니다.
+    pub fn else_branch(&self) -> Option<Expr> {
+        nth_child(&self.0, 2)
+    }
+}
+
+impl MatchExpr {
+    // 검사할 값입니다.
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    pub fn match_arm_list(&self) -> Option<MatchArmList> {
+        child(&self.0)
+    }
+}
+
+impl MatchArmList {
+    pub fn arms(&self) -> impl Iterator<Item = MatchArm> + '_ {
+        children(&self.0)
+    }
+}
+
+impl MatchArm {
+    pub fn pat(&self) -> Option<Pat> {
+        child(&self.0)
+    }
+
+    pub fn guard(&self) -> Option<MatchGuard> {
+        child(&self.0)
+    }
+
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl MatchGuard {
+    pub fn condition(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl WhileExpr {
+    pub fn condition(&self) -> Option<Expr> {
+        nth_child(&self.0, 0)
+    }
+
+    pub fn body(&self) -> Option<BlockExpr> {
+        nth_child::<Expr>(&self.0, 1).and_then(|expr| BlockExpr::cast(expr.syntax().clone()))
+    }
+}
+
+impl ForExpr {
+    pub fn pat(&self) -> Option<Pat> {
+        child(&self.0)
+    }
+
+    pub fn iterable(&self) -> Option<Expr> {
+        nth_child(&self.0, 0)
+    }
+
+    pub fn body(&self) -> Option<BlockExpr> {
+        nth_child::<Expr>(&self.0, 1).and_then(|expr| BlockExpr::cast(expr.syntax().clone()))
+    }
+}
+
+impl LoopExpr {
+    pub fn body(&self) -> Option<BlockExpr> {
+        child(&self.0)
+    }
+}
+
+impl ReturnExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl ClosureExpr {
+    pub fn is_move(&self) -> bool {
+        keyword(&self.0, "move").is_some()
+    }
+
+    pub fn param_list(&self) -> Option<ParamList> {
+        child(&self.0)
+    }
+
+    pub fn ret_type(&self) -> Option<RetType> {
+        child(&self.0)
+    }
+
+    pub fn body(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl QualifiedPathExpr {
+    // `<Type as Trait>::item`의 `Type`입니다.
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+
+    pub fn trait_path(&self) -> Option<Path> {
+        nth_child(&self.0, 0)
+    }
+
+    // `::` 뒤의 경로입니다.
+    pub fn path(&self) -> Option<Path> {
+        nth_child(&self.0, 1)
+    }
+}
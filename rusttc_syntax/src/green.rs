@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use rusttc_lexer::TokenKind;
+
+use crate::SyntaxKind;
+
+// 초록 트리의 토큰입니다. 종류와 소스 텍스트만 가지며, 위치는 부모가 계산합니다.
+// 바뀌지 않으므로 같은 토큰을 여러 트리가 나눠 씁니다.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct GreenToken(Arc<GreenTokenData>);
+
+#[derive(PartialEq, Eq, Hash)]
+struct GreenTokenData {
+    kind: TokenKind,
+    text: String,
+}
+
+impl GreenToken {
+    pub fn new(kind: TokenKind, text: &str) -> Self {
+        GreenToken(Arc::new(GreenTokenData { kind, text: text.to_string() }))
+    }
+
+    pub fn kind(&self) -> &TokenKind {
+        &self.0.kind
+    }
+
+    pub fn text(&self) -> &str {
+        &self.0.text
+    }
+
+    pub fn text_len(&self) -> u32 {
+        self.0.text.len() as u32
+    }
+}
+
+impl fmt::Debug for GreenToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {:?}", self.kind(), self.text())
+    }
+}
+
+// 초록 트리의 노드입니다. 종류, 자식 목록, 텍스트 길이만 가지고 부모나 절대 위치는 모릅니다.
+// 바뀌지 않으므로 고칠 때는 바뀐 경로만 새로 만들고 나머지 부분 트리는 그대로 나눠 씁니다.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct GreenNode(Arc<GreenNodeData>);
+
+#[derive(PartialEq, Eq, Hash)]
+struct GreenNodeData {
+    kind: SyntaxKind,
+    text_len: u32,              // 자식 텍스트 길이의 합
+    children: Vec<GreenElement>,
+}
+
+// 초록 노드의 자식입니다.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GreenElement {
+    Node(GreenNode),
+    Token(GreenToken),
+}
+
+impl GreenElement {
+    pub fn text_len(&self) -> u32 {
+        match self {
+            GreenElement::Node(node) => node.text_len(),
+            GreenElement::Token(token) => token.text_len(),
+        }
+    }
+}
+
+impl GreenNode {
+    pub fn new(kind: SyntaxKind, children: Vec<GreenElement>) -> Self {
+        let text_len = children.iter().map(GreenElement::text_len).sum();
+        GreenNode(Arc::new(GreenNodeData { kind, text_len, children }))
+    }
+
+    pub fn kind(&self) -> SyntaxKind {
+        self.0.kind
+    }
+
+    pub fn text_len(&self) -> u32 {
+        self.0.text_len
+    }
+
+    pub fn children(&self) -> &[GreenElement] {
+        &self.0.children
+    }
+
+    // `index`번째 자식만 바꾼 새 노드입니다. 나머지 자식은 그대로 나눠 씁니다.
+    pub fn replace_child(&self, index: usize, child: GreenElement) -> GreenNode {
+        let mut children = self.children().to_vec();
+        children[index] = child;
+        GreenNode::new(self.kind(), children)
+    }
+
+    // 두 노드가 같은 메모리를 나눠 쓰는지 확인합니다.
+    pub fn ptr_eq(&self, other: &GreenNode) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+// 깊이가 수만 단계인 트리에서도 스택이 넘치지 않도록 소스 텍스트를 반복문으로 씁니다.
+impl fmt::Display for GreenNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut stack = vec![self.children().iter()];
+        while let Some(children) = stack.last_mut() {
+            match children.next() {
+                Some(GreenElement::Node(node)) => stack.push(node.children().iter()),
+                Some(GreenElement::Token(token)) => f.write_str(token.text())?,
+                None => {
+                    stack.pop();
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for GreenNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}@{}", self.kind(), self.text_len())
+    }
+}
+
+// 마지막 참조가 사라진 자식 노드를 재귀 없이 풀어 깊은 트리에서도 스택이 넘치지 않게 합니다.
+impl Drop for GreenNodeData {
+    fn drop(&mut self) {
+        let mut stack = std::mem::take(&mut self.children);
+        while let Some(child) = stack.pop() {
+            if let GreenElement::Node(node) = child {
+                if let Some(mut data) = Arc::into_inner(node.0) {
+                    stack.append(&mut data.children);
+                }
+            }
+        }
+    }
+}
+
+// `GreenNodeBuilder::checkpoint`가 돌려주는 위치입니다. 나중에 이 위치부터 노드를 열 수 있습니다.
+#[derive(Debug, Clone, Copy)]
+pub struct Checkpoint(usize);
+
+// 토큰을 앞에서부터 받아 초록 트리를 아래에서 위로 쌓습니다.
+// 같은 종류와 텍스트의 토큰은 한 번만 만들어 나눠 씁니다.
+#[derive(Default)]
+pub struct GreenNodeBuilder {
+    parents: Vec<(SyntaxKind, usize)>,  // 열린 노드와 그 첫 자식의 위치
+    children: Vec<GreenElement>,        // 아직 부모가 닫히지 않은 자식
+    tokens: HashMap<(TokenKind, String), GreenToken>,
+}
+
+impl GreenNodeBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start_node(&mut self, kind: SyntaxKind) {
+        self.parents.push((kind, self.children.len()));
+    }
+
+    pub fn token(&mut self, kind: TokenKind, text: &str) {
+        let token = self
+            .tokens
+            .entry((kind.clone(), text.to_string()))
+            .or_insert_with(|| GreenToken::new(kind, text))
+            .clone();
+        self.children.push(GreenElement::Token(token));
+    }
+
+    // 이미 만든 노드를 자식으로 그대로 넣습니다.
+    pub fn node(&mut self, node: GreenNode) {
+        self.children.push(GreenElement::Node(node));
+    }
+
+    pub fn finish_node(&mut self) {
+        let (kind, first) = self.parents.pop().expect("finish_node without start_node");
+        let children = self.children.split_off(first);
+        self.children.push(GreenElement::Node(GreenNode::new(kind, children)));
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.children.len())
+    }
+
+    // `checkpoint` 뒤에 쌓인 자식들을 감싸는 노드를 엽니다. 왼쪽 피연산자를 파싱한 뒤에야
+    // 이항 연산임을 아는 경우에 씁니다.
+    pub fn start_node_at(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        let Checkpoint(first) = checkpoint;
+        assert!(first <= self.children.len(), "checkpoint is no longer valid");
+        if let Some(&(_, parent_first)) = self.parents.last() {
+            assert!(first >= parent_first, "checkpoint is outside the current node");
+        }
+        self.parents.push((kind, first));
+    }
+
+    // 하나 남은 루트 노드를 돌려줍니다.
+    pub fn finish(mut self) -> GreenNode {
+        assert!(self.parents.is_empty(), "unfinished nodes remain");
+        match (self.children.pop(), self.children.is_empty()) {
+            (Some(GreenElement::Node(node)), true) => node,
+            _ => panic!("the builder must produce exactly one root node"),
+        }
+    }
+}
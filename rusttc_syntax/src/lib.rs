@@ -0,0 +1,309 @@
+pub mod ast;
+mod green;
+mod parser;
+mod red;
+mod reparsing;
+
+use std::fmt;
+
+use rusttc_lexer::{tokenize, Span, TokenKind};
+
+use crate::ast::AstNode;
+
+pub use green::{Checkpoint, GreenElement, GreenNode, GreenNodeBuilder, GreenToken};
+pub use red::{SyntaxElement, SyntaxNode, SyntaxToken};
+pub use reparsing::{reparse, Edit};
+pub use rusttc_parse::syntax_kind::SyntaxKind;
+
+// 구문 트리를 만들며 찾은 오류입니다. 파서는 첫 오류에서 멈추지만, 트리는 남은 토큰을 `Error` 노드에
+// 담아 모든 토큰을 품은 채 끝까지 만들어집니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    pub message: String,
+    pub span: Span,             // 오류가 난 토큰의 위치
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)
+    }
+}
+
+// 소스 파일 하나를 파싱한 결과입니다.
+#[derive(Debug, Clone)]
+pub struct Parse {
+    green: GreenNode,
+    errors: Vec<SyntaxError>,
+}
+
+impl Parse {
+    pub fn green(&self) -> &GreenNode {
+        &self.green
+    }
+
+    pub fn syntax_node(&self) -> SyntaxNode {
+        SyntaxNode::new_root(self.green.clone())
+    }
+
+    // 트리 루트를 타입이 있는 AST 노드로 봅니다.
+    pub fn tree(&self) -> ast::SourceFile {
+        ast::SourceFile::cast(self.syntax_node()).expect("the root is always a source file")
+    }
+
+    pub fn errors(&self) -> &[SyntaxError] {
+        &self.errors
+    }
+}
+
+// 소스 텍스트를 무손실 구문 트리로 파싱합니다. 공백과 주석을 포함한 모든 토큰이 트리에 남으므로
+// 루트의 텍스트는 언제나 `text`와 같습니다. 매크로는 전개하지 않고 호출 모양 그대로 둡니다.
+pub fn parse(text: &str) -> Parse {
+    let tokens = tokenize(text);
+    let (green, errors) = parser::parse_source_file(&tokens);
+    Parse { green, errors }
+}
+
+// 공백과 주석 토큰입니다. 파서는 건너뛰지만 트리에는 남깁니다.
+pub fn is_trivia(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rusttc_lexer::{Span, TokenKind};
+
+    use crate::ast::{self, AstNode, HasName};
+    use crate::{parse, GreenElement, GreenNodeBuilder, SyntaxKind, SyntaxNode};
+
+    const SOURCE: &str = "\
+#![allow(unused)]
+// 점을 나타냅니다.
+#[derive(Debug)]
+pub struct Point { x: i32, y: i32 }
+
+macro_rules! square {
+    ($e:expr) => { $e * $e };
+}
+
+impl Shape for Point {
+    /* 넓이 */
+    fn area(&self) -> i32 {
+        let v = [0; 4];
+        let f = move |n: i32| -> i32 { n + self.x };
+        for i in 0..=3 { println!(\"{} {}\", i, v[i]); }
+        square!(self.x) + <Point as Shape>::zero()  // 끝
+    }
+}
+";
+
+    fn first<N: AstNode>(node: &SyntaxNode) -> N {
+        node.descendants().find_map(N::cast).unwrap()
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let parse = parse(SOURCE);
+        assert_eq!(parse.errors(), &[]);
+        assert_eq!(parse.syntax_node().text(), SOURCE);
+        assert_eq!(parse.syntax_node().text_range(), Span::new(0, SOURCE.len() as u32));
+
+        // 오류가 있는 소스도 토큰을 하나도 잃지 않습니다.
+        let broken = "fn f( { let x = ; } }\n) struct";
+        let parse = crate::parse(broken);
+        assert_eq!(parse.errors().len(), 1);
+        assert_eq!(parse.syntax_node().text(), broken);
+        // 첫 오류 뒤의 토큰은 `Error` 노드에 남습니다.
+        let error = parse.syntax_node().descendants().find(|node| node.kind() == SyntaxKind::Error).unwrap();
+        assert_eq!(error.text(), "{ let x = ; } }\n) struct");
+
+        // 예약어 뒤의 `!`는 매크로 호출이 아니므로 토큰을 건너뛰며 끝까지 파싱합니다.
+        let parse = crate::parse("let! q");
+        assert_eq!(parse.errors()[0].message, "expected item, found `let`");
+        assert_eq!(parse.syntax_node().text(), "let! q");
+    }
+
+    #[test]
+    fn test_parse_tree_shape() {
+        // 노드는 공백으로 시작하지 않고, 노드 끝의 공백과 주석은 부모에 남습니다.
+        let parse = parse("fn f() { 1 + 2 } // 끝\n");
+        assert_eq!(
+            parse.syntax_node().debug_dump(),
+            "\
+SourceFile@0..24
+  Fn@0..16
+    Ident@0..2 \"fn\"
+    Whitespace@2..3 \" \"
+    Name@3..4
+      Ident@3..4 \"f\"
+    ParamList@4..6
+      OpenParen@4..5 \"(\"
+      CloseParen@5..6 \")\"
+    Whitespace@6..7 \" \"
+    BlockExpr@7..16
+      OpenBrace@7..8 \"{\"
+      Whitespace@8..9 \" \"
+      BinExpr@9..14
+        Literal@9..10
+          Literal@9..10 \"1\"
+        Whitespace@10..11 \" \"
+        Plus@11..12 \"+\"
+        Whitespace@12..13 \" \"
+        Literal@13..14
+          Literal@13..14 \"2\"
+      Whitespace@14..15 \" \"
+      CloseBrace@15..16 \"}\"
+  Whitespace@16..17 \" \"
+  LineComment { doc_style: None }@17..23 \"// 끝\"
+  Whitespace@23..24 \"\\n\"
+"
+        );
+    }
+
+    #[test]
+    fn test_parent_navigation() {
+        let root = parse(SOURCE).syntax_node();
+        let offset = SOURCE.find("n + self.x").unwrap() as u32 + 2;
+        let plus = root.token_at_offset(offset).unwrap();
+        assert_eq!(plus.kind(), &TokenKind::Plus);
+
+        let kinds: Vec<SyntaxKind> = plus.parent().ancestors().map(|node| node.kind()).collect();
+        assert_eq!(
+            kinds,
+            [
+                SyntaxKind::BinExpr,
+                SyntaxKind::BlockExpr,
+                SyntaxKind::ClosureExpr,
+                SyntaxKind::LetStmt,
+                SyntaxKind::BlockExpr,
+                SyntaxKind::Fn,
+                SyntaxKind::ItemList,
+                SyntaxKind::Impl,
+                SyntaxKind::SourceFile,
+            ]
+        );
+        assert_eq!(plus.next_token().unwrap().text(), " ");
+
+        // 범위로 찾은 노드는 트리를 거쳐 찾은 노드와 같습니다.
+        let closure: ast::ClosureExpr = first(&root);
+        assert_eq!(root.covering_node(closure.syntax().text_range()), *closure.syntax());
+        assert_eq!(closure.syntax().parent().unwrap().kind(), SyntaxKind::LetStmt);
+    }
+
+    #[test]
+    fn test_typed_ast_view() {
+        let file = parse(SOURCE).tree();
+        let items: Vec<ast::Item> = file.items().collect();
+        assert_eq!(items.len(), 3);
+
+        let ast::Item::Struct(point) = &items[0] else { panic!("expected a struct") };
+        assert_eq!(point.name().unwrap().text(), "Point");
+        let fields: Vec<String> = point.field_list().unwrap().fields().map(|field| field.name().unwrap().text()).collect();
+        assert_eq!(fields, ["x", "y"]);
+
+        let ast::Item::MacroCall(square) = &items[1] else { panic!("expected a macro definition") };
+        assert_eq!(square.name().unwrap().text(), "square");
+
+        let ast::Item::Impl(imp) = &items[2] else { panic!("expected an impl") };
+        assert_eq!(imp.trait_().unwrap().syntax().text(), "Shape");
+        assert_eq!(imp.self_ty().unwrap().syntax().text(), "Point");
+
+        let ast::Item::Fn(area) = imp.item_list().unwrap().items().next().unwrap() else { panic!("expected a method") };
+        assert_eq!(area.name().unwrap().text(), "area");
+        assert!(area.param_list().unwrap().self_param().unwrap().is_ref());
+        assert_eq!(area.ret_type().unwrap().ty().unwrap().syntax().text(), "i32");
+
+        let body = area.body().unwrap();
+        assert_eq!(body.statements().count(), 3);
+        let ast::Expr::BinExpr(tail) = body.tail_expr().unwrap() else { panic!("expected a binary expression") };
+        assert_eq!(tail.op_token().unwrap().text(), "+");
+        assert!(matches!(tail.lhs(), Some(ast::Expr::MacroCall(_))));
+        assert!(matches!(tail.rhs(), Some(ast::Expr::CallExpr(_))));
+
+        let range: ast::RangeExpr = first(body.syntax());
+        assert!(range.is_inclusive());
+        assert_eq!(range.start().unwrap().syntax().text(), "0");
+        assert_eq!(range.end().unwrap().syntax().text(), "3");
+    }
+
+    #[test]
+    fn test_match_view() {
+        let source = "fn f() { match p { Point { x: 0, .. } | Point { y: -1..=1, .. } => 0, Point { x, y } if x > y => { 1 } _ => 2 } }";
+        let parse = parse(source);
+        assert_eq!(parse.errors(), &[]);
+        assert_eq!(parse.syntax_node().text(), source);
+
+        let match_expr: ast::MatchExpr = first(&parse.syntax_node());
+        assert_eq!(match_expr.expr().unwrap().syntax().text(), "p");
+        let arms: Vec<ast::MatchArm> = match_expr.match_arm_list().unwrap().arms().collect();
+        assert_eq!(arms.len(), 3);
+
+        let Some(ast::Pat::OrPat(or)) = arms[0].pat() else { panic!("expected an or-pattern") };
+        let alternatives: Vec<ast::Pat> = or.pats().collect();
+        assert_eq!(alternatives.len(), 2);
+        let ast::Pat::RecordPat(record) = &alternatives[1] else { panic!("expected a record pattern") };
+        assert_eq!(record.path().unwrap().syntax().text(), "Point");
+        let field_list = record.field_list().unwrap();
+        assert!(field_list.has_rest());
+        let field = field_list.fields().next().unwrap();
+        assert_eq!(field.name_token().unwrap().text(), "y");
+        let Some(ast::Pat::RangePat(range)) = field.pat() else { panic!("expected a range pattern") };
+        assert_eq!(range.start().unwrap().syntax().text(), "-1");
+        assert_eq!(range.end().unwrap().syntax().text(), "1");
+
+        // 필드 이름만 적은 필드는 이름이 곧 패턴입니다.
+        let Some(ast::Pat::RecordPat(record)) = arms[1].pat() else { panic!("expected a record pattern") };
+        let field_list = record.field_list().unwrap();
+        assert!(!field_list.has_rest());
+        assert_eq!(field_list.fields().map(|field| field.syntax().text()).collect::<Vec<_>>(), ["x", "y"]);
+        assert_eq!(arms[1].guard().unwrap().condition().unwrap().syntax().text(), "x > y");
+        assert!(matches!(arms[1].expr(), Some(ast::Expr::BlockExpr(_))));
+        assert!(matches!(arms[2].pat(), Some(ast::Pat::WildcardPat(_))));
+    }
+
+    #[test]
+    fn test_green_node_sharing() {
+        let parse = parse("fn f() { (1, 1) }");
+        let root = parse.syntax_node();
+        let tuple: ast::TupleExpr = first(&root);
+        let ones: Vec<_> = tuple.fields().map(|field| field.syntax().green().clone()).collect();
+        // 같은 토큰은 한 번만 만들어 나눠 씁니다.
+        let (GreenElement::Token(a), GreenElement::Token(b)) = (&ones[0].children()[0], &ones[1].children()[0]) else {
+            panic!("expected literal tokens");
+        };
+        assert_eq!(a, b);
+
+        // 자식 하나를 바꾸면 나머지 자식은 새 트리와 옛 트리가 나눠 씁니다.
+        let fn_node = root.green().children()[0].clone();
+        let GreenElement::Node(fn_node) = fn_node else { panic!("expected a fn node") };
+        let name = fn_node.children()[2].clone();
+        let renamed = fn_node.replace_child(0, GreenElement::Token(crate::GreenToken::new(TokenKind::Ident, "fn")));
+        let GreenElement::Node(old_name) = name else { panic!("expected a name node") };
+        let GreenElement::Node(new_name) = &renamed.children()[2] else { panic!("expected a name node") };
+        assert!(old_name.ptr_eq(new_name));
+        assert_eq!(renamed.to_string(), "fn f() { (1, 1) }");
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        // 깊이 20만의 트리도 스택을 넘치지 않고 텍스트를 만들고 해제합니다.
+        let depth = 200_000;
+        let mut builder = GreenNodeBuilder::new();
+        for _ in 0..depth {
+            builder.start_node(SyntaxKind::ParenExpr);
+            builder.token(TokenKind::OpenParen, "(");
+        }
+        for _ in 0..depth {
+            builder.token(TokenKind::CloseParen, ")");
+            builder.finish_node();
+        }
+        let green = builder.finish();
+        assert_eq!(green.text_len(), 2 * depth);
+        assert_eq!(green.to_string().len(), 2 * depth as usize);
+
+        let leaf = SyntaxNode::new_root(green).descendants().last().unwrap();
+        assert_eq!(leaf.ancestors().count(), depth as usize);
+    }
+}
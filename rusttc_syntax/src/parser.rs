@@ -0,0 +1,129 @@
+use rusttc_lexer::{Token, TokenKind};
+use rusttc_parse::parser::{parse_block_events, parse_source_file_events, Event, SyntaxEvents};
+
+use crate::green::{Checkpoint, GreenNode, GreenNodeBuilder};
+use crate::{is_trivia, SyntaxError, SyntaxKind};
+
+// 토큰 전체를 소스 파일 노드 하나로 파싱합니다. 문법은 `rusttc_parse`의 파서 하나뿐이고,
+// 여기서는 파서가 남긴 사건에 공백과 주석을 끼워 넣어 트리를 쌓습니다.
+//
+// 파서는 첫 오류에서 멈추므로, 오류 뒤의 토큰은 그때 열려 있던 가장 안쪽 노드에 `Error` 노드로 넣습니다.
+pub(crate) fn parse_source_file(tokens: &[Token]) -> (GreenNode, Vec<SyntaxError>) {
+    let SyntaxEvents { events, error } = parse_source_file_events(tokens);
+    let mut sink = Sink::new(tokens);
+    // 루트는 맨 앞의 공백과 주석까지 품어야 하므로 공백을 먼저 내보내지 않고 엽니다.
+    sink.builder.start_node(SyntaxKind::SourceFile);
+    let open = sink.replay(&events);
+
+    if sink.pos < sink.significant.len() {
+        sink.start_node(SyntaxKind::Error);
+        while sink.pos < sink.significant.len() {
+            sink.token();
+        }
+        sink.builder.finish_node();
+    }
+    for _ in 0..open {
+        sink.builder.finish_node();
+    }
+    sink.flush_trivia(tokens.len());
+    sink.builder.finish_node();
+
+    let errors = error.map(|error| SyntaxError { message: error.message, span: error.span });
+    (sink.builder.finish(), errors.into_iter().collect())
+}
+
+// `{`로 시작해 짝이 맞는 `}`로 끝나는 토큰을 블록 표현식 하나로 파싱합니다. 증분 재파싱에서 씁니다.
+// 블록에 오류가 있거나 블록이 토큰을 남김없이 담지 못하면 `None`을 돌려줍니다.
+pub(crate) fn parse_block(tokens: &[Token]) -> Option<GreenNode> {
+    if tokens.first()?.kind != TokenKind::OpenBrace {
+        return None;
+    }
+    let SyntaxEvents { events, error } = parse_block_events(tokens);
+    if error.is_some() {
+        return None;
+    }
+    let mut sink = Sink::new(tokens);
+    sink.replay(&events);
+    Some(sink.builder.finish())
+}
+
+// 사건을 차례로 초록 트리에 옮깁니다.
+//
+// 공백과 주석은 다음 토큰을 넣기 직전에 그때 열려 있는 노드에 넣습니다. 노드를 열 때는 밀린 공백을
+// 먼저 부모에 넣으므로, 노드는 공백으로 시작하지 않고 끝의 공백은 부모에 남습니다.
+struct Sink<'t> {
+    tokens: &'t [Token],        // 공백과 주석을 포함한 모든 토큰
+    significant: Vec<usize>,    // 공백과 주석이 아닌 토큰의 위치
+    pos: usize,                 // 다음에 넣을 토큰의 `significant` 안 위치
+    emitted: usize,             // 트리에 넣은 토큰 수
+    builder: GreenNodeBuilder,
+}
+
+impl<'t> Sink<'t> {
+    fn new(tokens: &'t [Token]) -> Self {
+        let significant = (0..tokens.len())
+            .filter(|&i| !is_trivia(&tokens[i].kind) && tokens[i].kind != TokenKind::EOF)
+            .collect();
+        Sink { tokens, significant, pos: 0, emitted: 0, builder: GreenNodeBuilder::new() }
+    }
+
+    // 사건을 모두 옮기고, 닫히지 않은 채 남은 노드의 수를 돌려줍니다.
+    fn replay(&mut self, events: &[Event]) -> usize {
+        let mut checkpoints: Vec<Option<Checkpoint>> = vec![None; events.len()];
+        let mut open = 0;
+        for (index, event) in events.iter().enumerate() {
+            match *event {
+                Event::Start(kind) => {
+                    self.start_node(kind);
+                    open += 1;
+                },
+                Event::Checkpoint => {
+                    self.flush_pending_trivia();
+                    checkpoints[index] = Some(self.builder.checkpoint());
+                },
+                Event::StartAt(checkpoint, kind) => {
+                    let checkpoint = checkpoints[checkpoint].expect("a node starts at a recorded checkpoint");
+                    self.builder.start_node_at(checkpoint, kind);
+                    open += 1;
+                },
+                Event::Finish => {
+                    self.builder.finish_node();
+                    open -= 1;
+                },
+                Event::Token => self.token(),
+            }
+        }
+        open
+    }
+
+    // 다음 토큰을 앞의 공백, 주석과 함께 지금 열린 노드에 넣습니다.
+    fn token(&mut self) {
+        let index = self.significant[self.pos];
+        self.flush_trivia(index);
+        let token = &self.tokens[index];
+        self.builder.token(token.kind.clone(), &token.text);
+        self.emitted = index + 1;
+        self.pos += 1;
+    }
+
+    // 밀린 공백과 주석을 지금 노드에 넣은 뒤 새 노드를 엽니다.
+    fn start_node(&mut self, kind: SyntaxKind) {
+        self.flush_pending_trivia();
+        self.builder.start_node(kind);
+    }
+
+    fn flush_trivia(&mut self, end: usize) {
+        while self.emitted < end {
+            let token = &self.tokens[self.emitted];
+            self.builder.token(token.kind.clone(), &token.text);
+            self.emitted += 1;
+        }
+    }
+
+    // 입력 끝의 공백과 주석은 루트가 닫힐 때 넣습니다.
+    fn flush_pending_trivia(&mut self) {
+        if let Some(&index) = self.significant.get(self.pos) {
+            self.flush_trivia(index);
+        }
+    }
+}
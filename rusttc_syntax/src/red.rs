@@ -0,0 +1,344 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use rusttc_lexer::{Span, TokenKind};
+
+use crate::green::{GreenElement, GreenNode, GreenToken};
+use crate::SyntaxKind;
+
+// 빨강 트리의 노드입니다. 초록 노드에 부모와 소스 안의 절대 위치를 붙인 것으로,
+// 필요할 때 루트에서부터 만들어지고 부모 쪽으로 거슬러 올라갈 수 있습니다.
+#[derive(Clone)]
+pub struct SyntaxNode(Rc<NodeData>);
+
+struct NodeData {
+    green: GreenNode,
+    parent: Option<SyntaxNode>,
+    index: usize,               // 부모의 자식 목록에서의 위치
+    offset: u32,                // 소스 안의 시작 위치
+}
+
+// 빨강 트리의 토큰입니다. 부모 노드와 그 안의 위치로 초록 토큰을 가리킵니다.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SyntaxToken {
+    parent: SyntaxNode,
+    index: usize,
+    offset: u32,
+}
+
+// 빨강 노드의 자식입니다.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+impl SyntaxNode {
+    pub fn new_root(green: GreenNode) -> Self {
+        SyntaxNode(Rc::new(NodeData { green, parent: None, index: 0, offset: 0 }))
+    }
+
+    fn new_child(green: GreenNode, parent: SyntaxNode, index: usize, offset: u32) -> Self {
+        SyntaxNode(Rc::new(NodeData { green, parent: Some(parent), index, offset }))
+    }
+
+    pub fn green(&self) -> &GreenNode {
+        &self.0.green
+    }
+
+    pub fn kind(&self) -> SyntaxKind {
+        self.0.green.kind()
+    }
+
+    pub fn text_range(&self) -> Span {
+        Span::new(self.0.offset, self.0.offset + self.0.green.text_len())
+    }
+
+    // 노드가 덮는 소스 텍스트입니다. 공백과 주석도 그대로 들어 있습니다.
+    pub fn text(&self) -> String {
+        self.0.green.to_string()
+    }
+
+    pub fn parent(&self) -> Option<SyntaxNode> {
+        self.0.parent.clone()
+    }
+
+    // 부모 목록에서의 위치입니다. 루트는 0입니다.
+    pub fn index(&self) -> usize {
+        self.0.index
+    }
+
+    // 자기 자신부터 루트까지 거슬러 올라갑니다.
+    pub fn ancestors(&self) -> impl Iterator<Item = SyntaxNode> {
+        std::iter::successors(Some(self.clone()), SyntaxNode::parent)
+    }
+
+    pub fn children_with_tokens(&self) -> impl Iterator<Item = SyntaxElement> + '_ {
+        let mut offset = self.0.offset;
+        self.0.green.children().iter().enumerate().map(move |(index, child)| {
+            let element = self.child_element(index, child, offset);
+            offset += child.text_len();
+            element
+        })
+    }
+
+    pub fn children(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.children_with_tokens().filter_map(SyntaxElement::into_node)
+    }
+
+    pub fn first_child(&self) -> Option<SyntaxNode> {
+        self.children().next()
+    }
+
+    pub fn first_token(&self) -> Option<SyntaxToken> {
+        self.descendants_with_tokens().find_map(SyntaxElement::into_token)
+    }
+
+    pub fn last_token(&self) -> Option<SyntaxToken> {
+        let mut node = self.clone();
+        loop {
+            let last = node.children_with_tokens().last()?;
+            match last {
+                SyntaxElement::Node(child) => node = child,
+                SyntaxElement::Token(token) => return Some(token),
+            }
+        }
+    }
+
+    pub fn next_sibling(&self) -> Option<SyntaxNode> {
+        let parent = self.parent()?;
+        let sibling = parent.children().find(|child| child.index() > self.index());
+        sibling
+    }
+
+    pub fn prev_sibling(&self) -> Option<SyntaxNode> {
+        let parent = self.parent()?;
+        let sibling = parent.children().take_while(|child| child.index() < self.index()).last();
+        sibling
+    }
+
+    // 자기 자신을 포함한 모든 자손 노드를 앞에서부터(전위 순회) 돌려줍니다.
+    pub fn descendants(&self) -> impl Iterator<Item = SyntaxNode> {
+        self.descendants_with_tokens().filter_map(SyntaxElement::into_node)
+    }
+
+    // 자기 자신을 포함한 모든 자손 노드와 토큰을 앞에서부터 돌려줍니다.
+    pub fn descendants_with_tokens(&self) -> impl Iterator<Item = SyntaxElement> {
+        let mut stack = vec![SyntaxElement::Node(self.clone())];
+        std::iter::from_fn(move || {
+            let element = stack.pop()?;
+            if let SyntaxElement::Node(node) = &element {
+                let children: Vec<SyntaxElement> = node.children_with_tokens().collect();
+                stack.extend(children.into_iter().rev());
+            }
+            Some(element)
+        })
+    }
+
+    // `offset` 위치의 글자를 담은 토큰입니다. 노드 범위 밖이면 None입니다.
+    pub fn token_at_offset(&self, offset: u32) -> Option<SyntaxToken> {
+        let range = self.text_range();
+        if offset < range.lo || offset >= range.hi {
+            return None;
+        }
+        let mut node = self.clone();
+        loop {
+            let child = node.children_with_tokens().find(|child| child.text_range().hi > offset)?;
+            match child {
+                SyntaxElement::Node(child) => node = child,
+                SyntaxElement::Token(token) => return Some(token),
+            }
+        }
+    }
+
+    // `range`를 모두 덮는 가장 깊은 노드입니다.
+    pub fn covering_node(&self, range: Span) -> SyntaxNode {
+        let mut node = self.clone();
+        loop {
+            // 빈 범위는 앞뒤 노드 어느 쪽에도 걸리므로 비어 있지 않은 자식만 따라 내려갑니다.
+            let child = node.children().find(|child| {
+                let child_range = child.text_range();
+                child_range.lo < child_range.hi && contains(child_range, range)
+            });
+            match child {
+                Some(child) => node = child,
+                None => return node,
+            }
+        }
+    }
+
+    // 들여쓰기로 트리 구조를 보여 주는 문자열입니다. 노드는 `Kind@lo..hi`, 토큰은 텍스트를 덧붙입니다.
+    pub fn debug_dump(&self) -> String {
+        let mut out = String::new();
+        let mut depth = 0;
+        let mut stack = vec![Some(SyntaxElement::Node(self.clone()))];
+        while let Some(entry) = stack.pop() {
+            let Some(element) = entry else {
+                depth -= 1;
+                continue;
+            };
+            let range = element.text_range();
+            out.push_str(&"  ".repeat(depth));
+            match element {
+                SyntaxElement::Node(node) => {
+                    out.push_str(&format!("{:?}@{}..{}\n", node.kind(), range.lo, range.hi));
+                    stack.push(None);
+                    let children: Vec<SyntaxElement> = node.children_with_tokens().collect();
+                    stack.extend(children.into_iter().rev().map(Some));
+                    depth += 1;
+                },
+                SyntaxElement::Token(token) => {
+                    out.push_str(&format!("{:?}@{}..{} {:?}\n", token.kind(), range.lo, range.hi, token.text()));
+                },
+            }
+        }
+        out
+    }
+
+    fn child_element(&self, index: usize, child: &GreenElement, offset: u32) -> SyntaxElement {
+        match child {
+            GreenElement::Node(green) => SyntaxElement::Node(SyntaxNode::new_child(green.clone(), self.clone(), index, offset)),
+            GreenElement::Token(_) => SyntaxElement::Token(SyntaxToken { parent: self.clone(), index, offset }),
+        }
+    }
+}
+
+fn contains(outer: Span, inner: Span) -> bool {
+    outer.lo <= inner.lo && inner.hi <= outer.hi
+}
+
+// 같은 초록 노드를 같은 위치에서 가리키면 같은 노드입니다.
+impl PartialEq for SyntaxNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.green.ptr_eq(&other.0.green) && self.0.offset == other.0.offset
+    }
+}
+
+impl Eq for SyntaxNode {}
+
+impl Hash for SyntaxNode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.offset.hash(state);
+        self.kind().hash(state);
+    }
+}
+
+impl fmt::Display for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.green)
+    }
+}
+
+impl fmt::Debug for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let range = self.text_range();
+        write!(f, "{:?}@{}..{}", self.kind(), range.lo, range.hi)
+    }
+}
+
+// 노드 하나만 남아 있던 깊은 부모 사슬을 재귀 없이 풉니다.
+impl Drop for NodeData {
+    fn drop(&mut self) {
+        let mut parent = self.parent.take();
+        while let Some(node) = parent {
+            parent = match Rc::into_inner(node.0) {
+                Some(mut data) => data.parent.take(),
+                None => None,
+            };
+        }
+    }
+}
+
+impl SyntaxToken {
+    fn green(&self) -> &GreenToken {
+        match &self.parent.green().children()[self.index] {
+            GreenElement::Token(token) => token,
+            GreenElement::Node(_) => unreachable!("a token points at a node"),
+        }
+    }
+
+    pub fn kind(&self) -> &TokenKind {
+        self.green().kind()
+    }
+
+    pub fn text(&self) -> &str {
+        self.green().text()
+    }
+
+    pub fn text_range(&self) -> Span {
+        Span::new(self.offset, self.offset + self.green().text_len())
+    }
+
+    pub fn parent(&self) -> SyntaxNode {
+        self.parent.clone()
+    }
+
+    // 부모의 자식 목록에서 이 토큰의 위치입니다.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    // 공백과 주석 토큰인지 확인합니다.
+    pub fn is_trivia(&self) -> bool {
+        crate::is_trivia(self.kind())
+    }
+
+    // 예약어 `keyword`인지 확인합니다. 렉서는 예약어도 식별자로 내보냅니다.
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        *self.kind() == TokenKind::Ident && self.text() == keyword
+    }
+
+    // 트리 전체에서 바로 다음 토큰입니다. 토큰이 없는 빈 노드는 건너뜁니다.
+    pub fn next_token(&self) -> Option<SyntaxToken> {
+        let mut node = self.parent.clone();
+        let mut index = self.index;
+        loop {
+            let next = node.children_with_tokens().skip(index + 1).find_map(|element| match element {
+                SyntaxElement::Token(token) => Some(token),
+                SyntaxElement::Node(child) => child.first_token(),
+            });
+            if next.is_some() {
+                return next;
+            }
+            index = node.index();
+            node = node.parent()?;
+        }
+    }
+}
+
+impl fmt::Debug for SyntaxToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let range = self.text_range();
+        write!(f, "{:?}@{}..{} {:?}", self.kind(), range.lo, range.hi, self.text())
+    }
+}
+
+impl fmt::Display for SyntaxToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text())
+    }
+}
+
+impl SyntaxElement {
+    pub fn text_range(&self) -> Span {
+        match self {
+            SyntaxElement::Node(node) => node.text_range(),
+            SyntaxElement::Token(token) => token.text_range(),
+        }
+    }
+
+    pub fn into_node(self) -> Option<SyntaxNode> {
+        match self {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        }
+    }
+
+    pub fn into_token(self) -> Option<SyntaxToken> {
+        match self {
+            SyntaxElement::Node(_) => None,
+            SyntaxElement::Token(token) => Some(token),
+        }
+    }
+}
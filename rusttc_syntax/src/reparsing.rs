@@ -0,0 +1,328 @@
+use rusttc_ast::ast::FN_TRAITS;
+use rusttc_lexer::{tokenize_at, Span, Token, TokenKind};
+
+use crate::green::{GreenElement, GreenNode, GreenToken};
+use rusttc_parse::parser::KEYWORDS;
+
+use crate::parser;
+use crate::{is_trivia, Parse, SyntaxElement, SyntaxError, SyntaxKind, SyntaxNode, SyntaxToken};
+
+const LOOKAHEAD: usize = 3;     // 파서가 현재 토큰을 포함해 내다보는 토큰 수
+
+// 소스에 가한 편집 하나입니다. `delete` 구간을 지우고 그 자리에 `insert`를 넣습니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edit {
+    pub delete: Span,
+    pub insert: String,
+}
+
+impl Edit {
+    pub fn new(delete: Span, insert: impl Into<String>) -> Self {
+        Edit { delete, insert: insert.into() }
+    }
+
+    // `text`에 편집을 적용한 결과입니다.
+    pub fn apply(&self, text: &str) -> String {
+        let (lo, hi) = (self.delete.lo as usize, self.delete.hi as usize);
+        format!("{}{}{}", &text[..lo], self.insert, &text[hi..])
+    }
+
+    // `range` 안쪽의 편집을 `range`의 텍스트에 적용합니다.
+    fn apply_within(&self, text: &str, range: Span) -> String {
+        let (lo, hi) = ((self.delete.lo - range.lo) as usize, (self.delete.hi - range.lo) as usize);
+        format!("{}{}{}", &text[..lo], self.insert, &text[hi..])
+    }
+
+    // 편집 뒤에 있던 스팬을 편집 후의 위치로 옮깁니다. 앞에 있던 스팬은 그대로입니다.
+    fn shift(&self, span: Span) -> Span {
+        if span.lo < self.delete.hi {
+            return span;
+        }
+        let shift = |offset: u32| offset + self.insert.len() as u32 - (self.delete.hi - self.delete.lo);
+        Span::new(shift(span.lo), shift(span.hi))
+    }
+}
+
+// 편집 하나를 반영해 `old`를 다시 파싱합니다. 결과는 편집한 텍스트를 처음부터 파싱한 것과 같습니다.
+//
+// 편집이 토큰 하나 안에서 끝나고 토큰의 종류가 그대로면 그 토큰만 바꿉니다. 아니면 편집을 감싸는
+// 가장 작은 블록만 다시 토큰화하고 파싱해 끼워 넣습니다. 바뀌지 않은 부분 트리는 그대로 나눠 쓰며,
+// 둘 다 안 되면 전체를 다시 파싱합니다. 파서는 첫 오류에서 멈추므로 블록은 옛 트리와 새 블록에
+// 모두 오류가 없을 때만 바꿉니다. 오류가 고쳐지면 그 뒤를 처음으로 파싱하게 되기 때문입니다.
+pub fn reparse(old: &Parse, edit: &Edit) -> Parse {
+    let root = old.syntax_node();
+    assert!(edit.delete.lo <= edit.delete.hi && edit.delete.hi <= root.text_range().hi, "edit is outside the source");
+
+    reparse_token(old, &root, edit)
+        .or_else(|| reparse_block(old, &root, edit))
+        .unwrap_or_else(|| crate::parse(&edit.apply(&root.text())))
+}
+
+fn reparse_token(old: &Parse, root: &SyntaxNode, edit: &Edit) -> Option<Parse> {
+    // 토큰 끝에 덧붙이는 편집도 받도록 편집 바로 앞 글자의 토큰도 살펴봅니다.
+    let token = [Some(edit.delete.lo), edit.delete.lo.checked_sub(1)]
+        .into_iter()
+        .flatten()
+        .filter_map(|offset| root.token_at_offset(offset))
+        .find(|token| contains(token.text_range(), edit.delete))?;
+    let range = token.text_range();
+    if !is_relexable(token.kind(), token.text()) {
+        return None;
+    }
+    // 오류 메시지는 토큰 텍스트를 담으므로 오류가 걸린 토큰은 블록째 다시 파싱합니다.
+    if old.errors.iter().any(|error| error.span.lo < range.hi && range.lo < error.span.hi) {
+        return None;
+    }
+
+    // 앞뒤 토큰과 합쳐지거나 나뉘지 않는지 보려고 이웃 토큰까지 함께 다시 토큰화합니다.
+    let prev = range.lo.checked_sub(1).and_then(|offset| root.token_at_offset(offset));
+    let next = token.next_token();
+    let start = prev.as_ref().map_or(range.lo, |prev| prev.text_range().lo);
+    let text = format!(
+        "{}{}{}",
+        prev.as_ref().map_or("", |prev| prev.text()),
+        edit.apply_within(token.text(), range),
+        next.as_ref().map_or("", |next| next.text()),
+    );
+    let tokens = tokenize_at(&text, start);
+
+    let mut tokens = tokens.iter();
+    let same = |token: Option<&Token>, old: &SyntaxToken| {
+        token.is_some_and(|token| token.kind == *old.kind() && token.text == old.text())
+    };
+    if prev.as_ref().is_some_and(|prev| !same(tokens.next(), prev)) {
+        return None;
+    }
+    let new = tokens.next()?;
+    if new.kind != *token.kind() || !is_relexable(&new.kind, &new.text) {
+        return None;
+    }
+    if next.as_ref().is_some_and(|next| !same(tokens.next(), next)) || tokens.next().is_some() {
+        return None;
+    }
+
+    let new = GreenToken::new(new.kind.clone(), &new.text);
+    let parent = token.parent().green().replace_child(token.index(), GreenElement::Token(new));
+    Some(Parse {
+        green: splice(token.parent(), parent),
+        errors: old.errors.iter().map(|error| shift_error(error, edit)).collect(),
+    })
+}
+
+fn reparse_block(old: &Parse, root: &SyntaxNode, edit: &Edit) -> Option<Parse> {
+    if !old.errors.is_empty() {
+        return None;
+    }
+    // 괄호를 건드리지 않고 안쪽만 고친 블록을 안에서부터 찾습니다.
+    let node = root.covering_node(edit.delete);
+    node.ancestors().filter(|node| node.kind() == SyntaxKind::BlockExpr).find_map(|block| {
+        let range = block.text_range();
+        if !(range.lo < edit.delete.lo && edit.delete.hi < range.hi) {
+            return None;
+        }
+        let text = edit.apply_within(&block.text(), range);
+        let tokens = tokenize_at(&text, range.lo);
+        if !is_balanced(&tokens) || !same_head(&block, &tokens) {
+            return None;
+        }
+        let green = parser::parse_block(&tokens)?;
+        Some(Parse { green: splice(block, green), errors: Vec::new() })
+    })
+}
+
+// 블록 앞의 파서는 `{`부터 토큰 세 개까지 내다보고 구조체 리터럴인지 정하므로 그 토큰들은 그대로여야 합니다.
+fn same_head(block: &SyntaxNode, tokens: &[Token]) -> bool {
+    let old = block
+        .descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .filter(|token| !token.is_trivia())
+        .take(LOOKAHEAD);
+    let new = tokens.iter().filter(|token| !is_trivia(&token.kind)).take(LOOKAHEAD);
+    old.map(|token| (token.kind().clone(), token.text().to_string()))
+        .eq(new.map(|token| (token.kind.clone(), token.text.clone())))
+}
+
+// `node`를 초록 노드 `green`으로 바꾼 새 루트입니다. 루트까지의 경로만 새로 만듭니다.
+fn splice(mut node: SyntaxNode, mut green: GreenNode) -> GreenNode {
+    while let Some(parent) = node.parent() {
+        green = parent.green().replace_child(node.index(), GreenElement::Node(green));
+        node = parent;
+    }
+    green
+}
+
+fn shift_error(error: &SyntaxError, edit: &Edit) -> SyntaxError {
+    SyntaxError { message: error.message.clone(), span: edit.shift(error.span) }
+}
+
+// 텍스트만 바뀌어도 트리 모양이 그대로인 토큰입니다. 예약어처럼 파서가 텍스트를 보는 식별자는 뺍니다.
+fn is_relexable(kind: &TokenKind, text: &str) -> bool {
+    match kind {
+        TokenKind::Ident => {
+            text != "_" && text != "macro_rules" && !KEYWORDS.contains(&text) && !FN_TRAITS.contains(&text)
+        },
+        TokenKind::Literal | TokenKind::StringLiteral | TokenKind::CharLiteral => true,
+        kind => is_trivia(kind),
+    }
+}
+
+// `{`로 시작해 그와 짝인 `}`로 끝나는지 확인합니다.
+fn is_balanced(tokens: &[Token]) -> bool {
+    if tokens.first().map(|token| &token.kind) != Some(&TokenKind::OpenBrace)
+        || tokens.last().map(|token| &token.kind) != Some(&TokenKind::CloseBrace)
+    {
+        return false;
+    }
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.kind {
+            TokenKind::OpenBrace => depth += 1,
+            TokenKind::CloseBrace => depth -= 1,
+            _ => {},
+        }
+        if depth == 0 && i + 1 < tokens.len() {
+            return false;
+        }
+    }
+    depth == 0
+}
+
+fn contains(outer: Span, inner: Span) -> bool {
+    outer.lo <= inner.lo && inner.hi <= outer.hi
+}
+
+#[cfg(test)]
+mod tests {
+    use rusttc_lexer::Span;
+
+    use crate::{parse, reparse, Edit, Parse, SyntaxKind};
+
+    const SOURCE: &str = "\
+struct Point { x: i32, y: i32 }
+
+fn area(p: &Point) -> i32 {
+    let w = p.x; // 너비
+    if w > 0 { w * p.y } else { 0 }
+}
+
+impl Point {
+    fn new() -> Point { Point { x: 1, y: 2 } }
+    fn sum(&self) -> i32 { let v = [1, 2]; v[0] + self.x }
+}
+
+fn main() { let s = \"가나\"; println!(\"{}\", s); }
+";
+
+    // 편집 하나를 증분으로 반영한 결과가 처음부터 파싱한 결과와 같은지 확인합니다.
+    fn check(old: &Parse, text: &str, edit: &Edit) -> (Parse, String) {
+        let text = edit.apply(text);
+        let incremental = reparse(old, edit);
+        let fresh = parse(&text);
+        assert_eq!(incremental.green(), fresh.green(), "different trees after {:?} in\n{}", edit, text);
+        assert_eq!(incremental.errors(), fresh.errors(), "different errors after {:?} in\n{}", edit, text);
+        (incremental, text)
+    }
+
+    fn edit_at(text: &str, old: &str, new: &str) -> Edit {
+        let lo = text.find(old).unwrap() as u32;
+        Edit::new(Span::new(lo, lo + old.len() as u32), new)
+    }
+
+    fn item(parse: &Parse, index: usize) -> crate::GreenNode {
+        parse.syntax_node().children().nth(index).unwrap().green().clone()
+    }
+
+    #[test]
+    fn test_reparse_token() {
+        let old = parse(SOURCE);
+        let (new, _) = check(&old, SOURCE, &edit_at(SOURCE, "w > 0", "width > 0"));
+        // 바뀐 함수 밖의 항목은 그대로 나눠 씁니다.
+        assert!(item(&new, 0).ptr_eq(&item(&old, 0)));
+        assert!(item(&new, 2).ptr_eq(&item(&old, 2)));
+        assert!(!item(&new, 1).ptr_eq(&item(&old, 1)));
+
+        check(&old, SOURCE, &edit_at(SOURCE, "너비", "높이"));
+        check(&old, SOURCE, &edit_at(SOURCE, "가나", "다"));
+        // 예약어가 되는 식별자는 토큰만 바꿀 수 없습니다.
+        check(&old, SOURCE, &edit_at(SOURCE, "w > 0", "if > 0"));
+    }
+
+    #[test]
+    fn test_reparse_block() {
+        let old = parse(SOURCE);
+        let (new, text) = check(&old, SOURCE, &edit_at(SOURCE, "v[0] + self.x", "let t = v[0]; t + self.x"));
+        assert!(item(&new, 1).ptr_eq(&item(&old, 1)));
+        let blocks = |parse: &Parse| {
+            parse.syntax_node().descendants().filter(|node| node.kind() == SyntaxKind::BlockExpr).count()
+        };
+        assert_eq!(blocks(&new), blocks(&old));
+
+        // 블록 안에서 오류가 생기고 없어져도 오류 목록이 같습니다. 파서는 첫 오류에서 멈춥니다.
+        let (broken, text) = check(&new, &text, &edit_at(&text, "let t = v[0];", "let = v[0] +;"));
+        assert_eq!(broken.errors().len(), 1);
+        let (fixed, _) = check(&broken, &text, &edit_at(&text, "let = v[0] +;", "let t = v[0];"));
+        assert!(fixed.errors().is_empty());
+    }
+
+    #[test]
+    fn test_reparse_fallback() {
+        let old = parse(SOURCE);
+        // 괄호를 건드리거나 짝이 깨지면 더 큰 블록이나 파일 전체를 다시 파싱합니다.
+        check(&old, SOURCE, &edit_at(SOURCE, "{ 0 }", "0 }"));
+        check(&old, SOURCE, &edit_at(SOURCE, "w * p.y", "w * { p.y"));
+        check(&old, SOURCE, &edit_at(SOURCE, "// 너비", "/* 너비"));
+        check(&old, SOURCE, &Edit::new(Span::new(0, SOURCE.len() as u32), ""));
+        check(&old, SOURCE, &Edit::new(Span::new(SOURCE.len() as u32, SOURCE.len() as u32), "fn"));
+
+        // 닫히지 않았던 블록이 닫히면 입력 끝의 오류도 사라집니다.
+        let text = "fn f() { g(); { a }\n";
+        let (closed, _) = check(&parse(text), text, &edit_at(text, "a }", "a } }"));
+        assert!(closed.errors().is_empty());
+        // 블록 첫 토큰이 바뀌면 앞의 경로가 구조체 리터럴이 되기도 합니다.
+        let text = "fn f() { S { x::1 } }";
+        let (record, _) = check(&parse(text), text, &edit_at(text, "x::1", "x: 1"));
+        assert!(record.syntax_node().descendants().any(|node| node.kind() == SyntaxKind::RecordExpr));
+    }
+
+    // 의존성 없이 쓰는 선형 합동 난수 생성기입니다. 시드가 같으면 편집 순서도 같습니다.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((self.0 >> 33) as usize) % n
+        }
+    }
+
+    #[test]
+    fn test_reparse_random_edits() {
+        const INSERTS: &[&str] = &[
+            "", "", "a", "x", "1", "_", " ", "\n", ";", ".", ",", "+", "=", "!", "&", "|", "<", ">", "::",
+            "(", ")", "[", "]", "{", "}", "\"", "'", "/", "*", "//", "/*", "*/", "let", "fn", "mut ",
+            "if c { 1 } else { 2 }", "let q = 3;", "f(y)", "S { a: 1 }", "m!(1)", "{ }", "가",
+        ];
+        for seed in 0..100 {
+            let mut rng = Rng(seed);
+            let mut text = SOURCE.to_string();
+            let mut old = parse(&text);
+            for _ in 0..40 {
+                let boundary = |rng: &mut Rng, text: &str| {
+                    let mut offset = rng.below(text.len() + 1);
+                    while !text.is_char_boundary(offset) {
+                        offset -= 1;
+                    }
+                    offset
+                };
+                let lo = boundary(&mut rng, &text);
+                // 대부분은 타자처럼 짧게 지우고, 가끔 길게 지웁니다.
+                let len = if rng.below(8) == 0 { rng.below(40) } else { rng.below(3) };
+                let mut hi = (lo + len).min(text.len());
+                while !text.is_char_boundary(hi) {
+                    hi += 1;
+                }
+                let edit = Edit::new(Span::new(lo as u32, hi as u32), INSERTS[rng.below(INSERTS.len())]);
+                (old, text) = check(&old, &text, &edit);
+            }
+        }
+    }
+}
@@ -0,0 +1,1833 @@
+mod closure;
+mod method;
+
+use std::collections::{HashMap, HashSet};
+
+use rusttc_ast::ast::{
+    self, ArrayLen, AstArena, BinaryOp, Block, Call, Capture, Closure, ClosureKind, ConstDef, Crate, ExprId, FieldDef, FnDef, GenericParam, Generics, Impl, Item, Local,
+    Match, MetaItemKind, NestedMeta, Node, NodeId, NodeKind, Pat, PatKind, Path, SelfKind, StmtKind, StructDef, StructLit, TyKind, UnaryOp, Visibility,
+};
+use rusttc_ast::visit::{self, Visitor};
+use rusttc_ir::consteval::{ConstEvalError, ConstEvaluator, ConstItem, Value};
+use rusttc_ir::ir::convert_to_ir;
+use rusttc_ir::resolve::{Res, Resolutions};
+use rusttc_lexer::{Span, TokenKind};
+
+use crate::infer::{InferCtxt, TypeMismatch};
+use crate::ty::{FloatTy, InferTy, IntTy, Ty};
+use crate::usefulness::{self, IntValue, PatTy};
+use crate::writeback::write_literal_types;
+use crate::{CalleeKind, MethodCallee, TypeError, TypeWarning, TypeckResults};
+
+// 함수 본문을 모두 검사합니다. 트레이트의 기본 메서드는 impl 블록마다 복사된 본문으로 검사합니다.
+// `const`, `static`은 함수보다 먼저 검사하고 계산해 두어 함수 본문의 배열 길이에 씁니다.
+pub(crate) fn check_crate(krate: &Crate, resolutions: &Resolutions) -> (TypeckResults, Vec<TypeError>) {
+    let mut collector = ItemCollector { arena: &krate.arena, items: Vec::new(), array_lens: Vec::new() };
+    collector.visit_crate(krate);
+    let mut tcx = ItemCtxt::collect(&collector.items);
+
+    let mut results = TypeckResults::default();
+    let mut errors = Vec::new();
+    // 타입 표기의 배열 길이는 상수를 쓰므로 상수를 먼저 계산합니다.
+    // 자기 타입에 길이 식이 있는 상수(`const A: [i32; N]`)만 길이를 계산한 다음에 검사합니다.
+    let (later, first): (Vec<&Item>, Vec<&Item>) = collector.items.iter().partition(|item| match item {
+        Item::Const(def) | Item::Static(def) => has_array_len_expr(&def.ty),
+        _ => false,
+    });
+    check_consts(&mut tcx, &krate.arena, resolutions, &first, &mut results, &mut errors);
+    check_array_lens(&mut tcx, &krate.arena, resolutions, &collector.array_lens, &mut results, &mut errors);
+    check_consts(&mut tcx, &krate.arena, resolutions, &later, &mut results, &mut errors);
+    for item in &collector.items {
+        match item {
+            Item::Fn(function) => check_fn(&tcx, &krate.arena, resolutions, function, None, &mut results, &mut errors),
+            Item::Impl(imp) => {
+                for function in &imp.items {
+                    check_fn(&tcx, &krate.arena, resolutions, function, Some(imp), &mut results, &mut errors);
+                }
+            },
+            _ => {},
+        }
+    }
+    (results, errors)
+}
+
+fn check_fn(
+    tcx: &ItemCtxt,
+    arena: &AstArena,
+    resolutions: &Resolutions,
+    function: &FnDef,
+    imp: Option<&Impl>,
+    results: &mut TypeckResults,
+    errors: &mut Vec<TypeError>,
+) {
+    let Some(body) = &function.body else { return };
+    let mut fcx = FnCtxt::new(tcx, arena, resolutions);
+    // impl 블록이 선언된 모듈은 펼치면서 사라지므로, 대상 타입의 모듈에 있는 것으로 봅니다.
+    fcx.module = match imp.map(|imp| &imp.self_ty.kind) {
+        Some(TyKind::Path(path)) => module_of(&path_name(path)).to_string(),
+        Some(_) => String::new(),
+        None => module_of(&function.name).to_string(),
+    };
+    fcx.check_fn(function, body, imp);
+    fcx.finish(results, errors);
+}
+
+// `const`, `static`의 초기값을 검사하고 값을 계산합니다. 다른 상수를 쓰는 초기값은 그 상수를 먼저 계산합니다.
+fn check_consts(
+    tcx: &mut ItemCtxt,
+    arena: &AstArena,
+    resolutions: &Resolutions,
+    items: &[&Item],
+    results: &mut TypeckResults,
+    errors: &mut Vec<TypeError>,
+) {
+    let mut checked: Vec<(&ConstDef, bool)> = Vec::new();
+    for item in items {
+        let (Item::Const(def) | Item::Static(def)) = item else { continue };
+        let is_static = matches!(item, Item::Static(_));
+        let before = errors.len();
+        errors.extend(ConstChecker::check(tcx, arena, resolutions, def.value, if is_static { "static" } else { "constant" }));
+        if errors.len() > before {
+            continue;                                   // `return` 등이 있으면 타입 검사를 할 수 없습니다
+        }
+        let mut fcx = FnCtxt::new(tcx, arena, resolutions);
+        fcx.module = module_of(&def.name).to_string();
+        fcx.check_expr_coercible(def.value, &tcx.lower_ty(&def.ty, &HashMap::new()), ty_span(&def.ty));
+        fcx.finish(results, errors);
+        if errors.len() == before {
+            checked.push((def, is_static));
+        }
+    }
+
+    // 추론한 리터럴 타입을 붙인 사본에서 IR을 만들어 계산합니다. 앞서 검사한 상수도 함께 평가기에 넘깁니다.
+    let mut typed = arena.clone();
+    write_literal_types(&mut typed, &results.literal_types);
+    tcx.const_items.extend(
+        checked
+            .iter()
+            .map(|&(def, is_static)| ConstItem { name: def.name.clone(), init: convert_to_ir(&typed, &[def.value]).remove(0), is_static }),
+    );
+
+    let mut values = HashMap::new();
+    let mut cycles = HashSet::new();
+    let mut evaluator = ConstEvaluator::new(&tcx.const_items);
+    for &(def, is_static) in &checked {
+        let span = arena[def.value].span;
+        match evaluator.eval_const(&def.name) {
+            Ok(value) => {
+                values.insert(def.name.clone(), value);
+            },
+            // 실패한 상수를 쓰는 상수는 따로 보고하지 않습니다.
+            Err(ConstEvalError::Erroneous(_)) => {},
+            Err(error @ ConstEvalError::Cycle(_)) => {
+                if cycles.insert(error.to_string()) {
+                    errors.push(TypeError { message: error.to_string(), span, expected_span: None });
+                }
+            },
+            Err(error) => {
+                let message = match is_static {
+                    true => format!("could not evaluate static initializer: {}", error),
+                    false => format!("evaluation of constant value failed: {}", error),
+                };
+                errors.push(TypeError { message, span, expected_span: None });
+            },
+        }
+    }
+    drop(evaluator);
+    tcx.const_values.extend(values);
+}
+
+// 타입 표기 안의 배열 길이 식(`[T; N]`)을 검사하고 계산합니다. 안쪽 타입의 길이가 먼저 옵니다.
+// 계산하지 못한 길이의 타입은 오류 타입이 되어 같은 오류를 되풀이하지 않습니다.
+fn check_array_lens(
+    tcx: &mut ItemCtxt,
+    arena: &AstArena,
+    resolutions: &Resolutions,
+    lens: &[ExprId],
+    results: &mut TypeckResults,
+    errors: &mut Vec<TypeError>,
+) {
+    for &len in lens {
+        let mut fcx = FnCtxt::new(tcx, arena, resolutions);
+        fcx.check_expr_coercible(len, &Ty::usize(), None);
+        let value = fcx.eval_array_len(len);
+        fcx.finish(results, errors);
+        if let Some(value) = value {
+            tcx.array_lens.insert(len, value);
+            results.array_lens.insert(len, value);
+        }
+    }
+}
+
+// 타입 표기에 아직 계산하지 않은 배열 길이가 있는지 확인합니다.
+fn has_array_len_expr(ty: &ast::Ty) -> bool {
+    match &ty.kind {
+        TyKind::Path(path) => path.segments.iter().any(|segment| segment.args.iter().chain(segment.output.as_deref()).any(has_array_len_expr)),
+        TyKind::Ref { ty, .. } | TyKind::Slice(ty) => has_array_len_expr(ty),
+        TyKind::Array(ty, len) => matches!(len, ArrayLen::Expr(_)) || has_array_len_expr(ty),
+        TyKind::Unit => false,
+        TyKind::Tuple(tys) => tys.iter().any(has_array_len_expr),
+    }
+}
+
+// 컴파일 중에 계산하는 식(`const`, `static`의 초기값, 배열 길이)에 쓸 수 없는 것을 찾습니다.
+// 함수 호출은 `panic!`만 되고, 바깥 함수의 지역 변수와 (`static`이 아니면) `static`은 쓸 수 없습니다.
+struct ConstChecker<'a> {
+    tcx: &'a ItemCtxt,
+    arena: &'a AstArena,
+    resolutions: &'a Resolutions,
+    kind: &'static str,             // "constant", "static" (오류 메시지에 씁니다)
+    span: Span,                     // 검사하는 식 전체의 위치 (이 안에서 바인딩한 지역 변수는 쓸 수 있습니다)
+    errors: Vec<TypeError>,
+}
+
+impl<'a> ConstChecker<'a> {
+    fn check(tcx: &'a ItemCtxt, arena: &'a AstArena, resolutions: &'a Resolutions, id: ExprId, kind: &'static str) -> Vec<TypeError> {
+        let span = arena[id].span;
+        let mut checker = ConstChecker { tcx, arena, resolutions, kind, span, errors: Vec::new() };
+        checker.visit_expr(&arena[id]);
+        checker.errors
+    }
+
+    fn error(&mut self, message: String, span: Span) {
+        self.errors.push(TypeError { message, span, expected_span: None });
+    }
+}
+
+impl<'a> Visitor<'a> for ConstChecker<'a> {
+    fn arena(&self) -> &'a AstArena {
+        self.arena
+    }
+
+    // 블록 안의 아이템은 따로 검사합니다.
+    fn visit_item(&mut self, _item: &'a Item) {}
+
+    fn visit_expr(&mut self, node: &'a Node) {
+        let keyword = if self.kind == "static" { "static" } else { "const" };
+        match &node.kind {
+            NodeKind::Ident(name) => match self.resolutions.uses.get(&node.id) {
+                Some(Res::Local(id)) if !(self.span.lo..self.span.hi).contains(&self.resolutions.bindings[id].span.lo) => {
+                    self.error("attempt to use a non-constant value in a constant".to_string(), node.span);
+                },
+                Some(Res::Item(_)) if self.kind != "static" && self.tcx.consts.get(name).is_some_and(|&(_, is_static)| is_static) => {
+                    self.error("constants cannot refer to statics".to_string(), node.span);
+                },
+                _ => {},
+            },
+            NodeKind::Call(call) => {
+                let callee = match &self.arena[call.callee].kind {
+                    NodeKind::Ident(name) => name.clone(),
+                    NodeKind::Path(path) => path.to_string(),
+                    _ => String::new(),
+                };
+                if callee != "std::panicking::panic" {
+                    let message = format!("cannot call non-const fn `{}` in {}s", callee, self.kind);
+                    self.error(message, node.span);
+                }
+            },
+            NodeKind::Closure(_) => self.error(format!("closures are not allowed in {}s", self.kind), node.span),
+            NodeKind::Return(_) => self.error("return statement outside of function body".to_string(), node.span),
+            NodeKind::ForLoop(_) => self.error(format!("`for` is not allowed in a `{}`", keyword), node.span),
+            _ => {},
+        }
+        visit::walk_expr(self, node);
+    }
+}
+
+// 블록 안의 아이템까지 포함해 크레이트의 모든 아이템을 모읍니다.
+struct ItemCollector<'a> {
+    arena: &'a AstArena,
+    items: Vec<&'a Item>,
+    array_lens: Vec<ExprId>,    // 타입 표기 안의 배열 길이 식 (안쪽 타입의 것이 먼저)
+}
+
+impl<'a> Visitor<'a> for ItemCollector<'a> {
+    fn arena(&self) -> &'a AstArena {
+        self.arena
+    }
+
+    fn visit_item(&mut self, item: &'a Item) {
+        self.items.push(item);
+        visit::walk_item(self, item);
+    }
+
+    fn visit_ty(&mut self, ty: &'a ast::Ty) {
+        visit::walk_ty(self, ty);
+        if let TyKind::Array(_, ArrayLen::Expr(len)) = ty.kind {
+            self.array_lens.push(len);
+        }
+    }
+}
+
+// 함수 시그니처입니다. 타입은 호출할 때마다 `FnCtxt::instantiate`로 새로 만듭니다.
+#[derive(Clone)]
+struct FnInfo {
+    vis: Visibility,                // 가시성 (고유 impl의 비공개 함수는 대상 타입의 모듈 밖에서 부를 수 없습니다)
+    self_param: Option<SelfKind>,   // 리시버 (첫 번째 인자)
+    self_ty: Option<ast::Ty>,       // 고유 impl의 대상 타입 (트레이트 메서드는 호출 경로에서 정해집니다)
+    impl_generics: Vec<String>,     // impl의 타입 매개변수
+    generics: Vec<GenericParam>,    // 함수 자신의 타입 매개변수와 바운드
+    params: Vec<ast::Ty>,           // 매개변수 타입
+    ret: Option<ast::Ty>,           // 반환 타입 (없으면 `()`)
+}
+
+impl FnInfo {
+    fn new(function: &FnDef, self_ty: Option<&ast::Ty>, impl_generics: &Generics) -> Self {
+        FnInfo {
+            vis: function.vis,
+            self_param: function.self_param,
+            self_ty: self_ty.cloned(),
+            impl_generics: impl_generics.params.iter().map(|param| param.name.clone()).collect(),
+            generics: function.generics.params.clone(),
+            params: function.params.iter().map(|param| param.ty.clone()).collect(),
+            ret: function.ret.clone(),
+        }
+    }
+}
+
+// 구조체의 타입 매개변수와 필드입니다.
+struct StructInfo {
+    generics: Vec<String>,
+    fields: Vec<FieldDef>,
+}
+
+// 크레이트 전체의 시그니처입니다.
+#[derive(Default)]
+struct ItemCtxt {
+    structs: HashMap<String, StructInfo>,               // 구조체 이름 → 필드
+    fns: HashMap<String, FnInfo>,                       // 함수 이름, 고유 impl의 `Type::name` → 시그니처
+    traits: HashMap<String, HashMap<String, FnInfo>>,   // 트레이트 이름 → 메서드 선언
+    trait_impls: HashSet<(String, String)>,             // 구현된 (타입 이름, 트레이트)
+    consts: HashMap<String, (ast::Ty, bool)>,           // 상수 이름 → 타입과 `static`인지
+    const_items: Vec<ConstItem>,                        // 검사를 통과한 상수의 초기값
+    const_values: HashMap<String, Value>,               // 계산한 상수 값
+    array_lens: HashMap<ExprId, usize>,                 // 타입 표기 안의 배열 길이 식 → 계산한 길이
+}
+
+impl ItemCtxt {
+    fn collect(items: &[&Item]) -> Self {
+        let mut tcx = ItemCtxt::default();
+        for item in items {
+            match item {
+                Item::Fn(function) => {
+                    tcx.fns.insert(function.name.clone(), FnInfo::new(function, None, &Generics::default()));
+                },
+                Item::Struct(def) => {
+                    let info = StructInfo {
+                        generics: def.generics.params.iter().map(|param| param.name.clone()).collect(),
+                        fields: def.fields.clone(),
+                    };
+                    tcx.structs.insert(def.name.clone(), info);
+                    for trait_name in derived_traits(def) {
+                        tcx.trait_impls.insert((def.name.clone(), trait_name));
+                    }
+                },
+                Item::Impl(imp) => match &imp.of_trait {
+                    // 트레이트 구현은 기본 타입, 튜플, 참조에도 있을 수 있으므로 대상 타입의 모양으로 찾습니다.
+                    Some(trait_path) => {
+                        let subst = imp.generics.params.iter().map(|param| (param.name.clone(), Ty::Param(param.name.clone()))).collect();
+                        if let Some(key) = method::ty_key(&tcx.lower_ty(&imp.self_ty, &subst)) {
+                            tcx.trait_impls.insert((key, path_name(trait_path)));
+                        }
+                    },
+                    None => {
+                        let TyKind::Path(path) = &imp.self_ty.kind else { continue };
+                        let name = path_name(path);
+                        for function in &imp.items {
+                            let info = FnInfo::new(function, Some(&imp.self_ty), &imp.generics);
+                            tcx.fns.insert(format!("{}::{}", name, function.name), info);
+                        }
+                    },
+                },
+                Item::Trait(def) => {
+                    let decls = def
+                        .items
+                        .iter()
+                        .map(|function| (function.name.clone(), FnInfo::new(function, None, &Generics::default())))
+                        .collect();
+                    tcx.traits.insert(def.name.clone(), decls);
+                },
+                Item::Const(def) => {
+                    tcx.consts.insert(def.name.clone(), (def.ty.clone(), false));
+                },
+                Item::Static(def) => {
+                    tcx.consts.insert(def.name.clone(), (def.ty.clone(), true));
+                },
+                Item::Mod(_) | Item::Use(_) => {},
+            }
+        }
+        tcx
+    }
+
+    // 타입 표기를 타입으로 바꿉니다. `subst`에 있는 이름(타입 매개변수, `Self`)은 그 값으로 바꿉니다.
+    fn lower_ty(&self, ty: &ast::Ty, subst: &HashMap<String, Ty>) -> Ty {
+        match &ty.kind {
+            TyKind::Path(path) => {
+                let name = path_name(path);
+                let args = path.segments.last().map_or(&[][..], |segment| &segment.args);
+                let args: Vec<Ty> = args.iter().map(|arg| self.lower_ty(arg, subst)).collect();
+                if let Some(ty) = subst.get(&name).filter(|_| args.is_empty()) {
+                    return ty.clone();
+                }
+                match name.as_str() {
+                    "bool" => Ty::Bool,
+                    "char" => Ty::Char,
+                    "str" => Ty::Str,
+                    _ if name.contains("{closure#") => Ty::Closure(name),
+                    _ => match (IntTy::from_name(&name), FloatTy::from_name(&name)) {
+                        (Some(int), _) => Ty::Int(int),
+                        (_, Some(float)) => Ty::Float(float),
+                        _ => Ty::Adt(name, args),
+                    },
+                }
+            },
+            TyKind::Ref { mutable, ty } => Ty::ref_to(*mutable, self.lower_ty(ty, subst)),
+            TyKind::Unit => Ty::unit(),
+            TyKind::Array(ty, len) => {
+                let len = match len {
+                    ArrayLen::Known(len) => Some(*len),
+                    ArrayLen::Expr(id) => self.array_lens.get(id).copied(),
+                };
+                len.map_or(Ty::Error, |len| Ty::Array(Box::new(self.lower_ty(ty, subst)), len))
+            },
+            TyKind::Slice(ty) => Ty::Slice(Box::new(self.lower_ty(ty, subst))),
+            TyKind::Tuple(tys) => Ty::Tuple(tys.iter().map(|ty| self.lower_ty(ty, subst)).collect()),
+        }
+    }
+
+    // `ty`가 `trait_name`을 구현하는지 확인합니다. 기본 타입은 비교 트레이트를 모두 구현합니다.
+    fn implements(&self, ty: &Ty, trait_name: &str) -> bool {
+        match ty {
+            Ty::Adt(name, _) => self.trait_impls.contains(&(name.clone(), trait_name.to_string())),
+            _ => true,
+        }
+    }
+}
+
+// 전체 이름(`shapes::Point`)의 아이템이 선언된 모듈의 경로입니다. 루트는 빈 문자열입니다.
+fn module_of(name: &str) -> &str {
+    name.rsplit_once("::").map_or("", |(module, _)| module)
+}
+
+// `#[derive(Copy, Clone)]`로 구현한 트레이트의 이름입니다.
+fn derived_traits(def: &StructDef) -> Vec<String> {
+    def.attrs
+        .iter()
+        .filter(|attr| attr.has_name("derive"))
+        .flat_map(|attr| match &attr.meta.kind {
+            MetaItemKind::List(list) => list.as_slice(),
+            _ => &[],
+        })
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(meta) => meta.path.as_ident().map(str::to_string),
+            _ => None,
+        })
+        .collect()
+}
+
+// 경로의 세그먼트 이름을 `::`로 이은 문자열입니다. 제네릭 인자는 뺍니다.
+fn path_name(path: &Path) -> String {
+    path.segments.iter().map(|segment| segment.ident.as_str()).collect::<Vec<_>>().join("::")
+}
+
+// 타입 표기의 위치입니다. 컴파일러가 만든 표기라면 없습니다.
+fn ty_span(ty: &ast::Ty) -> Option<Span> {
+    Some(ty.span).filter(|span| !span.is_dummy())
+}
+
+// 호출 하나에 맞게 타입 매개변수를 채운 시그니처입니다.
+struct Signature {
+    inputs: Vec<(Ty, Option<Span>)>,    // 인자 타입과 그 타입을 적은 위치
+    output: Ty,
+    bounds: Vec<(Ty, Path)>,            // 타입 인자와 그 트레이트 바운드
+    subst: HashMap<String, Ty>,         // 바운드 안의 타입 매개변수 값
+    impl_args: Vec<Ty>,                 // impl의 타입 인자
+    fn_args: Vec<Ty>,                   // 함수의 타입 인자
+}
+
+impl Signature {
+    // 타입 매개변수도 바운드도 없는 시그니처입니다 (클로저, 함수 값).
+    fn plain(inputs: Vec<Ty>, output: Ty) -> Signature {
+        Signature {
+            inputs: inputs.into_iter().map(|ty| (ty, None)).collect(),
+            output,
+            bounds: Vec::new(),
+            subst: HashMap::new(),
+            impl_args: Vec::new(),
+            fn_args: Vec::new(),
+        }
+    }
+}
+
+// 호출 하나의 타입 인자와 그 타입 매개변수 이름, 매개변수를 선언한 아이템입니다.
+type InferredArgs = Vec<(Ty, String, String)>;
+
+struct FnCtxt<'a> {
+    tcx: &'a ItemCtxt,
+    arena: &'a AstArena,
+    resolutions: &'a Resolutions,
+    infcx: InferCtxt,
+    subst: HashMap<String, Ty>,                 // 타입 표기 안의 `Self`와 타입 매개변수
+    bounds: HashMap<String, Vec<Path>>,         // 타입 매개변수 → 트레이트 바운드
+    locals: HashMap<NodeId, Ty>,                // 바인딩 노드 번호 → 타입 (`self`는 함수의 번호)
+    let_bindings: Vec<(NodeId, String, Span)>,  // 타입을 끝까지 추론해야 하는 `let` 바인딩
+    closures: HashMap<String, (Vec<Ty>, Ty)>,   // 클로저 이름 → 매개변수와 반환 타입
+    returns: Vec<(Ty, Option<Span>)>,           // `return`이 돌아갈 함수나 클로저의 반환 타입
+    loops: Vec<bool>,                           // 바깥 루프들에 `break`가 있었는지
+    node_types: HashMap<NodeId, Ty>,            // 표현식과 바인딩의 타입
+    literals: Vec<(ExprId, Ty)>,                // 숫자 리터럴과 그 타입
+    negated: HashSet<ExprId>,                   // `-` 바로 뒤의 리터럴 (`-128i8`)
+    generic_args: Vec<(ExprId, Vec<Vec<Ty>>)>,  // 호출 경로, 구조체 리터럴의 세그먼트별 타입 인자
+    inferred_args: Vec<(InferredArgs, Span)>,   // 호출마다 끝까지 추론해야 하는 타입 인자
+    obligations: Vec<(Ty, Path, Span)>,         // 함수 검사가 끝날 때 확인할 트레이트 바운드
+    method_calls: HashMap<ExprId, MethodCallee>, // 메서드 호출과 클로저 호출 → 찾은 함수
+    closure_calls: HashMap<ExprId, (Ty, usize)>, // 클로저 값 호출 → 클로저 타입과 벗긴 참조 수
+    closure_exprs: Vec<(ExprId, String)>,       // 본문 검사를 마친 순서(안쪽 먼저)의 클로저
+    closure_captures: HashMap<ExprId, (ClosureKind, closure::Captures)>, // 분석한 클로저의 종류와 캡처
+    matches: Vec<(ExprId, Ty)>,                 // 빠짐없는지 확인할 `match`와 검사할 값의 타입
+    module: String,                             // 검사하는 본문이 있는 모듈의 경로 (루트는 빈 문자열)
+    errors: Vec<TypeError>,
+}
+
+impl<'a> FnCtxt<'a> {
+    fn new(tcx: &'a ItemCtxt, arena: &'a AstArena, resolutions: &'a Resolutions) -> Self {
+        FnCtxt {
+            tcx,
+            arena,
+            resolutions,
+            infcx: InferCtxt::default(),
+            subst: HashMap::new(),
+            bounds: HashMap::new(),
+            locals: HashMap::new(),
+            let_bindings: Vec::new(),
+            closures: HashMap::new(),
+            returns: Vec::new(),
+            loops: Vec::new(),
+            node_types: HashMap::new(),
+            literals: Vec::new(),
+            negated: HashSet::new(),
+            generic_args: Vec::new(),
+            inferred_args: Vec::new(),
+            obligations: Vec::new(),
+            method_calls: HashMap::new(),
+            closure_calls: HashMap::new(),
+            closure_exprs: Vec::new(),
+            closure_captures: HashMap::new(),
+            matches: Vec::new(),
+            module: String::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn check_fn(&mut self, function: &FnDef, body: &Block, imp: Option<&Impl>) {
+        let generics = imp.map(|imp| &imp.generics.params[..]).unwrap_or_default().iter().chain(&function.generics.params);
+        for param in generics {
+            self.subst.insert(param.name.clone(), Ty::Param(param.name.clone()));
+            self.bounds.insert(param.name.clone(), param.bounds.clone());
+        }
+        if let Some(imp) = imp {
+            let self_ty = self.tcx.lower_ty(&imp.self_ty, &self.subst);
+            self.subst.insert("Self".to_string(), self_ty);
+        }
+
+        if let (Some(kind), Some(self_ty)) = (function.self_param, self.subst.get("Self")) {
+            let ty = receiver_ty(kind, self_ty.clone());
+            self.node_types.insert(function.id, ty.clone());
+            self.locals.insert(function.id, ty);
+        }
+        for param in &function.params {
+            let ty = self.tcx.lower_ty(&param.ty, &self.subst);
+            self.bind_pat(&param.pat, ty);
+        }
+
+        let ret = function.ret.as_ref().map_or(Ty::unit(), |ret| self.tcx.lower_ty(ret, &self.subst));
+        let ret_span = function.ret.as_ref().and_then(ty_span);
+        self.returns.push((ret.clone(), ret_span));
+        let ty = self.check_block(body);
+        let span = self.tail_span(body).unwrap_or(function.span);
+        self.demand_coerce(&ty, &ret, span, ret_span);
+    }
+
+    // 남은 리터럴 변수를 `i32`, `f64`로 정하고, 타입을 알 수 없는 바인딩과 타입 인자, 범위를 벗어난 리터럴을 보고한 뒤 결과를 모읍니다.
+    // 클로저의 캡처와 종류는 타입이 모두 정해진 뒤에 분석하고, 미뤄 둔 트레이트 바운드를 그 종류로 확인합니다.
+    fn finish(mut self, results: &mut TypeckResults, errors: &mut Vec<TypeError>) {
+        self.infcx.default_literal_vars();
+        for (id, name, span) in std::mem::take(&mut self.let_bindings) {
+            if has_vars(&self.infcx.resolve(&self.locals[&id])) {
+                self.error(format!("type annotations needed: consider giving `{}` an explicit type", name), span, None);
+            }
+        }
+        for (args, span) in std::mem::take(&mut self.inferred_args) {
+            if let Some((_, param, owner)) = args.iter().find(|(ty, ..)| has_vars(&self.infcx.resolve(ty))) {
+                let message = format!("type annotations needed: cannot infer type of the type parameter `{}` declared on `{}`", param, owner);
+                self.error(message, span, None);
+            }
+        }
+
+        let kinds = self.analyze_closures();
+        // 클로저 값 호출 `f(x)`는 클로저의 종류에 맞는 트레이트 메서드 `<F as Fn(i32)>::call(&f, x)`를 부릅니다.
+        for (id, (ty, derefs)) in std::mem::take(&mut self.closure_calls) {
+            let self_ty = self.resolve(&ty);
+            let kind = match &self_ty {
+                Ty::Closure(name) => kinds.get(name).copied(),
+                Ty::Param(name) => self.fn_bound_kind(name),
+                _ => None,
+            };
+            let Some(kind) = kind else { continue };
+            let (name, self_param) = match kind {
+                ClosureKind::Fn => ("call", SelfKind::Ref),
+                ClosureKind::FnMut => ("call_mut", SelfKind::RefMut),
+                ClosureKind::FnOnce => ("call_once", SelfKind::Value { mutable: false }),
+            };
+            let kind = CalleeKind::Trait { self_ty, trait_name: kind.trait_name().to_string(), name: name.to_string(), fn_args: Vec::new() };
+            self.method_calls.insert(id, MethodCallee { kind, self_param, derefs });
+        }
+        for (ty, bound, span) in std::mem::take(&mut self.obligations) {
+            let ty = self.resolve(&ty);
+            self.check_bound(&ty, &bound, span, &kinds);
+        }
+        for (id, mut callee) in std::mem::take(&mut self.method_calls) {
+            match &mut callee.kind {
+                CalleeKind::Inherent { impl_args, fn_args, .. } => {
+                    impl_args.iter_mut().chain(fn_args.iter_mut()).for_each(|ty| *ty = self.infcx.resolve(ty));
+                },
+                CalleeKind::Trait { self_ty, fn_args, .. } => {
+                    std::iter::once(self_ty).chain(fn_args.iter_mut()).for_each(|ty| *ty = self.infcx.resolve(ty));
+                },
+                CalleeKind::Borrow => {},
+            }
+            results.method_calls.insert(id, callee);
+        }
+        for (id, (kind, captures)) in std::mem::take(&mut self.closure_captures) {
+            let captures = captures.into_iter().map(|(name, _, mode)| Capture { name, mode }).collect();
+            results.closures.insert(id, (kind, captures));
+        }
+        for (id, ty) in std::mem::take(&mut self.literals) {
+            let ty = self.infcx.resolve(&ty);
+            self.check_literal_range(id, &ty);
+            results.literal_types.insert(id, ty);
+        }
+        // 패턴의 타입이 틀렸다면 빠짐없는지 따질 수 없으므로, 오류가 없을 때만 확인합니다.
+        if self.errors.is_empty() {
+            for (id, ty) in std::mem::take(&mut self.matches) {
+                self.check_exhaustive(id, &ty, &mut results.warnings);
+            }
+        }
+        for (id, ty) in &self.node_types {
+            let ty = self.infcx.resolve(ty);
+            results.node_types.insert(*id, if has_vars(&ty) { Ty::Error } else { ty });
+        }
+        for (id, segments) in &self.generic_args {
+            let segments = segments.iter().map(|args| args.iter().map(|ty| self.infcx.resolve(ty)).collect()).collect();
+            results.generic_args.insert(*id, segments);
+        }
+        errors.append(&mut self.errors);
+    }
+
+    // 배열 길이 `[0; N * 2]`를 계산합니다. 계산할 수 없으면 오류를 보고하고 `None`을 돌려줍니다.
+    // 길이 식의 리터럴 타입은 아직 함수 전체의 추론이 끝나지 않았으므로 지금까지 정해진 것을 씁니다.
+    fn eval_array_len(&mut self, count: ExprId) -> Option<usize> {
+        let node = &self.arena[count];
+        if let NodeKind::Int(len, _) = node.kind {
+            return Some(len as usize);
+        }
+        let errors = ConstChecker::check(self.tcx, self.arena, self.resolutions, count, "constant");
+        if !errors.is_empty() {
+            self.errors.extend(errors);
+            return None;
+        }
+        let value = match &node.kind {
+            NodeKind::Ident(name) => self.tcx.const_values.get(name).cloned().ok_or_else(|| ConstEvalError::Erroneous(name.clone())),
+            _ => {
+                let literals = self.literals.iter().map(|(id, ty)| (*id, self.infcx.resolve(ty))).collect();
+                let mut typed = self.arena.clone();
+                write_literal_types(&mut typed, &literals);
+                let init = convert_to_ir(&typed, &[count]).remove(0);
+                ConstEvaluator::new(&self.tcx.const_items).eval(&init)
+            },
+        };
+        match value {
+            Ok(value) => value.as_usize().map(|len| len as usize),
+            Err(ConstEvalError::Erroneous(_)) => None,
+            Err(error) => {
+                self.error(format!("evaluation of constant value failed: {}", error), node.span, None);
+                None
+            },
+        }
+    }
+
+    // 리터럴 값이 그 타입에 들어가는지 확인합니다. `-` 바로 뒤의 정수는 절댓값으로 봅니다.
+    fn check_literal_range(&mut self, id: ExprId, ty: &Ty) {
+        let node = &self.arena[id];
+        let negated = self.negated.contains(&id);
+        let (fits, range) = match (&node.kind, ty) {
+            (NodeKind::Int(value, _), Ty::Int(int)) => {
+                let limit = if negated { int.min_abs() } else { int.max() };
+                let min = if int.is_signed() { format!("-{}", int.min_abs()) } else { "0".to_string() };
+                (*value <= limit, format!("`{}..={}`", min, int.max()))
+            },
+            (NodeKind::Float(value, _), Ty::Float(FloatTy::F32)) => (*value <= f32::MAX as f64, "`-3.40282347e38..=3.40282347e38`".to_string()),
+            (NodeKind::Float(value, _), Ty::Float(FloatTy::F64)) => (value.is_finite(), "`-1.7976931348623157e308..=1.7976931348623157e308`".to_string()),
+            _ => return,
+        };
+        if !fits {
+            let literal = &self.source_literal(node);
+            let message = format!(
+                "literal out of range for `{}`: the literal `{}{}` does not fit into the type `{}` whose range is {}",
+                ty,
+                if negated { "-" } else { "" },
+                literal,
+                ty,
+                range,
+            );
+            self.error(message, node.span, None);
+        }
+    }
+
+    // 오류 메시지에 쓸 리터럴의 소스 텍스트입니다. 접미사는 뗍니다.
+    // 소스를 알 수 없거나 매크로가 만든 리터럴이면 값을 출력합니다. `1e400`처럼 `inf`가 된 값도 그대로 보이도록 소스가 우선입니다.
+    fn source_literal(&self, node: &Node) -> String {
+        let is_number = |text: &str| text.starts_with(|c: char| c.is_ascii_digit()) && text.chars().all(|c| c.is_ascii_alphanumeric() || "._+-".contains(c));
+        if let Some(text) = node.span.snippet().filter(|text| is_number(text)) {
+            let suffix = match node.kind {
+                NodeKind::Int(_, Some(ty)) => ty.name(),
+                NodeKind::Float(_, Some(ty)) => ty.name(),
+                _ => "",
+            };
+            return text.strip_suffix(suffix).unwrap_or(&text).trim_end_matches('_').to_string();
+        }
+        match node.kind {
+            NodeKind::Int(value, _) => value.to_string(),
+            NodeKind::Float(value, _) => format!("{:?}", value),
+            _ => String::new(),
+        }
+    }
+
+    fn error(&mut self, message: String, span: Span, expected_span: Option<Span>) {
+        self.errors.push(TypeError { message, span, expected_span });
+    }
+
+    fn resolve(&self, ty: &Ty) -> Ty {
+        self.infcx.resolve(ty)
+    }
+
+    fn is_never(&self, ty: &Ty) -> bool {
+        self.infcx.shallow_resolve(ty) == Ty::Never
+    }
+
+    // 값이 `expected` 자리에 들어갈 수 있도록 맞춥니다. 강제 변환으로 `!`는 어떤 타입이든 되고,
+    // `&mut T`는 `&T`로, `&[T; N]`은 `&[T]`로 바뀔 수 있습니다.
+    fn coerce(&mut self, found: &Ty, expected: &Ty) -> Result<(), TypeMismatch> {
+        let (found_ty, expected_ty) = (self.infcx.shallow_resolve(found), self.infcx.shallow_resolve(expected));
+        match (&found_ty, &expected_ty) {
+            (Ty::Never, _) => Ok(()),
+            (Ty::Ref(found_mut, found_inner), Ty::Ref(expected_mut, expected_inner)) if *found_mut || !*expected_mut => {
+                let found_inner = self.infcx.shallow_resolve(found_inner);
+                match (&found_inner, &self.infcx.shallow_resolve(expected_inner)) {
+                    (Ty::Array(elem, _), Ty::Slice(expected_elem)) => self.infcx.unify(elem, expected_elem),
+                    _ => self.infcx.unify(&found_inner, expected_inner),
+                }
+            },
+            _ => self.infcx.unify(found, expected),
+        }
+    }
+
+    fn demand_coerce(&mut self, found: &Ty, expected: &Ty, span: Span, expected_span: Option<Span>) -> bool {
+        if self.coerce(found, expected).is_ok() {
+            return true;
+        }
+        self.report_mismatch("mismatched types", expected, found, span, expected_span);
+        false
+    }
+
+    fn report_mismatch(&mut self, what: &str, expected: &Ty, found: &Ty, span: Span, expected_span: Option<Span>) {
+        let (expected, found) = (self.resolve(expected), self.resolve(found));
+        self.error(format!("{}: expected `{}`, found `{}`", what, expected, found), span, expected_span);
+    }
+
+    fn bind_pat(&mut self, pat: &Pat, ty: Ty) {
+        if let PatKind::Ident { .. } = pat.kind {
+            self.node_types.insert(pat.id, ty.clone());
+            self.locals.insert(pat.id, ty);
+        }
+    }
+
+    // 블록의 값을 내는 마지막 식의 위치입니다. 없으면 마지막 문장의 위치입니다.
+    fn tail_span(&self, block: &Block) -> Option<Span> {
+        let &last = block.stmts.last()?;
+        Some(match &self.arena[last].kind {
+            StmtKind::Expr(expr) => self.arena[*expr].span,
+            _ => self.arena[last].span,
+        })
+    }
+
+    fn check_block(&mut self, block: &Block) -> Ty {
+        let mut diverges = false;
+        let mut tail = None;
+        for (i, &id) in block.stmts.iter().enumerate() {
+            match &self.arena[id].kind {
+                StmtKind::Let(local) => diverges |= self.check_local(local),
+                // 블록 안의 아이템은 따로 검사합니다.
+                StmtKind::Item(_) => {},
+                StmtKind::Expr(expr) if i + 1 == block.stmts.len() => tail = Some(self.check_expr(*expr)),
+                StmtKind::Expr(expr) | StmtKind::Semi(expr) => {
+                    let ty = self.check_expr(*expr);
+                    diverges |= self.is_never(&ty);
+                },
+            }
+        }
+        match tail {
+            Some(ty) => ty,
+            None if diverges => Ty::Never,
+            None => Ty::unit(),
+        }
+    }
+
+    // `let` 문을 검사하고, 초기값이 값을 내지 않는지(`let x = return;`) 돌려줍니다.
+    fn check_local(&mut self, local: &Local) -> bool {
+        let annotation = local.ty.as_ref();
+        let ty = match annotation {
+            Some(ty) => self.tcx.lower_ty(ty, &self.subst),
+            None => self.infcx.new_var(),
+        };
+        let mut diverges = false;
+        if let Some(init) = local.init {
+            let init_ty = self.check_expr(init);
+            diverges = self.is_never(&init_ty);
+            self.demand_coerce(&init_ty, &ty, self.arena[init].span, annotation.and_then(ty_span));
+        }
+        if let PatKind::Ident { name, .. } = &local.pat.kind {
+            self.let_bindings.push((local.pat.id, name.clone(), local.pat.span));
+        }
+        self.bind_pat(&local.pat, ty);
+        diverges
+    }
+
+    fn check_expr_coercible(&mut self, id: ExprId, expected: &Ty, expected_span: Option<Span>) -> Ty {
+        let ty = self.check_expr(id);
+        self.demand_coerce(&ty, expected, self.arena[id].span, expected_span);
+        ty
+    }
+
+    fn check_expr(&mut self, id: ExprId) -> Ty {
+        let node = &self.arena[id];
+        let ty = self.check_node(id, node);
+        if node.id != NodeId::DUMMY {
+            self.node_types.insert(node.id, ty.clone());
+        }
+        ty
+    }
+
+    fn check_node(&mut self, id: ExprId, node: &Node) -> Ty {
+        let arena = self.arena;
+        match &node.kind {
+            NodeKind::Int(_, suffix) => {
+                let ty = suffix.map_or_else(|| self.infcx.new_int_var(), Ty::Int);
+                self.literals.push((id, ty.clone()));
+                ty
+            },
+            NodeKind::Float(_, suffix) => {
+                let ty = suffix.map_or_else(|| self.infcx.new_float_var(), Ty::Float);
+                self.literals.push((id, ty.clone()));
+                ty
+            },
+            NodeKind::Char(_) => Ty::Char,
+            NodeKind::Bool(_) => Ty::Bool,
+            NodeKind::Str(_) => Ty::ref_to(false, Ty::Str),
+            NodeKind::Ident(name) => match self.resolutions.uses.get(&node.id) {
+                Some(Res::Local(id)) => self.locals.get(id).cloned().unwrap_or(Ty::Error),
+                Some(Res::Item(_)) if self.tcx.structs.contains_key(name) => Ty::Adt(name.clone(), Vec::new()),
+                Some(Res::Item(_)) if self.tcx.fns.contains_key(name) => self.fn_value(id),
+                Some(Res::Item(_)) => self.tcx.consts.get(name).map_or(Ty::Error, |(ty, _)| self.tcx.lower_ty(ty, &HashMap::new())),
+                _ => Ty::Error,
+            },
+            NodeKind::Path(_) => self.fn_value(id),
+            NodeKind::QualifiedPath(..) => Ty::Error,
+            NodeKind::BinaryOp(binary_op) => self.check_binary(binary_op),
+            NodeKind::Unary(unary_op) => self.check_unary(unary_op, node.span),
+            NodeKind::Ref { mutable, expr } => {
+                let ty = self.check_expr(*expr);
+                Ty::ref_to(*mutable, ty)
+            },
+            NodeKind::Call(call) => self.check_call(id, call, node.span),
+            NodeKind::MethodCall(call) => self.check_method_call(id, call, node.span),
+            NodeKind::Field(base, field) => self.check_field(*base, field, node.span),
+            NodeKind::StructLit(lit) => self.check_struct_lit(id, lit, node.span),
+            NodeKind::Assign(assign) => {
+                let target = self.check_expr(assign.target);
+                let value = self.check_expr(assign.value);
+                let (target_span, value_span) = (arena[assign.target].span, arena[assign.value].span);
+                match &assign.operator {
+                    None => {
+                        self.demand_coerce(&value, &target, value_span, Some(target_span));
+                    },
+                    Some(operator) => {
+                        let kind = binary_kind(&operator.kind);
+                        self.check_arith(&kind, &operator.text, &target, &value, target_span, value_span, "binary assignment operation");
+                    },
+                }
+                Ty::unit()
+            },
+            NodeKind::Block(block) => self.check_block(block),
+            NodeKind::If(if_expr) => {
+                self.check_expr_coercible(if_expr.cond, &Ty::Bool, None);
+                let then_ty = self.check_block(&if_expr.then);
+                let then_span = self.tail_span(&if_expr.then).unwrap_or(node.span);
+                match if_expr.els {
+                    Some(els) => {
+                        let else_ty = self.check_expr(els);
+                        let else_span = match &arena[els].kind {
+                            NodeKind::Block(block) => self.tail_span(block),
+                            _ => None,
+                        };
+                        self.join_branches("`if` and `else` have incompatible types", then_ty, then_span, else_ty, else_span.unwrap_or(arena[els].span))
+                    },
+                    None => {
+                        if self.coerce(&then_ty, &Ty::unit()).is_err() {
+                            self.report_mismatch("`if` may be missing an `else` clause", &Ty::unit(), &then_ty, then_span, None);
+                        }
+                        Ty::unit()
+                    },
+                }
+            },
+            NodeKind::While(while_expr) => {
+                self.check_expr_coercible(while_expr.cond, &Ty::Bool, None);
+                self.check_loop_body(&while_expr.body);
+                Ty::unit()
+            },
+            NodeKind::Loop(body) => match self.check_loop_body(body) {
+                true => Ty::unit(),
+                false => Ty::Never,
+            },
+            NodeKind::ForLoop(for_loop) => {
+                let iter_ty = self.check_expr(for_loop.iter);
+                let item_ty = self.iter_item(&iter_ty, arena[for_loop.iter].span);
+                self.bind_pat(&for_loop.pat, item_ty);
+                self.check_loop_body(&for_loop.body);
+                Ty::unit()
+            },
+            NodeKind::Break => {
+                if let Some(broke) = self.loops.last_mut() {
+                    *broke = true;
+                }
+                Ty::Never
+            },
+            NodeKind::Continue => Ty::Never,
+            NodeKind::Return(value) => {
+                let (ret, ret_span) = self.returns.last().cloned().expect("`return` outside of a function");
+                match value {
+                    Some(value) => {
+                        self.check_expr_coercible(*value, &ret, ret_span);
+                    },
+                    None => {
+                        self.demand_coerce(&Ty::unit(), &ret, node.span, ret_span);
+                    },
+                }
+                Ty::Never
+            },
+            NodeKind::Closure(closure) => self.check_closure(id, closure),
+            NodeKind::Match(match_expr) => self.check_match(id, match_expr, node.span),
+            NodeKind::Array(elems) => {
+                let Some((&first, rest)) = elems.split_first() else {
+                    return Ty::Array(Box::new(self.infcx.new_var()), 0);
+                };
+                let elem = self.check_expr(first);
+                for &other in rest {
+                    self.check_expr_coercible(other, &elem, Some(arena[first].span));
+                }
+                Ty::Array(Box::new(elem), elems.len())
+            },
+            NodeKind::Repeat(value, count) => {
+                let elem = self.check_expr(*value);
+                self.check_expr_coercible(*count, &Ty::usize(), None);
+                match self.eval_array_len(*count) {
+                    Some(len) => Ty::Array(Box::new(elem), len),
+                    None => Ty::Error,
+                }
+            },
+            NodeKind::Tuple(elems) => Ty::Tuple(elems.iter().map(|&elem| self.check_expr(elem)).collect()),
+            NodeKind::Index(base, index) => self.check_index(*base, *index),
+            NodeKind::Range(range) => {
+                let bounds: Vec<ExprId> = range.start.iter().chain(&range.end).copied().collect();
+                let name = match (&range.start, &range.end, range.inclusive) {
+                    (Some(_), Some(_), false) => "Range",
+                    (Some(_), Some(_), true) => "RangeInclusive",
+                    (Some(_), None, _) => "RangeFrom",
+                    (None, Some(_), false) => "RangeTo",
+                    (None, Some(_), true) => "RangeToInclusive",
+                    (None, None, _) => return Ty::Adt("RangeFull".to_string(), Vec::new()),
+                };
+                let idx = self.check_expr(bounds[0]);
+                if let Some(&end) = bounds.get(1) {
+                    self.check_expr_coercible(end, &idx, Some(arena[bounds[0]].span));
+                }
+                Ty::Adt(name.to_string(), vec![idx])
+            },
+            NodeKind::FormatArgs(format) => {
+                format.args.iter().for_each(|arg| {
+                    self.check_expr(arg.expr);
+                });
+                Ty::Adt("Arguments".to_string(), Vec::new())
+            },
+        }
+    }
+
+    // 두 갈래의 타입을 맞춥니다. 값을 내지 않는 갈래는 다른 갈래의 타입을 따릅니다.
+    fn join_branches(&mut self, what: &str, then_ty: Ty, then_span: Span, else_ty: Ty, else_span: Span) -> Ty {
+        if self.is_never(&then_ty) {
+            return else_ty;
+        }
+        if self.coerce(&else_ty, &then_ty).is_err() {
+            self.report_mismatch(what, &then_ty, &else_ty, else_span, Some(then_span));
+        }
+        then_ty
+    }
+
+    // `match`를 검사합니다. 갈래의 값은 `if`/`else`처럼 맞추고, 갈래가 없으면 값을 내지 않습니다.
+    // 빠짐없는지는 타입이 모두 정해진 뒤 `finish`에서 확인합니다.
+    fn check_match(&mut self, id: ExprId, match_expr: &Match, span: Span) -> Ty {
+        let scrutinee = self.check_expr(match_expr.scrutinee);
+        let (mut ty, mut ty_span) = (Ty::Never, span);
+        for arm in &match_expr.arms {
+            self.check_pat(&arm.pat, &scrutinee);
+            if let Some(guard) = arm.guard {
+                self.check_expr_coercible(guard, &Ty::Bool, None);
+            }
+            let body_ty = self.check_expr(arm.body);
+            let body_span = match &self.arena[arm.body].kind {
+                NodeKind::Block(block) => self.tail_span(block),
+                _ => None,
+            };
+            let body_span = body_span.unwrap_or(self.arena[arm.body].span);
+            let first = self.is_never(&ty);
+            ty = self.join_branches("`match` arms have incompatible types", ty, ty_span, body_ty, body_span);
+            if first {
+                ty_span = body_span;
+            }
+        }
+        self.matches.push((id, scrutinee));
+        ty
+    }
+
+    // 패턴이 `expected` 타입의 값에 맞는지 검사하고 바인딩에 타입을 줍니다. 안쪽 패턴까지 모든 패턴 노드의 타입을 남깁니다.
+    // 패턴에서는 강제 변환이 일어나지 않으므로 타입이 같아야 합니다.
+    fn check_pat(&mut self, pat: &Pat, expected: &Ty) {
+        self.node_types.insert(pat.id, expected.clone());
+        match &pat.kind {
+            PatKind::Ident { .. } => {
+                self.locals.insert(pat.id, expected.clone());
+            },
+            PatKind::Wild => {},
+            PatKind::Lit(expr) => {
+                let ty = self.check_expr(*expr);
+                self.demand_pat(&ty, expected, self.arena[*expr].span);
+            },
+            PatKind::Range(start, end) => {
+                for bound in [*start, *end] {
+                    let ty = self.check_expr(bound);
+                    self.demand_pat(&ty, expected, self.arena[bound].span);
+                }
+                let ty = self.resolve(expected);
+                let numeric = matches!(ty, Ty::Int(_) | Ty::Char | Ty::Float(_) | Ty::Error | Ty::Infer(InferTy::IntVar(_) | InferTy::FloatVar(_)));
+                if !numeric {
+                    self.error("only `char` and numeric types are allowed in range patterns".to_string(), pat.span, None);
+                }
+            },
+            PatKind::Tuple(pats) => {
+                let tys: Vec<Ty> = pats.iter().map(|_| self.infcx.new_var()).collect();
+                let tuple = Ty::Tuple(tys.clone());
+                if !self.demand_pat(&tuple, expected, pat.span) {
+                    pats.iter().for_each(|pat| self.check_pat(pat, &Ty::Error));
+                    return;
+                }
+                for (pat, ty) in pats.iter().zip(&tys) {
+                    self.check_pat(pat, ty);
+                }
+            },
+            PatKind::Struct { path, fields, rest } => self.check_struct_pat(pat, path, fields, *rest, expected),
+            PatKind::Or(alternatives) => {
+                let Some((first, rest)) = alternatives.split_first() else { return };
+                self.check_pat(first, expected);
+                let bindings: HashMap<&str, NodeId> = first
+                    .bindings()
+                    .into_iter()
+                    .filter_map(|pat| match &pat.kind {
+                        PatKind::Ident { name, .. } => Some((name.as_str(), pat.id)),
+                        _ => None,
+                    })
+                    .collect();
+                for alternative in rest {
+                    self.check_pat(alternative, expected);
+                    // 대안마다 같은 이름의 바인딩은 타입이 같아야 합니다. 없는 이름은 해석 단계가 보고했습니다.
+                    for binding in alternative.bindings() {
+                        let PatKind::Ident { name, .. } = &binding.kind else { continue };
+                        let Some(first_id) = bindings.get(name.as_str()) else { continue };
+                        let (first_ty, ty) = (self.locals[first_id].clone(), self.locals[&binding.id].clone());
+                        self.demand_pat(&ty, &first_ty, binding.span);
+                    }
+                }
+            },
+        }
+    }
+
+    fn demand_pat(&mut self, found: &Ty, expected: &Ty, span: Span) -> bool {
+        if self.infcx.unify(found, expected).is_ok() {
+            return true;
+        }
+        self.report_mismatch("mismatched types", expected, found, span, None);
+        false
+    }
+
+    // 구조체 패턴 `Point { x, y: 0, .. }`를 검사합니다. 없는 구조체와 필드는 `method::resolve_methods`가 이미 보고했습니다.
+    fn check_struct_pat(&mut self, pat: &Pat, path: &Path, fields: &[(String, Pat)], rest: bool, expected: &Ty) {
+        let tcx = self.tcx;
+        let info = match path.segments.as_slice() {
+            [segment] => tcx.structs.get(&segment.ident).map(|info| (segment, info)),
+            _ => None,
+        };
+        let Some((segment, info)) = info else {
+            fields.iter().for_each(|(_, pat)| self.check_pat(pat, &Ty::Error));
+            return;
+        };
+
+        self.check_arg_count("struct", &segment.args, info.generics.len(), pat.span);
+        let args = self.lower_args(&segment.args, info.generics.len());
+        let ty = Ty::Adt(segment.ident.clone(), args.clone());
+        if !self.demand_pat(&ty, expected, pat.span) {
+            fields.iter().for_each(|(_, pat)| self.check_pat(pat, &Ty::Error));
+            return;
+        }
+        let subst: HashMap<String, Ty> = info.generics.iter().cloned().zip(args).collect();
+        for (field, field_pat) in fields {
+            match info.fields.iter().find(|def| def.name == *field) {
+                Some(def) => {
+                    self.check_field_privacy(def, &segment.ident, field_pat.span);
+                    self.check_pat(field_pat, &self.tcx.lower_ty(&def.ty, &subst));
+                },
+                None => self.check_pat(field_pat, &Ty::Error),
+            }
+        }
+
+        let missing: Vec<String> =
+            info.fields.iter().filter(|def| !fields.iter().any(|(field, _)| *field == def.name)).map(|def| format!("`{}`", def.name)).collect();
+        if !rest && !missing.is_empty() {
+            let noun = if missing.len() == 1 { "field" } else { "fields" };
+            self.error(format!("pattern does not mention {} {}", noun, missing.join(", ")), pat.span, None);
+        }
+    }
+
+    // `match`의 갈래가 모든 값을 덮는지 확인하고, 앞 갈래에 가려 닿을 수 없는 갈래는 경고합니다.
+    fn check_exhaustive(&mut self, id: ExprId, scrutinee: &Ty, warnings: &mut Vec<TypeWarning>) {
+        let arena = self.arena;
+        let NodeKind::Match(match_expr) = &arena[id].kind else { return };
+        let ty = self.pat_ty(&self.resolve(scrutinee));
+        let mut arms = Vec::new();
+        for arm in &match_expr.arms {
+            let Some(pat) = self.usefulness_pat(&arm.pat) else { return };
+            arms.push(usefulness::Arm { pat, guard: arm.guard.is_some() });
+        }
+        let check = usefulness::check_match(&ty, &arms);
+        for &index in &check.unreachable {
+            warnings.push(TypeWarning { message: "unreachable pattern".to_string(), span: match_expr.arms[index].pat.span });
+        }
+        if let Some(message) = check.message() {
+            self.error(message, arena[match_expr.scrutinee].span, None);
+        }
+    }
+
+    // 검사할 값의 타입을 패턴으로 나눌 수 있는 모양으로 바꿉니다. 끝까지 모르는 정수 리터럴은 `i32`입니다.
+    fn pat_ty(&self, ty: &Ty) -> PatTy {
+        match ty {
+            Ty::Bool => PatTy::Bool,
+            Ty::Int(int) => PatTy::Int(*int),
+            Ty::Infer(InferTy::IntVar(_)) => PatTy::Int(IntTy::I32),
+            Ty::Tuple(tys) => PatTy::Tuple(tys.iter().map(|ty| self.pat_ty(&self.resolve(ty))).collect()),
+            Ty::Adt(name, args) => match self.tcx.structs.get(name) {
+                Some(info) => {
+                    let subst: HashMap<String, Ty> = info.generics.iter().cloned().zip(args.iter().cloned()).collect();
+                    let fields = info.fields.iter().map(|def| (def.name.clone(), self.pat_ty(&self.tcx.lower_ty(&def.ty, &subst)))).collect();
+                    PatTy::Struct { name: name.clone(), fields }
+                },
+                None => PatTy::Opaque,
+            },
+            _ => PatTy::Opaque,
+        }
+    }
+
+    // 패턴을 빠짐없는지 따지는 모양으로 바꿉니다. 범위의 끝이 거꾸로면 오류를 보고하고 `None`을 돌려줍니다.
+    fn usefulness_pat(&mut self, pat: &Pat) -> Option<usefulness::Pat> {
+        Some(match &pat.kind {
+            PatKind::Ident { .. } | PatKind::Wild => usefulness::Pat::Wild,
+            PatKind::Lit(expr) => match (&self.arena[*expr].kind, self.int_value(*expr)) {
+                (NodeKind::Bool(value), _) => usefulness::Pat::Bool(*value),
+                (_, Some(value)) => usefulness::Pat::Range(value, value),
+                _ => usefulness::Pat::Opaque,
+            },
+            PatKind::Range(start, end) => match (self.int_value(*start), self.int_value(*end)) {
+                (Some(lo), Some(hi)) if lo > hi => {
+                    self.error("lower range bound must be less than or equal to upper".to_string(), pat.span, None);
+                    return None;
+                },
+                (Some(lo), Some(hi)) => usefulness::Pat::Range(lo, hi),
+                _ => usefulness::Pat::Opaque,
+            },
+            PatKind::Tuple(pats) => usefulness::Pat::Tuple(pats.iter().map(|pat| self.usefulness_pat(pat)).collect::<Option<_>>()?),
+            PatKind::Struct { fields, .. } => usefulness::Pat::Struct(
+                fields.iter().map(|(name, pat)| Some((name.clone(), self.usefulness_pat(pat)?))).collect::<Option<_>>()?,
+            ),
+            PatKind::Or(pats) => usefulness::Pat::Or(pats.iter().map(|pat| self.usefulness_pat(pat)).collect::<Option<_>>()?),
+        })
+    }
+
+    // 정수 리터럴 패턴(`-1`)의 값입니다.
+    fn int_value(&self, id: ExprId) -> Option<IntValue> {
+        match &self.arena[id].kind {
+            NodeKind::Int(value, _) => Some(IntValue::Unsigned(*value)),
+            NodeKind::Unary(unary_op) if unary_op.operator.kind == TokenKind::Minus => match self.arena[unary_op.operand].kind {
+                NodeKind::Int(value, _) => Some(IntValue::Signed((value as i128).wrapping_neg())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // 루프 본문을 검사하고, 본문에 이 루프를 빠져나가는 `break`가 있는지 돌려줍니다.
+    fn check_loop_body(&mut self, body: &Block) -> bool {
+        self.loops.push(false);
+        let ty = self.check_block(body);
+        let span = self.tail_span(body).unwrap_or(Span::DUMMY);
+        self.demand_coerce(&ty, &Ty::unit(), span, None);
+        self.loops.pop().unwrap()
+    }
+
+    // `for` 루프가 `ty` 값에서 꺼내는 원소의 타입입니다.
+    fn iter_item(&mut self, ty: &Ty, span: Span) -> Ty {
+        let ty = self.resolve(ty);
+        match &ty {
+            Ty::Adt(name, args) if matches!(name.as_str(), "Range" | "RangeInclusive" | "RangeFrom") => args[0].clone(),
+            Ty::Array(elem, _) => (**elem).clone(),
+            Ty::Adt(name, args) if name == "Vec" => args[0].clone(),
+            Ty::Ref(mutable, inner) => match &**inner {
+                Ty::Array(elem, _) | Ty::Slice(elem) => Ty::ref_to(*mutable, (**elem).clone()),
+                Ty::Adt(name, args) if name == "Vec" => Ty::ref_to(*mutable, args[0].clone()),
+                _ => self.not_an_iterator(&ty, span),
+            },
+            Ty::Error | Ty::Infer(_) => Ty::Error,
+            _ => self.not_an_iterator(&ty, span),
+        }
+    }
+
+    fn not_an_iterator(&mut self, ty: &Ty, span: Span) -> Ty {
+        self.error(format!("`{}` is not an iterator", ty), span, None);
+        Ty::Error
+    }
+
+    fn check_closure(&mut self, id: ExprId, closure: &Closure) -> Ty {
+        let params: Vec<Ty> = closure
+            .params
+            .iter()
+            .map(|param| {
+                let ty = match &param.ty {
+                    Some(ty) => self.tcx.lower_ty(ty, &self.subst),
+                    None => self.infcx.new_var(),
+                };
+                self.bind_pat(&param.pat, ty.clone());
+                ty
+            })
+            .collect();
+        let ret = match &closure.ret {
+            Some(ret) => self.tcx.lower_ty(ret, &self.subst),
+            None => self.infcx.new_var(),
+        };
+        let ret_span = closure.ret.as_ref().and_then(ty_span);
+        self.closures.insert(closure.name.clone(), (params, ret.clone()));
+
+        // 클로저 본문의 `return`은 클로저에서 돌아가고, 바깥 루프를 `break`할 수 없습니다.
+        let loops = std::mem::take(&mut self.loops);
+        self.returns.push((ret.clone(), ret_span));
+        self.check_expr_coercible(closure.body, &ret, ret_span);
+        self.returns.pop();
+        self.loops = loops;
+        self.closure_exprs.push((id, closure.name.clone()));
+        Ty::Closure(closure.name.clone())
+    }
+
+    fn check_binary(&mut self, binary_op: &BinaryOp) -> Ty {
+        let left = self.check_expr(binary_op.left);
+        let right = self.check_expr(binary_op.right);
+        let (left_span, right_span) = (self.arena[binary_op.left].span, self.arena[binary_op.right].span);
+        let operator = &binary_op.operator;
+        match operator.kind {
+            TokenKind::AndAnd | TokenKind::OrOr => {
+                self.demand_coerce(&left, &Ty::Bool, left_span, None);
+                self.demand_coerce(&right, &Ty::Bool, right_span, None);
+                Ty::Bool
+            },
+            TokenKind::EqEq | TokenKind::Ne | TokenKind::Lt | TokenKind::Gt | TokenKind::Le | TokenKind::Ge => {
+                let ordering = !matches!(operator.kind, TokenKind::EqEq | TokenKind::Ne);
+                let ty = self.resolve(&left);
+                if self.is_comparable(&ty, ordering) {
+                    self.demand_coerce(&right, &left, right_span, Some(left_span));
+                } else {
+                    self.error(format!("binary operation `{}` cannot be applied to type `{}`", operator.text, ty), left_span, None);
+                }
+                Ty::Bool
+            },
+            _ => self.check_arith(&operator.kind, &operator.text, &left, &right, left_span, right_span, "binary operation"),
+        }
+    }
+
+    // 산술, 비트 연산자의 피연산자를 확인합니다. 두 피연산자는 같은 정수나 실수 타입이어야 하고,
+    // 비트 연산자는 실수에는 쓸 수 없고 `bool`에는 쓸 수 있습니다. 결과는 왼쪽 피연산자의 타입입니다.
+    #[allow(clippy::too_many_arguments)]
+    fn check_arith(&mut self, kind: &TokenKind, text: &str, left: &Ty, right: &Ty, left_span: Span, right_span: Span, what: &str) -> Ty {
+        let ty = self.resolve(left);
+        let bitwise = matches!(kind, TokenKind::And | TokenKind::Or | TokenKind::Caret);
+        let applies = match &ty {
+            Ty::Int(_) | Ty::Infer(InferTy::Var(_) | InferTy::IntVar(_)) | Ty::Error => true,
+            Ty::Float(_) | Ty::Infer(InferTy::FloatVar(_)) => !bitwise,
+            Ty::Bool => bitwise,
+            _ => false,
+        };
+        if !applies {
+            self.error(format!("{} `{}` cannot be applied to type `{}`", what, text, ty), left_span, None);
+            return Ty::Error;
+        }
+        self.demand_coerce(right, left, right_span, Some(left_span));
+        left.clone()
+    }
+
+    // `==`(또는 `ordering`이면 `<`)로 비교할 수 있는 타입인지 확인합니다.
+    // 구조체와 타입 매개변수는 해당하는 비교 트레이트를 구현해야 합니다.
+    fn is_comparable(&self, ty: &Ty, ordering: bool) -> bool {
+        let traits: &[&str] = if ordering { &["PartialOrd", "Ord"] } else { &["PartialEq", "Eq", "PartialOrd", "Ord"] };
+        match ty {
+            Ty::Ref(_, ty) | Ty::Array(ty, _) | Ty::Slice(ty) => self.is_comparable(ty, ordering),
+            Ty::Tuple(tys) => tys.iter().all(|ty| self.is_comparable(ty, ordering)),
+            Ty::Param(name) => self.bounds.get(name).is_some_and(|bounds| {
+                bounds.iter().any(|bound| traits.contains(&path_name(bound).as_str()))
+            }),
+            Ty::Adt(name, _) if self.tcx.structs.contains_key(name) => traits.iter().any(|name| self.tcx.implements(ty, name)),
+            Ty::Adt(..) | Ty::Closure(_) => false,
+            _ => true,
+        }
+    }
+
+    fn check_unary(&mut self, unary_op: &UnaryOp, span: Span) -> Ty {
+        if unary_op.operator.kind == TokenKind::Minus {
+            self.negated.insert(unary_op.operand);
+        }
+        let operand = self.check_expr(unary_op.operand);
+        let ty = self.resolve(&operand);
+        let applies = match (&unary_op.operator.kind, &ty) {
+            (TokenKind::Star, Ty::Ref(_, inner)) => return (**inner).clone(),
+            (TokenKind::Star, Ty::Error) => return Ty::Error,
+            (TokenKind::Star, Ty::Infer(_)) => {
+                self.error("type annotations needed".to_string(), span, None);
+                return Ty::Error;
+            },
+            (TokenKind::Star, _) => {
+                self.error(format!("type `{}` cannot be dereferenced", ty), span, None);
+                return Ty::Error;
+            },
+            (TokenKind::Minus, Ty::Int(int)) => int.is_signed(),
+            (TokenKind::Minus, Ty::Float(_) | Ty::Infer(InferTy::FloatVar(_))) => true,
+            (TokenKind::Bang, Ty::Int(_) | Ty::Bool) => true,
+            (TokenKind::Bang, Ty::Infer(InferTy::FloatVar(_))) => false,
+            (_, Ty::Infer(_) | Ty::Error) => true,
+            _ => false,
+        };
+        if !applies {
+            self.error(format!("cannot apply unary operator `{}` to type `{}`", unary_op.operator.text, ty), span, None);
+            return Ty::Error;
+        }
+        operand
+    }
+
+    fn check_field(&mut self, base: ExprId, field: &str, span: Span) -> Ty {
+        let base_ty = self.check_expr(base);
+        let ty = self.resolve(&base_ty);
+        match ty.peel_refs() {
+            Ty::Adt(name, args) if self.tcx.structs.contains_key(name) => {
+                let info = &self.tcx.structs[name];
+                let Some(def) = info.fields.iter().find(|def| def.name == field) else {
+                    return self.no_field(field, &ty, span);
+                };
+                self.check_field_privacy(def, name, span);
+                let subst: HashMap<String, Ty> = info.generics.iter().cloned().zip(args.iter().cloned()).collect();
+                self.tcx.lower_ty(&def.ty, &subst)
+            },
+            Ty::Tuple(tys) => match field.parse::<usize>().ok().and_then(|index| tys.get(index)) {
+                Some(ty) => ty.clone(),
+                None => self.no_field(field, &ty, span),
+            },
+            Ty::Error => Ty::Error,
+            Ty::Infer(_) => {
+                self.error("type annotations needed".to_string(), self.arena[base].span, None);
+                Ty::Error
+            },
+            _ => self.no_field(field, &ty, span),
+        }
+    }
+
+    fn no_field(&mut self, field: &str, ty: &Ty, span: Span) -> Ty {
+        self.error(format!("no field `{}` on type `{}`", field, ty), span, None);
+        Ty::Error
+    }
+
+    // 비공개 필드는 구조체가 선언된 모듈과 그 하위 모듈에서만 읽고 쓸 수 있습니다.
+    fn check_field_privacy(&mut self, def: &FieldDef, struct_name: &str, span: Span) {
+        if !self.is_accessible(def.vis, module_of(struct_name)) {
+            self.error(format!("field `{}` of struct `{}` is private", def.name, struct_name), span, None);
+        }
+    }
+
+    // 모듈 `owner`에 선언된 `vis` 가시성의 이름을 지금 검사하는 본문에서 쓸 수 있는지 확인합니다.
+    fn is_accessible(&self, vis: Visibility, owner: &str) -> bool {
+        vis == Visibility::Public
+            || owner.is_empty()
+            || self.module == owner
+            || self.module.strip_prefix(owner).is_some_and(|rest| rest.starts_with("::"))
+    }
+
+    // 고유 impl의 비공개 함수는 대상 타입의 모듈과 그 하위 모듈에서만 부를 수 있습니다.
+    fn check_fn_privacy(&mut self, info: &FnInfo, ty_name: &str, name: &str, span: Span) {
+        if !self.is_accessible(info.vis, module_of(ty_name)) {
+            let kind = if info.self_param.is_some() { "method" } else { "associated function" };
+            self.error(format!("{} `{}` is private", kind, name), span, None);
+        }
+    }
+
+    fn check_struct_lit(&mut self, id: ExprId, lit: &StructLit, span: Span) -> Ty {
+        let tcx = self.tcx;
+        let info = match lit.path.segments.as_slice() {
+            [segment] => tcx.structs.get(&segment.ident).map(|info| (segment, info)),
+            _ => None,
+        };
+        let Some((segment, info)) = info else {
+            // 없는 구조체는 `method::resolve_methods`가 이미 보고했습니다.
+            lit.fields.iter().for_each(|&(_, value)| {
+                self.check_expr(value);
+            });
+            return Ty::Error;
+        };
+
+        self.check_arg_count("struct", &segment.args, info.generics.len(), span);
+        let args = self.lower_args(&segment.args, info.generics.len());
+        if !args.is_empty() {
+            self.generic_args.push((id, vec![args.clone()]));
+        }
+        let subst: HashMap<String, Ty> = info.generics.iter().cloned().zip(args.iter().cloned()).collect();
+        for (field, value) in &lit.fields {
+            match info.fields.iter().find(|def| def.name == *field) {
+                Some(def) => {
+                    self.check_field_privacy(def, &segment.ident, self.arena[*value].span);
+                    self.check_expr_coercible(*value, &self.tcx.lower_ty(&def.ty, &subst), ty_span(&def.ty));
+                },
+                None => {
+                    self.check_expr(*value);
+                },
+            }
+        }
+
+        let missing: Vec<String> = info
+            .fields
+            .iter()
+            .filter(|def| !lit.fields.iter().any(|(field, _)| *field == def.name))
+            .map(|def| format!("`{}`", def.name))
+            .collect();
+        if let Some((last, rest)) = missing.split_last() {
+            let fields = match rest {
+                [] => format!("field {}", last),
+                _ => format!("fields {} and {}", rest.join(", "), last),
+            };
+            self.error(format!("missing {} in initializer of `{}`", fields, segment.ident), span, None);
+        }
+        Ty::Adt(segment.ident.clone(), args)
+    }
+
+    fn check_index(&mut self, base: ExprId, index: ExprId) -> Ty {
+        let base_ty = self.check_expr(base);
+        let index_ty = self.check_expr(index);
+        let ty = self.resolve(&base_ty);
+        let ty = ty.peel_refs();
+        let elem = match ty {
+            Ty::Array(elem, _) | Ty::Slice(elem) => (**elem).clone(),
+            Ty::Adt(name, args) if name == "Vec" => args[0].clone(),
+            Ty::Error => return Ty::Error,
+            _ => {
+                self.error(format!("cannot index into a value of type `{}`", ty), self.arena[base].span, None);
+                return Ty::Error;
+            },
+        };
+
+        // 인덱스는 `usize`이고, `usize` 범위로 자르면 슬라이스가 나옵니다.
+        let (expected, result) = match &self.arena[index].kind {
+            NodeKind::Range(_) => {
+                let idx = match self.resolve(&index_ty) {
+                    Ty::Adt(_, args) => args.first().cloned().unwrap_or(Ty::Error),
+                    _ => Ty::Error,
+                };
+                (idx, Ty::Slice(Box::new(elem)))
+            },
+            _ => (index_ty.clone(), elem),
+        };
+        if self.coerce(&expected, &Ty::usize()).is_err() {
+            let index_ty = self.resolve(&index_ty);
+            self.error(format!("the type `{}` cannot be indexed by `{}`", ty, index_ty), self.arena[index].span, None);
+        }
+        result
+    }
+
+    fn check_call(&mut self, id: ExprId, call: &Call, span: Span) -> Ty {
+        let callee = &self.arena[call.callee];
+        let tcx = self.tcx;
+        let sig = match &callee.kind {
+            NodeKind::Path(path) if path.segments[0].ident == "std" => return self.check_builtin_call(&path_name(path), call, span),
+            NodeKind::QualifiedPath(qself, path) => {
+                let self_ty = self.tcx.lower_ty(&qself.ty, &self.subst);
+                let trait_name = path_name(&qself.trait_path);
+                let method = path.segments.last().unwrap();
+                if qself.trait_path.segments.last().is_some_and(|segment| segment.is_fn_trait()) {
+                    self.closure_sig(&self_ty, &method.ident)
+                } else {
+                    let info = tcx.traits.get(&trait_name).and_then(|decls| decls.get(&method.ident));
+                    info.map(|info| {
+                        self.check_arg_count("function", &method.args, info.generics.len(), callee.span);
+                        let sig = self.instantiate(info, Some(self_ty.clone()), &[], &method.args);
+                        self.record_args(call.callee, vec![sig.fn_args.clone()]);
+                        self.require_bound(self_ty, qself.trait_path.clone(), callee.span);
+                        self.infer_args(info, &sig, &method.ident, callee.span);
+                        sig
+                    })
+                }
+            },
+            _ => match self.item_sig(call.callee) {
+                Some(sig) => Some(sig),
+                None => {
+                    let ty = self.check_expr(call.callee);
+                    self.callee_sig(id, &ty, callee.span)
+                },
+            },
+        };
+
+        let Some(sig) = sig else {
+            call.args.iter().for_each(|&arg| {
+                self.check_expr(arg);
+            });
+            return Ty::Error;
+        };
+        self.check_args(&sig.inputs, &call.args, span, "function");
+        self.check_closure_bounds(&sig, span);
+        self.require_bounds(&sig, span);
+        sig.output
+    }
+
+    // 인자를 매개변수 타입에 맞춰 검사합니다. `what`은 오류 메시지에 쓸 호출 대상의 종류입니다 ("function", "method").
+    fn check_args(&mut self, inputs: &[(Ty, Option<Span>)], args: &[ExprId], span: Span, what: &str) {
+        if inputs.len() != args.len() {
+            let (expected, found) = (inputs.len(), args.len());
+            self.error(
+                format!(
+                    "this {} takes {} argument{} but {} argument{} supplied",
+                    what,
+                    expected,
+                    if expected == 1 { "" } else { "s" },
+                    found,
+                    if found == 1 { " was" } else { "s were" },
+                ),
+                span,
+                None,
+            );
+        }
+        for (&arg, (ty, ty_span)) in args.iter().zip(inputs) {
+            self.check_expr_coercible(arg, ty, *ty_span);
+        }
+        args.iter().skip(inputs.len()).for_each(|&arg| {
+            self.check_expr(arg);
+        });
+    }
+
+    // 함수 아이템을 가리키는 경로(`f`, `Pair::new`, `max::<u8>`)의 시그니처입니다.
+    // 지역 변수나 함수가 아닌 아이템을 가리키면 `None`입니다.
+    fn item_sig(&mut self, id: ExprId) -> Option<Signature> {
+        let (tcx, arena) = (self.tcx, self.arena);
+        let span = arena[id].span;
+        match &arena[id].kind {
+            NodeKind::Ident(name) if !matches!(self.resolutions.uses.get(&arena[id].id), Some(Res::Local(_))) => {
+                tcx.fns.get(name).map(|info| {
+                    let sig = self.instantiate(info, None, &[], &[]);
+                    self.record_args(id, vec![sig.fn_args.clone()]);
+                    self.infer_args(info, &sig, name, span);
+                    sig
+                })
+            },
+            NodeKind::Path(path) => match path.segments.as_slice() {
+                [function] => tcx.fns.get(&function.ident).map(|info| {
+                    self.check_arg_count("function", &function.args, info.generics.len(), span);
+                    let sig = self.instantiate(info, None, &[], &function.args);
+                    self.record_args(id, vec![sig.fn_args.clone()]);
+                    self.infer_args(info, &sig, &function.ident, span);
+                    sig
+                }),
+                [ty, function] => tcx.fns.get(&format!("{}::{}", ty.ident, function.ident)).map(|info| {
+                    self.check_fn_privacy(info, &ty.ident, &function.ident, span);
+                    self.check_arg_count("struct", &ty.args, info.impl_generics.len(), span);
+                    self.check_arg_count("function", &function.args, info.generics.len(), span);
+                    let sig = self.instantiate(info, None, &ty.args, &function.args);
+                    self.record_args(id, vec![sig.impl_args.clone(), sig.fn_args.clone()]);
+                    self.infer_args(info, &sig, &function.ident, span);
+                    sig
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // 경로에 적은 타입 인자의 수가 타입 매개변수의 수와 다르면 보고합니다. `what`은 "function", "struct"입니다.
+    fn check_arg_count(&mut self, what: &str, args: &[ast::Ty], count: usize, span: Span) {
+        if !args.is_empty() && args.len() != count {
+            let found = args.len();
+            let message = format!(
+                "{} takes {} generic argument{} but {} generic argument{} supplied",
+                what,
+                count,
+                if count == 1 { "" } else { "s" },
+                found,
+                if found == 1 { " was" } else { "s were" },
+            );
+            self.error(message, span, None);
+        }
+    }
+
+    // 호출의 타입 인자를 끝까지 추론해야 한다고 기록합니다. 함수 검사가 끝날 때 정해지지 않은 것을 보고합니다.
+    // impl의 타입 매개변수는 구현 대상 타입에, 함수의 타입 매개변수는 함수 `name`에 선언된 것입니다.
+    fn infer_args(&mut self, info: &FnInfo, sig: &Signature, name: &str, span: Span) {
+        let owner = match &info.self_ty {
+            Some(ast::Ty { kind: TyKind::Path(path), .. }) => path_name(path),
+            _ => name.to_string(),
+        };
+        let impl_args = info.impl_generics.iter().zip(&sig.impl_args).map(|(param, ty)| (ty.clone(), param.clone(), owner.clone()));
+        let fn_args = info.generics.iter().zip(&sig.fn_args).map(|(param, ty)| (ty.clone(), param.name.clone(), name.to_string()));
+        let args: InferredArgs = impl_args.chain(fn_args).collect();
+        if !args.is_empty() {
+            self.inferred_args.push((args, span));
+        }
+    }
+
+    // 함수 이름을 값으로 쓴 식(`let g = f;`)의 타입입니다. 제네릭 함수의 타입 인자는 쓰임새에서 추론합니다.
+    fn fn_value(&mut self, id: ExprId) -> Ty {
+        match self.item_sig(id) {
+            Some(sig) => Ty::FnPtr(sig.inputs.into_iter().map(|(ty, _)| ty).collect(), Box::new(sig.output)),
+            None => Ty::Error,
+        }
+    }
+
+    // 값을 호출할 때의 시그니처입니다. 함수 값과 클로저, 클로저 트레이트 바운드를 가진 타입 매개변수를 호출할 수 있습니다.
+    // 클로저 호출은 참조를 따라가며, 클로저의 종류가 정해진 뒤 `<F as Fn>::call(&f, x)`로 바뀌도록 기록합니다.
+    fn callee_sig(&mut self, id: ExprId, ty: &Ty, span: Span) -> Option<Signature> {
+        let resolved = self.resolve(ty);
+        let (self_ty, derefs) = method::peel_refs(&resolved);
+        let (inputs, output) = match self_ty {
+            Ty::FnPtr(inputs, output) if derefs == 0 => (inputs.clone(), (**output).clone()),
+            Ty::Closure(_) | Ty::Param(_) if self.fn_trait_sig(self_ty).is_some() => {
+                self.closure_calls.insert(id, (self_ty.clone(), derefs));
+                self.fn_trait_sig(self_ty)?
+            },
+            Ty::Error => return None,
+            Ty::Infer(InferTy::Var(_)) => {
+                self.error("type annotations needed".to_string(), span, None);
+                return None;
+            },
+            _ => {
+                self.error(format!("expected function, found `{}`", resolved), span, None);
+                return None;
+            },
+        };
+        Some(Signature::plain(inputs, output))
+    }
+
+    // 경로 세그먼트의 타입 인자를 `count`개의 타입으로 바꿉니다. 적지 않았거나 수가 맞지 않으면
+    // 새 타입 변수로 두어 쓰임새에서 추론합니다.
+    fn lower_args(&mut self, args: &[ast::Ty], count: usize) -> Vec<Ty> {
+        (0..count)
+            .map(|i| match args.get(i).filter(|_| args.len() == count) {
+                Some(arg) => self.tcx.lower_ty(arg, &self.subst),
+                None => self.infcx.new_var(),
+            })
+            .collect()
+    }
+
+    // 호출 경로의 세그먼트별 타입 인자를 기록해 검사가 끝나면 크레이트에 다시 씁니다.
+    fn record_args(&mut self, callee: ExprId, segments: Vec<Vec<Ty>>) {
+        if segments.iter().any(|args| !args.is_empty()) {
+            self.generic_args.push((callee, segments));
+        }
+    }
+
+    // 시그니처의 타입 매개변수를 이 호출의 타입으로 채웁니다.
+    // 경로에 적힌 타입 인자(`Pair::<i32>::new`, `max::<u8>`)가 있으면 그것을, 없으면 새 타입 변수를 씁니다.
+    fn instantiate(&mut self, info: &FnInfo, self_ty: Option<Ty>, impl_args: &[ast::Ty], fn_args: &[ast::Ty]) -> Signature {
+        let mut subst = HashMap::new();
+        let impl_args = self.lower_args(impl_args, info.impl_generics.len());
+        for (name, ty) in info.impl_generics.iter().zip(&impl_args) {
+            subst.insert(name.clone(), ty.clone());
+        }
+        let self_ty = self_ty.or_else(|| info.self_ty.as_ref().map(|ty| self.tcx.lower_ty(ty, &subst)));
+        if let Some(self_ty) = self_ty {
+            subst.insert("Self".to_string(), self_ty);
+        }
+        let mut bounds = Vec::new();
+        let fn_args = self.lower_args(fn_args, info.generics.len());
+        for (param, ty) in info.generics.iter().zip(&fn_args) {
+            bounds.extend(param.bounds.iter().map(|bound| (ty.clone(), bound.clone())));
+            subst.insert(param.name.clone(), ty.clone());
+        }
+
+        let receiver = info.self_param.map(|kind| (receiver_ty(kind, subst.get("Self").cloned().unwrap_or(Ty::Error)), None));
+        let params = info.params.iter().map(|ty| (self.tcx.lower_ty(ty, &subst), ty_span(ty)));
+        Signature {
+            inputs: receiver.into_iter().chain(params).collect(),
+            output: info.ret.as_ref().map_or(Ty::unit(), |ret| self.tcx.lower_ty(ret, &subst)),
+            bounds,
+            subst,
+            impl_args,
+            fn_args,
+        }
+    }
+
+    // 클로저 호출 `<C as Fn>::call(&c, args)`의 시그니처입니다.
+    fn closure_sig(&mut self, self_ty: &Ty, method: &str) -> Option<Signature> {
+        let (params, output) = self.fn_trait_sig(&self.infcx.shallow_resolve(self_ty))?;
+        let receiver = match method {
+            "call" => Ty::ref_to(false, self_ty.clone()),
+            "call_mut" => Ty::ref_to(true, self_ty.clone()),
+            _ => self_ty.clone(),
+        };
+        Some(Signature::plain([receiver].into_iter().chain(params).collect(), output))
+    }
+
+    // 호출할 수 있는 값의 매개변수와 반환 타입입니다.
+    // 클로저 타입이면 검사한 클로저의 시그니처를, 타입 매개변수라면 `Fn(i32) -> i32` 바운드를 씁니다.
+    fn fn_trait_sig(&self, ty: &Ty) -> Option<(Vec<Ty>, Ty)> {
+        match ty {
+            Ty::Closure(name) => self.closures.get(name).cloned(),
+            Ty::Param(name) => {
+                let bound = self.bounds.get(name)?.iter().find_map(|bound| bound.segments.last().filter(|segment| segment.is_fn_trait()))?;
+                let params = bound.args.iter().map(|ty| self.tcx.lower_ty(ty, &self.subst)).collect();
+                let output = bound.output.as_ref().map_or(Ty::unit(), |ty| self.tcx.lower_ty(ty, &self.subst));
+                Some((params, output))
+            },
+            _ => None,
+        }
+    }
+
+    // 클로저를 받는 타입 매개변수의 `Fn(A) -> R` 바운드로 클로저의 매개변수와 반환 타입을 정합니다.
+    fn check_closure_bounds(&mut self, sig: &Signature, span: Span) {
+        for (ty, bound) in &sig.bounds {
+            let Some(segment) = bound.segments.last().filter(|segment| segment.is_fn_trait()) else { continue };
+            let Ty::Closure(name) = self.infcx.shallow_resolve(ty) else { continue };
+            let Some((params, ret)) = self.closures.get(&name).cloned() else { continue };
+
+            if params.len() != segment.args.len() {
+                let (expected, found) = (segment.args.len(), params.len());
+                self.error(
+                    format!(
+                        "closure is expected to take {} argument{}, but it takes {} argument{}",
+                        expected,
+                        if expected == 1 { "" } else { "s" },
+                        found,
+                        if found == 1 { "" } else { "s" },
+                    ),
+                    span,
+                    None,
+                );
+                continue;
+            }
+            for (param, expected) in params.iter().zip(&segment.args) {
+                let expected_ty = self.tcx.lower_ty(expected, &sig.subst);
+                if self.infcx.unify(param, &expected_ty).is_err() {
+                    self.report_mismatch("type mismatch in closure arguments", &expected_ty, param, span, ty_span(expected));
+                }
+            }
+            let output = segment.output.as_ref().map_or(Ty::unit(), |ty| self.tcx.lower_ty(ty, &sig.subst));
+            if self.coerce(&ret, &output).is_err() {
+                let output_span = segment.output.as_deref().and_then(ty_span);
+                self.report_mismatch("type mismatch in closure return type", &output, &ret, span, output_span);
+            }
+        }
+    }
+
+    // 매크로가 부르는 `std::...` 함수입니다.
+    fn check_builtin_call(&mut self, name: &str, call: &Call, span: Span) -> Ty {
+        let arguments = || Ty::Adt("Arguments".to_string(), Vec::new());
+        let elem = self.infcx.new_var();
+        let vec = Ty::Adt("Vec".to_string(), vec![elem.clone()]);
+        let (inputs, output) = match name {
+            "std::io::_print" => (vec![arguments()], Ty::unit()),
+            "std::panicking::panic" => (vec![Ty::ref_to(false, Ty::Str)], Ty::Never),
+            "std::panicking::panic_fmt" => (vec![arguments()], Ty::Never),
+            "std::vec::new" => (Vec::new(), vec),
+            "std::vec::from_elem" => (vec![elem, Ty::usize()], vec),
+            "std::vec::from_array" => {
+                let array = call.args.first().map(|&arg| self.check_expr(arg));
+                return match array.map(|ty| self.resolve(&ty)) {
+                    Some(Ty::Array(elem, _)) => Ty::Adt("Vec".to_string(), vec![*elem]),
+                    _ => Ty::Error,
+                };
+            },
+            _ => {
+                call.args.iter().for_each(|&arg| {
+                    self.check_expr(arg);
+                });
+                return Ty::Error;
+            },
+        };
+        let sig = Signature {
+            inputs: inputs.into_iter().map(|ty| (ty, None)).collect(),
+            output,
+            bounds: Vec::new(),
+            subst: HashMap::new(),
+            impl_args: Vec::new(),
+            fn_args: Vec::new(),
+        };
+        if sig.inputs.len() != call.args.len() {
+            self.error(format!("`{}` takes {} argument(s) but {} were supplied", name, sig.inputs.len(), call.args.len()), span, None);
+        }
+        for (&arg, (ty, _)) in call.args.iter().zip(&sig.inputs) {
+            self.check_expr_coercible(arg, ty, None);
+        }
+        sig.output
+    }
+}
+
+// self 형태에 따른 리시버의 타입입니다.
+fn receiver_ty(kind: SelfKind, self_ty: Ty) -> Ty {
+    match kind {
+        SelfKind::Value { .. } => self_ty,
+        SelfKind::Ref => Ty::ref_to(false, self_ty),
+        SelfKind::RefMut => Ty::ref_to(true, self_ty),
+    }
+}
+
+// 복합 대입 연산자(`+=`)에 해당하는 이항 연산자(`+`)입니다.
+fn binary_kind(kind: &TokenKind) -> TokenKind {
+    match kind {
+        TokenKind::PlusEq => TokenKind::Plus,
+        TokenKind::MinusEq => TokenKind::Minus,
+        TokenKind::StarEq => TokenKind::Star,
+        TokenKind::SlashEq => TokenKind::Slash,
+        _ => TokenKind::Percent,
+    }
+}
+
+// 아직 정해지지 않은 타입 변수가 남아 있는지 확인합니다.
+fn has_vars(ty: &Ty) -> bool {
+    match ty {
+        Ty::Infer(_) => true,
+        Ty::Adt(_, tys) | Ty::Tuple(tys) => tys.iter().any(has_vars),
+        Ty::Ref(_, ty) | Ty::Array(ty, _) | Ty::Slice(ty) => has_vars(ty),
+        _ => false,
+    }
+}
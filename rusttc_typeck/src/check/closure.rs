@@ -0,0 +1,277 @@
+use std::collections::{HashMap, HashSet};
+
+use rusttc_ast::ast::{Block, CaptureBy, CaptureMode, ClosureKind, ExprId, Node, NodeId, NodeKind, Pat, PatKind, SelfKind, StmtKind};
+use rusttc_ir::resolve::Res;
+use rusttc_lexer::TokenKind;
+
+use super::method::closure_kind;
+use super::{path_name, FnCtxt};
+use crate::ty::Ty;
+
+// 클로저 본문에서 바깥 변수를 쓰는 방식입니다. 약한 것부터 강한 순서입니다.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Use {
+    Read,                       // 읽거나 복사합니다
+    Mutate,                     // 값을 바꾸거나 가변 참조를 만듭니다
+    Move,                       // 값을 이동합니다
+}
+
+// 표현식이 놓인 자리입니다.
+#[derive(Clone, Copy, PartialEq)]
+enum Ctx {
+    Value,                      // 값으로 쓰입니다
+    Borrow,                     // 공유 참조 대상입니다 (`&x`, `x.f`, `x == y`)
+    MutBorrow,                  // 가변 참조나 대입 대상입니다 (`&mut x`, `x = 1`)
+}
+
+// 클로저가 캡처한 변수의 이름, 바인딩 노드 번호, 캡처 방식입니다.
+pub(super) type Captures = Vec<(String, NodeId, CaptureMode)>;
+
+impl FnCtxt<'_> {
+    // 함수 안의 클로저를 안쪽 클로저부터 분석해 캡처 방식과 클로저 트레이트를 정하고, 클로저 이름별 종류를 돌려줍니다.
+    // 읽기만 하면 참조로, 바꾸면 가변 참조로, Copy가 아닌 값을 이동하면 값으로 캡처합니다.
+    // `move` 클로저는 모든 변수를 값으로 캡처합니다. 리시버와 클로저 호출의 쓰임은 검사에서 찾은 self 형태로 정합니다.
+    pub(super) fn analyze_closures(&mut self) -> HashMap<String, ClosureKind> {
+        let mut kinds = HashMap::new();
+        for (id, name) in std::mem::take(&mut self.closure_exprs) {
+            let NodeKind::Closure(closure) = &self.arena[id].kind else { continue };
+            let mut collector = CaptureCollector { fcx: self, kinds: &kinds, inner: HashSet::new(), uses: Vec::new() };
+            closure.params.iter().for_each(|param| collector.bind(&param.pat));
+            collector.visit(closure.body, Ctx::Value);
+
+            let uses = collector.uses;
+            let kind = match uses.iter().map(|(_, _, u)| *u).max() {
+                Some(Use::Move) => ClosureKind::FnOnce,
+                Some(Use::Mutate) => ClosureKind::FnMut,
+                _ => ClosureKind::Fn,
+            };
+            let captures = uses
+                .into_iter()
+                .map(|(name, binding, u)| {
+                    let mode = match (closure.capture_by, u) {
+                        (CaptureBy::Value, _) | (_, Use::Move) => CaptureMode::ByValue,
+                        (CaptureBy::Ref, Use::Mutate) => CaptureMode::ByMutRef,
+                        (CaptureBy::Ref, Use::Read) => CaptureMode::ByRef,
+                    };
+                    (name, binding, mode)
+                })
+                .collect();
+            kinds.insert(name, kind);
+            self.closure_captures.insert(id, (kind, captures));
+        }
+        kinds
+    }
+
+    // 타입 매개변수의 클로저 트레이트 바운드(`F: FnMut(i32)`)가 정하는 클로저 종류입니다.
+    pub(super) fn fn_bound_kind(&self, param: &str) -> Option<ClosureKind> {
+        self.bounds.get(param)?.iter().find_map(|bound| closure_kind(&path_name(bound)))
+    }
+
+    // 값으로 쓰여도 이동하지 않고 복사되는 타입인지 확인합니다. 구조체는 `#[derive(Copy)]`나 `impl Copy`가 있어야 하고,
+    // 끝내 타입을 알 수 없었다면 이동으로 봅니다.
+    fn is_copy(&self, ty: &Ty) -> bool {
+        match ty {
+            // `&mut T`는 값으로 넘겨도 다시 빌려지므로 이동으로 보지 않습니다.
+            Ty::Ref(..) => true,
+            Ty::Array(elem, _) => self.is_copy(elem),
+            Ty::Tuple(tys) => tys.iter().all(|ty| self.is_copy(ty)),
+            Ty::Adt(..) => self.tcx.implements(ty, "Copy"),
+            Ty::Param(name) => self.bounds.get(name).is_some_and(|bounds| bounds.iter().any(|bound| path_name(bound) == "Copy")),
+            Ty::Bool | Ty::Char | Ty::Int(_) | Ty::Float(_) | Ty::FnPtr(..) | Ty::Never => true,
+            Ty::Str | Ty::Slice(_) | Ty::Closure(_) | Ty::Infer(_) | Ty::Error => false,
+        }
+    }
+}
+
+// 클로저 본문을 돌며 바깥 지역 변수의 쓰임을 모읍니다.
+struct CaptureCollector<'a, 'b> {
+    fcx: &'b FnCtxt<'a>,
+    kinds: &'b HashMap<String, ClosureKind>,    // 분석을 마친 클로저의 종류
+    inner: HashSet<NodeId>,                     // 클로저 안에서 묶인 바인딩 (매개변수, let, for)
+    uses: Vec<(String, NodeId, Use)>,           // 처음 쓰인 순서대로 가장 강한 쓰임
+}
+
+impl CaptureCollector<'_, '_> {
+    fn bind(&mut self, pat: &Pat) {
+        pat.walk(&mut |pat| {
+            if let PatKind::Ident { .. } = pat.kind {
+                self.inner.insert(pat.id);
+            }
+        });
+    }
+
+    // `match`의 값이 놓인 자리입니다. Copy가 아닌 값을 바인딩하는 패턴이 있으면 이동하고, 아니면 패턴과 비교하려고 빌립니다.
+    fn scrutinee_ctx(&self, pats: &[&Pat]) -> Ctx {
+        let mut moves = false;
+        for pat in pats {
+            pat.walk(&mut |pat| {
+                if let PatKind::Ident { .. } = pat.kind {
+                    moves |= self.fcx.locals.get(&pat.id).is_some_and(|ty| !self.fcx.is_copy(&self.fcx.resolve(ty)));
+                }
+            });
+        }
+        if moves { Ctx::Value } else { Ctx::Borrow }
+    }
+
+    fn record(&mut self, node: &Node, ctx: Ctx) {
+        let (Some(&Res::Local(binding)), NodeKind::Ident(name)) = (self.fcx.resolutions.uses.get(&node.id), &node.kind) else { return };
+        self.record_binding(name, binding, ctx);
+    }
+
+    fn record_binding(&mut self, name: &str, binding: NodeId, ctx: Ctx) {
+        if self.inner.contains(&binding) {
+            return;
+        }
+        let new = match ctx {
+            Ctx::Value if self.fcx.locals.get(&binding).is_none_or(|ty| self.fcx.is_copy(&self.fcx.resolve(ty))) => Use::Read,
+            Ctx::Value => Use::Move,
+            Ctx::Borrow => Use::Read,
+            Ctx::MutBorrow => Use::Mutate,
+        };
+        match self.uses.iter_mut().find(|(_, used, _)| *used == binding) {
+            Some((_, _, old)) => *old = (*old).max(new),
+            None => self.uses.push((name.to_string(), binding, new)),
+        }
+    }
+
+    // 메서드 호출의 리시버가 놓인 자리입니다. 자동 참조는 빌리고, 참조를 따라간 리시버는 바탕 변수를 빌립니다.
+    fn receiver_ctx(&self, id: ExprId) -> Ctx {
+        let Some(callee) = self.fcx.method_calls.get(&id) else { return Ctx::Borrow };
+        match (callee.self_param, callee.derefs) {
+            (SelfKind::Value { .. }, 0) => Ctx::Value,
+            (SelfKind::RefMut, _) => Ctx::MutBorrow,
+            _ => Ctx::Borrow,
+        }
+    }
+
+    // 클로저 값을 호출할 때 호출 대상이 놓인 자리입니다. 호출되는 클로저의 종류가 정합니다.
+    fn callee_ctx(&self, id: ExprId) -> Ctx {
+        let Some((ty, derefs)) = self.fcx.closure_calls.get(&id) else { return Ctx::Value };
+        let kind = match self.fcx.resolve(ty) {
+            Ty::Closure(name) => self.kinds.get(&name).copied(),
+            Ty::Param(name) => self.fcx.fn_bound_kind(&name),
+            _ => None,
+        };
+        match (kind, *derefs) {
+            (Some(ClosureKind::Fn), _) => Ctx::Borrow,
+            (Some(ClosureKind::FnMut), _) => Ctx::MutBorrow,
+            (Some(ClosureKind::FnOnce), 0) | (None, _) => Ctx::Value,
+            (Some(ClosureKind::FnOnce), _) => Ctx::Borrow,
+        }
+    }
+
+    fn visit(&mut self, id: ExprId, ctx: Ctx) {
+        let node = &self.fcx.arena[id];
+        // 필드, 인덱스, 역참조는 바탕이 되는 변수를 빌립니다. 쓰기라면 가변으로 빌립니다.
+        let place = if ctx == Ctx::MutBorrow { Ctx::MutBorrow } else { Ctx::Borrow };
+        match &node.kind {
+            NodeKind::Int(..) | NodeKind::Float(..) | NodeKind::Char(_) | NodeKind::Bool(_) | NodeKind::Str(_) | NodeKind::Path(_) | NodeKind::QualifiedPath(..) => {},
+            NodeKind::Break | NodeKind::Continue => {},
+            NodeKind::Ident(_) => self.record(node, ctx),
+            NodeKind::Ref { mutable, expr } => {
+                self.visit(*expr, if *mutable { Ctx::MutBorrow } else { Ctx::Borrow });
+            },
+            NodeKind::Field(base, _) => self.visit(*base, place),
+            NodeKind::Index(base, index) => {
+                self.visit(*base, place);
+                self.visit(*index, Ctx::Value);
+            },
+            NodeKind::Unary(unary_op) if unary_op.operator.kind == TokenKind::Star => self.visit(unary_op.operand, place),
+            NodeKind::Unary(unary_op) => self.visit(unary_op.operand, Ctx::Value),
+            NodeKind::BinaryOp(binary_op) => {
+                // 비교 연산자는 피연산자를 참조로 받습니다.
+                let ctx = match binary_op.operator.kind {
+                    TokenKind::EqEq | TokenKind::Ne | TokenKind::Lt | TokenKind::Gt | TokenKind::Le | TokenKind::Ge => {
+                        Ctx::Borrow
+                    },
+                    _ => Ctx::Value,
+                };
+                self.visit(binary_op.left, ctx);
+                self.visit(binary_op.right, ctx);
+            },
+            NodeKind::Assign(assign) => {
+                self.visit(assign.target, Ctx::MutBorrow);
+                self.visit(assign.value, Ctx::Value);
+            },
+            NodeKind::Call(call) => {
+                self.visit(call.callee, self.callee_ctx(id));
+                call.args.iter().for_each(|&arg| self.visit(arg, Ctx::Value));
+            },
+            NodeKind::MethodCall(call) => {
+                self.visit(call.receiver, self.receiver_ctx(id));
+                call.args.iter().for_each(|&arg| self.visit(arg, Ctx::Value));
+            },
+            NodeKind::StructLit(lit) => lit.fields.iter().for_each(|&(_, value)| self.visit(value, Ctx::Value)),
+            NodeKind::Block(block) | NodeKind::Loop(block) => self.visit_block(block),
+            NodeKind::If(if_expr) => {
+                self.visit(if_expr.cond, Ctx::Value);
+                self.visit_block(&if_expr.then);
+                if let Some(els) = if_expr.els {
+                    self.visit(els, Ctx::Value);
+                }
+            },
+            NodeKind::While(while_expr) => {
+                self.visit(while_expr.cond, Ctx::Value);
+                self.visit_block(&while_expr.body);
+            },
+            NodeKind::ForLoop(for_loop) => {
+                self.visit(for_loop.iter, Ctx::Value);
+                self.bind(&for_loop.pat);
+                self.visit_block(&for_loop.body);
+            },
+            NodeKind::Match(match_expr) => {
+                let pats: Vec<&Pat> = match_expr.arms.iter().map(|arm| &arm.pat).collect();
+                self.visit(match_expr.scrutinee, self.scrutinee_ctx(&pats));
+                for arm in &match_expr.arms {
+                    self.bind(&arm.pat);
+                    if let Some(guard) = arm.guard {
+                        self.visit(guard, Ctx::Value);
+                    }
+                    self.visit(arm.body, Ctx::Value);
+                }
+            },
+            NodeKind::Return(value) => {
+                if let Some(value) = value {
+                    self.visit(*value, Ctx::Value);
+                }
+            },
+            NodeKind::Array(elems) | NodeKind::Tuple(elems) => elems.iter().for_each(|&elem| self.visit(elem, Ctx::Value)),
+            // 포맷 인자는 참조로 읽힙니다.
+            NodeKind::FormatArgs(format) => format.args.iter().for_each(|arg| self.visit(arg.expr, Ctx::Borrow)),
+            NodeKind::Repeat(value, count) => {
+                self.visit(*value, Ctx::Value);
+                self.visit(*count, Ctx::Value);
+            },
+            NodeKind::Range(range) => range.start.iter().chain(&range.end).for_each(|&bound| self.visit(bound, Ctx::Value)),
+            // 안쪽 클로저가 캡처한 변수는 그 캡처 방식대로 바깥 클로저도 캡처합니다.
+            NodeKind::Closure(inner) => {
+                inner.params.iter().for_each(|param| self.bind(&param.pat));
+                let Some((_, captures)) = self.fcx.closure_captures.get(&id) else { return };
+                for (name, binding, mode) in captures {
+                    let ctx = match mode {
+                        CaptureMode::ByRef => Ctx::Borrow,
+                        CaptureMode::ByMutRef => Ctx::MutBorrow,
+                        CaptureMode::ByValue => Ctx::Value,
+                    };
+                    self.record_binding(name, *binding, ctx);
+                }
+            },
+        }
+    }
+
+    // 블록 안에서 묶인 이름은 해석 단계가 이미 구분했으므로 바인딩 번호만 모아 둡니다.
+    fn visit_block(&mut self, block: &Block) {
+        for &stmt in &block.stmts {
+            match &self.fcx.arena[stmt].kind {
+                StmtKind::Let(local) => {
+                    if let Some(init) = local.init {
+                        self.visit(init, Ctx::Value);
+                    }
+                    self.bind(&local.pat);
+                },
+                StmtKind::Item(_) => {},
+                StmtKind::Expr(node) | StmtKind::Semi(node) => self.visit(*node, Ctx::Value),
+            }
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+
+use rusttc_ast::ast::{ClosureKind, ExprId, MethodCall, Path, SelfKind, FN_TRAITS};
+use rusttc_lexer::Span;
+
+use super::{has_vars, path_name, receiver_ty, FnCtxt, FnInfo, Signature};
+use crate::ty::{InferTy, Ty};
+use crate::{CalleeKind, MethodCallee};
+
+// 컴파일러가 기본으로 제공하는 트레이트입니다. 타입의 구조로 구현 여부를 정합니다.
+const BUILTIN_TRAITS: &[&str] = &["Copy", "Clone", "PartialEq", "Eq", "PartialOrd", "Ord"];
+
+impl FnCtxt<'_> {
+    // `receiver.method(args)`에서 리시버 타입의 메서드를 찾아 검사합니다.
+    // 리시버의 참조를 벗겨 가며 고유 impl, 구현된 트레이트(타입 매개변수라면 바운드) 순서로 찾고,
+    // 찾은 메서드와 리시버 조정 방식을 기록해 쓰기 단계에서 경로 호출(`Point::len(&p)`)로 바꾸게 합니다.
+    pub(super) fn check_method_call(&mut self, id: ExprId, call: &MethodCall, span: Span) -> Ty {
+        let receiver = self.check_expr(call.receiver);
+        let receiver = self.resolve(&receiver);
+        let (self_ty, derefs) = peel_refs(&receiver);
+        let self_ty = self_ty.clone();
+        match &self_ty {
+            Ty::Error => return self.check_args_only(&call.args),
+            Ty::Infer(InferTy::Var(_)) => {
+                self.error("type annotations needed".to_string(), self.arena[call.receiver].span, None);
+                return self.check_args_only(&call.args);
+            },
+            _ => {},
+        }
+
+        // 배열과 슬라이스의 `iter()`, `iter_mut()`은 원소를 빌려 주는 참조 `&a`, `&mut a`와 같습니다.
+        if let (Ty::Array(..) | Ty::Slice(_), "iter" | "iter_mut", true) = (&self_ty, call.method.as_str(), call.args.is_empty()) {
+            let mutable = call.method == "iter_mut";
+            let self_param = if mutable { SelfKind::RefMut } else { SelfKind::Ref };
+            self.method_calls.insert(id, MethodCallee { kind: CalleeKind::Borrow, self_param, derefs });
+            return Ty::ref_to(mutable, self_ty);
+        }
+
+        let Some((kind, info)) = self.probe_method(&self_ty, &call.method, span) else {
+            // 접미사 없는 숫자의 트레이트 메서드는 어느 타입의 구현인지 정할 수 없습니다.
+            let numeric = matches!(self_ty, Ty::Infer(InferTy::IntVar(_) | InferTy::FloatVar(_)));
+            if numeric && self.tcx.traits.values().any(|decls| decls.contains_key(&call.method)) {
+                let message = format!("can't call method `{}` on ambiguous numeric type `{}`", call.method, receiver);
+                self.error(message, span, None);
+                return self.check_args_only(&call.args);
+            }
+            let kind = match &self_ty {
+                Ty::Adt(name, _) if self.tcx.structs.contains_key(name) => "struct",
+                Ty::Param(_) => "type parameter",
+                _ => "type",
+            };
+            let message = format!("no method named `{}` found for {} `{}` in the current scope", call.method, kind, receiver);
+            self.error(message, span, None);
+            return self.check_args_only(&call.args);
+        };
+        let Some(self_param) = info.self_param else {
+            let owner = match &self_ty {
+                Ty::Adt(name, _) | Ty::Param(name) => name.clone(),
+                ty => ty.to_string(),
+            };
+            let message = format!(
+                "no method named `{}` found for struct `{}`: `{}::{}` is an associated function, not a method",
+                call.method, receiver, owner, call.method
+            );
+            self.error(message, span, None);
+            return self.check_args_only(&call.args);
+        };
+
+        let (sig, kind) = match kind {
+            CalleeKind::Inherent { ty, name, .. } => {
+                self.check_fn_privacy(&info, &ty, &name, span);
+                let sig = self.instantiate(&info, None, &[], &[]);
+                let kind = CalleeKind::Inherent { ty, impl_args: sig.impl_args.clone(), name, fn_args: sig.fn_args.clone() };
+                (sig, kind)
+            },
+            CalleeKind::Trait { self_ty, trait_name, name, .. } => {
+                let sig = self.instantiate(&info, Some(self_ty.clone()), &[], &[]);
+                let kind = CalleeKind::Trait { self_ty, trait_name, name, fn_args: sig.fn_args.clone() };
+                (sig, kind)
+            },
+            CalleeKind::Borrow => unreachable!(),
+        };
+
+        // 메서드의 self 형태에 맞게 참조하거나 역참조한 리시버가 첫 번째 인자입니다.
+        let adjusted = match (self_param, derefs) {
+            (SelfKind::Value { .. }, _) => self_ty.clone(),
+            (SelfKind::Ref | SelfKind::RefMut, 0) => receiver_ty(self_param, self_ty.clone()),
+            (SelfKind::Ref | SelfKind::RefMut, depth) => (0..depth - 1).fold(receiver.clone(), |ty, _| match ty {
+                Ty::Ref(_, inner) => *inner,
+                ty => ty,
+            }),
+        };
+        if let Some((expected, _)) = sig.inputs.first() {
+            self.demand_coerce(&adjusted, expected, self.arena[call.receiver].span, None);
+        }
+        self.check_args(&sig.inputs[1..], &call.args, span, "method");
+        self.check_closure_bounds(&sig, span);
+        self.require_bounds(&sig, span);
+        self.infer_args(&info, &sig, &call.method, span);
+        self.method_calls.insert(id, MethodCallee { kind, self_param, derefs });
+        sig.output
+    }
+
+    fn check_args_only(&mut self, args: &[ExprId]) -> Ty {
+        args.iter().for_each(|&arg| {
+            self.check_expr(arg);
+        });
+        Ty::Error
+    }
+
+    // `self_ty`의 메서드 `name`을 찾습니다. 고유 impl에 없으면 구현된 트레이트에서 찾습니다.
+    // 돌려주는 호출 대상의 타입 인자는 비어 있고, 시그니처를 채울 때 정해집니다.
+    fn probe_method(&mut self, self_ty: &Ty, name: &str, span: Span) -> Option<(CalleeKind, FnInfo)> {
+        let tcx = self.tcx;
+        if let Ty::Adt(adt, _) = self_ty {
+            if let Some(info) = tcx.fns.get(&format!("{}::{}", adt, name)) {
+                let kind = CalleeKind::Inherent { ty: adt.clone(), impl_args: Vec::new(), name: name.to_string(), fn_args: Vec::new() };
+                return Some((kind, info.clone()));
+            }
+        }
+
+        let traits: Vec<String> = match self_ty {
+            Ty::Param(param) => self.bounds.get(param).map_or(Vec::new(), |bounds| bounds.iter().map(path_name).collect()),
+            ty => {
+                let key = ty_key(ty)?;
+                let mut traits: Vec<String> =
+                    tcx.trait_impls.iter().filter(|(ty, _)| *ty == key).map(|(_, trait_name)| trait_name.clone()).collect();
+                traits.sort();
+                traits
+            },
+        };
+        let candidates: Vec<&String> =
+            traits.iter().filter(|trait_name| tcx.traits.get(*trait_name).is_some_and(|decls| decls.contains_key(name))).collect();
+        if candidates.len() > 1 {
+            let message = format!(
+                "multiple applicable items in scope: `{}` is defined in traits {}",
+                name,
+                candidates.iter().map(|t| format!("`{}`", t)).collect::<Vec<_>>().join(", ")
+            );
+            self.error(message, span, None);
+        }
+        let trait_name = candidates.first()?;
+        let info = tcx.traits[*trait_name][name].clone();
+        let kind = CalleeKind::Trait { self_ty: self_ty.clone(), trait_name: trait_name.to_string(), name: name.to_string(), fn_args: Vec::new() };
+        Some((kind, info))
+    }
+
+    // 타입 인자의 트레이트 바운드를 확인합니다. 타입이 아직 정해지지 않았거나 클로저라면
+    // (클로저가 구현하는 트레이트는 캡처를 분석한 뒤에 정해집니다) 함수 검사가 끝날 때 확인합니다.
+    pub(super) fn require_bounds(&mut self, sig: &Signature, span: Span) {
+        for (ty, bound) in &sig.bounds {
+            self.require_bound(ty.clone(), bound.clone(), span);
+        }
+    }
+
+    pub(super) fn require_bound(&mut self, ty: Ty, bound: Path, span: Span) {
+        let resolved = self.resolve(&ty);
+        if has_vars(&resolved) || matches!(resolved, Ty::Closure(_)) {
+            self.obligations.push((ty, bound, span));
+            return;
+        }
+        self.check_bound(&resolved, &bound, span, &HashMap::new());
+    }
+
+    // `ty: bound`가 성립하지 않으면 보고합니다. `closure_kinds`는 분석을 마친 클로저의 종류입니다.
+    pub(super) fn check_bound(&mut self, ty: &Ty, bound: &Path, span: Span, closure_kinds: &HashMap<String, ClosureKind>) {
+        let trait_name = path_name(bound);
+        if !self.satisfies(ty, &trait_name, closure_kinds) {
+            let ty = match ty {
+                Ty::Closure(name) => name.clone(),
+                ty => ty.to_string(),
+            };
+            self.error(format!("the trait bound `{}: {}` is not satisfied", ty, trait_name), span, None);
+        }
+    }
+
+    // `ty`가 트레이트 `trait_name`을 구현하는지 확인합니다.
+    // 내장 트레이트는 타입의 구조로, 클로저 트레이트는 클로저의 종류로, 나머지는 impl 블록으로 정합니다.
+    fn satisfies(&self, ty: &Ty, trait_name: &str, closure_kinds: &HashMap<String, ClosureKind>) -> bool {
+        match ty {
+            Ty::Error | Ty::Infer(_) | Ty::Never => true,
+            Ty::Param(name) => self.bounds.get(name).is_some_and(|bounds| {
+                bounds.iter().any(|bound| {
+                    let bound = path_name(bound);
+                    bound == trait_name || closure_kind(&bound).is_some_and(|kind| closure_implements(kind, trait_name))
+                })
+            }),
+            Ty::Closure(name) => closure_kinds.get(name).is_none_or(|&kind| closure_implements(kind, trait_name)),
+            Ty::FnPtr(..) => FN_TRAITS.contains(&trait_name) || matches!(trait_name, "Copy" | "Clone"),
+            _ if FN_TRAITS.contains(&trait_name) => false,
+            Ty::Adt(name, _) if self.tcx.structs.contains_key(name) => self.tcx.implements(ty, trait_name),
+            _ if BUILTIN_TRAITS.contains(&trait_name) => self.builtin_impl(ty, trait_name, closure_kinds),
+            ty => ty_key(ty).is_some_and(|key| self.tcx.trait_impls.contains(&(key, trait_name.to_string()))),
+        }
+    }
+
+    // 구조체가 아닌 타입의 내장 트레이트 구현입니다. 부동소수점은 전순서가 없고, `&mut T`와 `str`은 복사할 수 없습니다.
+    fn builtin_impl(&self, ty: &Ty, trait_name: &str, closure_kinds: &HashMap<String, ClosureKind>) -> bool {
+        let copy = matches!(trait_name, "Copy" | "Clone");
+        match ty {
+            Ty::Float(_) | Ty::Infer(InferTy::FloatVar(_)) => !matches!(trait_name, "Eq" | "Ord"),
+            Ty::Str => !copy,
+            Ty::Ref(false, _) if copy => true,
+            Ty::Ref(true, _) if copy => false,
+            Ty::Ref(_, inner) | Ty::Array(inner, _) => self.satisfies(inner, trait_name, closure_kinds),
+            Ty::Slice(inner) => !copy && self.satisfies(inner, trait_name, closure_kinds),
+            Ty::Tuple(tys) => tys.iter().all(|ty| self.satisfies(ty, trait_name, closure_kinds)),
+            Ty::Adt(name, _) if name == "Vec" => trait_name != "Copy",
+            _ => true,
+        }
+    }
+}
+
+// 참조를 벗겨낸 타입과 참조 단계 수를 돌려줍니다.
+pub(super) fn peel_refs(ty: &Ty) -> (&Ty, usize) {
+    match ty {
+        Ty::Ref(_, inner) => {
+            let (inner, depth) = peel_refs(inner);
+            (inner, depth + 1)
+        },
+        _ => (ty, 0),
+    }
+}
+
+// 트레이트 구현을 찾을 때 쓰는 타입 이름입니다 (`impl Shape for Circle`의 `Circle`).
+// 구조체와 기본 타입은 이름으로, 튜플과 참조, 배열은 모양으로(`(_, _)`) 찾고 타입 인자는 구별하지 않습니다.
+pub(super) fn ty_key(ty: &Ty) -> Option<String> {
+    match ty {
+        Ty::Adt(name, _) => Some(name.clone()),
+        Ty::Bool | Ty::Char | Ty::Int(_) | Ty::Float(_) | Ty::Str => Some(ty.to_string()),
+        Ty::Ref(false, _) => Some("&_".to_string()),
+        Ty::Ref(true, _) => Some("&mut _".to_string()),
+        Ty::Array(..) => Some("[_; _]".to_string()),
+        Ty::Slice(_) => Some("[_]".to_string()),
+        Ty::Tuple(tys) => Some(format!("({})", vec!["_"; tys.len()].join(", "))),
+        _ => None,
+    }
+}
+
+// 클로저 트레이트의 종류입니다. 클로저 트레이트가 아니면 `None`입니다.
+pub(super) fn closure_kind(trait_name: &str) -> Option<ClosureKind> {
+    match trait_name {
+        "Fn" => Some(ClosureKind::Fn),
+        "FnMut" => Some(ClosureKind::FnMut),
+        "FnOnce" => Some(ClosureKind::FnOnce),
+        _ => None,
+    }
+}
+
+// `kind` 종류의 클로저가 클로저 트레이트 `trait_name`을 구현하는지 확인합니다.
+fn closure_implements(kind: ClosureKind, trait_name: &str) -> bool {
+    match kind {
+        ClosureKind::Fn => FN_TRAITS.contains(&trait_name),
+        ClosureKind::FnMut => trait_name == "FnMut" || trait_name == "FnOnce",
+        ClosureKind::FnOnce => trait_name == "FnOnce",
+    }
+}
@@ -0,0 +1,181 @@
+use crate::ty::{FloatTy, InferTy, IntTy, Ty, TyVid};
+
+// 함수 하나를 검사하는 동안의 타입 변수 테이블입니다.
+// 변수는 다른 타입(다른 변수일 수도 있습니다)에 묶이거나 아직 비어 있습니다.
+#[derive(Debug, Default)]
+pub(crate) struct InferCtxt {
+    values: Vec<Option<Ty>>,    // 변수 번호 → 묶인 타입
+    int_vars: Vec<TyVid>,       // 정수 리터럴에서 나온 변수
+    float_vars: Vec<TyVid>,     // 실수 리터럴에서 나온 변수
+}
+
+// 두 타입을 맞출 수 없습니다.
+#[derive(Debug, PartialEq)]
+pub(crate) struct TypeMismatch;
+
+impl InferCtxt {
+    pub(crate) fn new_var(&mut self) -> Ty {
+        Ty::Infer(InferTy::Var(self.alloc()))
+    }
+
+    pub(crate) fn new_int_var(&mut self) -> Ty {
+        let vid = self.alloc();
+        self.int_vars.push(vid);
+        Ty::Infer(InferTy::IntVar(vid))
+    }
+
+    pub(crate) fn new_float_var(&mut self) -> Ty {
+        let vid = self.alloc();
+        self.float_vars.push(vid);
+        Ty::Infer(InferTy::FloatVar(vid))
+    }
+
+    fn alloc(&mut self) -> TyVid {
+        self.values.push(None);
+        TyVid(self.values.len() as u32 - 1)
+    }
+
+    fn value(&self, vid: TyVid) -> Option<&Ty> {
+        self.values[vid.0 as usize].as_ref()
+    }
+
+    // 맨 바깥의 묶인 변수만 따라갑니다.
+    pub(crate) fn shallow_resolve(&self, ty: &Ty) -> Ty {
+        let mut ty = ty;
+        while let Ty::Infer(InferTy::Var(vid) | InferTy::IntVar(vid) | InferTy::FloatVar(vid)) = ty {
+            match self.value(*vid) {
+                Some(value) => ty = value,
+                None => break,
+            }
+        }
+        ty.clone()
+    }
+
+    // 타입 안의 묶인 변수를 모두 풀어 씁니다. 아직 비어 있는 변수는 그대로 남습니다.
+    pub(crate) fn resolve(&self, ty: &Ty) -> Ty {
+        match self.shallow_resolve(ty) {
+            Ty::Adt(name, args) => Ty::Adt(name, args.iter().map(|arg| self.resolve(arg)).collect()),
+            Ty::Ref(mutable, ty) => Ty::Ref(mutable, Box::new(self.resolve(&ty))),
+            Ty::Array(ty, len) => Ty::Array(Box::new(self.resolve(&ty)), len),
+            Ty::Slice(ty) => Ty::Slice(Box::new(self.resolve(&ty))),
+            Ty::Tuple(tys) => Ty::Tuple(tys.iter().map(|ty| self.resolve(ty)).collect()),
+            Ty::FnPtr(inputs, output) => Ty::FnPtr(inputs.iter().map(|ty| self.resolve(ty)).collect(), Box::new(self.resolve(&output))),
+            ty => ty,
+        }
+    }
+
+    // 끝까지 정해지지 않은 정수, 실수 변수를 `i32`, `f64`로 정합니다.
+    pub(crate) fn default_literal_vars(&mut self) {
+        let defaults = self.int_vars.iter().map(|&vid| (vid, Ty::Int(IntTy::I32)));
+        let defaults = defaults.chain(self.float_vars.iter().map(|&vid| (vid, Ty::Float(FloatTy::F64))));
+        for (vid, ty) in defaults {
+            let value = &mut self.values[vid.0 as usize];
+            if value.is_none() {
+                *value = Some(ty);
+            }
+        }
+    }
+
+    // 두 타입이 같아지도록 변수를 묶습니다. 맞출 수 없으면 그때까지 묶은 변수는 그대로 두고 실패합니다.
+    pub(crate) fn unify(&mut self, a: &Ty, b: &Ty) -> Result<(), TypeMismatch> {
+        let (a, b) = (self.shallow_resolve(a), self.shallow_resolve(b));
+        match (&a, &b) {
+            (Ty::Infer(InferTy::Var(v)), Ty::Infer(InferTy::Var(w))) if v == w => Ok(()),
+            (Ty::Infer(InferTy::Var(vid)), other) | (other, Ty::Infer(InferTy::Var(vid))) => self.bind(*vid, other),
+            (Ty::Error, _) | (_, Ty::Error) => Ok(()),
+            (Ty::Infer(InferTy::IntVar(v)), Ty::Infer(InferTy::IntVar(w))) if v == w => Ok(()),
+            (Ty::Infer(InferTy::IntVar(vid)), other @ (Ty::Int(_) | Ty::Infer(InferTy::IntVar(_))))
+            | (other @ Ty::Int(_), Ty::Infer(InferTy::IntVar(vid))) => self.bind(*vid, other),
+            (Ty::Infer(InferTy::FloatVar(v)), Ty::Infer(InferTy::FloatVar(w))) if v == w => Ok(()),
+            (Ty::Infer(InferTy::FloatVar(vid)), other @ (Ty::Float(_) | Ty::Infer(InferTy::FloatVar(_))))
+            | (other @ Ty::Float(_), Ty::Infer(InferTy::FloatVar(vid))) => self.bind(*vid, other),
+            (Ty::Bool, Ty::Bool) | (Ty::Char, Ty::Char) | (Ty::Str, Ty::Str) | (Ty::Never, Ty::Never) => Ok(()),
+            (Ty::Int(a), Ty::Int(b)) if a == b => Ok(()),
+            (Ty::Float(a), Ty::Float(b)) if a == b => Ok(()),
+            (Ty::Param(a), Ty::Param(b)) | (Ty::Closure(a), Ty::Closure(b)) if a == b => Ok(()),
+            (Ty::Adt(a, a_args), Ty::Adt(b, b_args)) if a == b && a_args.len() == b_args.len() => {
+                a_args.iter().zip(b_args).try_for_each(|(a, b)| self.unify(a, b))
+            },
+            (Ty::Ref(a_mut, a), Ty::Ref(b_mut, b)) if a_mut == b_mut => self.unify(a, b),
+            (Ty::Array(a, a_len), Ty::Array(b, b_len)) if a_len == b_len => self.unify(a, b),
+            (Ty::Slice(a), Ty::Slice(b)) => self.unify(a, b),
+            (Ty::Tuple(a), Ty::Tuple(b)) if a.len() == b.len() => a.iter().zip(b).try_for_each(|(a, b)| self.unify(a, b)),
+            (Ty::FnPtr(a, a_output), Ty::FnPtr(b, b_output)) if a.len() == b.len() => {
+                a.iter().zip(b).try_for_each(|(a, b)| self.unify(a, b))?;
+                self.unify(a_output, b_output)
+            },
+            _ => Err(TypeMismatch),
+        }
+    }
+
+    fn bind(&mut self, vid: TyVid, ty: &Ty) -> Result<(), TypeMismatch> {
+        // `T = Vec<T>`처럼 자기 자신을 담는 타입은 만들 수 없습니다.
+        if self.occurs(vid, ty) {
+            return Err(TypeMismatch);
+        }
+        self.values[vid.0 as usize] = Some(ty.clone());
+        Ok(())
+    }
+
+    fn occurs(&self, vid: TyVid, ty: &Ty) -> bool {
+        match self.shallow_resolve(ty) {
+            Ty::Infer(InferTy::Var(other) | InferTy::IntVar(other) | InferTy::FloatVar(other)) => other == vid,
+            Ty::Adt(_, tys) | Ty::Tuple(tys) => tys.iter().any(|ty| self.occurs(vid, ty)),
+            Ty::Ref(_, ty) | Ty::Array(ty, _) | Ty::Slice(ty) => self.occurs(vid, &ty),
+            Ty::FnPtr(inputs, output) => inputs.iter().any(|ty| self.occurs(vid, ty)) || self.occurs(vid, &output),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unify_binds_variables() {
+        let mut infcx = InferCtxt::default();
+        let (a, b) = (infcx.new_var(), infcx.new_var());
+        let pair = Ty::Tuple(vec![a.clone(), Ty::Bool]);
+        assert_eq!(infcx.unify(&pair, &Ty::Tuple(vec![b.clone(), b.clone()])), Ok(()));
+        assert_eq!(infcx.resolve(&a), Ty::Bool);
+        assert_eq!(infcx.resolve(&pair), Ty::Tuple(vec![Ty::Bool, Ty::Bool]));
+    }
+
+    #[test]
+    fn test_int_vars_only_take_integers() {
+        let mut infcx = InferCtxt::default();
+        let (lit, other) = (infcx.new_int_var(), infcx.new_int_var());
+        assert_eq!(infcx.unify(&lit, &Ty::Bool), Err(TypeMismatch));
+        assert_eq!(infcx.unify(&lit, &Ty::Int(IntTy::U8)), Ok(()));
+        assert_eq!(infcx.resolve(&lit), Ty::Int(IntTy::U8));
+
+        // 일반 변수가 정수 변수에 묶이면 정수 타입만 받습니다.
+        let var = infcx.new_var();
+        assert_eq!(infcx.unify(&var, &other), Ok(()));
+        assert_eq!(infcx.unify(&var, &Ty::Str), Err(TypeMismatch));
+        infcx.default_literal_vars();
+        assert_eq!(infcx.resolve(&var), Ty::Int(IntTy::I32));
+    }
+
+    #[test]
+    fn test_float_vars_default_to_f64() {
+        let mut infcx = InferCtxt::default();
+        let (a, b, int) = (infcx.new_float_var(), infcx.new_float_var(), infcx.new_int_var());
+        assert_eq!(infcx.unify(&a, &int), Err(TypeMismatch));
+        assert_eq!(infcx.unify(&b, &Ty::Float(FloatTy::F32)), Ok(()));
+        infcx.default_literal_vars();
+        assert_eq!(infcx.resolve(&a), Ty::Float(FloatTy::F64));
+        assert_eq!(infcx.resolve(&b), Ty::Float(FloatTy::F32));
+        assert_eq!(infcx.resolve(&int), Ty::Int(IntTy::I32));
+    }
+
+    #[test]
+    fn test_occurs_check() {
+        let mut infcx = InferCtxt::default();
+        let var = infcx.new_var();
+        let array = Ty::Array(Box::new(var.clone()), 2);
+        assert_eq!(infcx.unify(&var, &array), Err(TypeMismatch));
+        assert_eq!(infcx.unify(&var, &Ty::Error), Ok(()));
+    }
+}
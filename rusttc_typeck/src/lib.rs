@@ -0,0 +1,851 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use rusttc_ast::ast::{Capture, ClosureKind, Crate, ExprId, NodeId, SelfKind};
+use rusttc_ir::resolve::Resolutions;
+use rusttc_lexer::Span;
+
+mod check;
+mod infer;
+pub mod ty;
+pub mod usefulness;
+mod writeback;
+
+use ty::Ty;
+pub use writeback::write_back;
+
+// 타입 검사 중에 발생한 오류입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeError {
+    pub message: String,
+    pub span: Span,                     // 잘못된 값의 위치
+    pub expected_span: Option<Span>,    // 기대한 타입을 정한 표기의 위치 (`let x: i32`의 `i32`)
+}
+
+// 소스 맵에 파일이 등록돼 있으면 오류 위치와 기대한 타입을 정한 위치도 함께 출력합니다.
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)?;
+        if let Some(location) = self.span.location() {
+            write!(f, "\n  --> {}", location)?;
+        }
+        if let Some(location) = self.expected_span.and_then(Span::location) {
+            write!(f, "\n  = note: expected due to the type at {}", location)?;
+        }
+        Ok(())
+    }
+}
+
+// 타입 검사 중에 발견한 경고입니다. 컴파일을 멈추지 않습니다 (`unreachable pattern`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeWarning {
+    pub message: String,
+    pub span: Span,
+}
+
+impl fmt::Display for TypeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "warning: {}", self.message)?;
+        if let Some(location) = self.span.location() {
+            write!(f, "\n  --> {}", location)?;
+        }
+        Ok(())
+    }
+}
+
+// 타입 검사 결과입니다.
+#[derive(Debug, Default)]
+pub struct TypeckResults {
+    pub node_types: HashMap<NodeId, Ty>,            // 표현식과 바인딩 패턴의 노드 번호 → 타입
+    pub literal_types: HashMap<ExprId, Ty>,         // 숫자 리터럴 → 타입
+    pub generic_args: HashMap<ExprId, Vec<Vec<Ty>>>, // 호출 경로, 구조체 리터럴 → 세그먼트마다 추론한 타입 인자
+    pub array_lens: HashMap<ExprId, usize>,         // 타입 표기 안의 배열 길이 식 → 계산한 길이
+    pub method_calls: HashMap<ExprId, MethodCallee>, // 메서드 호출, 클로저 값 호출 → 부를 함수
+    pub closures: HashMap<ExprId, (ClosureKind, Vec<Capture>)>, // 클로저 → 구현하는 트레이트와 캡처
+    pub warnings: Vec<TypeWarning>,                 // 닿을 수 없는 `match` 갈래
+}
+
+// 메서드 호출 `recv.m(args)`가 부르는 함수와 받는 쪽을 맞추는 방법입니다.
+#[derive(Debug, Clone)]
+pub struct MethodCallee {
+    pub kind: CalleeKind,
+    pub self_param: SelfKind,   // 받는 쪽 매개변수의 형태 (`&self`면 받는 쪽을 빌립니다)
+    pub derefs: usize,          // 받는 쪽 식에서 벗길 참조의 수
+}
+
+#[derive(Debug, Clone)]
+pub enum CalleeKind {
+    // 고유 메서드 `Ty::<impl_args>::name::<fn_args>`
+    Inherent { ty: String, impl_args: Vec<Ty>, name: String, fn_args: Vec<Ty> },
+    // 트레이트 메서드 `<self_ty as Trait>::name::<fn_args>`
+    Trait { self_ty: Ty, trait_name: String, name: String, fn_args: Vec<Ty> },
+    // 배열과 슬라이스의 `iter()`, `iter_mut()`는 받는 쪽을 빌린 값 그대로입니다.
+    Borrow,
+}
+
+// 크레이트의 모든 함수 본문을 검사하고 지역 변수의 타입을 추론합니다.
+// 메서드 호출은 추론한 받는 쪽 타입으로 찾고, `write_back`이 경로 호출로 바꿀 수 있게 결과에 남깁니다.
+pub fn check_crate(krate: &Crate, resolutions: &Resolutions) -> Result<TypeckResults, Vec<TypeError>> {
+    let (results, errors) = check::check_crate(krate, resolutions);
+    if errors.is_empty() {
+        Ok(results)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusttc_ast::ast::{CaptureMode, Closure, FnDef, Item, Local, NodeKind, StmtKind};
+    use rusttc_ast::pretty::{expr_to_string, stmt_to_string};
+    use rusttc_expand::expand;
+    use rusttc_ir::ir::{lower_crate, BinaryExpression, Const, IrNode, IrStruct, UnaryExpression};
+    use rusttc_ir::method::resolve_methods;
+    use rusttc_ir::mono::monomorphize;
+    use rusttc_ir::modules::resolve_modules;
+    use rusttc_ir::resolve::resolve_names;
+    use rusttc_lexer::{source_map, tokenize, Token, TokenKind};
+    use rusttc_parse::parser::parse_crate;
+    use ty::IntTy;
+
+    fn check(source: &str) -> (Crate, Result<TypeckResults, Vec<TypeError>>) {
+        let mut krate = resolve_modules(&parse_crate(&expand(&tokenize(source)).unwrap()).unwrap()).unwrap();
+        let resolutions = resolve_names(&krate).unwrap();
+        resolve_methods(&mut krate).unwrap();
+        let result = check_crate(&krate, &resolutions);
+        (krate, result)
+    }
+
+    fn errors(source: &str) -> Vec<String> {
+        check(source).1.unwrap_err().into_iter().map(|error| error.message).collect()
+    }
+
+    // 소스에서 `text`가 처음 나오는 위치입니다.
+    fn span_of(source: &str, text: &str) -> Span {
+        let lo = source.find(text).unwrap() as u32;
+        Span::new(lo, lo + text.len() as u32)
+    }
+
+    // `main` 본문의 `let` 바인딩 타입을 차례로 돌려줍니다.
+    fn main_locals(source: &str) -> Vec<String> {
+        let (krate, result) = check(source);
+        let results = result.unwrap();
+        let main = krate.items.iter().find_map(|item| match item {
+            Item::Fn(function) if function.name == "main" => function.body.as_ref(),
+            _ => None,
+        });
+        main.unwrap()
+            .stmts
+            .iter()
+            .filter_map(|&stmt| match &krate.arena[stmt].kind {
+                StmtKind::Let(local) => Some(results.node_types[&local.pat.id].to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_infer_locals_from_later_use() {
+        let source = "fn main() { let x = 1; let y: u8 = x; let z = (x, true); let w = [1, 2]; }";
+        assert_eq!(main_locals(source), ["u8", "u8", "(u8, bool)", "[i32; 2]"]);
+    }
+
+    #[test]
+    fn test_mismatch_points_at_value_and_annotation() {
+        let source = "fn main() { let x: i32 = true; }";
+        let errors = check(source).1.unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "error: mismatched types: expected `i32`, found `bool`");
+        assert_eq!(errors[0].span, span_of(source, "true"));
+        assert_eq!(errors[0].expected_span, Some(span_of(source, "i32")));
+    }
+
+    #[test]
+    fn test_error_renders_locations_from_source_map() {
+        let source = "fn main() {\n    let x: i32 =\n        true;\n}";
+        source_map::clear();
+        assert_eq!(source_map::add_file("main.rs", source), 0);
+        let errors = check(source).1.unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "error: mismatched types: expected `i32`, found `bool`\n  --> main.rs:3:9\n  = note: expected due to the type at main.rs:2:12",
+        );
+
+        // 다른 파일의 같은 오프셋은 그 파일 기준으로 셉니다.
+        let other = source_map::add_file("other.rs", &"// a\n".repeat(10));
+        let error = TypeError { span: errors[0].span.with_file(other), expected_span: None, ..errors[0].clone() };
+        assert!(error.to_string().ends_with("\n  --> other.rs:8:3"), "{}", error);
+        source_map::clear();
+    }
+
+    #[test]
+    fn test_operator_operands() {
+        assert_eq!(
+            errors("fn main() { let a = 1 + true; let b = true + 1; }")
+                .into_iter()
+                .take(2)
+                .collect::<Vec<_>>(),
+            ["mismatched types: expected `{integer}`, found `bool`", "binary operation `+` cannot be applied to type `bool`"],
+        );
+        assert_eq!(errors("fn main() { let a = 1 < 2 && 3; }"), ["mismatched types: expected `bool`, found `{integer}`"]);
+        assert_eq!(
+            errors("fn main() { let x: u32 = 1; let y = -x; }"),
+            ["cannot apply unary operator `-` to type `u32`"],
+        );
+        assert_eq!(
+            errors("struct P { x: i32 } fn main() { let mut p = P { x: 1 }; p += 1; }"),
+            ["binary assignment operation `+=` cannot be applied to type `P`"],
+        );
+    }
+
+    #[test]
+    fn test_calls_and_returns() {
+        let source = "fn add(a: i32, b: i64) -> i32 { a } fn main() { let x = add(1, true); add(1); }";
+        assert_eq!(
+            errors(source),
+            ["mismatched types: expected `i64`, found `bool`", "this function takes 2 arguments but 1 argument was supplied"],
+        );
+
+        let source = "fn add(a: i32, b: i64) -> i32 { b }";
+        let errors = check(source).1.unwrap_err();
+        assert_eq!(errors[0].message, "mismatched types: expected `i32`, found `i64`");
+        assert_eq!(errors[0].span, Span::new(source.rfind('b').unwrap() as u32, source.rfind('b').unwrap() as u32 + 1));
+        assert_eq!(errors[0].expected_span, Some(span_of(source, "i32 {")).map(|span| Span::new(span.lo, span.lo + 3)));
+    }
+
+    #[test]
+    fn test_methods_generics_and_closures() {
+        let source = "
+            struct Pair<T> { a: T, b: T }
+            impl<T> Pair<T> {
+                fn new(a: T, b: T) -> Self { Pair { a: a, b: b } }
+                fn first(&self) -> &T { &self.a }
+            }
+            fn apply<F: Fn(i32) -> i32>(f: F, x: i32) -> i32 { f(x) }
+            fn main() {
+                let s: u8 = 1;
+                let p = Pair::new(s, 2);
+                let a = p.first();
+                let n = apply(|x| x * 2, 3);
+                let c = |y| y + 1;
+                let e: u64 = 5;
+                let d = c(e);
+            }
+        ";
+        assert_eq!(main_locals(source), ["u8", "Pair<u8>", "&u8", "i32", "{closure `main::{closure#1}`}", "u64", "u64"]);
+    }
+
+    #[test]
+    fn test_never_and_missing_tail() {
+        assert!(check("fn f(x: bool) -> i32 { if x { return 1; } loop {} } fn main() {}").1.is_ok());
+        assert!(check("fn f(x: bool) -> i32 { let y = if x { 1 } else { panic!(\"no\") }; y } fn main() {}").1.is_ok());
+        assert_eq!(errors("fn f() -> i32 { let x = 1; } fn main() {}"), ["mismatched types: expected `i32`, found `()`"]);
+        assert_eq!(
+            errors("fn main() { let x = if true { 1 } else { false }; if true { 1 } }"),
+            [
+                "`if` and `else` have incompatible types: expected `{integer}`, found `bool`",
+                "`if` may be missing an `else` clause: expected `()`, found `{integer}`",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_calling_values() {
+        let source = "fn f(x: i32) {} fn id<T>(x: T) -> T { x } fn main() { let g = f; g(1); let h = id; let a: u8 = h(2); }";
+        assert_eq!(main_locals(source), ["fn(i32)", "fn(u8) -> u8", "u8"]);
+        assert_eq!(
+            errors("fn f(x: i32) {} fn main() { let x = true; x(1, 2); let g = f; g(true); g(); }"),
+            [
+                "expected function, found `bool`",
+                "mismatched types: expected `i32`, found `bool`",
+                "this function takes 1 argument but 0 arguments were supplied",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_annotations_needed() {
+        assert_eq!(
+            errors("fn main() { let v = vec![]; }"),
+            ["type annotations needed: consider giving `v` an explicit type"],
+        );
+        let results = check("fn main() { let v = vec![]; let n: i8 = v[0]; }").1.unwrap();
+        assert!(results.node_types.values().any(|ty| *ty == Ty::Adt("Vec".to_string(), vec![Ty::Int(IntTy::I8)])));
+    }
+
+    #[test]
+    fn test_literal_types_from_suffix_and_context() {
+        let source = "fn main() { let a: u8 = 1; let b = 2u16; let c = 1.5; let d: f32 = 2.0; let e = 'x'; let f = 3; }";
+        assert_eq!(main_locals(source), ["u8", "u16", "f64", "f32", "char", "i32"]);
+        assert_eq!(
+            errors("fn main() { let a = 1.5 & 2.0; let b = !1.0; let c: f32 = 1; }"),
+            [
+                "binary operation `&` cannot be applied to type `{float}`",
+                "cannot apply unary operator `!` to type `{float}`",
+                "mismatched types: expected `f32`, found `{integer}`",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_literal_out_of_range() {
+        assert!(check("fn main() { let a: u8 = 255; let b: i8 = -128; let c = 0xffu8; }").1.is_ok());
+        assert_eq!(
+            errors("fn main() { let a: u8 = 256; let b: i8 = -129; let c = 3e40f32; }"),
+            [
+                "literal out of range for `u8`: the literal `256` does not fit into the type `u8` whose range is `0..=255`",
+                "literal out of range for `i8`: the literal `-129` does not fit into the type `i8` whose range is `-128..=127`",
+                "literal out of range for `f32`: the literal `3e40` does not fit into the type `f32` whose range is `-3.40282347e38..=3.40282347e38`",
+            ],
+        );
+
+        // 소스를 알면 값이 아니라 소스에 쓴 그대로 인용합니다.
+        let source = "fn main() { let a = 1e400; let b: u8 = 1_000; }";
+        source_map::clear();
+        source_map::add_file("main.rs", source);
+        assert_eq!(
+            errors(source),
+            [
+                "literal out of range for `f64`: the literal `1e400` does not fit into the type `f64` whose range is `-1.7976931348623157e308..=1.7976931348623157e308`",
+                "literal out of range for `u8`: the literal `1_000` does not fit into the type `u8` whose range is `0..=255`",
+            ],
+        );
+        source_map::clear();
+    }
+
+    #[test]
+    fn test_write_back_literals_and_generic_args() {
+        let source = "fn id<T>(x: T) -> T { x } fn main() { let z = id(2); let w: u64 = z; let f = 1.0; }";
+        let (mut krate, result) = check(source);
+        write_back(&mut krate, &result.unwrap());
+        let pretty = rusttc_ast::pretty::crate_to_string(&krate);
+        assert!(pretty.contains("id::<u64>(2u64)"), "{}", pretty);
+        assert!(pretty.contains("1.0f64"), "{}", pretty);
+    }
+
+    #[test]
+    fn test_const_array_len() {
+        let source = "const N: usize = 4 * 16; mod m { pub const K: usize = super::N / 8; } \
+                      fn main() { let a = [0; N]; let b = [0u8; m::K + 1]; let c = N; let d = [true; { let x = 2; x * N }]; }";
+        assert_eq!(main_locals(source), ["[i32; 64]", "[u8; 9]", "usize", "[bool; 128]"]);
+    }
+
+    #[test]
+    fn test_expanded_output_round_trips() {
+        // 매크로가 묶은 `x`는 `x#1`이 되고, 출력할 때는 이미 있는 `x_1`과 겹치지 않는 이름이 됩니다.
+        let source = "macro_rules! twice { ($e:expr) => {{ let x = $e; x + x }} } \
+                      fn main() { let x = 1u8; let x_1 = 2u8; let y = twice!(x_1 + x); }";
+        let krate = parse_crate(&expand(&tokenize(source)).unwrap()).unwrap();
+        let printed = rusttc_ast::pretty::expanded_crate_to_string(&krate);
+        assert!(!printed.contains('#'), "{}", printed);
+        assert!(printed.contains("let x_1_ = x_1 + x;"), "{}", printed);
+
+        // 출력은 다시 파싱하면 같은 모양이고, 같은 타입으로 검사됩니다.
+        let reparsed = parse_crate(&expand(&tokenize(&printed)).unwrap()).unwrap();
+        assert_eq!(rusttc_ast::pretty::crate_to_string(&reparsed), printed);
+        assert_eq!(main_locals(&printed), main_locals(source));
+        assert_eq!(main_locals(&printed), ["u8", "u8", "u8"]);
+    }
+
+    #[test]
+    fn test_const_array_len_in_types() {
+        let source = "const N: usize = 4; const G: [i32; N] = [1; N]; struct S { a: [u8; N * 2] } \
+                      fn f(x: [bool; N]) -> [i32; N] { [0; N] } \
+                      fn main() { let a: [i32; N] = f([true; 4]); let s = S { a: [0; 8] }; let b = G; let c: [u8; 3usize] = [0; 3]; }";
+        assert_eq!(main_locals(source), ["[i32; 4]", "S", "[i32; 4]", "[u8; 3]"]);
+
+        // 쓰기 단계가 길이 식을 계산한 길이로 바꿔 씁니다.
+        let (mut krate, result) = check(source);
+        writeback::write_back(&mut krate, &result.unwrap());
+        let printed = rusttc_ast::pretty::crate_to_string(&krate);
+        assert!(printed.contains("const G: [i32; 4] = [1i32; N];"), "{}", printed);
+        assert!(printed.contains("fn f(x: [bool; 4]) -> [i32; 4]"), "{}", printed);
+
+        let source = "const N: usize = 4; fn main() { let a: [i32; N] = [0; 3]; let n = 2; let b: [i32; n] = [0; 2]; }";
+        assert_eq!(
+            errors(source),
+            [
+                "attempt to use a non-constant value in a constant",
+                "mismatched types: expected `[i32; 4]`, found `[{integer}; 3]`",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_const_negated_min_literals() {
+        let source = "const A: i32 = -2147483648; const B: i8 = -128i8; static C: i64 = -9223372036854775808; \
+                      const D: i32 = A + 1; fn main() { let a = [0; 2]; }";
+        let (_, result) = check(source);
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
+    #[test]
+    fn test_const_eval_errors() {
+        let source = "const A: u8 = 255 + 1; const B: i32 = 1 / 0; const C: i32 = [1, 2][2]; const D: usize = E; const E: usize = D; \
+                      static S: i32 = -(-2147483647 - 1); fn main() {}";
+        assert_eq!(
+            errors(source),
+            [
+                "evaluation of constant value failed: attempt to compute `u8::MAX + 1_u8`, which would overflow",
+                "evaluation of constant value failed: attempt to divide `1_i32` by zero",
+                "evaluation of constant value failed: evaluation panicked: index out of bounds: the len is 2 but the index is 2",
+                "cycle detected when const-evaluating + checking `D`",
+                "could not evaluate static initializer: attempt to negate `i32::MIN`, which would overflow",
+            ],
+        );
+        let source = "static S: usize = 1; fn f() -> usize { 1 } const A: usize = S; const B: usize = f(); \
+                      fn main() { let n = 2; let a = [0; n]; let b = [0; 1 - 2]; }";
+        assert_eq!(
+            errors(source),
+            [
+                "constants cannot refer to statics",
+                "cannot call non-const fn `f` in constants",
+                "attempt to use a non-constant value in a constant",
+                "evaluation of constant value failed: attempt to compute `1_usize - 2_usize`, which would overflow",
+            ],
+        );
+    }
+
+    // 검사를 통과한 크레이트에 결과를 다시 씁니다. 메서드 호출은 경로 호출이 됩니다.
+    fn written(source: &str) -> Crate {
+        let (mut krate, result) = check(source);
+        write_back(&mut krate, &result.unwrap());
+        krate
+    }
+
+    fn find_fn<'a>(krate: &'a Crate, name: &str) -> &'a FnDef {
+        krate
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Item::Fn(function) if function.name == name => Some(function),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    // 함수 본문의 `i`번째 문장을 소스로 출력합니다.
+    fn stmt(krate: &Crate, function: &FnDef, i: usize) -> String {
+        stmt_to_string(&krate.arena, function.body.as_ref().unwrap().stmts[i])
+    }
+
+    // 함수 본문의 `let`으로 묶은 클로저들입니다.
+    fn closures<'a>(krate: &'a Crate, function: &FnDef) -> Vec<&'a Closure> {
+        function
+            .body
+            .as_ref()
+            .unwrap()
+            .stmts
+            .iter()
+            .filter_map(|&stmt| match &krate.arena[stmt].kind {
+                StmtKind::Let(Local { init: Some(init), .. }) => match &krate.arena[*init].kind {
+                    NodeKind::Closure(closure) => Some(closure.as_ref()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    const POINT: &str = "
+        struct Point { x: i32 }
+        impl Point {
+            fn new(x: i32) -> Self { Self { x } }
+            fn len(&self) -> i32 { self.x }
+            fn grow(&mut self) { self.x += 1; }
+            fn into_x(self) -> i32 { self.x }
+        }
+    ";
+
+    const SHAPES: &str = "
+        struct Circle { r: i32 }
+        struct Square { side: i32 }
+        trait Shape {
+            fn area(&self) -> i32;
+            fn describe(&self) -> i32 { self.area() + 1 }
+        }
+        impl Shape for Circle {
+            fn area(&self) -> i32 { 3 * self.r * self.r }
+        }
+    ";
+
+    #[test]
+    fn test_method_calls_become_path_calls() {
+        let source = format!("{} fn main() {{ let mut p = Point::new(1); p.len(); p.grow(); Point::new(1).len(); }} \
+                              fn get(p: &Point) -> i32 {{ p.len() + p.into_x() }}", POINT);
+        let krate = written(&source);
+        let main = find_fn(&krate, "main");
+        assert_eq!(stmt(&krate, main, 1), "Point::len(&p);");
+        assert_eq!(stmt(&krate, main, 2), "Point::grow(&mut p);");
+        assert_eq!(stmt(&krate, main, 3), "Point::len(&Point::new(1i32));");
+        assert_eq!(stmt(&krate, find_fn(&krate, "get"), 0), "Point::len(p) + Point::into_x(*p)");
+    }
+
+    #[test]
+    fn test_method_receivers_use_inferred_types() {
+        // 받는 쪽의 타입은 이름 해석이 아니라 추론으로 정해집니다 (유닛 구조체, 나중에 초기화한 변수).
+        let source = "struct C; impl C { fn a(&self) {} } fn main() { let c = C; c.a(); }";
+        let krate = written(source);
+        assert_eq!(stmt(&krate, find_fn(&krate, "main"), 1), "C::a(&c);");
+
+        let source = format!("{} fn main() {{ let p; p = Point {{ x: 1 }}; p.len(); }}", POINT);
+        let krate = written(&source);
+        assert_eq!(stmt(&krate, find_fn(&krate, "main"), 2), "Point::len(&p);");
+    }
+
+    #[test]
+    fn test_trait_methods_are_statically_dispatched() {
+        let source = format!("{} fn show<T: Shape>(s: &T) -> i32 {{ s.area() }} \
+                              fn main() {{ let c = Circle {{ r: 2 }}; c.describe(); show(&c); }}", SHAPES);
+        let krate = written(&source);
+        assert_eq!(stmt(&krate, find_fn(&krate, "main"), 1), "<Circle as Shape>::describe(&c);");
+        assert_eq!(stmt(&krate, find_fn(&krate, "show"), 0), "<T as Shape>::area(s)");
+
+        // 기본 메서드는 impl 블록마다 복사된 본문에서 구현 타입의 메서드를 부릅니다.
+        let Item::Impl(imp) = &krate.items[3] else { panic!() };
+        assert_eq!(stmt(&krate, &imp.items[1], 0), "<Circle as Shape>::area(self) + 1i32");
+        let Item::Trait(def) = &krate.items[2] else { panic!() };
+        assert_eq!(stmt(&krate, &def.items[1], 0), "self.area() + 1");
+    }
+
+    #[test]
+    fn test_trait_methods_on_primitives_and_tuples() {
+        let source = "
+            trait Double { fn double(&self) -> i32; }
+            impl Double for i32 { fn double(&self) -> i32 { *self * 2 } }
+            impl Double for (i32, i32) { fn double(&self) -> i32 { self.0 + self.1 } }
+            fn show<T: Double>(t: &T) -> i32 { t.double() }
+            fn main() { let x: i32 = 4; x.double(); let p = (1, 2); p.double(); show(&p); }
+        ";
+        let krate = written(source);
+        let main = find_fn(&krate, "main");
+        assert_eq!(stmt(&krate, main, 1), "<i32 as Double>::double(&x);");
+        assert_eq!(stmt(&krate, main, 3), "<(i32, i32) as Double>::double(&p);");
+
+        let source = "
+            trait Double { fn double(&self) -> i32; }
+            impl Double for i32 { fn double(&self) -> i32 { *self * 2 } }
+            fn show<T: Double>(t: T) -> i32 { t.double() }
+            fn main() { 5.double(); show(true); }
+        ";
+        assert_eq!(errors(source), [
+            "can't call method `double` on ambiguous numeric type `{integer}`",
+            "the trait bound `bool: Double` is not satisfied",
+        ]);
+    }
+
+    #[test]
+    fn test_method_errors() {
+        let source = format!("{} fn main() {{ let p = Point::new(1); p.area(); p.new(2); p.len(3); }}", POINT);
+        assert_eq!(errors(&source), [
+            "no method named `area` found for struct `Point` in the current scope",
+            "no method named `new` found for struct `Point`: `Point::new` is an associated function, not a method",
+            "this method takes 0 arguments but 1 argument was supplied",
+        ]);
+
+        let source = format!("{} fn show<T: Shape>(s: &T) -> i32 {{ s.area() }} \
+                              fn main() {{ let sq = Square {{ side: 1 }}; show(&sq); sq.area(); }}", SHAPES);
+        assert_eq!(errors(&source), [
+            "the trait bound `Square: Shape` is not satisfied",
+            "no method named `area` found for struct `Square` in the current scope",
+        ]);
+    }
+
+    #[test]
+    fn test_private_fields_and_methods() {
+        let module = "
+            mod a {
+                pub struct S { pub y: i32, x: i32 }
+                impl S {
+                    pub fn new() -> S { S { y: 0, x: 1 } }
+                    fn hidden(&self) -> i32 { self.x }
+                    fn make() -> S { S::new() }
+                }
+                pub fn peek(s: &S) -> i32 { s.hidden() + s.x }
+                pub mod inner { pub fn get(s: &super::S) -> i32 { s.x } }
+            }";
+        // 선언된 모듈과 그 하위 모듈에서는 비공개 필드와 메서드를 쓸 수 있습니다.
+        assert!(check(&format!("{} fn main() {{ let s = a::S::new(); s.y; a::peek(&s); a::inner::get(&s); }}", module)).1.is_ok());
+        let source = format!("{} fn main() {{ let s = a::S {{ y: 1, x: 2 }}; s.x; s.hidden(); a::S::make(); }}", module);
+        assert_eq!(errors(&source), [
+            "field `x` of struct `a::S` is private",
+            "field `x` of struct `a::S` is private",
+            "method `hidden` is private",
+            "associated function `make` is private",
+        ]);
+    }
+
+    #[test]
+    fn test_generic_calls_record_inferred_arguments() {
+        let source = "
+            struct Pair<T> { a: T, b: T }
+            impl<T> Pair<T> { fn new(a: T, b: T) -> Self { Pair { a, b } } fn first(&self) -> T { self.a } }
+            fn max<T: Ord>(a: T, b: T) -> T { if a > b { a } else { b } }
+            fn main() { let x: u8 = 1; max(2, x); let p = Pair::new(true, false); p.first(); }
+        ";
+        let krate = written(source);
+        let main = find_fn(&krate, "main");
+        assert_eq!(stmt(&krate, main, 1), "max::<u8>(2u8, x);");
+        assert_eq!(stmt(&krate, main, 2), "let p = Pair::<bool>::new(true, false);");
+        assert_eq!(stmt(&krate, main, 3), "Pair::<bool>::first(&p);");
+    }
+
+    #[test]
+    fn test_generic_call_errors() {
+        let source = format!("
+            {}
+            struct Pair<T> {{ a: T, b: T }}
+            fn max<T: Ord>(a: T, b: T) -> T {{ a }}
+            fn make<T>() -> i32 {{ 0 }}
+            fn main() {{ max(Circle {{ r: 1 }}, Circle {{ r: 2 }}); max::<i32, i32>(1, 2); make(); Pair::<i32, bool> {{ a: 1, b: 2 }}; }}
+        ", SHAPES);
+        // 추론해야 하는 타입 인자는 함수 검사가 끝날 때 확인합니다.
+        assert_eq!(errors(&source), [
+            "the trait bound `Circle: Ord` is not satisfied",
+            "function takes 1 generic argument but 2 generic arguments were supplied",
+            "struct takes 1 generic argument but 2 generic arguments were supplied",
+            "type annotations needed: cannot infer type of the type parameter `T` declared on `make`",
+        ]);
+    }
+
+    #[test]
+    fn test_closure_capture_modes() {
+        let source = format!("{} fn main() {{
+            let n = 1;
+            let mut count = 0;
+            let p = Point::new(2);
+            let read = |x| x + n;
+            let bump = || count += n;
+            let consume = || p.into_x();
+            let owned = move || n + count;
+            let shadow = |n: i32| {{ let count = n; count }};
+            read(1); bump(); consume(); owned(); shadow(1);
+        }}", POINT);
+        let krate = written(&source);
+        let summary: Vec<_> = closures(&krate, find_fn(&krate, "main"))
+            .into_iter()
+            .map(|closure| {
+                let captures: Vec<(&str, CaptureMode)> = closure.captures.iter().map(|capture| (capture.name.as_str(), capture.mode)).collect();
+                (closure.name.as_str(), closure.kind, captures)
+            })
+            .collect();
+        assert_eq!(summary, vec![
+            ("main::{closure#0}", ClosureKind::Fn, vec![("n", CaptureMode::ByRef)]),
+            ("main::{closure#1}", ClosureKind::FnMut, vec![("count", CaptureMode::ByMutRef), ("n", CaptureMode::ByRef)]),
+            ("main::{closure#2}", ClosureKind::FnOnce, vec![("p", CaptureMode::ByValue)]),
+            ("main::{closure#3}", ClosureKind::Fn, vec![("n", CaptureMode::ByValue), ("count", CaptureMode::ByValue)]),
+            ("main::{closure#4}", ClosureKind::Fn, vec![]),
+        ]);
+    }
+
+    #[test]
+    fn test_closure_captures_through_match() {
+        // 패턴과 비교만 하면 빌리고, Copy가 아닌 값을 바인딩하면 옮깁니다. 갈래의 바인딩은 캡처가 아닙니다.
+        let source = format!("{} fn main() {{
+            let n = 1;
+            let p = Point::new(2);
+            let q = (Point::new(3), 1);
+            let peek = || match n {{ 0 => 0, m => m }};
+            let split = || match q {{ (r, 1) => r.into_x(), _ => 0 }};
+            let check = || match p {{ Point {{ x: 0 }} => true, _ => false }};
+            peek(); split(); check();
+        }}", POINT);
+        let krate = written(&source);
+        let summary: Vec<_> = closures(&krate, find_fn(&krate, "main"))
+            .into_iter()
+            .map(|closure| (closure.kind, closure.captures.iter().map(|capture| (capture.name.as_str(), capture.mode)).collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(summary, vec![
+            (ClosureKind::Fn, vec![("n", CaptureMode::ByRef)]),
+            (ClosureKind::FnOnce, vec![("q", CaptureMode::ByValue)]),
+            (ClosureKind::Fn, vec![("p", CaptureMode::ByRef)]),
+        ]);
+    }
+
+    #[test]
+    fn test_closure_moves_depend_on_copy_impls() {
+        // `Copy`를 구현한 구조체는 값으로 써도 읽기이고, 그렇지 않은 구조체는 이동입니다.
+        let source = "
+            #[derive(Clone, Copy)] struct A { x: i32 }
+            struct B { x: i32 }
+            impl Copy for B {}
+            struct C { x: i32 }
+            fn take<T>(t: T) {}
+            fn main() {
+                let a = A { x: 1 }; let b = B { x: 2 }; let c = C { x: 3 };
+                let f = || take(a); let g = || take(b); let h = || take(c);
+                f(); g(); h();
+            }
+        ";
+        let krate = written(source);
+        let kinds: Vec<(ClosureKind, CaptureMode)> =
+            closures(&krate, find_fn(&krate, "main")).iter().map(|closure| (closure.kind, closure.captures[0].mode)).collect();
+        assert_eq!(kinds, [
+            (ClosureKind::Fn, CaptureMode::ByRef),
+            (ClosureKind::Fn, CaptureMode::ByRef),
+            (ClosureKind::FnOnce, CaptureMode::ByValue),
+        ]);
+        assert!(check("#[derive(Copy, Clone)] struct A { x: i32 } fn dup<T: Copy>(t: T) {} fn main() { dup(A { x: 1 }); }").1.is_ok());
+    }
+
+    #[test]
+    fn test_closure_calls_use_fn_traits() {
+        let source = "
+            fn apply<F: Fn(i32) -> i32>(f: F, x: i32) -> i32 { f(x) }
+            fn once<F: FnOnce() -> i32>(f: F) -> i32 { f() }
+            fn main() { let mut total = 0; let mut add = |x: i32| total += x; add(1); apply(|x| x * 2, 3); once(|| total); }
+        ";
+        let krate = written(source);
+        assert_eq!(stmt(&krate, find_fn(&krate, "apply"), 0), "<F as Fn()>::call(&f, x)");
+        assert_eq!(stmt(&krate, find_fn(&krate, "once"), 0), "<F as FnOnce()>::call_once(f)");
+
+        let main = find_fn(&krate, "main");
+        assert_eq!(stmt(&krate, main, 2), "<main::{closure#0} as FnMut()>::call_mut(&mut add, 1i32);");
+        assert_eq!(stmt(&krate, main, 3), "apply::<main::{closure#1}>(|x| x * 2i32, 3i32);");
+    }
+
+    #[test]
+    fn test_closure_kind_must_satisfy_bound() {
+        let source = "
+            fn apply<F: Fn()>(f: F) { f() }
+            fn main() { let mut n = 0; apply(|| n += 1); }
+        ";
+        assert_eq!(errors(source), ["the trait bound `main::{closure#0}: Fn` is not satisfied"]);
+    }
+
+    #[test]
+    fn test_array_methods_and_for_loops() {
+        let source = format!("{} fn first<T: Copy>(a: &[T; 3]) -> T {{ a[0] }} fn main() {{
+            let t = (1, [true, false, true]);
+            first(&t.1);
+            let points = [Point::new(1)];
+            for p in points.iter() {{ p.len(); }}
+        }}", POINT);
+        let krate = written(&source);
+        let main = find_fn(&krate, "main");
+        assert_eq!(stmt(&krate, main, 1), "first::<bool>(&t.1);");
+        let StmtKind::Expr(for_loop) = krate.arena[main.body.as_ref().unwrap().stmts[3]].kind else { panic!() };
+        let NodeKind::ForLoop(for_loop) = &krate.arena[for_loop].kind else { panic!() };
+        assert_eq!(expr_to_string(&krate.arena, for_loop.iter), "&points");
+        assert_eq!(stmt_to_string(&krate.arena, for_loop.body.stmts[0]), "Point::len(p);");
+
+        let source = format!("{} fn main() {{ for i in 0..3 {{ i.len(); }} }}", POINT);
+        assert_eq!(errors(&source), ["no method named `len` found for type `{integer}` in the current scope"]);
+    }
+
+    #[test]
+    fn test_match_types_and_exhaustiveness() {
+        let source = "struct P { x: i32, y: bool } fn main() {
+            let a = match 3u8 { 0 => 'a', 1..=9 | 10 => 'b', _ => return };
+            let b = match (P { x: 1, y: true }) { P { x: 0, .. } => 0, P { x, y: true } => x, P { y: false, .. } => -1 };
+            let c = match (1, -1) { (n, -128..=-1) if n > 0 => n, (n, _) => n + 1i64 };
+        }";
+        assert_eq!(main_locals(source), ["char", "i32", "i64"]);
+
+        let errors = |body: &str| super::tests::errors(&format!("struct P {{ x: i32, y: bool }} fn main() {{ {} }}", body));
+        assert_eq!(errors("match 1u8 { 0..=99 => {} 101..=255 => {} }"), ["non-exhaustive patterns: `100_u8` not covered"]);
+        assert_eq!(errors("match (true, P { x: 1, y: true }) { (true, _) => {} (false, P { y: true, .. }) => {} }"), [
+            "non-exhaustive patterns: `(false, P { y: false, .. })` not covered"
+        ]);
+        assert_eq!(errors("let x = match 1 { 0 => 1, _ => true };"), ["`match` arms have incompatible types: expected `{integer}`, found `bool`"]);
+        assert_eq!(errors("match true { 1 => {} _ => {} }"), ["mismatched types: expected `bool`, found `{integer}`"]);
+        assert_eq!(errors("match (1, 2) { (a, b, c) => {} }"), ["mismatched types: expected `({integer}, {integer})`, found `(_, _, _)`"]);
+        assert_eq!(errors("match (P { x: 1, y: true }) { P { x } => {} }"), ["pattern does not mention field `y`"]);
+        assert_eq!(errors("match true { false..=true => {} }"), ["only `char` and numeric types are allowed in range patterns"]);
+        assert_eq!(errors("match 1 { 5..=1 => {} _ => {} }"), ["lower range bound must be less than or equal to upper"]);
+        assert_eq!(errors("match 300u8 { _ => {} }"), [
+            "literal out of range for `u8`: the literal `300` does not fit into the type `u8` whose range is `0..=255`"
+        ]);
+
+        // 앞 갈래가 모두 덮은 갈래는 경고만 하고, 가드가 붙은 갈래는 뒤 갈래를 가리지 않습니다.
+        let source = "fn main() { let n = 5; match n { x if x > 1 => {} 0 | _ => {} 3 => {} } }";
+        let results = check(source).1.unwrap();
+        assert_eq!(results.warnings, [TypeWarning { message: "unreachable pattern".to_string(), span: span_of(source, "3") }]);
+        assert_eq!(results.warnings[0].to_string(), "warning: unreachable pattern");
+    }
+
+    #[test]
+    fn test_lower_match_to_if_chain() {
+        let module = lower_crate(&written("fn main() { let t = (1, true); let v = match t { (0, _) => 1, (n, true) => n, _ => 2 }; }"));
+        let IrNode::Block { stmts, .. } = &module.functions[0].body else { panic!() };
+        let IrNode::Let { value: Some(value), .. } = &stmts[1] else { panic!() };
+        let IrNode::Block { stmts, value: Some(chain) } = &**value else { panic!() };
+        assert!(matches!(&stmts[..], [IrNode::Let { name, .. }] if name == "scrutinee#0"));
+
+        // 첫 갈래는 `scrutinee#0.0 == 0`, 둘째 갈래는 `scrutinee#0.1 == true`를 확인한 뒤 `n`을 묶고, `_`는 마지막 `else`입니다.
+        let IrNode::If { cond, els: Some(els), .. } = &**chain else { panic!() };
+        let IrNode::BinaryExpression(first) = &**cond else { panic!() };
+        let field = |i: &str| IrNode::Field { base: Box::new(IrNode::Variable("scrutinee#0".to_string())), field: i.to_string() };
+        assert_eq!(first.left, field("0"));
+        let IrNode::If { then, els: Some(last), .. } = &**els else { panic!() };
+        let IrNode::Block { stmts, value: Some(value) } = &**then else { panic!() };
+        assert!(matches!(&stmts[..], [IrNode::Let { name, value: Some(value), .. }] if name == "n" && **value == field("0")));
+        assert_eq!(**value, IrNode::Variable("n".to_string()));
+        assert_eq!(**last, IrNode::Block { stmts: Vec::new(), value: Some(Box::new(IrNode::Constant(Const::Int(2, Some(IntTy::I32))))) });
+    }
+
+    #[test]
+    fn test_lower_closure_to_env_struct_and_function() {
+        let source = "fn main() { let n = 1; let add = |x| x + n; add(2); }";
+        let module = lower_crate(&written(source));
+
+        let closure = "main::{closure#0}".to_string();
+        assert_eq!(module.structs, vec![IrStruct { name: closure.clone(), fields: vec!["n".to_string()] }]);
+        let names: Vec<&str> = module.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["main::{closure#0}", "main"]);
+
+        // 참조로 캡처한 `n`은 본문에서 `*__env.n`이 됩니다.
+        let add = &module.functions[0];
+        assert_eq!(add.params, vec!["env#0".to_string(), "x".to_string()]);
+        let env_n = IrNode::Field { base: Box::new(IrNode::Variable("env#0".to_string())), field: "n".to_string() };
+        assert_eq!(add.body, IrNode::BinaryExpression(Box::new(BinaryExpression {
+            left: IrNode::Variable("x".to_string()),
+            operator: Token::new(TokenKind::Plus, "+".to_string()),
+            right: IrNode::UnaryExpression(Box::new(UnaryExpression {
+                operator: Token::new(TokenKind::Star, "*".to_string()),
+                operand: env_n,
+            })),
+        })));
+
+        let IrNode::Block { stmts, .. } = &module.functions[1].body else { panic!() };
+        let n = IrNode::Ref { mutable: false, place: Box::new(IrNode::Variable("n".to_string())) };
+        assert_eq!(stmts[1], IrNode::Let {
+            name: "add".to_string(),
+            value: Some(Box::new(IrNode::StructInit { name: closure.clone(), fields: vec![("n".to_string(), n)] })),
+            span: span_of(source, "let add = |x| x + n;"),
+        });
+        let add = IrNode::Ref { mutable: false, place: Box::new(IrNode::Variable("add".to_string())) };
+        assert_eq!(stmts[2], IrNode::Call {
+            func: closure,
+            args: vec![add, IrNode::Constant(Const::Int(2, Some(IntTy::I32)))],
+            span: span_of(source, "add(2)"),
+        });
+    }
+
+    #[test]
+    fn test_closures_are_renamed_per_instance() {
+        let krate = monomorphize(&written("
+            fn apply<F: Fn(i32) -> i32>(f: F, x: i32) -> i32 { f(x) }
+            fn scale<T: Copy>(t: T, x: i32) -> i32 { apply(|y| y * 2, x) }
+            fn main() { scale(true, 1); scale(1, 2); }
+        ")).unwrap();
+        let names: Vec<&str> = krate
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Fn(function) => Some(function.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, vec![
+            "main",
+            "scale::<bool>",
+            "scale::<i32>",
+            "apply::<scale::<bool>::{closure#0}>",
+            "apply::<scale::<i32>::{closure#0}>",
+        ]);
+        let apply = find_fn(&krate, "apply::<scale::<i32>::{closure#0}>");
+        assert_eq!(stmt(&krate, apply, 0), "<scale::<i32>::{closure#0} as Fn()>::call(&f, x)");
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt;
+
+pub use rusttc_ast::ast::{FloatTy, IntTy};
+
+// 추론 중인 타입 변수의 번호입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TyVid(pub u32);
+
+// 아직 정해지지 않은 타입입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InferTy {
+    Var(TyVid),                 // 어떤 타입이든 될 수 있습니다
+    IntVar(TyVid),              // 정수 리터럴: 정수 타입만 될 수 있고, 끝까지 모르면 `i32`입니다
+    FloatVar(TyVid),            // 실수 리터럴: 실수 타입만 될 수 있고, 끝까지 모르면 `f64`입니다
+}
+
+// 타입 검사기가 다루는 타입입니다. AST의 타입 표기는 `check::lower_ty`로 이렇게 바뀝니다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ty {
+    Bool,
+    Int(IntTy),
+    Float(FloatTy),
+    Char,
+    Str,                        // `str` (항상 참조 뒤에 옵니다)
+    Adt(String, Vec<Ty>),       // 구조체와 표준 라이브러리 타입 (`Point`, `Range<i32>`)
+    Param(String),              // 타입 매개변수 (`T`, 트레이트 본문의 `Self`)
+    Ref(bool, Box<Ty>),         // &T, &mut T
+    Array(Box<Ty>, usize),      // [T; 3]
+    Slice(Box<Ty>),             // [T]
+    Tuple(Vec<Ty>),             // (T, U), `()`는 빈 튜플
+    Closure(String),            // 클로저 (이름으로 시그니처를 찾습니다)
+    FnPtr(Vec<Ty>, Box<Ty>),    // 값으로 쓴 함수 (`let g = f;`의 `g`): 매개변수와 반환 타입
+    Never,                      // `!`: return, break, 패닉처럼 값을 내지 않는 식
+    Infer(InferTy),             // 타입 변수
+    Error,                      // 이미 오류를 보고한 타입으로, 어떤 타입과도 맞습니다
+}
+
+impl Ty {
+    pub fn unit() -> Ty {
+        Ty::Tuple(Vec::new())
+    }
+
+    pub fn is_unit(&self) -> bool {
+        matches!(self, Ty::Tuple(tys) if tys.is_empty())
+    }
+
+    pub fn usize() -> Ty {
+        Ty::Int(IntTy::Usize)
+    }
+
+    pub fn ref_to(mutable: bool, ty: Ty) -> Ty {
+        Ty::Ref(mutable, Box::new(ty))
+    }
+
+    // 참조를 모두 벗긴 타입입니다. 필드 접근과 인덱싱은 참조를 자동으로 따라갑니다.
+    pub fn peel_refs(&self) -> &Ty {
+        match self {
+            Ty::Ref(_, ty) => ty.peel_refs(),
+            _ => self,
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |tys: &[Ty]| tys.iter().map(Ty::to_string).collect::<Vec<_>>().join(", ");
+        match self {
+            Ty::Bool => write!(f, "bool"),
+            Ty::Int(ty) => write!(f, "{}", ty.name()),
+            Ty::Float(ty) => write!(f, "{}", ty.name()),
+            Ty::Char => write!(f, "char"),
+            Ty::Str => write!(f, "str"),
+            Ty::Adt(name, args) if args.is_empty() => write!(f, "{}", name),
+            Ty::Adt(name, args) => write!(f, "{}<{}>", name, list(args)),
+            Ty::Param(name) => write!(f, "{}", name),
+            Ty::Ref(false, ty) => write!(f, "&{}", ty),
+            Ty::Ref(true, ty) => write!(f, "&mut {}", ty),
+            Ty::Array(ty, len) => write!(f, "[{}; {}]", ty, len),
+            Ty::Slice(ty) => write!(f, "[{}]", ty),
+            Ty::Tuple(tys) if tys.len() == 1 => write!(f, "({},)", tys[0]),
+            Ty::Tuple(tys) => write!(f, "({})", list(tys)),
+            Ty::Closure(name) => write!(f, "{{closure `{}`}}", name),
+            Ty::FnPtr(inputs, output) if output.is_unit() => write!(f, "fn({})", list(inputs)),
+            Ty::FnPtr(inputs, output) => write!(f, "fn({}) -> {}", list(inputs), output),
+            Ty::Never => write!(f, "!"),
+            Ty::Infer(InferTy::Var(_)) => write!(f, "_"),
+            Ty::Infer(InferTy::IntVar(_)) => write!(f, "{{integer}}"),
+            Ty::Infer(InferTy::FloatVar(_)) => write!(f, "{{float}}"),
+            Ty::Error => write!(f, "{{type error}}"),
+        }
+    }
+}
@@ -0,0 +1,518 @@
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+use rusttc_ast::ast::IntTy;
+
+// `match`로 검사할 값의 타입입니다. 패턴으로 나눌 수 있는 모양만 담습니다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatTy {
+    Bool,
+    Int(IntTy),
+    Tuple(Vec<PatTy>),
+    Struct { name: String, fields: Vec<(String, PatTy)> },
+    Enum { name: String, variants: Vec<(String, Vec<PatTy>)> },  // 변형마다 이름과 튜플 필드
+    Opaque,                                                     // 값을 나눌 수 없는 타입 (`_`나 바인딩만 모두 덮습니다)
+}
+
+// 검사할 패턴입니다. 바인딩은 `Wild`로 적습니다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pat {
+    Wild,                               // _, x
+    Bool(bool),
+    Range(IntValue, IntValue),          // 1, 1..=5 (양 끝 포함)
+    Tuple(Vec<Pat>),
+    Struct(Vec<(String, Pat)>),         // 적지 않은 필드는 `..`처럼 무엇이든 맞습니다
+    Variant(String, Vec<Pat>),          // Some(x), None
+    Or(Vec<Pat>),                       // 1 | 2
+    Opaque,                             // 값을 나눠 따지지 않는 리터럴 ('a', "a"): 일부 값에만 맞고 다른 패턴을 가리지 않습니다
+}
+
+// 정수 패턴의 끝점입니다. `u128`은 `i128`에 다 담기지 않으므로 부호를 나눠 담습니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntValue {
+    Signed(i128),
+    Unsigned(u128),
+}
+
+// 부호가 달라도 수의 크기로 비교합니다 (`-1 < 0u128`).
+impl PartialOrd for IntValue {
+    fn partial_cmp(&self, other: &IntValue) -> Option<Ordering> {
+        match (*self, *other) {
+            (IntValue::Signed(a), IntValue::Signed(b)) => a.partial_cmp(&b),
+            (IntValue::Unsigned(a), IntValue::Unsigned(b)) => a.partial_cmp(&b),
+            (IntValue::Signed(a), IntValue::Unsigned(_)) if a < 0 => Some(Ordering::Less),
+            (IntValue::Signed(a), IntValue::Unsigned(b)) => (a as u128).partial_cmp(&b),
+            (IntValue::Unsigned(_), IntValue::Signed(_)) => other.partial_cmp(self).map(Ordering::reverse),
+        }
+    }
+}
+
+// `match`의 갈래 하나입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arm {
+    pub pat: Pat,
+    pub guard: bool,                    // `if` 조건이 붙은 갈래는 값을 모두 덮는다고 볼 수 없습니다
+}
+
+// 검사 결과입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchCheck {
+    pub unreachable: Vec<usize>,        // 앞 갈래가 이미 모두 덮어 닿을 수 없는 갈래의 번호
+    pub missing: Vec<String>,           // 어느 갈래에도 맞지 않는 값의 예 (`Some(_)`)
+}
+
+impl MatchCheck {
+    // 덮지 못한 값이 있으면 오류 메시지입니다. 예는 세 개까지 보여 줍니다.
+    pub fn message(&self) -> Option<String> {
+        let quoted: Vec<String> = self.missing.iter().map(|pat| format!("`{}`", pat)).collect();
+        let list = match quoted.len() {
+            0 => return None,
+            1 => return Some(format!("non-exhaustive patterns: {} not covered", quoted[0])),
+            2 | 3 => format!("{} and {}", quoted[..quoted.len() - 1].join(", "), quoted[quoted.len() - 1]),
+            n => format!("{} and {} more", quoted[..3].join(", "), n - 3),
+        };
+        Some(format!("non-exhaustive patterns: {} not covered", list))
+    }
+}
+
+// 갈래들이 모든 값을 덮는지, 닿을 수 없는 갈래가 있는지 검사합니다.
+// Maranget의 usefulness 알고리즘을 따릅니다: 패턴 행렬에 대해 새 패턴 벡터가 덮는 값이 하나라도 남는지를 생성자별로 나눠 확인합니다.
+// 정수는 범위 끝점으로 타입의 전체 범위를 잘라, 갈래마다 통째로 맞거나 안 맞는 구간만 다룹니다.
+pub fn check_match(ty: &PatTy, arms: &[Arm]) -> MatchCheck {
+    let mut rows = Vec::new();
+    let mut unreachable = Vec::new();
+    for (i, arm) in arms.iter().enumerate() {
+        let pat = lower(&arm.pat, ty);
+        if !is_useful(&rows, std::slice::from_ref(&pat), &[ty]) {
+            unreachable.push(i);
+        }
+        if !arm.guard {
+            rows.push(vec![pat]);
+        }
+    }
+    let missing = witnesses(&rows, &[ty]).iter().map(|witness| show(&witness[0], ty)).collect();
+    MatchCheck { unreachable, missing }
+}
+
+// 패턴 맨 앞의 생성자입니다. 정수 범위는 부호 있는 타입도 순서가 유지되도록 2^127을 더한 값으로 담습니다.
+#[derive(Debug, Clone, PartialEq)]
+enum Ctor {
+    Wild,
+    Bool(bool),
+    Range(u128, u128),
+    Variant(usize),
+    Single,                             // 튜플과 구조체
+    Opaque,                             // 어느 생성자도 덮지 않습니다
+    Or,                                 // 필드가 곧 대안들입니다
+}
+
+#[derive(Debug, Clone)]
+struct DPat {
+    ctor: Ctor,
+    fields: Vec<DPat>,
+}
+
+impl DPat {
+    fn wild() -> DPat {
+        DPat { ctor: Ctor::Wild, fields: Vec::new() }
+    }
+}
+
+const BIAS: u128 = 1 << 127;
+
+// 끝점을 순서가 유지되는 `u128`로 바꿉니다. 타입 범위를 벗어난 값은 타입 검사가 이미 보고했으므로 범위 끝에 맞춥니다.
+fn encode(int: IntTy, value: IntValue) -> u128 {
+    let (min, max) = type_range(int);
+    let value = match (int.is_signed(), value) {
+        (true, IntValue::Signed(value)) => (value as u128).wrapping_add(BIAS),
+        (true, IntValue::Unsigned(value)) => value.saturating_add(BIAS),
+        (false, IntValue::Signed(value)) => value.max(0) as u128,
+        (false, IntValue::Unsigned(value)) => value,
+    };
+    value.clamp(min, max)
+}
+
+fn type_range(int: IntTy) -> (u128, u128) {
+    match int.is_signed() {
+        true => (BIAS - int.min_abs(), BIAS + int.max()),
+        false => (0, int.max()),
+    }
+}
+
+// 패턴을 타입에 맞춰 생성자와 필드로 풉니다. 타입과 맞지 않는 패턴은 타입 검사가 이미 보고했으므로 `_`로 봅니다.
+fn lower(pat: &Pat, ty: &PatTy) -> DPat {
+    let (ctor, fields) = match (pat, ty) {
+        (Pat::Bool(value), PatTy::Bool) => (Ctor::Bool(*value), Vec::new()),
+        (Pat::Range(lo, hi), PatTy::Int(int)) => (Ctor::Range(encode(*int, *lo), encode(*int, *hi)), Vec::new()),
+        (Pat::Tuple(pats), PatTy::Tuple(tys)) if pats.len() == tys.len() => {
+            (Ctor::Single, pats.iter().zip(tys).map(|(pat, ty)| lower(pat, ty)).collect())
+        },
+        (Pat::Struct(pats), PatTy::Struct { fields, .. }) => {
+            let fields = fields
+                .iter()
+                .map(|(name, ty)| pats.iter().find(|(field, _)| field == name).map_or_else(DPat::wild, |(_, pat)| lower(pat, ty)))
+                .collect();
+            (Ctor::Single, fields)
+        },
+        (Pat::Variant(name, pats), PatTy::Enum { variants, .. }) => match variants.iter().position(|(variant, _)| variant == name) {
+            Some(index) if variants[index].1.len() == pats.len() => {
+                (Ctor::Variant(index), pats.iter().zip(&variants[index].1).map(|(pat, ty)| lower(pat, ty)).collect())
+            },
+            _ => (Ctor::Wild, Vec::new()),
+        },
+        (Pat::Or(pats), _) => (Ctor::Or, pats.iter().map(|pat| lower(pat, ty)).collect()),
+        (Pat::Opaque, _) => (Ctor::Opaque, Vec::new()),
+        _ => (Ctor::Wild, Vec::new()),
+    };
+    DPat { ctor, fields }
+}
+
+// 생성자의 필드 타입입니다.
+fn field_tys<'a>(ty: &'a PatTy, ctor: &Ctor) -> Vec<&'a PatTy> {
+    match (ty, ctor) {
+        (PatTy::Tuple(tys), Ctor::Single) => tys.iter().collect(),
+        (PatTy::Struct { fields, .. }, Ctor::Single) => fields.iter().map(|(_, ty)| ty).collect(),
+        (PatTy::Enum { variants, .. }, Ctor::Variant(index)) => variants[*index].1.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+// `head`가 `ctor`의 모든 값을 덮는지 확인합니다. `ctor`는 이미 잘린 생성자라 일부만 겹치는 경우는 없습니다.
+fn covers(head: &Ctor, ctor: &Ctor) -> bool {
+    match (head, ctor) {
+        (Ctor::Wild, _) | (Ctor::Single, Ctor::Single) => true,
+        (Ctor::Bool(a), Ctor::Bool(b)) => a == b,
+        (Ctor::Range(lo, hi), Ctor::Range(a, b)) => lo <= a && b <= hi,
+        (Ctor::Variant(a), Ctor::Variant(b)) => a == b,
+        _ => false,
+    }
+}
+
+// 맨 앞 열에 올 수 있는 생성자를 행렬의 생성자에 맞춰 잘게 나눕니다. `within`이 있으면 그 생성자 안에서만 나눕니다.
+// 값을 나눌 수 없는 타입이면 없습니다.
+fn split(ty: &PatTy, heads: &[&Ctor], within: Option<&Ctor>) -> Option<Vec<Ctor>> {
+    let ctors = match (ty, within) {
+        (PatTy::Int(int), within) => {
+            let (lo, hi) = match within {
+                Some(Ctor::Range(lo, hi)) => (*lo, *hi),
+                _ => type_range(*int),
+            };
+            let mut starts = vec![lo];
+            for head in heads {
+                if let Ctor::Range(a, b) = head {
+                    if *a > lo && *a <= hi {
+                        starts.push(*a);
+                    }
+                    if let Some(next) = b.checked_add(1).filter(|next| *next > lo && *next <= hi) {
+                        starts.push(next);
+                    }
+                }
+            }
+            starts.sort_unstable();
+            starts.dedup();
+            let ends = starts.iter().skip(1).map(|start| start - 1).chain([hi]);
+            starts.iter().zip(ends).map(|(&lo, hi)| Ctor::Range(lo, hi)).collect()
+        },
+        (_, Some(ctor)) => vec![ctor.clone()],
+        (PatTy::Bool, None) => vec![Ctor::Bool(false), Ctor::Bool(true)],
+        (PatTy::Tuple(_) | PatTy::Struct { .. }, None) => vec![Ctor::Single],
+        (PatTy::Enum { variants, .. }, None) => (0..variants.len()).map(Ctor::Variant).collect(),
+        (PatTy::Opaque, None) => return None,
+    };
+    Some(ctors)
+}
+
+// 맨 앞이 `|` 패턴인 행을 대안마다 한 행으로 폅니다.
+fn expand_or(rows: &[Vec<DPat>]) -> Vec<Vec<DPat>> {
+    let mut expanded = Vec::new();
+    for row in rows {
+        match row.first() {
+            Some(DPat { ctor: Ctor::Or, fields }) => {
+                let alternatives: Vec<Vec<DPat>> = fields.iter().map(|alt| [alt.clone()].into_iter().chain(row[1..].iter().cloned()).collect()).collect();
+                expanded.extend(expand_or(&alternatives));
+            },
+            _ => expanded.push(row.clone()),
+        }
+    }
+    expanded
+}
+
+// `ctor`로 시작하는 값만 남기고 맨 앞 패턴을 그 필드들로 바꿉니다.
+fn specialize(rows: &[Vec<DPat>], ctor: &Ctor, arity: usize) -> Vec<Vec<DPat>> {
+    rows.iter()
+        .filter_map(|row| {
+            let head = &row[0];
+            let fields = match head.ctor {
+                Ctor::Wild => vec![DPat::wild(); arity],
+                _ if covers(&head.ctor, ctor) => head.fields.clone(),
+                _ => return None,
+            };
+            Some(fields.into_iter().chain(row[1..].iter().cloned()).collect())
+        })
+        .collect()
+}
+
+// 맨 앞이 `_`인 행만 남기고 맨 앞 열을 지웁니다.
+fn default_rows(rows: &[Vec<DPat>]) -> Vec<Vec<DPat>> {
+    rows.iter().filter(|row| row[0].ctor == Ctor::Wild).map(|row| row[1..].to_vec()).collect()
+}
+
+fn head_ctors(rows: &[Vec<DPat>]) -> Vec<&Ctor> {
+    rows.iter().map(|row| &row[0].ctor).filter(|ctor| **ctor != Ctor::Wild).collect()
+}
+
+// 나눈 생성자가 행렬의 생성자로 모두 덮이는지 확인합니다.
+fn is_complete(ctors: &Option<Vec<Ctor>>, heads: &[&Ctor]) -> bool {
+    ctors.as_ref().is_some_and(|ctors| ctors.iter().all(|ctor| heads.iter().any(|head| covers(head, ctor))))
+}
+
+// 행렬의 어느 행에도 맞지 않으면서 `row`에는 맞는 값이 있는지 확인합니다.
+fn is_useful(rows: &[Vec<DPat>], row: &[DPat], tys: &[&PatTy]) -> bool {
+    let Some(head) = row.first() else { return rows.is_empty() };
+    let rows = expand_or(rows);
+    if head.ctor == Ctor::Or {
+        return head.fields.iter().any(|alt| {
+            let row: Vec<DPat> = [alt.clone()].into_iter().chain(row[1..].iter().cloned()).collect();
+            is_useful(&rows, &row, tys)
+        });
+    }
+
+    // 나눠 따지지 않는 리터럴은 `_`로 시작하는 행에만 가려집니다.
+    if head.ctor == Ctor::Opaque {
+        return is_useful(&default_rows(&rows), &row[1..], &tys[1..]);
+    }
+
+    let heads = head_ctors(&rows);
+    let within = (head.ctor != Ctor::Wild).then_some(&head.ctor);
+    let ctors = split(tys[0], &heads, within);
+    if head.ctor == Ctor::Wild && !is_complete(&ctors, &heads) {
+        return is_useful(&default_rows(&rows), &row[1..], &tys[1..]);
+    }
+    ctors.unwrap_or_default().iter().any(|ctor| {
+        let fields = field_tys(tys[0], ctor);
+        let tys: Vec<&PatTy> = fields.iter().copied().chain(tys[1..].iter().copied()).collect();
+        let row = specialize(&[row.to_vec()], ctor, fields.len()).pop().unwrap();
+        is_useful(&specialize(&rows, ctor, fields.len()), &row, &tys)
+    })
+}
+
+// 행렬의 어느 행에도 맞지 않는 값의 예를 모두 구합니다. 예마다 열의 수만큼 패턴이 있습니다.
+fn witnesses(rows: &[Vec<DPat>], tys: &[&PatTy]) -> Vec<Vec<DPat>> {
+    let Some(&ty) = tys.first() else {
+        return if rows.is_empty() { vec![Vec::new()] } else { Vec::new() };
+    };
+    let rows = expand_or(rows);
+    let heads = head_ctors(&rows);
+    let ctors = split(ty, &heads, None);
+
+    // 맨 앞 열에 생성자가 하나도 없고 모든 값을 덮지도 못하면 `_` 하나로 충분합니다.
+    let ctors = match ctors {
+        Some(ctors) if !heads.is_empty() || is_complete(&Some(ctors.clone()), &heads) => ctors,
+        _ => return witnesses(&default_rows(&rows), &tys[1..]).into_iter().map(|rest| [DPat::wild()].into_iter().chain(rest).collect()).collect(),
+    };
+
+    // 생성자 순서대로, 덮인 생성자는 그 안을 더 따지고 덮이지 않은 생성자는 나머지 열을 `_`만 남은 행들로 따집니다.
+    let mut missing: Vec<Ctor> = Vec::new();
+    let mut parts: Vec<(Ctor, bool)> = Vec::new();
+    for ctor in ctors {
+        if heads.iter().any(|head| covers(head, &ctor)) {
+            parts.push((ctor, true));
+        } else {
+            missing.push(ctor.clone());
+            parts.push((ctor, false));
+        }
+    }
+    let merged = merge_ranges(missing);
+    let defaults = match merged.is_empty() {
+        true => Vec::new(),
+        false => witnesses(&default_rows(&rows), &tys[1..]),
+    };
+
+    let mut result = Vec::new();
+    let mut next_missing = merged.iter();
+    for (ctor, covered) in parts {
+        let fields = field_tys(ty, &ctor);
+        let arity = fields.len();
+        if covered {
+            let tys: Vec<&PatTy> = fields.into_iter().chain(tys[1..].iter().copied()).collect();
+            for mut witness in witnesses(&specialize(&rows, &ctor, arity), &tys) {
+                let rest = witness.split_off(arity);
+                result.push([DPat { ctor: ctor.clone(), fields: witness }].into_iter().chain(rest).collect());
+            }
+            continue;
+        }
+        // 합쳐진 구간은 그 첫 조각에서 한 번만 내보냅니다.
+        let Some(merged) = next_missing.clone().next().filter(|merged| starts_with(merged, &ctor)) else { continue };
+        next_missing.next();
+        for rest in &defaults {
+            let pat = DPat { ctor: merged.clone(), fields: vec![DPat::wild(); arity] };
+            result.push([pat].into_iter().chain(rest.iter().cloned()).collect());
+        }
+    }
+    result
+}
+
+// 합친 생성자가 `ctor`에서 시작하는지 확인합니다.
+fn starts_with(merged: &Ctor, ctor: &Ctor) -> bool {
+    match (merged, ctor) {
+        (Ctor::Range(a, _), Ctor::Range(b, _)) => a == b,
+        _ => merged == ctor,
+    }
+}
+
+// 이어진 정수 구간을 하나로 합칩니다.
+fn merge_ranges(ctors: Vec<Ctor>) -> Vec<Ctor> {
+    let mut merged: Vec<Ctor> = Vec::new();
+    for ctor in ctors {
+        match (merged.last_mut(), &ctor) {
+            (Some(Ctor::Range(_, hi)), Ctor::Range(lo, next_hi)) if hi.checked_add(1) == Some(*lo) => *hi = *next_hi,
+            _ => merged.push(ctor),
+        }
+    }
+    merged
+}
+
+// 오류 메시지에 쓸 패턴입니다 (`Some(_)`, `Point { x: true, .. }`, `i32::MIN..=-1_i32`).
+fn show(pat: &DPat, ty: &PatTy) -> String {
+    match (&pat.ctor, ty) {
+        (Ctor::Bool(value), _) => value.to_string(),
+        (Ctor::Range(lo, hi), PatTy::Int(int)) => {
+            let (min, max) = type_range(*int);
+            let bound = |value: u128| match value {
+                _ if value == min => format!("{}::MIN", int.name()),
+                _ if value == max => format!("{}::MAX", int.name()),
+                _ if int.is_signed() => format!("{}_{}", value.wrapping_sub(BIAS) as i128, int.name()),
+                _ => format!("{}_{}", value, int.name()),
+            };
+            match lo == hi {
+                true => bound(*lo),
+                false => format!("{}..={}", bound(*lo), bound(*hi)),
+            }
+        },
+        (Ctor::Single, PatTy::Tuple(tys)) => {
+            let fields: Vec<String> = pat.fields.iter().zip(tys).map(|(field, ty)| show(field, ty)).collect();
+            match fields.len() {
+                1 => format!("({},)", fields[0]),
+                _ => format!("({})", fields.join(", ")),
+            }
+        },
+        (Ctor::Single, PatTy::Struct { name, fields }) => {
+            let mut text = format!("{} {{ ", name);
+            let shown: Vec<String> = pat
+                .fields
+                .iter()
+                .zip(fields)
+                .filter(|(field, _)| field.ctor != Ctor::Wild)
+                .map(|(field, (field_name, ty))| format!("{}: {}", field_name, show(field, ty)))
+                .collect();
+            text.push_str(&shown.join(", "));
+            if shown.len() < fields.len() {
+                text.push_str(if shown.is_empty() { ".." } else { ", .." });
+            }
+            text.push_str(" }");
+            text
+        },
+        (Ctor::Variant(index), PatTy::Enum { name, variants }) => {
+            let (variant, tys) = &variants[*index];
+            // 프렐루드의 `Option`과 `Result` 변형은 열거형 이름 없이 씁니다.
+            let mut text = match name.as_str() {
+                "Option" | "Result" => variant.clone(),
+                _ => format!("{}::{}", name, variant),
+            };
+            if !tys.is_empty() {
+                let fields: Vec<String> = pat.fields.iter().zip(tys).map(|(field, ty)| show(field, ty)).collect();
+                let _ = write!(text, "({})", fields.join(", "));
+            }
+            text
+        },
+        _ => "_".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(ty: PatTy) -> PatTy {
+        PatTy::Enum { name: "Option".to_string(), variants: vec![("None".to_string(), Vec::new()), ("Some".to_string(), vec![ty])] }
+    }
+
+    fn arms(pats: Vec<Pat>) -> Vec<Arm> {
+        pats.into_iter().map(|pat| Arm { pat, guard: false }).collect()
+    }
+
+    fn range(lo: i128, hi: i128) -> Pat {
+        Pat::Range(IntValue::Signed(lo), IntValue::Signed(hi))
+    }
+
+    fn some(pat: Pat) -> Pat {
+        Pat::Variant("Some".to_string(), vec![pat])
+    }
+
+    #[test]
+    fn test_missing_variants_and_booleans() {
+        let ty = option(PatTy::Bool);
+        let none = Pat::Variant("None".to_string(), Vec::new());
+        let check = check_match(&ty, &arms(vec![none.clone()]));
+        assert_eq!(check.message().unwrap(), "non-exhaustive patterns: `Some(_)` not covered");
+
+        let check = check_match(&ty, &arms(vec![none.clone(), some(Pat::Bool(true))]));
+        assert_eq!(check.missing, ["Some(false)"]);
+
+        let check = check_match(&ty, &arms(vec![some(Pat::Wild), none]));
+        assert_eq!(check, MatchCheck { unreachable: Vec::new(), missing: Vec::new() });
+
+        let pair = PatTy::Tuple(vec![PatTy::Bool, PatTy::Bool]);
+        let check = check_match(&pair, &arms(vec![Pat::Tuple(vec![Pat::Bool(true), Pat::Bool(true)])]));
+        assert_eq!(check.message().unwrap(), "non-exhaustive patterns: `(false, _)` and `(true, false)` not covered");
+    }
+
+    #[test]
+    fn test_integer_ranges() {
+        let ty = PatTy::Int(IntTy::U8);
+        let check = check_match(&ty, &arms(vec![range(0, 99), range(101, 255)]));
+        assert_eq!(check.missing, ["100_u8"]);
+        assert!(check_match(&ty, &arms(vec![range(0, 99), range(100, 100), range(101, 255)])).missing.is_empty());
+
+        let ty = PatTy::Int(IntTy::I32);
+        let check = check_match(&ty, &arms(vec![range(0, 0)]));
+        assert_eq!(check.message().unwrap(), "non-exhaustive patterns: `i32::MIN..=-1_i32` and `1_i32..=i32::MAX` not covered");
+
+        let ty = PatTy::Int(IntTy::U128);
+        let check = check_match(&ty, &arms(vec![range(0, i128::MAX)]));
+        assert_eq!(check.missing, ["170141183460469231731687303715884105728_u128..=u128::MAX"]);
+
+        // `i128::MAX`보다 큰 `u128` 끝점도 그대로 다룹니다.
+        let top = Pat::Range(IntValue::Unsigned(u128::MAX - 1), IntValue::Unsigned(u128::MAX));
+        let check = check_match(&ty, &arms(vec![range(0, i128::MAX), top]));
+        assert_eq!(check.missing, ["170141183460469231731687303715884105728_u128..=340282366920938463463374607431768211453_u128"]);
+        let all = Pat::Range(IntValue::Unsigned(0), IntValue::Unsigned(u128::MAX));
+        assert_eq!(check_match(&ty, &arms(vec![all, range(5, 5)])).unreachable, [1]);
+    }
+
+    #[test]
+    fn test_unreachable_arms_structs_and_guards() {
+        let point = PatTy::Struct { name: "Point".to_string(), fields: vec![("x".to_string(), PatTy::Bool), ("y".to_string(), PatTy::Int(IntTy::I8))] };
+        let x = |value: bool| Pat::Struct(vec![("x".to_string(), Pat::Bool(value))]);
+        let check = check_match(&point, &arms(vec![x(false)]));
+        assert_eq!(check.message().unwrap(), "non-exhaustive patterns: `Point { x: true, .. }` not covered");
+
+        let check = check_match(&point, &arms(vec![x(false), x(true), Pat::Wild]));
+        assert_eq!(check.unreachable, [2]);
+
+        let ty = PatTy::Int(IntTy::U8);
+        let check = check_match(&ty, &arms(vec![Pat::Or(vec![range(0, 9), range(10, 255)]), range(5, 5)]));
+        assert_eq!(check.unreachable, [1]);
+
+        // 조건이 붙은 갈래는 다음 갈래를 가리지 않지만, 그것만으로 모든 값을 덮지는 못합니다.
+        let guarded = vec![Arm { pat: Pat::Wild, guard: true }, Arm { pat: Pat::Wild, guard: false }];
+        assert_eq!(check_match(&PatTy::Opaque, &guarded), MatchCheck { unreachable: Vec::new(), missing: Vec::new() });
+        let check = check_match(&PatTy::Opaque, &guarded[..1]);
+        assert_eq!(check.missing, ["_"]);
+
+        // 문자 리터럴은 서로 가리지 않고, 모두 적어도 `_`가 있어야 빠짐없습니다.
+        let check = check_match(&PatTy::Opaque, &arms(vec![Pat::Opaque, Pat::Opaque]));
+        assert_eq!(check, MatchCheck { unreachable: Vec::new(), missing: vec!["_".to_string()] });
+        assert_eq!(check_match(&PatTy::Opaque, &arms(vec![Pat::Wild, Pat::Opaque])).unreachable, [1]);
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use rusttc_ast::ast::{
+    self, ArrayLen, AstArena, Call, Crate, ExprId, Node, NodeId, NodeKind, Path, PathSegment, QSelf, SelfKind, TyKind, UnaryOp,
+};
+use rusttc_ast::mut_visit::{self, MutVisitor};
+use rusttc_lexer::{Span, Token, TokenKind};
+
+use crate::ty::Ty;
+use crate::{CalleeKind, MethodCallee, TypeckResults};
+
+// 추론한 타입을 크레이트에 다시 씁니다. 접미사 없는 숫자 리터럴에는 접미사를 붙이고(`1` → `1u8`),
+// 적지 않은 경로의 타입 인자를 추론한 타입으로 채웁니다(`id(1)` → `id::<i32>(1)`).
+// 메서드 호출과 클로저 값 호출은 찾은 함수의 경로 호출로 바꾸고(`p.len()` → `Point::len(&p)`),
+// 클로저에는 분석한 종류와 캡처를 적습니다. 타입 표기 안의 배열 길이 식(`[T; N]`)은 계산한 길이로 바꿉니다.
+// 이후 단계(빌림 검사, 단형화, IR 낮추기)는 이 결과를 그대로 씁니다.
+pub fn write_back(krate: &mut Crate, results: &TypeckResults) {
+    write_literal_types(&mut krate.arena, &results.literal_types);
+
+    let mut writer = ArrayLenWriter { arena: std::mem::take(&mut krate.arena), lens: &results.array_lens };
+    writer.visit_crate(krate);
+    krate.arena = writer.arena;
+
+    for (&id, segments) in &results.generic_args {
+        let node = &mut krate.arena[id];
+        if let NodeKind::Ident(name) = &node.kind {
+            node.kind = NodeKind::Path(Path::from_ident(name));
+        }
+        let path = match &mut node.kind {
+            NodeKind::Path(path) => path,
+            NodeKind::QualifiedPath(_, path) => path,
+            NodeKind::StructLit(lit) => &mut lit.path,
+            _ => continue,
+        };
+        // 경로 세그먼트 중 뒤쪽이 타입 인자 목록과 짝을 이룹니다 (`Pair::new` → `[impl 인자, 함수 인자]`).
+        let skip = path.segments.len().saturating_sub(segments.len());
+        for (segment, args) in path.segments.iter_mut().skip(skip).zip(segments) {
+            if !segment.args.is_empty() {
+                continue;                                       // 사용자가 적은 인자는 그대로 둡니다
+            }
+            if let Some(args) = args.iter().map(raise_ty).collect::<Option<Vec<_>>>() {
+                segment.args = args;
+            }
+        }
+    }
+
+    let mut method_calls: Vec<(&ExprId, &MethodCallee)> = results.method_calls.iter().collect();
+    method_calls.sort_by_key(|(id, _)| **id);
+    for (&id, callee) in method_calls {
+        desugar_call(&mut krate.arena, id, callee);
+    }
+
+    for (&id, (kind, captures)) in &results.closures {
+        if let NodeKind::Closure(closure) = &mut krate.arena[id].kind {
+            closure.kind = *kind;
+            closure.captures = captures.clone();
+        }
+    }
+}
+
+// 메서드 호출 `recv.m(args)`와 클로저 값 호출 `f(args)`를 찾은 함수의 경로 호출로 바꿉니다.
+// 리시버는 self 형태에 맞게 참조하거나 역참조해 첫 번째 인자가 됩니다. 호출 노드의 번호는 그대로 둡니다.
+fn desugar_call(arena: &mut AstArena, id: ExprId, callee: &MethodCallee) {
+    let span = arena[id].span;
+    let (receiver, args) = match &arena[id].kind {
+        NodeKind::MethodCall(call) => (call.receiver, call.args.clone()),
+        NodeKind::Call(call) => (call.callee, call.args.clone()),
+        _ => return,
+    };
+    // 배열의 `iter()`는 호출 노드를 원소를 빌려 주는 참조 `&a`(참조를 통했다면 `&*a`)로 바꿉니다.
+    if let CalleeKind::Borrow = callee.kind {
+        let expr = adjust_receiver(arena, receiver, callee.derefs, SelfKind::Value { mutable: false });
+        arena[id].kind = NodeKind::Ref { mutable: callee.self_param == SelfKind::RefMut, expr };
+        return;
+    }
+    let receiver = adjust_receiver(arena, receiver, callee.derefs, callee.self_param);
+
+    let path = match &callee.kind {
+        CalleeKind::Borrow => unreachable!(),
+        CalleeKind::Inherent { ty, impl_args, name, fn_args } => {
+            let mut ty = PathSegment::from_ident(ty);
+            ty.args = impl_args.iter().map(raise_ty).collect::<Option<_>>().unwrap_or_default();
+            NodeKind::Path(Path { segments: vec![ty, method_segment(name, fn_args)] })
+        },
+        CalleeKind::Trait { self_ty, trait_name, name, fn_args } => {
+            let Some(self_ty) = raise_ty(self_ty) else { return };
+            let qself = QSelf { ty: self_ty, trait_path: Path::from_ident(trait_name) };
+            NodeKind::QualifiedPath(Box::new(qself), Path { segments: vec![method_segment(name, fn_args)] })
+        },
+    };
+    let path = arena.alloc_expr(synthesized(path, span));
+    arena[id].kind = NodeKind::Call(Call { callee: path, args: [receiver].into_iter().chain(args).collect() });
+}
+
+// 함수 이름 세그먼트입니다. 타입 인자를 끝내 추론하지 못했다면 적지 않고 둡니다.
+fn method_segment(name: &str, fn_args: &[Ty]) -> PathSegment {
+    let mut segment = PathSegment::from_ident(name);
+    segment.args = fn_args.iter().map(raise_ty).collect::<Option<_>>().unwrap_or_default();
+    segment
+}
+
+// 참조 단계 수(`depth`)를 가진 리시버를 self 형태에 맞게 조정합니다.
+// 덧붙인 참조와 역참조는 리시버의 위치를 쓰며, 조정한 리시버의 번호를 돌려줍니다.
+fn adjust_receiver(arena: &mut AstArena, receiver: ExprId, depth: usize, self_param: SelfKind) -> ExprId {
+    let span = arena[receiver].span;
+    let deref = |arena: &mut AstArena, mut operand: ExprId, times: usize| {
+        for _ in 0..times {
+            let operator = Token::new(TokenKind::Star, "*".to_string());
+            operand = arena.alloc_expr(synthesized(NodeKind::Unary(UnaryOp { operator, operand }), span));
+        }
+        operand
+    };
+
+    match (self_param, depth) {
+        (SelfKind::Value { .. }, depth) => deref(arena, receiver, depth),
+        (SelfKind::Ref, 0) => arena.alloc_expr(synthesized(NodeKind::Ref { mutable: false, expr: receiver }, span)),
+        (SelfKind::RefMut, 0) => arena.alloc_expr(synthesized(NodeKind::Ref { mutable: true, expr: receiver }, span)),
+        (SelfKind::Ref | SelfKind::RefMut, depth) => deref(arena, receiver, depth - 1),
+    }
+}
+
+// 쓰기 단계에서 만든 노드입니다. 노드 번호는 없고 바탕이 된 식의 위치를 가집니다.
+fn synthesized(kind: NodeKind, span: Span) -> Node {
+    Node { id: NodeId::DUMMY, span, kind }
+}
+
+struct ArrayLenWriter<'a> {
+    arena: AstArena,
+    lens: &'a HashMap<ExprId, usize>,
+}
+
+impl MutVisitor for ArrayLenWriter<'_> {
+    fn arena(&mut self) -> &mut AstArena {
+        &mut self.arena
+    }
+
+    fn visit_ty(&mut self, ty: &mut ast::Ty) {
+        mut_visit::walk_ty(self, ty);
+        if let TyKind::Array(_, len) = &mut ty.kind {
+            let known = match len {
+                ArrayLen::Expr(id) => self.lens.get(id).copied(),
+                ArrayLen::Known(_) => None,
+            };
+            if let Some(known) = known {
+                *len = ArrayLen::Known(known);
+            }
+        }
+    }
+}
+
+// 접미사 없는 숫자 리터럴에 추론한 타입을 접미사로 붙입니다.
+// 상수 평가는 크레이트를 고치지 않도록 저장소의 사본에 붙여 IR을 만듭니다.
+pub(crate) fn write_literal_types(arena: &mut AstArena, literal_types: &HashMap<ExprId, Ty>) {
+    for (&id, ty) in literal_types {
+        match (&mut arena[id].kind, ty) {
+            (NodeKind::Int(_, suffix @ None), Ty::Int(int)) => *suffix = Some(*int),
+            (NodeKind::Float(_, suffix @ None), Ty::Float(float)) => *suffix = Some(*float),
+            _ => {},
+        }
+    }
+}
+
+// 타입을 타입 표기로 바꿉니다. 끝내 정해지지 않았거나 오류가 난 타입은 바꾸지 않습니다.
+fn raise_ty(ty: &Ty) -> Option<ast::Ty> {
+    let kind = match ty {
+        Ty::Bool => TyKind::Path(Path::from_ident("bool")),
+        Ty::Int(int) => TyKind::Path(Path::from_ident(int.name())),
+        Ty::Float(float) => TyKind::Path(Path::from_ident(float.name())),
+        Ty::Char => TyKind::Path(Path::from_ident("char")),
+        Ty::Str => TyKind::Path(Path::from_ident("str")),
+        Ty::Param(name) | Ty::Closure(name) => TyKind::Path(Path::from_ident(name)),
+        Ty::Adt(name, args) => {
+            let mut segment = PathSegment::from_ident(name);
+            segment.args = args.iter().map(raise_ty).collect::<Option<_>>()?;
+            TyKind::Path(Path { segments: vec![segment] })
+        },
+        Ty::Ref(mutable, ty) => TyKind::Ref { mutable: *mutable, ty: Box::new(raise_ty(ty)?) },
+        Ty::Array(ty, len) => TyKind::Array(Box::new(raise_ty(ty)?), ArrayLen::Known(*len)),
+        Ty::Slice(ty) => TyKind::Slice(Box::new(raise_ty(ty)?)),
+        Ty::Tuple(tys) if tys.is_empty() => TyKind::Unit,
+        Ty::Tuple(tys) => TyKind::Tuple(tys.iter().map(raise_ty).collect::<Option<_>>()?),
+        // 함수 포인터 타입은 표기할 문법이 없습니다.
+        Ty::FnPtr(..) | Ty::Never | Ty::Infer(_) | Ty::Error => return None,
+    };
+    Some(kind.into())
+}
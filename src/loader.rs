@@ -0,0 +1,245 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rusttc_ast::ast::{AstArena, Crate, Item, NodeId};
+use rusttc_expand::expand;
+use rusttc_ir::cfg::{is_configured, strip_unconfigured, CfgError, CfgOptions};
+use rusttc_lexer::{source_map, tokenize_file, Span};
+use rusttc_parse::parser::parse_crate_from;
+
+// 소스 파일을 읽거나 파싱하는 중에 발생한 오류입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadError {
+    pub message: String,
+    pub span: Span,             // 파싱, 전개 오류의 위치 (파일을 읽지 못한 경우 등은 DUMMY)
+}
+
+impl LoadError {
+    fn new(message: impl Into<String>) -> Self {
+        LoadError { message: message.into(), span: Span::DUMMY }
+    }
+
+    // 파일 안의 오류입니다. 위치를 모르면 메시지 앞에 파일 경로를 붙입니다.
+    fn in_file(path: &Path, message: String, span: Span) -> Self {
+        if span.is_dummy() {
+            LoadError::new(format!("{}: {}", path.display(), message))
+        } else {
+            LoadError { message, span }
+        }
+    }
+}
+
+impl From<CfgError> for LoadError {
+    fn from(error: CfgError) -> Self {
+        LoadError::new(error.message)
+    }
+}
+
+// 여러 줄 메시지(형식 문자열 오류)의 위치는 첫 줄 바로 뒤에 둡니다.
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (head, rest) = match self.message.split_once('\n') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (self.message.as_str(), None),
+        };
+        write!(f, "error: {}", head)?;
+        if let Some(location) = self.span.location() {
+            write!(f, "\n  --> {}", location)?;
+        }
+        if let Some(rest) = rest {
+            write!(f, "\n{}", rest)?;
+        }
+        Ok(())
+    }
+}
+
+// 크레이트 루트 파일을 읽고, `mod foo;` 선언마다 모듈 파일을 찾아 읽어 모듈 트리를 채웁니다.
+// `foo`는 선언한 파일 기준으로 `foo.rs` 또는 `foo/mod.rs`에서 찾고,
+// 그 안의 하위 모듈은 `foo/` 디렉터리에서 찾습니다. 인라인 모듈 `mod foo { mod bar; }`도 같습니다.
+// `#[cfg]` 조건이 거짓인 모듈은 파일을 읽지 않고, 다 읽은 뒤에는 나머지 아이템도 cfg에 따라 정리합니다.
+pub fn load_crate(path: &Path, cfg: &CfgOptions) -> Result<Crate, LoadError> {
+    let mut next_id = NodeId::FIRST;
+    let mut krate = parse_file(path, AstArena::default(), &mut next_id)?;
+    if !is_configured(&krate.attrs, cfg)? {
+        krate.items.clear();
+    }
+    let dir = path.parent().unwrap_or(Path::new(""));
+    load_modules(&mut krate.items, &mut krate.arena, dir, cfg, &mut next_id)?;
+    strip_unconfigured(&mut krate.items, &mut krate.arena, cfg)?;
+    Ok(krate)
+}
+
+// 파일을 읽어 매크로를 전개한 뒤 크레이트로 파싱합니다.
+// 파일은 소스 맵에 등록하므로 이 파일에서 나온 스팬은 오류를 출력할 때 파일 이름과 줄, 열로 보입니다.
+// 노드 번호는 `next_id`부터 매기므로 파일이 여럿이어도 크레이트 안에서 겹치지 않습니다.
+// 표현식과 문장은 `arena`에 이어 넣고, 파싱한 크레이트가 그 저장소를 가집니다.
+fn parse_file(path: &Path, arena: AstArena, next_id: &mut NodeId) -> Result<Crate, LoadError> {
+    let source = fs::read_to_string(path)
+        .map_err(|error| LoadError::new(format!("couldn't read `{}`: {}", path.display(), error)))?;
+    let file = source_map::add_file(path.display().to_string(), &source);
+    let tokens = expand(&tokenize_file(&source, file)).map_err(|error| LoadError::in_file(path, error.message, error.span))?;
+    let (krate, next) =
+        parse_crate_from(&tokens, arena, *next_id).map_err(|error| LoadError::in_file(path, error.message, error.span))?;
+    *next_id = next;
+    Ok(krate)
+}
+
+// `dir`은 `items` 안 모듈 선언의 파일을 찾는 디렉터리입니다.
+// 모듈 파일 맨 앞의 안쪽 속성(`#![cfg(test)]`)은 모듈 선언의 속성에 더해집니다.
+fn load_modules(
+    items: &mut Vec<Item>,
+    arena: &mut AstArena,
+    dir: &Path,
+    cfg: &CfgOptions,
+    next_id: &mut NodeId,
+) -> Result<(), LoadError> {
+    let mut loaded = Vec::new();
+    for mut item in std::mem::take(items) {
+        if let Item::Mod(def) = &mut item {
+            if !is_configured(&def.attrs, cfg)? {
+                continue;
+            }
+            if def.items.is_none() {
+                let file = find_module_file(dir, &def.name).map_err(|error| LoadError { span: def.span, ..error })?;
+                let krate = parse_file(&file, std::mem::take(arena), next_id)?;
+                *arena = krate.arena;
+                def.attrs.extend(krate.attrs);
+                if !is_configured(&def.attrs, cfg)? {
+                    continue;
+                }
+                def.items = Some(krate.items);
+            }
+            load_modules(def.items.as_mut().unwrap(), arena, &dir.join(&def.name), cfg, next_id)?;
+        }
+        loaded.push(item);
+    }
+    *items = loaded;
+    Ok(())
+}
+
+fn find_module_file(dir: &Path, name: &str) -> Result<PathBuf, LoadError> {
+    let file = dir.join(format!("{}.rs", name));
+    let mod_file = dir.join(name).join("mod.rs");
+    match (file.is_file(), mod_file.is_file()) {
+        (true, false) => Ok(file),
+        (false, true) => Ok(mod_file),
+        (true, true) => Err(LoadError::new(format!(
+            "file for module `{}` found at both \"{}\" and \"{}\"",
+            name,
+            file.display(),
+            mod_file.display()
+        ))),
+        (false, false) => Err(LoadError::new(format!("file not found for module `{}`", name))),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use rusttc_ast::ast::ModDef;
+
+    // 테스트마다 임시 디렉터리에 소스 파일을 만듭니다. `main.rs`의 테스트도 씁니다.
+    pub(crate) fn write_files(test: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rustt_{}_{}", std::process::id(), test));
+        let _ = fs::remove_dir_all(&dir);
+        for (path, source) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+        dir
+    }
+
+    fn module<'a>(items: &'a [Item], name: &str) -> &'a [Item] {
+        let def = items.iter().find_map(|item| match item {
+            Item::Mod(ModDef { name: mod_name, items: Some(items), .. }) if mod_name == name => Some(items),
+            _ => None,
+        });
+        def.unwrap_or_else(|| panic!("module `{}` not loaded", name))
+    }
+
+    #[test]
+    fn test_load_file_and_directory_modules() {
+        let dir = write_files("tree", &[
+            ("main.rs", "mod shapes; mod util { pub mod fmt; } fn main() {}"),
+            ("shapes/mod.rs", "pub mod circle; pub struct Point;"),
+            ("shapes/circle.rs", "pub fn area() -> i32 { 3 }"),
+            ("util/fmt.rs", "pub fn show() {}"),
+        ]);
+        let krate = load_crate(&dir.join("main.rs"), &CfgOptions::default()).unwrap();
+
+        let shapes = module(&krate.items, "shapes");
+        let circle = module(shapes, "circle");
+        assert!(matches!(&circle[0], Item::Fn(function) if function.name == "area"));
+        let fmt = module(module(&krate.items, "util"), "fmt");
+        assert!(matches!(&fmt[0], Item::Fn(function) if function.name == "show"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_and_ambiguous_module_files() {
+        let dir = write_files("missing", &[("main.rs", "mod gone;")]);
+        let error = load_crate(&dir.join("main.rs"), &CfgOptions::default()).unwrap_err();
+        assert_eq!(error.message, "file not found for module `gone`");
+        fs::remove_dir_all(dir).unwrap();
+
+        let dir = write_files("ambiguous", &[("main.rs", "mod a;"), ("a.rs", ""), ("a/mod.rs", "")]);
+        let error = load_crate(&dir.join("main.rs"), &CfgOptions::default()).unwrap_err();
+        assert!(error.message.starts_with("file for module `a` found at both"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_errors_point_into_the_failing_file() {
+        let dir = write_files("located", &[
+            ("main.rs", "mod a;\nmod b;\nfn main() {}"),
+            ("a.rs", "pub fn f() {}"),
+            ("b.rs", "pub fn g() {\n    let x = 1 +;\n}"),
+        ]);
+        source_map::clear();
+        let error = load_crate(&dir.join("main.rs"), &CfgOptions::default()).unwrap_err();
+        let file = dir.join("b.rs");
+        assert_eq!(error.to_string(), format!("error: expected expression, found `;`\n  --> {}:2:16", file.display()));
+        fs::remove_dir_all(dir).unwrap();
+
+        let dir = write_files("located_mod", &[("main.rs", "fn main() {}\nmod gone;")]);
+        let error = load_crate(&dir.join("main.rs"), &CfgOptions::default()).unwrap_err();
+        let file = dir.join("main.rs");
+        assert_eq!(error.to_string(), format!("error: file not found for module `gone`\n  --> {}:2:1", file.display()));
+        source_map::clear();
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_cfg_modules_are_not_loaded() {
+        let dir = write_files("cfg", &[
+            ("main.rs", "#[cfg(test)] mod tests; mod util; fn main() {}"),
+            ("util.rs", "#![cfg(feature = \"util\")] pub fn helper() {}"),
+        ]);
+        let krate = load_crate(&dir.join("main.rs"), &CfgOptions::default()).unwrap();
+        assert_eq!(krate.items.len(), 1);
+
+        let mut cfg = CfgOptions::default();
+        cfg.insert("feature", Some("util"));
+        let krate = load_crate(&dir.join("main.rs"), &cfg).unwrap();
+        assert_eq!(module(&krate.items, "util").len(), 1);
+
+        cfg.insert("test", None);
+        let error = load_crate(&dir.join("main.rs"), &cfg).unwrap_err();
+        assert_eq!(error.message, "file not found for module `tests`");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_node_ids_are_unique_across_files() {
+        let dir = write_files("ids", &[("main.rs", "mod a; fn main() {}"), ("a.rs", "pub fn f() {}")]);
+        let krate = load_crate(&dir.join("main.rs"), &CfgOptions::default()).unwrap();
+
+        let Item::Fn(main) = &krate.items[1] else { panic!("expected fn") };
+        let Item::Fn(f) = &module(&krate.items, "a")[0] else { panic!("expected fn") };
+        assert_ne!(main.id, f.id);
+        assert!(f.id > main.id);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,3 +1,221 @@
+mod loader;
+
+use std::env;
+use std::fs;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+
+use rusttc_ast::ast::Crate;
+use rusttc_ast::pretty::expanded_crate_to_string;
+use rusttc_ir::cfg::CfgOptions;
+use rusttc_ir::ir::{lower_crate, IrModule};
+use rusttc_ir::lint::{self, Level};
+use rusttc_ir::method::resolve_methods;
+use rusttc_ir::modules::resolve_modules;
+use rusttc_ir::mono::monomorphize;
+use rusttc_ir::resolve::resolve_names;
+
+// 컴파일러를 돌리는 스레드의 스택 크기입니다. 이름 해석부터 IR까지의 단계는 트리를 재귀로 내려가므로
+// 식이 한 단계 깊어질 때마다 스택을 씁니다. 전체 단계를 거치며 단계마다 가장 많이 쓴 양은 디버그 빌드에서
+// 약 10.5KiB(중첩 호출), 릴리스 빌드에서 약 3KiB였습니다. `MAX_DEPTH`(5000)단계면 디버그 빌드도 약 52MiB이므로
+// 두 배 넘는 여유를 둡니다. 스택은 실제로 쓴 만큼만 메모리를 차지합니다.
+const STACK_SIZE: usize = 128 << 20;
+
+const USAGE: &str = "usage: rustt [--test] [--cfg <spec>]... [--pretty=expanded] [--emit=ast-json|ast-sexp|cst] <file.rs>";
+
+// 명령줄 옵션입니다.
+struct Options {
+    input: PathBuf,             // 크레이트 루트 파일
+    cfg: CfgOptions,            // 켜진 cfg 옵션 (`--test`는 `test`를 켭니다)
+    output: Output,             // 무엇을 출력하고 멈출지
+}
+
+// 컴파일러가 출력할 결과입니다. `Ir`이 아니면 모듈 로딩이 끝난 크레이트를 출력하고 멈춥니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Output {
+    Ir,                         // 기본값: 변환한 IR
+    Expanded,                   // `--pretty=expanded`: 매크로 전개가 끝난 소스
+    AstJson,                    // `--emit=ast-json`: AST를 JSON으로
+    AstSexp,                    // `--emit=ast-sexp`: AST를 S-식으로
+    Cst,                        // `--emit=cst`: 루트 파일의 무손실 구문 트리 (매크로 전개 전)
+}
+
 fn main() {
-    println!("hello world!");
-}
\ No newline at end of file
+    on_compiler_stack(run);
+}
+
+// `f`를 `STACK_SIZE` 스택을 가진 스레드에서 실행합니다. 스레드의 패닉은 호출한 쪽으로 이어집니다.
+fn on_compiler_stack<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    thread::scope(|scope| {
+        let compiler = thread::Builder::new().stack_size(STACK_SIZE).spawn_scoped(scope, f).expect("failed to spawn the compiler thread");
+        compiler.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+fn run() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        },
+    };
+
+    if options.output == Output::Cst {
+        match dump_syntax(&options.input) {
+            Ok(text) => print!("{}", text),
+            Err(errors) => {
+                errors.iter().for_each(|error| eprintln!("{}", error));
+                process::exit(1);
+            },
+        }
+        return;
+    }
+
+    if options.output != Output::Ir {
+        match loader::load_crate(&options.input, &options.cfg).map_err(|error| error.to_string())
+            .and_then(|krate| dump_crate(&krate, options.output)) {
+            Ok(text) => print!("{}", text),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            },
+        }
+        return;
+    }
+
+    match compile(&options.input, &options.cfg) {
+        Ok(module) => println!("{:#?}", module),
+        Err(errors) => {
+            errors.iter().for_each(|error| eprintln!("{}", error));
+            process::exit(1);
+        },
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut input = None;
+    let mut cfg = CfgOptions::default();
+    let mut output = Output::Ir;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--test" => cfg.insert("test", None),
+            "--cfg" => {
+                let spec = args.next().ok_or("error: `--cfg` requires an argument")?;
+                cfg.insert_spec(&spec).map_err(|error| error.to_string())?;
+            },
+            "--pretty=expanded" => output = Output::Expanded,
+            _ if arg.starts_with("--pretty") => {
+                return Err("error: argument to `--pretty` must be `expanded`".to_string());
+            },
+            "--emit=ast-json" => output = Output::AstJson,
+            "--emit=ast-sexp" => output = Output::AstSexp,
+            "--emit=cst" => output = Output::Cst,
+            _ if arg.starts_with("--emit") => {
+                return Err("error: argument to `--emit` must be `ast-json`, `ast-sexp`, or `cst`".to_string());
+            },
+            _ if arg.starts_with('-') => return Err(format!("error: unknown option `{}`\n{}", arg, USAGE)),
+            _ if input.is_none() => input = Some(PathBuf::from(arg)),
+            _ => return Err(format!("error: multiple input files\n{}", USAGE)),
+        }
+    }
+    let input = input.ok_or(USAGE)?;
+    Ok(Options { input, cfg, output })
+}
+
+// 로딩이 끝난 크레이트를 `output` 형식의 문자열로 바꿉니다.
+fn dump_crate(krate: &Crate, output: Output) -> Result<String, String> {
+    match output {
+        Output::Ir | Output::Cst => unreachable!(),
+        Output::Expanded => Ok(expanded_crate_to_string(krate)),
+        #[cfg(feature = "ast-dump")]
+        // 들여쓰기는 깊이에 비례해 늘어나 깊은 식에서는 출력이 제곱으로 커지므로 한 줄로 씁니다.
+        Output::AstJson => serde_json::to_string(krate).map(|json| json + "\n").map_err(|error| format!("error: {}", error)),
+        #[cfg(feature = "ast-dump")]
+        Output::AstSexp => rusttc_ast::sexp::to_string(krate).map(|sexp| sexp + "\n").map_err(|error| error.to_string()),
+        #[cfg(not(feature = "ast-dump"))]
+        Output::AstJson | Output::AstSexp => {
+            Err("error: AST dumps are not available; rebuild rustt with the `ast-dump` feature".to_string())
+        },
+    }
+}
+
+// 파일 하나를 구문 트리로 파싱해 트리 모양을 문자열로 돌려줍니다. 모듈 파일은 따라가지 않습니다.
+fn dump_syntax(path: &Path) -> Result<String, Vec<String>> {
+    let source = fs::read_to_string(path)
+        .map_err(|error| vec![format!("error: couldn't read `{}`: {}", path.display(), error)])?;
+    let parse = rusttc_syntax::parse(&source);
+    if !parse.errors().is_empty() {
+        return Err(parse.errors().iter().map(|error| format!("error: {}: {}", path.display(), error.message)).collect());
+    }
+    Ok(parse.syntax_node().debug_dump())
+}
+
+// 크레이트 루트 파일부터 모듈을 읽어 IR까지 변환합니다.
+// 린트 경고는 바로 출력하고, `deny` 수준의 린트가 있으면 그 뒤 단계로 넘어가지 않습니다.
+fn compile(path: &Path, cfg: &CfgOptions) -> Result<IrModule, Vec<String>> {
+    let krate = loader::load_crate(path, cfg).map_err(|error| vec![error.to_string()])?;
+
+    let diagnostics = lint::check_crate(&krate);
+    let (denied, warned): (Vec<_>, Vec<_>) = diagnostics.iter().partition(|diagnostic| diagnostic.level == Level::Deny);
+    warned.iter().for_each(|diagnostic| eprintln!("{}", diagnostic));
+    if !denied.is_empty() {
+        return Err(messages(denied));
+    }
+
+    let mut krate = resolve_modules(&krate).map_err(messages)?;
+    let resolutions = resolve_names(&krate).map_err(messages)?;
+    resolve_methods(&mut krate).map_err(messages)?;
+    let results = rusttc_typeck::check_crate(&krate, &resolutions).map_err(messages)?;
+    results.warnings.iter().for_each(|warning| eprintln!("{}", warning));
+    rusttc_typeck::write_back(&mut krate, &results);
+    rusttc_borrowck::check_crate(&krate, &resolutions, &results).map_err(messages)?;
+    let krate = monomorphize(&krate).map_err(|error| vec![error.to_string()])?;
+    Ok(lower_crate(&krate))
+}
+
+fn messages<E: Display>(errors: Vec<E>) -> Vec<String> {
+    errors.iter().map(E::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::tests::write_files;
+    use rusttc_ast::arena::MAX_DEPTH;
+
+    #[test]
+    fn test_nesting_past_supported_depth_is_an_error() {
+        // 왼쪽으로 이어지는 덧셈은 재귀 없이 파싱한 뒤 트리의 깊이로 거부하고 가장 깊은 첫 항을 가리킵니다.
+        // 단항 연산자는 파서가, 괄호는 매크로 전개가 `MAX_DEPTH`단계에 이른 자리에서 멈추므로 뒤의 단계는 돌지 않습니다.
+        let sources = [
+            ("sum", vec!["1"; MAX_DEPTH].join(" + "), 18),
+            ("neg", format!("{}1", "- ".repeat(MAX_DEPTH + 1)), 18 + 2 * MAX_DEPTH),
+            // 토큰 트리는 `main`의 본문 중괄호도 한 단계로 세므로 `MAX_DEPTH`번째 괄호에서 멈춥니다.
+            ("parens", format!("{}1{}", "(".repeat(MAX_DEPTH + 1), ")".repeat(MAX_DEPTH + 1)), 17 + MAX_DEPTH),
+        ];
+        for (name, expr, column) in sources {
+            let dir = write_files(&format!("too_deep_{}", name), &[("main.rs", &format!("fn main() {{\n    let x: i32 = {};\n}}", expr))]);
+            let path = dir.join("main.rs");
+            let errors = on_compiler_stack(|| compile(&path, &CfgOptions::default())).unwrap_err();
+            let message = format!("error: nesting exceeds the supported depth of {} levels\n  --> {}:2:{}", MAX_DEPTH, path.display(), column);
+            assert_eq!(errors, vec![message], "{}", name);
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_unused_format_argument_location() {
+        let dir = write_files("unused_format_arg", &[("main.rs", "fn main() {\n    let x = 1;\n    let y = 2;\n    println!(\"{y}\", x);\n}")]);
+        let path = dir.join("main.rs");
+        let errors = compile(&path, &CfgOptions::default()).unwrap_err();
+        let message = format!(
+            "error: argument never used\n  --> {}:4:21\n  |\n  | \"{{y}}\"\n  |  ^^^ formatting specifier missing",
+            path.display()
+        );
+        assert_eq!(errors, vec![message]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}